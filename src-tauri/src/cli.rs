@@ -0,0 +1,354 @@
+/// Terminal capture: `stik new [--folder F] [text]` and `stik open <query>`.
+///
+/// The running app listens on `~/.stik/cli/cli.sock`. A second launch with a
+/// subcommand forwards it there as one JSON line, prints the reply and
+/// exits without creating windows. When nothing is listening, the launch
+/// becomes the app and runs the command once setup has finished.
+use serde::{Deserialize, Serialize};
+use std::io::{IsTerminal, Read};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::index::NoteIndex;
+use crate::commands::{notes, settings};
+use crate::windows;
+
+pub const USAGE: &str =
+    "Usage:\n  stik new [--folder <name>] [text]   (reads piped stdin too)\n  stik open <query>";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum CliCommand {
+    New {
+        folder: Option<String>,
+        content: String,
+    },
+    Open {
+        query: String,
+    },
+    /// Plain relaunch while already running: show the capture window.
+    Show,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CliResponse {
+    ok: bool,
+    message: String,
+}
+
+pub enum Forwarded {
+    Handled(Result<String, String>),
+    NotRunning,
+}
+
+/// Parse process arguments (without the binary name). `Ok(None)` means a
+/// regular launch; macOS may pass its own flags, so unknown ones are ignored.
+pub fn parse_args(args: &[String]) -> Result<Option<CliCommand>, String> {
+    let Some((subcommand, rest)) = args.split_first() else {
+        return Ok(None);
+    };
+
+    match subcommand.as_str() {
+        "new" => {
+            let mut folder = None;
+            let mut words = Vec::new();
+            let mut rest = rest.iter();
+            while let Some(arg) = rest.next() {
+                match arg.as_str() {
+                    "--folder" | "-f" => {
+                        let name = rest.next().ok_or("--folder needs a folder name")?;
+                        folder = Some(name.clone());
+                    }
+                    _ => words.push(arg.as_str()),
+                }
+            }
+            Ok(Some(CliCommand::New {
+                folder,
+                content: words.join(" "),
+            }))
+        }
+        "open" => {
+            let query = rest.join(" ");
+            if query.trim().is_empty() {
+                return Err("stik open needs a search query".to_string());
+            }
+            Ok(Some(CliCommand::Open { query }))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Append piped stdin to a `new` command's content.
+pub fn read_piped_stdin(command: &mut CliCommand) -> Result<(), String> {
+    let CliCommand::New { content, .. } = command else {
+        return Ok(());
+    };
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Ok(());
+    }
+
+    let mut piped = String::new();
+    stdin
+        .lock()
+        .read_to_string(&mut piped)
+        .map_err(|e| format!("Failed to read stdin: {}", e))?;
+    *content = join_content(content, &piped);
+    Ok(())
+}
+
+fn join_content(args: &str, piped: &str) -> String {
+    let piped = piped.trim_end();
+    match (args.is_empty(), piped.is_empty()) {
+        (_, true) => args.to_string(),
+        (true, false) => piped.to_string(),
+        (false, false) => format!("{}\n\n{}", args, piped),
+    }
+}
+
+/// Run `command` in the running app, if there is one.
+pub fn forward(command: &CliCommand) -> Forwarded {
+    imp::forward(command)
+}
+
+/// Serve forwarded commands for the lifetime of the app.
+pub fn start_listener(app: AppHandle) {
+    imp::start_listener(app);
+}
+
+/// Run a command that arrived with this launch, after setup.
+pub fn run_on_launch(app: AppHandle, command: CliCommand) {
+    std::thread::Builder::new()
+        .name("stik-cli-launch".to_string())
+        .spawn(move || match execute(&app, command) {
+            Ok(message) => println!("{}", message),
+            Err(e) => eprintln!("stik: {}", e),
+        })
+        .ok();
+}
+
+fn execute(app: &AppHandle, command: CliCommand) -> Result<String, String> {
+    match command {
+        CliCommand::New { folder, content } => {
+            let folder = folder
+                .unwrap_or_else(|| settings::get_settings().unwrap_or_default().default_folder);
+            let saved = notes::save_note_inner(folder, content.clone())?;
+            if saved.path.is_empty() {
+                return Err("Nothing to save: the note is empty".to_string());
+            }
+            notes::post_save_processing(app, &saved, &content);
+            Ok(saved.path)
+        }
+        CliCommand::Open { query } => {
+            let (entry, _) = app
+                .state::<NoteIndex>()
+                .search(&query, None)?
+                .into_iter()
+                .next()
+                .ok_or_else(|| format!("No note matches '{}'", query))?;
+            let content = notes::get_note_content_inner(&entry.path)?;
+            tauri::async_runtime::block_on(windows::open_note_for_viewing(
+                app.clone(),
+                content,
+                entry.folder,
+                entry.path.clone(),
+            ))?;
+            Ok(entry.path)
+        }
+        CliCommand::Show => {
+            let settings = settings::get_settings().unwrap_or_default();
+            windows::show_postit_with_folder(app, &settings.default_folder);
+            Ok(String::new())
+        }
+    }
+}
+
+fn socket_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".stik").join("cli").join("cli.sock"))
+}
+
+#[cfg(unix)]
+mod imp {
+    use super::*;
+    use std::io::{BufRead, BufReader, ErrorKind, Write};
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::time::Duration;
+
+    /// How long an accepted client may take to send its request.
+    const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+    pub fn forward(command: &CliCommand) -> Forwarded {
+        let Some(path) = socket_path() else {
+            return Forwarded::NotRunning;
+        };
+        // A socket file left by a crashed instance refuses connections.
+        let Ok(mut stream) = UnixStream::connect(&path) else {
+            return Forwarded::NotRunning;
+        };
+        Forwarded::Handled(send(&mut stream, command))
+    }
+
+    fn send(stream: &mut UnixStream, command: &CliCommand) -> Result<String, String> {
+        let mut request = serde_json::to_string(command).map_err(|e| e.to_string())?;
+        request.push('\n');
+        stream
+            .write_all(request.as_bytes())
+            .map_err(|e| format!("Failed to reach the running Stik: {}", e))?;
+
+        let mut reply = String::new();
+        BufReader::new(stream)
+            .read_line(&mut reply)
+            .map_err(|e| format!("No reply from the running Stik: {}", e))?;
+        let response: CliResponse = serde_json::from_str(&reply)
+            .map_err(|e| format!("Unexpected reply from the running Stik: {}", e))?;
+        if response.ok {
+            Ok(response.message)
+        } else {
+            Err(response.message)
+        }
+    }
+
+    pub fn start_listener(app: AppHandle) {
+        let Some(path) = socket_path() else {
+            return;
+        };
+        // Only the owner may reach the socket: it lives in a 0700 directory,
+        // so it is never exposed between `bind` and any later chmod.
+        if let Some(parent) = path.parent() {
+            let created = std::fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(parent)
+                .and_then(|()| {
+                    std::fs::set_permissions(parent, std::fs::Permissions::from_mode(0o700))
+                });
+            if let Err(e) = created {
+                log::error!("Failed to prepare the CLI socket directory: {}", e);
+                return;
+            }
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => log::warn!("Failed to remove stale CLI socket: {}", e),
+        }
+
+        let listener = match UnixListener::bind(&path) {
+            Ok(listener) => listener,
+            Err(e) => {
+                log::error!("Failed to start CLI listener: {}", e);
+                return;
+            }
+        };
+
+        std::thread::Builder::new()
+            .name("stik-cli".to_string())
+            .spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    if let Err(e) = serve(&app, stream) {
+                        log::warn!("CLI request failed: {}", e);
+                    }
+                }
+            })
+            .ok();
+    }
+
+    fn serve(app: &AppHandle, stream: UnixStream) -> Result<(), String> {
+        // A client that never sends its line must not stall the listener.
+        stream
+            .set_read_timeout(Some(READ_TIMEOUT))
+            .map_err(|e| e.to_string())?;
+        let mut request = String::new();
+        BufReader::new(&stream)
+            .read_line(&mut request)
+            .map_err(|e| e.to_string())?;
+
+        let result = serde_json::from_str::<CliCommand>(&request)
+            .map_err(|e| format!("Malformed CLI request: {}", e))
+            .and_then(|command| execute(app, command));
+        let response = match result {
+            Ok(message) => CliResponse { ok: true, message },
+            Err(message) => CliResponse { ok: false, message },
+        };
+
+        let mut reply = serde_json::to_string(&response).map_err(|e| e.to_string())?;
+        reply.push('\n');
+        (&stream)
+            .write_all(reply.as_bytes())
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use super::*;
+
+    pub fn forward(_command: &CliCommand) -> Forwarded {
+        Forwarded::NotRunning
+    }
+
+    pub fn start_listener(_app: AppHandle) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_new_with_folder_and_text() {
+        assert_eq!(
+            parse_args(&args(&["new", "--folder", "Ideas", "ship", "it"])),
+            Ok(Some(CliCommand::New {
+                folder: Some("Ideas".to_string()),
+                content: "ship it".to_string(),
+            }))
+        );
+        assert_eq!(
+            parse_args(&args(&["new"])),
+            Ok(Some(CliCommand::New {
+                folder: None,
+                content: String::new(),
+            }))
+        );
+        assert!(parse_args(&args(&["new", "--folder"])).is_err());
+    }
+
+    #[test]
+    fn parses_open_and_ignores_unknown_launch_args() {
+        assert_eq!(
+            parse_args(&args(&["open", "quarterly", "plan"])),
+            Ok(Some(CliCommand::Open {
+                query: "quarterly plan".to_string()
+            }))
+        );
+        assert!(parse_args(&args(&["open"])).is_err());
+        assert_eq!(parse_args(&args(&[])), Ok(None));
+        assert_eq!(parse_args(&args(&["-psn_0_12345"])), Ok(None));
+    }
+
+    #[test]
+    fn piped_stdin_follows_argument_text() {
+        assert_eq!(join_content("", "idea\n"), "idea");
+        assert_eq!(join_content("title", ""), "title");
+        assert_eq!(join_content("title", "body\n"), "title\n\nbody");
+    }
+
+    #[test]
+    fn commands_round_trip_as_json() {
+        let command = CliCommand::New {
+            folder: Some("Ideas".to_string()),
+            content: "line one\nline two".to_string(),
+        };
+        let json = serde_json::to_string(&command).expect("serializes");
+        assert!(!json.contains('\n'));
+        assert_eq!(
+            serde_json::from_str::<CliCommand>(&json).expect("parses"),
+            command
+        );
+    }
+}
@@ -45,7 +45,7 @@ pub struct GenerateResult {
 
 // ── Helpers ────────────────────────────────────────────────────────
 
-fn check_ai_enabled() -> Result<(), String> {
+pub(crate) fn check_ai_enabled() -> Result<(), String> {
     let enabled = super::settings::get_settings().ok()
         .map(|s| s.ai_features_enabled)
         .unwrap_or(false);
@@ -94,6 +94,10 @@ fn build_rag_context(
         return String::new();
     }
 
+    let excluded = super::settings::load_settings_from_file()
+        .map(|s| s.ai_excluded_folders)
+        .unwrap_or_default();
+
     // Find similar notes
     let nearest = embeddings.nearest(&vector, max_notes, &lang);
     let mut context_parts = Vec::new();
@@ -103,6 +107,9 @@ fn build_rag_context(
             continue;
         }
         if let Some(entry) = index.get(&path) {
+            if excluded.contains(&entry.folder) {
+                continue;
+            }
             context_parts.push(format!(
                 "- [{}] {}: {}",
                 entry.folder,
@@ -2,22 +2,101 @@
 //
 // Events: app_opened, note_created, note_updated, note_deleted
 // Properties: word count, system info — never content, titles, folders, or PII.
+//
+// Events are queued in ~/.stik/analytics_queue.json and sent in batches to
+// PostHog's /batch endpoint, so offline use doesn't lose them or spam failed
+// requests. Queued events older than a week are dropped.
+//
+// Every payload that is actually sent is also recorded, with its HTTP
+// status, in ~/.stik/analytics_log.json (last 200) so the privacy settings
+// can show it. That log never leaves the machine.
 
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::HashSet;
 use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
-use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
 use tauri::AppHandle;
 use uuid::Uuid;
 
+use super::{config_paths, versioning};
+
 // Injected at build time via POSTHOG_API_KEY env var (set in CI from GitHub secret).
 // When unset (local dev builds), analytics silently no-ops.
 const POSTHOG_API_KEY: Option<&str> = option_env!("POSTHOG_API_KEY");
 const POSTHOG_HOST: &str = "https://eu.i.posthog.com";
 
+const FLUSH_INTERVAL_SECONDS: u64 = 5 * 60;
+const FLUSH_THRESHOLD: usize = 20;
+const MAX_BATCH_SIZE: usize = 100;
+const MAX_QUEUED_EVENTS: usize = 1000;
+const MAX_EVENT_AGE_DAYS: i64 = 7;
+const QUEUE_FILE: &str = "analytics_queue.json";
+/// Local-only receipt of sent events.
+const LOG_FILE: &str = "analytics_log.json";
+const MAX_LOG_ENTRIES: usize = 200;
+
 static DEVICE_ID: OnceLock<String> = OnceLock::new();
-static ANALYTICS_ENABLED: OnceLock<bool> = OnceLock::new();
+// Live flag: `save_settings` flips it, so toggling needs no restart.
+static ANALYTICS_ENABLED: AtomicBool = AtomicBool::new(false);
+static QUEUE: OnceLock<Mutex<Vec<QueuedEvent>>> = OnceLock::new();
+static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+static FLUSHING: AtomicBool = AtomicBool::new(false);
+static FLUSHER_RUNNING: OnceLock<()> = OnceLock::new();
+static LOG_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct QueuedEvent {
+    /// Identifies the event in the queue, so a sent batch removes exactly
+    /// its own events. Queues written before ids get fresh ones on load.
+    #[serde(default = "new_event_id")]
+    id: String,
+    event: String,
+    properties: Value,
+    timestamp: DateTime<Utc>,
+}
+
+fn new_event_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct AnalyticsQueue {
+    events: Vec<QueuedEvent>,
+}
+
+/// One event as it was sent to PostHog.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentEventRecord {
+    pub event: String,
+    pub properties: Value,
+    pub timestamp: String,
+    /// HTTP status of the batch request; None when it never got a response.
+    pub status: Option<u16>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct AnalyticsLog {
+    entries: Vec<SentEventRecord>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalyticsStatus {
+    /// Whether this build has a PostHog key; dev builds never send.
+    pub key_compiled_in: bool,
+    pub user_enabled: bool,
+    /// Both of the above: events are actually being collected.
+    pub active: bool,
+    pub device_id: String,
+    pub queued_events: usize,
+}
 
 fn analytics_id_path() -> Result<PathBuf, String> {
     let home = dirs::home_dir().ok_or("Could not find home directory")?;
@@ -86,30 +165,73 @@ fn collect_system_props() -> Value {
     })
 }
 
-async fn send_event(event: &str, extra_properties: Value) {
-    let api_key = match POSTHOG_API_KEY {
-        Some(k) if !k.is_empty() => k,
-        _ => return,
-    };
+fn queue() -> &'static Mutex<Vec<QueuedEvent>> {
+    QUEUE.get_or_init(|| Mutex::new(load_queue().unwrap_or_default().events))
+}
 
-    let device_id = match DEVICE_ID.get() {
-        Some(id) => id.clone(),
-        None => return,
+fn queue_path() -> Result<PathBuf, String> {
+    let stik_config = config_paths::home_config_dir()?;
+    fs::create_dir_all(&stik_config).map_err(|e| e.to_string())?;
+    Ok(stik_config.join(QUEUE_FILE))
+}
+
+fn load_queue() -> Result<AnalyticsQueue, String> {
+    Ok(versioning::load_versioned(&queue_path()?)?.unwrap_or_default())
+}
+
+fn save_queue(events: &[QueuedEvent]) {
+    let store = AnalyticsQueue {
+        events: events.to_vec(),
     };
+    if let Err(e) = queue_path().and_then(|path| versioning::save_versioned(&path, &store)) {
+        log::error!("Failed to save analytics queue: {}", e);
+    }
+}
 
-    let mut properties = extra_properties.as_object().cloned().unwrap_or_default();
-    properties.insert("distinct_id".to_string(), json!(device_id));
+/// Drop events older than a week, then the oldest beyond the queue cap.
+fn prune(events: &mut Vec<QueuedEvent>, now: DateTime<Utc>) {
+    let cutoff = now - Duration::days(MAX_EVENT_AGE_DAYS);
+    events.retain(|queued| queued.timestamp >= cutoff);
+    if events.len() > MAX_QUEUED_EVENTS {
+        let excess = events.len() - MAX_QUEUED_EVENTS;
+        events.drain(..excess);
+    }
+}
+
+/// Remove the events in `sent` from the queue. Others may have been
+/// pruned or queued while the batch was in flight.
+fn remove_sent(events: &mut Vec<QueuedEvent>, sent: &[QueuedEvent]) {
+    let sent: HashSet<&str> = sent.iter().map(|queued| queued.id.as_str()).collect();
+    events.retain(|queued| !sent.contains(queued.id.as_str()));
+}
 
+fn client() -> &'static reqwest::Client {
+    CLIENT.get_or_init(reqwest::Client::new)
+}
+
+/// POST one batch. Returns whether PostHog accepted it.
+async fn send_events(api_key: &str, device_id: &str, batch: &[QueuedEvent]) -> bool {
+    let events: Vec<Value> = batch
+        .iter()
+        .map(|queued| {
+            let mut properties = queued.properties.as_object().cloned().unwrap_or_default();
+            properties.insert("distinct_id".to_string(), json!(device_id));
+            json!({
+                "event": queued.event,
+                "properties": properties,
+                "timestamp": queued.timestamp.to_rfc3339(),
+            })
+        })
+        .collect();
     let body = json!({
         "api_key": api_key,
-        "event": event,
-        "properties": properties,
+        "batch": events,
     });
 
-    eprintln!("[analytics] sending: {}", event);
+    log::debug!("Sending {} events", batch.len());
 
-    match reqwest::Client::new()
-        .post(format!("{}/capture/", POSTHOG_HOST))
+    let (status, error) = match client()
+        .post(format!("{}/batch/", POSTHOG_HOST))
         .json(&body)
         .send()
         .await
@@ -117,22 +239,141 @@ async fn send_event(event: &str, extra_properties: Value) {
         Ok(resp) => {
             let status = resp.status();
             let body_text = resp.text().await.unwrap_or_default();
-            eprintln!("[analytics] {} → {} {}", event, status, body_text);
+            log::debug!("Analytics batch → {} {}", status, body_text);
+            (Some(status), None)
         }
-        Err(e) => eprintln!("[analytics] {} failed: {}", event, e),
+        Err(e) => {
+            log::warn!("Analytics batch failed: {}", e);
+            (None, Some(e.to_string()))
+        }
+    };
+
+    let records = body["batch"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|sent| SentEventRecord {
+            event: sent["event"].as_str().unwrap_or_default().to_string(),
+            properties: sent["properties"].clone(),
+            timestamp: sent["timestamp"].as_str().unwrap_or_default().to_string(),
+            status: status.map(|status| status.as_u16()),
+            error: error.clone(),
+        })
+        .collect();
+    append_to_log(records);
+
+    status.is_some_and(|status| status.is_success())
+}
+
+fn log_path() -> Result<PathBuf, String> {
+    let stik_config = config_paths::home_config_dir()?;
+    fs::create_dir_all(&stik_config).map_err(|e| e.to_string())?;
+    Ok(stik_config.join(LOG_FILE))
+}
+
+fn load_log() -> Result<AnalyticsLog, String> {
+    Ok(versioning::load_versioned(&log_path()?)?.unwrap_or_default())
+}
+
+fn append_to_log(records: Vec<SentEventRecord>) {
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut log = load_log().unwrap_or_default();
+    log.entries.extend(records);
+    trim_log(&mut log.entries);
+    if let Err(e) = log_path().and_then(|path| versioning::save_versioned(&path, &log)) {
+        log::error!("Failed to save analytics log: {}", e);
+    }
+}
+
+fn trim_log(entries: &mut Vec<SentEventRecord>) {
+    if entries.len() > MAX_LOG_ENTRIES {
+        let excess = entries.len() - MAX_LOG_ENTRIES;
+        entries.drain(..excess);
     }
 }
 
-/// Fire-and-forget: spawns an async task to send the event.
+/// Send everything queued, in batches. Stops at the first failure and
+/// keeps the rest for the next attempt.
+async fn flush() {
+    let api_key = match POSTHOG_API_KEY {
+        Some(k) if !k.is_empty() => k,
+        _ => return,
+    };
+    let Some(device_id) = DEVICE_ID.get() else {
+        return;
+    };
+    if FLUSHING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    loop {
+        let batch: Vec<QueuedEvent> = {
+            let mut events = queue().lock().unwrap_or_else(|e| e.into_inner());
+            prune(&mut events, Utc::now());
+            events.iter().take(MAX_BATCH_SIZE).cloned().collect()
+        };
+        if batch.is_empty() || !send_events(api_key, device_id, &batch).await {
+            break;
+        }
+
+        let mut events = queue().lock().unwrap_or_else(|e| e.into_inner());
+        remove_sent(&mut events, &batch);
+        save_queue(&events);
+    }
+
+    FLUSHING.store(false, Ordering::SeqCst);
+}
+
+fn spawn_flush() {
+    tauri::async_runtime::spawn(flush());
+}
+
+fn enqueue(event: &str, properties: Value) {
+    let mut events = queue().lock().unwrap_or_else(|e| e.into_inner());
+    events.push(QueuedEvent {
+        id: new_event_id(),
+        event: event.to_string(),
+        properties,
+        timestamp: Utc::now(),
+    });
+    prune(&mut events, Utc::now());
+    save_queue(&events);
+    let should_flush = events.len() >= FLUSH_THRESHOLD;
+    drop(events);
+
+    if should_flush {
+        spawn_flush();
+    }
+}
+
+fn clear_queue() {
+    let mut events = queue().lock().unwrap_or_else(|e| e.into_inner());
+    events.clear();
+    if let Ok(path) = queue_path() {
+        let _ = fs::remove_file(path);
+    }
+}
+
+fn start_flusher() {
+    if FLUSHER_RUNNING.set(()).is_err() {
+        return;
+    }
+    thread::Builder::new()
+        .name("stik-analytics".to_string())
+        .spawn(|| loop {
+            thread::sleep(std::time::Duration::from_secs(FLUSH_INTERVAL_SECONDS));
+            tauri::async_runtime::block_on(flush());
+        })
+        .ok();
+}
+
+/// Queue an event; it is sent with the next batch.
 /// No-ops silently if analytics is disabled or no API key is present.
 pub fn track(event: &str, properties: Value) {
-    if !ANALYTICS_ENABLED.get().copied().unwrap_or(false) {
+    if !ANALYTICS_ENABLED.load(Ordering::SeqCst) {
         return;
     }
-    let event = event.to_string();
-    tauri::async_runtime::spawn(async move {
-        send_event(&event, properties).await;
-    });
+    enqueue(event, properties);
 }
 
 pub fn start_analytics(app: &AppHandle) {
@@ -147,24 +388,135 @@ pub fn start_analytics(app: &AppHandle) {
     if let Ok(id) = get_or_create_device_id() {
         let _ = DEVICE_ID.set(id);
     }
-    let _ = ANALYTICS_ENABLED.set(enabled);
+    ANALYTICS_ENABLED.store(enabled, Ordering::SeqCst);
 
     if !enabled {
-        eprintln!("[analytics] disabled (key={}, setting={})",
+        log::info!(
+            "Analytics disabled (key={}, setting={})",
             POSTHOG_API_KEY.is_some(),
-            super::settings::load_settings_from_file().map(|s| s.analytics_enabled).unwrap_or(false),
+            super::settings::load_settings_from_file()
+                .map(|s| s.analytics_enabled)
+                .unwrap_or(false),
         );
+        clear_queue();
         return;
     }
 
-    // Send app_opened with full system info (only at startup)
-    let system_props = collect_system_props();
-    tauri::async_runtime::spawn(async move {
-        send_event("app_opened", system_props).await;
-    });
+    // Send app_opened with full system info (only at startup), along with
+    // anything left over from an offline session.
+    enqueue("app_opened", collect_system_props());
+    spawn_flush();
+    start_flusher();
+}
+
+/// Follow the `analytics_enabled` setting after it was saved. Turning it
+/// off drops anything still queued; turning it back on resumes quietly,
+/// without another `app_opened`.
+pub fn apply_settings(settings: &super::settings::StikSettings) {
+    let enabled = POSTHOG_API_KEY.is_some() && settings.analytics_enabled;
+    if ANALYTICS_ENABLED.swap(enabled, Ordering::SeqCst) == enabled {
+        return;
+    }
+    if enabled {
+        start_flusher();
+    } else {
+        clear_queue();
+    }
+}
+
+#[tauri::command]
+pub fn get_analytics_status() -> Result<AnalyticsStatus, String> {
+    let user_enabled = super::settings::load_settings_from_file()?.analytics_enabled;
+    let key_compiled_in = POSTHOG_API_KEY.is_some_and(|key| !key.is_empty());
+    Ok(AnalyticsStatus {
+        key_compiled_in,
+        user_enabled,
+        active: ANALYTICS_ENABLED.load(Ordering::SeqCst),
+        device_id: get_or_create_device_id()?,
+        queued_events: queue().lock().unwrap_or_else(|e| e.into_inner()).len(),
+    })
+}
+
+/// Sent events, newest first.
+#[tauri::command]
+pub fn get_analytics_log() -> Result<Vec<SentEventRecord>, String> {
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = load_log()?.entries;
+    entries.reverse();
+    Ok(entries)
+}
+
+#[tauri::command]
+pub fn clear_analytics_log() -> Result<(), String> {
+    let _guard = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    match fs::remove_file(log_path()?) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to clear analytics log: {}", e)),
+    }
 }
 
 #[tauri::command]
 pub fn get_analytics_device_id() -> Result<String, String> {
     get_or_create_device_id()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queued(event: &str, age_days: i64, now: DateTime<Utc>) -> QueuedEvent {
+        QueuedEvent {
+            id: new_event_id(),
+            event: event.to_string(),
+            properties: json!({}),
+            timestamp: now - Duration::days(age_days),
+        }
+    }
+
+    #[test]
+    fn prune_drops_stale_events_and_caps_the_queue() {
+        let now = Utc::now();
+        let mut events = vec![queued("stale", 8, now), queued("fresh", 1, now)];
+        prune(&mut events, now);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "fresh");
+
+        let mut events: Vec<QueuedEvent> = (0..MAX_QUEUED_EVENTS + 5)
+            .map(|i| queued(&i.to_string(), 0, now))
+            .collect();
+        prune(&mut events, now);
+        assert_eq!(events.len(), MAX_QUEUED_EVENTS);
+        assert_eq!(events[0].event, "5");
+    }
+
+    #[test]
+    fn only_the_sent_events_leave_the_queue() {
+        let now = Utc::now();
+        let mut events = vec![queued("a", 0, now), queued("b", 0, now)];
+        let batch = events.clone();
+        // While the batch was in flight, "a" was pruned and "c" queued.
+        events.remove(0);
+        events.push(queued("c", 0, now));
+
+        remove_sent(&mut events, &batch);
+        let left: Vec<&str> = events.iter().map(|queued| queued.event.as_str()).collect();
+        assert_eq!(left, vec!["c"]);
+    }
+
+    #[test]
+    fn log_keeps_only_the_latest_entries() {
+        let mut entries: Vec<SentEventRecord> = (0..MAX_LOG_ENTRIES + 3)
+            .map(|i| SentEventRecord {
+                event: i.to_string(),
+                properties: json!({}),
+                timestamp: String::new(),
+                status: Some(200),
+                error: None,
+            })
+            .collect();
+        trim_log(&mut entries);
+        assert_eq!(entries.len(), MAX_LOG_ENTRIES);
+        assert_eq!(entries[0].event, "3");
+    }
+}
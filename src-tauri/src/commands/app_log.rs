@@ -0,0 +1,239 @@
+/// Leveled internal log at `~/.stik/logs/stik.log`, behind the `log` macros.
+///
+/// stderr is invisible in a packaged app, so every entry is also appended
+/// to the log file, which rolls to `stik.log.1` and `stik.log.2` at
+/// `MAX_LOG_BYTES`. Stik's own records pass at the current level (`info`
+/// by default, or the saved `log_level`); other crates only at `warn` and
+/// above. Note content may only be logged at `debug` or `trace`.
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use super::config_paths;
+use super::settings::{self, StikSettings};
+
+const LOG_FILE: &str = "stik.log";
+/// Files kept, the current one included.
+const LOG_FILES: usize = 3;
+const MAX_LOG_BYTES: u64 = 2 * 1024 * 1024;
+const DEFAULT_RECENT_LINES: usize = 200;
+const CRATE_PREFIX: &str = "stik";
+
+static LOGGER: FileLogger = FileLogger {
+    written: Mutex::new(None),
+};
+
+struct FileLogger {
+    /// Size of the current log file, read from disk on the first write and
+    /// tracked from then on. The lock serialises writers so rotation never
+    /// races an append.
+    written: Mutex<Option<u64>>,
+}
+
+/// Stik's records at `max_level`, other crates' at `warn` and above.
+fn passes_filter(metadata: &Metadata, max_level: LevelFilter) -> bool {
+    if metadata.target().starts_with(CRATE_PREFIX) {
+        metadata.level() <= max_level
+    } else {
+        metadata.level() <= Level::Warn
+    }
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        passes_filter(metadata, log::max_level())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format_line(
+            &chrono::Local::now().to_rfc3339(),
+            record.level(),
+            record.target(),
+            &record.args().to_string(),
+        );
+        eprint!("{}", line);
+        let mut written = self.written.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = append(&line, &mut written) {
+            eprintln!("Failed to write log: {}", e);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn default_level() -> LevelFilter {
+    if cfg!(debug_assertions) {
+        LevelFilter::Debug
+    } else {
+        LevelFilter::Info
+    }
+}
+
+/// Install the file logger at the saved level. Call once, early in `main`.
+pub fn init() {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(default_level());
+        apply_settings(&settings::load_settings_from_file().unwrap_or_default());
+    }
+}
+
+/// Follow `log_level` after settings were loaded or saved.
+pub fn apply_settings(settings: &StikSettings) {
+    let level =
+        LevelFilter::from_str(settings.log_level.trim()).unwrap_or_else(|_| default_level());
+    log::set_max_level(level);
+}
+
+fn logs_dir() -> Result<PathBuf, String> {
+    Ok(config_paths::home_config_dir()?.join("logs"))
+}
+
+/// `stik.log` for 0, `stik.log.<n>` for older files.
+fn log_file_name(generation: usize) -> String {
+    if generation == 0 {
+        LOG_FILE.to_string()
+    } else {
+        format!("{}.{}", LOG_FILE, generation)
+    }
+}
+
+fn format_line(timestamp: &str, level: Level, target: &str, message: &str) -> String {
+    // One entry per line, so `tail` can't split an entry.
+    format!(
+        "{} {:<5} {}: {}\n",
+        timestamp,
+        level,
+        target,
+        message.replace('\n', " ⏎ ")
+    )
+}
+
+/// Append `line`, rotating first when the current file is full. `written`
+/// is the current file's size, `None` until it has been read once.
+fn append(line: &str, written: &mut Option<u64>) -> Result<(), String> {
+    let dir = logs_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(LOG_FILE);
+    let size = *written.get_or_insert_with(|| fs::metadata(&path).map_or(0, |meta| meta.len()));
+    if size >= MAX_LOG_BYTES {
+        for generation in (1..LOG_FILES).rev() {
+            let older = dir.join(log_file_name(generation - 1));
+            if older.exists() {
+                fs::rename(&older, dir.join(log_file_name(generation)))
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        *written = Some(0);
+    }
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| e.to_string())?;
+    *written = written.map(|size| size + line.len() as u64);
+    Ok(())
+}
+
+fn last_lines(text: &str, count: usize) -> Vec<String> {
+    let lines: Vec<&str> = text.lines().filter(|line| !line.is_empty()).collect();
+    lines[lines.len().saturating_sub(count)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// The newest `count` entries, oldest first, reaching into rotated files
+/// when the current one is short.
+pub fn tail(count: usize) -> Vec<String> {
+    let Ok(dir) = logs_dir() else {
+        return Vec::new();
+    };
+    let mut lines = Vec::new();
+    for generation in 0..LOG_FILES {
+        if lines.len() >= count {
+            break;
+        }
+        let text = fs::read_to_string(dir.join(log_file_name(generation))).unwrap_or_default();
+        let mut older = last_lines(&text, count - lines.len());
+        older.append(&mut lines);
+        lines = older;
+    }
+    lines
+}
+
+/// The newest log entries for the settings window, oldest first.
+#[tauri::command]
+pub fn get_recent_logs(lines: Option<usize>) -> Vec<String> {
+    tail(lines.unwrap_or(DEFAULT_RECENT_LINES))
+}
+
+/// Change and save the verbosity: `error`, `warn`, `info`, `debug` or
+/// `trace`.
+#[tauri::command]
+pub fn set_log_level(level: String) -> Result<(), String> {
+    let filter =
+        LevelFilter::from_str(level.trim()).map_err(|_| format!("Unknown log level: {}", level))?;
+    settings::save_log_level(&filter.to_string().to_lowercase())?;
+    log::set_max_level(filter);
+    log::info!("Log level set to {}", filter);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multi_line_messages_stay_one_entry() {
+        assert_eq!(
+            format_line(
+                "2026-02-06T14:02:00+01:00",
+                Level::Warn,
+                "stik_lib::commands::git_share",
+                "push failed\nremote: denied"
+            ),
+            "2026-02-06T14:02:00+01:00 WARN  stik_lib::commands::git_share: push failed ⏎ remote: denied\n"
+        );
+    }
+
+    #[test]
+    fn last_lines_keeps_the_newest_in_order() {
+        assert_eq!(last_lines("a\nb\n\nc\n", 2), vec!["b", "c"]);
+        assert_eq!(last_lines("a\n", 5), vec!["a"]);
+        assert!(last_lines("", 3).is_empty());
+    }
+
+    #[test]
+    fn rotated_files_are_numbered() {
+        assert_eq!(log_file_name(0), "stik.log");
+        assert_eq!(log_file_name(2), "stik.log.2");
+    }
+
+    #[test]
+    fn other_crates_only_log_warnings() {
+        let ours = Metadata::builder()
+            .level(Level::Info)
+            .target("stik_lib::commands::notes")
+            .build();
+        let theirs = Metadata::builder()
+            .level(Level::Info)
+            .target("tao::platform_impl")
+            .build();
+        let warning = Metadata::builder()
+            .level(Level::Warn)
+            .target("tao::platform_impl")
+            .build();
+        assert!(passes_filter(&ours, LevelFilter::Info));
+        assert!(!passes_filter(&ours, LevelFilter::Warn));
+        assert!(!passes_filter(&theirs, LevelFilter::Trace));
+        assert!(passes_filter(&warning, LevelFilter::Error));
+    }
+}
@@ -4,6 +4,11 @@ use rusqlite::{Connection, OpenFlags};
 use serde::{Deserialize, Serialize};
 use std::io::Read;
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::index::NoteIndex;
+use super::{git_share, notes, tray_menu};
 
 // Generated protobuf types from apple_notes.proto
 mod proto {
@@ -13,6 +18,12 @@ mod proto {
 // Core Foundation epoch offset: seconds between 1970-01-01 and 2001-01-01
 const CF_EPOCH_OFFSET: i64 = 978_307_200;
 
+/// Rows read between `apple-list-progress` events.
+const LIST_PROGRESS_EVERY: usize = 250;
+
+/// Set by `apple_import_cancel`; the bulk import checks it between notes.
+static IMPORT_CANCELLED: AtomicBool = AtomicBool::new(false);
+
 // ── Data types returned to frontend ──
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +36,33 @@ pub struct AppleNoteEntry {
     pub account_name: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+struct AppleListProgress {
+    loaded: usize,
+    total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AppleImportProgress {
+    done: usize,
+    total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppleImportFailure {
+    pub note_id: i64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AppleImportSummary {
+    pub imported: usize,
+    pub total: usize,
+    /// Stopped by `apple_import_cancel`. Notes imported before that stay.
+    pub cancelled: bool,
+    pub failed: Vec<AppleImportFailure>,
+}
+
 // ── SQLite connection ──
 
 fn notes_db_path() -> String {
@@ -91,10 +129,27 @@ fn detect_account_column(conn: &Connection) -> &'static str {
 
 // ── List notes ──
 
-fn list_apple_notes_inner() -> Result<Vec<AppleNoteEntry>, String> {
+const LISTED_NOTES_FILTER: &str = "n.ZTITLE1 IS NOT NULL
+          AND (n.ZMARKEDFORDELETION IS NULL OR n.ZMARKEDFORDELETION != 1)";
+
+fn list_apple_notes_inner(
+    mut on_progress: impl FnMut(AppleListProgress),
+) -> Result<Vec<AppleNoteEntry>, String> {
     let conn = open_readonly_connection()?;
     let account_col = detect_account_column(&conn);
 
+    let total: usize = conn
+        .query_row(
+            &format!(
+                "SELECT COUNT(*) FROM ZICCLOUDSYNCINGOBJECT n WHERE {}",
+                LISTED_NOTES_FILTER
+            ),
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .map(|count| count.max(0) as usize)
+        .unwrap_or(0);
+
     let query = format!(
         "SELECT
             n.Z_PK,
@@ -106,10 +161,9 @@ fn list_apple_notes_inner() -> Result<Vec<AppleNoteEntry>, String> {
         FROM ZICCLOUDSYNCINGOBJECT n
         LEFT JOIN ZICCLOUDSYNCINGOBJECT f ON n.ZFOLDER = f.Z_PK
         LEFT JOIN ZICCLOUDSYNCINGOBJECT a ON n.{} = a.Z_PK
-        WHERE n.ZTITLE1 IS NOT NULL
-          AND (n.ZMARKEDFORDELETION IS NULL OR n.ZMARKEDFORDELETION != 1)
+        WHERE {}
         ORDER BY n.ZMODIFICATIONDATE1 DESC",
-        account_col
+        account_col, LISTED_NOTES_FILTER
     );
 
     let mut stmt = conn
@@ -137,12 +191,22 @@ fn list_apple_notes_inner() -> Result<Vec<AppleNoteEntry>, String> {
         .map_err(|e| format!("Failed to query notes: {}", e))?;
 
     let mut notes = Vec::new();
-    for row in rows {
+    for (read, row) in rows.enumerate() {
         match row {
             Ok(entry) => notes.push(entry),
-            Err(e) => eprintln!("Skipping note row: {}", e),
+            Err(e) => log::warn!("Skipping note row: {}", e),
+        }
+        if (read + 1) % LIST_PROGRESS_EVERY == 0 {
+            on_progress(AppleListProgress {
+                loaded: notes.len(),
+                total: total.max(read + 1),
+            });
         }
     }
+    on_progress(AppleListProgress {
+        loaded: notes.len(),
+        total: notes.len(),
+    });
 
     Ok(notes)
 }
@@ -151,7 +215,10 @@ fn list_apple_notes_inner() -> Result<Vec<AppleNoteEntry>, String> {
 
 pub fn import_apple_note_inner(note_id: i64) -> Result<String, String> {
     let conn = open_readonly_connection()?;
+    read_note_markdown(&conn, note_id)
+}
 
+fn read_note_markdown(conn: &Connection, note_id: i64) -> Result<String, String> {
     let compressed: Vec<u8> = conn
         .query_row(
             "SELECT nd.ZDATA
@@ -188,6 +255,35 @@ pub fn import_apple_note_inner(note_id: i64) -> Result<String, String> {
     Ok(protobuf_to_markdown(&note))
 }
 
+/// Run `import_one` over `note_ids` in order, stopping between notes once
+/// `cancelled` is set. A failing note is recorded and the rest still run.
+fn import_notes(
+    note_ids: &[i64],
+    cancelled: &AtomicBool,
+    mut import_one: impl FnMut(i64) -> Result<(), String>,
+    mut on_progress: impl FnMut(AppleImportProgress),
+) -> AppleImportSummary {
+    let mut summary = AppleImportSummary {
+        total: note_ids.len(),
+        ..AppleImportSummary::default()
+    };
+    for (done, &note_id) in note_ids.iter().enumerate() {
+        if cancelled.load(Ordering::SeqCst) {
+            summary.cancelled = true;
+            break;
+        }
+        match import_one(note_id) {
+            Ok(()) => summary.imported += 1,
+            Err(reason) => summary.failed.push(AppleImportFailure { note_id, reason }),
+        }
+        on_progress(AppleImportProgress {
+            done: done + 1,
+            total: note_ids.len(),
+        });
+    }
+    summary
+}
+
 // ── Protobuf → Markdown converter ──
 
 fn protobuf_to_markdown(note: &proto::Note) -> String {
@@ -362,8 +458,14 @@ fn apply_inline_formatting(text: &str, font_weight: i32, strikethrough: i32, lin
 // ── Tauri commands ──
 
 #[tauri::command]
-pub fn list_apple_notes() -> Result<Vec<AppleNoteEntry>, String> {
-    list_apple_notes_inner()
+pub async fn list_apple_notes(app: AppHandle) -> Result<Vec<AppleNoteEntry>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        list_apple_notes_inner(|progress| {
+            let _ = app.emit("apple-list-progress", progress);
+        })
+    })
+    .await
+    .map_err(|e| format!("Listing Apple Notes failed: {}", e))?
 }
 
 #[tauri::command]
@@ -371,6 +473,64 @@ pub fn import_apple_note(note_id: i64) -> Result<String, String> {
     import_apple_note_inner(note_id)
 }
 
+/// Import several notes into `folder`, emitting `apple-import-progress`
+/// after each. `apple_import_cancel` stops it between notes; whatever was
+/// imported by then is kept and indexed.
+#[tauri::command]
+pub async fn import_apple_notes(
+    app: AppHandle,
+    note_ids: Vec<i64>,
+    folder: String,
+) -> Result<AppleImportSummary, String> {
+    IMPORT_CANCELLED.store(false, Ordering::SeqCst);
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = open_readonly_connection()?;
+        let mut written = Vec::new();
+        let summary = import_notes(
+            &note_ids,
+            &IMPORT_CANCELLED,
+            |note_id| {
+                let markdown = read_note_markdown(&conn, note_id)?;
+                let saved = notes::save_note_inner(folder.clone(), markdown)?;
+                if !saved.path.is_empty() {
+                    written.push(saved.path);
+                }
+                Ok(())
+            },
+            |progress| {
+                let _ = app.emit("apple-import-progress", progress);
+            },
+        );
+
+        // Index once at the end rather than per note.
+        let index = app.state::<NoteIndex>();
+        for path in &written {
+            index.add(path, &folder);
+        }
+        if !written.is_empty() {
+            super::stats::invalidate_day_counts();
+            git_share::notify_note_changed(&folder);
+            super::widget_feed::notes_changed();
+            tray_menu::rebuild_tray_menu(&app);
+        }
+        if summary.cancelled {
+            log::info!(
+                "Apple Notes import cancelled after {} of {} notes",
+                summary.imported,
+                summary.total
+            );
+        }
+        Ok(summary)
+    })
+    .await
+    .map_err(|e| format!("Apple Notes import failed: {}", e))?
+}
+
+#[tauri::command]
+pub fn apple_import_cancel() {
+    IMPORT_CANCELLED.store(true, Ordering::SeqCst);
+}
+
 #[tauri::command]
 pub fn open_full_disk_access_settings() -> Result<(), String> {
     Command::new("open")
@@ -616,6 +776,33 @@ mod tests {
         assert!(md.contains("  - Sub item"));
     }
 
+    #[test]
+    fn cancelling_keeps_notes_already_imported() {
+        let cancelled = AtomicBool::new(false);
+        let mut imported = Vec::new();
+        let mut progress = Vec::new();
+        let summary = import_notes(
+            &[1, 2, 3, 4],
+            &cancelled,
+            |note_id| {
+                if note_id == 2 {
+                    cancelled.store(true, Ordering::SeqCst);
+                    return Err("no data".to_string());
+                }
+                imported.push(note_id);
+                Ok(())
+            },
+            |p| progress.push(p.done),
+        );
+        assert_eq!(imported, vec![1]);
+        assert_eq!(summary.imported, 1);
+        assert_eq!(summary.total, 4);
+        assert!(summary.cancelled);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].note_id, 2);
+        assert_eq!(progress, vec![1, 2]);
+    }
+
     #[test]
     fn cf_timestamp_conversion() {
         // 2024-01-01 00:00:00 UTC = 1704067200 unix
@@ -0,0 +1,606 @@
+/// Scheduled zip backups of the whole notes directory, behind the opt-in
+/// `backup` settings section.
+///
+/// Every `frequency_days` the library, leaving out `.git` and
+/// `backup.excluded_folders`, is zipped straight from the notes directory
+/// into `stik-backup-YYYYMMDD-HHMMSS.zip` in the destination. Only the newest
+/// `retention_count` archives are kept. Restoring always extracts into a
+/// new, empty folder; the live notes directory is never written to.
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::thread;
+use tauri::{AppHandle, Emitter};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::folders::get_stik_folder;
+use super::settings::{self, BackupSettings};
+use super::{config_paths, versioning};
+
+const ARCHIVE_PREFIX: &str = "stik-backup-";
+const ARCHIVE_EXTENSION: &str = ".zip";
+const ARCHIVE_TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+const GIT_DIR: &str = ".git";
+const SCHEDULER_TICK_SECONDS: u64 = 600;
+/// After a failed scheduled backup, wait this long before trying again.
+const RETRY_AFTER_HOURS: i64 = 1;
+const PROGRESS_EVENT: &str = "backup-progress";
+/// Files zipped between progress events.
+const PROGRESS_EVERY_FILES: usize = 50;
+const FINISHED_EVENT: &str = "backup-finished";
+
+static SCHEDULER_RUNNING: OnceLock<()> = OnceLock::new();
+static BACKUP_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Outcome of the most recent backups, persisted in `backup.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupStatus {
+    /// RFC 3339, scheduled or manual.
+    pub last_attempt_at: Option<String>,
+    pub last_success_at: Option<String>,
+    /// Archive written by the last successful backup.
+    pub last_archive: Option<String>,
+    pub last_files: usize,
+    pub last_archive_bytes: u64,
+    /// Why the last attempt failed; cleared by a success.
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BackupArchive {
+    pub path: String,
+    /// RFC 3339, from the file name.
+    pub created_at: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct BackupProgress {
+    done: usize,
+    total: usize,
+    path: String,
+}
+
+fn archive_name(at: NaiveDateTime) -> String {
+    format!(
+        "{}{}{}",
+        ARCHIVE_PREFIX,
+        at.format(ARCHIVE_TIME_FORMAT),
+        ARCHIVE_EXTENSION
+    )
+}
+
+/// When the archive was made, if `name` is one of ours.
+fn archive_time(name: &str) -> Option<NaiveDateTime> {
+    let stamp = name
+        .strip_prefix(ARCHIVE_PREFIX)?
+        .strip_suffix(ARCHIVE_EXTENSION)?;
+    NaiveDateTime::parse_from_str(stamp, ARCHIVE_TIME_FORMAT).ok()
+}
+
+fn parse_rfc3339(value: Option<&str>) -> Option<NaiveDateTime> {
+    DateTime::parse_from_rfc3339(value?)
+        .ok()
+        .map(|at| at.with_timezone(&Local).naive_local())
+}
+
+fn is_backup_due(settings: &BackupSettings, status: &BackupStatus, now: NaiveDateTime) -> bool {
+    if !settings.enabled || settings.destination.trim().is_empty() {
+        return false;
+    }
+    let frequency = chrono::Duration::days(i64::from(settings.frequency_days.max(1)));
+    let succeeded_recently =
+        parse_rfc3339(status.last_success_at.as_deref()).is_some_and(|at| now - at < frequency);
+    let tried_recently = parse_rfc3339(status.last_attempt_at.as_deref())
+        .is_some_and(|at| now - at < chrono::Duration::hours(RETRY_AFTER_HOURS));
+    !succeeded_recently && !tried_recently
+}
+
+/// Archives beyond the newest `retention_count`; 0 keeps them all.
+/// `archives` is newest first.
+fn archives_to_prune(archives: &[BackupArchive], retention_count: u32) -> &[BackupArchive] {
+    match retention_count as usize {
+        0 => &[],
+        keep => archives.get(keep..).unwrap_or(&[]),
+    }
+}
+
+/// The destination as an absolute directory outside the notes directory.
+fn destination_dir(settings: &BackupSettings, root: &Path) -> Result<PathBuf, String> {
+    let destination = settings.destination.trim();
+    if destination.is_empty() {
+        return Err("Choose a backup folder first".to_string());
+    }
+    let destination = PathBuf::from(destination);
+    if !destination.is_absolute() {
+        return Err("Backup folder must be an absolute path".to_string());
+    }
+    if is_inside(&destination, root) {
+        return Err("Choose a backup folder outside the Stik folder".to_string());
+    }
+    Ok(destination)
+}
+
+/// `path` with symlinks resolved as far as it exists.
+fn resolve(path: &Path) -> PathBuf {
+    if let Ok(resolved) = path.canonicalize() {
+        return resolved;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => resolve(parent).join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Whether `path` is `dir` or somewhere below it.
+fn is_inside(path: &Path, dir: &Path) -> bool {
+    resolve(path).starts_with(resolve(dir))
+}
+
+/// Every file to back up under `root`, relative to it, in a stable order.
+/// Skips `.git` at any depth, top-level `excluded_folders` and symlinks.
+fn library_files(root: &Path, excluded_folders: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(relative) = pending.pop() {
+        let entries = fs::read_dir(root.join(&relative))
+            .map_err(|e| format!("Failed to read {}: {}", relative.display(), e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            let file_type = entry.file_type().map_err(|e| e.to_string())?;
+            let path = relative.join(&name);
+            if file_type.is_dir() {
+                let excluded = relative.as_os_str().is_empty() && excluded_folders.contains(&name);
+                if name != GIT_DIR && !excluded {
+                    pending.push(path);
+                }
+            } else if file_type.is_file() {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Zip `files` from `root` into `archive`, under one top-level folder
+/// named `top_dir`, reporting progress every `PROGRESS_EVERY_FILES` files
+/// and at the end.
+fn write_archive(
+    root: &Path,
+    files: &[PathBuf],
+    archive: &Path,
+    top_dir: &str,
+    mut on_progress: impl FnMut(BackupProgress),
+) -> Result<(), String> {
+    let file = File::create(archive).map_err(|e| format!("Failed to write backup: {}", e))?;
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (done, relative) in files.iter().enumerate() {
+        let relative_name = relative.to_string_lossy().replace('\\', "/");
+        let mut source = File::open(root.join(relative))
+            .map_err(|e| format!("Failed to read {}: {}", relative_name, e))?;
+        zip.start_file(format!("{}/{}", top_dir, relative_name), options)
+            .map_err(|e| format!("Failed to write backup: {}", e))?;
+        io::copy(&mut source, &mut zip)
+            .map_err(|e| format!("Failed to back up {}: {}", relative_name, e))?;
+        let done = done + 1;
+        if done % PROGRESS_EVERY_FILES == 0 || done == files.len() {
+            on_progress(BackupProgress {
+                done,
+                total: files.len(),
+                path: relative_name,
+            });
+        }
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to write backup: {}", e))?
+        .flush()
+        .map_err(|e| format!("Failed to write backup: {}", e))
+}
+
+fn list_archives(destination: &Path) -> Result<Vec<BackupArchive>, String> {
+    if !destination.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut archives: Vec<(NaiveDateTime, BackupArchive)> = fs::read_dir(destination)
+        .map_err(|e| format!("Failed to read backup folder: {}", e))?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let created = archive_time(&entry.file_name().to_string_lossy())?;
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            let created_at = Local
+                .from_local_datetime(&created)
+                .earliest()
+                .map(|at| at.to_rfc3339())
+                .unwrap_or_default();
+            Some((
+                created,
+                BackupArchive {
+                    path: entry.path().to_string_lossy().to_string(),
+                    created_at,
+                    size_bytes: metadata.len(),
+                },
+            ))
+        })
+        .collect();
+    archives.sort_by(|(a, _), (b, _)| b.cmp(a));
+    Ok(archives.into_iter().map(|(_, archive)| archive).collect())
+}
+
+/// Zip the library into the destination and prune old archives.
+fn write_backup(
+    settings: &BackupSettings,
+    now: NaiveDateTime,
+    on_progress: impl FnMut(BackupProgress),
+) -> Result<(PathBuf, usize), String> {
+    let root = get_stik_folder()?;
+    let destination = destination_dir(settings, &root)?;
+    fs::create_dir_all(&destination)
+        .map_err(|e| format!("Failed to create backup folder: {}", e))?;
+
+    let files = library_files(&root, &settings.excluded_folders)?;
+    let name = archive_name(now);
+    let archive = destination.join(&name);
+    // Written as `.partial` so a half-written archive never looks finished.
+    let partial = destination.join(format!("{}.partial", name));
+    let top_dir = name.trim_end_matches(ARCHIVE_EXTENSION);
+    let result = write_archive(&root, &files, &partial, top_dir, on_progress).and_then(|()| {
+        fs::rename(&partial, &archive).map_err(|e| format!("Failed to write backup: {}", e))
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    result?;
+
+    let archives = list_archives(&destination)?;
+    for old in archives_to_prune(&archives, settings.retention_count) {
+        if let Err(e) = fs::remove_file(&old.path) {
+            log::error!("Failed to remove old backup {}: {}", old.path, e);
+        }
+    }
+    Ok((archive, files.len()))
+}
+
+/// Run one backup and record how it went. Fails straight away when a
+/// backup is already running.
+fn run_backup(app: &AppHandle) -> Result<BackupStatus, String> {
+    if BACKUP_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err("A backup is already running".to_string());
+    }
+    let result = settings::load_settings_from_file().and_then(|settings| {
+        write_backup(&settings.backup, Local::now().naive_local(), |progress| {
+            let _ = app.emit(PROGRESS_EVENT, progress);
+        })
+    });
+    BACKUP_RUNNING.store(false, Ordering::SeqCst);
+
+    let mut status = load_status().unwrap_or_default();
+    status.last_attempt_at = Some(Local::now().to_rfc3339());
+    match &result {
+        Ok((archive, files)) => {
+            status.last_success_at = status.last_attempt_at.clone();
+            status.last_archive = Some(archive.to_string_lossy().to_string());
+            status.last_files = *files;
+            status.last_archive_bytes = fs::metadata(archive).map(|m| m.len()).unwrap_or(0);
+            status.last_error = None;
+            log::info!("Backed up {} files to {}", files, archive.display());
+        }
+        Err(e) => {
+            status.last_error = Some(e.clone());
+            log::error!("Backup failed: {}", e);
+        }
+    }
+    if let Err(e) = save_status(&status) {
+        log::error!("Failed to save backup status: {}", e);
+    }
+    let _ = app.emit(FINISHED_EVENT, &status);
+    result.map(|_| status)
+}
+
+/// Start the scheduler that backs up the library when a backup is due.
+/// Call once during app setup.
+pub fn start_scheduler(app: AppHandle) {
+    if SCHEDULER_RUNNING.set(()).is_err() {
+        return;
+    }
+
+    thread::Builder::new()
+        .name("stik-backup".to_string())
+        .spawn(move || loop {
+            thread::sleep(std::time::Duration::from_secs(SCHEDULER_TICK_SECONDS));
+            let Ok(settings) = settings::load_settings_from_file() else {
+                continue;
+            };
+            let status = load_status().unwrap_or_default();
+            if is_backup_due(&settings.backup, &status, Local::now().naive_local()) {
+                let _ = run_backup(&app);
+            }
+        })
+        .ok();
+}
+
+fn load_status() -> Result<BackupStatus, String> {
+    let path = config_paths::config_file("backup.json")?;
+    Ok(versioning::load_versioned(&path)?.unwrap_or_default())
+}
+
+fn save_status(status: &BackupStatus) -> Result<(), String> {
+    let path = config_paths::writable_config_file("backup.json")?;
+    versioning::save_versioned(&path, status)
+}
+
+/// Extract `archive` into `destination`, which must not exist yet or be
+/// empty, and must not be inside the notes directory. Returns the folder
+/// holding the restored notes.
+fn restore_archive(archive: &Path, destination: &Path, root: &Path) -> Result<PathBuf, String> {
+    if !archive.is_file()
+        || archive_time(&archive.file_name().unwrap_or_default().to_string_lossy()).is_none()
+    {
+        return Err("Not a Stik backup archive".to_string());
+    }
+    if !destination.is_absolute() {
+        return Err("Restore folder must be an absolute path".to_string());
+    }
+    if is_inside(destination, root) || is_inside(root, destination) {
+        return Err("Restore into a new folder outside the Stik folder".to_string());
+    }
+    if destination.exists() {
+        let empty = fs::read_dir(destination)
+            .map_err(|e| format!("Failed to read restore folder: {}", e))?
+            .next()
+            .is_none();
+        if !empty {
+            return Err("Restore into a new or empty folder".to_string());
+        }
+    }
+    fs::create_dir_all(destination)
+        .map_err(|e| format!("Failed to create restore folder: {}", e))?;
+
+    File::open(archive)
+        .map_err(|e| e.to_string())
+        .and_then(|file| ZipArchive::new(file).map_err(|e| e.to_string()))
+        .and_then(|mut zip| zip.extract(destination).map_err(|e| e.to_string()))
+        .map_err(|e| format!("Could not extract the backup: {}", e))?;
+
+    // Archives hold one top-level folder named after the archive.
+    let library = archive
+        .file_stem()
+        .map(|stem| destination.join(stem))
+        .filter(|library| library.is_dir())
+        .unwrap_or_else(|| destination.to_path_buf());
+    Ok(library)
+}
+
+/// Back up now, whatever the schedule says, as long as a destination is
+/// set. Progress arrives as `backup-progress` events.
+#[tauri::command]
+pub async fn run_backup_now(app: AppHandle) -> Result<BackupStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || run_backup(&app))
+        .await
+        .map_err(|e| format!("Backup failed: {}", e))?
+}
+
+/// Archives in the configured backup folder, newest first.
+#[tauri::command]
+pub fn list_backups() -> Result<Vec<BackupArchive>, String> {
+    let settings = settings::get_settings()?;
+    let destination = settings.backup.destination.trim();
+    if destination.is_empty() {
+        return Ok(Vec::new());
+    }
+    list_archives(Path::new(destination))
+}
+
+#[tauri::command]
+pub fn get_backup_status() -> Result<BackupStatus, String> {
+    load_status()
+}
+
+/// Extract a backup into a fresh folder and return where the notes are.
+/// Never touches the current notes directory.
+#[tauri::command]
+pub async fn restore_backup(path: String, destination: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = get_stik_folder()?;
+        restore_archive(Path::new(&path), Path::new(&destination), &root)
+            .map(|library| library.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| format!("Restore failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::temp_dir;
+    use chrono::NaiveDate;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 2, day)
+            .and_then(|date| date.and_hms_opt(hour, 0, 0))
+            .expect("valid time")
+    }
+
+    fn rfc3339(at: NaiveDateTime) -> Option<String> {
+        Local
+            .from_local_datetime(&at)
+            .earliest()
+            .map(|at| at.to_rfc3339())
+    }
+
+    #[test]
+    fn archive_names_round_trip() {
+        let name = archive_name(at(6, 9));
+        assert_eq!(name, "stik-backup-20260206-090000.zip");
+        assert_eq!(archive_time(&name), Some(at(6, 9)));
+        assert_eq!(archive_time("notes.zip"), None);
+        assert_eq!(
+            archive_time("stik-backup-20260206-090000.zip.partial"),
+            None
+        );
+    }
+
+    #[test]
+    fn backups_are_due_after_the_frequency_and_retry_hourly() {
+        let settings = BackupSettings {
+            enabled: true,
+            destination: "/Volumes/Backup".to_string(),
+            frequency_days: 2,
+            ..Default::default()
+        };
+        assert!(is_backup_due(&settings, &BackupStatus::default(), at(6, 9)));
+
+        let done = BackupStatus {
+            last_attempt_at: rfc3339(at(6, 9)),
+            last_success_at: rfc3339(at(6, 9)),
+            ..Default::default()
+        };
+        assert!(!is_backup_due(&settings, &done, at(7, 9)));
+        assert!(is_backup_due(&settings, &done, at(8, 9)));
+
+        let failed = BackupStatus {
+            last_attempt_at: rfc3339(at(8, 9)),
+            ..done
+        };
+        assert!(!is_backup_due(&settings, &failed, at(8, 9)));
+        assert!(is_backup_due(&settings, &failed, at(8, 10)));
+
+        let off = BackupSettings {
+            enabled: false,
+            ..settings
+        };
+        assert!(!is_backup_due(&off, &BackupStatus::default(), at(6, 9)));
+    }
+
+    #[test]
+    fn pruning_keeps_the_newest_archives() {
+        let archives: Vec<BackupArchive> = (1..=4)
+            .rev()
+            .map(|day| BackupArchive {
+                path: archive_name(at(day, 9)),
+                created_at: String::new(),
+                size_bytes: 0,
+            })
+            .collect();
+        let pruned: Vec<&str> = archives_to_prune(&archives, 2)
+            .iter()
+            .map(|archive| archive.path.as_str())
+            .collect();
+        assert_eq!(
+            pruned,
+            vec![
+                "stik-backup-20260202-090000.zip",
+                "stik-backup-20260201-090000.zip"
+            ]
+        );
+        assert!(archives_to_prune(&archives, 0).is_empty());
+        assert!(archives_to_prune(&archives, 10).is_empty());
+    }
+
+    #[test]
+    fn library_files_skip_git_and_excluded_folders() {
+        let root = temp_dir("backup-files");
+        for (path, content) in [
+            ("Inbox/a.md", "a"),
+            ("Inbox/.assets/img.png", "png"),
+            ("Private/secret.md", "s"),
+            ("Work/.git/HEAD", "ref"),
+            ("Work/plan.md", "p"),
+            (".git/config", "c"),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let files = library_files(&root, &["Private".to_string()]).expect("list files");
+        let files: Vec<String> = files
+            .iter()
+            .map(|path| path.to_string_lossy().replace('\\', "/"))
+            .collect();
+        assert_eq!(
+            files,
+            vec!["Inbox/.assets/img.png", "Inbox/a.md", "Work/plan.md"]
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn archives_restore_into_a_folder_named_after_them() {
+        let root = temp_dir("backup-zip-root");
+        let elsewhere = temp_dir("backup-zip-dest");
+        for (path, content) in [("Inbox/a.md", "a"), ("Work/plan.md", "p")] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        let files = library_files(&root, &[]).expect("list files");
+        let name = archive_name(at(6, 9));
+        let archive = elsewhere.join(&name);
+        let mut reported = Vec::new();
+        write_archive(
+            &root,
+            &files,
+            &archive,
+            name.trim_end_matches(ARCHIVE_EXTENSION),
+            |progress| reported.push(progress.done),
+        )
+        .expect("write archive");
+        assert_eq!(reported, vec![2]);
+
+        let library =
+            restore_archive(&archive, &elsewhere.join("Restored"), &root).expect("restore archive");
+        assert_eq!(
+            library,
+            elsewhere
+                .join("Restored")
+                .join("stik-backup-20260206-090000")
+        );
+        assert_eq!(fs::read_to_string(library.join("Inbox/a.md")).unwrap(), "a");
+        assert_eq!(
+            fs::read_to_string(library.join("Work/plan.md")).unwrap(),
+            "p"
+        );
+
+        let _ = fs::remove_dir_all(&root);
+        let _ = fs::remove_dir_all(&elsewhere);
+    }
+
+    #[test]
+    fn restore_refuses_non_empty_or_in_library_folders() {
+        let root = temp_dir("backup-root");
+        let elsewhere = temp_dir("backup-dest");
+        let archive = elsewhere.join(archive_name(at(6, 9)));
+        fs::write(&archive, b"zip").unwrap();
+        fs::write(elsewhere.join("existing.md"), "x").unwrap();
+
+        assert_eq!(
+            restore_archive(&archive, &elsewhere, &root),
+            Err("Restore into a new or empty folder".to_string())
+        );
+        assert_eq!(
+            restore_archive(&archive, &root.join("Restored"), &root),
+            Err("Restore into a new folder outside the Stik folder".to_string())
+        );
+        assert_eq!(
+            restore_archive(
+                &elsewhere.join("existing.md"),
+                &elsewhere.join("new"),
+                &root
+            ),
+            Err("Not a Stik backup archive".to_string())
+        );
+
+        let _ = fs::remove_dir_all(&root);
+        let _ = fs::remove_dir_all(&elsewhere);
+    }
+}
@@ -0,0 +1,241 @@
+/// Capture context — with the `capture_context` setting on, a note saved
+/// from the capture window ends with a footer naming where it was taken:
+///
+/// ```text
+/// ---
+/// Source: Safari — "Page title" (https://example.com)
+/// Captured: 2026-02-06 14:02
+/// ```
+///
+/// The frontmost app is recorded when the capture window is summoned. For
+/// known browsers the current tab's title and URL are then read through
+/// AppleScript in the background; when that fails (Automation denied, no
+/// window open) the footer names just the app. Only the capture window's
+/// own saves get a footer, never imports, the CLI or the API server.
+use chrono::{Local, NaiveDateTime};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::{AppHandle, Manager, State};
+
+use super::embeddings::EmbeddingIndex;
+use super::index::NoteIndex;
+use super::notes::{self, NoteSaved};
+use super::{frontmost_app, settings};
+use crate::state::{AppState, CaptureSource};
+
+/// Browsers whose front tab we can ask for, and the dictionary they use.
+const SAFARI_BROWSERS: [&str; 2] = ["Safari", "Safari Technology Preview"];
+const CHROMIUM_BROWSERS: [&str; 6] = [
+    "Google Chrome",
+    "Arc",
+    "Brave Browser",
+    "Microsoft Edge",
+    "Vivaldi",
+    "Chromium",
+];
+
+/// Bumped on every capture, so a slow tab lookup can't overwrite the
+/// source of a later one.
+static CAPTURE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// AppleScript returning the front tab's URL and title on two lines.
+fn tab_script(app_name: &str) -> Option<String> {
+    if SAFARI_BROWSERS.contains(&app_name) {
+        Some(format!(
+            "tell application \"{}\" to return (URL of front document) & linefeed & (name of front document)",
+            app_name
+        ))
+    } else if CHROMIUM_BROWSERS.contains(&app_name) {
+        Some(format!(
+            "tell application \"{}\" to return (URL of active tab of front window) & linefeed & (title of active tab of front window)",
+            app_name
+        ))
+    } else {
+        None
+    }
+}
+
+/// `(title, url)` from the script's output. Only web URLs count; empty or
+/// `missing value` lines are dropped.
+fn parse_tab(output: &str) -> (Option<String>, Option<String>) {
+    let mut lines = output.lines().map(str::trim);
+    let url = lines
+        .next()
+        .filter(|url| url.starts_with("http://") || url.starts_with("https://"))
+        .map(str::to_string);
+    let title = lines
+        .next()
+        .filter(|title| !title.is_empty() && *title != "missing value")
+        .map(str::to_string);
+    (title, url)
+}
+
+#[cfg(target_os = "macos")]
+fn run_script(script: &str) -> Option<String> {
+    let output = std::process::Command::new("osascript")
+        .arg("-e")
+        .arg(script)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn run_script(_script: &str) -> Option<String> {
+    None
+}
+
+/// Record the frontmost app as the source of the capture that is about to
+/// start. Call before the capture window takes focus.
+pub fn remember_capture_source(app: &AppHandle) {
+    let generation = CAPTURE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let enabled = settings::load_settings_from_file()
+        .map(|s| s.capture_context)
+        .unwrap_or(false);
+    let source = enabled
+        .then(|| {
+            frontmost_app::frontmost_pid()
+                .filter(|pid| !frontmost_app::is_stik(*pid))
+                .and_then(|_| frontmost_app::frontmost_name())
+        })
+        .flatten()
+        .map(|name| CaptureSource {
+            app: name,
+            title: None,
+            url: None,
+        });
+
+    let state = app.state::<AppState>();
+    *state
+        .capture_source
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = source.clone();
+
+    let Some(script) = source.as_ref().and_then(|source| tab_script(&source.app)) else {
+        return;
+    };
+    let app = app.clone();
+    std::thread::Builder::new()
+        .name("stik-capture-context".to_string())
+        .spawn(move || {
+            let Some(output) = run_script(&script) else {
+                return;
+            };
+            let (title, url) = parse_tab(&output);
+            if CAPTURE_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let state = app.state::<AppState>();
+            let mut current = state
+                .capture_source
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if let Some(source) = current.as_mut() {
+                source.title = title;
+                source.url = url;
+            }
+        })
+        .ok();
+}
+
+fn footer(source: &CaptureSource, at: NaiveDateTime) -> String {
+    let mut line = format!("Source: {}", source.app);
+    if let Some(title) = &source.title {
+        line.push_str(&format!(" — \"{}\"", title));
+    }
+    if let Some(url) = &source.url {
+        line.push_str(&format!(" ({})", url));
+    }
+    format!("---\n{}\nCaptured: {}\n", line, at.format("%Y-%m-%d %H:%M"))
+}
+
+/// `content` ending in the context footer. The blank line keeps the `---`
+/// a rule rather than turning the last line into a heading.
+fn with_footer(content: &str, source: &CaptureSource, at: NaiveDateTime) -> String {
+    format!("{}\n\n{}", content.trim_end(), footer(source, at))
+}
+
+/// `save_note` for the capture window: adds the context footer when the
+/// setting is on and a source was recorded for this capture.
+#[tauri::command]
+pub fn save_note_with_context(
+    app: AppHandle,
+    folder: String,
+    content: String,
+    index: State<'_, NoteIndex>,
+    emb_index: State<'_, EmbeddingIndex>,
+) -> Result<NoteSaved, String> {
+    // Cloned, not taken: a repeated save of this capture must produce the
+    // same text for `save_note` to recognise it as a duplicate.
+    let source = app
+        .state::<AppState>()
+        .capture_source
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let enabled = settings::load_settings_from_file()
+        .map(|s| s.capture_context)
+        .unwrap_or(false);
+    let content = match source {
+        Some(source) if enabled && !notes::is_effectively_empty_markdown(&content) => {
+            with_footer(&content, &source, Local::now().naive_local())
+        }
+        _ => content,
+    };
+    notes::save_note(app, folder, content, index, emb_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 2, 6)
+            .and_then(|date| date.and_hms_opt(14, 2, 31))
+            .expect("valid time")
+    }
+
+    fn source(title: Option<&str>, url: Option<&str>) -> CaptureSource {
+        CaptureSource {
+            app: "Safari".to_string(),
+            title: title.map(str::to_string),
+            url: url.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn footer_names_page_and_url_when_known() {
+        let full = source(Some("Page title"), Some("https://example.com/a"));
+        assert_eq!(
+            with_footer("Idea\n\n", &full, at()),
+            "Idea\n\n---\nSource: Safari — \"Page title\" (https://example.com/a)\nCaptured: 2026-02-06 14:02\n"
+        );
+        assert_eq!(
+            footer(&source(None, None), at()),
+            "---\nSource: Safari\nCaptured: 2026-02-06 14:02\n"
+        );
+    }
+
+    #[test]
+    fn only_known_browsers_are_scripted() {
+        assert!(tab_script("Safari").unwrap().contains("front document"));
+        assert!(tab_script("Arc").unwrap().contains("active tab"));
+        assert_eq!(tab_script("Finder"), None);
+    }
+
+    #[test]
+    fn tab_output_keeps_web_urls_and_real_titles() {
+        assert_eq!(
+            parse_tab("https://example.com\nExample\n"),
+            (
+                Some("Example".to_string()),
+                Some("https://example.com".to_string())
+            )
+        );
+        assert_eq!(parse_tab("favorites://\nmissing value\n"), (None, None));
+        assert_eq!(parse_tab(""), (None, None));
+    }
+}
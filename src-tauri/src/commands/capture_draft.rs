@@ -0,0 +1,41 @@
+/// The capture draft: what was in the postit when a blur hid it under the
+/// `keep_draft` policy. Kept in `capture_draft.json` so it survives a
+/// restart, and handed back to the next capture session exactly once.
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::{config_paths, versioning};
+
+const CAPTURE_DRAFT_FILE: &str = "capture_draft.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureDraft {
+    pub content: String,
+    pub folder: String,
+    /// RFC 3339 timestamp.
+    pub saved_at: String,
+}
+
+/// Replace the stored draft with `content`.
+pub fn stash(content: &str, folder: &str) -> Result<(), String> {
+    let path = config_paths::writable_config_file(CAPTURE_DRAFT_FILE)?;
+    let draft = CaptureDraft {
+        content: content.to_string(),
+        folder: folder.to_string(),
+        saved_at: Local::now().to_rfc3339(),
+    };
+    versioning::save_versioned(&path, &draft)
+}
+
+/// The stored draft, removing it so it is only restored once.
+#[tauri::command]
+pub fn take_capture_draft() -> Result<Option<CaptureDraft>, String> {
+    let path = config_paths::config_file(CAPTURE_DRAFT_FILE)?;
+    let draft = versioning::load_versioned::<CaptureDraft>(&path)?;
+    if draft.is_some() {
+        let writable = config_paths::writable_config_file(CAPTURE_DRAFT_FILE)?;
+        fs::remove_file(&writable).map_err(|e| format!("Failed to clear capture draft: {}", e))?;
+    }
+    Ok(draft)
+}
@@ -0,0 +1,244 @@
+/// Notes directories inside iCloud Drive, Dropbox, OneDrive and the like.
+///
+/// Sync clients evict files they think are unused and restore clashing
+/// edits as `name 2.md` copies. An evicted note can read as empty until
+/// it is downloaded again, so saves and the index check `looks_evicted`
+/// before trusting an empty read, and `find_cloud_conflict_duplicates`
+/// pairs conflict copies with their originals so they can be merged.
+use serde::Serialize;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, State};
+
+use super::embeddings::EmbeddingIndex;
+use super::folders::get_stik_folder;
+use super::index::NoteIndex;
+use super::{notes, spotlight, storage, tray_menu, widget_feed};
+
+/// Stik never leaves an empty note behind, so a zero-length note this old
+/// in a synced folder is a placeholder rather than something being written.
+const EVICTED_EMPTY_AGE: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CloudConflictDuplicate {
+    pub original: String,
+    pub duplicate: String,
+    pub folder: String,
+    /// Same content as the original, so merging just removes the copy.
+    pub identical: bool,
+}
+
+/// Inside a folder kept in sync by iCloud Drive or a File Provider client.
+pub fn is_cloud_synced(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    ["/Library/Mobile Documents/", "/Library/CloudStorage/"]
+        .iter()
+        .any(|marker| path.contains(marker))
+}
+
+#[cfg(target_os = "macos")]
+fn is_dataless(metadata: &std::fs::Metadata) -> bool {
+    use std::os::macos::fs::MetadataExt;
+    const SF_DATALESS: u32 = 0x4000_0000;
+    metadata.st_flags() & SF_DATALESS != 0
+}
+
+#[cfg(not(target_os = "macos"))]
+fn is_dataless(_metadata: &std::fs::Metadata) -> bool {
+    false
+}
+
+/// The note's content isn't on this machine right now: it has been
+/// evicted to a placeholder, so reading it says nothing about what it holds.
+pub fn looks_evicted(path: &Path) -> bool {
+    let placeholder = path
+        .file_name()
+        .map(|name| path.with_file_name(format!(".{}.icloud", name.to_string_lossy())));
+    if placeholder.is_some_and(|placeholder| placeholder.exists()) {
+        return true;
+    }
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    if is_dataless(&metadata) {
+        return true;
+    }
+    let old_enough = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age >= EVICTED_EMPTY_AGE);
+    metadata.len() == 0 && old_enough && is_cloud_synced(path)
+}
+
+/// `name.md` for a conflict copy named `name 2.md`, `name 3.md`, ...
+/// Three-digit suffixes are left alone; those are real names.
+fn conflict_original_name(name: &str) -> Option<String> {
+    let stem = name.strip_suffix(".md")?;
+    let (base, number) = stem.rsplit_once(' ')?;
+    let copy: u32 = number.parse().ok()?;
+    let plausible = (2..100).contains(&copy) && !number.starts_with('0');
+    (plausible && !base.is_empty()).then(|| format!("{}.md", base))
+}
+
+/// Pair conflict copies with originals among `names` (one folder's files).
+fn pair_conflict_copies(names: &[String]) -> Vec<(String, String)> {
+    let mut pairs: Vec<(String, String)> = names
+        .iter()
+        .filter_map(|name| {
+            let original = conflict_original_name(name)?;
+            names.contains(&original).then(|| (original, name.clone()))
+        })
+        .collect();
+    pairs.sort();
+    pairs
+}
+
+#[tauri::command]
+pub fn find_cloud_conflict_duplicates() -> Result<Vec<CloudConflictDuplicate>, String> {
+    let root = get_stik_folder()?;
+    let mut folders = vec![String::new()];
+    folders.extend(
+        storage::list_dir(&root.to_string_lossy())?
+            .into_iter()
+            .filter(|entry| entry.is_directory && !entry.name.starts_with('.'))
+            .map(|entry| entry.name),
+    );
+
+    let mut duplicates = Vec::new();
+    for folder in folders {
+        let folder_path = root.join(&folder);
+        let Ok(entries) = storage::list_dir(&folder_path.to_string_lossy()) else {
+            continue;
+        };
+        let names: Vec<String> = entries
+            .into_iter()
+            .filter(|entry| !entry.is_directory && entry.name.ends_with(".md"))
+            .map(|entry| entry.name)
+            .collect();
+        for (original, duplicate) in pair_conflict_copies(&names) {
+            let original = folder_path.join(original).to_string_lossy().to_string();
+            let duplicate = folder_path.join(duplicate).to_string_lossy().to_string();
+            // Comparing an evicted file would download it; leave it unpaired.
+            if looks_evicted(Path::new(&original)) || looks_evicted(Path::new(&duplicate)) {
+                continue;
+            }
+            let identical = match (
+                storage::read_file(&original),
+                storage::read_file(&duplicate),
+            ) {
+                (Ok(a), Ok(b)) => a.trim_end() == b.trim_end(),
+                _ => false,
+            };
+            duplicates.push(CloudConflictDuplicate {
+                original,
+                duplicate,
+                folder: folder.clone(),
+                identical,
+            });
+        }
+    }
+    Ok(duplicates)
+}
+
+/// The original followed by whatever the copy adds, under a divider.
+fn merged_content(original: &str, duplicate: &str) -> String {
+    if original.trim_end() == duplicate.trim_end() || duplicate.trim().is_empty() {
+        return original.to_string();
+    }
+    format!(
+        "{}\n\n---\n\n{}",
+        original.trim_end(),
+        duplicate.trim_start()
+    )
+}
+
+/// Fold a conflict copy into its original and delete the copy. The copy's
+/// images are left alone: they are the same files the original links to.
+#[tauri::command]
+pub fn merge_cloud_conflict_duplicate(
+    app: AppHandle,
+    original: String,
+    duplicate: String,
+    index: State<'_, NoteIndex>,
+    emb_index: State<'_, EmbeddingIndex>,
+) -> Result<notes::NoteSaved, String> {
+    let root = get_stik_folder()?;
+    if !Path::new(&original).starts_with(&root) || !Path::new(&duplicate).starts_with(&root) {
+        return Err("Invalid path: notes must be within Stik folder".to_string());
+    }
+    if looks_evicted(Path::new(&original)) || looks_evicted(Path::new(&duplicate)) {
+        return Err("Wait for both notes to finish downloading before merging".to_string());
+    }
+    let original_content = storage::read_file(&original)?;
+    let duplicate_content = storage::read_file(&duplicate)?;
+    let merged = merged_content(&original_content, &duplicate_content);
+
+    let saved = if merged == original_content {
+        let folder = Path::new(&original)
+            .parent()
+            .and_then(|parent| parent.strip_prefix(&root).ok())
+            .map(|folder| folder.to_string_lossy().to_string())
+            .unwrap_or_default();
+        notes::NoteSaved {
+            filename: Path::new(&original)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path: original,
+            folder,
+        }
+    } else {
+        notes::update_note(
+            app.clone(),
+            original,
+            merged,
+            index.clone(),
+            emb_index.clone(),
+        )?
+    };
+
+    storage::delete_file(&duplicate).map_err(|e| format!("Failed to delete copy: {}", e))?;
+    index.remove(&duplicate);
+    spotlight::note_removed(&duplicate);
+    widget_feed::notes_changed();
+    emb_index.remove_entry(&duplicate);
+    let _ = emb_index.save();
+    tray_menu::rebuild_tray_menu(&app);
+    notes::emit_note_deleted(&app, &duplicate);
+    Ok(saved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conflict_copies_pair_with_their_originals() {
+        let names: Vec<String> = [
+            "plan.md",
+            "plan 2.md",
+            "plan 3.md",
+            "trip 2.md",
+            "version 1.md",
+            "room 101.md",
+            "room.md",
+        ]
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+        assert_eq!(
+            pair_conflict_copies(&names),
+            vec![
+                ("plan.md".to_string(), "plan 2.md".to_string()),
+                ("plan.md".to_string(), "plan 3.md".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn merging_keeps_both_sides_once() {
+        assert_eq!(merged_content("a\n", "a"), "a\n");
+        assert_eq!(merged_content("a\n", "b\n"), "a\n\n---\n\nb\n");
+    }
+}
@@ -0,0 +1,276 @@
+/// Where Stik keeps its own state: settings, sticked notes, embeddings,
+/// stats and the other small JSON stores.
+///
+/// Normally that is `~/.stik`. In portable mode it is `.stik-config/`
+/// inside the notes folder, so the state travels with a notes folder kept
+/// on an external drive. Portable mode is on when that directory exists or
+/// when `portable_mode` is set. `~/.stik/settings.json` stays the locator:
+/// it records where the notes folder is, so it is read first and keeps the
+/// storage fields in sync with the portable copy.
+///
+/// Machine-bound files always stay in `~/.stik`: the CLI socket, API
+/// token, note encryption key, the analytics id, queue and log, the widget
+/// feed, app logs and crash reports. They describe this machine, or are
+/// read by other processes on it, so they don't travel with the notes.
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use super::settings::StikSettings;
+use super::storage::{self, StorageMode};
+use super::versioning;
+
+pub const PORTABLE_DIR_NAME: &str = ".stik-config";
+pub const SETTINGS_FILE: &str = "settings.json";
+pub const UNAVAILABLE_EVENT: &str = "portable-config-unavailable";
+
+/// State files that move into `.stik-config/` when portable mode starts.
+const PORTABLE_FILES: &[&str] = &[
+    SETTINGS_FILE,
+    "sticked_notes.json",
+    "embeddings.json",
+    "stats.json",
+    "on_this_day.json",
+    "daily_digest.json",
+    "cursor_positions.json",
+    "window_geometry.json",
+    "reminders_export.json",
+    "edit_locks.json",
+    "note_expiry.json",
+    "backup.json",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", content = "dir", rename_all = "snake_case")]
+pub enum ConfigLocation {
+    Home(PathBuf),
+    Portable(PathBuf),
+    /// Portable mode is on but the notes folder (its drive) is missing.
+    /// Reads fall back to defaults and writes are refused.
+    Unavailable(PathBuf),
+}
+
+impl ConfigLocation {
+    pub fn dir(&self) -> &Path {
+        match self {
+            Self::Home(dir) | Self::Portable(dir) | Self::Unavailable(dir) => dir,
+        }
+    }
+}
+
+static LOCATION: OnceLock<Mutex<Option<ConfigLocation>>> = OnceLock::new();
+
+fn cached() -> &'static Mutex<Option<ConfigLocation>> {
+    LOCATION.get_or_init(|| Mutex::new(None))
+}
+
+/// The active config location. Resolved once and cached; an unavailable
+/// portable location is re-checked on every call so a drive plugged in
+/// later is picked up.
+pub fn location() -> Result<ConfigLocation, String> {
+    let mut cached = cached().lock().unwrap_or_else(|e| e.into_inner());
+    match cached.as_ref() {
+        Some(location) if !matches!(location, ConfigLocation::Unavailable(_)) => {
+            return Ok(location.clone());
+        }
+        _ => {}
+    }
+
+    let home = home_config_dir()?;
+    let locator = load_locator(&home);
+    let notes_root = notes_root_for(&locator);
+    let location = resolve(&home, notes_root.as_deref(), locator.portable_mode);
+    if let ConfigLocation::Portable(dir) = &location {
+        if !dir.exists() {
+            start_portable_dir(&home, dir)?;
+        }
+        if !locator.portable_mode {
+            remember_portable_mode(&home, locator);
+        }
+    }
+    *cached = Some(location.clone());
+    Ok(location)
+}
+
+/// The active config location, for the warning shown while a portable
+/// config is unreachable. Asking again re-checks an unavailable one.
+#[tauri::command]
+pub fn get_config_location() -> Result<ConfigLocation, String> {
+    location()
+}
+
+/// Forget the cached location, e.g. after the notes folder changed.
+pub fn refresh() {
+    *cached().lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Path of a state file for reading. While the portable location is
+/// unavailable this points at a missing file, so loaders see defaults.
+pub fn config_file(name: &str) -> Result<PathBuf, String> {
+    let location = location()?;
+    if !matches!(location, ConfigLocation::Unavailable(_)) {
+        fs::create_dir_all(location.dir()).map_err(|e| e.to_string())?;
+    }
+    Ok(location.dir().join(name))
+}
+
+/// Path of a state file for writing.
+pub fn writable_config_file(name: &str) -> Result<PathBuf, String> {
+    if let ConfigLocation::Unavailable(dir) = location()? {
+        return Err(format!(
+            "Stik's portable config at {} isn't available. Reconnect the drive with your notes to save changes.",
+            dir.display()
+        ));
+    }
+    config_file(name)
+}
+
+/// `~/.stik`, regardless of mode.
+pub fn home_config_dir() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not find home directory")?;
+    Ok(home.join(".stik"))
+}
+
+/// The settings in `~/.stik/settings.json`, which locate the notes folder.
+pub(crate) fn load_home_settings() -> Result<StikSettings, String> {
+    Ok(load_locator(&home_config_dir()?))
+}
+
+fn load_locator(home: &Path) -> StikSettings {
+    versioning::load_versioned::<StikSettings>(&home.join(SETTINGS_FILE))
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// The notes folder the settings point at, without creating it.
+fn notes_root_for(settings: &StikSettings) -> Option<PathBuf> {
+    match storage::mode_for(settings) {
+        StorageMode::ICloud => storage::icloud_container_path()
+            .ok()
+            .map(|drive| drive.join("Stik")),
+        StorageMode::Custom(dir) if settings.use_directory_as_root => Some(PathBuf::from(dir)),
+        StorageMode::Custom(dir) => Some(PathBuf::from(dir).join("Stik")),
+        StorageMode::Local => dirs::document_dir().map(|docs| docs.join("Stik")),
+    }
+}
+
+fn resolve(home: &Path, notes_root: Option<&Path>, portable_requested: bool) -> ConfigLocation {
+    let Some(root) = notes_root else {
+        return ConfigLocation::Home(home.to_path_buf());
+    };
+    let portable_dir = root.join(PORTABLE_DIR_NAME);
+    if portable_dir.is_dir() {
+        return ConfigLocation::Portable(portable_dir);
+    }
+    if !portable_requested {
+        return ConfigLocation::Home(home.to_path_buf());
+    }
+    if root.is_dir() {
+        ConfigLocation::Portable(portable_dir)
+    } else {
+        ConfigLocation::Unavailable(portable_dir)
+    }
+}
+
+/// Create the portable directory, seeded with the current home state.
+fn start_portable_dir(home: &Path, dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    for name in PORTABLE_FILES {
+        let source = home.join(name);
+        if source.is_file() {
+            fs::copy(&source, dir.join(name))
+                .map_err(|e| format!("Failed to copy {} to portable config: {}", name, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// A `.stik-config/` found on disk turns the setting on, so the locator
+/// still knows to wait for the drive when it is missing at a later launch.
+fn remember_portable_mode(home: &Path, mut locator: StikSettings) {
+    locator.portable_mode = true;
+    if let Err(e) = versioning::save_versioned(&home.join(SETTINGS_FILE), &locator) {
+        log::error!("Failed to record portable mode: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::temp_dir;
+
+    #[test]
+    fn home_is_used_unless_portable_mode_is_on() {
+        let base = temp_dir("config-paths-home");
+        let home = base.join(".stik");
+        let notes = base.join("Notes");
+        fs::create_dir_all(&notes).expect("create notes dir");
+
+        assert_eq!(
+            resolve(&home, Some(&notes), false),
+            ConfigLocation::Home(home.clone())
+        );
+        assert_eq!(
+            resolve(&home, None, true),
+            ConfigLocation::Home(home.clone())
+        );
+        let _ = fs::remove_dir_all(base);
+    }
+
+    #[test]
+    fn portable_dir_in_notes_folder_wins() {
+        let base = temp_dir("config-paths-portable");
+        let home = base.join(".stik");
+        let notes = base.join("Notes");
+        let portable = notes.join(PORTABLE_DIR_NAME);
+        fs::create_dir_all(&notes).expect("create notes dir");
+
+        // Requested by the setting: used even before the directory exists.
+        assert_eq!(
+            resolve(&home, Some(&notes), true),
+            ConfigLocation::Portable(portable.clone())
+        );
+
+        // Present on disk: used without the setting.
+        fs::create_dir_all(&portable).expect("create portable dir");
+        assert_eq!(
+            resolve(&home, Some(&notes), false),
+            ConfigLocation::Portable(portable)
+        );
+        let _ = fs::remove_dir_all(base);
+    }
+
+    #[test]
+    fn missing_drive_is_unavailable_not_home() {
+        let base = temp_dir("config-paths-missing");
+        let home = base.join(".stik");
+        let notes = base.join("Volumes").join("Notes");
+
+        assert_eq!(
+            resolve(&home, Some(&notes), true),
+            ConfigLocation::Unavailable(notes.join(PORTABLE_DIR_NAME))
+        );
+        assert_eq!(
+            resolve(&home, Some(&notes), false),
+            ConfigLocation::Home(home)
+        );
+        let _ = fs::remove_dir_all(base);
+    }
+
+    #[test]
+    fn starting_portable_mode_copies_existing_state() {
+        let base = temp_dir("config-paths-seed");
+        let home = base.join(".stik");
+        let portable = base.join("Notes").join(PORTABLE_DIR_NAME);
+        fs::create_dir_all(&home).expect("create home config");
+        fs::write(home.join("stats.json"), "{}").expect("write stats");
+        fs::write(home.join("api-token"), "secret").expect("write token");
+
+        start_portable_dir(&home, &portable).expect("seed portable dir");
+        assert!(portable.join("stats.json").is_file());
+        assert!(!portable.join("api-token").exists());
+        let _ = fs::remove_dir_all(base);
+    }
+}
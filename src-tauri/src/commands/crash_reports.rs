@@ -0,0 +1,212 @@
+/// Local crash reports written by a panic hook.
+///
+/// Each panic leaves `~/.stik/crashes/crash-<timestamp>.txt` with the
+/// message, location, backtrace, app version and OS version. Nothing is
+/// uploaded: the settings window lists the reports so the user can copy
+/// one into a GitHub issue themselves.
+use serde::Serialize;
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::process::Command;
+
+use super::config_paths;
+
+const REPORT_PREFIX: &str = "crash-";
+const REPORT_EXTENSION: &str = ".txt";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashReportInfo {
+    pub name: String,
+    /// RFC 3339 modification time.
+    pub created: String,
+    pub size_bytes: u64,
+}
+
+/// Write a report for every panic, then run the default hook. Call first
+/// thing in `main`.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        match write_report(info) {
+            Ok(path) => log::error!("Crash report written to {}", path.display()),
+            Err(e) => log::error!("Failed to write crash report: {}", e),
+        }
+        default_hook(info);
+    }));
+}
+
+fn crashes_dir() -> Result<PathBuf, String> {
+    Ok(config_paths::home_config_dir()?.join("crashes"))
+}
+
+fn write_report(info: &PanicHookInfo<'_>) -> Result<PathBuf, String> {
+    let dir = crashes_dir()?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let now = chrono::Local::now();
+    let name = format!(
+        "{}{}{}",
+        REPORT_PREFIX,
+        now.format("%Y%m%d-%H%M%S-%3f"),
+        REPORT_EXTENSION
+    );
+    let location = info
+        .location()
+        .map(|location| location.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let report = format_report(
+        &panic_message(info),
+        &location,
+        std::thread::current().name().unwrap_or("unnamed"),
+        &now.to_rfc3339(),
+        &os_version(),
+        &Backtrace::force_capture().to_string(),
+    );
+
+    let path = dir.join(name);
+    fs::write(&path, report).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+fn panic_message(info: &PanicHookInfo<'_>) -> String {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "(non-string panic payload)".to_string())
+}
+
+pub(crate) fn os_version() -> String {
+    let version = Command::new("sw_vers")
+        .arg("-productVersion")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .unwrap_or_default();
+    if version.is_empty() {
+        std::env::consts::OS.to_string()
+    } else {
+        format!("macOS {}", version)
+    }
+}
+
+fn format_report(
+    message: &str,
+    location: &str,
+    thread: &str,
+    timestamp: &str,
+    os_version: &str,
+    backtrace: &str,
+) -> String {
+    format!(
+        "Stik {version} crash report\n\
+         Time: {timestamp}\n\
+         OS: {os_version} ({arch})\n\
+         Thread: {thread}\n\
+         Location: {location}\n\
+         \n\
+         Message:\n{message}\n\
+         \n\
+         Backtrace:\n{backtrace}\n",
+        version = env!("CARGO_PKG_VERSION"),
+        arch = std::env::consts::ARCH,
+    )
+}
+
+/// Only bare report file names are accepted, never paths.
+fn is_report_name(name: &str) -> bool {
+    name.starts_with(REPORT_PREFIX)
+        && name.ends_with(REPORT_EXTENSION)
+        && !name.contains(['/', '\\'])
+        && !name.contains("..")
+}
+
+/// Reports on disk, newest first.
+#[tauri::command]
+pub fn list_crash_reports() -> Result<Vec<CrashReportInfo>, String> {
+    let dir = crashes_dir()?;
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut reports: Vec<CrashReportInfo> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !is_report_name(&name) {
+                return None;
+            }
+            let metadata = entry.metadata().ok()?;
+            let created = metadata
+                .modified()
+                .ok()
+                .map(|time| chrono::DateTime::<chrono::Local>::from(time).to_rfc3339())
+                .unwrap_or_default();
+            Some(CrashReportInfo {
+                name,
+                created,
+                size_bytes: metadata.len(),
+            })
+        })
+        .collect();
+    // Names embed the timestamp, so they sort chronologically.
+    reports.sort_by(|a, b| b.name.cmp(&a.name));
+    Ok(reports)
+}
+
+#[tauri::command]
+pub fn get_crash_report(name: String) -> Result<String, String> {
+    if !is_report_name(&name) {
+        return Err("Invalid crash report name".to_string());
+    }
+    fs::read_to_string(crashes_dir()?.join(&name))
+        .map_err(|e| format!("Failed to read crash report: {}", e))
+}
+
+/// Delete every report. Returns how many were removed.
+#[tauri::command]
+pub fn delete_crash_reports() -> Result<usize, String> {
+    let dir = crashes_dir()?;
+    let mut removed = 0;
+    for report in list_crash_reports()? {
+        fs::remove_file(dir.join(&report.name))
+            .map_err(|e| format!("Failed to delete {}: {}", report.name, e))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_bare_report_names_are_readable() {
+        assert!(is_report_name("crash-20260206-101530-123.txt"));
+        assert!(!is_report_name("../settings.json"));
+        assert!(!is_report_name("crash-../../etc/passwd.txt"));
+        assert!(!is_report_name("crash-a/b.txt"));
+        assert!(!is_report_name("notes.txt"));
+    }
+
+    #[test]
+    fn report_includes_message_location_and_versions() {
+        let report = format_report(
+            "byte index 3 is not a char boundary",
+            "src/commands/index.rs:42:17",
+            "main",
+            "2026-02-06T10:15:30+01:00",
+            "macOS 15.3",
+            "0: stik::main",
+        );
+        assert!(report.starts_with(&format!("Stik {} crash report", env!("CARGO_PKG_VERSION"))));
+        assert!(report.contains("Location: src/commands/index.rs:42:17"));
+        assert!(report.contains("OS: macOS 15.3"));
+        assert!(report.contains("Message:\nbyte index 3 is not a char boundary"));
+        assert!(report.contains("Backtrace:\n0: stik::main"));
+    }
+}
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+
+use super::config_paths;
+use super::versioning;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CursorPosition {
@@ -9,15 +11,8 @@ pub struct CursorPosition {
     pub anchor: usize,
 }
 
-fn get_cursor_positions_path() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    let stik_config = home.join(".stik");
-    fs::create_dir_all(&stik_config).map_err(|e| e.to_string())?;
-    Ok(stik_config.join("cursor_positions.json"))
-}
-
 fn load_positions() -> Result<HashMap<String, CursorPosition>, String> {
-    let path = get_cursor_positions_path()?;
+    let path = config_paths::config_file("cursor_positions.json")?;
     if !path.exists() {
         return Ok(HashMap::new());
     }
@@ -26,11 +21,8 @@ fn load_positions() -> Result<HashMap<String, CursorPosition>, String> {
 }
 
 fn write_positions(positions: &HashMap<String, CursorPosition>) -> Result<(), String> {
-    let path = get_cursor_positions_path()?;
-    let tmp = path.with_extension("json.tmp");
-    let json = serde_json::to_string(positions).map_err(|e| e.to_string())?;
-    fs::write(&tmp, json).map_err(|e| e.to_string())?;
-    fs::rename(&tmp, &path).map_err(|e| e.to_string())
+    let path = config_paths::writable_config_file("cursor_positions.json")?;
+    versioning::atomic_write_json(&path, positions)
 }
 
 #[tauri::command]
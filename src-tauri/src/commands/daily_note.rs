@@ -0,0 +1,122 @@
+/// Daily notes: one note per day, always in the same folder and named
+/// `YYYYMMDD-daily.md`, so it can be found again without a search.
+///
+/// The name skips `filename_template` and its UUID suffix on purpose: the
+/// same day always maps to the same file. It still starts with the
+/// original date scheme, so stats and "On this day" date it like any other
+/// note.
+use chrono::{Local, NaiveDate};
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::folders::{self, get_stik_folder};
+use super::notes::{self, NoteSaved, NOTE_SAVED_EVENT};
+use super::settings::{self, StikSettings};
+use super::{storage, templates};
+use crate::state::{AppState, LastSavedNote};
+
+const DAILY_NOTE_SUFFIX: &str = "-daily.md";
+const DAILY_DATE_FORMAT: &str = "%Y%m%d";
+
+pub struct DailyNote {
+    pub path: String,
+    pub folder: String,
+    pub content: String,
+}
+
+pub fn daily_note_filename(date: NaiveDate) -> String {
+    format!("{}{}", date.format(DAILY_DATE_FORMAT), DAILY_NOTE_SUFFIX)
+}
+
+/// An explicit folder wins, then `daily_note_folder`, then the default.
+fn resolve_folder(requested: Option<String>, settings: &StikSettings) -> String {
+    requested
+        .map(|folder| folder.trim().to_string())
+        .filter(|folder| !folder.is_empty())
+        .or_else(|| {
+            let configured = settings.daily_note_folder.trim();
+            (!configured.is_empty()).then(|| configured.to_string())
+        })
+        .unwrap_or_else(|| settings.default_folder.clone())
+}
+
+fn remember_last_saved(app: &AppHandle, path: &str, folder: &str) {
+    let state = app.state::<AppState>();
+    *state
+        .last_saved_note
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = Some(LastSavedNote {
+        path: path.to_string(),
+        folder: folder.to_string(),
+    });
+}
+
+/// Today's daily note, written from the daily template when it doesn't
+/// exist yet. Either way it becomes the last saved note.
+pub fn ensure_daily_note(app: &AppHandle, folder: Option<String>) -> Result<DailyNote, String> {
+    let settings = settings::get_settings()?;
+    let folder = resolve_folder(folder, &settings);
+    if !folder.is_empty() {
+        folders::validate_name(&folder)?;
+    }
+
+    let now = Local::now().naive_local();
+    let folder_path = get_stik_folder()?.join(&folder);
+    let filename = daily_note_filename(now.date());
+    let path = folder_path.join(&filename).to_string_lossy().to_string();
+
+    if storage::path_exists(&path) {
+        let content = notes::get_note_content_inner(&path)?;
+        remember_last_saved(app, &path, &folder);
+        return Ok(DailyNote {
+            path,
+            folder,
+            content,
+        });
+    }
+
+    storage::ensure_dir(&folder_path.to_string_lossy())?;
+    let content = templates::daily_note_body(&settings.custom_templates, &folder, now);
+    storage::write_file(&path, &content)?;
+    let saved = NoteSaved {
+        path: path.clone(),
+        folder: folder.clone(),
+        filename,
+    };
+    notes::post_save_processing(app, &saved, &content);
+    let _ = app.emit(NOTE_SAVED_EVENT, &saved);
+    log::info!("Created daily note {}", path);
+
+    Ok(DailyNote {
+        path,
+        folder,
+        content,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::stats;
+
+    #[test]
+    fn daily_names_are_fixed_and_still_dated() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 6).unwrap();
+        let name = daily_note_filename(date);
+        assert_eq!(name, "20260206-daily.md");
+        assert_eq!(stats::parse_date_from_filename(&name), Some(date));
+        assert_eq!(stats::parse_timestamp_from_filename(&name), None);
+    }
+
+    #[test]
+    fn folder_falls_back_to_the_setting_then_the_default() {
+        let mut settings = StikSettings {
+            default_folder: "Inbox".to_string(),
+            ..StikSettings::default()
+        };
+        assert_eq!(resolve_folder(None, &settings), "Inbox");
+
+        settings.daily_note_folder = "Journal".to_string();
+        assert_eq!(resolve_folder(Some("  ".to_string()), &settings), "Journal");
+        assert_eq!(resolve_folder(Some("Work".to_string()), &settings), "Work");
+    }
+}
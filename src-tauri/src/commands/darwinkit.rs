@@ -118,7 +118,7 @@ pub fn start_bridge(app: tauri::AppHandle) {
     let sidecar_path = match resolve_sidecar_path(&app) {
         Ok(p) => p,
         Err(e) => {
-            eprintln!("DarwinKit sidecar not available: {}", e);
+            log::warn!("DarwinKit sidecar not available: {}", e);
             return;
         }
     };
@@ -132,7 +132,7 @@ pub fn start_bridge(app: tauri::AppHandle) {
         .name("stik-darwinkit".to_string())
         .spawn(move || bridge_loop(sidecar_path, rx))
     {
-        eprintln!("Failed to start darwinkit bridge thread: {}", e);
+        log::error!("Failed to start darwinkit bridge thread: {}", e);
     }
 }
 
@@ -190,7 +190,7 @@ fn bridge_loop(sidecar_path: String, rx: Receiver<BridgeMessage>) {
                 let _ = child.wait();
             }
             Err(e) => {
-                eprintln!("Failed to spawn darwinkit sidecar: {}", e);
+                log::error!("Failed to spawn darwinkit sidecar: {}", e);
             }
         }
 
@@ -244,7 +244,7 @@ fn spawn_sidecar(path: &str) -> Result<(Child, ChildStdin, ChildStdout), String>
                 {
                     Ok(f) => f,
                     Err(e) => {
-                        eprintln!("Failed to open {}: {}", path, e);
+                        log::error!("Failed to open {}: {}", path, e);
                         return;
                     }
                 };
@@ -252,7 +252,7 @@ fn spawn_sidecar(path: &str) -> Result<(Child, ChildStdin, ChildStdout), String>
                 for line in reader.lines().map_while(Result::ok) {
                     let _ = writeln!(file, "{}", line);
                     let _ = file.flush();
-                    eprintln!("[sidecar-err] {}", line);
+                    log::debug!("DarwinKit sidecar: {}", line);
                 }
             })
             .ok();
@@ -293,7 +293,7 @@ fn run_session(mut stdin: ChildStdin, stdout: ChildStdout, rx: &Receiver<BridgeM
                 let response: JsonRpcResponse = match serde_json::from_str(&line) {
                     Ok(r) => r,
                     Err(e) => {
-                        eprintln!("darwinkit: invalid JSON response: {}", e);
+                        log::error!("Invalid DarwinKit JSON response: {}", e);
                         continue;
                     }
                 };
@@ -360,7 +360,7 @@ fn run_session(mut stdin: ChildStdin, stdout: ChildStdout, rx: &Receiver<BridgeM
         });
 
     if reader_handle.is_err() {
-        eprintln!("Failed to spawn darwinkit reader thread");
+        log::error!("Failed to spawn darwinkit reader thread");
         return;
     }
 
@@ -538,6 +538,20 @@ fn suggest_folder_inner(
     current_folder: &str,
     embeddings: &super::embeddings::EmbeddingIndex,
 ) -> Result<Option<String>, String> {
+    // Only suggest if score > 0.35 and different from current
+    match best_folder_match(content, embeddings)? {
+        Some((folder, score)) if score > 0.35 && folder != current_folder => Ok(Some(folder)),
+        _ => Ok(None),
+    }
+}
+
+/// Folder whose centroid is closest to `content`, with its cosine score.
+/// Returns None when AI is off, the sidecar is down, or there aren't
+/// enough folders to compare.
+pub(crate) fn best_folder_match(
+    content: &str,
+    embeddings: &super::embeddings::EmbeddingIndex,
+) -> Result<Option<(String, f64)>, String> {
     if !super::settings::load_settings_from_file().map(|s| s.ai_features_enabled).unwrap_or(false) {
         return Ok(None);
     }
@@ -605,9 +619,5 @@ fn suggest_folder_inner(
         }
     }
 
-    // Only suggest if score > 0.35 and different from current
-    match best_folder {
-        Some(folder) if best_score > 0.35 && folder != current_folder => Ok(Some(folder)),
-        _ => Ok(None),
-    }
+    Ok(best_folder.map(|folder| (folder, best_score)))
 }
@@ -0,0 +1,444 @@
+/// Import from a Day One JSON export (the `.zip`, or the folder it unzips
+/// to): one markdown note per entry, named after the entry's creation
+/// date, photos copied into `.assets/`, tags appended as `#hashtags`.
+///
+/// Journal files are streamed entry by entry, so multi-year exports never
+/// sit in memory whole. Entries that can't be converted are reported
+/// rather than failing the import.
+use chrono::{DateTime, Local};
+use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::folders::{self, get_stik_folder};
+use super::index::NoteIndex;
+use super::{git_share, notes, storage, tray_menu};
+
+const MOMENT_SCHEME: &str = "dayone-moment://";
+
+#[derive(Debug, Deserialize)]
+struct DayOneEntry {
+    #[serde(default)]
+    text: String,
+    #[serde(rename = "creationDate")]
+    creation_date: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    photos: Vec<DayOnePhoto>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DayOnePhoto {
+    identifier: String,
+    md5: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SkippedEntry {
+    pub journal: String,
+    /// Position in the journal's `entries` array.
+    pub position: usize,
+    pub uuid: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DayOneImportSummary {
+    pub imported: usize,
+    pub photos: usize,
+    pub skipped: Vec<SkippedEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct DayOneImportProgress {
+    imported: usize,
+    skipped: usize,
+    journal: String,
+}
+
+#[tauri::command]
+pub async fn import_day_one(
+    app: AppHandle,
+    export_zip_or_dir: String,
+    target_folder: String,
+) -> Result<DayOneImportSummary, String> {
+    folders::validate_name(&target_folder)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let target_dir = get_stik_folder()?.join(&target_folder);
+        let source = PathBuf::from(&export_zip_or_dir);
+
+        let (export_dir, extracted) = if source.is_dir() {
+            (source, None)
+        } else {
+            let dir = extract_zip(&source)?;
+            (dir.clone(), Some(dir))
+        };
+
+        let mut written = Vec::new();
+        let result = import_export_dir(&export_dir, &target_dir, &mut written, |progress| {
+            let _ = app.emit("day-one-import-progress", progress);
+        });
+        if let Some(dir) = extracted {
+            let _ = fs::remove_dir_all(dir);
+        }
+
+        // Index once at the end rather than per entry.
+        let index = app.state::<NoteIndex>();
+        for path in &written {
+            index.add(path, &target_folder);
+        }
+        if !written.is_empty() {
+            git_share::notify_note_changed(&target_folder);
+            super::widget_feed::notes_changed();
+            tray_menu::rebuild_tray_menu(&app);
+        }
+        result
+    })
+    .await
+    .map_err(|e| format!("Day One import failed: {}", e))?
+}
+
+/// Unzip with the system tools into a temp dir the caller removes.
+fn extract_zip(zip: &Path) -> Result<PathBuf, String> {
+    if !zip.is_file() {
+        return Err(format!("Day One export not found: {}", zip.display()));
+    }
+    let dir = std::env::temp_dir().join(format!("stik-dayone-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let status = if cfg!(target_os = "macos") {
+        Command::new("ditto")
+            .arg("-x")
+            .arg("-k")
+            .arg(zip)
+            .arg(&dir)
+            .status()
+    } else {
+        Command::new("unzip")
+            .arg("-q")
+            .arg(zip)
+            .arg("-d")
+            .arg(&dir)
+            .status()
+    };
+    match status {
+        Ok(status) if status.success() => Ok(dir),
+        Ok(_) | Err(_) => {
+            let _ = fs::remove_dir_all(&dir);
+            Err("Could not unzip the Day One export".to_string())
+        }
+    }
+}
+
+fn import_export_dir(
+    export_dir: &Path,
+    target_dir: &Path,
+    written: &mut Vec<String>,
+    mut on_progress: impl FnMut(DayOneImportProgress),
+) -> Result<DayOneImportSummary, String> {
+    let mut journals: Vec<PathBuf> = fs::read_dir(export_dir)
+        .map_err(|e| format!("Failed to read Day One export: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        })
+        .collect();
+    if journals.is_empty() {
+        return Err("No Day One journal (.json) found in the export".to_string());
+    }
+    journals.sort();
+
+    storage::ensure_dir(&target_dir.to_string_lossy())?;
+    let photos_dir = export_dir.join("photos");
+    let assets_dir = target_dir.join(".assets");
+    let mut summary = DayOneImportSummary::default();
+
+    for journal in journals {
+        let journal_name = journal
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let file = fs::File::open(&journal)
+            .map_err(|e| format!("Failed to open {}: {}", journal.display(), e))?;
+
+        let mut position = 0;
+        stream_entries(BufReader::new(file), |value| {
+            let uuid = value
+                .get("uuid")
+                .and_then(|uuid| uuid.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let result = serde_json::from_value::<DayOneEntry>(value)
+                .map_err(|e| format!("Unreadable entry: {}", e))
+                .and_then(|entry| write_entry(&entry, target_dir, &photos_dir, &assets_dir));
+            match result {
+                Ok((path, photos)) => {
+                    written.push(path);
+                    summary.imported += 1;
+                    summary.photos += photos;
+                }
+                Err(reason) => summary.skipped.push(SkippedEntry {
+                    journal: journal_name.clone(),
+                    position,
+                    uuid,
+                    reason,
+                }),
+            }
+            position += 1;
+            on_progress(DayOneImportProgress {
+                imported: summary.imported,
+                skipped: summary.skipped.len(),
+                journal: journal_name.clone(),
+            });
+        })
+        .map_err(|e| format!("Failed to parse {}: {}", journal.display(), e))?;
+    }
+
+    Ok(summary)
+}
+
+/// Write one entry as a note; returns its path and how many photos were
+/// copied.
+fn write_entry(
+    entry: &DayOneEntry,
+    target_dir: &Path,
+    photos_dir: &Path,
+    assets_dir: &Path,
+) -> Result<(String, usize), String> {
+    let created = entry
+        .creation_date
+        .as_deref()
+        .ok_or("Entry has no creationDate")?;
+    let created = DateTime::parse_from_rfc3339(created)
+        .map_err(|_| format!("Unrecognised creationDate '{}'", created))?
+        .with_timezone(&Local)
+        .naive_local();
+
+    let (mut content, photos) = link_photos(&entry.text, &entry.photos, photos_dir, assets_dir)?;
+    let tags = hashtags(&entry.tags);
+    if !tags.is_empty() {
+        content = format!("{}\n\n{}", content.trim_end(), tags);
+    }
+    if notes::is_effectively_empty_markdown(&content) {
+        return Err("Entry is empty".to_string());
+    }
+
+    let path = target_dir.join(notes::generate_filename_at(&content, created));
+    let path = path.to_string_lossy().to_string();
+    storage::write_file(&path, &content)?;
+    Ok((path, photos))
+}
+
+/// Copy the entry's photos into `.assets/` and point `dayone-moment://`
+/// links at the copies. Links to photos missing from the export are left
+/// as they are.
+fn link_photos(
+    text: &str,
+    photos: &[DayOnePhoto],
+    photos_dir: &Path,
+    assets_dir: &Path,
+) -> Result<(String, usize), String> {
+    let mut content = text.to_string();
+    let mut copied = 0;
+    for photo in photos {
+        let Some(md5) = photo.md5.as_deref() else {
+            continue;
+        };
+        let ext = photo.kind.as_deref().unwrap_or("jpeg");
+        let source = photos_dir.join(format!("{}.{}", md5, ext));
+        if !source.is_file() {
+            continue;
+        }
+
+        let asset_name = format!("dayone-{}.{}", md5, ext);
+        let asset = assets_dir.join(&asset_name);
+        if !asset.exists() {
+            storage::ensure_dir(&assets_dir.to_string_lossy())?;
+            let data = fs::read(&source).map_err(|e| format!("Failed to read photo: {}", e))?;
+            storage::write_bytes(&asset.to_string_lossy(), &data)?;
+        }
+        copied += 1;
+        content = content.replace(
+            &format!("{}{}", MOMENT_SCHEME, photo.identifier),
+            &format!(".assets/{}", asset_name),
+        );
+    }
+    Ok((content, copied))
+}
+
+fn hashtags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| tag.split_whitespace().collect::<Vec<_>>().join("-"))
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| format!("#{}", tag.trim_start_matches('#')))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Call `on_entry` for each element of the top-level `entries` array
+/// without materialising the array.
+fn stream_entries<R: std::io::Read>(
+    reader: R,
+    on_entry: impl FnMut(serde_json::Value),
+) -> Result<(), serde_json::Error> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    JournalSeed { on_entry }.deserialize(&mut deserializer)?;
+    deserializer.end()
+}
+
+struct JournalSeed<F> {
+    on_entry: F,
+}
+
+impl<'de, F: FnMut(serde_json::Value)> DeserializeSeed<'de> for JournalSeed<F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F: FnMut(serde_json::Value)> Visitor<'de> for JournalSeed<F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a Day One journal object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "entries" {
+                map.next_value_seed(EntriesSeed {
+                    on_entry: &mut self.on_entry,
+                })?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct EntriesSeed<'a, F> {
+    on_entry: &'a mut F,
+}
+
+impl<'de, F: FnMut(serde_json::Value)> DeserializeSeed<'de> for EntriesSeed<'_, F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F: FnMut(serde_json::Value)> Visitor<'de> for EntriesSeed<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("an array of Day One entries")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(entry) = seq.next_element::<serde_json::Value>()? {
+            (self.on_entry)(entry);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::temp_dir;
+
+    const JOURNAL: &str = r#"{
+        "metadata": { "version": "1.0" },
+        "entries": [
+            {
+                "uuid": "A1",
+                "creationDate": "2018-06-01T08:30:00Z",
+                "text": "Morning run\n\n![](dayone-moment://P1)",
+                "tags": ["running", "good days"],
+                "photos": [{ "identifier": "P1", "md5": "abc123", "type": "jpeg" }]
+            },
+            { "uuid": "B2", "text": "No date here" },
+            { "uuid": "C3", "creationDate": "2019-01-02T10:00:00Z", "text": "Second" }
+        ]
+    }"#;
+
+    #[test]
+    fn streams_entries_and_skips_other_keys() {
+        let mut uuids = Vec::new();
+        stream_entries(JOURNAL.as_bytes(), |entry| {
+            uuids.push(entry["uuid"].as_str().unwrap_or_default().to_string())
+        })
+        .expect("parses");
+        assert_eq!(uuids, vec!["A1", "B2", "C3"]);
+    }
+
+    #[test]
+    fn tags_become_hashtags() {
+        assert_eq!(
+            hashtags(&[
+                "running".to_string(),
+                "good days".to_string(),
+                "#x".to_string()
+            ]),
+            "#running #good-days #x"
+        );
+    }
+
+    #[test]
+    fn imports_entries_with_photos_and_reports_failures() {
+        let export = temp_dir("dayone-export");
+        let target = temp_dir("dayone-target").join("Journal");
+        fs::create_dir_all(export.join("photos")).unwrap();
+        fs::write(export.join("photos/abc123.jpeg"), [0xffu8, 0xd8]).unwrap();
+        fs::write(export.join("Journal.json"), JOURNAL).unwrap();
+
+        let mut written = Vec::new();
+        let mut progress = 0;
+        let summary =
+            import_export_dir(&export, &target, &mut written, |_| progress += 1).expect("import");
+
+        assert_eq!(summary.imported, 2);
+        assert_eq!(summary.photos, 1);
+        assert_eq!(progress, 3);
+        assert_eq!(summary.skipped.len(), 1);
+        assert_eq!(summary.skipped[0].uuid, "B2");
+        assert_eq!(summary.skipped[0].position, 1);
+
+        let expected_stamp = DateTime::parse_from_rfc3339("2018-06-01T08:30:00Z")
+            .unwrap()
+            .with_timezone(&Local)
+            .format("%Y%m%d-%H%M%S")
+            .to_string();
+        let first = written
+            .iter()
+            .find(|path| {
+                Path::new(path)
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .starts_with(&expected_stamp)
+            })
+            .expect("note named after creationDate");
+        let content = fs::read_to_string(first).unwrap();
+        assert!(content.contains("![](.assets/dayone-abc123.jpeg)"));
+        assert!(content.ends_with("#running #good-days"));
+        assert!(target.join(".assets/dayone-abc123.jpeg").is_file());
+
+        let _ = fs::remove_dir_all(export);
+        let _ = fs::remove_dir_all(target.parent().unwrap());
+    }
+}
@@ -66,7 +66,7 @@ pub fn handle_notification(method: &str, params: &serde_json::Value) -> bool {
     if let Some(app) = APP_HANDLE.get() {
         let _ = app.emit(event_name, params.clone());
     } else {
-        eprintln!("[dictation-rs] APP_HANDLE not set when firing {}", method);
+        log::error!("APP_HANDLE not set when firing {}", method);
     }
 
     true
@@ -0,0 +1,349 @@
+/// Daily digest — an on-device LLM summary of the previous day's captures,
+/// saved as a regular note so it shows up in search and the manager.
+///
+/// The digest file name is derived from the date alone, so regenerating a
+/// day overwrites the existing digest instead of adding a duplicate. The
+/// `digest-` prefix also keeps digests out of the filename-based capture
+/// streak.
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use std::thread;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::folders::{get_stik_folder, validate_name};
+use super::index::{NoteEntry, NoteIndex};
+use super::settings::{self, DailyDigestSettings, QuietHoursSettings};
+use super::{ai_assistant, config_paths, darwinkit, git_share, stats, storage, versioning};
+
+const MAX_DIGEST_NOTES: usize = 50;
+const DIGEST_FILENAME_PREFIX: &str = "digest-";
+const DIGEST_PREVIEW_CHARS: usize = 200;
+const SCHEDULER_TICK_SECONDS: u64 = 60;
+const DIGEST_INSTRUCTIONS: &str = "You summarize a person's own notes into a short digest. \
+     Use markdown bullets, one per idea. Never invent content that is not in the notes.";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyDigestResult {
+    pub date: String,
+    pub note_count: usize,
+    /// None when the day had no captures and nothing was written.
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct DailyDigestState {
+    last_run_date: Option<String>,
+}
+
+static SCHEDULER_RUNNING: OnceLock<()> = OnceLock::new();
+
+#[tauri::command]
+pub async fn generate_daily_digest(
+    app: AppHandle,
+    date: Option<String>,
+) -> Result<DailyDigestResult, String> {
+    let date = match date {
+        Some(value) => NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .map_err(|e| format!("Invalid digest date '{}': {}", value, e))?,
+        None => Local::now().date_naive() - Duration::days(1),
+    };
+
+    tauri::async_runtime::spawn_blocking(move || generate_daily_digest_inner(&app, date))
+        .await
+        .map_err(|e| format!("Daily digest failed: {}", e))?
+}
+
+pub fn generate_daily_digest_inner(
+    app: &AppHandle,
+    date: NaiveDate,
+) -> Result<DailyDigestResult, String> {
+    let settings = settings::load_settings_from_file()?;
+    let digest_folder = settings.daily_digest.folder.trim().to_string();
+    validate_name(&digest_folder)?;
+
+    let index = app.state::<NoteIndex>();
+    let entries = collect_digest_entries(
+        index.list(None)?,
+        date,
+        &digest_folder,
+        &settings.ai_excluded_folders,
+    );
+
+    let date_label = date.format("%Y-%m-%d").to_string();
+    if entries.is_empty() {
+        return Ok(DailyDigestResult {
+            date: date_label,
+            note_count: 0,
+            path: None,
+        });
+    }
+
+    ai_assistant::check_ai_enabled()?;
+
+    let result = darwinkit::call_with_timeout(
+        "llm.generate",
+        Some(serde_json::json!({
+            "prompt": build_digest_prompt(date, &entries),
+            "systemInstructions": DIGEST_INSTRUCTIONS,
+        })),
+        60,
+    )?;
+    let summary = result
+        .get("text")
+        .and_then(|v| v.as_str())
+        .ok_or("Invalid response from LLM")?;
+
+    let folder_path = get_stik_folder()?.join(&digest_folder);
+    storage::ensure_dir(&folder_path.to_string_lossy())?;
+
+    let file_path = folder_path.join(digest_filename(date));
+    let path_str = file_path.to_string_lossy().to_string();
+    storage::write_file(&path_str, &build_digest_note(date, summary))?;
+
+    index.add(&path_str, &digest_folder);
+    git_share::notify_note_changed(&digest_folder);
+    let _ = app.emit("files-changed", vec![path_str.clone()]);
+
+    Ok(DailyDigestResult {
+        date: date_label,
+        note_count: entries.len(),
+        path: Some(path_str),
+    })
+}
+
+/// Start the once-a-minute scheduler that generates yesterday's digest
+/// after the configured time of day. Call once during app setup.
+pub fn start_scheduler(app: AppHandle) {
+    if SCHEDULER_RUNNING.set(()).is_err() {
+        return;
+    }
+
+    thread::Builder::new()
+        .name("stik-daily-digest".to_string())
+        .spawn(move || loop {
+            // Sleep first so the DarwinKit sidecar has come up before the
+            // first attempt on launch.
+            thread::sleep(std::time::Duration::from_secs(SCHEDULER_TICK_SECONDS));
+            run_scheduled_digest(&app);
+        })
+        .ok();
+}
+
+fn run_scheduled_digest(app: &AppHandle) {
+    let Ok(settings) = settings::load_settings_from_file() else {
+        return;
+    };
+    let state = load_state().unwrap_or_default();
+    let now = Local::now().naive_local();
+
+    if !is_digest_due(
+        &settings.daily_digest,
+        &settings.quiet_hours,
+        state.last_run_date.as_deref(),
+        now,
+    ) {
+        return;
+    }
+
+    // Record the attempt whether or not it succeeds, so an unavailable
+    // model doesn't get hammered every minute for the rest of the day.
+    let new_state = DailyDigestState {
+        last_run_date: Some(now.date().format("%Y-%m-%d").to_string()),
+    };
+    if let Err(e) = save_state(&new_state) {
+        log::error!("Failed to save daily digest state: {}", e);
+    }
+
+    let yesterday = now.date() - Duration::days(1);
+    match generate_daily_digest_inner(app, yesterday) {
+        Ok(result) if result.path.is_none() => {
+            log::info!("Daily digest skipped: no captures on {}", result.date);
+        }
+        Ok(_) => {}
+        Err(e) => log::error!("Failed to generate daily digest: {}", e),
+    }
+}
+
+fn is_digest_due(
+    digest: &DailyDigestSettings,
+    quiet_hours: &QuietHoursSettings,
+    last_run_date: Option<&str>,
+    now: NaiveDateTime,
+) -> bool {
+    if !digest.enabled {
+        return false;
+    }
+    let Some(scheduled_at) = settings::parse_time_of_day(&digest.time_of_day) else {
+        return false;
+    };
+    let today = now.date().format("%Y-%m-%d").to_string();
+    if last_run_date == Some(today.as_str()) {
+        return false;
+    }
+    now.time() >= scheduled_at && !quiet_hours.contains(now.time())
+}
+
+fn note_capture_date(entry: &NoteEntry) -> Option<NaiveDate> {
+    stats::parse_date_from_filename(&entry.filename)
+        .or_else(|| stats::parse_date_from_filename(&entry.created))
+}
+
+fn collect_digest_entries(
+    entries: Vec<NoteEntry>,
+    date: NaiveDate,
+    digest_folder: &str,
+    excluded_folders: &[String],
+) -> Vec<NoteEntry> {
+    let mut picked: Vec<NoteEntry> = entries
+        .into_iter()
+        .filter(|e| !e.locked)
+        .filter(|e| e.folder != digest_folder && !excluded_folders.contains(&e.folder))
+        .filter(|e| note_capture_date(e) == Some(date))
+        .collect();
+
+    picked.sort_by(|a, b| a.filename.cmp(&b.filename));
+    picked.truncate(MAX_DIGEST_NOTES);
+    picked
+}
+
+fn build_digest_prompt(date: NaiveDate, entries: &[NoteEntry]) -> String {
+    let mut prompt = format!(
+        "Summarize the notes I captured on {} as a short bullet list.\n\nNotes:\n",
+        date.format("%A, %B %-d, %Y")
+    );
+    for entry in entries {
+        let folder = if entry.folder.is_empty() {
+            "Root"
+        } else {
+            entry.folder.as_str()
+        };
+        let preview: String = entry
+            .preview
+            .replace('\n', " ")
+            .chars()
+            .take(DIGEST_PREVIEW_CHARS)
+            .collect();
+        prompt.push_str(&format!("- [{}] {}: {}\n", folder, entry.title, preview));
+    }
+    prompt
+}
+
+fn build_digest_note(date: NaiveDate, summary: &str) -> String {
+    format!(
+        "# Daily digest — {}\n\n{}\n",
+        date.format("%b %d, %Y"),
+        summary.trim()
+    )
+}
+
+fn digest_filename(date: NaiveDate) -> String {
+    format!("{}{}.md", DIGEST_FILENAME_PREFIX, date.format("%Y-%m-%d"))
+}
+
+/// Digests are generated, not captured, so stats leave them out.
+pub(crate) fn is_digest_filename(filename: &str) -> bool {
+    filename.starts_with(DIGEST_FILENAME_PREFIX)
+}
+
+fn load_state() -> Result<DailyDigestState, String> {
+    let path = config_paths::config_file("daily_digest.json")?;
+    match versioning::load_versioned::<DailyDigestState>(&path)? {
+        Some(state) => Ok(state),
+        None => Ok(DailyDigestState::default()),
+    }
+}
+
+fn save_state(state: &DailyDigestState) -> Result<(), String> {
+    let path = config_paths::writable_config_file("daily_digest.json")?;
+    versioning::save_versioned(&path, state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(filename: &str, folder: &str) -> NoteEntry {
+        NoteEntry {
+            path: format!("/tmp/Stik/{}/{}", folder, filename),
+            filename: filename.to_string(),
+            folder: folder.to_string(),
+            title: "Title".to_string(),
+            preview: "Preview".to_string(),
+            created: "20990101-000000".to_string(),
+            content_len: 7,
+            locked: false,
+            word_count: 0,
+            reading_minutes: 0,
+            language: "en".to_string(),
+            truncated_in_index: false,
+            id: None,
+            pinned: false,
+            created_date: None,
+            open_tasks: 0,
+            done_tasks: 0,
+        }
+    }
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M")
+            .expect("valid datetime")
+    }
+
+    fn enabled_digest() -> DailyDigestSettings {
+        DailyDigestSettings {
+            enabled: true,
+            ..DailyDigestSettings::default()
+        }
+    }
+
+    #[test]
+    fn digest_filename_is_deterministic_and_not_a_capture() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 4).expect("valid date");
+        assert_eq!(digest_filename(date), "digest-2026-03-04.md");
+        assert_eq!(stats::parse_date_from_filename(&digest_filename(date)), None);
+    }
+
+    #[test]
+    fn collects_only_that_days_captures_outside_excluded_folders() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 4).expect("valid date");
+        let entries = vec![
+            entry("20260304-090000-standup-ab12.md", "Work"),
+            entry("20260303-090000-older-cd34.md", "Work"),
+            entry("20260304-100000-journal-ef56.md", "Private"),
+            entry("digest-2026-03-04.md", "Digests"),
+        ];
+
+        let picked = collect_digest_entries(entries, date, "Digests", &["Private".to_string()]);
+        assert_eq!(picked.len(), 1);
+        assert_eq!(picked[0].filename, "20260304-090000-standup-ab12.md");
+    }
+
+    #[test]
+    fn digest_is_due_once_after_scheduled_time() {
+        let digest = enabled_digest();
+        let quiet = QuietHoursSettings::default();
+
+        assert!(!is_digest_due(&digest, &quiet, None, at("2026-03-05", "07:59")));
+        assert!(is_digest_due(&digest, &quiet, None, at("2026-03-05", "08:00")));
+        assert!(!is_digest_due(
+            &digest,
+            &quiet,
+            Some("2026-03-05"),
+            at("2026-03-05", "09:00")
+        ));
+    }
+
+    #[test]
+    fn digest_waits_out_quiet_hours() {
+        let digest = enabled_digest();
+        let quiet = QuietHoursSettings {
+            enabled: true,
+            start: "07:00".to_string(),
+            end: "09:00".to_string(),
+        };
+
+        assert!(!is_digest_due(&digest, &quiet, None, at("2026-03-05", "08:30")));
+        assert!(is_digest_due(&digest, &quiet, None, at("2026-03-05", "09:00")));
+    }
+}
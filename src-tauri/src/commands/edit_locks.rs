@@ -0,0 +1,155 @@
+// Edit locks: reference notes marked read-only so they aren't changed or
+// deleted by accident. Unrelated to `note_lock`, which encrypts a note.
+//
+// Locked notes are listed in `~/.stik/edit_locks.json` by their path
+// relative to the Stik folder. Moving a locked note between folders is
+// still allowed and keeps the lock; updating, formatting and deleting it
+// fail with `NOTE_LOCKED` until it is unlocked.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::config_paths;
+use super::folders::get_stik_folder;
+use super::versioning;
+
+/// Error returned when a locked note would be changed or deleted.
+pub const NOTE_LOCKED: &str = "NOTE_LOCKED";
+
+const EDIT_LOCKS_FILE: &str = "edit_locks.json";
+
+/// A note's path relative to the Stik folder, with `/` separators.
+fn lock_key(path: &str, stik_root: &Path) -> Option<String> {
+    let relative = Path::new(path).strip_prefix(stik_root).ok()?;
+    let key = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    (!key.is_empty()).then_some(key)
+}
+
+fn load_locks() -> Result<BTreeSet<String>, String> {
+    let path = config_paths::config_file(EDIT_LOCKS_FILE)?;
+    if !path.exists() {
+        return Ok(BTreeSet::new());
+    }
+    let data = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&data).map_err(|e| e.to_string())
+}
+
+fn write_locks(locks: &BTreeSet<String>) -> Result<(), String> {
+    let path = config_paths::writable_config_file(EDIT_LOCKS_FILE)?;
+    versioning::atomic_write_json(&path, locks)
+}
+
+/// Every locked note, for marking a whole listing at once.
+pub struct EditLocks {
+    root: Option<PathBuf>,
+    keys: BTreeSet<String>,
+}
+
+impl EditLocks {
+    pub fn load() -> Self {
+        Self {
+            root: get_stik_folder().ok(),
+            keys: load_locks().unwrap_or_default(),
+        }
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.root
+            .as_deref()
+            .and_then(|root| lock_key(path, root))
+            .is_some_and(|key| self.keys.contains(&key))
+    }
+}
+
+pub fn is_edit_locked(path: &str) -> bool {
+    EditLocks::load().contains(path)
+}
+
+/// `Err(NOTE_LOCKED)` when the note must not be changed or deleted.
+pub fn ensure_editable(path: &str) -> Result<(), String> {
+    if is_edit_locked(path) {
+        return Err(NOTE_LOCKED.to_string());
+    }
+    Ok(())
+}
+
+/// Carry a lock over to a note's new location.
+pub fn note_moved(old_path: &str, new_path: &str) {
+    let Ok(root) = get_stik_folder() else {
+        return;
+    };
+    let (Some(old_key), Some(new_key)) = (lock_key(old_path, &root), lock_key(new_path, &root))
+    else {
+        return;
+    };
+    let Ok(mut locks) = load_locks() else {
+        return;
+    };
+    if locks.remove(&old_key) {
+        locks.insert(new_key);
+        if let Err(e) = write_locks(&locks) {
+            log::error!("Failed to move edit lock: {}", e);
+        }
+    }
+}
+
+fn set_locked(path: &str, locked: bool) -> Result<bool, String> {
+    let root = get_stik_folder()?;
+    let key = lock_key(path, &root).ok_or("Invalid path: note must be within Stik folder")?;
+    if !super::storage::path_exists(path) {
+        return Err("Note file does not exist".to_string());
+    }
+    let mut locks = load_locks()?;
+    let changed = if locked {
+        locks.insert(key)
+    } else {
+        locks.remove(&key)
+    };
+    if changed {
+        write_locks(&locks)?;
+    }
+    Ok(locked)
+}
+
+/// Make a note read-only. Returns the new lock state.
+#[tauri::command]
+pub fn lock_note_edits(path: String) -> Result<bool, String> {
+    set_locked(&path, true)
+}
+
+#[tauri::command]
+pub fn unlock_note_edits(path: String) -> Result<bool, String> {
+    set_locked(&path, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locks_are_keyed_relative_to_the_stik_folder() {
+        let root = PathBuf::from("/Users/me/Documents/Stik");
+        assert_eq!(
+            lock_key("/Users/me/Documents/Stik/Work/runbook.md", &root),
+            Some("Work/runbook.md".to_string())
+        );
+        assert_eq!(lock_key("/Users/me/Desktop/runbook.md", &root), None);
+        assert_eq!(lock_key("/Users/me/Documents/Stik", &root), None);
+    }
+
+    #[test]
+    fn listing_checks_paths_against_loaded_keys() {
+        let locks = EditLocks {
+            root: Some(PathBuf::from("/Stik")),
+            keys: BTreeSet::from(["Work/runbook.md".to_string()]),
+        };
+        assert!(locks.contains("/Stik/Work/runbook.md"));
+        assert!(!locks.contains("/Stik/Inbox/runbook.md"));
+        assert!(!locks.contains("/Elsewhere/Work/runbook.md"));
+    }
+}
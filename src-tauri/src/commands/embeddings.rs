@@ -1,16 +1,23 @@
 /// Embedding index — persists note embeddings to disk, provides cosine
 /// similarity search and per-folder centroids for folder suggestion.
 ///
-/// Storage: `~/.stik/embeddings.json` (~4KB per note, 512 floats each).
+/// Storage: `embeddings.json` in the config dir (~4KB per note, 512 floats each).
 /// Uses content hashing to skip re-embedding unchanged notes.
+///
+/// A file that fails to parse (e.g. cut off by a crash) is moved aside to
+/// `embeddings.json.corrupt` and rebuilt in the background; entries whose
+/// vector length doesn't match the rest of their language are dropped and
+/// re-embedded the same way.
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::fs;
 use std::hash::{Hash, Hasher};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tauri::{AppHandle, Emitter, Manager, State};
 
-use super::darwinkit;
+use super::{config_paths, darwinkit, versioning};
 
 // ── Types ──────────────────────────────────────────────────────────
 
@@ -24,15 +31,63 @@ pub struct NoteEmbedding {
 pub struct EmbeddingIndex {
     entries: Mutex<HashMap<String, NoteEmbedding>>,
     loaded: Mutex<bool>,
+    active_builds: AtomicUsize,
+    recovery: Mutex<Option<LoadRecovery>>,
+}
+
+/// What `ensure_loaded` had to repair, for the health report.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LoadRecovery {
+    pub corrupt_file_moved: bool,
+    pub dropped_entries: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingIndexHealth {
+    pub entries: usize,
+    pub file_size_bytes: u64,
+    /// RFC 3339 modification time of `embeddings.json`.
+    pub last_saved: Option<String>,
+    pub rebuild_in_progress: bool,
+    pub recovery: Option<LoadRecovery>,
+}
+
+pub const RECOVERED_EVENT: &str = "embedding-index-recovered";
+const EMBEDDINGS_FILE: &str = "embeddings.json";
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// Call once from main.rs setup so a repaired load can schedule a rebuild.
+pub fn register_app(app: &AppHandle) {
+    let _ = APP_HANDLE.set(app.clone());
 }
 
 // ── Persistence ────────────────────────────────────────────────────
 
-fn embeddings_path() -> Result<std::path::PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Cannot determine home directory")?;
-    let config_dir = home.join(".stik");
-    fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
-    Ok(config_dir.join("embeddings.json"))
+/// Drop entries with an empty vector or a length different from the most
+/// common one for their language. Returns how many were dropped.
+fn drop_mismatched_vectors(entries: &mut HashMap<String, NoteEmbedding>) -> usize {
+    let mut lengths: HashMap<&str, HashMap<usize, usize>> = HashMap::new();
+    for emb in entries.values().filter(|emb| !emb.vector.is_empty()) {
+        *lengths
+            .entry(emb.language.as_str())
+            .or_default()
+            .entry(emb.vector.len())
+            .or_default() += 1;
+    }
+    let expected: HashMap<String, usize> = lengths
+        .into_iter()
+        .filter_map(|(language, counts)| {
+            let (len, _) = counts
+                .into_iter()
+                .max_by_key(|&(len, count)| (count, len))?;
+            Some((language.to_string(), len))
+        })
+        .collect();
+
+    let before = entries.len();
+    entries.retain(|_, emb| expected.get(&emb.language) == Some(&emb.vector.len()));
+    before - entries.len()
 }
 
 fn content_hash(content: &str) -> String {
@@ -73,6 +128,8 @@ impl EmbeddingIndex {
         Self {
             entries: Mutex::new(HashMap::new()),
             loaded: Mutex::new(false),
+            active_builds: AtomicUsize::new(0),
+            recovery: Mutex::new(None),
         }
     }
 
@@ -85,7 +142,7 @@ impl EmbeddingIndex {
         *loaded = true;
         drop(loaded);
 
-        let path = match embeddings_path() {
+        let path = match config_paths::config_file(EMBEDDINGS_FILE) {
             Ok(p) => p,
             Err(_) => return,
         };
@@ -94,32 +151,85 @@ impl EmbeddingIndex {
             return;
         }
 
-        let data = match fs::read_to_string(&path) {
+        let data = match fs::read(&path) {
             Ok(d) => d,
-            Err(_) => return,
+            Err(e) => {
+                log::error!("Failed to read {}: {}", path.display(), e);
+                return;
+            }
         };
 
-        let map: HashMap<String, NoteEmbedding> = match serde_json::from_str(&data) {
+        let mut recovery = LoadRecovery::default();
+        let mut map: HashMap<String, NoteEmbedding> = match serde_json::from_slice(&data) {
             Ok(m) => m,
-            Err(_) => return,
+            Err(e) => {
+                let corrupt = path.with_extension("json.corrupt");
+                log::warn!(
+                    "{} is unreadable ({}); moved to {} and rebuilding",
+                    path.display(),
+                    e,
+                    corrupt.display()
+                );
+                if let Err(e) = fs::rename(&path, &corrupt) {
+                    log::error!("Failed to move corrupt file aside: {}", e);
+                }
+                recovery.corrupt_file_moved = true;
+                HashMap::new()
+            }
         };
 
-        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
-        *entries = map;
+        recovery.dropped_entries = drop_mismatched_vectors(&mut map);
+        if recovery.dropped_entries > 0 {
+            log::warn!(
+                "dropped {} embeddings with the wrong vector length",
+                recovery.dropped_entries
+            );
+        }
+
+        *self.entries.lock().unwrap_or_else(|e| e.into_inner()) = map;
+
+        if recovery != LoadRecovery::default() {
+            *self.recovery.lock().unwrap_or_else(|e| e.into_inner()) = Some(recovery.clone());
+            self.schedule_rebuild(recovery);
+        }
     }
 
-    /// Atomic write to disk (tmp + rename).
+    /// Re-embed after a repaired load, unless a build is already running
+    /// (it started with the repaired entries and fills the gaps itself).
+    fn schedule_rebuild(&self, recovery: LoadRecovery) {
+        let Some(app) = APP_HANDLE.get() else {
+            return;
+        };
+        let _ = app.emit(RECOVERED_EVENT, &recovery);
+        if self.is_rebuilding() {
+            return;
+        }
+
+        let handle = app.clone();
+        std::thread::Builder::new()
+            .name("stik-embeddings-recovery".to_string())
+            .spawn(move || {
+                build_embeddings(
+                    &handle.state::<super::index::NoteIndex>(),
+                    &handle.state::<EmbeddingIndex>(),
+                );
+            })
+            .ok();
+    }
+
+    pub fn is_rebuilding(&self) -> bool {
+        self.active_builds.load(Ordering::SeqCst) > 0
+    }
+
+    /// Atomic write to disk. Compact JSON: the file holds every vector.
     pub fn save(&self) -> Result<(), String> {
-        let path = embeddings_path()?;
-        let tmp = path.with_extension("json.tmp");
+        let path = config_paths::writable_config_file(EMBEDDINGS_FILE)?;
 
         let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
-        let json = serde_json::to_string(&*entries).map_err(|e| e.to_string())?;
+        let json = serde_json::to_vec(&*entries).map_err(|e| e.to_string())?;
         drop(entries);
 
-        fs::write(&tmp, json).map_err(|e| e.to_string())?;
-        fs::rename(&tmp, &path).map_err(|e| e.to_string())?;
-        Ok(())
+        versioning::atomic_write(&path, &json)
     }
 
     /// Add or update an embedding for a note path.
@@ -134,6 +244,13 @@ impl EmbeddingIndex {
         entries.remove(path);
     }
 
+    pub fn remove_entries(&self, paths: &[String]) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        for path in paths {
+            entries.remove(path);
+        }
+    }
+
     /// Remove all embeddings whose path starts with `prefix`.
     pub fn remove_by_path_prefix(&self, prefix: &str) {
         let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
@@ -154,6 +271,12 @@ impl EmbeddingIndex {
         entries.get(path).map(|e| e.content_hash.clone())
     }
 
+    /// The language detected when a note was embedded.
+    pub fn get_language(&self, path: &str) -> Option<String> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.get(path).map(|e| e.language.clone())
+    }
+
     /// Find the k nearest notes to a query vector. Only compares embeddings
     /// in the same language since Apple NLEmbedding uses different vector
     /// spaces (and dimensions) per language.
@@ -264,12 +387,18 @@ pub fn embed_content(content: &str) -> Option<NoteEmbedding> {
 /// Build embeddings for all notes in the NoteIndex that are missing or stale.
 /// Called as a background task during app setup.
 pub fn build_embeddings(index: &super::index::NoteIndex, embeddings: &EmbeddingIndex) {
+    embeddings.active_builds.fetch_add(1, Ordering::SeqCst);
+    build_missing_embeddings(index, embeddings);
+    embeddings.active_builds.fetch_sub(1, Ordering::SeqCst);
+}
+
+fn build_missing_embeddings(index: &super::index::NoteIndex, embeddings: &EmbeddingIndex) {
     embeddings.ensure_loaded();
 
     let entries = match index.list(None) {
         Ok(e) => e,
         Err(e) => {
-            eprintln!("Failed to list notes for embedding build: {}", e);
+            log::error!("Failed to list notes for embedding build: {}", e);
             return;
         }
     };
@@ -283,7 +412,7 @@ pub fn build_embeddings(index: &super::index::NoteIndex, embeddings: &EmbeddingI
     }
 
     if !darwinkit::is_available() {
-        eprintln!("DarwinKit not available, skipping embedding build");
+        log::warn!("DarwinKit not available, skipping embedding build");
         return;
     }
 
@@ -306,15 +435,19 @@ pub fn build_embeddings(index: &super::index::NoteIndex, embeddings: &EmbeddingI
 
         let hash = content_hash(&content);
 
-        // Skip if hash matches existing embedding
+        // Skip if hash matches existing embedding, backfilling its language
         if let Some(existing_hash) = embeddings.get_hash(&entry.path) {
             if existing_hash == hash {
+                if let Some(language) = embeddings.get_language(&entry.path) {
+                    index.set_language(&entry.path, &language);
+                }
                 continue;
             }
         }
 
         // Embed
         if let Some(embedding) = embed_content(&content) {
+            index.set_language(&entry.path, &embedding.language);
             embeddings.add_entry(&entry.path, embedding);
             embedded += 1;
         }
@@ -324,7 +457,7 @@ pub fn build_embeddings(index: &super::index::NoteIndex, embeddings: &EmbeddingI
         // Save every 50 notes
         if processed % 50 == 0 {
             if let Err(e) = embeddings.save() {
-                eprintln!("Failed to save embeddings (batch): {}", e);
+                log::error!("Failed to save embeddings (batch): {}", e);
             }
         }
     }
@@ -332,13 +465,68 @@ pub fn build_embeddings(index: &super::index::NoteIndex, embeddings: &EmbeddingI
     // Final save
     if embedded > 0 {
         if let Err(e) = embeddings.save() {
-            eprintln!("Failed to save embeddings (final): {}", e);
+            log::error!("Failed to save embeddings (final): {}", e);
         }
     }
 
-    eprintln!(
+    log::info!(
         "Embedding build complete: {} embedded, {} total stored",
         embedded,
         embeddings.len()
     );
 }
+
+#[tauri::command]
+pub fn embedding_index_health(
+    embeddings: State<'_, EmbeddingIndex>,
+) -> Result<EmbeddingIndexHealth, String> {
+    embeddings.ensure_loaded();
+    let metadata = config_paths::config_file(EMBEDDINGS_FILE)
+        .ok()
+        .and_then(|path| fs::metadata(path).ok());
+    let last_saved = metadata
+        .as_ref()
+        .and_then(|meta| meta.modified().ok())
+        .map(|time| chrono::DateTime::<chrono::Local>::from(time).to_rfc3339());
+
+    Ok(EmbeddingIndexHealth {
+        entries: embeddings.len(),
+        file_size_bytes: metadata.map(|meta| meta.len()).unwrap_or(0),
+        last_saved,
+        rebuild_in_progress: embeddings.is_rebuilding(),
+        recovery: embeddings
+            .recovery
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn embedding(len: usize, language: &str) -> NoteEmbedding {
+        NoteEmbedding {
+            vector: vec![0.5; len],
+            content_hash: String::new(),
+            language: language.to_string(),
+        }
+    }
+
+    #[test]
+    fn drops_vectors_with_the_wrong_length_for_their_language() {
+        let mut entries = HashMap::from([
+            ("a".to_string(), embedding(512, "en")),
+            ("b".to_string(), embedding(512, "en")),
+            ("c".to_string(), embedding(100, "en")),
+            ("d".to_string(), embedding(0, "en")),
+            ("e".to_string(), embedding(300, "fr")),
+        ]);
+
+        assert_eq!(drop_mismatched_vectors(&mut entries), 2);
+        let mut kept: Vec<&str> = entries.keys().map(String::as_str).collect();
+        kept.sort();
+        assert_eq!(kept, vec!["a", "b", "e"]);
+    }
+}
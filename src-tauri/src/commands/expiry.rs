@@ -0,0 +1,318 @@
+/// Self-expiring notes — an `expires:` frontmatter key (`2026-03-05` or
+/// `2026-03-05T18:00`) marks a note for cleanup once that moment passes.
+///
+/// Once a day a maintenance pass moves expired notes into `.trash` at the
+/// Stik root, where they're kept like any other trashed note, drops them
+/// from the index and emits `notes-expired` listing what was moved so it
+/// can be rescued. The key lives in the note itself, so it syncs with it;
+/// with git sharing on the pass pulls first, and a note another machine
+/// already expired has simply disappeared by then.
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+use std::thread;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use super::edit_locks::EditLocks;
+use super::embeddings::EmbeddingIndex;
+use super::folders::get_stik_folder;
+use super::index::NoteIndex;
+use super::{
+    config_paths, git_share, note_lock, notes, spotlight, storage, tray_menu, versioning,
+    widget_feed,
+};
+
+const EXPIRES_KEY: &str = "expires";
+pub(crate) const TRASH_FOLDER: &str = ".trash";
+/// Enough to cover any realistic frontmatter block.
+const FRONTMATTER_READ_BYTES: usize = 4096;
+const SCHEDULER_TICK_SECONDS: u64 = 600;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExpiredNote {
+    pub path: String,
+    pub folder: String,
+    /// Where the note sits in `.trash` now.
+    pub trashed_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct ExpiryState {
+    last_run_date: Option<String>,
+}
+
+static SCHEDULER_RUNNING: OnceLock<()> = OnceLock::new();
+
+/// The fields between the `---` fences and the body after them, if the
+/// note starts with a frontmatter block.
+pub(crate) fn split_frontmatter(content: &str) -> Option<(Vec<&str>, &str)> {
+    let mut lines = content.split_inclusive('\n');
+    let opening = lines.next()?;
+    if opening.trim_end() != "---" {
+        return None;
+    }
+    let mut offset = opening.len();
+    let mut fields = Vec::new();
+    for line in lines {
+        offset += line.len();
+        if matches!(line.trim_end(), "---" | "...") {
+            return Some((fields, &content[offset..]));
+        }
+        fields.push(line.trim_end_matches(['\r', '\n']));
+    }
+    None
+}
+
+/// `content` without its frontmatter block, for anything shown or shared.
+pub(crate) fn strip_frontmatter(content: &str) -> &str {
+    split_frontmatter(content).map_or(content, |(_, body)| body)
+}
+
+fn parse_when(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(date.and_time(NaiveTime::MIN));
+    }
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+}
+
+/// When the note expires, from its `expires:` frontmatter key.
+pub fn note_expiry(content: &str) -> Option<NaiveDateTime> {
+    let (fields, _) = split_frontmatter(content)?;
+    fields.iter().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == EXPIRES_KEY)
+            .then_some(value)
+            .and_then(parse_when)
+    })
+}
+
+/// `content` with its `expires:` key set to `when`, or removed for `None`.
+/// Frontmatter is added when needed and dropped once it would be empty.
+fn with_expiry(content: &str, when: Option<&str>) -> String {
+    let is_expiry_line = |line: &&str| {
+        line.split_once(':')
+            .is_some_and(|(key, _)| key.trim() == EXPIRES_KEY)
+    };
+    let (mut fields, body) = split_frontmatter(content).unwrap_or((Vec::new(), content));
+    fields.retain(|line| !is_expiry_line(line));
+    let expiry_line = when.map(|when| format!("{}: {}", EXPIRES_KEY, when));
+    if let Some(line) = expiry_line.as_deref() {
+        fields.push(line);
+    }
+    if fields.is_empty() {
+        return body.to_string();
+    }
+    format!("---\n{}\n---\n{}", fields.join("\n"), body)
+}
+
+/// Normalize a requested expiry to the form written into frontmatter.
+fn normalize_when(when: &str) -> Result<String, String> {
+    let when = when.trim();
+    if NaiveDate::parse_from_str(when, "%Y-%m-%d").is_ok() {
+        return Ok(when.to_string());
+    }
+    parse_when(when)
+        .map(|at| at.format("%Y-%m-%dT%H:%M").to_string())
+        .ok_or_else(|| {
+            format!(
+                "Invalid expiry '{}': use YYYY-MM-DD or YYYY-MM-DDTHH:MM",
+                when
+            )
+        })
+}
+
+fn is_expired(expires_at: NaiveDateTime, now: NaiveDateTime) -> bool {
+    now >= expires_at
+}
+
+/// Set (or with `None`, clear) when a note expires.
+#[tauri::command]
+pub fn set_note_expiry(
+    app: AppHandle,
+    path: String,
+    when: Option<String>,
+    index: State<'_, NoteIndex>,
+    emb_index: State<'_, EmbeddingIndex>,
+) -> Result<notes::NoteSaved, String> {
+    let when = when
+        .filter(|when| !when.trim().is_empty())
+        .map(|when| normalize_when(&when))
+        .transpose()?;
+    let content = notes::get_note_content_inner(&path)?;
+    if note_lock::is_locked_content(&content) {
+        return Err("Unlock the note to set when it expires".to_string());
+    }
+    let updated = with_expiry(&content, when.as_deref());
+    notes::update_note(app, path, updated, index, emb_index)
+}
+
+/// A free path for `filename` in the trash folder.
+pub(crate) fn trash_destination(trash: &Path, filename: &str) -> String {
+    let (stem, ext) = filename.rsplit_once('.').unwrap_or((filename, "md"));
+    let mut candidate = trash.join(filename);
+    let mut n = 2;
+    while storage::path_exists(&candidate.to_string_lossy()) {
+        candidate = trash.join(format!("{}-{}.{}", stem, n, ext));
+        n += 1;
+    }
+    candidate.to_string_lossy().to_string()
+}
+
+/// Move every note whose expiry has passed into `.trash`.
+pub fn expire_due_notes(app: &AppHandle) -> Result<Vec<ExpiredNote>, String> {
+    let index = app.state::<NoteIndex>();
+    let emb_index = app.state::<EmbeddingIndex>();
+    let now = Local::now().naive_local();
+    let trash = get_stik_folder()?.join(TRASH_FOLDER);
+    let edit_locks = EditLocks::load();
+
+    let mut expired = Vec::new();
+    for entry in index.list(None)? {
+        if entry.locked || edit_locks.contains(&entry.path) {
+            continue;
+        }
+        let Ok((head, _)) = storage::read_file_prefix(&entry.path, FRONTMATTER_READ_BYTES) else {
+            continue;
+        };
+        if !note_expiry(&head).is_some_and(|at| is_expired(at, now)) {
+            continue;
+        }
+
+        storage::ensure_dir(&trash.to_string_lossy())?;
+        let trashed_path = trash_destination(&trash, &entry.filename);
+        if let Err(e) = storage::move_file(&entry.path, &trashed_path) {
+            log::error!("Failed to move expired note {} to trash: {}", entry.path, e);
+            continue;
+        }
+        index.remove(&entry.path);
+        spotlight::note_removed(&entry.path);
+        emb_index.remove_entry(&entry.path);
+        notes::emit_note_deleted(app, &entry.path);
+        expired.push(ExpiredNote {
+            path: entry.path,
+            folder: entry.folder,
+            trashed_path,
+        });
+    }
+
+    if !expired.is_empty() {
+        let folders: Vec<String> = expired.iter().map(|note| note.folder.clone()).collect();
+        git_share::notify_notes_changed(&folders);
+        widget_feed::notes_changed();
+        let _ = emb_index.save();
+        tray_menu::rebuild_tray_menu(app);
+        let _ = app.emit("notes-expired", &expired);
+    }
+    Ok(expired)
+}
+
+/// Start the scheduler that runs the expiry pass once a day. Call once
+/// during app setup.
+pub fn start_scheduler(app: AppHandle) {
+    if SCHEDULER_RUNNING.set(()).is_err() {
+        return;
+    }
+
+    thread::Builder::new()
+        .name("stik-note-expiry".to_string())
+        .spawn(move || loop {
+            thread::sleep(std::time::Duration::from_secs(SCHEDULER_TICK_SECONDS));
+            run_scheduled_expiry(&app);
+        })
+        .ok();
+}
+
+fn run_scheduled_expiry(app: &AppHandle) {
+    let state = load_state().unwrap_or_default();
+    let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+    if state.last_run_date.as_deref() == Some(today.as_str()) {
+        return;
+    }
+    let new_state = ExpiryState {
+        last_run_date: Some(today),
+    };
+    if let Err(e) = save_state(&new_state) {
+        log::error!("Failed to save note expiry state: {}", e);
+    }
+
+    git_share::sync_before_maintenance(app);
+    match expire_due_notes(app) {
+        Ok(expired) if !expired.is_empty() => {
+            log::info!("Moved {} expired note(s) to trash", expired.len());
+        }
+        Ok(_) => {}
+        Err(e) => log::error!("Failed to expire notes: {}", e),
+    }
+}
+
+fn load_state() -> Result<ExpiryState, String> {
+    let path = config_paths::config_file("note_expiry.json")?;
+    match versioning::load_versioned::<ExpiryState>(&path)? {
+        Some(state) => Ok(state),
+        None => Ok(ExpiryState::default()),
+    }
+}
+
+fn save_state(state: &ExpiryState) -> Result<(), String> {
+    let path = config_paths::writable_config_file("note_expiry.json")?;
+    versioning::save_versioned(&path, state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn reads_dates_and_times_from_frontmatter() {
+        assert_eq!(
+            note_expiry("---\nexpires: 2026-03-05\n---\nParking spot B3"),
+            Some(at("2026-03-05", "00:00"))
+        );
+        assert_eq!(
+            note_expiry("---\ntags: [car]\nexpires: \"2026-03-05T18:30\"\n---\n"),
+            Some(at("2026-03-05", "18:30"))
+        );
+        assert_eq!(note_expiry("expires: 2026-03-05\n"), None);
+        assert_eq!(note_expiry("---\nexpires: soon\n---\n"), None);
+        assert_eq!(note_expiry("---\nexpires: 2026-03-05\n"), None);
+    }
+
+    #[test]
+    fn notes_expire_once_the_moment_passes() {
+        let expires = at("2026-03-05", "00:00");
+        assert!(!is_expired(expires, at("2026-03-04", "23:59")));
+        assert!(is_expired(expires, at("2026-03-05", "00:00")));
+    }
+
+    #[test]
+    fn setting_an_expiry_adds_replaces_and_removes_the_key() {
+        let added = with_expiry("Parking spot B3\n", Some("2026-03-05"));
+        assert_eq!(added, "---\nexpires: 2026-03-05\n---\nParking spot B3\n");
+
+        let replaced = with_expiry(&added, Some("2026-03-06"));
+        assert_eq!(replaced, "---\nexpires: 2026-03-06\n---\nParking spot B3\n");
+
+        assert_eq!(with_expiry(&replaced, None), "Parking spot B3\n");
+
+        let tagged = "---\ntags: [car]\nexpires: 2026-03-05\n---\nB3\n";
+        assert_eq!(with_expiry(tagged, None), "---\ntags: [car]\n---\nB3\n");
+    }
+
+    #[test]
+    fn requested_expiries_are_validated() {
+        assert_eq!(normalize_when(" 2026-03-05 "), Ok("2026-03-05".to_string()));
+        assert_eq!(
+            normalize_when("2026-03-05 18:30"),
+            Ok("2026-03-05T18:30".to_string())
+        );
+        assert!(normalize_when("next week").is_err());
+    }
+}
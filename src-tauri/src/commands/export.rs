@@ -0,0 +1,462 @@
+/// Note export — standalone files that render the same outside Stik.
+use pulldown_cmark::{Event, Parser, Tag};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::notes::get_note_content_inner;
+use super::settings::{self, ThemeColors};
+use super::{index, note_lock, share};
+
+/// Write a note as a self-contained HTML file styled with a Stik theme.
+/// `theme_id` defaults to the active theme; `.assets` images are inlined.
+#[tauri::command]
+pub fn export_note_html(
+    path: String,
+    output_path: String,
+    theme_id: Option<String>,
+) -> Result<(), String> {
+    let html = render_note_html(&path, theme_id.as_deref())?;
+    fs::write(&output_path, html).map_err(|e| format!("Failed to write HTML export: {}", e))
+}
+
+/// Render a note to the standalone HTML document used by file exports.
+pub(crate) fn render_note_html(path: &str, theme_id: Option<&str>) -> Result<String, String> {
+    let content = get_note_content_inner(path)?;
+    if note_lock::is_locked_content(&content) {
+        return Err("Unlock the note before exporting it".to_string());
+    }
+
+    let settings = settings::load_settings_from_file()?;
+    let note_dir = Path::new(path).parent().ok_or("Invalid note path")?;
+    let body = share::markdown_to_html(
+        &content,
+        &share::HtmlOptions {
+            note_dir: Some(note_dir),
+            smart_punctuation: settings.smart_punctuation,
+            asset_urls: None,
+        },
+    );
+
+    let (colors, is_dark) = settings::resolve_theme_colors(&settings, theme_id);
+
+    Ok(build_html_document(
+        &document_title(&content),
+        &body,
+        &colors,
+        is_dark,
+    ))
+}
+
+const BUNDLE_ASSETS_DIR: &str = "assets";
+const MAX_PORTABLE_NAME_LEN: usize = 80;
+const MISSING_IMAGE_SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="320" height="120" viewBox="0 0 320 120"><rect x="0.5" y="0.5" width="319" height="119" rx="8" fill="#f4f4f4" stroke="#d0d0d0"/><text x="160" y="65" font-family="sans-serif" font-size="14" fill="#888" text-anchor="middle">Image not found</text></svg>
+"##;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NoteBundle {
+    /// Folder holding `index.html` and `assets/`.
+    pub directory: String,
+    /// Zip of that folder, next to it.
+    pub zip_path: String,
+    /// Files written to `assets/`, placeholders included.
+    pub assets: Vec<String>,
+    /// One line per image that was replaced by a placeholder.
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct BundleAssets {
+    /// Markdown image `src` → its path inside the bundle.
+    urls: HashMap<String, String>,
+    files: Vec<String>,
+    warnings: Vec<String>,
+}
+
+/// Export a note as a folder any browser can open: a themed `index.html`,
+/// only the images the note references in `assets/`, and a zip of both
+/// for sending. Missing images become placeholders listed in `warnings`.
+#[tauri::command]
+pub async fn export_note_bundle(
+    path: String,
+    destination_dir: String,
+) -> Result<NoteBundle, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        export_note_bundle_inner(&path, Path::new(&destination_dir))
+    })
+    .await
+    .map_err(|e| format!("Note bundle export failed: {}", e))?
+}
+
+fn export_note_bundle_inner(path: &str, destination: &Path) -> Result<NoteBundle, String> {
+    let content = get_note_content_inner(path)?;
+    if note_lock::is_locked_content(&content) {
+        return Err("Unlock the note before exporting it".to_string());
+    }
+
+    let settings = settings::load_settings_from_file()?;
+    let note_dir = Path::new(path).parent().ok_or("Invalid note path")?;
+    let title = document_title(&content);
+
+    fs::create_dir_all(destination)
+        .map_err(|e| format!("Failed to create export folder: {}", e))?;
+    let bundle_dir = unique_bundle_dir(destination, &portable_filename(&title, "note"));
+    let assets_dir = bundle_dir.join(BUNDLE_ASSETS_DIR);
+    fs::create_dir_all(&assets_dir)
+        .map_err(|e| format!("Failed to create {}: {}", assets_dir.display(), e))?;
+
+    let assets = copy_bundle_assets(&content, note_dir, &assets_dir)?;
+    let body = share::markdown_to_html(
+        &content,
+        &share::HtmlOptions {
+            note_dir: None,
+            smart_punctuation: settings.smart_punctuation,
+            asset_urls: Some(&assets.urls),
+        },
+    );
+    let (colors, is_dark) = settings::resolve_theme_colors(&settings, None);
+    fs::write(
+        bundle_dir.join("index.html"),
+        build_html_document(&title, &body, &colors, is_dark),
+    )
+    .map_err(|e| format!("Failed to write index.html: {}", e))?;
+
+    let zip_path = zip_bundle(&bundle_dir)?;
+    Ok(NoteBundle {
+        directory: bundle_dir.to_string_lossy().to_string(),
+        zip_path: zip_path.to_string_lossy().to_string(),
+        assets: assets.files,
+        warnings: assets.warnings,
+    })
+}
+
+fn image_sources(content: &str) -> Vec<String> {
+    Parser::new(content)
+        .filter_map(|event| match event {
+            Event::Start(Tag::Image { dest_url, .. }) => Some(dest_url.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Copy the `.assets/` images `content` references into `assets_dir`
+/// under portable names. Remote images are left alone.
+fn copy_bundle_assets(
+    content: &str,
+    note_dir: &Path,
+    assets_dir: &Path,
+) -> Result<BundleAssets, String> {
+    let mut assets = BundleAssets::default();
+    for src in image_sources(content) {
+        if assets.urls.contains_key(&src) {
+            continue;
+        }
+        let Some(name) = share::asset_name(&src) else {
+            continue;
+        };
+
+        let source = note_dir.join(".assets").join(name);
+        let portable = portable_filename(name, "image");
+        let exists = source.is_file();
+        let copied = exists && {
+            let target = unique_name(&assets.files, &portable);
+            match fs::copy(&source, assets_dir.join(&target)) {
+                Ok(_) => {
+                    assets.files.push(target);
+                    true
+                }
+                Err(e) => {
+                    assets
+                        .warnings
+                        .push(format!("{} could not be copied: {}", name, e));
+                    false
+                }
+            }
+        };
+        if !copied {
+            let stem = portable
+                .rsplit_once('.')
+                .map_or(portable.as_str(), |(stem, _)| stem);
+            let target = unique_name(&assets.files, &format!("{}-missing.svg", stem));
+            fs::write(assets_dir.join(&target), MISSING_IMAGE_SVG)
+                .map_err(|e| format!("Failed to write placeholder for {}: {}", name, e))?;
+            if !exists {
+                assets.warnings.push(format!("{} is missing", name));
+            }
+            assets.files.push(target);
+        }
+
+        let target = assets.files.last().cloned().unwrap_or_default();
+        assets
+            .urls
+            .insert(src, format!("{}/{}", BUNDLE_ASSETS_DIR, target));
+    }
+    Ok(assets)
+}
+
+/// Lowercase ASCII letters, digits, `-` and `_`, so the bundle survives
+/// any file system, zip tool or web server. The extension is kept.
+fn portable_filename(name: &str, fallback: &str) -> String {
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (name, None),
+    };
+
+    let mut portable = String::new();
+    for c in stem.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            portable.push(c.to_ascii_lowercase());
+        } else if !portable.ends_with('-') {
+            portable.push('-');
+        }
+    }
+    let mut portable = portable.trim_matches('-').to_string();
+    if portable.len() > MAX_PORTABLE_NAME_LEN {
+        portable.truncate(MAX_PORTABLE_NAME_LEN);
+        portable = portable.trim_end_matches('-').to_string();
+    }
+    if portable.is_empty() {
+        portable = fallback.to_string();
+    }
+
+    let extension: String = extension
+        .unwrap_or_default()
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    if extension.is_empty() {
+        portable
+    } else {
+        format!("{}.{}", portable, extension)
+    }
+}
+
+/// `name`, or `name` with `-2`, `-3`… before the extension when taken.
+fn unique_name(taken: &[String], name: &str) -> String {
+    let (stem, extension) = name
+        .rsplit_once('.')
+        .map_or((name, String::new()), |(stem, ext)| {
+            (stem, format!(".{}", ext))
+        });
+    let mut candidate = name.to_string();
+    let mut n = 2;
+    while taken.contains(&candidate) {
+        candidate = format!("{}-{}{}", stem, n, extension);
+        n += 1;
+    }
+    candidate
+}
+
+/// A bundle folder under `destination` whose folder and zip names are both
+/// free.
+fn unique_bundle_dir(destination: &Path, name: &str) -> PathBuf {
+    let mut candidate = name.to_string();
+    let mut n = 2;
+    while destination.join(&candidate).exists()
+        || destination.join(format!("{}.zip", candidate)).exists()
+    {
+        candidate = format!("{}-{}", name, n);
+        n += 1;
+    }
+    destination.join(candidate)
+}
+
+/// Zip `bundle_dir` next to itself with the system tools.
+fn zip_bundle(bundle_dir: &Path) -> Result<PathBuf, String> {
+    let parent = bundle_dir.parent().ok_or("Invalid bundle folder")?;
+    let name = bundle_dir
+        .file_name()
+        .ok_or("Invalid bundle folder")?
+        .to_string_lossy()
+        .to_string();
+    let zip_path = parent.join(format!("{}.zip", name));
+
+    let status = if cfg!(target_os = "macos") {
+        Command::new("ditto")
+            .args(["-c", "-k", "--keepParent"])
+            .arg(bundle_dir)
+            .arg(&zip_path)
+            .status()
+    } else {
+        Command::new("zip")
+            .arg("-r")
+            .arg("-q")
+            .arg(&zip_path)
+            .arg(&name)
+            .current_dir(parent)
+            .status()
+    };
+    match status {
+        Ok(status) if status.success() => Ok(zip_path),
+        Ok(_) | Err(_) => Err("Could not zip the note bundle".to_string()),
+    }
+}
+
+pub(crate) fn document_title(content: &str) -> String {
+    let title = index::extract_title(content);
+    let title = title.trim_start_matches('#').trim();
+    if title.is_empty() {
+        "Untitled".to_string()
+    } else {
+        title.to_string()
+    }
+}
+
+fn build_html_document(title: &str, body: &str, colors: &ThemeColors, is_dark: bool) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<style>
+:root {{
+  color-scheme: {scheme};
+  --color-bg: {bg};
+  --color-surface: {surface};
+  --color-ink: {ink};
+  --color-stone: {stone};
+  --color-line: {line};
+  --color-coral: {accent};
+  --color-coral-light: {accent_light};
+  --color-coral-dark: {accent_dark};
+}}
+body {{
+  margin: 0;
+  background: rgb(var(--color-bg));
+  color: rgb(var(--color-ink));
+  font: 15px/1.6 -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif;
+}}
+main {{ max-width: 720px; margin: 0 auto; padding: 48px 24px; }}
+a {{ color: rgb(var(--color-coral)); }}
+hr {{ border: 0; border-top: 1px solid rgb(var(--color-line)); }}
+img {{ max-width: 100%; border-radius: 6px; }}
+blockquote {{
+  margin: 0; padding-left: 16px;
+  border-left: 3px solid rgb(var(--color-coral));
+  color: rgb(var(--color-stone));
+}}
+code {{ background: rgb(var(--color-line)); padding: 1px 4px; border-radius: 4px; }}
+pre {{ background: rgb(var(--color-surface)); padding: 12px 16px; border-radius: 8px; overflow-x: auto; }}
+pre code {{ background: none; padding: 0; }}
+table {{ border-collapse: collapse; }}
+th, td {{ border: 1px solid rgb(var(--color-line)); padding: 6px 10px; }}
+del {{ color: rgb(var(--color-stone)); }}
+li:has(> input[type="checkbox"]) {{ list-style: none; }}
+input[type="checkbox"] {{ accent-color: rgb(var(--color-coral)); }}
+</style>
+</head>
+<body>
+<main>
+{body}</main>
+</body>
+</html>
+"#,
+        title = escape_html(title),
+        scheme = if is_dark { "dark" } else { "light" },
+        bg = colors.bg,
+        surface = colors.surface,
+        ink = colors.ink,
+        stone = colors.stone,
+        line = colors.line,
+        accent = colors.accent,
+        accent_light = colors.accent_light,
+        accent_dark = colors.accent_dark,
+        body = body,
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::temp_dir;
+
+    #[test]
+    fn title_strips_heading_marker_and_is_escaped() {
+        assert_eq!(document_title("# Plans <draft>\n\nbody"), "Plans <draft>");
+
+        let html = build_html_document(
+            "Plans <draft>",
+            "<p>body</p>\n",
+            &ThemeColors::default(),
+            false,
+        );
+        assert!(html.contains("<title>Plans &lt;draft&gt;</title>"));
+    }
+
+    #[test]
+    fn bundle_names_are_portable_and_unique() {
+        assert_eq!(portable_filename("Café Plans: Q3!", "note"), "caf-plans-q3");
+        assert_eq!(
+            portable_filename("Screen Shot 1.PNG", "image"),
+            "screen-shot-1.png"
+        );
+        assert_eq!(portable_filename("日本.png", "image"), "image.png");
+        let taken = vec!["a.png".to_string(), "a-2.png".to_string()];
+        assert_eq!(unique_name(&taken, "a.png"), "a-3.png");
+    }
+
+    #[test]
+    fn bundle_copies_only_referenced_assets_and_marks_missing_ones() {
+        let dir = temp_dir("bundle");
+        let note_dir = dir.join("Work");
+        let assets_dir = dir.join("bundle").join(BUNDLE_ASSETS_DIR);
+        fs::create_dir_all(note_dir.join(".assets")).unwrap();
+        fs::create_dir_all(&assets_dir).unwrap();
+        fs::write(
+            note_dir.join(".assets").join("My Diagram.png"),
+            [1_u8, 2, 3],
+        )
+        .unwrap();
+        fs::write(note_dir.join(".assets").join("unused.png"), [4_u8]).unwrap();
+
+        let assets = copy_bundle_assets(
+            "![d](<.assets/My Diagram.png>) ![g](.assets/gone.png) ![w](https://x.io/a.png)",
+            &note_dir,
+            &assets_dir,
+        )
+        .unwrap();
+
+        assert_eq!(assets.files, vec!["my-diagram.png", "gone-missing.svg"]);
+        assert_eq!(
+            assets
+                .urls
+                .get(".assets/My Diagram.png")
+                .map(String::as_str),
+            Some("assets/my-diagram.png")
+        );
+        assert_eq!(assets.warnings, vec!["gone.png is missing"]);
+        assert!(!assets_dir.join("unused.png").exists());
+        assert_eq!(
+            fs::read(assets_dir.join("my-diagram.png")).unwrap(),
+            [1, 2, 3]
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn document_carries_theme_css_variables() {
+        let colors = ThemeColors {
+            bg: "46 52 64".to_string(),
+            accent: "136 192 208".to_string(),
+            ..ThemeColors::default()
+        };
+        let html = build_html_document("Note", "<p>body</p>\n", &colors, true);
+        assert!(html.contains("--color-bg: 46 52 64;"));
+        assert!(html.contains("--color-coral: 136 192 208;"));
+        assert!(html.contains("color-scheme: dark;"));
+        assert!(html.contains("<main>\n<p>body</p>\n</main>"));
+    }
+}
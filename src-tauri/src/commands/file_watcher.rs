@@ -1,33 +1,137 @@
 /// Local file system watcher for detecting external changes to notes.
 /// Mirrors the iCloud monitoring flow: detects .md file changes, updates the
 /// NoteIndex + EmbeddingIndex, and emits a frontend event.
-use std::path::PathBuf;
-use std::sync::OnceLock;
-use std::time::Duration;
+///
+/// Stik's own writes go through `storage`, which records them here so the
+/// echo from the watcher is skipped.
+///
+/// Top-level folders are tracked too: folders created, removed or renamed
+/// in Finder update settings and the tray, and emit `folders-changed`.
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
 
 use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
 use tauri::{AppHandle, Emitter, Manager};
 
 use super::embeddings::{self, EmbeddingIndex};
+use super::folders::{self, FolderChange};
 use super::index::NoteIndex;
-use super::{notes, storage};
+use super::{cloud_sync, notes, spotlight, storage, tray_menu};
+use crate::state::AppState;
+
+/// What an on-disk change means for an open viewing window.
+#[derive(Debug, PartialEq)]
+enum ViewingRefresh {
+    Unchanged,
+    Updated,
+    Conflict,
+    Deleted,
+}
+
+/// One external change, as sent in `external-note-changed`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NoteChange {
+    Changed { path: String },
+    Removed { path: String },
+    Moved { from: String, to: String },
+}
+
+/// A write Stik made itself: when it happened and what the file looked
+/// like afterwards (`None` once deleted).
+struct OwnWrite {
+    at: Instant,
+    fingerprint: Option<(SystemTime, u64)>,
+}
+
+/// Longer than the debounce window plus FSEvents latency.
+const OWN_WRITE_TTL: Duration = Duration::from_secs(5);
+/// Past this many paths in one batch (`git checkout`, bulk copy), rebuild
+/// the index once instead of re-reading every file.
+const BULK_CHANGE_THRESHOLD: usize = 200;
+/// In a cloud-synced root, a batch this big starts a sync burst: changes
+/// are held until the client goes quiet so previews don't flicker through
+/// half-downloaded files.
+const CLOUD_BURST_THRESHOLD: usize = 20;
+const CLOUD_BURST_QUIET: Duration = Duration::from_secs(2);
+/// A burst that never goes quiet is still flushed this often.
+const CLOUD_BURST_MAX_WAIT: Duration = Duration::from_secs(10);
 
 static WATCHER_RUNNING: OnceLock<()> = OnceLock::new();
+static OWN_WRITES: OnceLock<Mutex<HashMap<PathBuf, OwnWrite>>> = OnceLock::new();
+
+fn own_writes() -> &'static Mutex<HashMap<PathBuf, OwnWrite>> {
+    OWN_WRITES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn fingerprint(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+/// Record that Stik just wrote, moved or deleted `path`.
+pub fn mark_own_write(path: &str) {
+    let path = PathBuf::from(path);
+    let fingerprint = fingerprint(&path);
+    let mut writes = own_writes().lock().unwrap_or_else(|e| e.into_inner());
+    writes.retain(|_, write| write.at.elapsed() < OWN_WRITE_TTL);
+    writes.insert(
+        path,
+        OwnWrite {
+            at: Instant::now(),
+            fingerprint,
+        },
+    );
+}
+
+/// True if the file still looks exactly as Stik left it, so the event is
+/// our own echo. An external edit right after ours changes the fingerprint.
+fn is_own_write(path: &Path) -> bool {
+    let writes = own_writes().lock().unwrap_or_else(|e| e.into_inner());
+    writes.get(path).is_some_and(|write| {
+        write.at.elapsed() < OWN_WRITE_TTL && write.fingerprint == fingerprint(path)
+    })
+}
+
+/// Markdown notes only: editors' hidden temp and lock files (`.#note.md`,
+/// `note.md~`) and atomic-save temps are ignored; the final rename onto
+/// the note shows up as a change to the note itself.
+fn is_note_path(path: &Path) -> bool {
+    let is_markdown = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("md"))
+        .unwrap_or(false);
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    is_markdown && !name.starts_with('.') && !name.starts_with('~')
+}
 
 /// Start watching the Stik root directory for .md file changes.
 /// No-ops if already running or if root cannot be resolved.
 pub fn start(app: AppHandle) {
-    if WATCHER_RUNNING.set(()).is_err() {
+    if WATCHER_RUNNING.get().is_some() {
         return; // already running
     }
 
+    // Resolved first, so a notes drive that isn't mounted yet leaves the
+    // watcher startable by `recheck_notes_directory`.
     let root = match super::folders::get_stik_folder() {
         Ok(r) => r,
         Err(e) => {
-            eprintln!("file_watcher: cannot resolve stik root: {}", e);
+            log::error!("Cannot resolve Stik root for file watcher: {}", e);
             return;
         }
     };
+    if WATCHER_RUNNING.set(()).is_err() {
+        return;
+    }
 
     std::thread::Builder::new()
         .name("stik-file-watcher".to_string())
@@ -41,7 +145,7 @@ fn run(app: AppHandle, root: PathBuf) {
     let mut debouncer = match new_debouncer(Duration::from_millis(500), tx) {
         Ok(d) => d,
         Err(e) => {
-            eprintln!("file_watcher: failed to create debouncer: {}", e);
+            log::error!("Failed to create debouncer: {}", e);
             return;
         }
     };
@@ -50,23 +154,47 @@ fn run(app: AppHandle, root: PathBuf) {
         .watcher()
         .watch(&root, notify::RecursiveMode::Recursive)
     {
-        eprintln!("file_watcher: failed to watch {}: {}", root.display(), e);
+        log::error!("Failed to watch {}: {}", root.display(), e);
         return;
     }
 
+    let canonical_root = root.canonicalize().unwrap_or_else(|_| root.clone());
+    let mut known_folders = folders::folder_snapshot(&root);
+    let cloud_synced = cloud_sync::is_cloud_synced(&canonical_root);
+    let mut burst: Vec<String> = Vec::new();
+    let mut burst_started = Instant::now();
+
     loop {
-        match rx.recv() {
+        if !burst.is_empty() && burst_started.elapsed() >= CLOUD_BURST_MAX_WAIT {
+            handle_detected(&app, std::mem::take(&mut burst));
+        }
+        let received = if burst.is_empty() {
+            rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        } else {
+            let wait = CLOUD_BURST_MAX_WAIT.saturating_sub(burst_started.elapsed());
+            rx.recv_timeout(wait.min(CLOUD_BURST_QUIET))
+        };
+        match received {
             Ok(Ok(events)) => {
+                let touches_folders = events.iter().any(|e| {
+                    e.path
+                        .parent()
+                        .is_some_and(|parent| parent == root || parent == canonical_root)
+                });
+                if touches_folders {
+                    let current = folders::folder_snapshot(&root);
+                    let changes = folders::diff_folder_snapshots(&known_folders, &current);
+                    known_folders = current;
+                    if !changes.is_empty() {
+                        handle_folder_changes(&app, &root, &changes);
+                    }
+                }
+
                 let paths: Vec<String> = events
                     .iter()
                     .filter(|e| e.kind == DebouncedEventKind::Any)
-                    .filter(|e| {
-                        e.path
-                            .extension()
-                            .and_then(|ext| ext.to_str())
-                            .map(|ext| ext.eq_ignore_ascii_case("md"))
-                            .unwrap_or(false)
-                    })
+                    .filter(|e| is_note_path(&e.path))
+                    .filter(|e| !is_own_write(&e.path))
                     .map(|e| e.path.to_string_lossy().to_string())
                     .collect();
 
@@ -74,22 +202,21 @@ fn run(app: AppHandle, root: PathBuf) {
                     continue;
                 }
 
-                // Deduplicate
-                let mut unique: Vec<String> = paths;
-                unique.sort();
-                unique.dedup();
-
-                eprintln!(
-                    "file_watcher: detected {} changed file(s): {:?}",
-                    unique.len(),
-                    unique
-                );
-                handle_changes(&app, &unique);
+                if cloud_synced && (!burst.is_empty() || paths.len() >= CLOUD_BURST_THRESHOLD) {
+                    if burst.is_empty() {
+                        burst_started = Instant::now();
+                    }
+                    burst.extend(paths);
+                    continue;
+                }
+
+                handle_detected(&app, paths);
             }
             Ok(Err(err)) => {
-                eprintln!("file_watcher: watch error: {}", err);
+                log::error!("File watch error: {}", err);
             }
-            Err(_) => break, // channel closed
+            Err(RecvTimeoutError::Timeout) => handle_detected(&app, std::mem::take(&mut burst)),
+            Err(RecvTimeoutError::Disconnected) => break, // channel closed
         }
     }
 
@@ -97,23 +224,379 @@ fn run(app: AppHandle, root: PathBuf) {
     drop(debouncer);
 }
 
+fn handle_detected(app: &AppHandle, paths: Vec<String>) {
+    // Deduplicate
+    let mut unique: Vec<String> = paths;
+    unique.sort();
+    unique.dedup();
+
+    log::debug!("Detected {} changed file(s): {:?}", unique.len(), unique);
+    handle_changes(app, &unique);
+}
+
 /// Shared handler: update NoteIndex, EmbeddingIndex, emit frontend event.
 /// Used by both the local file watcher and iCloud notification handler.
 pub fn handle_changes(app: &AppHandle, paths: &[String]) {
     let index = app.state::<NoteIndex>();
-    index.notify_external_change(paths);
-
     let emb = app.state::<EmbeddingIndex>();
-    for path_str in paths {
-        if let Ok(content) = storage::read_file(path_str) {
-            if !notes::is_effectively_empty_markdown(&content) {
-                if let Some(embedding) = embeddings::embed_content(&content) {
-                    emb.add_entry(path_str, embedding);
+
+    if paths.len() > BULK_CHANGE_THRESHOLD {
+        rebuild_indexes(app);
+        let changes: Vec<NoteChange> = paths
+            .iter()
+            .map(|path| {
+                let path = path.clone();
+                if storage::path_exists(&path) {
+                    NoteChange::Changed { path }
+                } else {
+                    NoteChange::Removed { path }
+                }
+            })
+            .collect();
+        let _ = app.emit("external-note-changed", &changes);
+    } else {
+        // Plan before re-indexing, while moved-away paths are still known.
+        let changes = plan_changes(paths, storage::path_exists, |path| {
+            index.get(path).is_some()
+        });
+        index.notify_external_change(paths);
+
+        for change in &changes {
+            match change {
+                NoteChange::Removed { path } => emb.remove_entry(path),
+                // Same content under a new name, so keep the embedding.
+                NoteChange::Moved { from, to } => emb.move_entry(from, to),
+                NoteChange::Changed { path } => {
+                    if let Ok(content) = storage::read_file(path) {
+                        if !notes::is_effectively_empty_markdown(&content) {
+                            if let Some(embedding) = embeddings::embed_content(&content) {
+                                index.set_language(path, &embedding.language);
+                                emb.add_entry(path, embedding);
+                            }
+                        }
+                    }
                 }
             }
         }
+        let _ = emb.save();
+        let _ = app.emit("external-note-changed", &changes);
     }
-    let _ = emb.save();
 
+    refresh_viewing_windows(app, paths);
     let _ = app.emit("files-changed", paths);
 }
+
+/// Rebuild the NoteIndex now and embeddings in the background.
+fn rebuild_indexes(app: &AppHandle) {
+    if let Err(e) = app.state::<NoteIndex>().build() {
+        log::error!("Failed to rebuild index: {}", e);
+    }
+    let handle = app.clone();
+    std::thread::Builder::new()
+        .name("stik-embeddings".to_string())
+        .spawn(move || {
+            embeddings::build_embeddings(
+                &handle.state::<NoteIndex>(),
+                &handle.state::<EmbeddingIndex>(),
+            );
+        })
+        .ok();
+}
+
+/// Folders created, removed or renamed outside Stik. Removed folders leave
+/// the indexes like an in-app delete; a rename moves every note in it, so
+/// the indexes are rebuilt.
+fn handle_folder_changes(app: &AppHandle, root: &Path, changes: &[FolderChange]) {
+    log::debug!("Folder changes: {:?}", changes);
+    let index = app.state::<NoteIndex>();
+    let emb = app.state::<EmbeddingIndex>();
+
+    let mut renamed = false;
+    for change in changes {
+        match change {
+            FolderChange::Removed { name } => {
+                spotlight::folder_removed(&index, name);
+                index.remove_by_folder(name);
+                emb.remove_by_path_prefix(&root.join(name).to_string_lossy());
+            }
+            FolderChange::Renamed { .. } => renamed = true,
+            FolderChange::Created { .. } => {}
+        }
+    }
+    if renamed {
+        rebuild_indexes(app);
+    } else {
+        let _ = emb.save();
+    }
+
+    if let Err(e) = folders::sync_settings_after_external_folder_changes(changes) {
+        log::error!("Failed to update folder settings: {}", e);
+    }
+    tray_menu::rebuild_tray_menu(app);
+    let _ = app.emit("folders-changed", changes);
+}
+
+/// Turn a batch of touched paths into changes. A path that vanished and
+/// one that appeared in the same batch are a move when they share a file
+/// name (moved between folders) or are the only pair in one folder
+/// (renamed in place).
+fn plan_changes(
+    paths: &[String],
+    exists: impl Fn(&str) -> bool,
+    known: impl Fn(&str) -> bool,
+) -> Vec<NoteChange> {
+    let (present, mut removed): (Vec<&String>, Vec<&String>) =
+        paths.iter().partition(|path| exists(path));
+    let mut appeared: Vec<&String> = present
+        .iter()
+        .copied()
+        .filter(|path| !known(path))
+        .collect();
+    let mut changes: Vec<NoteChange> = Vec::new();
+
+    let file_name = |path: &str| Path::new(path).file_name().map(|name| name.to_os_string());
+    let parent = |path: &str| Path::new(path).parent().map(Path::to_path_buf);
+
+    removed.retain(|from| {
+        let Some(pos) = appeared
+            .iter()
+            .position(|to| file_name(to) == file_name(from))
+        else {
+            return true;
+        };
+        let to = appeared.remove(pos);
+        changes.push(NoteChange::Moved {
+            from: (*from).clone(),
+            to: to.clone(),
+        });
+        false
+    });
+
+    removed.retain(|from| {
+        let in_folder: Vec<usize> = appeared
+            .iter()
+            .enumerate()
+            .filter(|(_, to)| parent(to) == parent(from))
+            .map(|(pos, _)| pos)
+            .collect();
+        let removed_in_folder = paths
+            .iter()
+            .filter(|path| !exists(path) && parent(path) == parent(from))
+            .count();
+        if in_folder.len() != 1 || removed_in_folder != 1 {
+            return true;
+        }
+        let to = appeared.remove(in_folder[0]);
+        changes.push(NoteChange::Moved {
+            from: (*from).clone(),
+            to: to.clone(),
+        });
+        false
+    });
+
+    let moved_to: Vec<String> = changes
+        .iter()
+        .filter_map(|change| match change {
+            NoteChange::Moved { to, .. } => Some(to.clone()),
+            _ => None,
+        })
+        .collect();
+    changes.extend(
+        present
+            .into_iter()
+            .filter(|path| !moved_to.contains(*path))
+            .map(|path| NoteChange::Changed { path: path.clone() }),
+    );
+    changes.extend(
+        removed
+            .into_iter()
+            .map(|path| NoteChange::Removed { path: path.clone() }),
+    );
+    changes
+}
+
+/// Push external edits into open `sticked-view-*` windows. Clean windows get
+/// the new content; windows with unsaved edits get a conflict to resolve.
+fn refresh_viewing_windows(app: &AppHandle, paths: &[String]) {
+    let mut events = Vec::new();
+    let mut deleted = Vec::new();
+    {
+        let state = app.state::<AppState>();
+        let mut viewing_notes = state
+            .viewing_notes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+
+        for note in viewing_notes.values_mut() {
+            if !paths.iter().any(|p| Path::new(p) == Path::new(&note.path)) {
+                continue;
+            }
+            let on_disk = match storage::read_file(&note.path) {
+                Ok(content) => Some(content),
+                // Unreadable mid-write; the next event will catch up.
+                Err(_) if storage::path_exists(&note.path) => continue,
+                Err(_) => None,
+            };
+            match classify_viewing_change(&note.content, note.has_unsaved_edits, on_disk.as_deref())
+            {
+                ViewingRefresh::Unchanged => {}
+                ViewingRefresh::Deleted => deleted.push(note.path.clone()),
+                refresh => {
+                    // Track what is on disk either way, so a repeat event for
+                    // the same write doesn't raise the conflict twice.
+                    note.content = on_disk.unwrap_or_default();
+                    let event = if refresh == ViewingRefresh::Conflict {
+                        "viewing-note-conflict"
+                    } else {
+                        "viewing-note-updated"
+                    };
+                    events.push((
+                        format!("sticked-{}", note.id),
+                        event,
+                        serde_json::json!({
+                            "id": note.id,
+                            "path": note.path,
+                            "content": note.content,
+                        }),
+                    ));
+                }
+            }
+        }
+    }
+
+    for (label, event, payload) in events {
+        let _ = app.emit_to(label.as_str(), event, payload);
+    }
+    deleted.dedup();
+    for path in deleted {
+        // Same event the in-app delete sends; viewing windows close on it.
+        notes::emit_note_deleted(app, &path);
+    }
+}
+
+fn classify_viewing_change(
+    cached: &str,
+    has_unsaved_edits: bool,
+    on_disk: Option<&str>,
+) -> ViewingRefresh {
+    match on_disk {
+        None => ViewingRefresh::Deleted,
+        Some(content) if content == cached => ViewingRefresh::Unchanged,
+        Some(_) if has_unsaved_edits => ViewingRefresh::Conflict,
+        Some(_) => ViewingRefresh::Updated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::temp_dir;
+
+    #[test]
+    fn classifies_external_changes_for_viewing_windows() {
+        assert_eq!(
+            classify_viewing_change("same", false, Some("same")),
+            ViewingRefresh::Unchanged
+        );
+        assert_eq!(
+            classify_viewing_change("same", true, Some("same")),
+            ViewingRefresh::Unchanged
+        );
+        assert_eq!(
+            classify_viewing_change("old", false, Some("new")),
+            ViewingRefresh::Updated
+        );
+        assert_eq!(
+            classify_viewing_change("old", true, Some("new")),
+            ViewingRefresh::Conflict
+        );
+        assert_eq!(
+            classify_viewing_change("old", true, None),
+            ViewingRefresh::Deleted
+        );
+    }
+
+    fn plan(paths: &[&str], existing: &[&str], known: &[&str]) -> Vec<NoteChange> {
+        let paths: Vec<String> = paths.iter().map(|path| path.to_string()).collect();
+        plan_changes(
+            &paths,
+            |path| existing.contains(&path),
+            |path| known.contains(&path),
+        )
+    }
+
+    #[test]
+    fn pairs_vanished_and_new_paths_into_moves() {
+        assert_eq!(
+            plan(
+                &["/S/Inbox/a.md", "/S/Work/a.md"],
+                &["/S/Work/a.md"],
+                &["/S/Inbox/a.md"]
+            ),
+            vec![NoteChange::Moved {
+                from: "/S/Inbox/a.md".to_string(),
+                to: "/S/Work/a.md".to_string(),
+            }]
+        );
+        assert_eq!(
+            plan(
+                &["/S/Work/old.md", "/S/Work/new.md"],
+                &["/S/Work/new.md"],
+                &["/S/Work/old.md"]
+            ),
+            vec![NoteChange::Moved {
+                from: "/S/Work/old.md".to_string(),
+                to: "/S/Work/new.md".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn unpaired_paths_are_changes_or_removals() {
+        assert_eq!(
+            plan(
+                &["/S/Work/edited.md", "/S/Work/gone.md", "/S/Ideas/new.md"],
+                &["/S/Work/edited.md", "/S/Ideas/new.md"],
+                &["/S/Work/edited.md", "/S/Work/gone.md"],
+            ),
+            vec![
+                NoteChange::Changed {
+                    path: "/S/Work/edited.md".to_string()
+                },
+                NoteChange::Changed {
+                    path: "/S/Ideas/new.md".to_string()
+                },
+                NoteChange::Removed {
+                    path: "/S/Work/gone.md".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_editor_temp_and_lock_files() {
+        assert!(is_note_path(Path::new("/S/Work/note.md")));
+        assert!(!is_note_path(Path::new("/S/Work/.#note.md")));
+        assert!(!is_note_path(Path::new("/S/Work/note.md~")));
+        assert!(!is_note_path(Path::new("/S/Work/note.md.tmp")));
+        assert!(!is_note_path(Path::new("/S/Work/~note.md")));
+    }
+
+    #[test]
+    fn own_writes_are_skipped_until_the_file_changes_again() {
+        let dir = temp_dir("own-write");
+        let path = dir.join("note.md");
+        std::fs::write(&path, "ours").expect("write");
+        mark_own_write(&path.to_string_lossy());
+        assert!(is_own_write(&path));
+
+        std::fs::write(&path, "someone else's longer edit").expect("write");
+        assert!(!is_own_write(&path));
+
+        std::fs::remove_file(&path).expect("remove");
+        mark_own_write(&path.to_string_lossy());
+        assert!(is_own_write(&path));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
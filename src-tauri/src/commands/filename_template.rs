@@ -0,0 +1,313 @@
+/// Note filenames from the `filename_template` setting.
+///
+/// Placeholders are `{date}`, `{time}`, `{slug}` and `{uuid4}`; `{date}` and
+/// `{time}` take an optional fixed-width chrono format, e.g.
+/// `{date:%Y-%m-%d} {slug}-{uuid4}`. A template needs `{date}` plus `{time}`
+/// or `{uuid4}` so two notes never get the same name. Anything that reads
+/// capture dates back out of filenames goes through
+/// `parse_created_from_filename`, which falls back to the original
+/// `YYYYMMDD-HHMMSS-slug-xxxx` scheme so renaming the setting keeps old
+/// notes dated.
+use chrono::format::{Item, StrftimeItems};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::sync::RwLock;
+
+use super::settings::StikSettings;
+
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{date}-{time}-{slug}-{uuid4}";
+const DEFAULT_DATE_FORMAT: &str = "%Y%m%d";
+const DEFAULT_TIME_FORMAT: &str = "%H%M%S";
+const UUID_LENGTH: usize = 4;
+
+/// The template in use. `None` until settings are applied, which means the
+/// default; tests never touch the user's settings.
+static CONFIGURED: RwLock<Option<String>> = RwLock::new(None);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(String),
+    Date(String),
+    Time(String),
+    Slug,
+    Uuid,
+}
+
+/// When a filename says its note was captured. `time` is `None` when the
+/// name carries only a date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CreatedFromFilename {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+}
+
+impl CreatedFromFilename {
+    pub fn timestamp(&self) -> Option<NaiveDateTime> {
+        self.time.map(|time| self.date.and_time(time))
+    }
+}
+
+/// Width of a chrono format, or `None` when it varies (month names etc.)
+/// and a name couldn't be split back into its fields.
+fn fixed_width(format: &str) -> Option<usize> {
+    // Formatting with an unknown specifier panics, so reject those first.
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return None;
+    }
+    let early = NaiveDate::from_ymd_opt(2026, 1, 1)?.and_hms_opt(1, 1, 1)?;
+    let late = NaiveDate::from_ymd_opt(2026, 12, 31)?.and_hms_opt(23, 59, 59)?;
+    let early = early.format(format).to_string();
+    let late = late.format(format).to_string();
+    (early.len() == late.len() && !early.is_empty()).then_some(early.len())
+}
+
+fn tokenize(template: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut literal = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        literal.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or("Unclosed '{' in filename template")?;
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(std::mem::take(&mut literal)));
+        }
+        let placeholder = &rest[start + 1..end];
+        let (name, format) = match placeholder.split_once(':') {
+            Some((name, format)) => (name, Some(format)),
+            None => (placeholder, None),
+        };
+        tokens.push(match (name, format) {
+            ("date", format) => Token::Date(format.unwrap_or(DEFAULT_DATE_FORMAT).to_string()),
+            ("time", format) => Token::Time(format.unwrap_or(DEFAULT_TIME_FORMAT).to_string()),
+            ("slug", None) => Token::Slug,
+            ("uuid4", None) => Token::Uuid,
+            _ => return Err(format!("Unknown placeholder {{{}}}", placeholder)),
+        });
+        rest = &rest[end + 1..];
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() {
+        tokens.push(Token::Literal(literal));
+    }
+    Ok(tokens)
+}
+
+/// Check a template before it is saved.
+pub fn validate(template: &str) -> Result<(), String> {
+    let template = template.trim();
+    if template.is_empty() {
+        return Ok(());
+    }
+    let tokens = tokenize(template)?;
+    if !tokens.iter().any(|token| matches!(token, Token::Date(_))) {
+        return Err("Filename template needs {date}".to_string());
+    }
+    if !tokens
+        .iter()
+        .any(|token| matches!(token, Token::Time(_) | Token::Uuid))
+    {
+        return Err("Filename template needs {time} or {uuid4} to keep names unique".to_string());
+    }
+    let sample = NaiveDate::from_ymd_opt(2026, 1, 1)
+        .and_then(|date| date.and_hms_opt(1, 1, 1))
+        .ok_or("Invalid sample date")?;
+    for token in &tokens {
+        match token {
+            Token::Date(format) | Token::Time(format) => {
+                if fixed_width(format).is_none() {
+                    return Err(format!("'{}' must always render the same width", format));
+                }
+                let rendered = sample.format(format).to_string();
+                // The name has to be readable back into a date or time.
+                let readable = match token {
+                    Token::Date(_) => NaiveDate::parse_from_str(&rendered, format).is_ok(),
+                    _ => NaiveTime::parse_from_str(&rendered, format).is_ok(),
+                };
+                if !readable || rendered.contains(['/', '\\', ':']) {
+                    return Err(format!("'{}' can't be used in a filename", format));
+                }
+            }
+            Token::Literal(text) if text.contains(['/', '\\']) || text.contains("..") => {
+                return Err("Filename template can't contain '/', '\\' or '..'".to_string());
+            }
+            _ => {}
+        }
+    }
+    if template.starts_with('.') {
+        return Err("Filename template can't start with '.'".to_string());
+    }
+    Ok(())
+}
+
+fn effective(template: &str) -> &str {
+    let template = template.trim();
+    let template = template.strip_suffix(".md").unwrap_or(template);
+    if template.is_empty() || validate(template).is_err() {
+        DEFAULT_FILENAME_TEMPLATE
+    } else {
+        template
+    }
+}
+
+/// Pick up `filename_template` from saved settings.
+pub fn apply_settings(settings: &StikSettings) {
+    let template = effective(&settings.filename_template).to_string();
+    *CONFIGURED.write().unwrap_or_else(|e| e.into_inner()) = Some(template);
+}
+
+pub fn configured_template() -> String {
+    CONFIGURED
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(|| DEFAULT_FILENAME_TEMPLATE.to_string())
+}
+
+/// `template` rendered for a note, with the `.md` extension.
+pub fn render(template: &str, created: NaiveDateTime, slug: &str, uuid: &str) -> String {
+    let tokens = tokenize(effective(template)).unwrap_or_default();
+    let mut name = String::new();
+    for token in tokens {
+        match token {
+            Token::Literal(text) => name.push_str(&text),
+            Token::Date(format) | Token::Time(format) => {
+                name.push_str(&created.format(&format).to_string())
+            }
+            Token::Slug => name.push_str(slug),
+            Token::Uuid => name.push_str(&uuid[..UUID_LENGTH.min(uuid.len())]),
+        }
+    }
+    name.push_str(".md");
+    name
+}
+
+type Fields = (Option<NaiveDate>, Option<NaiveTime>);
+
+fn match_tokens(tokens: &[Token], name: &str, fields: Fields) -> Option<Fields> {
+    let Some((token, rest)) = tokens.split_first() else {
+        return name.is_empty().then_some(fields);
+    };
+    match token {
+        Token::Literal(text) => match_tokens(rest, name.strip_prefix(text.as_str())?, fields),
+        Token::Date(format) => {
+            let width = fixed_width(format)?;
+            let date = NaiveDate::parse_from_str(name.get(..width)?, format).ok()?;
+            match_tokens(rest, &name[width..], (Some(date), fields.1))
+        }
+        Token::Time(format) => {
+            let width = fixed_width(format)?;
+            let time = NaiveTime::parse_from_str(name.get(..width)?, format).ok()?;
+            match_tokens(rest, &name[width..], (fields.0, Some(time)))
+        }
+        Token::Uuid => {
+            let uuid = name.get(..UUID_LENGTH)?;
+            if !uuid.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            match_tokens(rest, &name[UUID_LENGTH..], fields)
+        }
+        // Slugs may contain the template's own separators, so try every
+        // length until the rest of the template matches.
+        Token::Slug => name
+            .char_indices()
+            .skip(1)
+            .map(|(i, _)| i)
+            .chain(std::iter::once(name.len()))
+            .find_map(|end| match_tokens(rest, &name[end..], fields)),
+    }
+}
+
+/// The original scheme: an 8-digit date, then optionally a 6-digit time.
+fn parse_legacy(name: &str) -> Option<CreatedFromFilename> {
+    let mut segments = name.split('-');
+    let date_segment = segments.next()?;
+    if date_segment.len() != 8 {
+        return None;
+    }
+    let date = NaiveDate::parse_from_str(date_segment, "%Y%m%d").ok()?;
+    let time = segments
+        .next()
+        .map(|segment| segment.strip_suffix(".md").unwrap_or(segment))
+        .filter(|segment| segment.len() == 6)
+        .and_then(|segment| NaiveTime::parse_from_str(segment, "%H%M%S").ok());
+    Some(CreatedFromFilename { date, time })
+}
+
+/// When the note named `name` was captured, per `template` or the original
+/// scheme.
+pub fn parse_created_from_filename(template: &str, name: &str) -> Option<CreatedFromFilename> {
+    let stem = name.strip_suffix(".md").unwrap_or(name);
+    let from_template = tokenize(effective(template))
+        .ok()
+        .and_then(|tokens| match_tokens(&tokens, stem, (None, None)))
+        .and_then(|(date, time)| Some(CreatedFromFilename { date: date?, time }));
+    from_template.or_else(|| parse_legacy(name))
+}
+
+/// `parse_created_from_filename` with the configured template.
+pub fn created_from_filename(name: &str) -> Option<CreatedFromFilename> {
+    parse_created_from_filename(&configured_template(), name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn the_default_template_keeps_the_original_names() {
+        let name = render(
+            DEFAULT_FILENAME_TEMPLATE,
+            at("2026-02-06", "10:15:30"),
+            "quarterly-plan",
+            "a1b2c3d4",
+        );
+        assert_eq!(name, "20260206-101530-quarterly-plan-a1b2.md");
+        let created = parse_created_from_filename(DEFAULT_FILENAME_TEMPLATE, &name).unwrap();
+        assert_eq!(created.timestamp(), Some(at("2026-02-06", "10:15:30")));
+    }
+
+    #[test]
+    fn custom_templates_round_trip_and_old_names_still_parse() {
+        let template = "{date:%Y-%m-%d} {slug}-{uuid4}";
+        let name = render(
+            template,
+            at("2026-02-06", "10:15:30"),
+            "team-sync",
+            "beef0000",
+        );
+        assert_eq!(name, "2026-02-06 team-sync-beef.md");
+
+        let created = parse_created_from_filename(template, &name).unwrap();
+        assert_eq!(created.date, NaiveDate::from_ymd_opt(2026, 2, 6).unwrap());
+        assert_eq!(created.time, None);
+
+        let old = parse_created_from_filename(template, "20250101-080000-old-note-1234.md");
+        assert_eq!(
+            old.and_then(|created| created.timestamp()),
+            Some(at("2025-01-01", "08:00:00"))
+        );
+        assert_eq!(
+            parse_created_from_filename(template, "meeting-notes.md"),
+            None
+        );
+    }
+
+    #[test]
+    fn templates_need_a_date_and_a_uniqueness_source() {
+        assert!(validate("{date:%Y-%m-%d} {slug}-{uuid4}").is_ok());
+        assert!(validate("{date}-{time}").is_ok());
+        assert!(validate("{slug}-{uuid4}").is_err());
+        assert!(validate("{date} {slug}").is_err());
+        assert!(validate("{date:%B}-{uuid4}").is_err());
+        assert!(validate("{date:%Y/%m}-{uuid4}").is_err());
+        assert!(validate("{date}-{title}-{uuid4}").is_err());
+        assert!(validate("{date:%Q}-{uuid4}").is_err());
+        assert!(validate("").is_ok());
+    }
+}
@@ -0,0 +1,145 @@
+/// Whether Stik may interrupt right now: quiet hours and, when
+/// `respect_focus_modes` is on, macOS Focus / Do Not Disturb.
+///
+/// Notifications and windows that open on their own check
+/// `should_suppress_interruptions` and retry on their next tick rather
+/// than being dropped, so they arrive once the user is free again.
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::process::Command;
+
+use super::settings::{self, StikSettings};
+
+/// Where macOS 12+ records the manually enabled Focus. Scheduled Focus
+/// periods don't show up here.
+const FOCUS_ASSERTIONS_FILE: &str = "Library/DoNotDisturb/DB/Assertions.json";
+
+#[derive(Debug, Deserialize)]
+struct FocusAssertions {
+    #[serde(default)]
+    data: Vec<FocusAssertionStore>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FocusAssertionStore {
+    #[serde(default, rename = "storeAssertionRecords")]
+    records: Vec<serde_json::Value>,
+}
+
+/// Any Focus assertion means a Focus mode is on.
+fn focus_active_in_assertions(json: &str) -> Option<bool> {
+    let assertions: FocusAssertions = serde_json::from_str(json).ok()?;
+    Some(
+        assertions
+            .data
+            .iter()
+            .any(|store| !store.records.is_empty()),
+    )
+}
+
+/// The pre-Monterey Do Not Disturb flag, as `defaults` prints it.
+fn dnd_active_in_defaults(output: &str) -> bool {
+    output.trim() == "1"
+}
+
+/// Whether a Focus mode or Do Not Disturb is on. False when neither the
+/// assertions file nor the legacy preference can be read.
+pub fn is_focus_active() -> bool {
+    if !cfg!(target_os = "macos") {
+        return false;
+    }
+    let assertions = dirs::home_dir()
+        .map(|home| home.join(FOCUS_ASSERTIONS_FILE))
+        .unwrap_or_else(|| PathBuf::from(FOCUS_ASSERTIONS_FILE));
+    if let Some(active) = std::fs::read_to_string(&assertions)
+        .ok()
+        .and_then(|json| focus_active_in_assertions(&json))
+    {
+        return active;
+    }
+
+    Command::new("defaults")
+        .args([
+            "-currentHost",
+            "read",
+            "com.apple.notificationcenterui",
+            "doNotDisturb",
+        ])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .is_some_and(|output| dnd_active_in_defaults(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn suppress_interruptions(
+    in_quiet_hours: bool,
+    respect_focus_modes: bool,
+    focus_active: bool,
+) -> bool {
+    in_quiet_hours || (respect_focus_modes && focus_active)
+}
+
+fn should_suppress_with(settings: &StikSettings) -> bool {
+    let in_quiet_hours = settings.quiet_hours.is_active_now();
+    // Only ask macOS when the answer matters.
+    suppress_interruptions(
+        in_quiet_hours,
+        settings.respect_focus_modes,
+        !in_quiet_hours && settings.respect_focus_modes && is_focus_active(),
+    )
+}
+
+/// True during quiet hours, or while a Focus mode is on and the user asked
+/// Stik to respect it. Callers defer rather than drop what they'd show.
+pub fn should_suppress_interruptions() -> bool {
+    settings::load_settings_from_file()
+        .map(|settings| should_suppress_with(&settings))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_combination_of_quiet_hours_and_focus() {
+        // (in_quiet_hours, respect_focus_modes, focus_active) -> suppressed
+        let cases = [
+            ((false, false, false), false),
+            ((false, false, true), false),
+            ((false, true, false), false),
+            ((false, true, true), true),
+            ((true, false, false), true),
+            ((true, false, true), true),
+            ((true, true, false), true),
+            ((true, true, true), true),
+        ];
+        for ((quiet, respect, focus), expected) in cases {
+            assert_eq!(
+                suppress_interruptions(quiet, respect, focus),
+                expected,
+                "quiet={} respect={} focus={}",
+                quiet,
+                respect,
+                focus
+            );
+        }
+    }
+
+    #[test]
+    fn focus_assertions_are_read_from_the_store() {
+        let on = r#"{"data":[{"storeAssertionRecords":[{"assertionDetails":{"assertionDetailsModeIdentifier":"com.apple.focus.work"}}]}]}"#;
+        let off = r#"{"data":[{"storeAssertionRecords":[]}]}"#;
+        assert_eq!(focus_active_in_assertions(on), Some(true));
+        assert_eq!(focus_active_in_assertions(off), Some(false));
+        assert_eq!(focus_active_in_assertions(r#"{"data":[{}]}"#), Some(false));
+        assert_eq!(focus_active_in_assertions("not json"), None);
+    }
+
+    #[test]
+    fn legacy_dnd_flag_is_one_when_on() {
+        assert!(dnd_active_in_defaults("1\n"));
+        assert!(!dnd_active_in_defaults("0\n"));
+        assert!(!dnd_active_in_defaults(""));
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use super::settings::StikSettings;
@@ -9,7 +10,28 @@ pub struct FolderStats {
     pub note_count: usize,
 }
 
-fn is_visible_folder_name(name: &str) -> bool {
+/// Folder stats plus the counts shown as badges in the folder selector and
+/// manager.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FolderBadge {
+    pub name: String,
+    pub note_count: usize,
+    pub sticked_count: usize,
+    /// Notes not yet committed to the git-shared repository.
+    pub unsynced_count: usize,
+}
+
+/// A folder appearing, disappearing or being renamed outside Stik, as sent
+/// in `folders-changed`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FolderChange {
+    Created { name: String },
+    Removed { name: String },
+    Renamed { from: String, to: String },
+}
+
+pub(crate) fn is_visible_folder_name(name: &str) -> bool {
     let trimmed = name.trim();
     !trimmed.is_empty() && !trimmed.starts_with('.')
 }
@@ -46,6 +68,14 @@ fn reconcile_settings_after_folder_delete(
         settings.default_folder = fallback.to_string();
     }
 
+    // Empty falls back to the default folder, which was just reconciled.
+    if settings.daily_note_folder == deleted_folder {
+        settings.daily_note_folder.clear();
+    }
+    if settings.sync_summary_folder == deleted_folder {
+        settings.sync_summary_folder.clear();
+    }
+
     for mapping in &mut settings.shortcut_mappings {
         if mapping.folder == deleted_folder {
             mapping.folder = fallback.to_string();
@@ -68,6 +98,13 @@ fn reconcile_settings_after_folder_rename(
         settings.default_folder = new_name.to_string();
     }
 
+    if settings.daily_note_folder == old_name {
+        settings.daily_note_folder = new_name.to_string();
+    }
+    if settings.sync_summary_folder == old_name {
+        settings.sync_summary_folder = new_name.to_string();
+    }
+
     for mapping in &mut settings.shortcut_mappings {
         if mapping.folder == old_name {
             mapping.folder = new_name.to_string();
@@ -100,6 +137,113 @@ fn sync_settings_after_folder_rename(old_name: &str, new_name: &str) -> Result<(
     Ok(())
 }
 
+/// Visible top-level folders with their inode where the platform has one,
+/// for spotting renames made outside Stik.
+pub(crate) fn folder_snapshot(stik_folder: &Path) -> HashMap<String, Option<u64>> {
+    list_visible_folder_names(stik_folder)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|name| {
+            let inode = folder_inode(&stik_folder.join(&name));
+            (name, inode)
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn folder_inode(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(path).ok().map(|meta| meta.ino())
+}
+
+#[cfg(not(unix))]
+fn folder_inode(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// What changed between two snapshots. A folder that vanished is a rename
+/// when one that appeared has its inode, or, without inodes, when it is the
+/// only one that vanished and one appeared in the same batch.
+pub(crate) fn diff_folder_snapshots(
+    before: &HashMap<String, Option<u64>>,
+    after: &HashMap<String, Option<u64>>,
+) -> Vec<FolderChange> {
+    let mut removed: Vec<&String> = before
+        .keys()
+        .filter(|name| !after.contains_key(*name))
+        .collect();
+    let mut created: Vec<&String> = after
+        .keys()
+        .filter(|name| !before.contains_key(*name))
+        .collect();
+    removed.sort();
+    created.sort();
+
+    let mut changes = Vec::new();
+    removed.retain(|from| {
+        let inode = before[*from];
+        let Some(pos) = created
+            .iter()
+            .position(|to| inode.is_some() && after[*to] == inode)
+        else {
+            return true;
+        };
+        let to = created.remove(pos);
+        changes.push(FolderChange::Renamed {
+            from: (*from).clone(),
+            to: to.clone(),
+        });
+        false
+    });
+    if removed.len() == 1 && created.len() == 1 && before[removed[0]].is_none() {
+        changes.push(FolderChange::Renamed {
+            from: removed.remove(0).clone(),
+            to: created.remove(0).clone(),
+        });
+    }
+
+    changes.extend(
+        removed
+            .into_iter()
+            .map(|name| FolderChange::Removed { name: name.clone() }),
+    );
+    changes.extend(
+        created
+            .into_iter()
+            .map(|name| FolderChange::Created { name: name.clone() }),
+    );
+    changes
+}
+
+/// Point settings away from folders removed or renamed outside Stik.
+/// Changes Stik made itself were reconciled already and leave nothing to
+/// save.
+pub(crate) fn sync_settings_after_external_folder_changes(
+    changes: &[FolderChange],
+) -> Result<(), String> {
+    let stik_folder = get_stik_folder()?;
+    let mut settings = super::settings::get_settings()?;
+    let before = serde_json::to_value(&settings).map_err(|e| e.to_string())?;
+
+    let fallback = list_visible_folder_names(&stik_folder)?.into_iter().next();
+    for change in changes {
+        match change {
+            FolderChange::Removed { name } => {
+                reconcile_settings_after_folder_delete(&mut settings, name, fallback.as_deref())
+            }
+            FolderChange::Renamed { from, to } => {
+                reconcile_settings_after_folder_rename(&mut settings, from, to)
+            }
+            FolderChange::Created { .. } => {}
+        }
+    }
+
+    if serde_json::to_value(&settings).map_err(|e| e.to_string())? != before {
+        let _ = super::settings::save_settings(settings)?;
+    }
+    Ok(())
+}
+
 /// Validate a name for path traversal attacks
 pub fn validate_name(name: &str) -> Result<(), String> {
     if name.contains("..") || name.contains('/') || name.contains('\\') || name.contains('\0') {
@@ -126,25 +270,52 @@ pub fn get_notes_directory() -> Result<String, String> {
     Ok(path.to_string_lossy().to_string())
 }
 
+/// Check the configured notes directory again, e.g. once its drive is
+/// mounted, and rebuild the index and start watching it when it's back.
+#[tauri::command]
+pub fn recheck_notes_directory(
+    app: tauri::AppHandle,
+    index: tauri::State<'_, super::index::NoteIndex>,
+) -> Result<super::storage::NotesDirectoryStatus, String> {
+    let status = super::storage::check_notes_directory();
+    if status.available {
+        index.build()?;
+        super::file_watcher::start(app.clone());
+        super::tray_menu::rebuild_tray_menu(&app);
+    }
+    Ok(status)
+}
+
 #[tauri::command]
 pub fn list_folders() -> Result<Vec<String>, String> {
     let stik_folder = get_stik_folder()?;
     list_visible_folder_names(&stik_folder)
 }
 
-#[tauri::command]
-pub fn create_folder(name: String) -> Result<bool, String> {
-    validate_name(&name)?;
-    let stik_folder = get_stik_folder()?;
-    let folder_path = stik_folder.join(&name);
+/// Create the folder `name` unless it's already there. True when it was
+/// created just now.
+pub fn create_folder_inner(name: &str) -> Result<bool, String> {
+    validate_name(name)?;
+    let folder_path = get_stik_folder()?.join(name);
+    let folder_path = folder_path.to_string_lossy();
+    if super::storage::is_dir(&folder_path) {
+        return Ok(false);
+    }
+    super::storage::ensure_dir(&folder_path)?;
+    Ok(true)
+}
 
-    super::storage::ensure_dir(&folder_path.to_string_lossy())?;
+#[tauri::command]
+pub fn create_folder(app: tauri::AppHandle, name: String) -> Result<bool, String> {
+    create_folder_inner(&name)?;
+    super::tray_menu::rebuild_tray_menu(&app);
 
     Ok(true)
 }
 
 #[tauri::command]
 pub fn delete_folder(
+    app: tauri::AppHandle,
     name: String,
     index: tauri::State<'_, super::index::NoteIndex>,
     emb_index: tauri::State<'_, super::embeddings::EmbeddingIndex>,
@@ -164,6 +335,8 @@ pub fn delete_folder(
         .map_err(|e| format!("Failed to delete folder: {}", e))?;
 
     // Purge deleted notes from in-memory indices
+    super::spotlight::folder_removed(&index, &name);
+    super::widget_feed::notes_changed();
     index.remove_by_folder(&name);
     let prefix = folder_path.to_string_lossy();
     emb_index.remove_by_path_prefix(&prefix);
@@ -173,12 +346,17 @@ pub fn delete_folder(
         .into_iter()
         .next();
     sync_settings_after_folder_delete(&name, fallback.as_deref())?;
+    super::tray_menu::rebuild_tray_menu(&app);
 
     Ok(true)
 }
 
 #[tauri::command]
-pub fn rename_folder(old_name: String, new_name: String) -> Result<bool, String> {
+pub fn rename_folder(
+    app: tauri::AppHandle,
+    old_name: String,
+    new_name: String,
+) -> Result<bool, String> {
     validate_name(&old_name)?;
     validate_name(&new_name)?;
 
@@ -200,6 +378,7 @@ pub fn rename_folder(old_name: String, new_name: String) -> Result<bool, String>
     super::storage::move_file(&old_path.to_string_lossy(), &new_path.to_string_lossy())
         .map_err(|e| format!("Failed to rename folder: {}", e))?;
     sync_settings_after_folder_rename(&old_name, &new_name)?;
+    super::tray_menu::rebuild_tray_menu(&app);
 
     Ok(true)
 }
@@ -234,12 +413,32 @@ pub fn get_folder_stats() -> Result<Vec<FolderStats>, String> {
     Ok(stats)
 }
 
+#[tauri::command]
+pub fn get_folder_badges() -> Result<Vec<FolderBadge>, String> {
+    let stats = get_folder_stats()?;
+    let mut sticked_counts: HashMap<String, usize> = HashMap::new();
+    for note in super::sticked_notes::list_sticked_notes().unwrap_or_default() {
+        *sticked_counts.entry(note.folder).or_insert(0) += 1;
+    }
+    let unsynced_counts = super::git_share::unsynced_counts_by_folder();
+
+    Ok(stats
+        .into_iter()
+        .map(|stat| FolderBadge {
+            sticked_count: sticked_counts.get(&stat.name).copied().unwrap_or(0),
+            unsynced_count: unsynced_counts.get(&stat.name).copied().unwrap_or(0),
+            name: stat.name,
+            note_count: stat.note_count,
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
     use super::{
-        is_visible_folder_name, reconcile_settings_after_folder_delete,
-        reconcile_settings_after_folder_rename, validate_name,
+        diff_folder_snapshots, is_visible_folder_name, reconcile_settings_after_folder_delete,
+        reconcile_settings_after_folder_rename, validate_name, FolderChange,
     };
     use crate::commands::settings::{GitSharingSettings, ShortcutMapping, StikSettings};
 
@@ -268,10 +467,50 @@ mod tests {
             },
             folder_colors: HashMap::new(),
             system_shortcuts: HashMap::new(),
+            daily_note_folder: "Inbox".to_string(),
             ..StikSettings::default()
         }
     }
 
+    fn snapshot(folders: &[(&str, Option<u64>)]) -> HashMap<String, Option<u64>> {
+        folders
+            .iter()
+            .map(|(name, inode)| (name.to_string(), *inode))
+            .collect()
+    }
+
+    #[test]
+    fn external_folder_renames_are_paired_by_inode() {
+        let before = snapshot(&[("Inbox", Some(1)), ("Work", Some(2)), ("Old", Some(3))]);
+        let after = snapshot(&[("Inbox", Some(1)), ("Projects", Some(2)), ("New", Some(4))]);
+        assert_eq!(
+            diff_folder_snapshots(&before, &after),
+            vec![
+                FolderChange::Renamed {
+                    from: "Work".to_string(),
+                    to: "Projects".to_string()
+                },
+                FolderChange::Removed {
+                    name: "Old".to_string()
+                },
+                FolderChange::Created {
+                    name: "New".to_string()
+                },
+            ]
+        );
+
+        // No inodes: a lone delete plus create in one batch is a rename.
+        let before = snapshot(&[("Work", None)]);
+        let after = snapshot(&[("Projects", None)]);
+        assert_eq!(
+            diff_folder_snapshots(&before, &after),
+            vec![FolderChange::Renamed {
+                from: "Work".to_string(),
+                to: "Projects".to_string()
+            }]
+        );
+    }
+
     #[test]
     fn rejects_hidden_folder_names() {
         assert!(validate_name(".git").is_err());
@@ -295,6 +534,7 @@ mod tests {
         assert_eq!(settings.shortcut_mappings[0].folder, "Notes");
         assert_eq!(settings.shortcut_mappings[1].folder, "Work");
         assert_eq!(settings.git_sharing.shared_folder, "Notes");
+        assert_eq!(settings.daily_note_folder, "");
     }
 
     #[test]
@@ -316,6 +556,7 @@ mod tests {
         reconcile_settings_after_folder_rename(&mut settings, "Inbox", "Notes");
 
         assert_eq!(settings.default_folder, "Notes");
+        assert_eq!(settings.daily_note_folder, "Notes");
         assert_eq!(settings.shortcut_mappings[0].folder, "Notes");
         assert_eq!(settings.shortcut_mappings[1].folder, "Work");
         assert_eq!(settings.git_sharing.shared_folder, "Notes");
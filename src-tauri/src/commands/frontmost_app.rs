@@ -0,0 +1,70 @@
+/// Shared macOS helper for handing focus back to the app the user was in
+/// before the capture window appeared. Apps are tracked by process id, so
+/// one that has quit in the meantime is simply skipped.
+#[cfg(target_os = "macos")]
+use objc2::rc::Retained;
+#[cfg(target_os = "macos")]
+use objc2::runtime::AnyObject;
+#[cfg(target_os = "macos")]
+use objc2::{class, msg_send};
+
+/// Process id of the frontmost application, which may be Stik itself.
+#[cfg(target_os = "macos")]
+pub fn frontmost_pid() -> Option<i32> {
+    unsafe {
+        let workspace: Retained<AnyObject> = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let app: Option<Retained<AnyObject>> = msg_send![&*workspace, frontmostApplication];
+        app.map(|app| -> i32 { msg_send![&*app, processIdentifier] })
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn frontmost_pid() -> Option<i32> {
+    None
+}
+
+/// Display name of the frontmost application, e.g. "Safari".
+#[cfg(target_os = "macos")]
+pub fn frontmost_name() -> Option<String> {
+    use objc2_foundation::NSString;
+
+    unsafe {
+        let workspace: Retained<AnyObject> = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let app: Option<Retained<AnyObject>> = msg_send![&*workspace, frontmostApplication];
+        let name: Option<Retained<NSString>> = msg_send![&*app?, localizedName];
+        name.map(|name| name.to_string())
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn frontmost_name() -> Option<String> {
+    None
+}
+
+/// Bring the app with `pid` to the front. Returns false if it has quit.
+#[cfg(target_os = "macos")]
+pub fn activate(pid: i32) -> bool {
+    unsafe {
+        let running: Option<Retained<AnyObject>> = msg_send![
+            class!(NSRunningApplication),
+            runningApplicationWithProcessIdentifier: pid
+        ];
+        let Some(running) = running else {
+            return false;
+        };
+        let terminated: bool = msg_send![&*running, isTerminated];
+        if terminated {
+            return false;
+        }
+        msg_send![&*running, activateWithOptions: 0usize]
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn activate(_pid: i32) -> bool {
+    false
+}
+
+pub fn is_stik(pid: i32) -> bool {
+    u32::try_from(pid).is_ok_and(|pid| pid == std::process::id())
+}
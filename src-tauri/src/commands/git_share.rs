@@ -1,5 +1,6 @@
-use chrono::Local;
+use chrono::{DateTime, Local};
 use serde::Serialize;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -7,9 +8,10 @@ use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
 use std::sync::{Mutex, OnceLock};
 use std::thread;
 use std::time::{Duration, Instant};
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 
 use super::folders::{get_stik_folder, validate_name};
+use super::git_credentials;
 use super::index::NoteIndex;
 use super::settings::{self, GitSharingSettings};
 
@@ -17,6 +19,14 @@ const DEFAULT_DEBOUNCE_SECONDS: u64 = 30;
 const DEFAULT_PERIODIC_SYNC_SECONDS: u64 = 300;
 const MIN_PERIODIC_SYNC_SECONDS: u64 = 60;
 const DEFAULT_GITIGNORE_ENTRIES: [&str; 1] = [".DS_Store"];
+const STATUS_LABEL_REFRESH_SECONDS: u64 = 60;
+/// How long folder badges reuse the last `git status` before asking again.
+const UNSYNCED_COUNTS_CACHE_SECONDS: u64 = 5;
+/// Separators for `git log` output in the sync preview.
+const LOG_RECORD_SEPARATOR: char = '\u{1e}';
+const LOG_FIELD_SEPARATOR: char = '\u{1f}';
+/// Start of every commit message Stik writes when syncing.
+pub(crate) const STIK_COMMIT_PREFIX: &str = "stik: sync ";
 
 #[derive(Debug, Clone, Serialize)]
 pub struct GitSyncStatus {
@@ -26,12 +36,49 @@ pub struct GitSyncStatus {
     pub branch: String,
     pub repository_layout: String,
     pub repo_initialized: bool,
+    /// Whether the keychain holds a token for `remote_url`. The token itself
+    /// is never part of the status.
+    pub has_credentials: bool,
     pub pending_changes: bool,
     pub syncing: bool,
     pub last_sync_at: Option<String>,
     pub last_error: Option<String>,
 }
 
+/// A file that would be committed by the next sync.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GitLocalChange {
+    /// Relative to the repository root.
+    pub path: String,
+    /// `added`, `modified`, `deleted`, `renamed` or `conflicted`.
+    pub change: String,
+    /// Where a renamed file came from.
+    pub previous_path: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GitIncomingCommit {
+    pub hash: String,
+    pub author: String,
+    /// ISO 8601 author date.
+    pub date: String,
+    pub message: String,
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GitSyncPreview {
+    pub local_changes: Vec<GitLocalChange>,
+    pub incoming_commits: Vec<GitIncomingCommit>,
+    /// Paths changed both locally and by an incoming commit; the sync will
+    /// keep both versions of these.
+    pub predicted_conflicts: Vec<String>,
+    /// The fetch failed, so `incoming_commits` only covers what was
+    /// already fetched.
+    pub fetch_failed: bool,
+    pub fetch_error: Option<String>,
+}
+
 #[derive(Debug, Clone, Default)]
 struct RuntimeStatus {
     pending_changes: bool,
@@ -49,10 +96,107 @@ struct GitCommandOutput {
 
 #[derive(Debug)]
 enum WorkerMessage {
-    NoteChanged(String),
+    /// Folders whose notes changed; one message per save or bulk operation.
+    NotesChanged(Vec<String>),
+    /// Settings were written; the cached sync scope is stale.
+    SettingsChanged,
     ForceSync,
 }
 
+/// What the worker loop does after handling a message.
+#[derive(Debug, PartialEq, Eq)]
+enum WorkerAction {
+    Ignore,
+    Debounce,
+    SyncNow,
+}
+
+/// Which folders the worker syncs, as of the last settings read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SyncScope {
+    Off,
+    AllFolders,
+    Folder(String),
+}
+
+impl SyncScope {
+    fn from_settings(settings: &settings::StikSettings) -> Self {
+        // iCloud and Git are mutually exclusive (v1 simplicity)
+        if settings.icloud.enabled {
+            return SyncScope::Off;
+        }
+
+        let config = &settings.git_sharing;
+        if !config.enabled || config.remote_url.trim().is_empty() {
+            return SyncScope::Off;
+        }
+
+        match normalized_repository_layout(&config.repository_layout) {
+            "stik_root" => SyncScope::AllFolders,
+            _ => SyncScope::Folder(config.shared_folder.trim().to_string()),
+        }
+    }
+
+    fn includes(&self, folder: &str) -> bool {
+        match self {
+            SyncScope::Off => false,
+            SyncScope::AllFolders => true,
+            SyncScope::Folder(shared) => shared == folder.trim(),
+        }
+    }
+}
+
+/// The worker's sync scope, read from settings on first use and kept until
+/// `ForceSync` or `SettingsChanged`, so a burst of change notifications
+/// costs one settings load.
+#[derive(Debug, Default)]
+struct ScopeCache {
+    scope: Option<SyncScope>,
+}
+
+impl ScopeCache {
+    fn handle(
+        &mut self,
+        message: &WorkerMessage,
+        load_settings: impl FnOnce() -> Result<settings::StikSettings, String>,
+    ) -> WorkerAction {
+        match message {
+            WorkerMessage::NotesChanged(folders) => {
+                if self.any_linked(folders, load_settings) {
+                    WorkerAction::Debounce
+                } else {
+                    WorkerAction::Ignore
+                }
+            }
+            WorkerMessage::SettingsChanged => {
+                self.scope = None;
+                WorkerAction::Ignore
+            }
+            WorkerMessage::ForceSync => {
+                self.scope = None;
+                WorkerAction::SyncNow
+            }
+        }
+    }
+
+    /// A failed settings read isn't cached, so the next message retries.
+    fn any_linked(
+        &mut self,
+        folders: &[String],
+        load_settings: impl FnOnce() -> Result<settings::StikSettings, String>,
+    ) -> bool {
+        if self.scope.is_none() {
+            match load_settings() {
+                Ok(settings) => self.scope = Some(SyncScope::from_settings(&settings)),
+                Err(_) => return false,
+            }
+        }
+        self.scope
+            .as_ref()
+            .is_some_and(|scope| folders.iter().any(|folder| scope.includes(folder)))
+    }
+}
+
 #[derive(Clone, Copy)]
 enum SyncTrigger {
     Startup,
@@ -75,6 +219,8 @@ impl SyncTrigger {
 static RUNTIME_STATUS: OnceLock<Mutex<RuntimeStatus>> = OnceLock::new();
 static WORKER_SENDER: OnceLock<Sender<WorkerMessage>> = OnceLock::new();
 static SYNC_MUTEX: OnceLock<Mutex<()>> = OnceLock::new();
+static STATUS_APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+static UNSYNCED_COUNTS: Mutex<Option<(Instant, HashMap<String, usize>)>> = Mutex::new(None);
 
 fn runtime_status() -> &'static Mutex<RuntimeStatus> {
     RUNTIME_STATUS.get_or_init(|| Mutex::new(RuntimeStatus::default()))
@@ -87,6 +233,54 @@ fn sync_mutex() -> &'static Mutex<()> {
 fn update_runtime_status(update: impl FnOnce(&mut RuntimeStatus)) {
     let mut state = runtime_status().lock().unwrap_or_else(|e| e.into_inner());
     update(&mut state);
+    drop(state);
+    publish_status();
+}
+
+/// Broadcast the current status as `git-sync-status` and mirror it in the
+/// tray. No-op until the background worker has started.
+fn publish_status() {
+    let Some(app) = STATUS_APP.get() else {
+        return;
+    };
+    let Ok(status) = git_get_sync_status() else {
+        return;
+    };
+    let _ = app.emit("git-sync-status", &status);
+    super::tray_menu::update_sync_status(app, &status);
+}
+
+/// Re-broadcast the status after credentials were saved or cleared.
+pub(crate) fn credentials_changed() {
+    publish_status();
+}
+
+/// Short tray label for a sync status, e.g. "✓ Synced 2 min ago".
+pub(crate) fn sync_status_label(status: &GitSyncStatus, now: DateTime<Local>) -> String {
+    if status.syncing {
+        return "↻ Syncing…".to_string();
+    }
+    if status.last_error.is_some() {
+        return "⚠ Sync error".to_string();
+    }
+
+    let last_sync = status
+        .last_sync_at
+        .as_deref()
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok());
+    match last_sync {
+        Some(at) => {
+            let minutes = (now - at.with_timezone(&Local)).num_minutes().max(0);
+            match minutes {
+                0 => "✓ Synced just now".to_string(),
+                1..=59 => format!("✓ Synced {} min ago", minutes),
+                60..=1439 => format!("✓ Synced {} h ago", minutes / 60),
+                _ => format!("✓ Synced {}", at.format("%b %-d")),
+            }
+        }
+        None if status.pending_changes => "↻ Changes pending".to_string(),
+        None => "Not synced yet".to_string(),
+    }
 }
 
 fn snapshot_runtime_status() -> RuntimeStatus {
@@ -105,6 +299,7 @@ pub fn start_background_worker(app: tauri::AppHandle) {
     if WORKER_SENDER.set(sender).is_err() {
         return;
     }
+    let _ = STATUS_APP.set(app.clone());
 
     if let Err(error) = thread::Builder::new()
         .name("stik-git-sync".to_string())
@@ -120,8 +315,26 @@ pub fn start_background_worker(app: tauri::AppHandle) {
 }
 
 pub fn notify_note_changed(folder: &str) {
+    notify_notes_changed(&[folder.to_string()]);
+}
+
+/// One notification for a bulk operation that touched notes in `folders`.
+pub fn notify_notes_changed(folders: &[String]) {
+    if folders.is_empty() {
+        return;
+    }
+    let mut folders = folders.to_vec();
+    folders.sort_unstable();
+    folders.dedup();
+    if let Some(sender) = WORKER_SENDER.get() {
+        let _ = sender.send(WorkerMessage::NotesChanged(folders));
+    }
+}
+
+/// Settings were saved; the worker re-reads its sync scope on next use.
+pub(crate) fn settings_changed() {
     if let Some(sender) = WORKER_SENDER.get() {
-        let _ = sender.send(WorkerMessage::NoteChanged(folder.to_string()));
+        let _ = sender.send(WorkerMessage::SettingsChanged);
     }
 }
 
@@ -134,20 +347,26 @@ pub fn notify_force_sync() {
 fn background_worker_loop(app: tauri::AppHandle, receiver: Receiver<WorkerMessage>) {
     let mut pending_deadline: Option<Instant> = None;
     let mut next_periodic_sync = Instant::now() + periodic_sync_interval();
+    let mut next_label_refresh = Instant::now() + Duration::from_secs(STATUS_LABEL_REFRESH_SECONDS);
+    let mut scope = ScopeCache::default();
 
     loop {
         match receiver.recv_timeout(Duration::from_secs(1)) {
-            Ok(WorkerMessage::NoteChanged(folder)) => {
-                if is_folder_linked_for_sync(&folder) {
+            Ok(message) => match scope.handle(&message, settings::get_settings) {
+                WorkerAction::Debounce => {
                     pending_deadline =
                         Some(Instant::now() + Duration::from_secs(DEFAULT_DEBOUNCE_SECONDS));
                     update_runtime_status(|state| state.pending_changes = true);
                 }
-            }
-            Ok(WorkerMessage::ForceSync) => {
-                run_sync_from_saved_settings(&app, SyncTrigger::Startup);
-                next_periodic_sync = Instant::now() + periodic_sync_interval();
-            }
+                WorkerAction::SyncNow => {
+                    run_sync_from_saved_settings(&app, SyncTrigger::Startup);
+                    next_periodic_sync = Instant::now() + periodic_sync_interval();
+                    // Settings changes land here too; this shows or hides the
+                    // tray status even when no sync ran.
+                    publish_status();
+                }
+                WorkerAction::Ignore => {}
+            },
             Err(RecvTimeoutError::Timeout) => {}
             Err(RecvTimeoutError::Disconnected) => break,
         }
@@ -165,6 +384,12 @@ fn background_worker_loop(app: tauri::AppHandle, receiver: Receiver<WorkerMessag
             run_sync_from_saved_settings(&app, SyncTrigger::Periodic);
             next_periodic_sync = Instant::now() + periodic_sync_interval();
         }
+
+        // Keeps "Synced N min ago" honest between syncs.
+        if Instant::now() >= next_label_refresh {
+            publish_status();
+            next_label_refresh = Instant::now() + Duration::from_secs(STATUS_LABEL_REFRESH_SECONDS);
+        }
     }
 }
 
@@ -180,25 +405,28 @@ fn periodic_sync_interval() -> Duration {
     }
 }
 
-fn is_folder_linked_for_sync(folder: &str) -> bool {
-    let settings = match settings::get_settings() {
-        Ok(settings) => settings,
-        Err(_) => return false,
-    };
-
-    // iCloud and Git are mutually exclusive (v1 simplicity)
+/// Whether moving a note between folders would take it out of the git-linked
+/// folder, i.e. silently remove it from the shared repository.
+pub(crate) fn move_leaves_linked_folder(
+    settings: &settings::StikSettings,
+    from_folder: &str,
+    to_folder: &str,
+) -> bool {
     if settings.icloud.enabled {
         return false;
     }
 
-    let config = settings.git_sharing;
+    let config = &settings.git_sharing;
     if !config.enabled || config.remote_url.trim().is_empty() {
         return false;
     }
 
     match normalized_repository_layout(&config.repository_layout) {
-        "stik_root" => true,
-        _ => config.shared_folder.trim().eq(folder.trim()),
+        "stik_root" => false,
+        _ => {
+            let shared = config.shared_folder.trim();
+            shared == from_folder.trim() && shared != to_folder.trim()
+        }
     }
 }
 
@@ -226,9 +454,17 @@ fn run_sync_from_saved_settings(app: &tauri::AppHandle, trigger: SyncTrigger) {
     rebuild_note_index(app);
 }
 
+/// Sync now, before a maintenance pass changes notes, so it sees what
+/// other machines already did. Blocks until the sync is done; a no-op when
+/// git sharing is off.
+pub(crate) fn sync_before_maintenance(app: &tauri::AppHandle) {
+    run_sync_from_saved_settings(app, SyncTrigger::Periodic);
+}
+
 fn rebuild_note_index(app: &tauri::AppHandle) {
     let index = app.state::<NoteIndex>();
     if let Err(error) = index.build() {
+        log::error!("Index rebuild after git sync failed: {}", error);
         update_runtime_status(|state| {
             state.last_error = Some(format!(
                 "Git sync succeeded but index rebuild failed: {}",
@@ -256,6 +492,7 @@ fn run_sync_operation(config: &GitSharingSettings, trigger: SyncTrigger) -> Resu
         Ok::<(), String>(())
     })();
 
+    invalidate_unsynced_counts();
     update_runtime_status(|state| {
         state.syncing = false;
         match &result {
@@ -268,10 +505,314 @@ fn run_sync_operation(config: &GitSharingSettings, trigger: SyncTrigger) -> Resu
             }
         }
     });
+    if let Err(error) = &result {
+        log::warn!("Git sync failed: {}", error);
+    }
 
     result
 }
 
+/// Notes with uncommitted changes in the linked repository, per Stik
+/// folder. Empty when git sharing is off. Cached for a few seconds so
+/// folder badges can ask on every render.
+pub(crate) fn unsynced_counts_by_folder() -> HashMap<String, usize> {
+    let mut cache = UNSYNCED_COUNTS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((fetched_at, counts)) = cache.as_ref() {
+        if fetched_at.elapsed() < Duration::from_secs(UNSYNCED_COUNTS_CACHE_SECONDS) {
+            return counts.clone();
+        }
+    }
+    let counts = fetch_unsynced_counts().unwrap_or_default();
+    *cache = Some((Instant::now(), counts.clone()));
+    counts
+}
+
+fn invalidate_unsynced_counts() {
+    *UNSYNCED_COUNTS.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+fn fetch_unsynced_counts() -> Option<HashMap<String, usize>> {
+    let settings = settings::get_settings().ok()?;
+    let config = settings.git_sharing;
+    if settings.icloud.enabled || !config.enabled {
+        return None;
+    }
+    let repo_path = linked_folder_path_for_status(&config).ok()?;
+    if !repo_path.join(".git").exists() {
+        return None;
+    }
+    let output = run_git(
+        &repo_path,
+        &["status", "--porcelain", "-z", "--untracked-files=all"],
+    )
+    .ok()?;
+    if output.status_code != Some(0) {
+        return None;
+    }
+    let shared_folder = match normalized_repository_layout(&config.repository_layout) {
+        "stik_root" => None,
+        _ => Some(config.shared_folder.trim()),
+    };
+    Some(count_unsynced_notes(&output.stdout, shared_folder))
+}
+
+/// Count changed `.md` files in `git status --porcelain -z` output by
+/// folder. With a per-folder repository every path belongs to
+/// `shared_folder`; at the Stik root the first path component is the folder.
+fn count_unsynced_notes(porcelain: &str, shared_folder: Option<&str>) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    let mut records = porcelain.split('\0').filter(|record| !record.is_empty());
+    while let Some(record) = records.next() {
+        let (Some(code), Some(path)) = (record.get(..2), record.get(3..)) else {
+            continue;
+        };
+        // Renames and copies are followed by their source path.
+        if code.starts_with(['R', 'C']) {
+            records.next();
+        }
+        if !path.ends_with(".md") {
+            continue;
+        }
+        let folder = match shared_folder {
+            Some(folder) => folder,
+            None => match path.split_once('/') {
+                Some((folder, _)) => folder,
+                None => continue,
+            },
+        };
+        *counts.entry(folder.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn change_kind(code: &str) -> &'static str {
+    match code {
+        "??" => "added",
+        "AA" | "DD" => "conflicted",
+        _ if code.contains('U') => "conflicted",
+        _ if code.contains('R') => "renamed",
+        _ if code.contains('D') => "deleted",
+        _ if code.contains('A') || code.contains('C') => "added",
+        _ => "modified",
+    }
+}
+
+/// `git status --porcelain -z` output as one entry per changed file.
+fn parse_local_changes(porcelain: &str) -> Vec<GitLocalChange> {
+    let mut changes = Vec::new();
+    let mut records = porcelain.split('\0').filter(|record| !record.is_empty());
+    while let Some(record) = records.next() {
+        let (Some(code), Some(path)) = (record.get(..2), record.get(3..)) else {
+            continue;
+        };
+        // Renames and copies are followed by their source path.
+        let previous_path = if code.starts_with(['R', 'C']) {
+            records.next().map(str::to_string)
+        } else {
+            None
+        };
+        changes.push(GitLocalChange {
+            path: path.to_string(),
+            change: change_kind(code).to_string(),
+            previous_path: previous_path.filter(|_| code.starts_with('R')),
+        });
+    }
+    changes
+}
+
+/// `git log --name-only` output written with `LOG_RECORD_SEPARATOR` before
+/// each commit and the header fields split by `LOG_FIELD_SEPARATOR`.
+fn parse_incoming_commits(log: &str) -> Vec<GitIncomingCommit> {
+    log.split(LOG_RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let header = lines.next()?;
+            let mut fields = header.split(LOG_FIELD_SEPARATOR);
+            let hash = fields.next()?.trim();
+            if hash.is_empty() {
+                return None;
+            }
+            Some(GitIncomingCommit {
+                hash: hash.to_string(),
+                author: fields.next().unwrap_or_default().to_string(),
+                date: fields.next().unwrap_or_default().to_string(),
+                message: fields.next().unwrap_or_default().to_string(),
+                files: lines
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+fn predict_conflicts(local: &[GitLocalChange], incoming: &[GitIncomingCommit]) -> Vec<String> {
+    let incoming_files: std::collections::HashSet<&str> = incoming
+        .iter()
+        .flat_map(|commit| commit.files.iter().map(String::as_str))
+        .collect();
+    let mut conflicts: Vec<String> = local
+        .iter()
+        .flat_map(|change| std::iter::once(&change.path).chain(change.previous_path.as_ref()))
+        .filter(|path| incoming_files.contains(path.as_str()))
+        .cloned()
+        .collect();
+    conflicts.sort();
+    conflicts.dedup();
+    conflicts
+}
+
+fn ref_exists(repo_path: &Path, name: &str) -> Result<bool, String> {
+    let output = run_git(repo_path, &["rev-parse", "--verify", "--quiet", name])?;
+    Ok(output.status_code == Some(0))
+}
+
+/// Commits on `origin/<branch>` that HEAD doesn't have yet. Empty when the
+/// remote branch doesn't exist (nothing pushed yet).
+fn incoming_commits(repo_path: &Path, branch: &str) -> Result<Vec<GitIncomingCommit>, String> {
+    let remote_ref = format!("origin/{}", branch);
+    if !ref_exists(repo_path, &remote_ref)? {
+        return Ok(Vec::new());
+    }
+    let head_exists = ref_exists(repo_path, "HEAD")?;
+    let range = if head_exists {
+        format!("HEAD..{}", remote_ref)
+    } else {
+        remote_ref
+    };
+    let format = format!(
+        "--format={}%H{}%an{}%aI{}%s",
+        LOG_RECORD_SEPARATOR, LOG_FIELD_SEPARATOR, LOG_FIELD_SEPARATOR, LOG_FIELD_SEPARATOR
+    );
+    let output = run_git(repo_path, &["log", &format, "--name-only", &range, "--"])?;
+    if output.status_code != Some(0) {
+        return Err(format!(
+            "Failed to list incoming commits: {}",
+            command_error_message(&output)
+        ));
+    }
+    Ok(parse_incoming_commits(&output.stdout))
+}
+
+/// The shared repository's commits since a point in time.
+pub(crate) struct SharedHistory {
+    pub repo_path: PathBuf,
+    /// `user.name` of this machine's repository, which signs its syncs.
+    pub local_author: Option<String>,
+    /// Newest first.
+    pub commits: Vec<GitIncomingCommit>,
+}
+
+/// Every commit on HEAD authored since `since`, with the files each
+/// touched. Errors when git sharing is off or the repository isn't set up.
+pub(crate) fn shared_history(since: DateTime<Local>) -> Result<SharedHistory, String> {
+    let settings = settings::get_settings()?;
+    if settings.icloud.enabled || !settings.git_sharing.enabled {
+        return Err("Git sharing isn't configured: turn on Git sync in Settings first".to_string());
+    }
+    let repo_path = linked_folder_path_for_status(&settings.git_sharing)?;
+    if !repo_path.join(".git").exists() {
+        return Err("Git sync hasn't been set up for this folder yet".to_string());
+    }
+
+    let _sync_guard = sync_mutex().lock().unwrap_or_else(|e| e.into_inner());
+    let local_author = git_config_value(&repo_path, "user.name")?;
+    if !ref_exists(&repo_path, "HEAD")? {
+        return Ok(SharedHistory {
+            repo_path,
+            local_author,
+            commits: Vec::new(),
+        });
+    }
+    let format = format!(
+        "--format={}%H{}%an{}%aI{}%s",
+        LOG_RECORD_SEPARATOR, LOG_FIELD_SEPARATOR, LOG_FIELD_SEPARATOR, LOG_FIELD_SEPARATOR
+    );
+    let since = format!("--since={}", since.to_rfc3339());
+    let output = run_git(
+        &repo_path,
+        &["log", &format, "--name-only", &since, "HEAD", "--"],
+    )?;
+    if output.status_code != Some(0) {
+        return Err(format!(
+            "Failed to read shared history: {}",
+            command_error_message(&output)
+        ));
+    }
+    Ok(SharedHistory {
+        repo_path,
+        local_author,
+        commits: parse_incoming_commits(&output.stdout),
+    })
+}
+
+fn preview_sync(config: &GitSharingSettings) -> Result<GitSyncPreview, String> {
+    let repo_path = linked_folder_path_for_status(config)?;
+    if !repo_path.join(".git").exists() {
+        return Err("Git sync hasn't been set up for this folder yet".to_string());
+    }
+    let branch = normalized_branch(&config.branch);
+
+    // Held only for the git calls, so the preview never reads a
+    // half-finished sync or fetches underneath one.
+    let sync_guard = sync_mutex().lock().unwrap_or_else(|e| e.into_inner());
+    let status = run_git(
+        &repo_path,
+        &["status", "--porcelain", "-z", "--untracked-files=all"],
+    )?;
+    if status.status_code != Some(0) {
+        return Err(format!(
+            "Failed to inspect repository status: {}",
+            command_error_message(&status)
+        ));
+    }
+    let local_changes = parse_local_changes(&status.stdout);
+
+    let fetch = run_git(&repo_path, &["fetch", "origin", &branch])?;
+    let fetch_error = match fetch.status_code {
+        Some(0) => None,
+        _ => {
+            let error = command_error_message(&fetch);
+            let lower = error.to_lowercase();
+            // A branch that was never pushed has nothing incoming.
+            (!lower.contains("couldn't find remote ref")).then_some(error)
+        }
+    };
+    if let Some(error) = &fetch_error {
+        log::warn!("Sync preview fetch failed: {}", error);
+    }
+    let incoming_commits = incoming_commits(&repo_path, &branch).unwrap_or_else(|error| {
+        log::warn!("{}", error);
+        Vec::new()
+    });
+    drop(sync_guard);
+
+    Ok(GitSyncPreview {
+        predicted_conflicts: predict_conflicts(&local_changes, &incoming_commits),
+        local_changes,
+        incoming_commits,
+        fetch_failed: fetch_error.is_some(),
+        fetch_error,
+    })
+}
+
+/// What "Sync now" would do, without committing anything: local changes,
+/// incoming commits and the files both sides touched. A failed fetch
+/// still returns the local side, with `fetch_failed` set.
+#[tauri::command]
+pub async fn git_preview_sync() -> Result<GitSyncPreview, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let settings = settings::get_settings()?;
+        if settings.icloud.enabled || !settings.git_sharing.enabled {
+            return Err("Git sync is off".to_string());
+        }
+        preview_sync(&settings.git_sharing)
+    })
+    .await
+    .map_err(|e| format!("Failed to preview sync: {}", e))?
+}
+
 #[tauri::command]
 pub async fn git_prepare_repository(
     folder: String,
@@ -353,6 +894,10 @@ fn status_for_config(config: Option<&GitSharingSettings>) -> GitSyncStatus {
         .map(|path| path.join(".git").exists())
         .unwrap_or(false);
 
+    let has_credentials = normalized_remote
+        .as_deref()
+        .is_some_and(git_credentials::has_credentials);
+
     GitSyncStatus {
         enabled: config.enabled,
         linked_folder: normalized_folder,
@@ -360,6 +905,7 @@ fn status_for_config(config: Option<&GitSharingSettings>) -> GitSyncStatus {
         branch,
         repository_layout,
         repo_initialized,
+        has_credentials,
         pending_changes: runtime.pending_changes,
         syncing: runtime.syncing,
         last_sync_at: runtime.last_sync_at,
@@ -432,6 +978,7 @@ fn ensure_repository_ready(repo_path: &Path, config: &GitSharingSettings) -> Res
     ensure_local_identity(repo_path)?;
     ensure_repository_gitignore(repo_path)?;
     configure_origin_remote(repo_path, config.remote_url.trim())?;
+    configure_credential_helper(repo_path, config.remote_url.trim())?;
     run_git_success(
         repo_path,
         &["checkout", "-B", &branch],
@@ -500,6 +1047,30 @@ fn configure_origin_remote(repo_path: &Path, remote_url: &str) -> Result<(), Str
     }
 }
 
+/// Point HTTPS remotes at the keychain-backed helper. Rewritten on every
+/// sync so the helper path follows the app if it was moved.
+fn configure_credential_helper(repo_path: &Path, remote_url: &str) -> Result<(), String> {
+    if !remote_url.starts_with("https://") && !remote_url.starts_with("http://") {
+        return Ok(());
+    }
+    let helper = git_credentials::helper_command()?;
+    if git_config_value(repo_path, "credential.helper")?.as_deref() != Some(helper.as_str()) {
+        run_git_success(
+            repo_path,
+            &["config", "--replace-all", "credential.helper", &helper],
+            "configure git credential helper",
+        )?;
+    }
+    if git_config_value(repo_path, "credential.useHttpPath")?.as_deref() != Some("true") {
+        run_git_success(
+            repo_path,
+            &["config", "credential.useHttpPath", "true"],
+            "configure git credential helper",
+        )?;
+    }
+    Ok(())
+}
+
 fn commit_local_changes(repo_path: &Path, trigger: SyncTrigger) -> Result<(), String> {
     run_git_success(repo_path, &["add", "-A"], "stage note changes")?;
 
@@ -515,7 +1086,8 @@ fn commit_local_changes(repo_path: &Path, trigger: SyncTrigger) -> Result<(), St
     }
 
     let commit_message = format!(
-        "stik: sync {} notes ({})",
+        "{}{} notes ({})",
+        STIK_COMMIT_PREFIX,
         Local::now().format("%Y-%m-%d %H:%M:%S"),
         trigger.commit_label()
     );
@@ -884,17 +1456,8 @@ pub fn git_open_remote_url(remote_url: String) -> Result<String, String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::test_support::temp_dir;
     use std::fs;
-    use std::path::PathBuf;
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    fn unique_temp_dir(label: &str) -> PathBuf {
-        let nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("clock should be monotonic")
-            .as_nanos();
-        std::env::temp_dir().join(format!("stik-git-share-{label}-{nanos}"))
-    }
 
     fn folder_root_config(folder: &str) -> GitSharingSettings {
         GitSharingSettings {
@@ -950,6 +1513,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn moving_out_of_linked_folder_is_detected() {
+        let mut settings = settings::StikSettings::default();
+        settings.git_sharing = GitSharingSettings {
+            enabled: true,
+            remote_url: "git@github.com:team/notes.git".to_string(),
+            ..folder_root_config("Inbox")
+        };
+
+        assert!(move_leaves_linked_folder(&settings, "Inbox", "Work"));
+        assert!(!move_leaves_linked_folder(&settings, "Ideas", "Work"));
+
+        settings.git_sharing.repository_layout = "stik_root".to_string();
+        assert!(!move_leaves_linked_folder(&settings, "Inbox", "Work"));
+    }
+
+    #[test]
+    fn one_settings_read_serves_a_burst_of_notifications() {
+        let mut settings = settings::StikSettings::default();
+        settings.git_sharing = GitSharingSettings {
+            enabled: true,
+            remote_url: "git@github.com:team/notes.git".to_string(),
+            ..folder_root_config("Inbox")
+        };
+        let loads = std::cell::Cell::new(0);
+        let load = || {
+            loads.set(loads.get() + 1);
+            Ok(settings.clone())
+        };
+
+        let mut scope = ScopeCache::default();
+        for _ in 0..200 {
+            let message = WorkerMessage::NotesChanged(vec!["Inbox".to_string()]);
+            assert_eq!(scope.handle(&message, load), WorkerAction::Debounce);
+        }
+        let elsewhere = WorkerMessage::NotesChanged(vec!["Work".to_string()]);
+        assert_eq!(scope.handle(&elsewhere, load), WorkerAction::Ignore);
+        assert_eq!(loads.get(), 1);
+
+        assert_eq!(
+            scope.handle(&WorkerMessage::SettingsChanged, load),
+            WorkerAction::Ignore
+        );
+        let batch = WorkerMessage::NotesChanged(vec!["Work".to_string(), "Inbox".to_string()]);
+        assert_eq!(scope.handle(&batch, load), WorkerAction::Debounce);
+        assert_eq!(loads.get(), 2);
+
+        assert_eq!(
+            scope.handle(&WorkerMessage::ForceSync, load),
+            WorkerAction::SyncNow
+        );
+        assert_eq!(scope.handle(&batch, load), WorkerAction::Debounce);
+        assert_eq!(loads.get(), 3);
+    }
+
+    #[test]
+    fn failed_settings_reads_are_retried() {
+        let mut scope = ScopeCache::default();
+        let message = WorkerMessage::NotesChanged(vec!["Inbox".to_string()]);
+        assert_eq!(
+            scope.handle(&message, || Err("unreadable".to_string())),
+            WorkerAction::Ignore
+        );
+        assert_eq!(
+            scope.handle(&message, || Ok(settings::StikSettings::default())),
+            WorkerAction::Ignore
+        );
+        assert_eq!(scope.scope, Some(SyncScope::Off));
+    }
+
     #[test]
     fn converts_git_ssh_remote_to_browser_url() {
         let url = remote_to_browser_url("git@github.com:0xMassi/stik_notes.git").unwrap();
@@ -964,8 +1597,7 @@ mod tests {
 
     #[test]
     fn folder_path_resolution_does_not_create_folder_when_not_requested() {
-        let root = unique_temp_dir("status-no-create");
-        fs::create_dir_all(&root).expect("temp root should be created");
+        let root = temp_dir("git-share-status-no-create");
         let config = folder_root_config("Inbox");
         let expected = root.join("Inbox");
 
@@ -983,8 +1615,7 @@ mod tests {
 
     #[test]
     fn folder_path_resolution_creates_folder_when_requested() {
-        let root = unique_temp_dir("sync-create");
-        fs::create_dir_all(&root).expect("temp root should be created");
+        let root = temp_dir("git-share-sync-create");
         let config = folder_root_config("Inbox");
         let expected = root.join("Inbox");
 
@@ -999,4 +1630,101 @@ mod tests {
 
         let _ = fs::remove_dir_all(&root);
     }
+
+    fn sync_status(
+        syncing: bool,
+        last_sync_at: Option<String>,
+        last_error: Option<&str>,
+    ) -> GitSyncStatus {
+        GitSyncStatus {
+            enabled: true,
+            linked_folder: Some("Shared".to_string()),
+            remote_url: Some("git@github.com:me/notes.git".to_string()),
+            branch: "main".to_string(),
+            repository_layout: "stik_root".to_string(),
+            repo_initialized: true,
+            has_credentials: false,
+            pending_changes: false,
+            syncing,
+            last_sync_at,
+            last_error: last_error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn sync_status_label_reflects_state() {
+        let now = Local::now();
+        let two_minutes_ago = (now - chrono::Duration::minutes(2)).to_rfc3339();
+
+        assert_eq!(
+            sync_status_label(
+                &sync_status(false, Some(two_minutes_ago.clone()), None),
+                now
+            ),
+            "✓ Synced 2 min ago"
+        );
+        assert_eq!(
+            sync_status_label(&sync_status(true, Some(two_minutes_ago.clone()), None), now),
+            "↻ Syncing…"
+        );
+        assert_eq!(
+            sync_status_label(
+                &sync_status(false, Some(two_minutes_ago), Some("push rejected")),
+                now
+            ),
+            "⚠ Sync error"
+        );
+        assert_eq!(
+            sync_status_label(&sync_status(false, None, None), now),
+            "Not synced yet"
+        );
+    }
+
+    #[test]
+    fn unsynced_notes_are_counted_per_folder() {
+        let porcelain = " M Work/plan.md\0?? Work/new.md\0R  Inbox/b.md\0Work/b.md\0 M .gitignore\0 D Inbox/photo.png\0";
+        let counts = count_unsynced_notes(porcelain, None);
+        assert_eq!(counts.get("Work"), Some(&2));
+        assert_eq!(counts.get("Inbox"), Some(&1));
+        assert_eq!(counts.len(), 2);
+
+        let shared = count_unsynced_notes(" M plan.md\0?? ideas/new.md\0", Some("Work"));
+        assert_eq!(shared.get("Work"), Some(&2));
+    }
+
+    #[test]
+    fn porcelain_becomes_typed_changes() {
+        let porcelain =
+            " M Work/plan.md\0?? Work/new.md\0R  Inbox/b.md\0Work/b.md\0 D a.md\0UU c.md\0";
+        let changes = parse_local_changes(porcelain);
+        let kinds: Vec<(&str, &str)> = changes
+            .iter()
+            .map(|change| (change.path.as_str(), change.change.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("Work/plan.md", "modified"),
+                ("Work/new.md", "added"),
+                ("Inbox/b.md", "renamed"),
+                ("a.md", "deleted"),
+                ("c.md", "conflicted"),
+            ]
+        );
+        assert_eq!(changes[2].previous_path.as_deref(), Some("Work/b.md"));
+    }
+
+    #[test]
+    fn incoming_log_is_parsed_and_overlaps_predicted() {
+        let log = "\u{1e}abc123\u{1f}Ada\u{1f}2026-02-06T10:00:00+01:00\u{1f}Edit plan\n\nWork/plan.md\nWork/other.md\n\u{1e}def456\u{1f}Linus\u{1f}2026-02-05T09:00:00+01:00\u{1f}Empty\n";
+        let incoming = parse_incoming_commits(log);
+        assert_eq!(incoming.len(), 2);
+        assert_eq!(incoming[0].author, "Ada");
+        assert_eq!(incoming[0].message, "Edit plan");
+        assert_eq!(incoming[0].files, vec!["Work/plan.md", "Work/other.md"]);
+        assert!(incoming[1].files.is_empty());
+
+        let local = parse_local_changes(" M Work/plan.md\0?? Work/new.md\0");
+        assert_eq!(predict_conflicts(&local, &incoming), vec!["Work/plan.md"]);
+    }
 }
@@ -1,16 +1,53 @@
 use std::collections::HashMap;
 use std::fs;
+use std::io::Read;
 use std::path::PathBuf;
 use std::sync::Mutex;
-use std::time::Instant;
 use std::time::SystemTime;
+use std::time::{Duration, Instant};
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::Serialize;
 
+use super::filename_template;
 use super::folders::get_stik_folder;
+use super::tasks::{self, TaskCounts};
+use super::{cloud_sync, expiry, language, note_ids, note_metadata, pins};
 
 const PREVIEW_LENGTH: usize = 150;
 const STALE_SECONDS: u64 = 60;
+const WORDS_PER_MINUTE: usize = 200;
+/// Floor for the `index_read_limit_kb` setting.
+const MIN_READ_LIMIT_KB: usize = 16;
+/// Bytes read at a time when search falls back to a note's full text.
+const SEARCH_CHUNK_BYTES: usize = 64 * 1024;
+
+/// How many notes match a search in each folder and with each tag, across
+/// all folders whatever folder the results are filtered to.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SearchFacets {
+    pub total: usize,
+    pub folders: Vec<FacetCount>,
+    /// Lowercased tags from each match's indexed preview: frontmatter and
+    /// hashtags near the top of the note.
+    pub tags: Vec<FacetCount>,
+}
+
+/// Most matches first, then by name.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FacetCount {
+    pub name: String,
+    pub count: usize,
+}
+
+fn facet_counts(counts: HashMap<String, usize>) -> Vec<FacetCount> {
+    let mut facets: Vec<FacetCount> = counts
+        .into_iter()
+        .map(|(name, count)| FacetCount { name, count })
+        .collect();
+    facets.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    facets
+}
 
 #[derive(Debug, Clone)]
 pub struct NoteEntry {
@@ -22,11 +59,56 @@ pub struct NoteEntry {
     pub created: String,
     pub content_len: usize,
     pub locked: bool,
+    /// Words outside code fences; 0 for locked notes.
+    pub word_count: usize,
+    pub reading_minutes: usize,
+    /// BCP 47 code, or "und" when too short to tell.
+    pub language: String,
+    /// Larger than the index read limit: title, preview, word count and
+    /// language come from the start of the file only.
+    pub truncated_in_index: bool,
+    /// Stable id from the `stik-id:` frontmatter key, once it has one.
+    pub id: Option<String>,
+    /// `pinned: true` in the frontmatter; sorts first in lists and search.
+    pub pinned: bool,
+    /// Capture day: from the filename, else the file's modification day.
+    /// What stats count streaks and heatmaps by.
+    pub created_date: Option<NaiveDate>,
+    /// Unchecked `- [ ]` tasks outside code fences; 0 for locked notes.
+    pub open_tasks: usize,
+    /// Checked `- [x]` tasks outside code fences; 0 for locked notes.
+    pub done_tasks: usize,
 }
 
 pub struct NoteIndex {
     entries: Mutex<HashMap<String, NoteEntry>>,
     built_at: Mutex<Option<Instant>>,
+    /// Held for the length of a build, so a search arriving while the
+    /// startup build is running waits for it instead of scanning again.
+    building: Mutex<()>,
+    /// Languages from NLLanguageRecognizer, via the embedding build. They
+    /// win over the heuristic and survive rebuilds.
+    recognized_languages: Mutex<HashMap<String, String>>,
+    /// Note id → current path, for entries that carry an id. Always locked
+    /// after `entries`.
+    paths_by_id: Mutex<HashMap<String, String>>,
+}
+
+/// Keep `paths_by_id` in step with an entry being replaced by `new`
+/// (`None` when it is removed).
+fn update_id(ids: &mut HashMap<String, String>, old: Option<&NoteEntry>, new: Option<&NoteEntry>) {
+    if let Some(old) = old {
+        if let Some(id) = &old.id {
+            if ids.get(id) == Some(&old.path) {
+                ids.remove(id);
+            }
+        }
+    }
+    if let Some(new) = new {
+        if let Some(id) = &new.id {
+            ids.insert(id.clone(), new.path.clone());
+        }
+    }
 }
 
 impl NoteIndex {
@@ -34,13 +116,52 @@ impl NoteIndex {
         Self {
             entries: Mutex::new(HashMap::new()),
             built_at: Mutex::new(None),
+            building: Mutex::new(()),
+            recognized_languages: Mutex::new(HashMap::new()),
+            paths_by_id: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn apply_recognized_language(&self, entry: &mut NoteEntry) {
+        if entry.locked {
+            return;
+        }
+        let recognized = self
+            .recognized_languages
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(language) = recognized.get(&entry.path) {
+            entry.language = language::note_language(entry.word_count, Some(language), "");
+        }
+    }
+
+    /// Record the recognizer's language for a note, e.g. from its embedding.
+    pub fn set_language(&self, path: &str, recognized: &str) {
+        self.recognized_languages
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(path.to_string(), recognized.to_string());
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = entries.get_mut(path) {
+            if !entry.locked {
+                entry.language = language::note_language(entry.word_count, Some(recognized), "");
+            }
         }
     }
 
     pub fn build(&self) -> Result<(), String> {
+        let _building = self.building.lock().unwrap_or_else(|e| e.into_inner());
+        self.scan()
+    }
+
+    fn scan(&self) -> Result<(), String> {
+        let read_limit = read_limit_bytes();
         let stik_folder = get_stik_folder()?;
         let stik_path = stik_folder.to_string_lossy();
         let mut new_entries = HashMap::new();
+        // Evicted cloud files keep their previous entry instead of being
+        // read as empty.
+        let mut evicted = Vec::new();
 
         let dir_entries = super::storage::list_dir(&stik_path)?;
 
@@ -57,7 +178,14 @@ impl NoteIndex {
                 for file in files {
                     if !file.is_directory && file.name.ends_with(".md") {
                         let path = folder_path.join(&file.name);
-                        if let Some(note_entry) = read_note_entry(&path, folder_name) {
+                        if cloud_sync::looks_evicted(&path) {
+                            evicted.push(path.to_string_lossy().to_string());
+                            continue;
+                        }
+                        if let Some(mut note_entry) =
+                            read_note_entry(&path, folder_name, read_limit)
+                        {
+                            self.apply_recognized_language(&mut note_entry);
                             new_entries.insert(note_entry.path.clone(), note_entry);
                         }
                     }
@@ -69,14 +197,28 @@ impl NoteIndex {
         for dir_entry in &dir_entries {
             if !dir_entry.is_directory && dir_entry.name.ends_with(".md") {
                 let path = stik_folder.join(&dir_entry.name);
-                if let Some(note_entry) = read_note_entry(&path, "") {
+                if cloud_sync::looks_evicted(&path) {
+                    evicted.push(path.to_string_lossy().to_string());
+                    continue;
+                }
+                if let Some(mut note_entry) = read_note_entry(&path, "", read_limit) {
+                    self.apply_recognized_language(&mut note_entry);
                     new_entries.insert(note_entry.path.clone(), note_entry);
                 }
             }
         }
 
         let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        for path in evicted {
+            if let Some(entry) = entries.remove(&path) {
+                new_entries.insert(path, entry);
+            }
+        }
         *entries = new_entries;
+        *self.paths_by_id.lock().unwrap_or_else(|e| e.into_inner()) = entries
+            .values()
+            .filter_map(|entry| Some((entry.id.clone()?, entry.path.clone())))
+            .collect();
 
         let mut built_at = self.built_at.lock().unwrap_or_else(|e| e.into_inner());
         *built_at = Some(Instant::now());
@@ -84,16 +226,36 @@ impl NoteIndex {
         Ok(())
     }
 
-    fn ensure_fresh(&self) -> Result<(), String> {
+    /// Time since the last full scan, `None` before the first one.
+    pub fn age(&self) -> Option<Duration> {
+        self.built_at
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .map(|t| t.elapsed())
+    }
+
+    /// When the last full scan finished. Callers caching data derived from
+    /// the index key it on this.
+    pub fn built_at(&self) -> Option<Instant> {
+        *self.built_at.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn is_stale(&self) -> bool {
         let built_at = self.built_at.lock().unwrap_or_else(|e| e.into_inner());
-        let needs_rebuild = match *built_at {
+        match *built_at {
             Some(t) => t.elapsed().as_secs() > STALE_SECONDS,
             None => true,
-        };
-        drop(built_at);
+        }
+    }
 
-        if needs_rebuild {
-            self.build()?;
+    fn ensure_fresh(&self) -> Result<(), String> {
+        if !self.is_stale() {
+            return Ok(());
+        }
+        let _building = self.building.lock().unwrap_or_else(|e| e.into_inner());
+        // Another caller may have finished a build while we waited.
+        if self.is_stale() {
+            self.scan()?;
         }
         Ok(())
     }
@@ -101,29 +263,67 @@ impl NoteIndex {
     pub fn add(&self, path: &str, folder: &str) {
         let note_path = PathBuf::from(path);
         let folder_name = folder.to_string();
-        if let Some(entry) = read_note_entry(&note_path, &folder_name) {
+        if let Some(mut entry) = read_note_entry(&note_path, &folder_name, read_limit_bytes()) {
+            self.apply_recognized_language(&mut entry);
             let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+            let mut ids = self.paths_by_id.lock().unwrap_or_else(|e| e.into_inner());
+            update_id(&mut ids, entries.get(&entry.path), Some(&entry));
             entries.insert(entry.path.clone(), entry);
         }
     }
 
     pub fn remove(&self, path: &str) {
         let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
-        entries.remove(path);
+        let mut ids = self.paths_by_id.lock().unwrap_or_else(|e| e.into_inner());
+        update_id(&mut ids, entries.remove(path).as_ref(), None);
+        self.recognized_languages
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(path);
+    }
+
+    /// `remove` for many notes under a single lock.
+    pub fn remove_many(&self, paths: &[String]) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut ids = self.paths_by_id.lock().unwrap_or_else(|e| e.into_inner());
+        let mut languages = self
+            .recognized_languages
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        for path in paths {
+            update_id(&mut ids, entries.remove(path).as_ref(), None);
+            languages.remove(path);
+        }
     }
 
     pub fn remove_by_folder(&self, folder: &str) {
         let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
         entries.retain(|_, e| e.folder != folder);
+        self.paths_by_id
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|_, path| entries.contains_key(path));
     }
 
+    /// Re-key a note after a move or rename; its id now resolves to
+    /// `new_path`.
     pub fn move_entry(&self, old_path: &str, new_path: &str, new_folder: &str) {
         let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
         if let Some(mut entry) = entries.remove(old_path) {
+            let mut ids = self.paths_by_id.lock().unwrap_or_else(|e| e.into_inner());
+            let old = entry.clone();
             entry.path = new_path.to_string();
             entry.folder = new_folder.to_string();
+            update_id(&mut ids, Some(&old), Some(&entry));
             entries.insert(new_path.to_string(), entry);
         }
+        let mut recognized = self
+            .recognized_languages
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(language) = recognized.remove(old_path) {
+            recognized.insert(new_path.to_string(), language);
+        }
     }
 
     /// Handle external changes from iCloud sync — re-index specific paths.
@@ -134,7 +334,9 @@ impl NoteIndex {
             Err(_) => return,
         };
 
+        let read_limit = read_limit_bytes();
         let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut ids = self.paths_by_id.lock().unwrap_or_else(|e| e.into_inner());
 
         for path_str in paths {
             let path = PathBuf::from(path_str);
@@ -160,13 +362,20 @@ impl NoteIndex {
                 })
                 .unwrap_or_default();
 
+            // An evicted cloud file reads as empty; keep what we had.
+            if entries.contains_key(path_str.as_str()) && cloud_sync::looks_evicted(&path) {
+                continue;
+            }
+
             // Try to re-index — if file was deleted, remove from index
             if super::storage::path_exists(path_str) {
-                if let Some(entry) = read_note_entry(&path, &folder) {
+                if let Some(mut entry) = read_note_entry(&path, &folder, read_limit) {
+                    self.apply_recognized_language(&mut entry);
+                    update_id(&mut ids, entries.get(&entry.path), Some(&entry));
                     entries.insert(entry.path.clone(), entry);
                 }
             } else {
-                entries.remove(path_str);
+                update_id(&mut ids, entries.remove(path_str).as_ref(), None);
             }
         }
     }
@@ -176,6 +385,26 @@ impl NoteIndex {
         entries.get(path).cloned()
     }
 
+    /// Current path of the note with this id, after a rebuild if the index
+    /// is stale (a note moved outside Stik shows up under its new path).
+    pub fn path_for_id(&self, id: &str) -> Result<Option<String>, String> {
+        self.ensure_fresh()?;
+        let _entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let ids = self.paths_by_id.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(ids.get(id).cloned())
+    }
+
+    /// All entries as currently indexed, without a staleness rebuild.
+    /// None until the index has been built once.
+    pub fn snapshot(&self) -> Option<Vec<NoteEntry>> {
+        let built_at = self.built_at.lock().unwrap_or_else(|e| e.into_inner());
+        built_at.as_ref()?;
+        drop(built_at);
+
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        Some(entries.values().cloned().collect())
+    }
+
     pub fn list(&self, folder: Option<&str>) -> Result<Vec<NoteEntry>, String> {
         self.ensure_fresh()?;
         let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
@@ -186,7 +415,7 @@ impl NoteIndex {
             .cloned()
             .collect();
 
-        result.sort_by(|a, b| b.created.cmp(&a.created));
+        result.sort_by(pinned_then_newest);
         Ok(result)
     }
 
@@ -195,39 +424,75 @@ impl NoteIndex {
         query: &str,
         folder: Option<&str>,
     ) -> Result<Vec<(NoteEntry, String)>, String> {
+        self.search_with_facets(query, folder, false)
+            .map(|(results, _)| results)
+    }
+
+    /// `search`, optionally counting matches per folder and tag in the same
+    /// pass. Facets ignore `folder` so the sidebar can show every folder's
+    /// count while one is selected.
+    pub fn search_with_facets(
+        &self,
+        query: &str,
+        folder: Option<&str>,
+        include_facets: bool,
+    ) -> Result<(Vec<(NoteEntry, String)>, Option<SearchFacets>), String> {
         self.ensure_fresh()?;
         let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let (query, language_filter) = parse_search_query(query);
+        let query = query.as_str();
         let query_lower = query.to_lowercase();
+        let icloud = super::storage::current_mode() == super::storage::StorageMode::ICloud;
 
         let mut results: Vec<(NoteEntry, String)> = Vec::new();
+        let mut folder_counts: HashMap<String, usize> = HashMap::new();
+        let mut tag_counts: HashMap<String, usize> = HashMap::new();
 
         for entry in entries.values() {
             if entry.locked {
                 continue; // Can't search encrypted content
             }
-            if let Some(f) = folder {
-                if entry.folder != f {
+            let in_folder = folder.is_none() || folder == Some(entry.folder.as_str());
+            if !in_folder && !include_facets {
+                continue;
+            }
+            if let Some(filter) = language_filter.as_deref() {
+                if !language::matches_filter(&entry.language, filter) {
                     continue;
                 }
             }
 
             let preview_lower = entry.preview.to_lowercase();
-            if preview_lower.contains(&query_lower) {
-                let snippet = extract_snippet(&entry.preview, query, 100);
-                results.push((entry.clone(), snippet));
+            let snippet = if preview_lower.contains(&query_lower) {
+                Some(extract_snippet(&entry.preview, query, 100))
             } else if entry.content_len > PREVIEW_LENGTH {
-                // Preview didn't match but note is longer — fall back to full read
-                if let Ok(content) = super::storage::read_file(&entry.path) {
-                    if content.to_lowercase().contains(&query_lower) {
-                        let snippet = extract_snippet(&content, query, 100);
-                        results.push((entry.clone(), snippet));
-                    }
+                // Preview didn't match but note is longer — fall back to the full text
+                search_note_file(&entry.path, query, &query_lower, icloud)
+            } else {
+                None
+            };
+            let Some(snippet) = snippet else {
+                continue;
+            };
+
+            if include_facets {
+                *folder_counts.entry(entry.folder.clone()).or_default() += 1;
+                for tag in note_metadata::extract_tags(&entry.preview) {
+                    *tag_counts.entry(tag.to_lowercase()).or_default() += 1;
                 }
             }
+            if in_folder {
+                results.push((entry.clone(), snippet));
+            }
         }
 
-        results.sort_by(|a, b| b.0.created.cmp(&a.0.created));
-        Ok(results)
+        results.sort_by(|a, b| pinned_then_newest(&a.0, &b.0));
+        let facets = include_facets.then(|| SearchFacets {
+            total: folder_counts.values().sum(),
+            folders: facet_counts(folder_counts),
+            tags: facet_counts(tag_counts),
+        });
+        Ok((results, facets))
     }
 }
 
@@ -237,10 +502,91 @@ pub fn rebuild_index(index: tauri::State<'_, NoteIndex>) -> Result<bool, String>
     Ok(true)
 }
 
-fn read_note_entry(path: &PathBuf, folder: &str) -> Option<NoteEntry> {
+fn read_limit_bytes() -> usize {
+    let kb = super::settings::load_settings_from_file()
+        .map(|s| s.index_read_limit_kb)
+        .unwrap_or(super::settings::DEFAULT_INDEX_READ_LIMIT_KB);
+    kb.max(MIN_READ_LIMIT_KB) * 1024
+}
+
+/// Full-text search of one note, streamed so a huge note stops being read
+/// at the first match. Returns the snippet around it.
+fn search_note_file(path: &str, query: &str, query_lower: &str, icloud: bool) -> Option<String> {
+    if icloud {
+        // Coordinated reads are whole-file only.
+        let content = super::storage::read_file(path).ok()?;
+        return content
+            .to_lowercase()
+            .contains(query_lower)
+            .then(|| extract_snippet(&content, query, 100));
+    }
+    find_in_reader(fs::File::open(path).ok()?, query, query_lower)
+}
+
+fn find_in_reader(mut reader: impl Read, query: &str, query_lower: &str) -> Option<String> {
+    // Text kept from the previous chunk, so a match split across a chunk
+    // boundary is still found and the snippet has some leading context.
+    let overlap = query.len() * 4 + 100;
+    let mut chunk = vec![0u8; SEARCH_CHUNK_BYTES];
+    let mut pending: Vec<u8> = Vec::new();
+    let mut window = String::new();
+
+    loop {
+        let read = reader.read(&mut chunk).ok()?;
+        if read == 0 {
+            return None;
+        }
+        pending.extend_from_slice(&chunk[..read]);
+        let valid = match std::str::from_utf8(&pending) {
+            Ok(text) => text.len(),
+            // A character split by the chunk boundary; the rest comes next read.
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => return None,
+        };
+        window.push_str(std::str::from_utf8(&pending[..valid]).ok()?);
+        pending.drain(..valid);
+
+        if window.to_lowercase().contains(query_lower) {
+            return Some(extract_snippet(&window, query, 100));
+        }
+        let keep_from = ceil_char_boundary(&window, window.len().saturating_sub(overlap));
+        window.drain(..keep_from);
+    }
+}
+
+/// Pinned notes first, newest first within each group.
+fn pinned_then_newest(a: &NoteEntry, b: &NoteEntry) -> std::cmp::Ordering {
+    b.pinned
+        .cmp(&a.pinned)
+        .then_with(|| b.created.cmp(&a.created))
+}
+
+fn read_note_entry(path: &PathBuf, folder: &str, read_limit: usize) -> Option<NoteEntry> {
     let path_str = path.to_string_lossy();
-    let content = super::storage::read_file(&path_str).ok()?;
+    let (content, file_size) = super::storage::read_file_prefix(&path_str, read_limit).ok()?;
     let locked = super::note_lock::is_locked_content(&content);
+    let truncated_in_index = content.len() < file_size;
+    let id = note_ids::note_id(&content);
+    let pinned = !locked && pins::is_pinned(&content);
+    let task_counts = if locked {
+        TaskCounts::default()
+    } else {
+        tasks::count_tasks(&content)
+    };
+
+    let (word_count, language) = if locked {
+        (0, language::UNDETERMINED.to_string())
+    } else {
+        let mut word_count = count_words(&content);
+        if truncated_in_index && !content.is_empty() {
+            // Extrapolate from the part that was read.
+            word_count = word_count * file_size / content.len();
+        }
+        (
+            word_count,
+            language::note_language(word_count, None, &content),
+        )
+    };
 
     let (title, preview, content_len) = if locked {
         // Derive title from filename: YYYYMMDD-HHMMSS-slug-uuid.md → slug
@@ -254,16 +600,17 @@ fn read_note_entry(path: &PathBuf, folder: &str) -> Option<NoteEntry> {
             .unwrap_or_else(|| fname.to_string());
         (title, String::new(), 0)
     } else {
-        let content_len = content.len();
+        let content_len = file_size;
         let title = extract_title(&content);
-        let preview = if content.len() > PREVIEW_LENGTH {
+        let body = expiry::strip_frontmatter(&content);
+        let preview = if body.len() > PREVIEW_LENGTH {
             let mut end = PREVIEW_LENGTH;
-            while end > 0 && !content.is_char_boundary(end) {
+            while end > 0 && !body.is_char_boundary(end) {
                 end -= 1;
             }
-            content[..end].to_string()
+            body[..end].to_string()
         } else {
-            content
+            body.to_string()
         };
         (title, preview, content_len)
     };
@@ -274,10 +621,19 @@ fn read_note_entry(path: &PathBuf, folder: &str) -> Option<NoteEntry> {
         .to_string_lossy()
         .to_string();
 
-    let created = fs::metadata(path)
-        .and_then(|metadata| metadata.modified())
-        .map(format_timestamp)
-        .unwrap_or_else(|_| filename.split('-').take(2).collect::<Vec<_>>().join("-"));
+    let modified = fs::metadata(path).and_then(|metadata| metadata.modified());
+    let from_filename = filename_template::created_from_filename(&filename);
+    let created = match &modified {
+        Ok(modified) => format_timestamp(*modified),
+        Err(_) => from_filename
+            .and_then(|created| created.timestamp())
+            .map(|at| at.format("%Y%m%d-%H%M%S").to_string())
+            .unwrap_or_default(),
+    };
+    let created_date = from_filename.map(|created| created.date).or_else(|| {
+        let modified: DateTime<Local> = (*modified.as_ref().ok()?).into();
+        Some(modified.date_naive())
+    });
 
     Some(NoteEntry {
         path: path.to_string_lossy().to_string(),
@@ -288,10 +644,36 @@ fn read_note_entry(path: &PathBuf, folder: &str) -> Option<NoteEntry> {
         created,
         content_len,
         locked,
+        word_count,
+        reading_minutes: reading_minutes(word_count),
+        language,
+        truncated_in_index,
+        id,
+        pinned,
+        created_date,
+        open_tasks: task_counts.open,
+        done_tasks: task_counts.done,
     })
 }
 
-fn format_timestamp(time: SystemTime) -> String {
+/// Split `lang:xx` operators out of a search query. Returns the remaining
+/// text and the last language filter given.
+pub(crate) fn parse_search_query(query: &str) -> (String, Option<String>) {
+    let mut language = None;
+    let mut terms = Vec::new();
+    for term in query.split_whitespace() {
+        match term.strip_prefix("lang:") {
+            Some(code) if !code.is_empty() => language = Some(code.to_string()),
+            _ => terms.push(term),
+        }
+    }
+    match language {
+        Some(language) => (terms.join(" "), Some(language)),
+        None => (query.to_string(), None),
+    }
+}
+
+pub(crate) fn format_timestamp(time: SystemTime) -> String {
     let dt: DateTime<Local> = time.into();
     dt.format("%Y%m%d-%H%M%S").to_string()
 }
@@ -302,8 +684,9 @@ fn is_break_placeholder_line(line: &str) -> bool {
         || line.eq_ignore_ascii_case("<br />")
 }
 
-fn extract_title(content: &str) -> String {
-    content
+pub(crate) fn extract_title(content: &str) -> String {
+    // Frontmatter (ids, tags, expiry) is never the title.
+    expiry::strip_frontmatter(content)
         .lines()
         .map(str::trim)
         .find(|line| !line.is_empty() && !is_break_placeholder_line(line))
@@ -311,6 +694,58 @@ fn extract_title(content: &str) -> String {
         .unwrap_or_else(|| "Untitled".to_string())
 }
 
+/// Scripts written without spaces between words.
+fn is_unspaced_script(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}' // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+        | '\u{0E00}'..='\u{0E7F}' // Thai
+        | '\u{0E80}'..='\u{0EFF}' // Lao
+        | '\u{1000}'..='\u{109F}' // Myanmar
+        | '\u{1780}'..='\u{17FF}' // Khmer
+        | '\u{20000}'..='\u{2FA1F}' // CJK Extensions B+
+    )
+}
+
+fn count_token_words(token: &str) -> usize {
+    let unspaced = token.chars().filter(|c| is_unspaced_script(*c)).count();
+    if unspaced == 0 {
+        return usize::from(token.chars().any(char::is_alphanumeric));
+    }
+    // Roughly two characters per word, plus any Latin run mixed in.
+    let mixed = token
+        .chars()
+        .any(|c| c.is_alphanumeric() && !is_unspaced_script(c));
+    unspaced.div_ceil(2) + usize::from(mixed)
+}
+
+/// Word count of the prose in `content`; fenced code blocks are skipped.
+pub(crate) fn count_words(content: &str) -> usize {
+    let mut in_fence = false;
+    let mut words = 0;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if !in_fence {
+            words += line
+                .split_whitespace()
+                .map(count_token_words)
+                .sum::<usize>();
+        }
+    }
+    words
+}
+
+/// Minutes to read `word_count` words, rounded up; 0 for an empty note.
+pub(crate) fn reading_minutes(word_count: usize) -> usize {
+    word_count.div_ceil(WORDS_PER_MINUTE)
+}
+
 /// Find the nearest valid UTF-8 char boundary at or before `pos`.
 fn floor_char_boundary(s: &str, pos: usize) -> usize {
     let mut i = pos.min(s.len());
@@ -358,10 +793,31 @@ fn extract_snippet(content: &str, query: &str, max_len: usize) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{extract_title, read_note_entry};
+    use super::{
+        count_words, extract_title, facet_counts, find_in_reader, parse_search_query,
+        pinned_then_newest, read_note_entry, reading_minutes, FacetCount, SEARCH_CHUNK_BYTES,
+    };
+    use crate::commands::test_support::temp_dir;
+    use std::collections::HashMap;
     use std::fs;
     use std::path::PathBuf;
-    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn facets_sort_by_count_then_name() {
+        let counts = HashMap::from([
+            ("Ideas".to_string(), 3),
+            ("Work".to_string(), 12),
+            ("Archive".to_string(), 3),
+        ]);
+        let facet = |name: &str, count| FacetCount {
+            name: name.to_string(),
+            count,
+        };
+        assert_eq!(
+            facet_counts(counts),
+            vec![facet("Work", 12), facet("Archive", 3), facet("Ideas", 3)]
+        );
+    }
 
     #[test]
     fn title_uses_first_non_empty_line() {
@@ -371,6 +827,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn title_skips_frontmatter() {
+        assert_eq!(
+            extract_title("---\nstik-id: 1b4e28ba-2fa1-11d2-883f-0016d3cca427\n---\n# Plan\n"),
+            "# Plan"
+        );
+    }
+
     #[test]
     fn title_skips_break_placeholders() {
         assert_eq!(
@@ -385,22 +849,140 @@ mod tests {
     }
 
     #[test]
-    fn note_entry_created_uses_modified_time_not_filename_timestamp() {
-        let unique = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("clock should be after unix epoch")
-            .as_nanos();
+    fn words_are_counted_outside_code_fences() {
+        let content = "# Plan\n\nShip it - today.\n\n```rust\nfn main() {}\n```\nDone";
+        assert_eq!(count_words(content), 5);
+    }
 
-        let test_dir = std::env::temp_dir().join(format!("stik-index-test-{}", unique));
-        fs::create_dir_all(&test_dir).expect("create temp test dir");
+    #[test]
+    fn unspaced_scripts_fall_back_to_two_chars_per_word() {
+        assert_eq!(count_words("今日は良い天気です"), 5);
+        assert_eq!(count_words("iPhone用のアプリ"), 4);
+        assert_eq!(reading_minutes(0), 0);
+        assert_eq!(reading_minutes(420), 3);
+    }
+
+    #[test]
+    fn lang_operator_is_split_from_the_query() {
+        assert_eq!(
+            parse_search_query("lang:de projekt plan"),
+            ("projekt plan".to_string(), Some("de".to_string()))
+        );
+        assert_eq!(
+            parse_search_query("meeting  lang:en"),
+            ("meeting".to_string(), Some("en".to_string()))
+        );
+        assert_eq!(parse_search_query("a  b"), ("a  b".to_string(), None));
+        assert_eq!(parse_search_query("lang:"), ("lang:".to_string(), None));
+    }
+
+    #[test]
+    fn note_entry_created_uses_modified_time_not_filename_timestamp() {
+        let test_dir = temp_dir("index-test");
 
         let note_path: PathBuf = test_dir.join("20000101-000000-legacy-title.md");
         fs::write(&note_path, "updated content").expect("write note");
 
-        let entry = read_note_entry(&note_path, "Inbox").expect("note entry should load");
+        let entry = read_note_entry(&note_path, "Inbox", 1024).expect("note entry should load");
         assert_ne!(entry.created, "20000101-000000");
 
-        let _ = fs::remove_file(&note_path);
-        let _ = fs::remove_dir(&test_dir);
+        let _ = fs::remove_dir_all(&test_dir);
+    }
+
+    #[test]
+    fn note_entry_counts_tasks() {
+        let test_dir = temp_dir("index-tasks");
+
+        let note_path: PathBuf = test_dir.join("20260206-090000-plan.md");
+        fs::write(&note_path, "# Plan\n- [x] book\n- [ ] pack\n- [ ] go\n").expect("write note");
+        let entry = read_note_entry(&note_path, "Inbox", 1024).expect("note entry should load");
+        assert_eq!((entry.open_tasks, entry.done_tasks), (2, 1));
+
+        fs::write(&note_path, "# Plan\n- [x] book\n- [x] pack\n- [ ] go\n").expect("write note");
+        let entry = read_note_entry(&note_path, "Inbox", 1024).expect("note entry should load");
+        assert_eq!((entry.open_tasks, entry.done_tasks), (1, 2));
+
+        let _ = fs::remove_dir_all(&test_dir);
+    }
+
+    #[test]
+    fn note_entry_preview_leaves_out_frontmatter() {
+        let test_dir = temp_dir("index-frontmatter");
+
+        let note_path: PathBuf = test_dir.join("20260206-090000-plan.md");
+        fs::write(
+            &note_path,
+            "---\nstik-id: 1b4e28ba-2fa1-11d2-883f-0016d3cca427\n---\n# Plan\nPack bags\n",
+        )
+        .expect("write note");
+        let entry = read_note_entry(&note_path, "Inbox", 1024).expect("note entry should load");
+        assert_eq!(entry.title, "# Plan");
+        assert_eq!(entry.preview, "# Plan\nPack bags\n");
+        assert_eq!(
+            entry.id.as_deref(),
+            Some("1b4e28ba-2fa1-11d2-883f-0016d3cca427")
+        );
+
+        let _ = fs::remove_dir_all(&test_dir);
+    }
+
+    #[test]
+    fn pinned_notes_sort_before_newer_ones() {
+        let test_dir = temp_dir("index-pinned");
+
+        let pinned_path: PathBuf = test_dir.join("20000101-000000-reading-list.md");
+        fs::write(&pinned_path, "---\npinned: true\n---\nReading list").expect("write note");
+        let newer_path: PathBuf = test_dir.join("20260101-000000-groceries.md");
+        fs::write(&newer_path, "Groceries").expect("write note");
+
+        let mut pinned = read_note_entry(&pinned_path, "Inbox", 1024).expect("note entry");
+        pinned.created = "20000101-000000".to_string();
+        let newer = read_note_entry(&newer_path, "Inbox", 1024).expect("note entry");
+        assert!(pinned.pinned);
+        assert!(!newer.pinned);
+
+        let mut entries = vec![&newer, &pinned];
+        entries.sort_by(|a, b| pinned_then_newest(a, b));
+        assert_eq!(entries[0].title, "Reading list");
+
+        let _ = fs::remove_dir_all(&test_dir);
+    }
+
+    #[test]
+    fn large_notes_are_read_up_to_the_limit_on_a_char_boundary() {
+        let test_dir = temp_dir("index-limit");
+
+        // "é" is two bytes, so a 1023-byte limit lands mid-character.
+        let note_path: PathBuf = test_dir.join("20260101-000000-log-dump.md");
+        let content = format!("Log dump\n{}", "é ".repeat(2000));
+        fs::write(&note_path, &content).expect("write note");
+
+        let entry = read_note_entry(&note_path, "Inbox", 1023).expect("note entry should load");
+        assert!(entry.truncated_in_index);
+        assert_eq!(entry.content_len, content.len());
+        assert_eq!(entry.title, "Log dump");
+        assert!(
+            entry.word_count > 1500,
+            "extrapolated: {}",
+            entry.word_count
+        );
+
+        let entry = read_note_entry(&note_path, "Inbox", content.len()).expect("note entry");
+        assert!(!entry.truncated_in_index);
+
+        let _ = fs::remove_dir_all(&test_dir);
+    }
+
+    #[test]
+    fn streamed_search_finds_matches_across_chunk_boundaries() {
+        let mut content = "x".repeat(SEARCH_CHUNK_BYTES - 3);
+        content.push_str("Needle in the haystack");
+        content.push_str(&"y".repeat(SEARCH_CHUNK_BYTES));
+        let snippet = find_in_reader(content.as_bytes(), "needle", "needle").expect("match");
+        assert!(snippet.contains("Needle in the haystack"));
+
+        let split = format!("{}日本語", "a".repeat(SEARCH_CHUNK_BYTES - 1));
+        assert!(find_in_reader(split.as_bytes(), "日本", "日本").is_some());
+        assert!(find_in_reader(content.as_bytes(), "absent", "absent").is_none());
     }
 }
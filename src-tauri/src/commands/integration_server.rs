@@ -0,0 +1,629 @@
+/// Opt-in localhost HTTP API for launcher integrations (Raycast, Alfred),
+/// which can't invoke Tauri commands:
+///
+/// - `GET /notes?query=…` searches notes
+/// - `POST /notes` with `{"folder": …, "content": …}` saves a note
+/// - `GET /folders` lists folders
+///
+/// The listener binds 127.0.0.1 only and every request needs
+/// `Authorization: Bearer <token>`, with the token kept in
+/// `~/.stik/api-token`. Toggling the setting starts or stops the server
+/// in place.
+use rand::RngCore;
+use serde::Deserialize;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Url};
+
+use super::folders;
+use super::index::NoteIndex;
+use super::notes::{self, NoteSaved, SearchResult};
+use super::settings::StikSettings;
+
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+const MAX_HEADER_LINES: usize = 64;
+/// Longest request line or header line accepted.
+const MAX_LINE_BYTES: usize = 8 * 1024;
+/// Connections handled at once; more get a 503 straight away.
+const MAX_CONNECTIONS: usize = 8;
+const ACCEPT_POLL_MS: u64 = 100;
+const REQUEST_TIMEOUT_SECONDS: u64 = 5;
+
+/// The app's note internals the endpoints delegate to.
+enum AppBackend {
+    /// The running app: its note index and post-save side effects.
+    App(AppHandle),
+    /// A note index of its own and no side effects, for tests.
+    #[cfg(test)]
+    Index(NoteIndex),
+}
+
+impl AppBackend {
+    fn with_index<T>(&self, f: impl FnOnce(&NoteIndex) -> T) -> T {
+        match self {
+            Self::App(app) => f(&app.state::<NoteIndex>()),
+            #[cfg(test)]
+            Self::Index(index) => f(index),
+        }
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<SearchResult>, String> {
+        self.with_index(|index| notes::search_notes_inner(index, query, None))
+    }
+
+    fn save(&self, folder: Option<String>, content: String) -> Result<NoteSaved, String> {
+        let folder = folder.unwrap_or_else(|| {
+            super::settings::get_settings()
+                .unwrap_or_default()
+                .default_folder
+        });
+        let saved = notes::save_note_inner(folder, content.clone())?;
+        match self {
+            Self::App(app) => notes::post_save_processing(app, &saved, &content),
+            #[cfg(test)]
+            Self::Index(index) => {
+                if !saved.path.is_empty() {
+                    index.add(&saved.path, &saved.folder);
+                }
+            }
+        }
+        Ok(saved)
+    }
+
+    fn folders(&self) -> Result<Vec<String>, String> {
+        folders::list_folders()
+    }
+}
+
+struct RunningServer {
+    port: u16,
+    shutdown: Arc<AtomicBool>,
+}
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+static RUNNING: OnceLock<Mutex<Option<RunningServer>>> = OnceLock::new();
+
+fn running() -> &'static Mutex<Option<RunningServer>> {
+    RUNNING.get_or_init(|| Mutex::new(None))
+}
+
+/// Remember the app handle and start the server if enabled. Call once
+/// during app setup.
+pub fn start(app: AppHandle) {
+    let _ = APP.set(app);
+    apply_settings(&super::settings::get_settings().unwrap_or_default());
+}
+
+/// Start, stop or rebind the server to match `settings`.
+pub fn apply_settings(settings: &StikSettings) {
+    let Some(app) = APP.get() else {
+        return;
+    };
+    let mut running = running().lock().unwrap_or_else(|e| e.into_inner());
+
+    let wanted = settings
+        .integration_server_enabled
+        .then_some(settings.integration_server_port);
+    if running.as_ref().map(|server| server.port) == wanted {
+        return;
+    }
+    if let Some(server) = running.take() {
+        server.shutdown.store(true, Ordering::SeqCst);
+    }
+    let Some(port) = wanted else {
+        return;
+    };
+
+    let result = load_or_create_token().and_then(|token| {
+        let listener = bind(port)?;
+        let backend = Arc::new(AppBackend::App(app.clone()));
+        Ok(serve(listener, backend, token))
+    });
+    match result {
+        Ok(shutdown) => *running = Some(RunningServer { port, shutdown }),
+        Err(e) => log::error!("Failed to start integration server: {}", e),
+    }
+}
+
+fn bind(port: u16) -> Result<TcpListener, String> {
+    TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, port)))
+        .map_err(|e| format!("Could not listen on 127.0.0.1:{}: {}", port, e))
+}
+
+/// Accept connections on `listener` until the returned flag is set.
+fn serve(listener: TcpListener, backend: Arc<AppBackend>, token: String) -> Arc<AtomicBool> {
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let stop = shutdown.clone();
+    let port = listener.local_addr().map(|addr| addr.port()).unwrap_or(0);
+    let open_connections = Arc::new(AtomicUsize::new(0));
+
+    // Non-blocking accept so a stop request is noticed without a wake-up
+    // connection.
+    let _ = listener.set_nonblocking(true);
+    thread::Builder::new()
+        .name("stik-integration-server".to_string())
+        .spawn(move || {
+            while !stop.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((stream, peer)) => {
+                        if !peer.ip().is_loopback() {
+                            continue;
+                        }
+                        let Some(slot) = ConnectionSlot::take(&open_connections) else {
+                            let _ = stream.set_nonblocking(false);
+                            let busy = Response::error(503, "Too many connections");
+                            let _ = write_response(&stream, &busy);
+                            continue;
+                        };
+                        let backend = backend.clone();
+                        let token = token.clone();
+                        thread::spawn(move || {
+                            handle_connection(stream, &backend, &token, port);
+                            drop(slot);
+                        });
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        thread::sleep(Duration::from_millis(ACCEPT_POLL_MS));
+                    }
+                    Err(e) => {
+                        log::error!("Integration server accept failed: {}", e);
+                        thread::sleep(Duration::from_millis(ACCEPT_POLL_MS));
+                    }
+                }
+            }
+        })
+        .ok();
+    shutdown
+}
+
+/// One of the `MAX_CONNECTIONS` connections, given back when dropped.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    fn take(open: &Arc<AtomicUsize>) -> Option<Self> {
+        open.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+            (count < MAX_CONNECTIONS).then_some(count + 1)
+        })
+        .ok()
+        .map(|_| Self(open.clone()))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+struct Request {
+    method: String,
+    target: String,
+    host: Option<String>,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: u16,
+    body: serde_json::Value,
+}
+
+impl Response {
+    fn ok(body: impl serde::Serialize) -> Self {
+        Self::with_status(200, body)
+    }
+
+    fn with_status(status: u16, body: impl serde::Serialize) -> Self {
+        Self {
+            status,
+            body: serde_json::to_value(body).unwrap_or(serde_json::Value::Null),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::with_status(status, serde_json::json!({ "error": message.into() }))
+    }
+}
+
+#[derive(Deserialize)]
+struct SaveNoteRequest {
+    folder: Option<String>,
+    content: String,
+}
+
+fn handle_connection(stream: TcpStream, backend: &AppBackend, token: &str, port: u16) {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(REQUEST_TIMEOUT_SECONDS)));
+
+    let response = match read_request(&stream) {
+        Ok(request) => route(&request, backend, token, port),
+        Err(e) => Response::error(400, e),
+    };
+    let _ = write_response(&stream, &response);
+}
+
+/// Read one line into `line`, refusing lines over `MAX_LINE_BYTES`.
+fn read_line(reader: &mut impl BufRead, line: &mut String, what: &str) -> Result<(), String> {
+    line.clear();
+    let read = reader
+        .take(MAX_LINE_BYTES as u64)
+        .read_line(line)
+        .map_err(|e| format!("Failed to read {}: {}", what, e))?;
+    if read == MAX_LINE_BYTES && !line.ends_with('\n') {
+        return Err(format!("The {} is too long", what));
+    }
+    Ok(())
+}
+
+fn read_request(stream: &TcpStream) -> Result<Request, String> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    read_line(&mut reader, &mut line, "request line")?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err("Malformed request line".to_string());
+    };
+    let mut request = Request {
+        method: method.to_string(),
+        target: target.to_string(),
+        host: None,
+        authorization: None,
+        body: Vec::new(),
+    };
+
+    let mut content_length = 0usize;
+    for _ in 0..MAX_HEADER_LINES {
+        read_line(&mut reader, &mut line, "header")?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            return Err("Malformed header".to_string());
+        };
+        let value = value.trim().to_string();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "host" => request.host = Some(value),
+            "authorization" => request.authorization = Some(value),
+            "content-length" => {
+                content_length = value.parse().map_err(|_| "Invalid Content-Length")?;
+            }
+            _ => {}
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return Err("Request body too large".to_string());
+    }
+    request.body = vec![0; content_length];
+    reader
+        .read_exact(&mut request.body)
+        .map_err(|e| format!("Failed to read body: {}", e))?;
+    Ok(request)
+}
+
+fn route(request: &Request, backend: &AppBackend, token: &str, port: u16) -> Response {
+    // Browsers send the attacker's hostname after DNS rebinding; only
+    // clients that really targeted loopback get through.
+    if !is_loopback_host(request.host.as_deref(), port) {
+        return Response::error(403, "Requests must target 127.0.0.1 or localhost");
+    }
+    if !is_authorized(request.authorization.as_deref(), token) {
+        return Response::error(401, "Missing or invalid bearer token");
+    }
+
+    let Ok(url) = Url::parse(&format!("http://127.0.0.1{}", request.target)) else {
+        return Response::error(400, "Malformed request target");
+    };
+    let result = match (request.method.as_str(), url.path()) {
+        ("GET", "/notes") => {
+            let query = url
+                .query_pairs()
+                .find(|(key, _)| key == "query")
+                .map(|(_, value)| value.into_owned())
+                .unwrap_or_default();
+            backend.search(&query).map(Response::ok)
+        }
+        ("POST", "/notes") => {
+            let body: SaveNoteRequest = match serde_json::from_slice(&request.body) {
+                Ok(body) => body,
+                Err(e) => return Response::error(400, format!("Invalid JSON body: {}", e)),
+            };
+            if let Some(Err(e)) = body.folder.as_deref().map(folders::validate_name) {
+                return Response::error(400, e);
+            }
+            match backend.save(body.folder, body.content) {
+                Ok(saved) if saved.path.is_empty() => {
+                    return Response::error(400, "Nothing to save: the note is empty")
+                }
+                result => result.map(|saved| Response::with_status(201, saved)),
+            }
+        }
+        ("GET", "/folders") => backend.folders().map(Response::ok),
+        (_, "/notes") | (_, "/folders") => return Response::error(405, "Method not allowed"),
+        _ => return Response::error(404, "Not found"),
+    };
+    result.unwrap_or_else(|e| Response::error(500, e))
+}
+
+fn is_loopback_host(host: Option<&str>, port: u16) -> bool {
+    let Some(host) = host else {
+        return false;
+    };
+    ["127.0.0.1", "localhost"]
+        .iter()
+        .any(|name| host == *name || host == format!("{}:{}", name, port))
+}
+
+fn is_authorized(header: Option<&str>, token: &str) -> bool {
+    let Some(presented) = header.and_then(|value| value.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    // Constant-time so the token can't be guessed byte by byte.
+    presented.len() == token.len()
+        && presented
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn write_response(mut stream: &TcpStream, response: &Response) -> std::io::Result<()> {
+    let body = response.body.to_string();
+    let reason = match response.status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn token_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Could not find home directory")?;
+    let stik_config = home.join(".stik");
+    fs::create_dir_all(&stik_config).map_err(|e| e.to_string())?;
+    Ok(stik_config.join("api-token"))
+}
+
+fn load_or_create_token() -> Result<String, String> {
+    let path = token_path()?;
+    if let Ok(existing) = fs::read_to_string(&path) {
+        let existing = existing.trim();
+        if !existing.is_empty() {
+            return Ok(existing.to_string());
+        }
+    }
+
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let token: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    fs::write(&path, &token).map_err(|e| format!("Failed to write API token: {}", e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o600));
+    }
+    Ok(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::{temp_dir, use_notes_root, NotesRootGuard};
+
+    const TOKEN: &str = "test-token";
+
+    /// The real backend over a temp notes root.
+    struct TestServer {
+        port: u16,
+        root: PathBuf,
+        shutdown: Arc<AtomicBool>,
+        _notes_root: NotesRootGuard,
+    }
+
+    impl TestServer {
+        fn start() -> Self {
+            let root = temp_dir("integration");
+            fs::create_dir_all(root.join("Work")).expect("create temp notes dir");
+            let notes_root = use_notes_root(&root);
+            let listener = bind(0).expect("bind ephemeral port");
+            let port = listener.local_addr().expect("local addr").port();
+            let backend = Arc::new(AppBackend::Index(NoteIndex::new()));
+            let shutdown = serve(listener, backend, TOKEN.to_string());
+            Self {
+                port,
+                root,
+                shutdown,
+                _notes_root: notes_root,
+            }
+        }
+
+        fn request(
+            &self,
+            method: &str,
+            target: &str,
+            token: Option<&str>,
+            body: &str,
+        ) -> (u16, String) {
+            self.raw_request(
+                method,
+                target,
+                &format!("127.0.0.1:{}", self.port),
+                token,
+                body,
+            )
+        }
+
+        fn raw_request(
+            &self,
+            method: &str,
+            target: &str,
+            host: &str,
+            token: Option<&str>,
+            body: &str,
+        ) -> (u16, String) {
+            let mut stream =
+                TcpStream::connect(("127.0.0.1", self.port)).expect("connect to server");
+            let auth = token
+                .map(|token| format!("Authorization: Bearer {}\r\n", token))
+                .unwrap_or_default();
+            write!(
+                stream,
+                "{} {} HTTP/1.1\r\nHost: {}\r\n{}Content-Length: {}\r\n\r\n{}",
+                method,
+                target,
+                host,
+                auth,
+                body.len(),
+                body
+            )
+            .expect("send request");
+
+            let mut response = String::new();
+            stream.read_to_string(&mut response).expect("read response");
+            let status = response
+                .split_whitespace()
+                .nth(1)
+                .and_then(|code| code.parse().ok())
+                .expect("status code");
+            let body = response
+                .split_once("\r\n\r\n")
+                .map(|(_, body)| body.to_string())
+                .unwrap_or_default();
+            (status, body)
+        }
+    }
+
+    impl Drop for TestServer {
+        fn drop(&mut self) {
+            self.shutdown.store(true, Ordering::SeqCst);
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn rejects_requests_without_a_valid_token() {
+        let server = TestServer::start();
+        assert_eq!(server.request("GET", "/folders", None, "").0, 401);
+        assert_eq!(server.request("GET", "/folders", Some("wrong"), "").0, 401);
+        assert_eq!(server.request("GET", "/folders", Some(TOKEN), "").0, 200);
+    }
+
+    #[test]
+    fn rejects_non_loopback_host_headers() {
+        let server = TestServer::start();
+        let (status, _) = server.raw_request("GET", "/folders", "evil.example:80", Some(TOKEN), "");
+        assert_eq!(status, 403);
+    }
+
+    #[test]
+    fn saves_searches_and_lists_folders() {
+        let server = TestServer::start();
+
+        let (status, body) = server.request(
+            "POST",
+            "/notes",
+            Some(TOKEN),
+            r#"{"folder":"Ideas","content":"Raycast quick capture"}"#,
+        );
+        assert_eq!(status, 201);
+        let saved: serde_json::Value = serde_json::from_str(&body).expect("json");
+        assert_eq!(saved["folder"], "Ideas");
+        assert!(PathBuf::from(saved["path"].as_str().unwrap()).starts_with(&server.root));
+
+        let (status, body) = server.request("GET", "/notes?query=quick%20capture", Some(TOKEN), "");
+        assert_eq!(status, 200);
+        let results: serde_json::Value = serde_json::from_str(&body).expect("json");
+        assert_eq!(results.as_array().map(Vec::len), Some(1));
+        assert_eq!(results[0]["folder"], "Ideas");
+
+        let (status, body) = server.request("GET", "/folders", Some(TOKEN), "");
+        assert_eq!(status, 200);
+        assert_eq!(body, r#"["Ideas","Work"]"#);
+    }
+
+    #[test]
+    fn reports_bad_requests() {
+        let server = TestServer::start();
+        assert_eq!(
+            server.request("POST", "/notes", Some(TOKEN), "not json").0,
+            400
+        );
+        assert_eq!(
+            server
+                .request("POST", "/notes", Some(TOKEN), r#"{"content":"  "}"#)
+                .0,
+            400
+        );
+        assert_eq!(
+            server
+                .request(
+                    "POST",
+                    "/notes",
+                    Some(TOKEN),
+                    r#"{"folder":"../x","content":"a"}"#
+                )
+                .0,
+            400
+        );
+        assert_eq!(server.request("DELETE", "/notes", Some(TOKEN), "").0, 405);
+        assert_eq!(server.request("GET", "/nope", Some(TOKEN), "").0, 404);
+    }
+
+    #[test]
+    fn refuses_overlong_lines() {
+        let mut line = String::new();
+        let fits = format!("GET /{} HTTP/1.1\r\n", "a".repeat(MAX_LINE_BYTES - 20));
+        read_line(&mut fits.as_bytes(), &mut line, "request line").expect("fits");
+        assert_eq!(line, fits);
+
+        let too_long = format!("Host: {}\r\n", "a".repeat(MAX_LINE_BYTES));
+        assert_eq!(
+            read_line(&mut too_long.as_bytes(), &mut line, "header"),
+            Err("The header is too long".to_string())
+        );
+    }
+
+    #[test]
+    fn turns_away_connections_over_the_cap() {
+        let server = TestServer::start();
+        let idle: Vec<TcpStream> = (0..MAX_CONNECTIONS)
+            .map(|_| TcpStream::connect(("127.0.0.1", server.port)).expect("connect"))
+            .collect();
+        // Turned away before the request is read, so send nothing.
+        let mut busy = TcpStream::connect(("127.0.0.1", server.port)).expect("connect");
+        let mut response = String::new();
+        busy.read_to_string(&mut response).expect("read response");
+        assert!(response.starts_with("HTTP/1.1 503 "), "{}", response);
+
+        drop(idle);
+        thread::sleep(Duration::from_millis(ACCEPT_POLL_MS * 3));
+        assert_eq!(server.request("GET", "/folders", Some(TOKEN), "").0, 200);
+    }
+
+    #[test]
+    fn listener_is_loopback_only() {
+        let listener = bind(0).expect("bind");
+        assert!(listener.local_addr().expect("addr").ip().is_loopback());
+    }
+}
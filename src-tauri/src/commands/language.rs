@@ -0,0 +1,194 @@
+// Per-note language for filtering, as BCP 47 codes ("en", "de", "zh-Hans").
+//
+// NLLanguageRecognizer (through the embedding build) is preferred when it
+// has seen the note; otherwise `detect_language` guesses from the script
+// and common function words. Notes too short to tell are `"und"`.
+
+/// ISO 639 code for an undetermined language.
+pub const UNDETERMINED: &str = "und";
+
+/// Fewer words than this and no guess is made.
+const MIN_WORDS: usize = 4;
+/// Characters of a script without spaces needed for a guess.
+const MIN_SCRIPT_CHARS: usize = 4;
+
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "of", "to", "in", "that", "it", "with", "for", "this",
+            "was", "you", "have", "not", "but", "on",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ich", "ein", "eine", "mit", "zu", "den",
+            "auf", "für", "sich", "auch", "es", "wir",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "et", "est", "un", "une", "des", "du", "que", "pour", "pas", "dans",
+            "sur", "avec", "je", "nous", "ce",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "la", "los", "las", "y", "es", "un", "una", "que", "de", "para", "por", "con",
+            "no", "en", "se", "del", "lo",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "lo", "la", "gli", "le", "e", "è", "un", "una", "che", "di", "per", "non", "con",
+            "sono", "del", "della", "ho",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "a", "os", "as", "e", "é", "um", "uma", "que", "de", "para", "não", "com", "do",
+            "da", "em", "por", "se",
+        ],
+    ),
+    (
+        "nl",
+        &[
+            "de", "het", "een", "en", "is", "van", "niet", "ik", "dat", "op", "te", "met", "voor",
+            "zijn", "ook", "maar", "wij", "er",
+        ],
+    ),
+];
+
+/// Language of the dominant non-Latin script, if any.
+fn script_language(content: &str) -> Option<&'static str> {
+    const LANGUAGES: [&str; 9] = ["ja", "zh", "ko", "ru", "ar", "he", "el", "th", "hi"];
+    let mut counts = [0usize; LANGUAGES.len()];
+    for c in content.chars() {
+        let slot = match c {
+            '\u{3040}'..='\u{30FF}' => 0, // kana
+            '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' => 1,
+            '\u{1100}'..='\u{11FF}' | '\u{AC00}'..='\u{D7AF}' => 2,
+            '\u{0400}'..='\u{04FF}' => 3,
+            '\u{0600}'..='\u{06FF}' => 4,
+            '\u{0590}'..='\u{05FF}' => 5,
+            '\u{0370}'..='\u{03FF}' => 6,
+            '\u{0E00}'..='\u{0E7F}' => 7,
+            '\u{0900}'..='\u{097F}' => 8,
+            _ => continue,
+        };
+        counts[slot] += 1;
+    }
+
+    // Japanese mixes kanji with kana; any kana at all decides it.
+    if counts[0] > 0 && counts[0] + counts[1] >= MIN_SCRIPT_CHARS {
+        return Some("ja");
+    }
+    let (slot, count) = counts.iter().enumerate().max_by_key(|(_, count)| **count)?;
+    (*count >= MIN_SCRIPT_CHARS).then_some(LANGUAGES[slot])
+}
+
+/// Best guess at the language of `content`, or `UNDETERMINED`.
+pub fn detect_language(content: &str) -> String {
+    if let Some(language) = script_language(content) {
+        return language.to_string();
+    }
+
+    let words: Vec<String> = content
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    if words.len() < MIN_WORDS {
+        return UNDETERMINED.to_string();
+    }
+
+    let mut scores: Vec<(&str, usize)> = STOPWORDS
+        .iter()
+        .map(|(language, stopwords)| {
+            let hits = words
+                .iter()
+                .filter(|word| stopwords.contains(&word.as_str()))
+                .count();
+            (*language, hits)
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.cmp(&a.1));
+    match scores.as_slice() {
+        [(language, best), (_, second), ..] if *best >= 2 && best > second => language.to_string(),
+        _ => UNDETERMINED.to_string(),
+    }
+}
+
+/// The language recorded for a note: the recognizer's answer when there is
+/// one, the heuristic otherwise, and `UNDETERMINED` for very short notes.
+pub fn note_language(word_count: usize, recognized: Option<&str>, content: &str) -> String {
+    if word_count < MIN_WORDS {
+        return UNDETERMINED.to_string();
+    }
+    match recognized.filter(|language| !language.is_empty()) {
+        Some(language) => language.to_string(),
+        None => detect_language(content),
+    }
+}
+
+/// Whether a note in `language` matches a `lang:` filter. `zh` matches
+/// both `zh-Hans` and `zh-Hant`.
+pub fn matches_filter(language: &str, filter: &str) -> bool {
+    let language = language.to_lowercase();
+    let filter = filter.to_lowercase();
+    language == filter
+        || language
+            .strip_prefix(&filter)
+            .is_some_and(|rest| rest.starts_with('-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_common_latin_languages() {
+        assert_eq!(
+            detect_language("The meeting is on Monday and it is in the big room"),
+            "en"
+        );
+        assert_eq!(
+            detect_language("Ich habe die Notizen für das Treffen nicht mit"),
+            "de"
+        );
+        assert_eq!(
+            detect_language("Nous avons une réunion pour le projet dans la salle"),
+            "fr"
+        );
+    }
+
+    #[test]
+    fn detects_scripts_without_stopwords() {
+        assert_eq!(detect_language("今日は会議があります"), "ja");
+        assert_eq!(detect_language("我们明天开会讨论"), "zh");
+        assert_eq!(detect_language("오늘 회의가 있습니다"), "ko");
+        assert_eq!(detect_language("Завтра будет встреча"), "ru");
+    }
+
+    #[test]
+    fn short_or_ambiguous_notes_are_undetermined() {
+        assert_eq!(detect_language("Buy milk"), UNDETERMINED);
+        assert_eq!(detect_language("Q3 OKR roadmap v2 draft"), UNDETERMINED);
+        assert_eq!(note_language(2, Some("en"), "Buy milk"), UNDETERMINED);
+        assert_eq!(note_language(40, Some("de"), ""), "de");
+        assert_eq!(note_language(5, None, "Ich habe die Notizen nicht"), "de");
+    }
+
+    #[test]
+    fn filters_match_on_the_primary_subtag() {
+        assert!(matches_filter("zh-Hans", "zh"));
+        assert!(matches_filter("de", "DE"));
+        assert!(!matches_filter("de", "d"));
+        assert!(!matches_filter("en", "de"));
+    }
+}
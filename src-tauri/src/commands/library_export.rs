@@ -0,0 +1,384 @@
+/// Whole-library export as a plain Obsidian-compatible vault: one folder of
+/// markdown per Stik folder, images in a visible `assets/` directory
+/// (Obsidian hides dot folders), optional YAML frontmatter and an
+/// `index.md` per folder. The Stik folder itself is only read.
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+use super::filename_template;
+use super::folders::{self, get_stik_folder};
+use super::index::extract_title;
+use super::storage;
+
+const TRASH_FOLDER: &str = ".trash";
+const ARCHIVE_FOLDER: &str = ".archive";
+const INDEX_FILENAME: &str = "index.md";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LibraryExportOptions {
+    /// Prepend `created:` frontmatter derived from the Stik filename.
+    pub frontmatter: bool,
+    pub include_trash: bool,
+    pub include_archive: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LibraryExportSummary {
+    pub folders: usize,
+    pub notes: usize,
+    pub assets: usize,
+    pub bytes_written: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct LibraryExportProgress {
+    done: usize,
+    total: usize,
+    path: String,
+}
+
+#[tauri::command]
+pub async fn export_library(
+    app: AppHandle,
+    destination_dir: String,
+    options: Option<LibraryExportOptions>,
+) -> Result<LibraryExportSummary, String> {
+    let options = options.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = get_stik_folder()?;
+        export_library_inner(&root, Path::new(&destination_dir), &options, |progress| {
+            let _ = app.emit("library-export-progress", progress);
+        })
+    })
+    .await
+    .map_err(|e| format!("Library export failed: {}", e))?
+}
+
+fn export_library_inner(
+    root: &Path,
+    destination: &Path,
+    options: &LibraryExportOptions,
+    mut on_progress: impl FnMut(LibraryExportProgress),
+) -> Result<LibraryExportSummary, String> {
+    let root_canonical = root.canonicalize().map_err(|e| e.to_string())?;
+    fs::create_dir_all(destination)
+        .map_err(|e| format!("Failed to create export folder: {}", e))?;
+    let destination = destination.canonicalize().map_err(|e| e.to_string())?;
+    if destination.starts_with(&root_canonical) {
+        return Err("Choose an export folder outside the Stik folder".to_string());
+    }
+
+    let plan = plan_folders(root, options)?;
+    let total: usize = plan.iter().map(|folder| folder.notes.len()).sum();
+    let mut summary = LibraryExportSummary::default();
+
+    for folder in &plan {
+        let target_dir = destination.join(&folder.export_name);
+        fs::create_dir_all(&target_dir)
+            .map_err(|e| format!("Failed to create {}: {}", target_dir.display(), e))?;
+        summary.folders += 1;
+
+        let mut listed = Vec::new();
+        for note in &folder.notes {
+            let filename = note
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let content = storage::read_file(&note.to_string_lossy())?;
+            let exported = export_note_content(&content, &filename, options.frontmatter);
+            write_counted(
+                &target_dir.join(&filename),
+                exported.as_bytes(),
+                &mut summary,
+            )?;
+            summary.notes += 1;
+            listed.push((filename.clone(), extract_title(&content)));
+
+            on_progress(LibraryExportProgress {
+                done: summary.notes,
+                total,
+                path: format!("{}/{}", folder.export_name, filename),
+            });
+        }
+
+        copy_assets(
+            &folder.source.join(".assets"),
+            &target_dir.join("assets"),
+            &mut summary,
+        )?;
+
+        // A real note called index.md wins over the generated listing.
+        if !listed
+            .iter()
+            .any(|(filename, _)| filename.eq_ignore_ascii_case(INDEX_FILENAME))
+        {
+            let index = folder_index(&folder.display_name, &listed);
+            write_counted(
+                &target_dir.join(INDEX_FILENAME),
+                index.as_bytes(),
+                &mut summary,
+            )?;
+        }
+    }
+
+    Ok(summary)
+}
+
+struct FolderPlan {
+    source: PathBuf,
+    /// Relative directory in the export; empty for notes at the Stik root.
+    export_name: String,
+    display_name: String,
+    notes: Vec<PathBuf>,
+}
+
+fn plan_folders(root: &Path, options: &LibraryExportOptions) -> Result<Vec<FolderPlan>, String> {
+    let mut names: Vec<String> = fs::read_dir(root)
+        .map_err(|e| format!("Failed to read Stik folder: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| {
+            folders::is_visible_folder_name(name)
+                || (options.include_trash && name == TRASH_FOLDER)
+                || (options.include_archive && name == ARCHIVE_FOLDER)
+        })
+        .collect();
+    names.sort_unstable();
+
+    let mut plan = vec![FolderPlan {
+        source: root.to_path_buf(),
+        export_name: String::new(),
+        display_name: "Stik".to_string(),
+        notes: markdown_files(root)?,
+    }];
+    for name in names {
+        let source = root.join(&name);
+        // `.trash` -> `trash`, so Obsidian shows it.
+        let export_name = name.trim_start_matches('.').to_string();
+        plan.push(FolderPlan {
+            notes: markdown_files(&source)?,
+            source,
+            display_name: export_name.clone(),
+            export_name,
+        });
+    }
+    // Only export the root if it actually holds notes.
+    if plan[0].notes.is_empty() {
+        plan.remove(0);
+    }
+    Ok(plan)
+}
+
+fn markdown_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn export_note_content(content: &str, filename: &str, frontmatter: bool) -> String {
+    let content = rewrite_asset_refs(content);
+    let created = frontmatter
+        .then(|| created_from_filename(filename))
+        .flatten();
+    match created {
+        Some(created) if !content.starts_with("---\n") => format!(
+            "---\ncreated: {}\n---\n\n{}",
+            created.format("%Y-%m-%dT%H:%M:%S"),
+            content
+        ),
+        _ => content,
+    }
+}
+
+/// Point `.assets/` image references at the exported `assets/` directory.
+/// Only references (after `(`, quotes, `/` or at line start) are touched,
+/// not prose that happens to mention the folder.
+fn rewrite_asset_refs(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(idx) = rest.find(".assets/") {
+        let preceding = rest[..idx].chars().last();
+        let is_reference = matches!(preceding, None | Some('(' | '"' | '\'' | '/' | '\n' | ' '));
+        out.push_str(&rest[..idx]);
+        out.push_str(if is_reference { "assets/" } else { ".assets/" });
+        rest = &rest[idx + ".assets/".len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Capture time from a note named by the filename template.
+fn created_from_filename(filename: &str) -> Option<NaiveDateTime> {
+    filename_template::created_from_filename(filename)?.timestamp()
+}
+
+fn folder_index(folder: &str, notes: &[(String, String)]) -> String {
+    let mut index = format!("# {}\n\n", folder);
+    for (filename, title) in notes {
+        let stem = filename.trim_end_matches(".md");
+        let title = title.trim_start_matches('#').trim();
+        index.push_str(&format!(
+            "- [[{}|{}]]\n",
+            stem,
+            title.replace(['[', ']', '|'], "")
+        ));
+    }
+    index
+}
+
+fn copy_assets(
+    source: &Path,
+    target: &Path,
+    summary: &mut LibraryExportSummary,
+) -> Result<(), String> {
+    let Ok(entries) = fs::read_dir(source) else {
+        return Ok(());
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        fs::create_dir_all(target).map_err(|e| e.to_string())?;
+        let bytes = fs::copy(&path, target.join(entry.file_name()))
+            .map_err(|e| format!("Failed to copy {}: {}", path.display(), e))?;
+        summary.assets += 1;
+        summary.bytes_written += bytes;
+    }
+    Ok(())
+}
+
+fn write_counted(
+    path: &Path,
+    data: &[u8],
+    summary: &mut LibraryExportSummary,
+) -> Result<(), String> {
+    fs::write(path, data).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    summary.bytes_written += data.len() as u64;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::temp_dir;
+
+    #[test]
+    fn rewrites_asset_references_only() {
+        assert_eq!(
+            rewrite_asset_refs("![a](.assets/a.png) <img src=\".assets/b.png\">"),
+            "![a](assets/a.png) <img src=\"assets/b.png\">"
+        );
+        assert_eq!(
+            rewrite_asset_refs("![a](./.assets/a.png)"),
+            "![a](./assets/a.png)"
+        );
+        assert_eq!(
+            rewrite_asset_refs("see my.assets/ dir"),
+            "see my.assets/ dir"
+        );
+    }
+
+    #[test]
+    fn frontmatter_uses_the_filename_timestamp() {
+        let exported = export_note_content("# Plan\n", "20260301-093000-plan-ab12.md", true);
+        assert_eq!(
+            exported,
+            "---\ncreated: 2026-03-01T09:30:00\n---\n\n# Plan\n"
+        );
+        assert_eq!(export_note_content("# Plan\n", "plan.md", true), "# Plan\n");
+        assert_eq!(
+            export_note_content("# Plan\n", "20260301-093000-plan-ab12.md", false),
+            "# Plan\n"
+        );
+    }
+
+    #[test]
+    fn exports_folders_assets_and_indexes() {
+        let root = temp_dir("vault-src");
+        let dest = temp_dir("vault-dest");
+        fs::create_dir_all(root.join("Work/.assets")).unwrap();
+        fs::create_dir_all(root.join(".trash")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(
+            root.join("Work/20260301-093000-plan-ab12.md"),
+            "# Plan\n\n![chart](.assets/chart.png)\n",
+        )
+        .unwrap();
+        fs::write(root.join("Work/.assets/chart.png"), [1u8, 2, 3]).unwrap();
+        fs::write(root.join(".trash/old.md"), "Old\n").unwrap();
+
+        let mut progress = Vec::new();
+        let summary = export_library_inner(&root, &dest, &LibraryExportOptions::default(), |p| {
+            progress.push(p.done)
+        })
+        .expect("export");
+
+        assert_eq!(summary.folders, 1);
+        assert_eq!(summary.notes, 1);
+        assert_eq!(summary.assets, 1);
+        assert_eq!(progress, vec![1]);
+        let note = fs::read_to_string(dest.join("Work/20260301-093000-plan-ab12.md")).unwrap();
+        assert!(note.contains("](assets/chart.png)"));
+        assert!(dest.join("Work/assets/chart.png").is_file());
+        let index = fs::read_to_string(dest.join("Work/index.md")).unwrap();
+        assert!(index.contains("- [[20260301-093000-plan-ab12|Plan]]"));
+        assert!(!dest.join("trash").exists());
+        assert!(!dest.join(".git").exists());
+
+        let with_trash = export_library_inner(
+            &root,
+            &dest,
+            &LibraryExportOptions {
+                include_trash: true,
+                ..LibraryExportOptions::default()
+            },
+            |_| {},
+        )
+        .expect("export with trash");
+        assert_eq!(with_trash.folders, 2);
+        assert!(dest.join("trash/old.md").is_file());
+        assert_eq!(
+            summary.bytes_written,
+            fs::metadata(dest.join("Work/20260301-093000-plan-ab12.md"))
+                .unwrap()
+                .len()
+                + 3
+                + fs::metadata(dest.join("Work/index.md")).unwrap().len()
+        );
+
+        let _ = fs::remove_dir_all(root);
+        let _ = fs::remove_dir_all(dest);
+    }
+
+    #[test]
+    fn refuses_to_export_into_the_stik_folder() {
+        let root = temp_dir("vault-self");
+        let err = export_library_inner(
+            &root,
+            &root.join("export"),
+            &LibraryExportOptions::default(),
+            |_| {},
+        )
+        .err()
+        .expect("rejected");
+        assert!(err.contains("outside"));
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -28,7 +28,7 @@ pub fn show(_title: &str, _subtitle: &str, _body: &str) -> Result<(), String> {
     Ok(())
 }
 
-fn escape_applescript(value: &str) -> String {
+pub(crate) fn escape_applescript(value: &str) -> String {
     value
         .replace('\\', "\\\\")
         .replace('"', "\\\"")
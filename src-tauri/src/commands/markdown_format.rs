@@ -0,0 +1,210 @@
+// Markdown normalization for captured and pasted text.
+//
+// `format_markdown` rewrites `*`/`+` bullets as `-`, trims trailing
+// whitespace, collapses runs of three or more blank lines into one and
+// ends the note with a single newline. Frontmatter and fenced code blocks
+// are copied byte-for-byte. Formatting is idempotent.
+
+/// A fence opener or closer: the marker character and its length.
+fn fence_marker(line: &str) -> Option<(char, usize)> {
+    let trimmed = line.trim_start();
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|c| *c == marker).count();
+    (len >= 3).then_some((marker, len))
+}
+
+/// Length of the YAML frontmatter block at the start, closing line included.
+fn frontmatter_len(lines: &[&str]) -> usize {
+    if lines.first().map(|line| line.trim_end()) != Some("---") {
+        return 0;
+    }
+    lines
+        .iter()
+        .skip(1)
+        .position(|line| matches!(line.trim_end(), "---" | "..."))
+        .map_or(0, |closing| closing + 2)
+}
+
+fn normalize_bullet(line: &str) -> String {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+    let mut chars = rest.chars();
+    match (chars.next(), chars.next()) {
+        (Some('*' | '+'), Some(' ' | '\t')) => format!("{}-{}", &line[..indent], &rest[1..]),
+        _ => line.to_string(),
+    }
+}
+
+pub fn format_markdown(content: &str) -> String {
+    let lines: Vec<&str> = content.split('\n').collect();
+    let frontmatter = frontmatter_len(&lines);
+
+    // (text, verbatim): verbatim lines are never trimmed or dropped.
+    let mut out: Vec<(String, bool)> = lines[..frontmatter]
+        .iter()
+        .map(|line| (line.to_string(), true))
+        .collect();
+    let mut fence: Option<(char, usize)> = None;
+    let mut blank_run: Vec<String> = Vec::new();
+
+    for line in &lines[frontmatter..] {
+        if let Some((marker, len)) = fence {
+            out.push((line.to_string(), true));
+            if fence_marker(line).is_some_and(|(m, l)| m == marker && l >= len)
+                && line
+                    .trim_start()
+                    .trim_start_matches(marker)
+                    .trim()
+                    .is_empty()
+            {
+                fence = None;
+            }
+            continue;
+        }
+
+        if line.trim().is_empty() {
+            blank_run.push(String::new());
+            continue;
+        }
+        if blank_run.len() >= 3 {
+            blank_run.truncate(1);
+        }
+        out.extend(blank_run.drain(..).map(|blank| (blank, false)));
+
+        if let Some(marker) = fence_marker(line) {
+            fence = Some(marker);
+            out.push((line.to_string(), true));
+        } else {
+            out.push((normalize_bullet(line.trim_end()), false));
+        }
+    }
+
+    // Trailing blank lines go; the final newline is added back below.
+    while out
+        .last()
+        .is_some_and(|(line, verbatim)| !verbatim && line.is_empty())
+    {
+        out.pop();
+    }
+    if out.iter().all(|(line, _)| line.is_empty()) {
+        return String::new();
+    }
+    let mut formatted = out
+        .into_iter()
+        .map(|(line, _)| line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !formatted.ends_with('\n') {
+        formatted.push('\n');
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_markdown;
+
+    fn assert_idempotent(input: &str) {
+        let once = format_markdown(input);
+        assert_eq!(
+            format_markdown(&once),
+            once,
+            "not idempotent for {:?}",
+            input
+        );
+    }
+
+    #[test]
+    fn bullets_become_dashes_at_any_depth() {
+        assert_eq!(
+            format_markdown("* one\n+ two\n  * nested\n\t+ tabbed\n* [ ] task"),
+            "- one\n- two\n  - nested\n\t- tabbed\n- [ ] task\n"
+        );
+    }
+
+    #[test]
+    fn emphasis_and_rules_are_not_bullets() {
+        assert_eq!(
+            format_markdown("**bold** text\n***\n*italic*"),
+            "**bold** text\n***\n*italic*\n"
+        );
+    }
+
+    #[test]
+    fn trailing_whitespace_is_trimmed() {
+        assert_eq!(format_markdown("title   \nbody\t\n"), "title\nbody\n");
+        assert_eq!(format_markdown("windows\r\nline\r\n"), "windows\nline\n");
+    }
+
+    #[test]
+    fn long_blank_runs_collapse_to_one_line() {
+        assert_eq!(format_markdown("a\n\n\n\nb"), "a\n\nb\n");
+        assert_eq!(format_markdown("a\n \t\n  \n\nb"), "a\n\nb\n");
+        // Two blank lines are left alone.
+        assert_eq!(format_markdown("a\n\n\nb"), "a\n\n\nb\n");
+    }
+
+    #[test]
+    fn note_ends_with_exactly_one_newline() {
+        assert_eq!(format_markdown("text"), "text\n");
+        assert_eq!(format_markdown("text\n\n\n"), "text\n");
+        assert_eq!(format_markdown(""), "");
+        assert_eq!(format_markdown("\n\n  \n"), "");
+    }
+
+    #[test]
+    fn code_fences_are_untouched() {
+        let input = "* list\n```md\n* keep   \n\n\n\n+ this\t\n```\n* after  ";
+        assert_eq!(
+            format_markdown(input),
+            "- list\n```md\n* keep   \n\n\n\n+ this\t\n```\n- after\n"
+        );
+    }
+
+    #[test]
+    fn fences_close_only_on_a_matching_marker() {
+        let input = "````\n```\n* inside\n````\n* outside";
+        assert_eq!(
+            format_markdown(input),
+            "````\n```\n* inside\n````\n- outside\n"
+        );
+        let tilde = "~~~\n```\n* inside  \n~~~\n* out";
+        assert_eq!(format_markdown(tilde), "~~~\n```\n* inside  \n~~~\n- out\n");
+    }
+
+    #[test]
+    fn unclosed_fence_keeps_the_rest_verbatim() {
+        let input = "intro\n```\n* code  \n\n\n\n";
+        assert_eq!(format_markdown(input), input);
+    }
+
+    #[test]
+    fn frontmatter_is_untouched() {
+        let input = "---\ntags: [a, b]   \n* not a bullet\n---\n* bullet  \n";
+        assert_eq!(
+            format_markdown(input),
+            "---\ntags: [a, b]   \n* not a bullet\n---\n- bullet\n"
+        );
+        // Without a closing line it is ordinary text.
+        assert_eq!(format_markdown("---\n* item"), "---\n- item\n");
+    }
+
+    #[test]
+    fn formatting_twice_changes_nothing() {
+        for input in [
+            "",
+            "\n",
+            "plain",
+            "* a\n\n\n\n\n+ b   \n",
+            "---\ntitle: x\n---\n\n\n\n\n* y",
+            "```\n* code\n```\n\n\n\n* text\t",
+            "intro\n```\nunclosed  \n\n\n",
+            "  * indented\n\t+ tab\n\n\n",
+            "a\r\n\r\n\r\n\r\nb\r\n",
+            "~~~~\n~~~\n~~~~\n* z",
+            "日本語  \n* 項目\n\n\n\n",
+        ] {
+            assert_idempotent(input);
+        }
+    }
+}
@@ -1,22 +1,64 @@
 pub mod ai_assistant;
 pub mod analytics;
+pub mod app_log;
 pub mod apple_notes;
+pub mod backup;
+pub mod capture_context;
+pub mod capture_draft;
+pub mod cloud_sync;
+pub mod config_paths;
+pub mod crash_reports;
 pub mod cursor_positions;
+pub mod daily_note;
 pub mod darwinkit;
+pub mod day_one;
+pub mod diagnostics;
 pub mod dictation;
+pub mod digest;
+pub mod edit_locks;
 pub mod embeddings;
+pub mod expiry;
+pub mod export;
 pub mod file_watcher;
+pub mod filename_template;
+pub mod focus_mode;
 pub mod folders;
+pub mod frontmost_app;
+pub mod git_credentials;
 pub mod git_share;
 pub mod icloud;
 pub mod index;
+pub mod integration_server;
+pub mod language;
+pub mod library_export;
 pub mod macos_notify;
+pub mod markdown_format;
+pub mod note_ids;
 pub mod note_lock;
+pub mod note_metadata;
 pub mod notes;
 pub mod on_this_day;
+pub mod onboarding;
+pub mod outline;
+pub mod pins;
+pub mod reminders;
+pub mod selection_capture;
 pub mod settings;
 pub mod share;
+pub mod speech_dictation;
+pub mod spotlight;
 pub mod stats;
 pub mod sticked_notes;
 pub mod storage;
+pub mod sync_summary;
+pub mod tasks;
+pub mod templates;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod text_import;
+pub mod tray_menu;
+pub mod user_notifications;
 pub mod versioning;
+pub mod webhook;
+pub mod widget_feed;
+pub mod window_geometry;
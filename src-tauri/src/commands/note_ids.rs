@@ -0,0 +1,141 @@
+/// Stable note ids that survive renames and moves.
+///
+/// A note's id is a UUID in its `stik-id:` frontmatter key, written only
+/// when the user asks for one (`get_note_id`), never when a note is merely
+/// opened. A note without frontmatter gets a block holding just
+/// the id, with the rest of the file left byte-for-byte as it was. The
+/// NoteIndex keeps the id → path map, so an id resolves to wherever the
+/// note lives now.
+use std::path::Path;
+use tauri::{AppHandle, Manager, State};
+use uuid::Uuid;
+
+use super::expiry::split_frontmatter;
+use super::index::NoteIndex;
+use super::{cloud_sync, edit_locks, git_share, note_lock, notes, storage, window_geometry};
+
+pub const NOTE_ID_KEY: &str = "stik-id";
+
+/// The note's id, from its `stik-id:` frontmatter key.
+pub fn note_id(content: &str) -> Option<String> {
+    let (fields, _) = split_frontmatter(content)?;
+    fields.iter().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+        (key.trim() == NOTE_ID_KEY && !value.is_empty()).then(|| value.to_string())
+    })
+}
+
+/// `content` with `id` as the first frontmatter field. Everything after the
+/// opening `---` (or, without frontmatter, the whole note) is kept as is.
+fn with_note_id(content: &str, id: &str) -> String {
+    if split_frontmatter(content).is_some() {
+        let opening = content.split_inclusive('\n').next().unwrap_or_default();
+        let newline = if opening.ends_with("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        return format!(
+            "{}{}: {}{}{}",
+            opening,
+            NOTE_ID_KEY,
+            id,
+            newline,
+            &content[opening.len()..]
+        );
+    }
+    format!("---\n{}: {}\n---\n{}", NOTE_ID_KEY, id, content)
+}
+
+/// The id of the note at `path`, writing a new one into its frontmatter if
+/// it has none yet. Locked, read-only and not-yet-downloaded notes can't be
+/// given one.
+pub fn ensure_note_id(app: &AppHandle, path: &str) -> Result<String, String> {
+    let index = app.state::<NoteIndex>();
+    if let Some(id) = index.get(path).and_then(|entry| entry.id) {
+        return Ok(id);
+    }
+
+    let content = notes::get_note_content_inner(path)?;
+    let folder = notes::folder_of(Path::new(path));
+    if let Some(id) = note_id(&content) {
+        index.add(path, &folder);
+        return Ok(id);
+    }
+    if note_lock::is_locked_content(&content) {
+        return Err("Unlock the note to give it an id".to_string());
+    }
+    if cloud_sync::looks_evicted(Path::new(path)) {
+        return Err("Note is still downloading from the cloud".to_string());
+    }
+    edit_locks::ensure_editable(path)?;
+
+    let id = Uuid::new_v4().to_string();
+    let updated = with_note_id(&content, &id);
+    storage::write_file(path, &updated)?;
+    notes::sync_viewing_note_content(app, path, &updated);
+    index.add(path, &folder);
+    window_geometry::adopt_note_id(path, &id);
+    git_share::notify_note_changed(&folder);
+    Ok(id)
+}
+
+/// The stable id of the note at `path`, assigning one on first use.
+#[tauri::command]
+pub fn get_note_id(app: AppHandle, path: String) -> Result<String, String> {
+    ensure_note_id(&app, &path)
+}
+
+/// Where the note with this id lives now, or `None` if no indexed note has it.
+#[tauri::command]
+pub fn resolve_note_id(id: String, index: State<'_, NoteIndex>) -> Result<Option<String>, String> {
+    index.path_for_id(&id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "1b4e28ba-2fa1-11d2-883f-0016d3cca427";
+
+    #[test]
+    fn id_is_read_from_frontmatter_only() {
+        let content = format!("---\ntags: [a]\nstik-id: \"{}\"\n---\n# Note\n", ID);
+        assert_eq!(note_id(&content).as_deref(), Some(ID));
+        assert_eq!(note_id(&format!("stik-id: {}\n", ID)), None);
+        assert_eq!(note_id("---\nstik-id:\n---\nbody"), None);
+        assert_eq!(note_id("---stik-locked---\nabc"), None);
+    }
+
+    #[test]
+    fn id_gets_a_minimal_frontmatter_block() {
+        let content = "# Groceries\n\n- milk\n";
+        let updated = with_note_id(content, ID);
+        assert_eq!(updated, format!("---\nstik-id: {}\n---\n{}", ID, content));
+        assert_eq!(note_id(&updated).as_deref(), Some(ID));
+    }
+
+    #[test]
+    fn id_joins_existing_frontmatter_without_touching_the_rest() {
+        let content = "---\r\ntags: [car]\r\nexpires: 2026-03-05\r\n---\r\nB3  \n";
+        let updated = with_note_id(content, ID);
+        assert_eq!(
+            updated,
+            format!(
+                "---\r\nstik-id: {}\r\ntags: [car]\r\nexpires: 2026-03-05\r\n---\r\nB3  \n",
+                ID
+            )
+        );
+        assert_eq!(note_id(&updated).as_deref(), Some(ID));
+    }
+
+    #[test]
+    fn unclosed_frontmatter_is_treated_as_content() {
+        let content = "---\nnot frontmatter";
+        assert_eq!(
+            with_note_id(content, ID),
+            format!("---\nstik-id: {}\n---\n{}", ID, content)
+        );
+    }
+}
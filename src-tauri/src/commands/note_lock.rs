@@ -328,6 +328,7 @@ pub fn save_locked_note(path: String, content: String) -> Result<(), String> {
     if !is_session_unlocked(settings.note_lock.timeout_minutes) {
         return Err("Not authenticated".to_string());
     }
+    super::edit_locks::ensure_editable(&path)?;
 
     let key = get_or_create_key()?;
     let locked = encrypt(&content, &key)?;
@@ -0,0 +1,182 @@
+/// Per-note metadata for viewing windows: when a note was captured and last
+/// changed, how big it is and which tags it carries.
+///
+/// The word count comes from the NoteIndex while its entry still matches
+/// the file on disk; a missing or outdated entry is recounted from the
+/// note, which is read for its tags anyway. Times use the index's
+/// `YYYYMMDD-HHMMSS` form.
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, Manager, State};
+
+use super::index::{self, NoteIndex};
+use super::{expiry, note_lock, notes, stats};
+use crate::state::AppState;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteMetadata {
+    pub path: String,
+    /// Capture time from the filename, or the file's mtime for notes not
+    /// named by Stik.
+    pub created: String,
+    pub modified: String,
+    pub size_bytes: u64,
+    /// 0 for locked notes.
+    pub word_count: usize,
+    /// Frontmatter `tags:` followed by inline `#hashtags`, without the `#`.
+    pub tags: Vec<String>,
+    /// Open in a sticked viewing window.
+    pub sticked: bool,
+}
+
+fn push_tag(tags: &mut Vec<String>, tag: &str) {
+    let tag = tag
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .trim_start_matches('#');
+    if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+        tags.push(tag.to_string());
+    }
+}
+
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '/')
+}
+
+/// Tags from the frontmatter (`tags: [a, b]`, `tags: a, b` or a `- a`
+/// list) and `#hashtags` in the body outside code fences.
+pub fn extract_tags(content: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let (fields, body) = expiry::split_frontmatter(content).unwrap_or((Vec::new(), content));
+
+    let mut in_tag_list = false;
+    for line in fields {
+        if in_tag_list {
+            if let Some(item) = line.trim_start().strip_prefix("- ") {
+                push_tag(&mut tags, item);
+                continue;
+            }
+            in_tag_list = false;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        if !key.trim().eq_ignore_ascii_case("tags") {
+            continue;
+        }
+        let value = value.trim().trim_start_matches('[').trim_end_matches(']');
+        if value.is_empty() {
+            in_tag_list = true;
+        }
+        for tag in value.split(',') {
+            push_tag(&mut tags, tag);
+        }
+    }
+
+    let mut in_fence = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let mut previous = ' ';
+        for (i, c) in line.char_indices() {
+            if c == '#' && previous.is_whitespace() {
+                let rest = &line[i + 1..];
+                let end = rest.find(|c| !is_tag_char(c)).unwrap_or(rest.len());
+                let tag = &rest[..end];
+                // `#` alone is a heading marker, `#1` an issue number.
+                if tag.chars().next().is_some_and(char::is_alphabetic) {
+                    push_tag(&mut tags, tag);
+                }
+            }
+            previous = c;
+        }
+    }
+    tags
+}
+
+/// Metadata for the note at `path`.
+pub fn note_metadata(index: &NoteIndex, path: &str, sticked: bool) -> Result<NoteMetadata, String> {
+    let file_metadata =
+        fs::metadata(path).map_err(|e| format!("Failed to read note metadata: {}", e))?;
+    let modified = file_metadata
+        .modified()
+        .map(index::format_timestamp)
+        .unwrap_or_default();
+    let size_bytes = file_metadata.len();
+
+    let filename = Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let created = stats::parse_timestamp_from_filename(&filename)
+        .map(|at| at.format("%Y%m%d-%H%M%S").to_string())
+        .unwrap_or_else(|| modified.clone());
+
+    let content = notes::get_note_content_inner(path)?;
+    let locked = note_lock::is_locked_content(&content);
+    let indexed = index
+        .get(path)
+        .filter(|entry| entry.created == modified && entry.content_len as u64 == size_bytes);
+    let word_count = match indexed {
+        Some(entry) if !entry.truncated_in_index => entry.word_count,
+        _ if locked => 0,
+        _ => index::count_words(&content),
+    };
+    let tags = if locked {
+        Vec::new()
+    } else {
+        extract_tags(&content)
+    };
+
+    Ok(NoteMetadata {
+        path: path.to_string(),
+        created,
+        modified,
+        size_bytes,
+        word_count,
+        tags,
+        sticked,
+    })
+}
+
+#[tauri::command]
+pub fn get_note_metadata(
+    app: AppHandle,
+    path: String,
+    index: State<'_, NoteIndex>,
+) -> Result<NoteMetadata, String> {
+    let sticked = app
+        .state::<AppState>()
+        .viewing_note_ids
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains_key(&path);
+    note_metadata(&index, &path, sticked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_come_from_frontmatter_and_hashtags() {
+        let content =
+            "---\ntags: [Work, \"q3\"]\n---\n# Plan\nShip it #work #launch-day, see #12\n";
+        assert_eq!(extract_tags(content), vec!["Work", "q3", "launch-day"]);
+
+        let listed = "---\ntitle: x\ntags:\n  - errands\n  - car\nexpires: 2026-03-05\n---\nB3";
+        assert_eq!(extract_tags(listed), vec!["errands", "car"]);
+    }
+
+    #[test]
+    fn hashtags_in_code_and_words_are_ignored() {
+        let content = "C# and url.com/#anchor\n```\n#include <stdio.h>\n```\n#real";
+        assert_eq!(extract_tags(content), vec!["real"]);
+    }
+}
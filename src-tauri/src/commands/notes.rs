@@ -1,15 +1,35 @@
 use base64::Engine;
 use chrono::Local;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager, State};
 
 use super::analytics;
+use super::cloud_sync;
+use super::edit_locks::{self, EditLocks};
 use super::embeddings::{self, EmbeddingIndex};
+use super::expiry;
+use super::filename_template;
 use super::folders::get_stik_folder;
 use super::git_share;
-use super::index::NoteIndex;
-use crate::state::{AppState, LastSavedNote};
+use super::index::{NoteEntry, NoteIndex, SearchFacets};
+use super::markdown_format;
+use super::spotlight;
+use super::tray_menu;
+use super::webhook;
+use super::widget_feed;
+use crate::state::{AppState, LastSavedNote, RecentSave};
+
+const INBOX_FOLDER: &str = "Inbox";
+/// Identical saves closer together than this are treated as one.
+const DUPLICATE_SAVE_WINDOW: Duration = Duration::from_secs(5);
+const RECENT_SAVES_LIMIT: usize = 8;
+const RECENT_IN_FOLDER_DEFAULT: usize = 3;
+const RECENT_IN_FOLDER_MAX: usize = 20;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoteSaved {
@@ -18,6 +38,88 @@ pub struct NoteSaved {
     pub filename: String,
 }
 
+// Note change events, broadcast to every window once the index already
+// reflects the change, so a listener can re-query straight away:
+//
+// - `note-saved` (`NoteSaved`): a new note was written; after auto-filing
+//   this is where it ended up.
+// - `note-updated` (`NoteSaved`): an existing note was rewritten.
+// - `note-deleted` (`NoteDeleted`): a note is gone, including when an
+//   update emptied it or a note open for viewing was deleted outside Stik.
+// - `note-moved` (`NoteMoved`): a note changed folders.
+// - `notes-deleted` (`Vec<NoteDeleted>`): a batch delete from the manager
+//   removed these notes; sent once instead of a `note-deleted` per note.
+pub const NOTE_SAVED_EVENT: &str = "note-saved";
+pub const NOTE_UPDATED_EVENT: &str = "note-updated";
+pub const NOTE_DELETED_EVENT: &str = "note-deleted";
+pub const NOTE_MOVED_EVENT: &str = "note-moved";
+pub const NOTES_DELETED_EVENT: &str = "notes-deleted";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteDeleted {
+    pub path: String,
+    pub folder: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteMoved {
+    pub old_path: String,
+    pub old_folder: String,
+    pub path: String,
+    pub folder: String,
+    pub filename: String,
+}
+
+/// Folder name of a note path, i.e. its parent directory.
+pub(crate) fn folder_of(path: &std::path::Path) -> String {
+    path.parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+pub fn emit_note_deleted(app: &AppHandle, path: &str) {
+    let _ = app.emit(
+        NOTE_DELETED_EVENT,
+        NoteDeleted {
+            path: path.to_string(),
+            folder: folder_of(std::path::Path::new(path)),
+        },
+    );
+}
+
+fn emit_note_moved(app: &AppHandle, old_path: &str, moved: &NoteInfo) {
+    let _ = app.emit(
+        NOTE_MOVED_EVENT,
+        NoteMoved {
+            old_path: old_path.to_string(),
+            old_folder: folder_of(std::path::Path::new(old_path)),
+            path: moved.path.clone(),
+            folder: moved.folder.clone(),
+            filename: moved.filename.clone(),
+        },
+    );
+}
+
+/// Outcome of one path in a `delete_notes` batch.
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteDeletion {
+    pub path: String,
+    /// Where the note sits in `.trash` now, when it was trashed.
+    pub trashed_path: Option<String>,
+    /// Why this note was left in place; `None` on success.
+    pub error: Option<String>,
+}
+
+/// A note in the capture window's "recently captured here" strip.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecentNote {
+    pub path: String,
+    pub title: String,
+    pub preview: String,
+    pub created: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NoteInfo {
     pub path: String,
@@ -27,6 +129,23 @@ pub struct NoteInfo {
     pub created: String,
     #[serde(default)]
     pub locked: bool,
+    #[serde(default)]
+    pub word_count: usize,
+    #[serde(default)]
+    pub reading_minutes: usize,
+    #[serde(default)]
+    pub language: String,
+    #[serde(default)]
+    pub truncated_in_index: bool,
+    /// Edit-locked: updates and deletes fail with `NOTE_LOCKED`.
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub open_tasks: usize,
+    #[serde(default)]
+    pub done_tasks: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -39,6 +158,22 @@ pub struct SearchResult {
     pub created: String,
     #[serde(default)]
     pub locked: bool,
+    #[serde(default)]
+    pub word_count: usize,
+    #[serde(default)]
+    pub reading_minutes: usize,
+    #[serde(default)]
+    pub language: String,
+    #[serde(default)]
+    pub truncated_in_index: bool,
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub open_tasks: usize,
+    #[serde(default)]
+    pub done_tasks: usize,
 }
 
 /// Generate a slug from content (first 5 words, max 40 chars)
@@ -70,11 +205,20 @@ fn generate_slug(content: &str) -> String {
 
 /// Generate timestamp-based filename with UUID suffix to prevent collisions
 fn generate_filename(content: &str) -> String {
-    let now = Local::now();
-    let timestamp = now.format("%Y%m%d-%H%M%S").to_string();
+    generate_filename_at(content, Local::now().naive_local())
+}
+
+/// Filename for a note created at `created`, for imports that keep the
+/// original date.
+pub(crate) fn generate_filename_at(content: &str, created: chrono::NaiveDateTime) -> String {
     let slug = generate_slug(content);
-    let suffix = &uuid::Uuid::new_v4().to_string()[..4];
-    format!("{}-{}-{}.md", timestamp, slug, suffix)
+    let uuid = uuid::Uuid::new_v4().simple().to_string();
+    filename_template::render(
+        &filename_template::configured_template(),
+        created,
+        &slug,
+        &uuid,
+    )
 }
 
 fn is_break_placeholder_line(line: &str) -> bool {
@@ -90,6 +234,19 @@ pub fn is_effectively_empty_markdown(content: &str) -> bool {
     })
 }
 
+/// Content as it should be written: normalized when `format_on_save` is on.
+/// Locked notes are ciphertext and are never touched.
+fn formatted_for_save(content: String) -> String {
+    let enabled = super::settings::load_settings_from_file()
+        .map(|s| s.format_on_save)
+        .unwrap_or(false);
+    if enabled && !super::note_lock::is_locked_content(&content) {
+        markdown_format::format_markdown(&content)
+    } else {
+        content
+    }
+}
+
 /// Core save logic, callable from other Rust modules without Tauri State
 pub fn save_note_inner(folder: String, content: String) -> Result<NoteSaved, String> {
     if !folder.is_empty() {
@@ -105,6 +262,7 @@ pub fn save_note_inner(folder: String, content: String) -> Result<NoteSaved, Str
         });
     }
 
+    let content = formatted_for_save(content);
     let stik_folder = get_stik_folder()?;
     let folder_path = stik_folder.join(&folder);
 
@@ -136,10 +294,16 @@ pub fn post_save_processing(app: &AppHandle, result: &NoteSaved, content: &str)
         "note_created",
         serde_json::json!({ "word_count": word_count }),
     );
+    webhook::notify(webhook::NOTE_SAVED, &result.path, &result.folder, content);
 
     let index = app.state::<NoteIndex>();
     index.add(&result.path, &result.folder);
+    super::stats::invalidate_day_counts();
+    spotlight::note_saved(&index, &result.path);
+    widget_feed::notes_changed();
     git_share::notify_note_changed(&result.folder);
+    super::stats::refresh_tray_streak_debounced(app);
+    tray_menu::rebuild_tray_menu(app);
 
     if super::settings::load_settings_from_file()
         .map(|s| s.ai_features_enabled)
@@ -147,6 +311,7 @@ pub fn post_save_processing(app: &AppHandle, result: &NoteSaved, content: &str)
     {
         let emb_index = app.state::<EmbeddingIndex>();
         if let Some(emb) = embeddings::embed_content(content) {
+            index.set_language(&result.path, &emb.language);
             emb_index.add_entry(&result.path, emb);
             let _ = emb_index.save();
         }
@@ -163,6 +328,47 @@ pub fn post_save_processing(app: &AppHandle, result: &NoteSaved, content: &str)
     });
 }
 
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The note an identical save (same content and folder) wrote within
+/// `DUPLICATE_SAVE_WINDOW` of `now`.
+fn find_duplicate_save<'a>(
+    recent: &'a VecDeque<RecentSave>,
+    content_hash: u64,
+    folder: &str,
+    now: Instant,
+) -> Option<&'a RecentSave> {
+    recent.iter().rev().find(|save| {
+        save.content_hash == content_hash
+            && save.folder == folder
+            && now.saturating_duration_since(save.at) <= DUPLICATE_SAVE_WINDOW
+    })
+}
+
+fn record_save(recent: &mut VecDeque<RecentSave>, save: RecentSave) {
+    recent.retain(|old| save.at.saturating_duration_since(old.at) <= DUPLICATE_SAVE_WINDOW);
+    recent.push_back(save);
+    while recent.len() > RECENT_SAVES_LIMIT {
+        recent.pop_front();
+    }
+}
+
+/// Point the recorded save of the note at `old_path` to where it was moved.
+fn retarget_save(recent: &mut VecDeque<RecentSave>, old_path: &str, moved: &NoteSaved) {
+    if let Some(save) = recent.iter_mut().rev().find(|save| save.path == old_path) {
+        save.path = moved.path.clone();
+        save.saved_folder = moved.folder.clone();
+        save.filename = moved.filename.clone();
+    }
+}
+
+/// Save a new note. An identical save arriving within a few seconds of the
+/// last one (double ⌘Enter, a retry after a slow disk) returns the note
+/// already written instead of creating a copy.
 #[tauri::command]
 pub fn save_note(
     app: AppHandle,
@@ -171,27 +377,246 @@ pub fn save_note(
     _index: State<'_, NoteIndex>,
     _emb_index: State<'_, EmbeddingIndex>,
 ) -> Result<NoteSaved, String> {
-    let result = save_note_inner(folder, content.clone())?;
+    let state = app.state::<AppState>();
+    let hash = content_hash(&content);
+    let result = {
+        // Held only across the check, the file write and the record, so a
+        // concurrent duplicate waits for the first to be recorded but never
+        // for indexing or embedding requests.
+        let mut recent = state.recent_saves.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(save) = find_duplicate_save(&recent, hash, &folder, Instant::now())
+            .filter(|save| super::storage::path_exists(&save.path))
+        {
+            return Ok(NoteSaved {
+                path: save.path.clone(),
+                folder: save.saved_folder.clone(),
+                filename: save.filename.clone(),
+            });
+        }
+
+        let result = save_note_inner(folder.clone(), content.clone())?;
+        if !result.path.is_empty() {
+            record_save(
+                &mut recent,
+                RecentSave {
+                    content_hash: hash,
+                    folder,
+                    at: Instant::now(),
+                    path: result.path.clone(),
+                    saved_folder: result.folder.clone(),
+                    filename: result.filename.clone(),
+                },
+            );
+        }
+        result
+    };
+    if result.path.is_empty() {
+        return Ok(result);
+    }
+
     post_save_processing(&app, &result, &content);
+    let filed = auto_file_from_inbox(&app, &result, &content);
+    if let Some(filed) = &filed {
+        let mut recent = state.recent_saves.lock().unwrap_or_else(|e| e.into_inner());
+        retarget_save(&mut recent, &result.path, filed);
+    }
+    let result = filed.unwrap_or(result);
+    let _ = app.emit(NOTE_SAVED_EVENT, &result);
     Ok(result)
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoFiledNote {
+    pub path: String,
+    pub from: String,
+    pub to: String,
+    pub score: f64,
+}
+
+/// Move a note just saved to Inbox into its best-matching folder when the
+/// user opted in and the match is confident. Returns the new location.
+fn auto_file_from_inbox(app: &AppHandle, saved: &NoteSaved, content: &str) -> Option<NoteSaved> {
+    if saved.path.is_empty() || saved.folder != INBOX_FOLDER {
+        return None;
+    }
+
+    let settings = super::settings::load_settings_from_file().ok()?;
+    if !settings.auto_file_from_inbox {
+        return None;
+    }
+
+    let emb_index = app.state::<EmbeddingIndex>();
+    let (target, score) = match super::darwinkit::best_folder_match(content, &emb_index) {
+        Ok(Some(best)) => best,
+        Ok(None) => return None,
+        Err(e) => {
+            log::error!("Auto-file suggestion failed: {}", e);
+            return None;
+        }
+    };
+
+    if !should_auto_file(&settings, &saved.folder, &target, score) {
+        return None;
+    }
+
+    let index = app.state::<NoteIndex>();
+    let moved = match move_note_inner(&saved.path, &target, &index, &emb_index) {
+        Ok(moved) => moved,
+        Err(e) => {
+            log::error!("Failed to auto-file note: {}", e);
+            return None;
+        }
+    };
+
+    let state = app.state::<AppState>();
+    state
+        .auto_filed_notes
+        .lock()
+        .unwrap_or_else(|e: std::sync::PoisonError<_>| e.into_inner())
+        .insert(moved.path.clone(), saved.folder.clone());
+    update_last_saved_note(&state, &saved.path, &moved.path, &moved.folder);
+    emit_note_moved(app, &saved.path, &moved);
+
+    let _ = app.emit(
+        "note-auto-filed",
+        AutoFiledNote {
+            path: moved.path.clone(),
+            from: saved.folder.clone(),
+            to: moved.folder.clone(),
+            score: (score * 100.0).round() / 100.0,
+        },
+    );
+
+    Some(NoteSaved {
+        path: moved.path,
+        folder: moved.folder,
+        filename: moved.filename,
+    })
+}
+
+fn should_auto_file(
+    settings: &super::settings::StikSettings,
+    from_folder: &str,
+    to_folder: &str,
+    score: f64,
+) -> bool {
+    to_folder != INBOX_FOLDER
+        && to_folder != from_folder
+        && score >= settings.auto_file_threshold
+        && !git_share::move_leaves_linked_folder(settings, from_folder, to_folder)
+}
+
+/// Keep open viewing windows' cached content in step with our own writes,
+/// so the file watcher doesn't report them back as external edits.
+pub(crate) fn sync_viewing_note_content(app: &AppHandle, path: &str, content: &str) {
+    let state = app.state::<AppState>();
+    let Some(id) = state
+        .viewing_note_ids
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(path)
+        .cloned()
+    else {
+        return;
+    };
+    let mut viewing_notes = state.viewing_notes.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(note) = viewing_notes.get_mut(&id) {
+        note.content = content.to_string();
+        note.has_unsaved_edits = false;
+    }
+}
+
+/// Point `last_saved_note` at a note's new location if it was the last save.
+fn update_last_saved_note(state: &AppState, old_path: &str, new_path: &str, new_folder: &str) {
+    let mut last = state
+        .last_saved_note
+        .lock()
+        .unwrap_or_else(|e: std::sync::PoisonError<_>| e.into_inner());
+    if last.as_ref().is_some_and(|note| note.path == old_path) {
+        *last = Some(LastSavedNote {
+            path: new_path.to_string(),
+            folder: new_folder.to_string(),
+        });
+    }
+}
+
+/// Move an auto-filed note back to the folder it was saved to.
+#[tauri::command]
+pub fn undo_auto_file(
+    app: AppHandle,
+    path: String,
+    index: State<'_, NoteIndex>,
+    emb_index: State<'_, EmbeddingIndex>,
+) -> Result<NoteInfo, String> {
+    let state = app.state::<AppState>();
+    let original_folder = state
+        .auto_filed_notes
+        .lock()
+        .unwrap_or_else(|e: std::sync::PoisonError<_>| e.into_inner())
+        .remove(&path)
+        .ok_or("Note was not auto-filed")?;
+
+    let restored = move_note_inner(&path, &original_folder, &index, &emb_index)?;
+    update_last_saved_note(&state, &path, &restored.path, &restored.folder);
+    emit_note_moved(&app, &path, &restored);
+    let _ = app.emit("files-changed", vec![path, restored.path.clone()]);
+    Ok(restored)
+}
+
 #[tauri::command]
 pub fn list_notes(
     folder: Option<String>,
     index: State<'_, NoteIndex>,
 ) -> Result<Vec<NoteInfo>, String> {
-    let entries = index.list(folder.as_deref())?;
+    Ok(note_infos(index.list(folder.as_deref())?))
+}
 
-    Ok(entries
+/// Index entries as the manager lists them, previews in place of content.
+pub fn note_infos(entries: Vec<NoteEntry>) -> Vec<NoteInfo> {
+    let edit_locks = EditLocks::load();
+    entries
         .into_iter()
         .map(|e| NoteInfo {
+            read_only: edit_locks.contains(&e.path),
             locked: e.locked,
             path: e.path,
             filename: e.filename,
             folder: e.folder,
             content: e.preview,
             created: e.created,
+            word_count: e.word_count,
+            reading_minutes: e.reading_minutes,
+            language: e.language,
+            truncated_in_index: e.truncated_in_index,
+            pinned: e.pinned,
+            open_tasks: e.open_tasks,
+            done_tasks: e.done_tasks,
+        })
+        .collect()
+}
+
+/// The newest notes in `folder`, from the index. A folder that doesn't
+/// exist yet simply has none.
+#[tauri::command]
+pub fn get_recent_notes_in_folder(
+    folder: String,
+    limit: Option<usize>,
+    index: State<'_, NoteIndex>,
+) -> Result<Vec<RecentNote>, String> {
+    let limit = limit
+        .unwrap_or(RECENT_IN_FOLDER_DEFAULT)
+        .min(RECENT_IN_FOLDER_MAX);
+    // Newest captures, whether or not they are pinned.
+    let mut entries = index.list(Some(&folder))?;
+    entries.sort_by(|a, b| b.created.cmp(&a.created));
+    Ok(entries
+        .into_iter()
+        .take(limit)
+        .map(|e| RecentNote {
+            path: e.path,
+            title: e.title,
+            preview: e.preview,
+            created: e.created,
         })
         .collect())
 }
@@ -201,16 +626,29 @@ pub fn search_notes(
     query: String,
     folder: Option<String>,
     index: State<'_, NoteIndex>,
+) -> Result<Vec<SearchResult>, String> {
+    search_notes_inner(&index, &query, folder.as_deref())
+}
+
+pub fn search_notes_inner(
+    index: &NoteIndex,
+    query: &str,
+    folder: Option<&str>,
 ) -> Result<Vec<SearchResult>, String> {
     if query.trim().is_empty() {
         return Ok(Vec::new());
     }
 
-    let results = index.search(&query, folder.as_deref())?;
+    let results = index.search(query, folder)?;
+    Ok(to_search_results(results))
+}
 
-    Ok(results
+fn to_search_results(results: Vec<(NoteEntry, String)>) -> Vec<SearchResult> {
+    let edit_locks = EditLocks::load();
+    results
         .into_iter()
         .map(|(entry, snippet)| SearchResult {
+            read_only: edit_locks.contains(&entry.path),
             locked: entry.locked,
             path: entry.path,
             filename: entry.filename,
@@ -218,8 +656,44 @@ pub fn search_notes(
             title: entry.title,
             snippet,
             created: entry.created,
+            word_count: entry.word_count,
+            reading_minutes: entry.reading_minutes,
+            language: entry.language,
+            truncated_in_index: entry.truncated_in_index,
+            pinned: entry.pinned,
+            open_tasks: entry.open_tasks,
+            done_tasks: entry.done_tasks,
         })
-        .collect())
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FacetedSearch {
+    pub results: Vec<SearchResult>,
+    /// Present when asked for.
+    pub facets: Option<SearchFacets>,
+}
+
+/// `search_notes` plus, with `include_facets`, match counts per folder and
+/// tag for the manager sidebar.
+#[tauri::command]
+pub fn search_notes_faceted(
+    query: String,
+    folder: Option<String>,
+    include_facets: bool,
+    index: State<'_, NoteIndex>,
+) -> Result<FacetedSearch, String> {
+    if query.trim().is_empty() {
+        return Ok(FacetedSearch {
+            results: Vec::new(),
+            facets: include_facets.then(SearchFacets::default),
+        });
+    }
+    let (results, facets) = index.search_with_facets(&query, folder.as_deref(), include_facets)?;
+    Ok(FacetedSearch {
+        results: to_search_results(results),
+        facets,
+    })
 }
 
 pub fn get_note_content_inner(path: &str) -> Result<String, String> {
@@ -260,6 +734,7 @@ pub fn get_note_content(path: String) -> Result<String, String> {
 
 #[tauri::command]
 pub fn update_note(
+    app: AppHandle,
     path: String,
     content: String,
     index: State<'_, NoteIndex>,
@@ -287,13 +762,29 @@ pub fn update_note(
     if !super::storage::path_exists(&path) {
         return Err("Note file does not exist".to_string());
     }
+    edit_locks::ensure_editable(&path)?;
+
+    // Get folder name from path
+    let folder = folder_of(&note_path);
 
-    // In Stik-managed notes, empty content deletes the note.
+    // In Stik-managed notes, empty content deletes the note. A note evicted
+    // by iCloud or another sync client reads as empty, so the editor never
+    // saw what it holds; keep it.
     if in_stik_folder && is_effectively_empty_markdown(&content) {
+        if cloud_sync::looks_evicted(&note_path) {
+            return Err("Note is still downloading from the cloud".to_string());
+        }
         super::storage::delete_file(&path).map_err(|e| format!("Failed to delete note: {}", e))?;
+        webhook::notify(webhook::NOTE_DELETED, &path, &folder, &content);
         index.remove(&path);
+        super::stats::invalidate_day_counts();
+        spotlight::note_removed(&path);
+        widget_feed::notes_changed();
         emb_index.remove_entry(&path);
         let _ = emb_index.save();
+        git_share::notify_note_changed(&folder);
+        tray_menu::rebuild_tray_menu(&app);
+        emit_note_deleted(&app, &path);
         return Ok(NoteSaved {
             path: String::new(),
             folder: String::new(),
@@ -301,47 +792,80 @@ pub fn update_note(
         });
     }
 
-    // Get folder name from path
-    let folder = note_path
-        .parent()
-        .and_then(|p| p.file_name())
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_default();
-
     let filename = note_path
         .file_name()
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_default();
 
     // Write updated content
+    let content = formatted_for_save(content);
     super::storage::write_file(&path, &content)?;
+    sync_viewing_note_content(&app, &path, &content);
 
     let word_count = content.split_whitespace().count();
     analytics::track(
         "note_updated",
         serde_json::json!({ "word_count": word_count }),
     );
+    webhook::notify(webhook::NOTE_UPDATED, &path, &folder, &content);
 
     if in_stik_folder {
         // Re-index with updated content
         index.add(&path, &folder);
+        spotlight::note_saved(&index, &path);
+        widget_feed::notes_changed();
         git_share::notify_note_changed(&folder);
+        tray_menu::rebuild_tray_menu(&app);
         if super::settings::load_settings_from_file()
             .map(|s| s.ai_features_enabled)
             .unwrap_or(false)
         {
             if let Some(emb) = embeddings::embed_content(&content) {
+                index.set_language(&path, &emb.language);
                 emb_index.add_entry(&path, emb);
                 let _ = emb_index.save();
             }
         }
     }
 
-    Ok(NoteSaved {
+    let updated = NoteSaved {
         path: note_path.to_string_lossy().to_string(),
         folder,
         filename,
-    })
+    };
+    let _ = app.emit(NOTE_UPDATED_EVENT, &updated);
+    Ok(updated)
+}
+
+/// Normalize a note's markdown in place. Returns the formatted content.
+#[tauri::command]
+pub fn format_note(
+    app: AppHandle,
+    path: String,
+    index: State<'_, NoteIndex>,
+) -> Result<String, String> {
+    let stik_folder = get_stik_folder()?;
+    let note_path = PathBuf::from(&path);
+    if !note_path.starts_with(&stik_folder) {
+        return Err("Invalid path: note must be within Stik folder".to_string());
+    }
+
+    edit_locks::ensure_editable(&path)?;
+    let content = super::storage::read_file(&path)?;
+    if super::note_lock::is_locked_content(&content) {
+        return Err("Unlock the note to format it".to_string());
+    }
+    let formatted = markdown_format::format_markdown(&content);
+    if formatted == content {
+        return Ok(formatted);
+    }
+
+    super::storage::write_file(&path, &formatted)?;
+    sync_viewing_note_content(&app, &path, &formatted);
+    let folder = folder_of(&note_path);
+    index.add(&path, &folder);
+    git_share::notify_note_changed(&folder);
+    Ok(formatted)
 }
 
 #[tauri::command]
@@ -363,15 +887,13 @@ pub fn delete_note(
     if !super::storage::path_exists(&path) {
         return Err("Note file does not exist".to_string());
     }
+    edit_locks::ensure_editable(&path)?;
 
-    let folder = note_path
-        .parent()
-        .and_then(|p| p.file_name())
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_default();
+    let folder = folder_of(&note_path);
 
     // Delete referenced .assets/ images
-    if let Ok(content) = super::storage::read_file(&path) {
+    let content = super::storage::read_file(&path).unwrap_or_default();
+    if !content.is_empty() {
         let folder_path = note_path.parent().unwrap_or(&stik_folder);
         delete_note_assets(&content, folder_path);
     }
@@ -379,26 +901,177 @@ pub fn delete_note(
     // Delete the file
     super::storage::delete_file(&path).map_err(|e| format!("Failed to delete note: {}", e))?;
     analytics::track("note_deleted", serde_json::json!({}));
+    webhook::notify(webhook::NOTE_DELETED, &path, &folder, &content);
     index.remove(&path);
+    super::stats::invalidate_day_counts();
+    spotlight::note_removed(&path);
+    widget_feed::notes_changed();
     emb_index.remove_entry(&path);
     let _ = emb_index.save();
     git_share::notify_note_changed(&folder);
+    tray_menu::rebuild_tray_menu(&app);
 
-    // Notify any viewing windows so they can close themselves
-    let _ = app.emit("note-deleted", &path);
+    // Viewing windows of this note close themselves on it
+    emit_note_deleted(&app, &path);
 
     Ok(true)
 }
 
+/// Why `path` can't be deleted, if anything.
+fn deletion_error(path: &str, stik_folder: &std::path::Path, locks: &EditLocks) -> Option<String> {
+    if !PathBuf::from(path).starts_with(stik_folder) {
+        return Some("Invalid path: note must be within Stik folder".to_string());
+    }
+    if !super::storage::path_exists(path) {
+        return Some("Note file does not exist".to_string());
+    }
+    if locks.contains(path) {
+        return Some(edit_locks::NOTE_LOCKED.to_string());
+    }
+    None
+}
+
+/// Delete several notes at once, e.g. cleaning up after an import. Every
+/// path is validated before anything is touched; a note that fails is
+/// reported in its `NoteDeletion` and the rest still go. With `to_trash`
+/// notes move into `.trash` with their images left in place, otherwise
+/// they and their `.assets/` images are deleted for good. Index and
+/// embedding removals, git notifications and the `notes-deleted` event
+/// happen once for the whole batch.
+#[tauri::command]
+pub fn delete_notes(
+    app: AppHandle,
+    paths: Vec<String>,
+    to_trash: bool,
+    index: State<'_, NoteIndex>,
+    emb_index: State<'_, EmbeddingIndex>,
+) -> Result<Vec<NoteDeletion>, String> {
+    let stik_folder = get_stik_folder()?;
+    let locks = EditLocks::load();
+    let mut results: Vec<NoteDeletion> = paths
+        .into_iter()
+        .map(|path| NoteDeletion {
+            error: deletion_error(&path, &stik_folder, &locks),
+            path,
+            trashed_path: None,
+        })
+        .collect();
+
+    let trash = stik_folder.join(expiry::TRASH_FOLDER);
+    let mut deleted: Vec<NoteDeleted> = Vec::new();
+    for result in results.iter_mut().filter(|result| result.error.is_none()) {
+        let note_path = PathBuf::from(&result.path);
+        let content = super::storage::read_file(&result.path).unwrap_or_default();
+
+        let outcome = if to_trash {
+            super::storage::ensure_dir(&trash.to_string_lossy()).and_then(|_| {
+                let filename = note_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let trashed_path = expiry::trash_destination(&trash, &filename);
+                super::storage::move_file(&result.path, &trashed_path).map(|_| Some(trashed_path))
+            })
+        } else {
+            super::storage::delete_file(&result.path).map(|_| None)
+        };
+        match outcome {
+            Ok(trashed_path) => result.trashed_path = trashed_path,
+            Err(e) if to_trash => {
+                result.error = Some(format!("Failed to move note to trash: {}", e));
+                continue;
+            }
+            Err(e) => {
+                result.error = Some(format!("Failed to delete note: {}", e));
+                continue;
+            }
+        }
+
+        if !to_trash && !content.is_empty() {
+            delete_note_assets(&content, note_path.parent().unwrap_or(&stik_folder));
+        }
+        let folder = folder_of(&note_path);
+        webhook::notify(webhook::NOTE_DELETED, &result.path, &folder, &content);
+        deleted.push(NoteDeleted {
+            path: result.path.clone(),
+            folder,
+        });
+    }
+
+    if deleted.is_empty() {
+        return Ok(results);
+    }
+    let deleted_paths: Vec<String> = deleted.iter().map(|note| note.path.clone()).collect();
+    index.remove_many(&deleted_paths);
+    super::stats::invalidate_day_counts();
+    spotlight::notes_removed(&deleted_paths);
+    widget_feed::notes_changed();
+    emb_index.remove_entries(&deleted_paths);
+    let _ = emb_index.save();
+    let folders: Vec<String> = deleted.iter().map(|note| note.folder.clone()).collect();
+    git_share::notify_notes_changed(&folders);
+    analytics::track(
+        "notes_deleted",
+        serde_json::json!({ "count": deleted.len(), "to_trash": to_trash }),
+    );
+    tray_menu::rebuild_tray_menu(&app);
+    let _ = app.emit(NOTES_DELETED_EVENT, &deleted);
+
+    Ok(results)
+}
+
 #[tauri::command]
 pub fn move_note(
+    app: AppHandle,
     path: String,
     target_folder: String,
     index: State<'_, NoteIndex>,
     emb_index: State<'_, EmbeddingIndex>,
+) -> Result<NoteInfo, String> {
+    let moved = move_note_inner(&path, &target_folder, &index, &emb_index)?;
+    tray_menu::rebuild_tray_menu(&app);
+    emit_note_moved(&app, &path, &moved);
+    Ok(moved)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MovedToNewFolder {
+    pub note: NoteInfo,
+    /// False when the folder already existed and this was a plain move.
+    pub folder_created: bool,
+}
+
+/// Move a note into a folder that may not exist yet, creating it first.
+/// If the move then fails, the new folder is kept rather than rolled back.
+#[tauri::command]
+pub fn move_note_to_new_folder(
+    app: AppHandle,
+    path: String,
+    new_folder_name: String,
+    index: State<'_, NoteIndex>,
+    emb_index: State<'_, EmbeddingIndex>,
+) -> Result<MovedToNewFolder, String> {
+    let folder = new_folder_name.trim();
+    let folder_created = super::folders::create_folder_inner(folder)?;
+    let moved = move_note_inner(&path, folder, &index, &emb_index);
+    tray_menu::rebuild_tray_menu(&app);
+    let note = moved?;
+    emit_note_moved(&app, &path, &note);
+    Ok(MovedToNewFolder {
+        note,
+        folder_created,
+    })
+}
+
+/// Core move logic, shared by `move_note` and Inbox auto-filing.
+pub fn move_note_inner(
+    path: &str,
+    target_folder: &str,
+    index: &NoteIndex,
+    emb_index: &EmbeddingIndex,
 ) -> Result<NoteInfo, String> {
     let stik_folder = get_stik_folder()?;
-    let source_path = PathBuf::from(&path);
+    let source_path = PathBuf::from(path);
     let source_folder = source_path
         .parent()
         .and_then(|p| p.file_name())
@@ -411,12 +1084,12 @@ pub fn move_note(
     }
 
     // Check source file exists
-    if !super::storage::path_exists(&path) {
+    if !super::storage::path_exists(path) {
         return Err("Note file does not exist".to_string());
     }
 
     // Ensure target folder exists
-    let target_folder_path = stik_folder.join(&target_folder);
+    let target_folder_path = stik_folder.join(target_folder);
     super::storage::ensure_dir(&target_folder_path.to_string_lossy())?;
 
     // Get filename from source
@@ -430,7 +1103,7 @@ pub fn move_note(
     let target_path = target_folder_path.join(&filename);
 
     // Read content before moving
-    let content = super::storage::read_file(&path)?;
+    let content = super::storage::read_file(path)?;
 
     // Move referenced .assets/ images to the target folder
     if source_folder != target_folder {
@@ -439,27 +1112,57 @@ pub fn move_note(
     }
 
     // Move the file
-    super::storage::move_file(&path, &target_path.to_string_lossy())
+    super::storage::move_file(path, &target_path.to_string_lossy())
         .map_err(|e| format!("Failed to move note: {}", e))?;
 
     let new_path_str = target_path.to_string_lossy().to_string();
-    index.move_entry(&path, &new_path_str, &target_folder);
-    emb_index.move_entry(&path, &new_path_str);
+    edit_locks::note_moved(path, &new_path_str);
+    index.move_entry(path, &new_path_str, target_folder);
+    spotlight::note_moved(index, path, &new_path_str);
+    widget_feed::notes_changed();
+    emb_index.move_entry(path, &new_path_str);
     let _ = emb_index.save();
     git_share::notify_note_changed(&source_folder);
-    git_share::notify_note_changed(&target_folder);
+    git_share::notify_note_changed(target_folder);
 
     // Extract created date from filename
-    let created = filename.split('-').take(2).collect::<Vec<_>>().join("-");
+    let created = filename_template::created_from_filename(&filename)
+        .and_then(|created| created.timestamp())
+        .map(|at| at.format("%Y%m%d-%H%M%S").to_string())
+        .unwrap_or_default();
 
     let locked = super::note_lock::is_locked_content(&content);
+    let word_count = if locked {
+        0
+    } else {
+        super::index::count_words(&content)
+    };
+    let (language, truncated_in_index) = index
+        .get(&new_path_str)
+        .map(|entry| (entry.language, entry.truncated_in_index))
+        .unwrap_or_else(|| (super::language::UNDETERMINED.to_string(), false));
+    let read_only = edit_locks::is_edit_locked(&new_path_str);
+    let pinned = !locked && super::pins::is_pinned(&content);
+    let task_counts = if locked {
+        super::tasks::TaskCounts::default()
+    } else {
+        super::tasks::count_tasks(&content)
+    };
     Ok(NoteInfo {
         path: new_path_str,
         filename,
-        folder: target_folder,
+        folder: target_folder.to_string(),
         content,
         created,
         locked,
+        word_count,
+        reading_minutes: super::index::reading_minutes(word_count),
+        language,
+        truncated_in_index,
+        read_only,
+        pinned,
+        open_tasks: task_counts.open,
+        done_tasks: task_counts.done,
     })
 }
 
@@ -633,7 +1336,94 @@ pub fn save_note_image_from_path(
 
 #[cfg(test)]
 mod tests {
-    use super::is_effectively_empty_markdown;
+    use super::{
+        content_hash, find_duplicate_save, is_effectively_empty_markdown, record_save,
+        should_auto_file, DUPLICATE_SAVE_WINDOW, RECENT_SAVES_LIMIT,
+    };
+    use crate::commands::settings::StikSettings;
+    use crate::state::RecentSave;
+    use std::collections::VecDeque;
+    use std::time::{Duration, Instant};
+
+    fn recent_save(content: &str, folder: &str, at: Instant) -> RecentSave {
+        RecentSave {
+            content_hash: content_hash(content),
+            folder: folder.to_string(),
+            at,
+            path: format!("/Stik/{}/{}.md", folder, content),
+            saved_folder: folder.to_string(),
+            filename: format!("{}.md", content),
+        }
+    }
+
+    #[test]
+    fn identical_save_within_the_window_is_a_duplicate() {
+        let start = Instant::now();
+        let mut recent = VecDeque::new();
+        record_save(&mut recent, recent_save("idea", "Inbox", start));
+
+        let again = start + Duration::from_secs(2);
+        let found = find_duplicate_save(&recent, content_hash("idea"), "Inbox", again);
+        assert_eq!(
+            found.map(|save| save.path.as_str()),
+            Some("/Stik/Inbox/idea.md")
+        );
+        let edge = start + DUPLICATE_SAVE_WINDOW;
+        assert!(find_duplicate_save(&recent, content_hash("idea"), "Inbox", edge).is_some());
+    }
+
+    #[test]
+    fn different_text_or_folder_is_not_a_duplicate() {
+        let start = Instant::now();
+        let mut recent = VecDeque::new();
+        record_save(&mut recent, recent_save("idea", "Inbox", start));
+
+        let soon = start + Duration::from_secs(1);
+        assert!(find_duplicate_save(&recent, content_hash("idea!"), "Inbox", soon).is_none());
+        assert!(find_duplicate_save(&recent, content_hash("idea"), "Work", soon).is_none());
+    }
+
+    #[test]
+    fn repeated_capture_minutes_later_is_a_new_note() {
+        let start = Instant::now();
+        let mut recent = VecDeque::new();
+        record_save(&mut recent, recent_save("idea", "Inbox", start));
+
+        let later = start + Duration::from_secs(3 * 60);
+        assert!(find_duplicate_save(&recent, content_hash("idea"), "Inbox", later).is_none());
+        // Recording a save drops entries too old to match.
+        record_save(&mut recent, recent_save("other", "Inbox", later));
+        assert_eq!(recent.len(), 1);
+    }
+
+    #[test]
+    fn recent_saves_are_capped() {
+        let start = Instant::now();
+        let mut recent = VecDeque::new();
+        for n in 0..RECENT_SAVES_LIMIT + 3 {
+            record_save(&mut recent, recent_save(&n.to_string(), "Inbox", start));
+        }
+        assert_eq!(recent.len(), RECENT_SAVES_LIMIT);
+        assert!(find_duplicate_save(&recent, content_hash("0"), "Inbox", start).is_none());
+    }
+
+    #[test]
+    fn auto_filed_duplicate_points_at_the_moved_note() {
+        let start = Instant::now();
+        let mut recent = VecDeque::new();
+        record_save(&mut recent, recent_save("idea", "Inbox", start));
+        let moved = NoteSaved {
+            path: "/Stik/Work/idea.md".to_string(),
+            folder: "Work".to_string(),
+            filename: "idea.md".to_string(),
+        };
+        retarget_save(&mut recent, "/Stik/Inbox/idea.md", &moved);
+
+        let found = find_duplicate_save(&recent, content_hash("idea"), "Inbox", start)
+            .expect("save should still match on its original folder");
+        assert_eq!(found.path, "/Stik/Work/idea.md");
+        assert_eq!(found.saved_folder, "Work");
+    }
 
     #[test]
     fn placeholder_breaks_only_are_treated_as_empty() {
@@ -644,4 +1434,20 @@ mod tests {
     fn real_content_with_placeholders_is_not_empty() {
         assert!(!is_effectively_empty_markdown("hello\n\n<br>\n"));
     }
+
+    #[test]
+    fn auto_file_requires_confident_match_outside_inbox() {
+        let settings = StikSettings::default();
+        assert!(should_auto_file(&settings, "Inbox", "Work", 0.8));
+        assert!(!should_auto_file(&settings, "Inbox", "Work", 0.4));
+        assert!(!should_auto_file(&settings, "Inbox", "Inbox", 0.9));
+    }
+
+    #[test]
+    fn auto_file_never_moves_notes_out_of_git_linked_inbox() {
+        let mut settings = StikSettings::default();
+        settings.git_sharing.enabled = true;
+        settings.git_sharing.remote_url = "git@github.com:team/notes.git".to_string();
+        assert!(!should_auto_file(&settings, "Inbox", "Work", 0.9));
+    }
 }
@@ -1,21 +1,60 @@
-use chrono::{Datelike, Local, NaiveDate};
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::PathBuf;
-
-use super::folders::get_stik_folder;
-use super::macos_notify;
+use std::sync::OnceLock;
+use std::thread;
+use tauri::{AppHandle, Manager, State};
+
+use super::config_paths;
+use super::filename_template;
+use super::focus_mode;
+use super::folders::is_visible_folder_name;
+use super::index::NoteIndex;
+use super::settings;
+use super::user_notifications;
 use super::versioning;
 
 const PREVIEW_MAX_LEN: usize = 120;
 
+/// Notification kind; the payload is the candidate note's path.
+pub const NOTIFICATION_KIND: &str = "on-this-day";
+const SCHEDULER_TICK_SECONDS: u64 = 60;
+
+static SCHEDULER_RUNNING: OnceLock<()> = OnceLock::new();
+
 #[derive(Debug, Clone)]
 struct OnThisDayCandidate {
+    path: String,
     date: NaiveDate,
+    /// Year whose anniversary of today the note falls near. Differs from
+    /// `date.year()` for a late-December note matched in early January.
+    anniversary_year: i32,
+    /// Days between the note and that anniversary; 0 is an exact match.
+    days_off: i64,
     folder: String,
+    title: String,
     preview: String,
 }
 
+impl OnThisDayCandidate {
+    fn years_ago(&self, today: NaiveDate) -> i32 {
+        today.year() - self.anniversary_year
+    }
+}
+
+/// One past note written on today's month and day (or, when there are
+/// none, within the configured window around it).
+#[derive(Debug, Clone, Serialize)]
+pub struct OnThisDayNote {
+    pub path: String,
+    pub date: String,
+    pub years_ago: i32,
+    /// False for a note from the surrounding week rather than this day.
+    pub exact: bool,
+    pub folder: String,
+    pub title: String,
+    pub preview: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct OnThisDayState {
     last_notified_date: Option<String>,
@@ -30,31 +69,93 @@ pub struct OnThisDayStatus {
     pub preview: Option<String>,
 }
 
-pub fn maybe_show_on_this_day_notification() -> Result<(), String> {
-    let _ = check_on_this_day(false, true)?;
+/// Show today's notification if On This Day is enabled, its time has come
+/// and it hasn't been shown yet today. Safe to call repeatedly.
+pub fn maybe_show_on_this_day_notification(index: &NoteIndex) -> Result<(), String> {
+    let settings = settings::load_settings_from_file()?;
+    let state = load_state()?;
+    let now = Local::now().naive_local();
+
+    if !is_notification_due(
+        settings.on_this_day_enabled,
+        settings.on_this_day_time.as_deref(),
+        state.last_notified_date.as_deref(),
+        now,
+    ) {
+        return Ok(());
+    }
+    // Not recorded as shown, so the next tick tries again.
+    if focus_mode::should_suppress_interruptions() {
+        return Ok(());
+    }
+
+    // Record the check even when nothing matches, so the scheduler doesn't
+    // rescan the index every minute for the rest of the day.
+    save_state(&OnThisDayState {
+        last_notified_date: Some(now.date().format("%Y-%m-%d").to_string()),
+    })?;
+    let _ = check_on_this_day(index, true)?;
     Ok(())
 }
 
+/// Start the once-a-minute scheduler for the configured notification
+/// time. Call once during app setup.
+pub fn start_scheduler(app: AppHandle) {
+    if SCHEDULER_RUNNING.set(()).is_err() {
+        return;
+    }
+
+    thread::Builder::new()
+        .name("stik-on-this-day".to_string())
+        .spawn(move || loop {
+            thread::sleep(std::time::Duration::from_secs(SCHEDULER_TICK_SECONDS));
+            if let Err(e) = maybe_show_on_this_day_notification(&app.state::<NoteIndex>()) {
+                log::error!("Failed to check On This Day notification: {}", e);
+            }
+        })
+        .ok();
+}
+
+/// Works whether or not the scheduled notification is enabled.
 #[tauri::command]
-pub fn check_on_this_day_now() -> Result<OnThisDayStatus, String> {
-    check_on_this_day(true, true)
+pub fn check_on_this_day_now(index: State<'_, NoteIndex>) -> Result<OnThisDayStatus, String> {
+    check_on_this_day(&index, true)
 }
 
-fn check_on_this_day(force: bool, show_notification: bool) -> Result<OnThisDayStatus, String> {
+/// Every note from today's month and day in earlier years, newest first.
+/// Read-only: the notification throttle is left alone.
+#[tauri::command]
+pub fn get_on_this_day_notes(index: State<'_, NoteIndex>) -> Result<Vec<OnThisDayNote>, String> {
     let today = Local::now().date_naive();
-    let state = load_state()?;
-
-    if !force && !should_notify_today(state.last_notified_date.as_deref(), today) {
-        return Ok(OnThisDayStatus {
-            found: false,
-            message: "On This Day already shown today".to_string(),
-            date: None,
-            folder: None,
-            preview: None,
-        });
-    }
+    let window_days = settings::load_settings_from_file()
+        .map(|s| s.on_this_day_window_days)
+        .unwrap_or_default();
+    let mut candidates = collect_candidates(&index, today, window_days)?;
+    candidates.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.path.cmp(&b.path)));
+
+    Ok(candidates
+        .into_iter()
+        .map(|candidate| OnThisDayNote {
+            years_ago: candidate.years_ago(today),
+            exact: candidate.days_off == 0,
+            date: candidate.date.format("%Y-%m-%d").to_string(),
+            path: candidate.path,
+            folder: candidate.folder,
+            title: candidate.title,
+            preview: candidate.preview,
+        })
+        .collect())
+}
 
-    let candidates = collect_candidates(today)?;
+fn check_on_this_day(
+    index: &NoteIndex,
+    show_notification: bool,
+) -> Result<OnThisDayStatus, String> {
+    let today = Local::now().date_naive();
+    let window_days = settings::load_settings_from_file()
+        .map(|s| s.on_this_day_window_days)
+        .unwrap_or_default();
+    let candidates = collect_candidates(index, today, window_days)?;
     let Some(candidate) = select_best_candidate(&candidates) else {
         return Ok(OnThisDayStatus {
             found: false,
@@ -68,11 +169,18 @@ fn check_on_this_day(force: bool, show_notification: bool) -> Result<OnThisDaySt
     if show_notification {
         let title = "On This Day";
         let subtitle = &format!(
-            "{} ({})",
+            "{} · {} ({})",
+            describe_when(&candidate, today),
             candidate.folder,
             candidate.date.format("%b %d, %Y")
         );
-        macos_notify::show(title, subtitle, &candidate.preview)?;
+        user_notifications::show(
+            NOTIFICATION_KIND,
+            &candidate.path,
+            title,
+            subtitle,
+            &candidate.preview,
+        )?;
 
         let new_state = OnThisDayState {
             last_notified_date: Some(today.format("%Y-%m-%d").to_string()),
@@ -89,71 +197,93 @@ fn check_on_this_day(force: bool, show_notification: bool) -> Result<OnThisDaySt
     })
 }
 
-fn collect_candidates(today: NaiveDate) -> Result<Vec<OnThisDayCandidate>, String> {
-    let stik_folder = get_stik_folder()?;
-    let mut candidates = Vec::new();
-
-    let folders: Vec<PathBuf> = fs::read_dir(&stik_folder)
-        .map_err(|e| e.to_string())?
-        .filter_map(|entry| entry.ok())
-        .filter(|entry| entry.path().is_dir())
-        .map(|entry| entry.path())
+/// Notes from today's month and day in earlier years. When there are none,
+/// notes within `window_days` of that day instead.
+fn collect_candidates(
+    index: &NoteIndex,
+    today: NaiveDate,
+    window_days: u32,
+) -> Result<Vec<OnThisDayCandidate>, String> {
+    let candidates: Vec<OnThisDayCandidate> = index
+        .list(None)?
+        .into_iter()
+        .filter(|entry| is_visible_folder_name(&entry.folder))
+        .filter_map(|entry| {
+            let date = parse_date_from_filename(&entry.filename)?;
+            let (anniversary_year, days_off) = nearest_anniversary(date, today, window_days)?;
+            let preview = if entry.locked {
+                "(locked note)".to_string()
+            } else {
+                build_preview(&entry.preview)
+            };
+            Some(OnThisDayCandidate {
+                path: entry.path,
+                date,
+                anniversary_year,
+                days_off,
+                folder: entry.folder,
+                title: entry.title,
+                preview,
+            })
+        })
         .collect();
 
-    for folder_path in folders {
-        let folder_name = folder_path
-            .file_name()
-            .and_then(|name| name.to_str())
-            .unwrap_or("Inbox")
-            .to_string();
-
-        if let Ok(entries) = fs::read_dir(&folder_path) {
-            for entry in entries.filter_map(|e| e.ok()) {
-                let path = entry.path();
-                if !path.extension().is_some_and(|ext| ext == "md") {
-                    continue;
-                }
-
-                let filename = match path.file_name().and_then(|name| name.to_str()) {
-                    Some(name) => name,
-                    None => continue,
-                };
-
-                let Some(date) = parse_date_from_filename(filename) else {
-                    continue;
-                };
-
-                if date.month() == today.month()
-                    && date.day() == today.day()
-                    && date.year() < today.year()
-                {
-                    let content = fs::read_to_string(&path).unwrap_or_default();
-                    candidates.push(OnThisDayCandidate {
-                        date,
-                        folder: folder_name.clone(),
-                        preview: build_preview(&content),
-                    });
-                }
-            }
-        }
+    if candidates.iter().any(|candidate| candidate.days_off == 0) {
+        return Ok(candidates
+            .into_iter()
+            .filter(|candidate| candidate.days_off == 0)
+            .collect());
     }
-
     Ok(candidates)
 }
 
+/// The earlier year whose anniversary of `today` is closest to `date`, and
+/// how many days off it is — or None beyond `window_days`. Notes from the
+/// current year never match.
+fn nearest_anniversary(date: NaiveDate, today: NaiveDate, window_days: u32) -> Option<(i32, i64)> {
+    if date.year() >= today.year() {
+        return None;
+    }
+    [date.year() - 1, date.year(), date.year() + 1]
+        .into_iter()
+        .filter(|year| *year < today.year())
+        .filter_map(|year| {
+            // Feb 29 falls on Feb 28 in common years.
+            let anniversary = today
+                .with_year(year)
+                .or_else(|| NaiveDate::from_ymd_opt(year, today.month(), today.day() - 1))?;
+            Some((year, (date - anniversary).num_days().abs()))
+        })
+        .filter(|(_, days_off)| *days_off <= i64::from(window_days))
+        .min_by_key(|(_, days_off)| *days_off)
+}
+
+/// Exact-day matches first, then the most recent year, then the closest day.
 fn select_best_candidate(candidates: &[OnThisDayCandidate]) -> Option<OnThisDayCandidate> {
-    candidates
-        .iter()
-        .cloned()
-        .max_by_key(|candidate| candidate.date)
+    candidates.iter().cloned().max_by_key(|candidate| {
+        (
+            candidate.days_off == 0,
+            candidate.anniversary_year,
+            std::cmp::Reverse(candidate.days_off),
+            candidate.date,
+        )
+    })
 }
 
-fn parse_date_from_filename(filename: &str) -> Option<NaiveDate> {
-    let date_segment = filename.split('-').next()?;
-    if date_segment.len() != 8 {
-        return None;
+fn describe_when(candidate: &OnThisDayCandidate, today: NaiveDate) -> String {
+    let years = match candidate.years_ago(today) {
+        1 => "1 year ago".to_string(),
+        n => format!("{} years ago", n),
+    };
+    if candidate.days_off == 0 {
+        format!("{} today", years)
+    } else {
+        format!("{} this week", years)
     }
-    NaiveDate::parse_from_str(date_segment, "%Y%m%d").ok()
+}
+
+fn parse_date_from_filename(filename: &str) -> Option<NaiveDate> {
+    filename_template::created_from_filename(filename).map(|created| created.date)
 }
 
 fn build_preview(content: &str) -> String {
@@ -180,6 +310,22 @@ fn build_preview(content: &str) -> String {
     }
 }
 
+fn is_notification_due(
+    enabled: bool,
+    time_of_day: Option<&str>,
+    last_notified_date: Option<&str>,
+    now: NaiveDateTime,
+) -> bool {
+    if !enabled || !should_notify_today(last_notified_date, now.date()) {
+        return false;
+    }
+    // No time configured: show on the first check of the day (launch).
+    match time_of_day.and_then(settings::parse_time_of_day) {
+        Some(scheduled_at) => now.time() >= scheduled_at,
+        None => true,
+    }
+}
+
 fn should_notify_today(last_notified_date: Option<&str>, today: NaiveDate) -> bool {
     match last_notified_date {
         Some(last) => last != today.format("%Y-%m-%d").to_string(),
@@ -187,15 +333,8 @@ fn should_notify_today(last_notified_date: Option<&str>, today: NaiveDate) -> bo
     }
 }
 
-fn get_state_path() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    let stik_config = home.join(".stik");
-    fs::create_dir_all(&stik_config).map_err(|e| e.to_string())?;
-    Ok(stik_config.join("on_this_day.json"))
-}
-
 fn load_state() -> Result<OnThisDayState, String> {
-    let path = get_state_path()?;
+    let path = config_paths::config_file("on_this_day.json")?;
     match versioning::load_versioned::<OnThisDayState>(&path)? {
         Some(state) => Ok(state),
         None => Ok(OnThisDayState::default()),
@@ -203,7 +342,7 @@ fn load_state() -> Result<OnThisDayState, String> {
 }
 
 fn save_state(state: &OnThisDayState) -> Result<(), String> {
-    let path = get_state_path()?;
+    let path = config_paths::writable_config_file("on_this_day.json")?;
     versioning::save_versioned(&path, state)
 }
 
@@ -211,10 +350,20 @@ fn save_state(state: &OnThisDayState) -> Result<(), String> {
 mod tests {
     use super::*;
 
+    fn day(date: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").expect("valid date")
+    }
+
     fn candidate(date: &str, folder: &str, preview: &str) -> OnThisDayCandidate {
+        let (anniversary_year, days_off) =
+            nearest_anniversary(day(date), day("2026-02-06"), 3).expect("within window");
         OnThisDayCandidate {
-            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").expect("valid date"),
+            path: format!("/notes/{}/{}.md", folder, date),
+            date: day(date),
+            anniversary_year,
+            days_off,
             folder: folder.to_string(),
+            title: preview.to_string(),
             preview: preview.to_string(),
         }
     }
@@ -241,6 +390,46 @@ mod tests {
         assert_eq!(selected.folder, "Work");
     }
 
+    #[test]
+    fn prefers_exact_day_over_a_newer_near_miss() {
+        let candidates = vec![
+            candidate("2025-02-08", "Inbox", "near"),
+            candidate("2022-02-06", "Work", "exact"),
+            candidate("2024-02-05", "Ideas", "near and older"),
+        ];
+        let selected = select_best_candidate(&candidates).expect("candidate exists");
+        assert_eq!(selected.preview, "exact");
+
+        let near_misses = vec![candidates[0].clone(), candidates[2].clone()];
+        let selected = select_best_candidate(&near_misses).expect("candidate exists");
+        assert_eq!(selected.preview, "near");
+        assert_eq!(
+            describe_when(&selected, day("2026-02-06")),
+            "1 year ago this week"
+        );
+    }
+
+    #[test]
+    fn window_spans_year_boundaries_and_skips_this_year() {
+        let today = day("2026-01-02");
+        assert_eq!(
+            nearest_anniversary(day("2024-12-31"), today, 3),
+            Some((2025, 2))
+        );
+        assert_eq!(
+            nearest_anniversary(day("2025-01-02"), today, 3),
+            Some((2025, 0))
+        );
+        assert_eq!(nearest_anniversary(day("2025-01-09"), today, 3), None);
+        assert_eq!(nearest_anniversary(day("2026-01-01"), today, 3), None);
+        // A note from late December last year is still "last year".
+        assert_eq!(nearest_anniversary(day("2025-12-31"), today, 3), None);
+        assert_eq!(
+            nearest_anniversary(day("2023-02-28"), day("2024-02-29"), 0),
+            Some((2023, 0))
+        );
+    }
+
     #[test]
     fn skips_notification_if_already_shown_today() {
         let today = NaiveDate::from_ymd_opt(2026, 2, 6).expect("valid date");
@@ -249,9 +438,39 @@ mod tests {
         assert!(should_notify_today(None, today));
     }
 
+    #[test]
+    fn notification_waits_for_the_configured_time() {
+        let at = |time: &str| {
+            NaiveDateTime::parse_from_str(&format!("2026-02-06 {}", time), "%Y-%m-%d %H:%M")
+                .expect("valid time")
+        };
+        assert!(!is_notification_due(true, Some("18:30"), None, at("09:47")));
+        assert!(is_notification_due(true, Some("18:30"), None, at("18:30")));
+        assert!(!is_notification_due(
+            true,
+            Some("18:30"),
+            Some("2026-02-06"),
+            at("19:00")
+        ));
+        assert!(is_notification_due(
+            true,
+            None,
+            Some("2026-02-05"),
+            at("09:47")
+        ));
+        assert!(!is_notification_due(false, None, None, at("09:47")));
+    }
+
     #[test]
     fn builds_single_line_preview() {
         let preview = build_preview("\nFirst line\n\nSecond line\n");
         assert_eq!(preview, "First line Second line");
     }
+
+    #[test]
+    fn truncates_preview_on_a_char_boundary() {
+        let preview = build_preview(&"é".repeat(PREVIEW_MAX_LEN));
+        assert!(preview.ends_with("..."));
+        assert!(preview.len() <= PREVIEW_MAX_LEN + 3);
+    }
 }
@@ -0,0 +1,161 @@
+/// First-launch onboarding. Fresh installs start with
+/// `settings.onboarding.completed = false`; the welcome flow collects the
+/// user's choices and hands them to `complete_onboarding`, which creates the
+/// folders, binds the capture shortcut and marks onboarding done.
+///
+/// Until then the seeded folder shortcuts stay unregistered, so the capture
+/// shortcut the user picks can't collide with one of them.
+use chrono::Local;
+use serde::Deserialize;
+
+use super::folders::{self, validate_name};
+use super::settings::{self, OnboardingSettings, ShortcutMapping, StikSettings};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OnboardingChoices {
+    pub default_folder: String,
+    /// Extra folders to create next to the default one.
+    #[serde(default)]
+    pub starter_folders: Vec<String>,
+    pub analytics_enabled: bool,
+    pub capture_shortcut: String,
+}
+
+#[tauri::command]
+pub fn get_onboarding_state() -> Result<OnboardingSettings, String> {
+    Ok(settings::get_settings()?.onboarding)
+}
+
+/// Apply `choices` to `settings`. Returns the folders to create, default
+/// folder first.
+fn apply_choices(
+    settings: &mut StikSettings,
+    choices: &OnboardingChoices,
+    completed_at: String,
+) -> Result<Vec<String>, String> {
+    let default_folder = choices.default_folder.trim();
+    if default_folder.is_empty() {
+        return Err("Pick a name for your default folder".to_string());
+    }
+    validate_name(default_folder)?;
+
+    let mut folders = vec![default_folder.to_string()];
+    for folder in &choices.starter_folders {
+        let folder = folder.trim();
+        if folder.is_empty() || folders.iter().any(|f| f == folder) {
+            continue;
+        }
+        validate_name(folder)?;
+        folders.push(folder.to_string());
+    }
+
+    let capture = settings::canonical_shortcut(&choices.capture_shortcut)
+        .ok_or("Hold ⌘, ⌃, ⇧, or ⌥ with a key for the capture shortcut")?;
+    if let Some(action) = settings::system_shortcut_conflict(settings, &capture) {
+        return Err(format!(
+            "{} is already used by the {} shortcut",
+            choices.capture_shortcut.trim(),
+            action
+        ));
+    }
+
+    // Keep the seeded shortcuts of folders the user kept, unless one is the
+    // capture shortcut they just picked.
+    let mut mappings = vec![ShortcutMapping {
+        shortcut: choices.capture_shortcut.trim().to_string(),
+        folder: default_folder.to_string(),
+        enabled: true,
+    }];
+    mappings.extend(settings.shortcut_mappings.drain(..).filter(|mapping| {
+        mapping.folder != default_folder
+            && folders.contains(&mapping.folder)
+            && settings::canonical_shortcut(&mapping.shortcut).as_deref() != Some(capture.as_str())
+    }));
+    settings.shortcut_mappings = mappings;
+
+    settings.default_folder = default_folder.to_string();
+    settings.analytics_enabled = choices.analytics_enabled;
+    // The choice was just made on the welcome screen.
+    settings.analytics_notice_dismissed = true;
+    settings.onboarding = OnboardingSettings {
+        completed: true,
+        completed_at: Some(completed_at),
+        version: Some(env!("CARGO_PKG_VERSION").to_string()),
+    };
+    Ok(folders)
+}
+
+/// Finish onboarding. The caller reloads shortcuts afterwards to register
+/// the new mappings.
+#[tauri::command]
+pub fn complete_onboarding(
+    app: tauri::AppHandle,
+    choices: OnboardingChoices,
+) -> Result<OnboardingSettings, String> {
+    let mut settings = settings::get_settings()?;
+    let folders = apply_choices(&mut settings, &choices, Local::now().to_rfc3339())?;
+    for folder in folders {
+        folders::create_folder(app.clone(), folder)?;
+    }
+    let onboarding = settings.onboarding.clone();
+    settings::save_settings(settings)?;
+    Ok(onboarding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn choices(shortcut: &str, starters: &[&str]) -> OnboardingChoices {
+        OnboardingChoices {
+            default_folder: "Notes".to_string(),
+            starter_folders: starters.iter().map(|s| s.to_string()).collect(),
+            analytics_enabled: false,
+            capture_shortcut: shortcut.to_string(),
+        }
+    }
+
+    #[test]
+    fn completing_binds_the_capture_shortcut_and_keeps_chosen_seeds() {
+        let mut settings = StikSettings::default();
+        let folders = apply_choices(
+            &mut settings,
+            &choices("Cmd+Shift+1", &["Work", "Ideas", "Notes"]),
+            "2026-03-05T09:00:00+01:00".to_string(),
+        )
+        .expect("valid choices");
+
+        assert_eq!(folders, vec!["Notes", "Work", "Ideas"]);
+        let bound: Vec<(&str, &str)> = settings
+            .shortcut_mappings
+            .iter()
+            .map(|m| (m.shortcut.as_str(), m.folder.as_str()))
+            .collect();
+        // Work's seed used Cmd+Shift+1, now the capture shortcut.
+        assert_eq!(
+            bound,
+            vec![
+                ("Cmd+Shift+1", "Notes"),
+                ("CommandOrControl+Shift+2", "Ideas")
+            ]
+        );
+        assert_eq!(settings.default_folder, "Notes");
+        assert!(!settings.analytics_enabled);
+        assert!(settings.onboarding.completed);
+    }
+
+    #[test]
+    fn capture_shortcut_must_not_clash_with_system_shortcuts() {
+        let mut settings = StikSettings::default();
+        let error = apply_choices(
+            &mut settings,
+            &choices("CommandOrControl+Shift+P", &[]),
+            String::new(),
+        )
+        .unwrap_err();
+        assert!(error.contains("search"));
+        assert!(!settings.onboarding.completed);
+
+        assert!(apply_choices(&mut settings, &choices("P", &[]), String::new()).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+/// Heading outline for the jump-to-heading menu in viewing and capture
+/// windows.
+///
+/// Headings come from the same pulldown-cmark parse as exports, so code
+/// fences and frontmatter never produce entries, and ids match the anchors
+/// `markdown_to_html` writes. Offsets point at the start of the heading
+/// line in the original content, frontmatter included.
+use pulldown_cmark::{Event, Parser, Tag};
+use serde::Serialize;
+
+use super::share::{self, HeadingSlugs};
+use super::{expiry, note_lock, notes};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutlineHeading {
+    /// 1 for `#` through 6 for `######`.
+    pub level: u8,
+    pub text: String,
+    /// Anchor id, numbered when the text repeats (`notes`, `notes-1`).
+    /// `None` for headings without sluggable text.
+    pub id: Option<String>,
+    /// Index of the nearest preceding heading with a lower level.
+    pub parent: Option<usize>,
+    pub byte_offset: usize,
+    /// Offset in Unicode scalar values, for editors that index by char.
+    pub char_offset: usize,
+}
+
+pub fn note_outline(content: &str) -> Vec<OutlineHeading> {
+    let body_start =
+        expiry::split_frontmatter(content).map_or(0, |(_, body)| content.len() - body.len());
+    let body = &content[body_start..];
+
+    let (events, ranges): (Vec<Event>, Vec<_>) =
+        Parser::new_ext(body, share::markdown_options(false))
+            .into_offset_iter()
+            .unzip();
+
+    let mut outline: Vec<OutlineHeading> = Vec::new();
+    let mut slugs = HeadingSlugs::default();
+    let mut chars_counted = (0, 0);
+    for (i, event) in events.iter().enumerate() {
+        let Event::Start(Tag::Heading { level, id, .. }) = event else {
+            continue;
+        };
+        let level = *level as u8;
+        let text = share::heading_text(&events[i + 1..]).trim().to_string();
+        let id = match id {
+            Some(explicit) => Some(explicit.to_string()),
+            None => slugs.next(share::slugify_heading(&text)),
+        };
+        let parent = outline.iter().rposition(|heading| heading.level < level);
+
+        // Headings arrive in source order, so count chars incrementally.
+        let byte_offset = body_start + ranges[i].start;
+        let (counted_bytes, counted_chars) = chars_counted;
+        let char_offset = counted_chars + content[counted_bytes..byte_offset].chars().count();
+        chars_counted = (byte_offset, char_offset);
+
+        outline.push(OutlineHeading {
+            level,
+            text,
+            id,
+            parent,
+            byte_offset,
+            char_offset,
+        });
+    }
+    outline
+}
+
+/// Outline of `content` when given (the capture window's live buffer),
+/// otherwise of the note at `path`. Locked notes have no outline.
+#[tauri::command]
+pub fn get_note_outline(
+    path: Option<String>,
+    content: Option<String>,
+) -> Result<Vec<OutlineHeading>, String> {
+    let content = match (content, path) {
+        (Some(content), _) => content,
+        (None, Some(path)) => notes::get_note_content_inner(&path)?,
+        (None, None) => return Err("Pass a note path or its content".to_string()),
+    };
+    if note_lock::is_locked_content(&content) {
+        return Ok(Vec::new());
+    }
+    Ok(note_outline(&content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(content: &str) -> Vec<(u8, String, Option<String>, Option<usize>)> {
+        note_outline(content)
+            .into_iter()
+            .map(|h| (h.level, h.text, h.id, h.parent))
+            .collect()
+    }
+
+    #[test]
+    fn outline_follows_heading_levels() {
+        let content = "# Plan\nintro\n## Goals\n### Q3\n## Risks\n# Later\n";
+        assert_eq!(
+            summary(content),
+            vec![
+                (1, "Plan".into(), Some("plan".into()), None),
+                (2, "Goals".into(), Some("goals".into()), Some(0)),
+                (3, "Q3".into(), Some("q3".into()), Some(1)),
+                (2, "Risks".into(), Some("risks".into()), Some(0)),
+                (1, "Later".into(), Some("later".into()), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_headings_get_distinct_entries() {
+        let content = "## Notes\na\n## Notes\nb\n## Notes {#custom}\n## Notes\n";
+        let outline = note_outline(content);
+        let ids: Vec<_> = outline.iter().map(|h| h.id.clone().unwrap()).collect();
+        assert_eq!(ids, vec!["notes", "notes-1", "custom", "notes-2"]);
+        let offsets: Vec<_> = outline.iter().map(|h| h.byte_offset).collect();
+        assert_eq!(offsets, vec![0, 11, 22, 41]);
+    }
+
+    #[test]
+    fn headings_in_code_fences_and_frontmatter_are_skipped() {
+        let content = "---\ntitle: x\n---\n# Real\n```sh\n# comment\n```\n~~~\n## also code\n~~~\n";
+        assert_eq!(
+            summary(content),
+            vec![(1, "Real".into(), Some("real".into()), None)]
+        );
+        assert_eq!(note_outline(content)[0].byte_offset, 17);
+    }
+
+    #[test]
+    fn offsets_point_at_the_heading_in_bytes_and_chars() {
+        let content = "日本語のメモ\n\n# 見出し\n\nbody **bold**\n\n## Next `code`\n";
+        let outline = note_outline(content);
+        assert_eq!(outline[0].byte_offset, content.find("# 見出し").unwrap());
+        assert_eq!(outline[0].char_offset, 8);
+        assert_eq!(outline[1].text, "Next code");
+        assert_eq!(
+            outline[1].char_offset,
+            content[..outline[1].byte_offset].chars().count()
+        );
+    }
+
+    #[test]
+    fn command_prefers_live_content_and_skips_locked_notes() {
+        let outline = get_note_outline(None, Some("# Draft".into())).unwrap();
+        assert_eq!(outline[0].text, "Draft");
+        assert!(get_note_outline(None, None).is_err());
+
+        let locked = "---stik-locked---\n# Not a heading\n";
+        assert!(get_note_outline(None, Some(locked.into()))
+            .unwrap()
+            .is_empty());
+    }
+}
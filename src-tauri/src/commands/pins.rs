@@ -0,0 +1,133 @@
+/// Pinned notes, which sort above the rest of their folder in the manager
+/// and in search results.
+///
+/// The flag is a `pinned: true` frontmatter key, so it travels with the
+/// note through git sync, iCloud and moves between folders.
+use std::path::Path;
+use tauri::{AppHandle, State};
+
+use super::embeddings::EmbeddingIndex;
+use super::expiry::split_frontmatter;
+use super::index::NoteIndex;
+use super::note_lock;
+use super::notes::{self, NoteInfo, NoteSaved};
+
+pub const PINNED_KEY: &str = "pinned";
+
+fn is_pinned_line(line: &str) -> bool {
+    line.split_once(':')
+        .is_some_and(|(key, _)| key.trim() == PINNED_KEY)
+}
+
+/// Whether the note's frontmatter has `pinned: true`.
+pub fn is_pinned(content: &str) -> bool {
+    split_frontmatter(content).is_some_and(|(fields, _)| {
+        fields.iter().any(|line| {
+            is_pinned_line(line)
+                && line.split_once(':').is_some_and(|(_, value)| {
+                    value.trim().trim_matches(|c| c == '"' || c == '\'') == "true"
+                })
+        })
+    })
+}
+
+/// `content` with `pinned: true` set, or with the key removed. Frontmatter
+/// is added when needed and dropped once it would be empty.
+fn with_pinned(content: &str, pinned: bool) -> String {
+    let (mut fields, body) = split_frontmatter(content).unwrap_or((Vec::new(), content));
+    fields.retain(|line| !is_pinned_line(line));
+    let pinned_line = format!("{}: true", PINNED_KEY);
+    if pinned {
+        fields.push(&pinned_line);
+    }
+    if fields.is_empty() {
+        return body.to_string();
+    }
+    format!("---\n{}\n---\n{}", fields.join("\n"), body)
+}
+
+fn set_pinned(
+    app: AppHandle,
+    path: String,
+    pinned: bool,
+    index: State<'_, NoteIndex>,
+    emb_index: State<'_, EmbeddingIndex>,
+) -> Result<NoteSaved, String> {
+    let content = notes::get_note_content_inner(&path)?;
+    if note_lock::is_locked_content(&content) {
+        return Err("Unlock the note to pin or unpin it".to_string());
+    }
+    if is_pinned(&content) == pinned {
+        return Ok(NoteSaved {
+            folder: notes::folder_of(Path::new(&path)),
+            filename: Path::new(&path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path,
+        });
+    }
+    notes::update_note(app, path, with_pinned(&content, pinned), index, emb_index)
+}
+
+#[tauri::command]
+pub fn pin_note(
+    app: AppHandle,
+    path: String,
+    index: State<'_, NoteIndex>,
+    emb_index: State<'_, EmbeddingIndex>,
+) -> Result<NoteSaved, String> {
+    set_pinned(app, path, true, index, emb_index)
+}
+
+#[tauri::command]
+pub fn unpin_note(
+    app: AppHandle,
+    path: String,
+    index: State<'_, NoteIndex>,
+    emb_index: State<'_, EmbeddingIndex>,
+) -> Result<NoteSaved, String> {
+    set_pinned(app, path, false, index, emb_index)
+}
+
+/// Every pinned note, newest first, for the palette's empty state.
+#[tauri::command]
+pub fn list_pinned_notes(index: State<'_, NoteIndex>) -> Result<Vec<NoteInfo>, String> {
+    let pinned = index
+        .list(None)?
+        .into_iter()
+        .filter(|entry| entry.pinned)
+        .collect();
+    Ok(notes::note_infos(pinned))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinned_needs_a_true_frontmatter_key() {
+        assert!(is_pinned("---\npinned: true\n---\nScratchpad"));
+        assert!(is_pinned("---\ntags: [a]\npinned: \"true\"\n---\n"));
+        assert!(!is_pinned("---\npinned: false\n---\nScratchpad"));
+        assert!(!is_pinned("pinned: true\n"));
+    }
+
+    #[test]
+    fn pinning_adds_and_removes_the_key() {
+        let pinned = with_pinned("# Reading list\n", true);
+        assert_eq!(pinned, "---\npinned: true\n---\n# Reading list\n");
+        assert_eq!(with_pinned(&pinned, true), pinned);
+        assert_eq!(with_pinned(&pinned, false), "# Reading list\n");
+    }
+
+    #[test]
+    fn other_frontmatter_is_kept() {
+        let content = "---\nstik-id: abc\npinned: false\n---\nBody";
+        assert_eq!(
+            with_pinned(content, true),
+            "---\nstik-id: abc\npinned: true\n---\nBody"
+        );
+        assert_eq!(with_pinned(content, false), "---\nstik-id: abc\n---\nBody");
+    }
+}
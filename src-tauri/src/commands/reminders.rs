@@ -0,0 +1,211 @@
+/// Send a note's open tasks to Apple Reminders.
+///
+/// Each unchecked `- [ ]` line becomes one reminder in the chosen list
+/// (created when missing, the default list otherwise). The note itself is
+/// left untouched; `~/.stik/reminders_export.json` remembers which tasks of
+/// which note were already sent, so running the export again only picks up
+/// tasks added since.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::index::extract_title;
+use super::macos_notify::escape_applescript;
+use super::notes::get_note_content_inner;
+use super::tasks::{self, TaskItem};
+use super::window_geometry::path_key;
+use super::{config_paths, note_lock, versioning};
+
+pub const AUTOMATION_DENIED_MESSAGE: &str = "Stik isn't allowed to control Reminders. Allow it in System Settings → Privacy & Security → Automation → Stik → Reminders, then try again.";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RemindersExportSummary {
+    pub created: usize,
+    /// Open tasks already sent by an earlier export.
+    pub skipped: usize,
+    pub list: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct RemindersLedger {
+    /// Note path → task text hash → created.
+    notes: HashMap<String, HashMap<String, bool>>,
+}
+
+impl RemindersLedger {
+    fn is_created(&self, path: &str, task: &TaskItem) -> bool {
+        self.notes
+            .get(path)
+            .and_then(|tasks| tasks.get(&task_key(task)))
+            .copied()
+            .unwrap_or(false)
+    }
+
+    fn mark_created(&mut self, path: &str, task: &TaskItem) {
+        self.notes
+            .entry(path.to_string())
+            .or_default()
+            .insert(task_key(task), true);
+    }
+}
+
+fn task_key(task: &TaskItem) -> String {
+    path_key(&task.text)
+}
+
+#[tauri::command]
+pub async fn export_tasks_to_reminders(
+    path: String,
+    list_name: Option<String>,
+) -> Result<RemindersExportSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || export_tasks(&path, list_name.as_deref()))
+        .await
+        .map_err(|e| format!("Reminders export failed: {}", e))?
+}
+
+fn export_tasks(path: &str, list_name: Option<&str>) -> Result<RemindersExportSummary, String> {
+    let content = get_note_content_inner(path)?;
+    if note_lock::is_locked_content(&content) {
+        return Err("Unlock the note before exporting its tasks".to_string());
+    }
+    let list_name = list_name.map(str::trim).filter(|name| !name.is_empty());
+
+    let mut ledger = load_ledger()?;
+    let (done, pending): (Vec<TaskItem>, Vec<TaskItem>) = tasks::open_tasks(&content)
+        .into_iter()
+        .partition(|task| ledger.is_created(path, task));
+
+    if pending.is_empty() {
+        return Ok(RemindersExportSummary {
+            created: 0,
+            skipped: done.len(),
+            list: list_name.unwrap_or_default().to_string(),
+        });
+    }
+
+    let script = build_script(list_name, &extract_title(&content), &pending);
+    let list = run_script(&script)?;
+
+    for task in &pending {
+        ledger.mark_created(path, task);
+    }
+    save_ledger(&ledger)?;
+
+    Ok(RemindersExportSummary {
+        created: pending.len(),
+        skipped: done.len(),
+        list,
+    })
+}
+
+/// One AppleScript run for the whole batch, so the automation prompt (and
+/// Reminders' launch) happens once. The script returns the list's name.
+fn build_script(list_name: Option<&str>, note_title: &str, tasks: &[TaskItem]) -> String {
+    let mut script = String::from("tell application \"Reminders\"\n");
+    match list_name {
+        Some(name) => {
+            let name = escape_applescript(name);
+            script.push_str(&format!(
+                "  if not (exists list \"{0}\") then make new list with properties {{name:\"{0}\"}}\n  set targetList to list \"{0}\"\n",
+                name
+            ));
+        }
+        None => script.push_str("  set targetList to default list\n"),
+    }
+
+    let body = escape_applescript(&format!("From Stik: {}", note_title));
+    for task in tasks {
+        script.push_str(&format!(
+            "  make new reminder at end of targetList with properties {{name:\"{}\", body:\"{}\"}}\n",
+            escape_applescript(&task.text),
+            body
+        ));
+    }
+    script.push_str("  return name of targetList\nend tell\n");
+    script
+}
+
+#[cfg(target_os = "macos")]
+fn run_script(script: &str) -> Result<String, String> {
+    let output = std::process::Command::new("osascript")
+        .arg("-e")
+        .arg(script)
+        .output()
+        .map_err(|e| format!("Failed to run osascript: {}", e))?;
+
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string());
+    }
+    Err(describe_failure(&String::from_utf8_lossy(&output.stderr)))
+}
+
+#[cfg(not(target_os = "macos"))]
+fn run_script(_script: &str) -> Result<String, String> {
+    Err("Reminders export is only available on macOS".to_string())
+}
+
+/// -1743 is "not authorized to send Apple events" (Automation denied);
+/// -1744 is the prompt being dismissed.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn describe_failure(stderr: &str) -> String {
+    if stderr.contains("-1743") || stderr.contains("-1744") || stderr.contains("Not authorized") {
+        return AUTOMATION_DENIED_MESSAGE.to_string();
+    }
+    format!("Reminders export failed: {}", stderr.trim())
+}
+
+fn load_ledger() -> Result<RemindersLedger, String> {
+    let path = config_paths::config_file("reminders_export.json")?;
+    match versioning::load_versioned::<RemindersLedger>(&path)? {
+        Some(ledger) => Ok(ledger),
+        None => Ok(RemindersLedger::default()),
+    }
+}
+
+fn save_ledger(ledger: &RemindersLedger) -> Result<(), String> {
+    let path = config_paths::writable_config_file("reminders_export.json")?;
+    versioning::save_versioned(&path, ledger)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(text: &str) -> TaskItem {
+        TaskItem {
+            line: 0,
+            text: text.to_string(),
+            done: false,
+        }
+    }
+
+    #[test]
+    fn ledger_tracks_tasks_per_note() {
+        let mut ledger = RemindersLedger::default();
+        ledger.mark_created("/n/a.md", &task("call Sam"));
+        assert!(ledger.is_created("/n/a.md", &task("call Sam")));
+        assert!(!ledger.is_created("/n/a.md", &task("call Alex")));
+        assert!(!ledger.is_created("/n/b.md", &task("call Sam")));
+    }
+
+    #[test]
+    fn script_creates_missing_list_and_escapes_text() {
+        let script = build_script(Some("Work \"Q3\""), "Plan", &[task("ask \"why\"")]);
+        assert!(script.contains("if not (exists list \"Work \\\"Q3\\\"\")"));
+        assert!(script.contains("name:\"ask \\\"why\\\"\", body:\"From Stik: Plan\""));
+
+        let script = build_script(None, "Plan", &[task("a"), task("b")]);
+        assert!(script.contains("set targetList to default list"));
+        assert_eq!(script.matches("make new reminder").count(), 2);
+    }
+
+    #[test]
+    fn automation_denial_points_to_system_settings() {
+        assert_eq!(
+            describe_failure(
+                "execution error: Not authorized to send Apple events to Reminders. (-1743)"
+            ),
+            AUTOMATION_DENIED_MESSAGE
+        );
+        assert!(describe_failure("syntax error").starts_with("Reminders export failed"));
+    }
+}
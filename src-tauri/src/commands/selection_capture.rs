@@ -0,0 +1,186 @@
+/// Capture the selection in the frontmost app without showing the postit.
+///
+/// Unlike `clip_capture`, which reads `kAXSelectedText`, this synthesizes
+/// ⌘C so it also works in apps that don't expose their selection through
+/// Accessibility (Electron apps, terminals). The user's clipboard text is
+/// restored afterwards. If the pasteboard doesn't change after ⌘C there was
+/// nothing selected, and nothing is saved.
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use super::frontmost_app;
+use super::macos_notify;
+use super::notes::{self, NoteSaved};
+
+/// How long the frontmost app gets to answer ⌘C.
+const COPY_TIMEOUT: Duration = Duration::from_millis(500);
+const COPY_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Checks whether the Stik process currently has Accessibility
+/// permission, *without* prompting the user. If it returns false,
+/// CGEventPost silently drops any keystrokes we send.
+#[cfg(target_os = "macos")]
+pub fn is_accessibility_granted() -> bool {
+    // Passing nullptr performs a silent check without popping the
+    // system prompt; callers open the Settings pane themselves.
+    use std::ffi::c_void;
+    #[link(name = "ApplicationServices", kind = "framework")]
+    unsafe extern "C" {
+        fn AXIsProcessTrustedWithOptions(options: *const c_void) -> bool;
+    }
+    unsafe { AXIsProcessTrustedWithOptions(std::ptr::null()) }
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn is_accessibility_granted() -> bool {
+    false
+}
+
+#[cfg(target_os = "macos")]
+fn pasteboard_change_count() -> isize {
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+
+    unsafe {
+        let pasteboard: Retained<AnyObject> = msg_send![class!(NSPasteboard), generalPasteboard];
+        msg_send![&*pasteboard, changeCount]
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn pasteboard_change_count() -> isize {
+    0
+}
+
+#[cfg(target_os = "macos")]
+fn post_copy_keystroke() -> Result<(), String> {
+    use core_graphics::event::{CGEvent, CGEventFlags, CGEventTapLocation};
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+
+    const KEY_C: u16 = 8; // kVK_ANSI_C
+
+    let source = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+        .map_err(|_| "Failed to create keyboard event source".to_string())?;
+    for key_down in [true, false] {
+        let event = CGEvent::new_keyboard_event(source.clone(), KEY_C, key_down)
+            .map_err(|_| "Failed to create ⌘C event".to_string())?;
+        // Explicit flags, so modifiers still held from the shortcut
+        // (e.g. Ctrl) don't turn this into a different keystroke.
+        event.set_flags(CGEventFlags::CGEventFlagCommand);
+        event.post(CGEventTapLocation::HID);
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn post_copy_keystroke() -> Result<(), String> {
+    Err("Capturing the selection is only supported on macOS".to_string())
+}
+
+/// Copy the frontmost app's selection, then put the previous clipboard
+/// text back. `None` when nothing was selected.
+fn copy_selection() -> Result<Option<String>, String> {
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| format!("Clipboard unavailable: {e}"))?;
+    let previous = clipboard.get_text().ok();
+    let before = pasteboard_change_count();
+
+    post_copy_keystroke()?;
+
+    let started = Instant::now();
+    while pasteboard_change_count() == before {
+        if started.elapsed() >= COPY_TIMEOUT {
+            return Ok(None);
+        }
+        std::thread::sleep(COPY_POLL_INTERVAL);
+    }
+
+    let copied = clipboard.get_text().ok();
+    let restored = match previous {
+        Some(text) => clipboard.set_text(text),
+        None => clipboard.clear(),
+    };
+    if let Err(e) = restored {
+        log::error!("Failed to restore clipboard after capture: {}", e);
+    }
+    Ok(copied.filter(|text| !text.trim().is_empty()))
+}
+
+fn with_source_prefix(app_name: Option<&str>, text: &str) -> String {
+    match app_name.map(str::trim).filter(|name| !name.is_empty()) {
+        Some(name) => format!("From {}\n\n{}", name, text.trim()),
+        None => text.trim().to_string(),
+    }
+}
+
+fn default_folder() -> String {
+    super::settings::load_settings_from_file()
+        .map(|s| s.default_folder)
+        .unwrap_or_else(|_| "Inbox".to_string())
+}
+
+/// Save the frontmost selection to `folder` (the default folder when
+/// empty) and confirm with a notification. `None` when nothing was
+/// selected.
+pub fn capture_selection(app: &AppHandle, folder: &str) -> Result<Option<NoteSaved>, String> {
+    if !is_accessibility_granted() {
+        return Err(
+            "Stik needs Accessibility permission to copy the selection. Enable it in System Settings → Privacy & Security → Accessibility."
+                .to_string(),
+        );
+    }
+
+    let source = frontmost_app::frontmost_pid()
+        .filter(|pid| !frontmost_app::is_stik(*pid))
+        .and_then(|_| frontmost_app::frontmost_name());
+    let Some(text) = copy_selection()? else {
+        return Ok(None);
+    };
+
+    let folder = if folder.trim().is_empty() {
+        default_folder()
+    } else {
+        folder.to_string()
+    };
+    let content = with_source_prefix(source.as_deref(), &text);
+    let saved = notes::save_note_inner(folder.clone(), content.clone())?;
+    notes::post_save_processing(app, &saved, &content);
+    let _ = app.emit("files-changed", vec![saved.path.clone()]);
+
+    let preview: String = text
+        .trim()
+        .lines()
+        .next()
+        .unwrap_or("")
+        .chars()
+        .take(60)
+        .collect();
+    let _ = macos_notify::show("Stik", &format!("Saved to {}", folder), &preview);
+    Ok(Some(saved))
+}
+
+#[tauri::command]
+pub async fn capture_frontmost_selection(
+    app: AppHandle,
+    folder: String,
+) -> Result<Option<NoteSaved>, String> {
+    tauri::async_runtime::spawn_blocking(move || capture_selection(&app, &folder))
+        .await
+        .map_err(|e| format!("Capture failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_prefix_names_the_app_on_its_own_line() {
+        assert_eq!(
+            with_source_prefix(Some("Safari"), "  quoted text\n"),
+            "From Safari\n\nquoted text"
+        );
+        assert_eq!(with_source_prefix(Some(" "), "text"), "text");
+        assert_eq!(with_source_prefix(None, "text"), "text");
+    }
+}
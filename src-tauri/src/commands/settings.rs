@@ -1,8 +1,10 @@
-use super::{git_share, versioning};
+use super::config_paths::{self, ConfigLocation};
+use super::{filename_template, git_share, versioning};
+use chrono::{Local, NaiveTime};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShortcutMapping {
@@ -108,6 +110,129 @@ impl Default for DictationSettings {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuietHoursSettings {
+    pub enabled: bool,
+    /// Local time, "HH:MM". The window may wrap past midnight.
+    pub start: String,
+    pub end: String,
+}
+
+impl Default for QuietHoursSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: "22:00".to_string(),
+            end: "08:00".to_string(),
+        }
+    }
+}
+
+impl QuietHoursSettings {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let (Some(start), Some(end)) =
+            (parse_time_of_day(&self.start), parse_time_of_day(&self.end))
+        else {
+            return false;
+        };
+        if start <= end {
+            time >= start && time < end
+        } else {
+            time >= start || time < end
+        }
+    }
+
+    pub fn is_active_now(&self) -> bool {
+        self.contains(Local::now().time())
+    }
+}
+
+/// Parse an "HH:MM" time-of-day setting.
+pub fn parse_time_of_day(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyDigestSettings {
+    pub enabled: bool,
+    /// Local time, "HH:MM", after which yesterday's digest is generated.
+    pub time_of_day: String,
+    pub folder: String,
+}
+
+impl Default for DailyDigestSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            time_of_day: "08:00".to_string(),
+            folder: "Digests".to_string(),
+        }
+    }
+}
+
+/// Scheduled zip backups of the notes directory; see `backup.rs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupSettings {
+    pub enabled: bool,
+    /// Absolute folder the archives are written to, outside the notes.
+    pub destination: String,
+    pub frequency_days: u32,
+    /// Archives kept; older ones are deleted. 0 keeps them all.
+    pub retention_count: u32,
+    /// Top-level folders left out of archives.
+    pub excluded_folders: Vec<String>,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            destination: String::new(),
+            frequency_days: 1,
+            retention_count: 10,
+            excluded_folders: vec![],
+        }
+    }
+}
+
+/// First-launch welcome flow. Settings files written before onboarding
+/// existed have no section and count as completed; fresh installs start
+/// from `OnboardingSettings::pending()`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OnboardingSettings {
+    pub completed: bool,
+    /// RFC 3339 timestamp.
+    pub completed_at: Option<String>,
+    /// App version that ran onboarding.
+    pub version: Option<String>,
+}
+
+impl Default for OnboardingSettings {
+    fn default() -> Self {
+        Self {
+            completed: true,
+            completed_at: None,
+            version: None,
+        }
+    }
+}
+
+impl OnboardingSettings {
+    pub fn pending() -> Self {
+        Self {
+            completed: false,
+            ..Self::default()
+        }
+    }
+}
+
 fn default_true() -> bool {
     true
 }
@@ -120,6 +245,56 @@ fn default_font_size() -> u32 {
     14
 }
 
+fn default_auto_file_threshold() -> f64 {
+    0.6
+}
+
+fn default_integration_server_port() -> u16 {
+    27811
+}
+
+/// Bytes (in KiB) of each note read for its title, preview and word count.
+pub const DEFAULT_INDEX_READ_LIMIT_KB: usize = 1024;
+
+fn default_index_read_limit_kb() -> usize {
+    DEFAULT_INDEX_READ_LIMIT_KB
+}
+
+/// Viewing note contents kept in memory before closed ones are evicted.
+pub const DEFAULT_VIEWING_NOTE_CACHE_LIMIT: usize = 32;
+
+fn default_viewing_note_cache_limit() -> usize {
+    DEFAULT_VIEWING_NOTE_CACHE_LIMIT
+}
+
+/// Sticked and viewing window size limits, logical pixels.
+pub const DEFAULT_STICKED_MIN_SIZE: (f64, f64) = (320.0, 200.0);
+pub const DEFAULT_STICKED_MAX_SIZE: (f64, f64) = (800.0, 600.0);
+/// Smallest minimum that still fits the note toolbar.
+const STICKED_SIZE_FLOOR: (f64, f64) = (240.0, 160.0);
+/// Largest size either limit may be, an 8K display.
+const STICKED_SIZE_CEILING: (f64, f64) = (7680.0, 4320.0);
+
+fn default_sticked_min_size() -> (f64, f64) {
+    DEFAULT_STICKED_MIN_SIZE
+}
+
+fn default_sticked_max_size() -> Option<(f64, f64)> {
+    Some(DEFAULT_STICKED_MAX_SIZE)
+}
+
+fn default_on_this_day_window_days() -> u32 {
+    3
+}
+
+fn default_capture_blur_behavior() -> String {
+    CAPTURE_BLUR_DISCARD.to_string()
+}
+
+fn default_filename_template() -> String {
+    filename_template::DEFAULT_FILENAME_TEMPLATE.to_string()
+}
+
 fn default_text_direction() -> String {
     "auto".to_string()
 }
@@ -135,8 +310,6 @@ pub struct StikSettings {
     #[serde(default)]
     pub vim_mode_enabled: bool,
     #[serde(default)]
-    pub theme_mode: String,
-    #[serde(default)]
     pub notes_directory: String,
     #[serde(default)]
     pub hide_dock_icon: bool,
@@ -151,8 +324,6 @@ pub struct StikSettings {
     #[serde(default = "default_font_size")]
     pub font_size: u32,
     #[serde(default)]
-    pub viewing_window_size: Option<(f64, f64)>,
-    #[serde(default)]
     pub viewing_window_position: Option<(f64, f64)>,
     #[serde(default)]
     pub custom_templates: Vec<CustomTemplate>,
@@ -164,8 +335,10 @@ pub struct StikSettings {
     pub text_direction: String,
     #[serde(default)]
     pub hide_tray_icon: bool,
+    /// Last size of each restorable window, keyed by `CAPTURE_WINDOW` or
+    /// `VIEWING_WINDOW`, as logical `(width, height)`.
     #[serde(default)]
-    pub capture_window_size: Option<(f64, f64)>,
+    pub window_sizes: HashMap<String, (f64, f64)>,
     #[serde(default)]
     pub active_theme: String,
     #[serde(default)]
@@ -184,6 +357,180 @@ pub struct StikSettings {
     pub use_directory_as_root: bool,
     #[serde(default)]
     pub dictation: DictationSettings,
+    #[serde(default)]
+    pub quiet_hours: QuietHoursSettings,
+    /// Hold back notifications and self-opening windows while a macOS
+    /// Focus mode is on, as during quiet hours.
+    #[serde(default)]
+    pub respect_focus_modes: bool,
+    #[serde(default)]
+    pub daily_digest: DailyDigestSettings,
+    #[serde(default)]
+    pub backup: BackupSettings,
+    /// Folders never fed to the on-device model as RAG or digest context.
+    #[serde(default)]
+    pub ai_excluded_folders: Vec<String>,
+    /// Move notes saved to Inbox into the suggested folder when the
+    /// embedding match clears `auto_file_threshold`.
+    #[serde(default)]
+    pub auto_file_from_inbox: bool,
+    #[serde(default = "default_auto_file_threshold")]
+    pub auto_file_threshold: f64,
+    /// Curly quotes, dashes and ellipses in rendered HTML.
+    #[serde(default)]
+    pub smart_punctuation: bool,
+    /// Localhost HTTP API for launcher integrations (Raycast, Alfred).
+    #[serde(default)]
+    pub integration_server_enabled: bool,
+    #[serde(default = "default_integration_server_port")]
+    pub integration_server_port: u16,
+    /// Empty disables the note webhook entirely.
+    #[serde(default)]
+    pub webhook_url: String,
+    #[serde(default)]
+    pub webhook_include_content: bool,
+    /// HMAC-SHA256 key for the `X-Stik-Signature` header; unsigned if empty.
+    #[serde(default)]
+    pub webhook_secret: String,
+    /// Keep Stik's state in `.stik-config/` inside the notes folder.
+    #[serde(default)]
+    pub portable_mode: bool,
+    #[serde(default = "default_true")]
+    pub on_this_day_enabled: bool,
+    /// Local "HH:MM" for the On This Day notification; None shows it at launch.
+    #[serde(default)]
+    pub on_this_day_time: Option<String>,
+    /// Days either side of today searched when no note matches exactly.
+    #[serde(default = "default_on_this_day_window_days")]
+    pub on_this_day_window_days: u32,
+    /// Donate notes to Core Spotlight so they are searchable by content.
+    #[serde(default)]
+    pub spotlight_indexing: bool,
+    /// Keep `~/.stik/widget/feed.json` up to date for widgets.
+    #[serde(default)]
+    pub widget_feed_enabled: bool,
+    /// Folders whose notes never appear in the widget feed.
+    #[serde(default)]
+    pub widget_excluded_folders: Vec<String>,
+    /// Missed days per month that don't break the capture streak.
+    #[serde(default)]
+    pub streak_grace_days_per_month: u32,
+    /// Run `format_markdown` on every note save.
+    #[serde(default)]
+    pub format_on_save: bool,
+    /// How much of each note the index reads, in KiB. Larger notes are
+    /// flagged `truncated_in_index`; full-text search still covers them.
+    #[serde(default = "default_index_read_limit_kb")]
+    pub index_read_limit_kb: usize,
+    #[serde(default)]
+    pub onboarding: OnboardingSettings,
+    /// What a blur does with a non-empty capture window: `discard` leaves
+    /// it open untouched, `save` saves and hides it, `keep_draft` stashes
+    /// the text for the next capture and hides it.
+    #[serde(default = "default_capture_blur_behavior")]
+    pub capture_blur_behavior: String,
+    /// Footer new captures with the app (and browser page) they were taken
+    /// over and when.
+    #[serde(default)]
+    pub capture_context: bool,
+    /// How new notes are named; see `filename_template`.
+    #[serde(default = "default_filename_template")]
+    pub filename_template: String,
+    /// Cap on cached viewing note contents. Entries for open windows are
+    /// never evicted, so the cache can exceed this while many are open.
+    #[serde(default = "default_viewing_note_cache_limit")]
+    pub viewing_note_cache_limit: usize,
+    /// Where `open_daily_note` keeps the day's note; empty means the
+    /// default folder.
+    #[serde(default)]
+    pub daily_note_folder: String,
+    /// Where `generate_sync_summary` writes its note; empty means the
+    /// default folder.
+    #[serde(default)]
+    pub sync_summary_folder: String,
+    /// Log verbosity set with `set_log_level`; empty means the build's default.
+    #[serde(default)]
+    pub log_level: String,
+    /// Smallest a sticked or viewing window can be resized to.
+    #[serde(default = "default_sticked_min_size")]
+    pub sticked_min_size: (f64, f64),
+    /// Largest a sticked or viewing window can be resized to; `None` (null)
+    /// leaves it unbounded.
+    #[serde(default = "default_sticked_max_size")]
+    pub sticked_max_size: Option<(f64, f64)>,
+}
+
+pub const CAPTURE_BLUR_DISCARD: &str = "discard";
+pub const CAPTURE_BLUR_SAVE: &str = "save";
+pub const CAPTURE_BLUR_KEEP_DRAFT: &str = "keep_draft";
+
+/// `window_sizes` key for the capture postit.
+pub const CAPTURE_WINDOW: &str = "capture";
+/// `window_sizes` key for note viewing windows without their own geometry.
+pub const VIEWING_WINDOW: &str = "viewing";
+
+impl StikSettings {
+    pub fn window_size(&self, window: &str) -> Option<(f64, f64)> {
+        self.window_sizes.get(window).copied()
+    }
+
+    pub fn set_window_size(&mut self, window: &str, width: f64, height: f64) {
+        self.window_sizes
+            .insert(window.to_string(), (width, height));
+    }
+
+    pub fn sticked_size_limits(&self) -> StickedSizeLimits {
+        StickedSizeLimits {
+            min: self.sticked_min_size,
+            max: self.sticked_max_size,
+        }
+    }
+}
+
+/// Resize limits for sticked and viewing windows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StickedSizeLimits {
+    pub min: (f64, f64),
+    pub max: Option<(f64, f64)>,
+}
+
+impl StickedSizeLimits {
+    /// `size` pulled inside the limits.
+    pub fn clamp(&self, (width, height): (f64, f64)) -> (f64, f64) {
+        let (max_width, max_height) = self.max.unwrap_or((f64::INFINITY, f64::INFINITY));
+        (
+            width.max(self.min.0).min(max_width),
+            height.max(self.min.1).min(max_height),
+        )
+    }
+
+    /// Minimum no larger than the maximum, and both on a real screen.
+    pub fn validate(&self) -> Result<(), String> {
+        let (min_width, min_height) = self.min;
+        if !(STICKED_SIZE_FLOOR.0..=STICKED_SIZE_CEILING.0).contains(&min_width)
+            || !(STICKED_SIZE_FLOOR.1..=STICKED_SIZE_CEILING.1).contains(&min_height)
+        {
+            return Err(format!(
+                "Minimum note size must be between {}×{} and {}×{}",
+                STICKED_SIZE_FLOOR.0,
+                STICKED_SIZE_FLOOR.1,
+                STICKED_SIZE_CEILING.0,
+                STICKED_SIZE_CEILING.1
+            ));
+        }
+        if let Some((max_width, max_height)) = self.max {
+            if max_width < min_width || max_height < min_height {
+                return Err("Maximum note size can't be smaller than the minimum".to_string());
+            }
+            if max_width > STICKED_SIZE_CEILING.0 || max_height > STICKED_SIZE_CEILING.1 {
+                return Err(format!(
+                    "Maximum note size can't exceed {}×{}",
+                    STICKED_SIZE_CEILING.0, STICKED_SIZE_CEILING.1
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for StikSettings {
@@ -215,7 +562,6 @@ impl Default for StikSettings {
             git_sharing: GitSharingSettings::default(),
             ai_features_enabled: true,
             vim_mode_enabled: false,
-            theme_mode: String::new(),
             notes_directory: String::new(),
             hide_dock_icon: false,
             folder_colors: HashMap::new(),
@@ -223,14 +569,13 @@ impl Default for StikSettings {
             analytics_enabled: true,
             analytics_notice_dismissed: false,
             font_size: 14,
-            viewing_window_size: None,
             viewing_window_position: None,
             custom_templates: vec![],
             sidebar_position: String::new(),
             auto_update_enabled: true,
             text_direction: "auto".to_string(),
             hide_tray_icon: false,
-            capture_window_size: None,
+            window_sizes: HashMap::new(),
             active_theme: String::new(),
             custom_themes: vec![],
             font_family: None,
@@ -240,6 +585,39 @@ impl Default for StikSettings {
             note_lock: NoteLockSettings::default(),
             use_directory_as_root: false,
             dictation: DictationSettings::default(),
+            quiet_hours: QuietHoursSettings::default(),
+            respect_focus_modes: false,
+            daily_digest: DailyDigestSettings::default(),
+            backup: BackupSettings::default(),
+            ai_excluded_folders: vec![],
+            auto_file_from_inbox: false,
+            auto_file_threshold: default_auto_file_threshold(),
+            smart_punctuation: false,
+            integration_server_enabled: false,
+            integration_server_port: default_integration_server_port(),
+            webhook_url: String::new(),
+            webhook_include_content: false,
+            webhook_secret: String::new(),
+            portable_mode: false,
+            on_this_day_enabled: true,
+            on_this_day_time: None,
+            on_this_day_window_days: default_on_this_day_window_days(),
+            spotlight_indexing: false,
+            widget_feed_enabled: false,
+            widget_excluded_folders: vec![],
+            streak_grace_days_per_month: 0,
+            format_on_save: false,
+            index_read_limit_kb: DEFAULT_INDEX_READ_LIMIT_KB,
+            onboarding: OnboardingSettings::pending(),
+            capture_blur_behavior: default_capture_blur_behavior(),
+            capture_context: false,
+            filename_template: default_filename_template(),
+            viewing_note_cache_limit: DEFAULT_VIEWING_NOTE_CACHE_LIMIT,
+            daily_note_folder: String::new(),
+            sync_summary_folder: String::new(),
+            log_level: String::new(),
+            sticked_min_size: DEFAULT_STICKED_MIN_SIZE,
+            sticked_max_size: Some(DEFAULT_STICKED_MAX_SIZE),
         }
     }
 }
@@ -249,11 +627,14 @@ pub fn default_system_shortcuts() -> HashMap<String, String> {
         ("search".to_string(), "Cmd+Shift+P".to_string()),
         ("manager".to_string(), "Cmd+Shift+M".to_string()),
         ("settings".to_string(), "Cmd+Shift+Comma".to_string()),
+        ("stats".to_string(), "Cmd+Shift+Y".to_string()),
         ("last_note".to_string(), "Cmd+Shift+L".to_string()),
         ("zen_mode".to_string(), "Cmd+Period".to_string()),
         ("dictation".to_string(), "Cmd+Shift+D".to_string()),
         ("voice_note".to_string(), "Cmd+Shift+V".to_string()),
         ("clip_capture".to_string(), "Cmd+Shift+C".to_string()),
+        ("capture_selection".to_string(), "Cmd+Ctrl+C".to_string()),
+        ("daily_note".to_string(), "Cmd+Shift+J".to_string()),
     ])
 }
 
@@ -262,6 +643,50 @@ pub fn local_only_actions() -> &'static [&'static str] {
     &["zen_mode", "dictation"]
 }
 
+/// A shortcut in one spelling, so "CommandOrControl+Shift+S" and
+/// "Cmd+Shift+KeyS" compare equal. `None` without a modifier or a key.
+pub fn canonical_shortcut(shortcut: &str) -> Option<String> {
+    let parts: Vec<&str> = shortcut.split('+').map(str::trim).collect();
+    let (key, modifiers) = parts.split_last()?;
+    let key = key
+        .strip_prefix("Key")
+        .or_else(|| key.strip_prefix("Digit"))
+        .unwrap_or(key);
+    if key.is_empty() {
+        return None;
+    }
+
+    let mut held = Vec::new();
+    for modifier in modifiers {
+        held.push(match modifier.to_lowercase().as_str() {
+            "commandorcontrol" | "cmd" | "command" | "meta" | "super" => "Cmd",
+            "ctrl" | "control" => "Ctrl",
+            "shift" => "Shift",
+            "alt" | "option" => "Alt",
+            _ => return None,
+        });
+    }
+    let mut canonical: Vec<&str> = ["Cmd", "Ctrl", "Shift", "Alt"]
+        .into_iter()
+        .filter(|modifier| held.contains(modifier))
+        .collect();
+    if canonical.is_empty() {
+        return None;
+    }
+    canonical.push(key);
+    Some(canonical.join("+"))
+}
+
+/// The system action already bound to `shortcut`, if any.
+pub fn system_shortcut_conflict(settings: &StikSettings, shortcut: &str) -> Option<String> {
+    let wanted = canonical_shortcut(shortcut)?;
+    settings
+        .system_shortcuts
+        .iter()
+        .find(|(_, bound)| canonical_shortcut(bound).as_deref() == Some(wanted.as_str()))
+        .map(|(action, _)| action.clone())
+}
+
 fn normalize_system_shortcuts(shortcuts: &mut HashMap<String, String>) {
     let defaults = default_system_shortcuts();
     for (action, default_shortcut) in &defaults {
@@ -283,13 +708,55 @@ const BUILTIN_THEME_IDS: &[&str] = &[
     "tokyo-night",
 ];
 
-fn is_legacy_theme_mode(mode: &str) -> bool {
-    mode == "system" || mode == "light" || mode == "dark"
+/// Built-in palettes, shared with the frontend through `src/themes/builtin.json`.
+const BUILTIN_THEMES_JSON: &str = include_str!("../../../src/themes/builtin.json");
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BuiltinTheme {
+    id: String,
+    is_dark: bool,
+    colors: ThemeColors,
+}
+
+fn builtin_themes() -> &'static [BuiltinTheme] {
+    static THEMES: OnceLock<Vec<BuiltinTheme>> = OnceLock::new();
+    THEMES.get_or_init(|| {
+        serde_json::from_str(BUILTIN_THEMES_JSON).expect("builtin.json holds valid themes")
+    })
+}
+
+/// Palette for a built-in theme as `(colors, is_dark)`.
+fn builtin_theme_colors(id: &str) -> Option<(ThemeColors, bool)> {
+    builtin_themes()
+        .iter()
+        .find(|theme| theme.id == id)
+        .map(|theme| (theme.colors.clone(), theme.is_dark))
+}
+
+/// Colors for `theme_id` (or the active theme when None), as `(colors, is_dark)`.
+/// "system" and unknown ids fall back to the light palette since there is no
+/// window to ask for the appearance.
+pub(crate) fn resolve_theme_colors(
+    settings: &StikSettings,
+    theme_id: Option<&str>,
+) -> (ThemeColors, bool) {
+    let id = theme_id
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .unwrap_or(&settings.active_theme);
+
+    if let Some(custom) = settings.custom_themes.iter().find(|t| t.id == id) {
+        return (custom.colors.clone(), custom.is_dark);
+    }
+    builtin_theme_colors(id)
+        .or_else(|| builtin_theme_colors("light"))
+        .expect("light theme is built in")
 }
 
 fn is_valid_active_theme(active_theme: &str, custom_themes: &[CustomThemeDefinition]) -> bool {
     active_theme.is_empty()
-        || is_legacy_theme_mode(active_theme)
+        || active_theme == "system"
         || BUILTIN_THEME_IDS.contains(&active_theme)
         || custom_themes.iter().any(|theme| theme.id == active_theme)
 }
@@ -303,31 +770,48 @@ fn normalize_loaded_settings(mut settings: StikSettings) -> StikSettings {
 
     normalize_system_shortcuts(&mut settings.system_shortcuts);
 
-    if settings.active_theme.is_empty() && is_legacy_theme_mode(&settings.theme_mode) {
-        settings.active_theme = settings.theme_mode.clone();
+    // A custom theme that was deleted falls back to following the system.
+    if !is_valid_active_theme(&settings.active_theme, &settings.custom_themes) {
+        settings.active_theme = String::new();
     }
 
-    if !is_valid_active_theme(&settings.active_theme, &settings.custom_themes) {
-        settings.active_theme = if is_legacy_theme_mode(&settings.theme_mode) {
-            settings.theme_mode.clone()
-        } else {
-            String::new()
-        };
+    // Hand-edited size limits that can't work fall back to the defaults.
+    if settings.sticked_size_limits().validate().is_err() {
+        settings.sticked_min_size = DEFAULT_STICKED_MIN_SIZE;
+        settings.sticked_max_size = Some(DEFAULT_STICKED_MAX_SIZE);
     }
 
-    settings
-}
+    // A hand-edited template that can't name notes safely is replaced.
+    if filename_template::validate(&settings.filename_template).is_err()
+        || settings.filename_template.trim().is_empty()
+    {
+        settings.filename_template = default_filename_template();
+    }
 
-fn get_settings_path() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    let stik_config = home.join(".stik");
-    fs::create_dir_all(&stik_config).map_err(|e| e.to_string())?;
-    Ok(stik_config.join("settings.json"))
+    settings
 }
 
 pub(crate) fn load_settings_from_file() -> Result<StikSettings, String> {
-    let path = get_settings_path()?;
+    match config_paths::location()? {
+        ConfigLocation::Home(_) => {}
+        // The notes drive is missing: run read-only on the locator's copy.
+        ConfigLocation::Unavailable(_) => {
+            return Ok(normalize_loaded_settings(
+                config_paths::load_home_settings()?
+            ));
+        }
+        ConfigLocation::Portable(_) => {
+            let path = config_paths::config_file(config_paths::SETTINGS_FILE)?;
+            let locator = config_paths::load_home_settings()?;
+            let settings = versioning::load_versioned::<StikSettings>(&path)?
+                .unwrap_or_else(|| locator.clone());
+            return Ok(normalize_loaded_settings(with_storage_location(
+                settings, &locator,
+            )));
+        }
+    }
 
+    let path = config_paths::config_file(config_paths::SETTINGS_FILE)?;
     match versioning::load_versioned::<StikSettings>(&path)? {
         Some(settings) => Ok(normalize_loaded_settings(settings)),
         None => {
@@ -339,8 +823,27 @@ pub(crate) fn load_settings_from_file() -> Result<StikSettings, String> {
 }
 
 fn save_settings_to_file(settings: &StikSettings) -> Result<(), String> {
-    let path = get_settings_path()?;
-    versioning::save_versioned(&path, settings)
+    let path = config_paths::writable_config_file(config_paths::SETTINGS_FILE)?;
+    versioning::save_versioned(&path, settings)?;
+
+    if matches!(config_paths::location()?, ConfigLocation::Portable(_)) {
+        // Keep the locator pointing at the notes folder that holds this copy.
+        let home_path = config_paths::home_config_dir()?.join(config_paths::SETTINGS_FILE);
+        let locator = config_paths::load_home_settings()?;
+        versioning::save_versioned(&home_path, &with_storage_location(locator, settings))?;
+    }
+    config_paths::refresh();
+    git_share::settings_changed();
+    Ok(())
+}
+
+/// `settings` with the fields that decide where notes live taken from `from`.
+fn with_storage_location(mut settings: StikSettings, from: &StikSettings) -> StikSettings {
+    settings.notes_directory = from.notes_directory.clone();
+    settings.use_directory_as_root = from.use_directory_as_root;
+    settings.icloud = from.icloud.clone();
+    settings.portable_mode = from.portable_mode;
+    settings
 }
 
 #[tauri::command]
@@ -349,9 +852,34 @@ pub fn get_settings() -> Result<StikSettings, String> {
 }
 
 #[tauri::command]
-pub fn save_settings(settings: StikSettings) -> Result<bool, String> {
+pub fn save_settings(mut settings: StikSettings) -> Result<bool, String> {
+    filename_template::validate(&settings.filename_template)?;
+    let limits = settings.sticked_size_limits();
+    limits.validate()?;
+    let previous_limits = load_settings_from_file()
+        .map(|previous| previous.sticked_size_limits())
+        .ok();
+    let limits_changed = previous_limits != Some(limits);
+    if limits_changed {
+        if let Some(size) = settings.window_size(VIEWING_WINDOW) {
+            let (width, height) = limits.clamp(size);
+            settings.set_window_size(VIEWING_WINDOW, width, height);
+        }
+    }
     save_settings_to_file(&settings)?;
+    if limits_changed {
+        if let Err(e) = super::sticked_notes::clamp_sizes(&limits) {
+            log::error!("Failed to fit sticked notes to new size limits: {}", e);
+        }
+        super::window_geometry::clamp_sizes(&limits);
+    }
     git_share::notify_force_sync();
+    super::integration_server::apply_settings(&settings);
+    super::analytics::apply_settings(&settings);
+    super::spotlight::apply_settings(&settings);
+    super::widget_feed::apply_settings(&settings);
+    filename_template::apply_settings(&settings);
+    super::app_log::apply_settings(&settings);
     Ok(true)
 }
 
@@ -374,22 +902,28 @@ pub fn apply_dock_icon_visibility(hide: bool) {
 #[tauri::command]
 pub fn save_viewing_window_size(width: f64, height: f64) -> Result<(), String> {
     let mut settings = load_settings_from_file()?;
-    settings.viewing_window_size = Some((width, height));
+    settings.set_window_size(VIEWING_WINDOW, width, height);
     save_settings_to_file(&settings)
 }
 
 #[tauri::command]
 pub fn save_viewing_window_geometry(width: f64, height: f64, x: f64, y: f64) -> Result<(), String> {
     let mut settings = load_settings_from_file()?;
-    settings.viewing_window_size = Some((width, height));
+    settings.set_window_size(VIEWING_WINDOW, width, height);
     settings.viewing_window_position = Some((x, y));
     save_settings_to_file(&settings)
 }
 
+pub(crate) fn save_log_level(level: &str) -> Result<(), String> {
+    let mut settings = load_settings_from_file()?;
+    settings.log_level = level.to_string();
+    save_settings_to_file(&settings)
+}
+
 #[tauri::command]
 pub fn save_capture_window_size(width: f64, height: f64) -> Result<(), String> {
     let mut settings = load_settings_from_file()?;
-    settings.capture_window_size = Some((width, height));
+    settings.set_window_size(CAPTURE_WINDOW, width, height);
     save_settings_to_file(&settings)
 }
 
@@ -539,7 +1073,68 @@ pub fn export_theme_file(
 
 #[cfg(test)]
 mod tests {
-    use super::{normalize_loaded_settings, parse_color_value, ShortcutMapping, StikSettings};
+    use super::{
+        builtin_theme_colors, canonical_shortcut, normalize_loaded_settings, parse_color_value,
+        resolve_theme_colors, system_shortcut_conflict, CustomThemeDefinition, QuietHoursSettings,
+        ShortcutMapping, StickedSizeLimits, StikSettings, ThemeColors, BUILTIN_THEME_IDS,
+        DEFAULT_STICKED_MAX_SIZE,
+    };
+    use chrono::NaiveTime;
+
+    #[test]
+    fn sticked_size_limits_clamp_and_allow_no_maximum() {
+        let limits = StickedSizeLimits {
+            min: (320.0, 200.0),
+            max: Some((800.0, 600.0)),
+        };
+        assert_eq!(limits.clamp((1200.0, 150.0)), (800.0, 200.0));
+        assert_eq!(limits.clamp((500.0, 400.0)), (500.0, 400.0));
+
+        let unbounded = StickedSizeLimits {
+            max: None,
+            ..limits
+        };
+        assert_eq!(unbounded.clamp((2400.0, 1600.0)), (2400.0, 1600.0));
+        assert!(unbounded.validate().is_ok());
+    }
+
+    #[test]
+    fn sticked_size_limits_are_validated() {
+        let limits = StickedSizeLimits {
+            min: (320.0, 200.0),
+            max: Some((1600.0, 1200.0)),
+        };
+        assert!(limits.validate().is_ok());
+        assert!(StickedSizeLimits {
+            max: Some((300.0, 1200.0)),
+            ..limits
+        }
+        .validate()
+        .is_err());
+        assert!(StickedSizeLimits {
+            min: (100.0, 200.0),
+            ..limits
+        }
+        .validate()
+        .is_err());
+        assert!(StickedSizeLimits {
+            max: Some((10_000.0, 1200.0)),
+            ..limits
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn invalid_loaded_size_limits_fall_back_to_defaults() {
+        let settings = normalize_loaded_settings(StikSettings {
+            sticked_min_size: (900.0, 700.0),
+            sticked_max_size: Some((800.0, 600.0)),
+            ..StikSettings::default()
+        });
+        assert_eq!(settings.sticked_min_size, (320.0, 200.0));
+        assert_eq!(settings.sticked_max_size, Some(DEFAULT_STICKED_MAX_SIZE));
+    }
 
     #[test]
     fn normalization_reenables_all_disabled_shortcuts() {
@@ -563,13 +1158,12 @@ mod tests {
     }
 
     #[test]
-    fn normalization_falls_back_to_legacy_theme_mode_when_active_theme_is_invalid() {
+    fn normalization_falls_back_to_system_theme_when_active_theme_is_invalid() {
         let mut settings = StikSettings::default();
-        settings.theme_mode = "dark".to_string();
         settings.active_theme = "removed-custom-theme".to_string();
 
         let normalized = normalize_loaded_settings(settings);
-        assert_eq!(normalized.active_theme, "dark");
+        assert_eq!(normalized.active_theme, "");
     }
 
     #[test]
@@ -578,4 +1172,78 @@ mod tests {
         assert_eq!(parse_color_value("10 20 30"), Some("10 20 30".to_string()));
         assert_eq!(parse_color_value("not-a-color"), None);
     }
+
+    #[test]
+    fn theme_resolution_prefers_requested_then_active_theme() {
+        let mut settings = StikSettings::default();
+        settings.active_theme = "nord".to_string();
+        settings.custom_themes.push(CustomThemeDefinition {
+            id: "mine".to_string(),
+            name: "Mine".to_string(),
+            is_dark: false,
+            colors: ThemeColors {
+                bg: "1 2 3".to_string(),
+                ..ThemeColors::default()
+            },
+        });
+
+        assert_eq!(resolve_theme_colors(&settings, Some("mine")).0.bg, "1 2 3");
+        assert_eq!(resolve_theme_colors(&settings, None).0.bg, "46 52 64");
+        assert!(!resolve_theme_colors(&settings, Some("system")).1);
+    }
+
+    #[test]
+    fn every_builtin_theme_has_a_shared_palette() {
+        for id in BUILTIN_THEME_IDS {
+            assert!(builtin_theme_colors(id).is_some(), "{} missing", id);
+        }
+        assert!(builtin_theme_colors("nord").is_some_and(|(_, is_dark)| is_dark));
+    }
+
+    #[test]
+    fn quiet_hours_wrap_past_midnight() {
+        let quiet = QuietHoursSettings {
+            enabled: true,
+            start: "22:00".to_string(),
+            end: "07:30".to_string(),
+        };
+        let at = |h, m| NaiveTime::from_hms_opt(h, m, 0).expect("valid time");
+        assert!(quiet.contains(at(23, 15)));
+        assert!(quiet.contains(at(3, 0)));
+        assert!(!quiet.contains(at(7, 30)));
+        assert!(!quiet.contains(at(12, 0)));
+    }
+
+    #[test]
+    fn disabled_quiet_hours_never_match() {
+        let quiet = QuietHoursSettings::default();
+        assert!(!quiet.contains(NaiveTime::from_hms_opt(23, 0, 0).expect("valid time")));
+    }
+
+    #[test]
+    fn onboarding_is_pending_only_for_fresh_installs() {
+        assert!(!StikSettings::default().onboarding.completed);
+
+        let existing: StikSettings =
+            serde_json::from_str(r#"{"shortcut_mappings": [], "default_folder": "Inbox"}"#)
+                .expect("settings without onboarding parse");
+        assert!(existing.onboarding.completed);
+    }
+
+    #[test]
+    fn shortcut_spellings_compare_equal() {
+        assert_eq!(
+            canonical_shortcut("CommandOrControl+Shift+S"),
+            canonical_shortcut("Shift+Cmd+KeyS")
+        );
+        assert_eq!(canonical_shortcut("S"), None);
+        assert_eq!(canonical_shortcut("Hyper+S"), None);
+
+        let settings = StikSettings::default();
+        assert_eq!(
+            system_shortcut_conflict(&settings, "Command+Shift+P"),
+            Some("search".to_string())
+        );
+        assert_eq!(system_shortcut_conflict(&settings, "Cmd+Shift+S"), None);
+    }
 }
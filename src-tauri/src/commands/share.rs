@@ -1,40 +1,95 @@
 use base64::Engine;
-use pulldown_cmark::{html, Options, Parser};
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
+use std::collections::HashMap;
 #[cfg(target_os = "macos")]
 use std::ffi::c_void;
+use std::fs;
+use std::path::{Path, PathBuf};
 #[cfg(target_os = "macos")]
 use std::ptr::NonNull;
 
+#[cfg(target_os = "macos")]
+use objc2::rc::Retained;
 #[cfg(target_os = "macos")]
 use objc2::runtime::AnyObject;
 #[cfg(target_os = "macos")]
-use objc2_app_kit::{NSBitmapImageFileType, NSBitmapImageRepPropertyKey, NSView};
+use objc2::{MainThreadMarker, MainThreadOnly};
+#[cfg(target_os = "macos")]
+use objc2_app_kit::{
+    NSBitmapImageFileType, NSBitmapImageRepPropertyKey, NSSharingServicePicker, NSView,
+};
 #[cfg(target_os = "macos")]
-use objc2_foundation::{NSData, NSDictionary, NSUInteger};
+use objc2_foundation::{
+    NSArray, NSData, NSDictionary, NSObject, NSRectEdge, NSString, NSUInteger, NSURL,
+};
+
+use super::expiry::strip_frontmatter;
+use super::folders::get_stik_folder;
+
+/// Images larger than this are left as relative references when inlining.
+const MAX_INLINE_IMAGE_BYTES: u64 = 5 * 1024 * 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClipboardPayload {
     pub plain_text: String,
     pub html: String,
+    /// Markdown syntax stripped, for "Copy as plain text".
+    pub stripped_text: String,
 }
 
+/// When `inline_images` is set, `.assets/` images next to `note_path` are
+/// embedded as `data:` URLs so the HTML survives pasting into Mail or Notes.
 #[tauri::command]
-pub fn build_clipboard_payload(markdown: String) -> Result<ClipboardPayload, String> {
+pub fn build_clipboard_payload(
+    markdown: String,
+    note_path: Option<String>,
+    inline_images: Option<bool>,
+) -> Result<ClipboardPayload, String> {
+    let note_dir = match note_path {
+        Some(path) if inline_images.unwrap_or(false) => Some(resolve_note_dir(&path)?),
+        _ => None,
+    };
+
+    let options = HtmlOptions {
+        note_dir: note_dir.as_deref(),
+        ..HtmlOptions::from_settings()
+    };
+
     Ok(ClipboardPayload {
-        plain_text: markdown.clone(),
-        html: markdown_to_html(&markdown),
+        html: markdown_to_html(&markdown, &options),
+        stripped_text: build_plain_text(&markdown),
+        plain_text: strip_frontmatter(&markdown).to_string(),
     })
 }
 
+/// Falls back to plain text where the platform clipboard has no HTML
+/// flavor (e.g. some Linux clipboard managers), rather than failing.
 #[tauri::command]
 pub fn copy_rich_text_to_clipboard(html: String, plain_text: String) -> Result<(), String> {
     let mut clipboard =
         arboard::Clipboard::new().map_err(|e| format!("Clipboard unavailable: {e}"))?;
-    clipboard
-        .set_html(&html, Some(&plain_text))
-        .map_err(|e| format!("Failed to write rich text to clipboard: {e}"))
+    if let Err(e) = clipboard.set_html(&html, Some(&plain_text)) {
+        log::warn!("Rich text clipboard unavailable, copying plain text: {e}");
+        return clipboard
+            .set_text(plain_text)
+            .map_err(|e| format!("Failed to write text to clipboard: {e}"));
+    }
+    Ok(())
+}
+
+/// How a "copy as image" request was handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageCopyOutcome {
+    /// The native webview snapshot is on the clipboard.
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    Copied,
+    /// No native capture on this platform: the frontend renders a canvas
+    /// snapshot and passes it to `copy_note_image_to_clipboard`.
+    #[cfg_attr(target_os = "macos", allow(dead_code))]
+    NeedsCanvasSnapshot,
 }
 
 #[tauri::command]
@@ -48,7 +103,7 @@ pub fn copy_note_image_to_clipboard(png_base64: String) -> Result<(), String> {
 #[tauri::command]
 pub fn copy_visible_note_image_to_clipboard(
     webview_window: tauri::WebviewWindow,
-) -> Result<(), String> {
+) -> Result<ImageCopyOutcome, String> {
     #[cfg(target_os = "macos")]
     {
         use std::sync::mpsc;
@@ -65,16 +120,307 @@ pub fn copy_visible_note_image_to_clipboard(
         let png_bytes = receiver
             .recv_timeout(Duration::from_secs(2))
             .map_err(|_| "Timed out while capturing note image".to_string())??;
-        copy_png_bytes_to_clipboard(&png_bytes)
+        copy_png_bytes_to_clipboard(&png_bytes)?;
+        Ok(ImageCopyOutcome::Copied)
     }
 
     #[cfg(not(target_os = "macos"))]
     {
         let _ = webview_window;
-        Err("Image snapshot copy is currently supported on macOS only".to_string())
+        Ok(canvas_snapshot_fallback())
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn canvas_snapshot_fallback() -> ImageCopyOutcome {
+    ImageCopyOutcome::NeedsCanvasSnapshot
+}
+
+/// A note snapshot written to disk by `save_note_image_snapshot`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SavedImageSnapshot {
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Write `png_bytes` to `output_path` (`.png` added when it has no
+/// extension), scaled down to `max_width` when wider. Unscaled snapshots
+/// are written as captured, without re-encoding.
+fn write_png_snapshot(
+    png_bytes: &[u8],
+    output_path: &Path,
+    max_width: Option<u32>,
+) -> Result<SavedImageSnapshot, String> {
+    if !output_path.is_absolute() {
+        return Err("Output path must be absolute".to_string());
+    }
+    let path = if output_path.extension().is_some() {
+        output_path.to_path_buf()
+    } else {
+        output_path.with_extension("png")
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create folder: {e}"))?;
+    }
+
+    let image = image::load_from_memory_with_format(png_bytes, image::ImageFormat::Png)
+        .map_err(|e| format!("Invalid PNG image: {e}"))?;
+    let (width, height) = (image.width(), image.height());
+    let (width, height) = match max_width.filter(|max| *max > 0 && width > *max) {
+        Some(max) => {
+            let scaled_height = ((u64::from(height) * u64::from(max)) / u64::from(width)).max(1);
+            let scaled = image.resize_exact(
+                max,
+                scaled_height as u32,
+                image::imageops::FilterType::Lanczos3,
+            );
+            scaled
+                .save_with_format(&path, image::ImageFormat::Png)
+                .map_err(|e| format!("Failed to write image: {e}"))?;
+            (scaled.width(), scaled.height())
+        }
+        None => {
+            fs::write(&path, png_bytes).map_err(|e| format!("Failed to write image: {e}"))?;
+            (width, height)
+        }
+    };
+
+    Ok(SavedImageSnapshot {
+        path: path.to_string_lossy().to_string(),
+        width,
+        height,
+    })
+}
+
+/// "Save as image…": write the rendered note in `window_label` to
+/// `output_path` as a PNG, optionally scaled down to `max_width`. Off
+/// macOS there is no native capture, so the frontend renders a canvas
+/// snapshot and passes it as `png_base64`, as for the clipboard.
+#[tauri::command]
+pub fn save_note_image_snapshot(
+    app: tauri::AppHandle,
+    window_label: String,
+    output_path: String,
+    max_width: Option<u32>,
+    png_base64: Option<String>,
+) -> Result<SavedImageSnapshot, String> {
+    #[cfg(target_os = "macos")]
+    let png_bytes = {
+        use std::sync::mpsc;
+        use std::time::Duration;
+        use tauri::Manager;
+
+        let _ = png_base64;
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| format!("Window not found: {window_label}"))?;
+        let (sender, receiver) = mpsc::channel();
+        window
+            .with_webview(move |webview| {
+                let result = unsafe { capture_webview_png_bytes(webview) };
+                let _ = sender.send(result);
+            })
+            .map_err(|e| format!("Failed to access webview: {e}"))?;
+        receiver
+            .recv_timeout(Duration::from_secs(2))
+            .map_err(|_| "Timed out while capturing note image".to_string())??
+    };
+
+    #[cfg(not(target_os = "macos"))]
+    let png_bytes = {
+        let _ = (app, window_label);
+        let png_base64 =
+            png_base64.ok_or("No native capture here; pass a canvas snapshot as png_base64")?;
+        base64::engine::general_purpose::STANDARD
+            .decode(&png_base64)
+            .map_err(|e| format!("Invalid image payload: {e}"))?
+    };
+
+    write_png_snapshot(&png_bytes, Path::new(&output_path), max_width)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ShareFormat {
+    Markdown,
+    Html,
+    Pdf,
+}
+
+impl ShareFormat {
+    fn parse(format: &str) -> Result<Self, String> {
+        match format.trim().to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            "pdf" => Ok(Self::Pdf),
+            other => Err(format!("Unsupported share format: {other}")),
+        }
+    }
+}
+
+/// Present the macOS share sheet (Messages, AirDrop, Mail, …) for a note,
+/// anchored to `window_label`. Markdown is shared as text; HTML and PDF go
+/// through a temporary file that is removed once the share window closes.
+/// The PDF is rendered from the window's webview as it is displayed.
+#[tauri::command]
+pub fn share_note_via_sheet(
+    app: tauri::AppHandle,
+    window_label: String,
+    path: String,
+    format: String,
+) -> Result<(), String> {
+    let share_format = ShareFormat::parse(&format)?;
+
+    #[cfg(target_os = "macos")]
+    {
+        use std::sync::mpsc;
+        use std::time::Duration;
+        use tauri::Manager;
+
+        let window = app
+            .get_webview_window(&window_label)
+            .ok_or_else(|| format!("Window not found: {window_label}"))?;
+
+        let content = super::notes::get_note_content_inner(&path)?;
+        if super::note_lock::is_locked_content(&content) {
+            return Err("Unlock the note before sharing it".to_string());
+        }
+
+        remove_stale_share_files();
+        let title = super::export::document_title(&content);
+        let item = match share_format {
+            ShareFormat::Markdown => ShareItem::Text(strip_frontmatter(&content).to_string()),
+            ShareFormat::Html => {
+                let html = super::export::render_note_html(&path, None)?;
+                let file = share_temp_file(&title, "html")?;
+                fs::write(&file, html).map_err(|e| format!("Failed to write share file: {e}"))?;
+                ShareItem::File(file)
+            }
+            ShareFormat::Pdf => ShareItem::WebviewPdf(share_temp_file(&title, "pdf")?),
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        window
+            .with_webview(move |webview| {
+                let result = unsafe { present_share_picker(webview, &item) };
+                let _ = sender.send(result);
+            })
+            .map_err(|e| format!("Failed to access webview: {e}"))?;
+
+        receiver
+            .recv_timeout(Duration::from_secs(2))
+            .map_err(|_| "Timed out while opening the share sheet".to_string())?
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = (app, window_label, path, share_format);
+        Err("Share sheet is currently supported on macOS only".to_string())
+    }
+}
+
+#[cfg(target_os = "macos")]
+enum ShareItem {
+    Text(String),
+    File(PathBuf),
+    /// Rendered from the webview on the main thread, then shared as a file.
+    WebviewPdf(PathBuf),
+}
+
+/// Share files outlive the command (the picker is asynchronous), so each
+/// lives in its own folder that is removed after this long.
+#[cfg(target_os = "macos")]
+const SHARE_FILE_TTL_SECS: u64 = 10 * 60;
+
+#[cfg(target_os = "macos")]
+fn share_temp_root() -> PathBuf {
+    std::env::temp_dir().join("stik-share")
+}
+
+/// Create `<tmp>/stik-share/<uuid>/<title>.<ext>` and schedule its removal.
+#[cfg(target_os = "macos")]
+fn share_temp_file(title: &str, ext: &str) -> Result<PathBuf, String> {
+    let dir = share_temp_root().join(uuid::Uuid::new_v4().to_string());
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create share folder: {e}"))?;
+
+    let cleanup_dir = dir.clone();
+    std::thread::Builder::new()
+        .name("stik-share-cleanup".to_string())
+        .spawn(move || {
+            std::thread::sleep(std::time::Duration::from_secs(SHARE_FILE_TTL_SECS));
+            let _ = fs::remove_dir_all(&cleanup_dir);
+        })
+        .ok();
+
+    Ok(dir.join(format!("{}.{}", share_file_stem(title), ext)))
+}
+
+/// Sweep share folders left behind by a previous run that quit before its
+/// cleanup thread fired.
+#[cfg(target_os = "macos")]
+fn remove_stale_share_files() {
+    let Ok(entries) = fs::read_dir(share_temp_root()) else {
+        return;
+    };
+    let ttl = std::time::Duration::from_secs(SHARE_FILE_TTL_SECS);
+    for entry in entries.flatten() {
+        let is_stale = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > ttl);
+        if is_stale {
+            let _ = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn share_file_stem(title: &str) -> String {
+    let stem: String = title
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+        .take(60)
+        .collect();
+    let stem = stem.trim();
+    if stem.is_empty() {
+        "Note".to_string()
+    } else {
+        stem.to_string()
     }
 }
 
+#[cfg(target_os = "macos")]
+unsafe fn present_share_picker(
+    webview: tauri::webview::PlatformWebview,
+    item: &ShareItem,
+) -> Result<(), String> {
+    let mtm = MainThreadMarker::new().ok_or("Share sheet must open on the main thread")?;
+    let view: &NSView = unsafe { &*webview.inner().cast() };
+    let bounds = view.bounds();
+
+    let file_url =
+        |path: &Path| NSURL::fileURLWithPath(&NSString::from_str(&path.to_string_lossy()));
+    let object: Retained<NSObject> = match item {
+        ShareItem::Text(text) => Retained::into_super(NSString::from_str(text)),
+        ShareItem::File(path) => Retained::into_super(file_url(path)),
+        ShareItem::WebviewPdf(path) => {
+            let pdf = view.dataWithPDFInsideRect(bounds);
+            fs::write(path, ns_data_to_vec(&pdf))
+                .map_err(|e| format!("Failed to write share PDF: {e}"))?;
+            Retained::into_super(file_url(path))
+        }
+    };
+
+    let items = NSArray::<AnyObject>::from_slice(&[&**object]);
+    let picker = unsafe {
+        NSSharingServicePicker::initWithItems(NSSharingServicePicker::alloc(mtm), &items)
+    };
+    picker.showRelativeToRect_ofView_preferredEdge(bounds, view, NSRectEdge::MinY);
+    Ok(())
+}
+
 fn copy_png_bytes_to_clipboard(png_bytes: &[u8]) -> Result<(), String> {
     let image = image::load_from_memory_with_format(png_bytes, image::ImageFormat::Png)
         .map_err(|e| format!("Invalid PNG image: {e}"))?
@@ -108,18 +454,353 @@ pub fn read_clipboard_text() -> Result<String, String> {
         .map_err(|e| format!("No text on clipboard: {e}"))
 }
 
-fn markdown_to_html(markdown: &str) -> String {
+pub(crate) fn markdown_options(smart_punctuation: bool) -> Options {
     let mut options = Options::empty();
     options.insert(Options::ENABLE_TABLES);
     options.insert(Options::ENABLE_STRIKETHROUGH);
     options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_FOOTNOTES);
+    options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
+    if smart_punctuation {
+        options.insert(Options::ENABLE_SMART_PUNCTUATION);
+    }
+    options
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct HtmlOptions<'a> {
+    /// Inline `.assets/` images from this folder as `data:` URLs. Missing
+    /// or oversized images keep their original reference.
+    pub note_dir: Option<&'a Path>,
+    /// Curly quotes, dashes and ellipses (the `smart_punctuation` setting).
+    pub smart_punctuation: bool,
+    /// Image sources to point elsewhere, keyed by their markdown `src`.
+    /// Takes precedence over `note_dir` inlining.
+    pub asset_urls: Option<&'a HashMap<String, String>>,
+}
+
+impl HtmlOptions<'_> {
+    fn from_settings() -> Self {
+        Self {
+            note_dir: None,
+            smart_punctuation: super::settings::load_settings_from_file()
+                .map(|s| s.smart_punctuation)
+                .unwrap_or(false),
+            asset_urls: None,
+        }
+    }
+}
+
+/// The one markdown renderer behind clipboard HTML and file exports.
+/// Headings get slugified ids (`## What's new?` → `id="whats-new"`) so
+/// exported documents can be deep-linked. Frontmatter is left out.
+pub(crate) fn markdown_to_html(markdown: &str, options: &HtmlOptions) -> String {
+    let markdown = strip_frontmatter(markdown);
+    let mut events: Vec<Event> =
+        Parser::new_ext(markdown, markdown_options(options.smart_punctuation))
+            .map(|event| match (event, options.note_dir) {
+                (
+                    Event::Start(Tag::Image {
+                        link_type,
+                        dest_url,
+                        title,
+                        id,
+                    }),
+                    dir,
+                ) if dir.is_some() || options.asset_urls.is_some() => {
+                    let rewritten = options
+                        .asset_urls
+                        .and_then(|urls| urls.get(&*dest_url).cloned())
+                        .or_else(|| dir.and_then(|dir| asset_data_url(&dest_url, dir)));
+                    let dest_url = rewritten.map(CowStr::from).unwrap_or(dest_url);
+                    Event::Start(Tag::Image {
+                        link_type,
+                        dest_url,
+                        title,
+                        id,
+                    })
+                }
+                (other, _) => other,
+            })
+            .collect();
+    assign_heading_ids(&mut events);
 
-    let parser = Parser::new_ext(markdown, options);
     let mut html_output = String::new();
-    html::push_html(&mut html_output, parser);
+    html::push_html(&mut html_output, events.into_iter());
     html_output
 }
 
+/// Give every heading without an explicit `{#id}` a unique slug id.
+fn assign_heading_ids(events: &mut [Event]) {
+    let headings: Vec<(usize, String)> = events
+        .iter()
+        .enumerate()
+        .filter_map(|(i, event)| match event {
+            Event::Start(Tag::Heading { id: None, .. }) => {
+                Some((i, slugify_heading(&heading_text(&events[i + 1..]))))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let mut slugs = HeadingSlugs::default();
+    for (i, base) in headings {
+        let Some(slug) = slugs.next(base) else {
+            continue;
+        };
+        if let Event::Start(Tag::Heading { id, .. }) = &mut events[i] {
+            *id = Some(CowStr::from(slug));
+        }
+    }
+}
+
+/// Numbers repeated heading slugs (`notes`, `notes-1`, `notes-2`) so every
+/// anchor in a document stays unique.
+#[derive(Debug, Default)]
+pub(crate) struct HeadingSlugs {
+    seen: HashMap<String, usize>,
+}
+
+impl HeadingSlugs {
+    /// The id for the next heading slugified to `base`; `None` when the
+    /// heading has no sluggable text.
+    pub(crate) fn next(&mut self, base: String) -> Option<String> {
+        if base.is_empty() {
+            return None;
+        }
+        let count = self.seen.entry(base.clone()).or_insert(0);
+        let slug = if *count == 0 {
+            base
+        } else {
+            format!("{}-{}", base, count)
+        };
+        *count += 1;
+        Some(slug)
+    }
+}
+
+pub(crate) fn heading_text(events: &[Event]) -> String {
+    events
+        .iter()
+        .take_while(|event| !matches!(event, Event::End(TagEnd::Heading(_))))
+        .filter_map(|event| match event {
+            Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+            _ => None,
+        })
+        .collect()
+}
+
+pub(crate) fn slugify_heading(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.trim().chars() {
+        if c.is_alphanumeric() || c == '_' {
+            slug.extend(c.to_lowercase());
+        } else if (c.is_whitespace() || c == '-') && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Folder containing a note, checked to be inside the Stik folder.
+pub(crate) fn resolve_note_dir(note_path: &str) -> Result<PathBuf, String> {
+    let stik_folder = get_stik_folder()?;
+    let note_dir = Path::new(note_path)
+        .parent()
+        .ok_or("Invalid note path")?
+        .to_path_buf();
+    if !note_dir.starts_with(&stik_folder) {
+        return Err("Invalid path: note must be within Stik folder".to_string());
+    }
+    Ok(note_dir)
+}
+
+/// The file name in a `.assets/name` image source, refusing anything that
+/// could point outside the folder's `.assets`.
+pub(crate) fn asset_name(src: &str) -> Option<&str> {
+    let name = src
+        .strip_prefix("./")
+        .unwrap_or(src)
+        .strip_prefix(".assets/")?;
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+        return None;
+    }
+    Some(name)
+}
+
+fn asset_data_url(src: &str, note_dir: &Path) -> Option<String> {
+    let name = asset_name(src)?;
+
+    let ext = Path::new(name).extension()?.to_str()?.to_ascii_lowercase();
+    let mime = match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        "avif" => "image/avif",
+        _ => return None,
+    };
+
+    let path = note_dir.join(".assets").join(name);
+    let size = fs::metadata(&path).ok()?.len();
+    if size > MAX_INLINE_IMAGE_BYTES {
+        return None;
+    }
+    let bytes = fs::read(&path).ok()?;
+
+    Some(format!(
+        "data:{};base64,{}",
+        mime,
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    ))
+}
+
+/// Render markdown as readable plain text: list bullets become "• ", links
+/// become "text (url)", images become their alt text, and fenced code is
+/// kept verbatim. Frontmatter is left out.
+pub fn build_plain_text(markdown: &str) -> String {
+    let markdown = strip_frontmatter(markdown);
+    let mut out = String::new();
+    // One entry per open list: the next number for ordered lists.
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    // Output offset where each open link's text starts, plus its URL.
+    let mut links: Vec<(usize, String)> = Vec::new();
+    let mut image_depth = 0usize;
+    let mut in_fence = false;
+    let mut item_just_started = false;
+    let mut cell_index = 0usize;
+
+    for event in Parser::new_ext(markdown, markdown_options(false)) {
+        match event {
+            Event::Start(Tag::Paragraph) => {
+                if !item_just_started {
+                    start_block(&mut out, !lists.is_empty());
+                }
+            }
+            Event::Start(Tag::Heading { .. }) | Event::Start(Tag::Table(_)) => {
+                start_block(&mut out, !lists.is_empty());
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                if !item_just_started {
+                    start_block(&mut out, !lists.is_empty());
+                }
+                item_just_started = false;
+                if let CodeBlockKind::Fenced(lang) = kind {
+                    out.push_str("```");
+                    out.push_str(&lang);
+                    out.push('\n');
+                    in_fence = true;
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                ensure_newline(&mut out);
+                if in_fence {
+                    out.push_str("```");
+                    in_fence = false;
+                }
+            }
+            Event::Start(Tag::List(start)) => {
+                if lists.is_empty() {
+                    start_block(&mut out, false);
+                }
+                lists.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+            }
+            Event::Start(Tag::Item) => {
+                ensure_newline(&mut out);
+                let depth = lists.len().saturating_sub(1);
+                out.push_str(&"  ".repeat(depth));
+                match lists.last_mut() {
+                    Some(Some(number)) => {
+                        out.push_str(&format!("{}. ", number));
+                        *number += 1;
+                    }
+                    _ => out.push_str("• "),
+                }
+                item_just_started = true;
+            }
+            Event::TaskListMarker(checked) => {
+                if out.ends_with("• ") {
+                    out.truncate(out.len() - "• ".len());
+                }
+                out.push_str(if checked { "☑ " } else { "☐ " });
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                links.push((out.len(), dest_url.to_string()));
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some((start, url)) = links.pop() {
+                    let text = out[start..].trim();
+                    let bare_url = url.strip_prefix("mailto:").unwrap_or(&url);
+                    if !url.is_empty() && text != url && text != bare_url {
+                        if text.is_empty() {
+                            out.push_str(&url);
+                        } else {
+                            out.push_str(&format!(" ({})", url));
+                        }
+                    }
+                }
+            }
+            Event::Start(Tag::Image { .. }) => image_depth += 1,
+            Event::End(TagEnd::Image) => image_depth = image_depth.saturating_sub(1),
+            Event::Start(Tag::TableRow) | Event::Start(Tag::TableHead) => {
+                ensure_newline(&mut out);
+                cell_index = 0;
+            }
+            Event::Start(Tag::TableCell) => {
+                if cell_index > 0 {
+                    out.push_str(" | ");
+                }
+                cell_index += 1;
+            }
+            Event::Text(text) | Event::Code(text) => {
+                item_just_started = false;
+                out.push_str(&text);
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                if image_depth == 0 {
+                    out.push('\n');
+                }
+            }
+            Event::Rule => {
+                start_block(&mut out, false);
+                out.push_str("———");
+            }
+            Event::FootnoteReference(label) => out.push_str(&format!("[{}]", label)),
+            Event::Start(Tag::FootnoteDefinition(label)) => {
+                start_block(&mut out, false);
+                out.push_str(&format!("[{}]: ", label));
+                item_just_started = true;
+            }
+            _ => {}
+        }
+    }
+
+    out.trim_end().to_string()
+}
+
+fn ensure_newline(out: &mut String) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Separate a new block from preceding output: a blank line at the top
+/// level, a single line break inside lists.
+fn start_block(out: &mut String, in_list: bool) {
+    if out.is_empty() {
+        return;
+    }
+    ensure_newline(out);
+    if !in_list && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
 #[cfg(target_os = "macos")]
 unsafe fn capture_webview_png_bytes(
     webview: tauri::webview::PlatformWebview,
@@ -159,17 +840,58 @@ fn ns_data_to_vec(data: &NSData) -> Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::test_support::temp_dir;
+
+    fn png_of_size(width: u32, height: u32) -> Vec<u8> {
+        let image = image::RgbaImage::from_pixel(width, height, image::Rgba([255, 0, 0, 255]));
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        image
+            .write_to(&mut bytes, image::ImageFormat::Png)
+            .expect("encode png");
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn snapshots_are_scaled_to_max_width_and_dirs_created() {
+        let dir = temp_dir("snapshot");
+        let png = png_of_size(800, 300);
+
+        let scaled = write_png_snapshot(&png, &dir.join("slides/plan"), Some(400)).unwrap();
+        assert_eq!((scaled.width, scaled.height), (400, 150));
+        assert!(scaled.path.ends_with("slides/plan.png"));
+        let written = image::open(&scaled.path).unwrap();
+        assert_eq!((written.width(), written.height()), (400, 150));
+
+        let kept = write_png_snapshot(&png, &dir.join("full.png"), Some(1200)).unwrap();
+        assert_eq!((kept.width, kept.height), (800, 300));
+        assert_eq!(fs::read(&kept.path).unwrap(), png);
+
+        assert!(write_png_snapshot(&png, Path::new("relative.png"), None).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
 
     #[test]
     fn renders_heading_and_paragraph() {
-        let html = markdown_to_html("# Title\n\nhello world");
-        assert!(html.contains("<h1>Title</h1>"));
+        let html = markdown_to_html("# Title\n\nhello world", &HtmlOptions::default());
+        assert!(html.contains("<h1 id=\"title\">Title</h1>"));
         assert!(html.contains("<p>hello world</p>"));
     }
 
+    #[test]
+    fn frontmatter_is_never_rendered_or_copied() {
+        let markdown = "---\nstik-id: 1b4e28ba\n---\n# Title\n\nBody";
+        let html = markdown_to_html(markdown, &HtmlOptions::default());
+        assert!(!html.contains("stik-id"));
+        assert!(html.contains("<h1 id=\"title\">Title</h1>"));
+        assert_eq!(build_plain_text(markdown), "Title\n\nBody");
+    }
+
     #[test]
     fn renders_basic_inline_markdown() {
-        let html = markdown_to_html("This has **bold**, *italic*, and `code`.");
+        let html = markdown_to_html(
+            "This has **bold**, *italic*, and `code`.",
+            &HtmlOptions::default(),
+        );
         assert!(html.contains("<strong>bold</strong>"));
         assert!(html.contains("<em>italic</em>"));
         assert!(html.contains("<code>code</code>"));
@@ -177,7 +899,7 @@ mod tests {
 
     #[test]
     fn renders_unordered_list_items() {
-        let html = markdown_to_html("- one\n- two");
+        let html = markdown_to_html("- one\n- two", &HtmlOptions::default());
         assert!(html.contains("<ul>"));
         assert!(html.contains("<li>one</li>"));
         assert!(html.contains("<li>two</li>"));
@@ -220,4 +942,140 @@ mod tests {
         let decoded = base64::engine::general_purpose::STANDARD.decode("not-valid-base64");
         assert!(decoded.is_err());
     }
+
+    #[test]
+    fn plain_text_strips_inline_syntax_and_headings() {
+        let text = build_plain_text("# Title\n\nThis has **bold** and `code`.");
+        assert_eq!(text, "Title\n\nThis has bold and code.");
+    }
+
+    #[test]
+    fn plain_text_renders_nested_lists_with_bullets() {
+        let text = build_plain_text("- one\n  - nested\n- two\n\n1. first\n2. second");
+        assert_eq!(text, "• one\n  • nested\n• two\n\n1. first\n2. second");
+    }
+
+    #[test]
+    fn plain_text_renders_task_list_checkboxes() {
+        let text = build_plain_text("- [x] done\n- [ ] todo");
+        assert_eq!(text, "☑ done\n☐ todo");
+    }
+
+    #[test]
+    fn plain_text_appends_link_urls_unless_redundant() {
+        let text = build_plain_text(
+            "See [docs](https://stik.app/docs) or [https://stik.app](https://stik.app).",
+        );
+        assert_eq!(
+            text,
+            "See docs (https://stik.app/docs) or https://stik.app."
+        );
+    }
+
+    #[test]
+    fn plain_text_uses_image_alt_and_keeps_code_fences() {
+        let text = build_plain_text("![diagram](.assets/a.png)\n\n```rust\nlet x = **1**;\n```");
+        assert_eq!(text, "diagram\n\n```rust\nlet x = **1**;\n```");
+    }
+
+    #[test]
+    fn inlines_asset_images_as_data_urls() {
+        let dir = temp_dir("share-inline");
+        fs::create_dir_all(dir.join(".assets")).unwrap();
+        fs::write(dir.join(".assets").join("pic.png"), [1_u8, 2, 3]).unwrap();
+
+        let options = HtmlOptions {
+            note_dir: Some(&dir),
+            ..HtmlOptions::default()
+        };
+        let html = markdown_to_html(
+            "![pic](.assets/pic.png) ![gone](.assets/missing.png)",
+            &options,
+        );
+        assert!(html.contains("src=\"data:image/png;base64,AQID\""));
+        assert!(html.contains("src=\".assets/missing.png\""));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn refuses_to_inline_assets_outside_the_note_folder() {
+        let dir = std::env::temp_dir();
+        assert_eq!(asset_data_url(".assets/../secret.png", &dir), None);
+        assert_eq!(asset_data_url("https://example.com/a.png", &dir), None);
+    }
+
+    #[test]
+    fn parses_share_formats() {
+        assert_eq!(ShareFormat::parse("PDF"), Ok(ShareFormat::Pdf));
+        assert_eq!(ShareFormat::parse("md"), Ok(ShareFormat::Markdown));
+        assert!(ShareFormat::parse("docx").is_err());
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn share_file_stem_keeps_titles_filesystem_safe() {
+        assert_eq!(share_file_stem("Plans: Q3/Q4?"), "Plans Q3Q4");
+        assert_eq!(share_file_stem("///"), "Note");
+    }
+
+    #[test]
+    fn renders_footnote_references_and_definitions() {
+        let html = markdown_to_html(
+            "A claim.[^src]\n\n[^src]: Smith, 2021.",
+            &HtmlOptions::default(),
+        );
+        assert!(html.contains("class=\"footnote-reference\""));
+        assert!(html.contains("class=\"footnote-definition\""));
+        assert!(!html.contains("[^src]"));
+    }
+
+    #[test]
+    fn slugifies_heading_ids_and_dedupes_them() {
+        let html = markdown_to_html(
+            "## What's new? (v2.0)\n\n## What's new? (v2.0)\n\n## Custom {#custom-id}",
+            &HtmlOptions::default(),
+        );
+        assert!(html.contains("<h2 id=\"whats-new-v20\">"));
+        assert!(html.contains("<h2 id=\"whats-new-v20-1\">"));
+        assert!(html.contains("<h2 id=\"custom-id\">Custom</h2>"));
+    }
+
+    #[test]
+    fn renders_nested_task_lists() {
+        let html = markdown_to_html(
+            "- [ ] parent\n  - [x] child\n  - [ ] sibling",
+            &HtmlOptions::default(),
+        );
+        assert_eq!(html.matches("type=\"checkbox\"").count(), 3);
+        assert_eq!(html.matches("<ul>").count(), 2);
+        assert_eq!(html.matches("checked=\"\"").count(), 1);
+    }
+
+    #[test]
+    fn smart_punctuation_is_opt_in() {
+        let markdown = "\"Quoted\" -- done...";
+        assert!(markdown_to_html(markdown, &HtmlOptions::default()).contains("&quot;Quoted&quot;"));
+
+        let options = HtmlOptions {
+            smart_punctuation: true,
+            ..HtmlOptions::default()
+        };
+        assert!(markdown_to_html(markdown, &options).contains("“Quoted” – done…"));
+    }
+
+    #[test]
+    fn image_copy_outcome_serializes_for_the_frontend() {
+        let json = serde_json::to_string(&ImageCopyOutcome::NeedsCanvasSnapshot).unwrap();
+        assert_eq!(json, "\"needs_canvas_snapshot\"");
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn non_macos_visible_capture_asks_for_canvas_snapshot() {
+        assert_eq!(
+            canvas_snapshot_fallback(),
+            ImageCopyOutcome::NeedsCanvasSnapshot
+        );
+    }
 }
@@ -0,0 +1,431 @@
+/// Dictation through macOS speech recognition (SFSpeechRecognizer fed by
+/// AVAudioEngine), for when no Whisper model is installed.
+///
+/// `start_dictation` opens the microphone and streams interim text to the
+/// window that started it as `dictation-partial` events. `stop_dictation`
+/// waits briefly for the final transcription, then either saves it as a
+/// note or hands it to the postit to edit. Closing that window mid-dictation
+/// tears the audio session down.
+use serde::Serialize;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, WebviewWindow, WindowEvent};
+
+use super::notes::{self, NoteSaved};
+
+/// Error strings the UI turns into a "grant access" prompt.
+pub const MICROPHONE_DENIED: &str = "microphone-permission-denied";
+pub const SPEECH_RECOGNITION_DENIED: &str = "speech-recognition-permission-denied";
+
+pub const PARTIAL_EVENT: &str = "dictation-partial";
+
+/// How long `stop_dictation` waits for the recognizer's final result.
+const FINAL_RESULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpeechDictationResult {
+    pub text: String,
+    /// Set when the text was saved directly instead of prefilled.
+    pub saved: Option<NoteSaved>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PartialTranscript {
+    text: String,
+}
+
+/// What the recognizer has reported so far.
+#[derive(Debug, Default)]
+struct Transcript {
+    text: String,
+    finished: bool,
+    error: Option<String>,
+}
+
+type SharedTranscript = Arc<Mutex<Transcript>>;
+
+struct ActiveDictation {
+    folder: String,
+    window_label: String,
+    transcript: SharedTranscript,
+    audio: imp::AudioSession,
+}
+
+static ACTIVE: OnceLock<Mutex<Option<ActiveDictation>>> = OnceLock::new();
+
+fn active() -> &'static Mutex<Option<ActiveDictation>> {
+    ACTIVE.get_or_init(|| Mutex::new(None))
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use super::*;
+    use block2::RcBlock;
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyObject, Bool};
+    use objc2::{class, msg_send};
+    use objc2_foundation::NSString;
+    use std::sync::mpsc;
+
+    #[link(name = "Speech", kind = "framework")]
+    extern "C" {}
+    #[link(name = "AVFoundation", kind = "framework")]
+    extern "C" {}
+
+    // SFSpeechRecognizerAuthorizationStatus / AVAuthorizationStatus
+    const SPEECH_NOT_DETERMINED: isize = 0;
+    const SPEECH_AUTHORIZED: isize = 3;
+    const MIC_NOT_DETERMINED: isize = 0;
+    const MIC_AUTHORIZED: isize = 3;
+    const AV_MEDIA_TYPE_AUDIO: &str = "soun";
+    const TAP_BUFFER_SIZE: u32 = 1024;
+    /// How long to wait for the user to answer a permission prompt.
+    const PERMISSION_TIMEOUT: Duration = Duration::from_secs(60);
+
+    /// The running engine, request and task. Only touched while holding
+    /// the `ACTIVE` lock, so moving it between threads is fine.
+    pub struct AudioSession {
+        engine: Retained<AnyObject>,
+        request: Retained<AnyObject>,
+        task: Retained<AnyObject>,
+        stopped: bool,
+    }
+
+    unsafe impl Send for AudioSession {}
+
+    impl AudioSession {
+        /// Stop capturing and tell the recognizer no more audio is coming.
+        pub fn finish(&mut self) {
+            if self.stopped {
+                return;
+            }
+            self.stopped = true;
+            unsafe {
+                let _: () = msg_send![&*self.engine, stop];
+                let input: Retained<AnyObject> = msg_send![&*self.engine, inputNode];
+                let _: () = msg_send![&*input, removeTapOnBus: 0usize];
+                let _: () = msg_send![&*self.request, endAudio];
+            }
+        }
+
+        /// Stop immediately, dropping anything not yet transcribed.
+        pub fn cancel(&mut self) {
+            self.finish();
+            unsafe {
+                let _: () = msg_send![&*self.task, cancel];
+            }
+        }
+    }
+
+    impl Drop for AudioSession {
+        fn drop(&mut self) {
+            self.cancel();
+        }
+    }
+
+    fn speech_authorized() -> Result<(), String> {
+        let status: isize = unsafe { msg_send![class!(SFSpeechRecognizer), authorizationStatus] };
+        if status == SPEECH_AUTHORIZED {
+            return Ok(());
+        }
+        if status != SPEECH_NOT_DETERMINED {
+            return Err(SPEECH_RECOGNITION_DENIED.to_string());
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let handler = RcBlock::new(move |status: isize| {
+            let _ = tx.send(status);
+        });
+        unsafe {
+            let _: () = msg_send![class!(SFSpeechRecognizer), requestAuthorization: &*handler];
+        }
+        match rx.recv_timeout(PERMISSION_TIMEOUT) {
+            Ok(SPEECH_AUTHORIZED) => Ok(()),
+            _ => Err(SPEECH_RECOGNITION_DENIED.to_string()),
+        }
+    }
+
+    fn microphone_authorized() -> Result<(), String> {
+        let media_type = NSString::from_str(AV_MEDIA_TYPE_AUDIO);
+        let status: isize = unsafe {
+            msg_send![class!(AVCaptureDevice), authorizationStatusForMediaType: &*media_type]
+        };
+        if status == MIC_AUTHORIZED {
+            return Ok(());
+        }
+        if status != MIC_NOT_DETERMINED {
+            return Err(MICROPHONE_DENIED.to_string());
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let handler = RcBlock::new(move |granted: Bool| {
+            let _ = tx.send(granted.as_bool());
+        });
+        unsafe {
+            let _: () = msg_send![
+                class!(AVCaptureDevice),
+                requestAccessForMediaType: &*media_type,
+                completionHandler: &*handler
+            ];
+        }
+        match rx.recv_timeout(PERMISSION_TIMEOUT) {
+            Ok(true) => Ok(()),
+            _ => Err(MICROPHONE_DENIED.to_string()),
+        }
+    }
+
+    fn recognizer(language: Option<&str>) -> Result<Retained<AnyObject>, String> {
+        let recognizer: Option<Retained<AnyObject>> = unsafe {
+            match language {
+                Some(language) => {
+                    let locale: Retained<AnyObject> = msg_send![
+                        class!(NSLocale),
+                        localeWithLocaleIdentifier: &*NSString::from_str(language)
+                    ];
+                    let recognizer: Retained<AnyObject> =
+                        msg_send![class!(SFSpeechRecognizer), alloc];
+                    msg_send![recognizer, initWithLocale: &*locale]
+                }
+                None => msg_send![class!(SFSpeechRecognizer), new],
+            }
+        };
+        let recognizer = recognizer.ok_or("Speech recognition doesn't support this language")?;
+        let available: bool = unsafe { msg_send![&*recognizer, isAvailable] };
+        if !available {
+            return Err("Speech recognition is currently unavailable".to_string());
+        }
+        Ok(recognizer)
+    }
+
+    fn error_description(error: &AnyObject) -> String {
+        let description: Retained<NSString> = unsafe { msg_send![error, localizedDescription] };
+        description.to_string()
+    }
+
+    pub fn start(
+        language: Option<&str>,
+        transcript: SharedTranscript,
+        on_partial: impl Fn(&str) + 'static,
+    ) -> Result<AudioSession, String> {
+        speech_authorized()?;
+        microphone_authorized()?;
+        let recognizer = recognizer(language)?;
+
+        unsafe {
+            let request: Retained<AnyObject> =
+                msg_send![class!(SFSpeechAudioBufferRecognitionRequest), new];
+            let _: () = msg_send![&*request, setShouldReportPartialResults: true];
+            let on_device: bool = msg_send![&*recognizer, supportsOnDeviceRecognition];
+            if on_device {
+                let _: () = msg_send![&*request, setRequiresOnDeviceRecognition: true];
+            }
+
+            let engine: Retained<AnyObject> = msg_send![class!(AVAudioEngine), new];
+            let input: Retained<AnyObject> = msg_send![&*engine, inputNode];
+            let format: Retained<AnyObject> = msg_send![&*input, outputFormatForBus: 0usize];
+            let tap_request = request.clone();
+            let tap = RcBlock::new(move |buffer: *mut AnyObject, _when: *mut AnyObject| {
+                if let Some(buffer) = buffer.as_ref() {
+                    let _: () = msg_send![&*tap_request, appendAudioPCMBuffer: buffer];
+                }
+            });
+            let _: () = msg_send![
+                &*input,
+                installTapOnBus: 0usize,
+                bufferSize: TAP_BUFFER_SIZE,
+                format: &*format,
+                block: &*tap
+            ];
+
+            let handler_transcript = transcript.clone();
+            let on_result = RcBlock::new(move |result: *mut AnyObject, error: *mut AnyObject| {
+                let mut transcript = handler_transcript.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(result) = result.as_ref() {
+                    let best: Retained<AnyObject> = msg_send![result, bestTranscription];
+                    let text: Retained<NSString> = msg_send![&*best, formattedString];
+                    transcript.text = text.to_string();
+                    let is_final: bool = msg_send![result, isFinal];
+                    transcript.finished |= is_final;
+                    if !is_final {
+                        on_partial(&transcript.text);
+                    }
+                }
+                if let Some(error) = error.as_ref() {
+                    transcript.error = Some(error_description(error));
+                    transcript.finished = true;
+                }
+            });
+            let task: Retained<AnyObject> = msg_send![
+                &*recognizer,
+                recognitionTaskWithRequest: &*request,
+                resultHandler: &*on_result
+            ];
+
+            let _: () = msg_send![&*engine, prepare];
+            let mut error: *mut AnyObject = std::ptr::null_mut();
+            let started: bool = msg_send![&*engine, startAndReturnError: &mut error];
+            let mut session = AudioSession {
+                engine,
+                request,
+                task,
+                stopped: false,
+            };
+            if !started {
+                session.cancel();
+                let reason = error
+                    .as_ref()
+                    .map(error_description)
+                    .unwrap_or_else(|| "unknown error".to_string());
+                return Err(format!("Couldn't start the microphone: {}", reason));
+            }
+            Ok(session)
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod imp {
+    use super::*;
+
+    pub struct AudioSession;
+
+    impl AudioSession {
+        pub fn finish(&mut self) {}
+        pub fn cancel(&mut self) {}
+    }
+
+    pub fn start(
+        _language: Option<&str>,
+        _transcript: SharedTranscript,
+        _on_partial: impl Fn(&str) + 'static,
+    ) -> Result<AudioSession, String> {
+        Err("Speech dictation is only supported on macOS".to_string())
+    }
+}
+
+/// Drop the running session, if it belongs to `window_label`.
+fn cancel_for_window(window_label: &str) {
+    let mut active = active().lock().unwrap_or_else(|e| e.into_inner());
+    if active
+        .as_ref()
+        .is_some_and(|dictation| dictation.window_label == window_label)
+    {
+        if let Some(mut dictation) = active.take() {
+            dictation.audio.cancel();
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn start_dictation(window: WebviewWindow, folder: String) -> Result<(), String> {
+    if !folder.is_empty() {
+        super::folders::validate_name(&folder)?;
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut active = active().lock().unwrap_or_else(|e| e.into_inner());
+        if active.is_some() {
+            return Err("Dictation is already running".to_string());
+        }
+
+        let language = super::settings::load_settings_from_file()
+            .ok()
+            .and_then(|settings| settings.dictation.active_language);
+        let transcript = SharedTranscript::default();
+        let emitter = window.clone();
+        let audio = imp::start(language.as_deref(), transcript.clone(), move |text| {
+            let _ = emitter.emit(
+                PARTIAL_EVENT,
+                PartialTranscript {
+                    text: text.to_string(),
+                },
+            );
+        })?;
+
+        let window_label = window.label().to_string();
+        let label = window_label.clone();
+        window.on_window_event(move |event| {
+            if matches!(
+                event,
+                WindowEvent::CloseRequested { .. } | WindowEvent::Destroyed
+            ) {
+                cancel_for_window(&label);
+            }
+        });
+
+        *active = Some(ActiveDictation {
+            folder,
+            window_label,
+            transcript,
+            audio,
+        });
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("start join error: {}", e))?
+}
+
+/// Finish dictation. With `save` the text is saved to the folder given to
+/// `start_dictation`; otherwise it is sent to the postit as a prefill.
+#[tauri::command]
+pub async fn stop_dictation(app: AppHandle, save: bool) -> Result<SpeechDictationResult, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut dictation = active()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+            .ok_or("Dictation isn't running")?;
+        dictation.audio.finish();
+
+        let started = Instant::now();
+        while started.elapsed() < FINAL_RESULT_TIMEOUT {
+            if dictation
+                .transcript
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .finished
+            {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        dictation.audio.cancel();
+
+        let (text, error) = {
+            let transcript = dictation
+                .transcript
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            (transcript.text.trim().to_string(), transcript.error.clone())
+        };
+        if text.is_empty() {
+            return Err(error.unwrap_or_else(|| "Nothing was heard".to_string()));
+        }
+
+        let folder = if dictation.folder.is_empty() {
+            super::settings::load_settings_from_file()
+                .map(|s| s.default_folder)
+                .unwrap_or_else(|_| "Inbox".to_string())
+        } else {
+            dictation.folder
+        };
+
+        if save {
+            let saved = notes::save_note_inner(folder, text.clone())?;
+            notes::post_save_processing(&app, &saved, &text);
+            let _ = app.emit("files-changed", vec![saved.path.clone()]);
+            return Ok(SpeechDictationResult {
+                text,
+                saved: Some(saved),
+            });
+        }
+
+        let _ = app.emit_to(
+            "postit",
+            "transfer-content",
+            serde_json::json!({ "content": text, "folder": folder }),
+        );
+        Ok(SpeechDictationResult { text, saved: None })
+    })
+    .await
+    .map_err(|e| format!("stop join error: {}", e))?
+}
@@ -0,0 +1,357 @@
+/// Core Spotlight donation, behind the opt-in `spotlight_indexing` setting.
+///
+/// Each note becomes a CSSearchableItem carrying its title, preview and
+/// folder, identified by its `stik://open` link. Items are donated on save
+/// and update, dropped on delete and re-donated on move. Locked notes are
+/// never donated: their title and preview would leak into Spotlight.
+/// Turning the setting off deletes everything Stik donated.
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager, State, Url};
+
+use super::folders;
+use super::index::{NoteEntry, NoteIndex};
+use super::settings::StikSettings;
+
+/// Items sent to Core Spotlight per `indexSearchableItems` call.
+const DONATION_BATCH: usize = 500;
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// A note ready to hand to Core Spotlight.
+#[derive(Debug, Clone, PartialEq)]
+struct SpotlightItem {
+    identifier: String,
+    title: String,
+    description: String,
+    keywords: Vec<String>,
+}
+
+/// `stik://open` link for a note path relative to the Stik folder.
+pub fn open_link(relative: &Path) -> String {
+    let relative = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let mut url = Url::parse("stik://open").expect("static URL parses");
+    url.query_pairs_mut().append_pair("path", &relative);
+    url.to_string()
+}
+
+fn identifier_for(path: &str, stik_root: &Path) -> Option<String> {
+    Path::new(path).strip_prefix(stik_root).ok().map(open_link)
+}
+
+fn item_for(entry: &NoteEntry, stik_root: &Path) -> Option<SpotlightItem> {
+    if entry.locked {
+        return None;
+    }
+    let title = if entry.title.trim().is_empty() {
+        entry.filename.trim_end_matches(".md").to_string()
+    } else {
+        entry.title.clone()
+    };
+    Some(SpotlightItem {
+        identifier: identifier_for(&entry.path, stik_root)?,
+        title,
+        description: entry.preview.clone(),
+        keywords: vec![entry.folder.clone()],
+    })
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use super::SpotlightItem;
+    use block2::RcBlock;
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+    use objc2_foundation::{NSArray, NSString};
+
+    #[link(name = "CoreSpotlight", kind = "framework")]
+    extern "C" {}
+    #[link(name = "UniformTypeIdentifiers", kind = "framework")]
+    extern "C" {}
+
+    const DOMAIN: &str = "notes";
+    const CONTENT_TYPE: &str = "net.daringfireball.markdown";
+    const FALLBACK_CONTENT_TYPE: &str = "public.plain-text";
+
+    fn searchable_index() -> Retained<AnyObject> {
+        unsafe { msg_send![class!(CSSearchableIndex), defaultSearchableIndex] }
+    }
+
+    fn content_type() -> Retained<AnyObject> {
+        unsafe {
+            let markdown: Option<Retained<AnyObject>> = msg_send![
+                class!(UTType),
+                typeWithIdentifier: &*NSString::from_str(CONTENT_TYPE)
+            ];
+            markdown.unwrap_or_else(|| {
+                msg_send![
+                    class!(UTType),
+                    typeWithIdentifier: &*NSString::from_str(FALLBACK_CONTENT_TYPE)
+                ]
+            })
+        }
+    }
+
+    fn report_errors(action: &'static str) -> RcBlock<dyn Fn(*mut AnyObject)> {
+        RcBlock::new(move |error: *mut AnyObject| {
+            if let Some(error) = unsafe { error.as_ref() } {
+                let description: Retained<NSString> =
+                    unsafe { msg_send![error, localizedDescription] };
+                log::error!("Spotlight {} failed: {}", action, description);
+            }
+        })
+    }
+
+    fn searchable_item(item: &SpotlightItem, content_type: &AnyObject) -> Retained<AnyObject> {
+        unsafe {
+            let attributes: Retained<AnyObject> =
+                msg_send![class!(CSSearchableItemAttributeSet), alloc];
+            let attributes: Retained<AnyObject> =
+                msg_send![attributes, initWithContentType: content_type];
+            let _: () = msg_send![&*attributes, setTitle: &*NSString::from_str(&item.title)];
+            let _: () = msg_send![
+                &*attributes,
+                setContentDescription: &*NSString::from_str(&item.description)
+            ];
+            let keywords: Vec<Retained<NSString>> = item
+                .keywords
+                .iter()
+                .map(|keyword| NSString::from_str(keyword))
+                .collect();
+            let keywords = NSArray::from_retained_slice(&keywords);
+            let _: () = msg_send![&*attributes, setKeywords: &*keywords];
+
+            let searchable: Retained<AnyObject> = msg_send![class!(CSSearchableItem), alloc];
+            msg_send![
+                searchable,
+                initWithUniqueIdentifier: &*NSString::from_str(&item.identifier),
+                domainIdentifier: &*NSString::from_str(DOMAIN),
+                attributeSet: &*attributes
+            ]
+        }
+    }
+
+    pub fn donate(items: &[SpotlightItem]) {
+        let content_type = content_type();
+        let items: Vec<Retained<AnyObject>> = items
+            .iter()
+            .map(|item| searchable_item(item, &content_type))
+            .collect();
+        let items = NSArray::from_retained_slice(&items);
+        let completion = report_errors("donation");
+        unsafe {
+            let _: () = msg_send![
+                &*searchable_index(),
+                indexSearchableItems: &*items,
+                completionHandler: &*completion
+            ];
+        }
+    }
+
+    pub fn remove(identifiers: &[String]) {
+        let identifiers: Vec<Retained<NSString>> = identifiers
+            .iter()
+            .map(|identifier| NSString::from_str(identifier))
+            .collect();
+        let identifiers = NSArray::from_retained_slice(&identifiers);
+        let completion = report_errors("removal");
+        unsafe {
+            let _: () = msg_send![
+                &*searchable_index(),
+                deleteSearchableItemsWithIdentifiers: &*identifiers,
+                completionHandler: &*completion
+            ];
+        }
+    }
+
+    pub fn remove_all() {
+        let completion = report_errors("reset");
+        unsafe {
+            let _: () = msg_send![
+                &*searchable_index(),
+                deleteAllSearchableItemsWithCompletionHandler: &*completion
+            ];
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod imp {
+    use super::SpotlightItem;
+
+    pub fn donate(_items: &[SpotlightItem]) {}
+    pub fn remove(_identifiers: &[String]) {}
+    pub fn remove_all() {}
+}
+
+/// Remember the app handle and pick up the current setting. Call once
+/// during app setup.
+pub fn register_app(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+    let enabled = super::settings::get_settings()
+        .map(|settings| settings.spotlight_indexing)
+        .unwrap_or(false);
+    ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// Donate the library when the setting is turned on, delete the donated
+/// index when it is turned off.
+pub fn apply_settings(settings: &StikSettings) {
+    let enabled = settings.spotlight_indexing;
+    if ENABLED.swap(enabled, Ordering::SeqCst) == enabled {
+        return;
+    }
+    if !enabled {
+        imp::remove_all();
+        return;
+    }
+    let Some(app) = APP.get().cloned() else {
+        return;
+    };
+    std::thread::spawn(move || {
+        if let Err(e) = donate_library(&app.state::<NoteIndex>()) {
+            log::error!("Spotlight reindex failed: {}", e);
+        }
+    });
+}
+
+/// Donate (or refresh) one note after it was saved and re-indexed.
+pub fn note_saved(index: &NoteIndex, path: &str) {
+    if !ENABLED.load(Ordering::SeqCst) {
+        return;
+    }
+    let Ok(root) = folders::get_stik_folder() else {
+        return;
+    };
+    match index.get(path).and_then(|entry| item_for(&entry, &root)) {
+        Some(item) => imp::donate(&[item]),
+        // Locked since the last donation, or not indexable at all.
+        None => {
+            if let Some(identifier) = identifier_for(path, &root) {
+                imp::remove(&[identifier]);
+            }
+        }
+    }
+}
+
+pub fn note_removed(path: &str) {
+    notes_removed(&[path.to_string()]);
+}
+
+/// Drop several deleted notes in one call.
+pub fn notes_removed(paths: &[String]) {
+    if !ENABLED.load(Ordering::SeqCst) || paths.is_empty() {
+        return;
+    }
+    let Ok(root) = folders::get_stik_folder() else {
+        return;
+    };
+    let identifiers: Vec<String> = paths
+        .iter()
+        .filter_map(|path| identifier_for(path, &root))
+        .collect();
+    if !identifiers.is_empty() {
+        imp::remove(&identifiers);
+    }
+}
+
+/// Drop every note of a folder that is about to leave the index.
+pub fn folder_removed(index: &NoteIndex, folder: &str) {
+    if !ENABLED.load(Ordering::SeqCst) {
+        return;
+    }
+    let (Ok(root), Ok(entries)) = (folders::get_stik_folder(), index.list(Some(folder))) else {
+        return;
+    };
+    let identifiers: Vec<String> = entries
+        .iter()
+        .filter_map(|entry| identifier_for(&entry.path, &root))
+        .collect();
+    if !identifiers.is_empty() {
+        imp::remove(&identifiers);
+    }
+}
+
+/// The identifier is the note's link, so a move is a removal plus a donation.
+pub fn note_moved(index: &NoteIndex, old_path: &str, new_path: &str) {
+    note_removed(old_path);
+    note_saved(index, new_path);
+}
+
+fn donate_library(index: &NoteIndex) -> Result<usize, String> {
+    let root = folders::get_stik_folder()?;
+    let items: Vec<SpotlightItem> = index
+        .list(None)?
+        .iter()
+        .filter_map(|entry| item_for(entry, &root))
+        .collect();
+    for batch in items.chunks(DONATION_BATCH) {
+        imp::donate(batch);
+    }
+    Ok(items.len())
+}
+
+/// Donate every note in the library. Returns how many were donated.
+#[tauri::command]
+pub fn reindex_spotlight(index: State<'_, NoteIndex>) -> Result<usize, String> {
+    if !ENABLED.load(Ordering::SeqCst) {
+        return Err("Spotlight indexing is turned off in Settings".to_string());
+    }
+    donate_library(&index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn entry(path: &str, locked: bool) -> NoteEntry {
+        NoteEntry {
+            path: path.to_string(),
+            filename: "20260206-101530-quarterly-plan-a1b2.md".to_string(),
+            folder: "Work".to_string(),
+            title: "Quarterly plan".to_string(),
+            preview: "Ship the sync rewrite".to_string(),
+            created: "20260206-101530".to_string(),
+            content_len: 42,
+            locked,
+            word_count: 4,
+            reading_minutes: 1,
+            language: "en".to_string(),
+            truncated_in_index: false,
+            id: None,
+            pinned: false,
+            created_date: None,
+            open_tasks: 0,
+            done_tasks: 0,
+        }
+    }
+
+    #[test]
+    fn items_are_identified_by_their_open_link() {
+        let root = PathBuf::from("/Users/me/Documents/Stik");
+        let item = item_for(
+            &entry("/Users/me/Documents/Stik/Work/my note.md", false),
+            &root,
+        )
+        .expect("donatable");
+        assert_eq!(item.identifier, "stik://open?path=Work%2Fmy+note.md");
+        assert_eq!(item.title, "Quarterly plan");
+        assert_eq!(item.keywords, vec!["Work".to_string()]);
+    }
+
+    #[test]
+    fn locked_and_outside_notes_are_not_donated() {
+        let root = PathBuf::from("/Users/me/Documents/Stik");
+        assert!(item_for(&entry("/Users/me/Documents/Stik/Work/a.md", true), &root).is_none());
+        assert!(item_for(&entry("/Users/me/Desktop/a.md", false), &root).is_none());
+    }
+}
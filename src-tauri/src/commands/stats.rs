@@ -1,58 +1,480 @@
-use chrono::{Duration, Local, NaiveDate};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, Timelike};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use std::fs;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Instant;
+use tauri::{AppHandle, Manager, State};
 
+use super::config_paths;
+use super::digest::is_digest_filename;
+use super::filename_template;
 use super::folders::get_stik_folder;
+use super::index::{NoteEntry, NoteIndex};
+use super::settings;
 use super::versioning;
+use crate::state::AppState;
+
+const DEFAULT_HEATMAP_DAYS: u32 = 365;
+const MAX_HEATMAP_DAYS: u32 = 730;
+const TRAY_STREAK_DEBOUNCE_SECONDS: u64 = 60;
+const CSV_HEADER: &str = "created,folder,word_count,char_count";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CaptureStats {
     pub capture_streak_days: u32,
+    /// Best streak ever seen; never decreases, even if old notes are deleted.
+    #[serde(default)]
+    pub longest_streak_days: u32,
+    #[serde(default)]
+    pub longest_streak_ended_on: Option<String>,
+    #[serde(default)]
+    pub total_capture_days: u32,
     pub last_computed_at: String,
+    /// Start date of the streak the "new longest streak" notification fired for.
+    #[serde(default)]
+    pub record_notified_for: Option<String>,
+    /// Grace days spent per month (`YYYY-MM`), including those of streaks
+    /// that have since ended.
+    #[serde(default)]
+    pub grace_days_used: BTreeMap<String, u32>,
+    /// Missed days (`YYYY-MM-DD`) the current streak was carried over.
+    /// Already paid for, so recomputing the streak doesn't spend them again.
+    #[serde(default)]
+    pub streak_grace_days: Vec<String>,
+    /// No capture since the streak's last day; grace is keeping it alive.
+    #[serde(default)]
+    pub streak_on_grace: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CaptureStreakStatus {
     pub days: u32,
     pub label: String,
+    #[serde(default)]
+    pub on_grace: bool,
 }
 
-pub fn calculate_and_persist_capture_streak() -> Result<u32, String> {
-    let note_dates = collect_note_dates()?;
-    let today = Local::now().date_naive();
-    let streak = compute_capture_streak_from_dates(&note_dates, today);
+#[derive(Debug, Clone, Serialize)]
+pub struct HeatmapDay {
+    pub date: String,
+    pub count: u32,
+}
 
-    let stats = CaptureStats {
-        capture_streak_days: streak,
-        last_computed_at: Local::now().to_rfc3339(),
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureHeatmap {
+    /// Oldest first, ending today.
+    pub days: Vec<HeatmapDay>,
+    /// Notes per weekday within the window, Monday first.
+    pub weekday_totals: [u32; 7],
+    pub total_notes: u32,
+    pub current_streak_days: u32,
+    pub longest_streak_days: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivityBreakdown {
+    /// Notes per hour of day, 0–23.
+    pub hour_totals: [u32; 24],
+    /// Notes per weekday, Monday first.
+    pub weekday_totals: [u32; 7],
+    /// Notes whose filename has no parseable time. They still count toward
+    /// `weekday_totals` when the date parses.
+    pub unknown_time: u32,
+    pub total_notes: u32,
+    pub most_active_hour: Option<u32>,
+    /// 0 is Monday, matching `weekday_totals`.
+    pub most_active_weekday: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StatsExportFormat {
+    Csv,
+    Json,
+}
+
+impl StatsExportFormat {
+    fn parse(format: &str) -> Result<Self, String> {
+        match format.trim().to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            other => Err(format!("Unsupported stats export format: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct NoteStatsRow {
+    created: String,
+    folder: String,
+    word_count: usize,
+    char_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct StatsExportSummary {
+    total_notes: usize,
+    total_words: usize,
+    total_chars: usize,
+    total_capture_days: u32,
+    current_streak_days: u32,
+    longest_streak_days: u32,
+}
+
+struct TrayStreakRefresh {
+    last_run: Option<Instant>,
+    pending: bool,
+}
+
+static TRAY_STREAK_REFRESH: Mutex<TrayStreakRefresh> = Mutex::new(TrayStreakRefresh {
+    last_run: None,
+    pending: false,
+});
+static MIDNIGHT_TIMER_RUNNING: OnceLock<()> = OnceLock::new();
+
+/// Notes captured per day.
+type DayCounts = BTreeMap<NaiveDate, u32>;
+
+/// Day counts from the index build they were computed against, so tray
+/// refreshes don't recount the whole library each time.
+struct CachedDayCounts {
+    built_at: Instant,
+    counts: DayCounts,
+}
+
+static DAY_COUNTS: Mutex<Option<CachedDayCounts>> = Mutex::new(None);
+
+/// Drop the memoized day counts. Call when a note is created or deleted;
+/// an index rebuild invalidates them on its own.
+pub fn invalidate_day_counts() {
+    *DAY_COUNTS.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Pass the NoteIndex when available; without a built index this falls
+/// back to walking the notes folder.
+pub fn calculate_and_persist_capture_streak(index: Option<&NoteIndex>) -> Result<u32, String> {
+    Ok(refresh_capture_stats(index)?.capture_streak_days)
+}
+
+/// The tray's streak label, e.g. "Streak: 4 days (on grace)".
+pub fn capture_streak_label(index: Option<&NoteIndex>) -> Result<String, String> {
+    let stats = refresh_capture_stats(index)?;
+    Ok(format_capture_streak_label(
+        stats.capture_streak_days,
+        stats.streak_on_grace,
+    ))
+}
+
+/// Recompute the streak and update the tray's streak item, if the tray
+/// has registered one.
+pub fn refresh_tray_streak(app: &AppHandle) {
+    let index = app.state::<NoteIndex>();
+    let label = match capture_streak_label(Some(index.inner())) {
+        Ok(label) => label,
+        Err(e) => {
+            log::error!("Failed to refresh capture streak: {}", e);
+            return;
+        }
     };
-    save_stats_to_file(&stats)?;
 
-    Ok(streak)
+    let state = app.state::<AppState>();
+    let item = state
+        .tray_streak_item
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    if let Some(item) = item {
+        if let Err(e) = item.set_text(label) {
+            log::error!("Failed to update tray streak: {}", e);
+        }
+    }
+}
+
+/// Refresh the tray streak at most once per minute. A call inside the
+/// window schedules one trailing refresh so the latest save still counts.
+pub fn refresh_tray_streak_debounced(app: &AppHandle) {
+    let debounce = std::time::Duration::from_secs(TRAY_STREAK_DEBOUNCE_SECONDS);
+    let mut refresh = TRAY_STREAK_REFRESH
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if refresh.pending {
+        return;
+    }
+
+    let wait = refresh
+        .last_run
+        .map(|last| debounce.saturating_sub(last.elapsed()))
+        .unwrap_or_default();
+    refresh.pending = true;
+    drop(refresh);
+
+    let app = app.clone();
+    let spawned = thread::Builder::new()
+        .name("stik-tray-streak".to_string())
+        .spawn(move || {
+            thread::sleep(wait);
+            {
+                let mut refresh = TRAY_STREAK_REFRESH
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner());
+                refresh.pending = false;
+                refresh.last_run = Some(Instant::now());
+            }
+            refresh_tray_streak(&app);
+        });
+    if spawned.is_err() {
+        TRAY_STREAK_REFRESH
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pending = false;
+    }
 }
 
-pub fn format_capture_streak_label(days: u32) -> String {
-    if days == 1 {
+/// Refresh the tray streak shortly after each local midnight, when
+/// yesterday's streak may have lapsed. Call once during tray setup.
+pub fn start_midnight_streak_timer(app: AppHandle) {
+    if MIDNIGHT_TIMER_RUNNING.set(()).is_err() {
+        return;
+    }
+
+    thread::Builder::new()
+        .name("stik-streak-midnight".to_string())
+        .spawn(move || loop {
+            thread::sleep(until_next_midnight(Local::now().naive_local()));
+            refresh_tray_streak(&app);
+        })
+        .ok();
+}
+
+/// Time until a few seconds past the next local midnight.
+fn until_next_midnight(now: NaiveDateTime) -> std::time::Duration {
+    let next = (now.date() + Duration::days(1))
+        .and_hms_opt(0, 0, 5)
+        .unwrap_or(now + Duration::days(1));
+    (next - now)
+        .to_std()
+        .unwrap_or(std::time::Duration::from_secs(60))
+}
+
+/// Recompute streaks from the notes on disk, merge with the persisted
+/// record, announce a new longest streak once, and save.
+fn refresh_capture_stats(index: Option<&NoteIndex>) -> Result<CaptureStats, String> {
+    let day_counts = note_day_counts(index)?;
+    let today = Local::now().date_naive();
+    let previous = load_stats_from_file().unwrap_or_else(|e| {
+        log::error!("Failed to load capture stats: {}", e);
+        None
+    });
+
+    let grace_per_month = settings::get_settings()
+        .map(|settings| settings.streak_grace_days_per_month)
+        .unwrap_or(0);
+
+    let (mut stats, new_record_start) =
+        merge_capture_stats(previous.as_ref(), &day_counts, today, grace_per_month);
+
+    if let Some(start) = new_record_start {
+        // Left unannounced, the record is offered again on the next refresh.
+        if !super::focus_mode::should_suppress_interruptions() {
+            let body = format!(
+                "{} days in a row — your best yet.",
+                stats.longest_streak_days
+            );
+            match super::macos_notify::show("Stik", "New longest streak", &body) {
+                Ok(()) => stats.record_notified_for = Some(start.format("%Y-%m-%d").to_string()),
+                Err(e) => log::error!("Failed to show streak notification: {}", e),
+            }
+        }
+    }
+
+    save_stats_to_file(&stats)?;
+    Ok(stats)
+}
+
+pub fn format_capture_streak_label(days: u32, on_grace: bool) -> String {
+    let label = if days == 1 {
         "Streak: 1 day".to_string()
     } else {
         format!("Streak: {} days", days)
+    };
+    if on_grace {
+        format!("{} (on grace)", label)
+    } else {
+        label
     }
 }
 
 #[tauri::command]
-pub fn get_capture_streak() -> Result<CaptureStreakStatus, String> {
-    let days = calculate_and_persist_capture_streak()?;
+pub fn get_capture_stats(index: State<'_, NoteIndex>) -> Result<CaptureStats, String> {
+    refresh_capture_stats(Some(index.inner()))
+}
+
+/// When notes get captured: counts by hour-of-day and weekday, parsed from
+/// the `YYYYMMDD-HHMMSS` filename prefix.
+#[tauri::command]
+pub fn get_activity_breakdown(index: State<'_, NoteIndex>) -> Result<ActivityBreakdown, String> {
+    let filenames = collect_note_filenames(Some(index.inner()))?;
+    Ok(build_activity_breakdown(&filenames))
+}
+
+/// Write one row per note (created, folder, word and character count) plus a
+/// streak/totals summary to `path` as CSV or JSON. Returns the row count.
+/// Refuses to replace an existing file unless `overwrite` is true.
+#[tauri::command]
+pub async fn export_stats(
+    app: tauri::AppHandle,
+    path: String,
+    format: String,
+    overwrite: Option<bool>,
+) -> Result<usize, String> {
+    let format = StatsExportFormat::parse(&format)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let index = app.state::<NoteIndex>();
+        let mut entries: Vec<NoteEntry> = index
+            .list(None)?
+            .into_iter()
+            .filter(|entry| !is_digest_filename(&entry.filename))
+            .collect();
+        entries.sort_by_key(entry_created_at);
+
+        let mut options = OpenOptions::new();
+        options.write(true);
+        if overwrite.unwrap_or(false) {
+            options.create(true).truncate(true);
+        } else {
+            options.create_new(true);
+        }
+        let file = options.open(&path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::AlreadyExists {
+                format!("{} already exists", path)
+            } else {
+                format!("Failed to create stats export: {}", e)
+            }
+        })?;
+
+        let mut writer = BufWriter::new(file);
+        write_stats_export(&mut writer, format, &entries, Local::now().date_naive())
+            .map_err(|e| format!("Failed to write stats export: {}", e))?;
+        writer
+            .flush()
+            .map_err(|e| format!("Failed to write stats export: {}", e))?;
+
+        Ok(entries.len())
+    })
+    .await
+    .map_err(|e| format!("Stats export failed: {}", e))?
+}
+
+#[tauri::command]
+pub fn get_capture_streak(index: State<'_, NoteIndex>) -> Result<CaptureStreakStatus, String> {
+    let stats = refresh_capture_stats(Some(index.inner()))?;
     Ok(CaptureStreakStatus {
-        days,
-        label: format_capture_streak_label(days),
+        days: stats.capture_streak_days,
+        label: format_capture_streak_label(stats.capture_streak_days, stats.streak_on_grace),
+        on_grace: stats.streak_on_grace,
     })
 }
 
-fn collect_note_dates() -> Result<Vec<NaiveDate>, String> {
+/// Heatmap of notes created per day over the last `days` days (0 means a
+/// year), with weekday totals and streaks.
+#[tauri::command]
+pub fn get_capture_heatmap(
+    days: u32,
+    index: State<'_, NoteIndex>,
+) -> Result<CaptureHeatmap, String> {
+    let day_counts = note_day_counts(Some(index.inner()))?;
+    let days = if days == 0 {
+        DEFAULT_HEATMAP_DAYS
+    } else {
+        days.min(MAX_HEATMAP_DAYS)
+    };
+    Ok(build_capture_heatmap(
+        &day_counts,
+        Local::now().date_naive(),
+        days,
+    ))
+}
+
+/// Notes captured per day, from the NoteIndex. Memoized until a note is
+/// saved or deleted or the index is rebuilt. Only while the index is
+/// unbuilt or empty is the notes folder walked instead.
+fn note_day_counts(index: Option<&NoteIndex>) -> Result<DayCounts, String> {
+    let built_at = index.and_then(NoteIndex::built_at);
+    let mut cache = DAY_COUNTS.lock().unwrap_or_else(|e| e.into_inner());
+    if let (Some(cached), Some(built_at)) = (cache.as_ref(), built_at) {
+        if cached.built_at == built_at {
+            return Ok(cached.counts.clone());
+        }
+    }
+
+    let entries = index
+        .and_then(|index| index.snapshot())
+        .filter(|entries| !entries.is_empty());
+    let (Some(entries), Some(built_at)) = (entries, built_at) else {
+        return Ok(day_counts(walk_note_dates()?));
+    };
+    let counts = day_counts_from_entries(&entries);
+    *cache = Some(CachedDayCounts {
+        built_at,
+        counts: counts.clone(),
+    });
+    Ok(counts)
+}
+
+fn day_counts(dates: impl IntoIterator<Item = NaiveDate>) -> DayCounts {
+    let mut counts = DayCounts::new();
+    for date in dates {
+        *counts.entry(date).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn day_counts_from_entries(entries: &[NoteEntry]) -> DayCounts {
+    day_counts(entries.iter().filter_map(note_entry_date))
+}
+
+/// Creation date of every note on disk, for when there is no index yet.
+/// Dates come from the filename, falling back to the file mtime.
+fn walk_note_dates() -> Result<Vec<NaiveDate>, String> {
+    let dates = note_file_paths()?
+        .iter()
+        .filter_map(|path| {
+            let filename = path.file_name()?.to_str()?;
+            if is_digest_filename(filename) {
+                return None;
+            }
+            parse_date_from_filename(filename).or_else(|| file_mtime_date(path))
+        })
+        .collect();
+
+    Ok(dates)
+}
+
+/// Filename of every captured note (digests excluded), from the NoteIndex
+/// when built, otherwise from a walk of the notes folder.
+fn collect_note_filenames(index: Option<&NoteIndex>) -> Result<Vec<String>, String> {
+    let filenames = match index.and_then(|index| index.snapshot()) {
+        Some(entries) => entries.into_iter().map(|entry| entry.filename).collect(),
+        None => note_file_paths()?
+            .iter()
+            .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+            .collect::<Vec<String>>(),
+    };
+
+    Ok(filenames
+        .into_iter()
+        .filter(|filename| !is_digest_filename(filename))
+        .collect())
+}
+
+/// Every `.md` file one level below the notes folder.
+fn note_file_paths() -> Result<Vec<PathBuf>, String> {
     let stik_folder = get_stik_folder()?;
-    let mut dates = Vec::new();
+    let mut paths = Vec::new();
 
     let folders: Vec<PathBuf> = fs::read_dir(&stik_folder)
         .map_err(|e| e.to_string())?
@@ -65,74 +487,430 @@ fn collect_note_dates() -> Result<Vec<NaiveDate>, String> {
         if let Ok(entries) = fs::read_dir(folder) {
             for entry in entries.filter_map(|e| e.ok()) {
                 let path = entry.path();
-                if !path.extension().is_some_and(|ext| ext == "md") {
-                    continue;
-                }
-
-                if let Some(filename) = path.file_name().and_then(|name| name.to_str()) {
-                    if let Some(date) = parse_date_from_filename(filename) {
-                        dates.push(date);
-                    }
+                if path.extension().is_some_and(|ext| ext == "md") {
+                    paths.push(path);
                 }
             }
         }
     }
 
-    Ok(dates)
+    Ok(paths)
 }
 
-fn get_stats_path() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    let stik_config = home.join(".stik");
-    fs::create_dir_all(&stik_config).map_err(|e| e.to_string())?;
-    Ok(stik_config.join("stats.json"))
+fn note_entry_date(entry: &NoteEntry) -> Option<NaiveDate> {
+    if is_digest_filename(&entry.filename) {
+        return None;
+    }
+    entry.created_date
+}
+
+fn file_mtime_date(path: &Path) -> Option<NaiveDate> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    let modified: DateTime<Local> = modified.into();
+    Some(modified.date_naive())
+}
+
+fn load_stats_from_file() -> Result<Option<CaptureStats>, String> {
+    let path = config_paths::config_file("stats.json")?;
+    versioning::load_versioned(&path)
 }
 
 fn save_stats_to_file(stats: &CaptureStats) -> Result<(), String> {
-    let path = get_stats_path()?;
+    let path = config_paths::writable_config_file("stats.json")?;
     versioning::save_versioned(&path, stats)
 }
 
-fn parse_date_from_filename(filename: &str) -> Option<NaiveDate> {
-    let date_segment = filename.split('-').next()?;
-    if date_segment.len() != 8 {
-        return None;
+pub(crate) fn parse_date_from_filename(filename: &str) -> Option<NaiveDate> {
+    filename_template::created_from_filename(filename).map(|created| created.date)
+}
+
+/// Full capture timestamp from the filename. None when the name carries no
+/// valid time, even if the date parses.
+pub(crate) fn parse_timestamp_from_filename(filename: &str) -> Option<NaiveDateTime> {
+    filename_template::created_from_filename(filename)?.timestamp()
+}
+
+/// Grace days a streak may spend on missed days.
+struct GraceBudget<'a> {
+    per_month: u32,
+    /// Already spent per month, as in `CaptureStats::grace_days_used`.
+    used: &'a BTreeMap<String, u32>,
+    /// Days bridged on an earlier run, free to bridge again.
+    paid: &'a [String],
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct StreakWalk {
+    /// Days with a capture; bridged days don't count.
+    days: u32,
+    started_on: Option<NaiveDate>,
+    /// Missed days inside the streak, newest first.
+    grace_days: Vec<NaiveDate>,
+    /// The days since the last capture were bridged.
+    on_grace: bool,
+}
+
+fn month_key(date: NaiveDate) -> String {
+    date.format("%Y-%m").to_string()
+}
+
+/// Walk back from today over capture days. A run of missed days is
+/// bridged only if the budget covers all of it; each day is charged to
+/// its own month. Today doesn't count as missed until it's over.
+fn walk_capture_streak(counts: &DayCounts, today: NaiveDate, budget: &GraceBudget) -> StreakWalk {
+    let mut walk = StreakWalk::default();
+    let Some(&first) = counts.keys().next() else {
+        return walk;
+    };
+
+    let mut spent: BTreeMap<String, u32> = BTreeMap::new();
+    let mut cursor = today;
+    if !counts.contains_key(&today) {
+        cursor -= Duration::days(1);
+    }
+    while cursor >= first {
+        if counts.contains_key(&cursor) {
+            walk.days += 1;
+            walk.started_on = Some(cursor);
+            cursor -= Duration::days(1);
+            continue;
+        }
+
+        let mut gap = Vec::new();
+        let mut gap_spent = spent.clone();
+        while cursor >= first && !counts.contains_key(&cursor) {
+            let key = cursor.format("%Y-%m-%d").to_string();
+            if !budget.paid.contains(&key) {
+                let month = month_key(cursor);
+                let used = budget.used.get(&month).copied().unwrap_or(0);
+                let month_spent = gap_spent.entry(month).or_default();
+                if used + *month_spent >= budget.per_month {
+                    return walk;
+                }
+                *month_spent += 1;
+            }
+            gap.push(cursor);
+            cursor -= Duration::days(1);
+        }
+        if cursor < first {
+            break;
+        }
+        if walk.days == 0 {
+            walk.on_grace = true;
+        }
+        spent = gap_spent;
+        walk.grace_days.extend(gap);
     }
+    walk
+}
 
-    NaiveDate::parse_from_str(date_segment, "%Y%m%d").ok()
+fn compute_capture_streak(counts: &DayCounts, today: NaiveDate) -> u32 {
+    let no_grace = GraceBudget {
+        per_month: 0,
+        used: &BTreeMap::new(),
+        paid: &[],
+    };
+    walk_capture_streak(counts, today, &no_grace).days
 }
 
-fn compute_capture_streak_from_dates(dates: &[NaiveDate], today: NaiveDate) -> u32 {
-    let unique_dates: HashSet<NaiveDate> = dates.iter().copied().collect();
+/// Longest run of consecutive capture days and the day it ended on.
+fn compute_longest_streak(counts: &DayCounts) -> (u32, Option<NaiveDate>) {
+    let mut longest = (0u32, None);
+    let mut run = 0u32;
+    let mut previous: Option<NaiveDate> = None;
+    for &date in counts.keys() {
+        run = match previous {
+            Some(prev) if date - prev == Duration::days(1) => run + 1,
+            _ => 1,
+        };
+        if run >= longest.0 {
+            longest = (run, Some(date));
+        }
+        previous = Some(date);
+    }
+    longest
+}
 
-    if unique_dates.is_empty() {
-        return 0;
+/// Fold freshly computed streaks into the persisted stats. Returns the new
+/// stats and, when the current streak just became a new record that hasn't
+/// been announced yet, the date that streak started.
+fn merge_capture_stats(
+    previous: Option<&CaptureStats>,
+    counts: &DayCounts,
+    today: NaiveDate,
+    grace_per_month: u32,
+) -> (CaptureStats, Option<NaiveDate>) {
+    let mut grace_days_used = previous.map_or_else(BTreeMap::new, |p| p.grace_days_used.clone());
+    let paid = previous.map_or(&[][..], |p| p.streak_grace_days.as_slice());
+    let walk = walk_capture_streak(
+        counts,
+        today,
+        &GraceBudget {
+            per_month: grace_per_month,
+            used: &grace_days_used,
+            paid,
+        },
+    );
+    let streak_grace_days: Vec<String> = walk
+        .grace_days
+        .iter()
+        .map(|day| day.format("%Y-%m-%d").to_string())
+        .collect();
+    for (day, key) in walk.grace_days.iter().zip(&streak_grace_days) {
+        if !paid.contains(key) {
+            *grace_days_used.entry(month_key(*day)).or_default() += 1;
+        }
     }
+    let streak = walk.days;
+    let (computed_longest, computed_end) = compute_longest_streak(counts);
+    let previous_longest = previous.map_or(0, |p| p.longest_streak_days);
 
-    let mut cursor = if unique_dates.contains(&today) {
-        today
+    let (longest, ended_on) = if computed_longest > previous_longest {
+        (
+            computed_longest,
+            computed_end.map(|d| d.format("%Y-%m-%d").to_string()),
+        )
     } else {
-        let yesterday = today - Duration::days(1);
-        if unique_dates.contains(&yesterday) {
-            yesterday
-        } else {
-            return 0;
+        (
+            previous_longest,
+            previous.and_then(|p| p.longest_streak_ended_on.clone()),
+        )
+    };
+
+    let record_notified_for = previous.and_then(|p| p.record_notified_for.clone());
+
+    // The first run has nothing to beat — don't announce an old streak.
+    let new_record_start = walk
+        .started_on
+        .filter(|_| streak > previous_longest && previous_longest > 0)
+        .filter(|start| {
+            record_notified_for.as_deref() != Some(start.format("%Y-%m-%d").to_string().as_str())
+        });
+
+    let stats = CaptureStats {
+        capture_streak_days: streak,
+        longest_streak_days: longest,
+        longest_streak_ended_on: ended_on,
+        total_capture_days: counts.len() as u32,
+        last_computed_at: Local::now().to_rfc3339(),
+        record_notified_for,
+        grace_days_used,
+        streak_grace_days,
+        streak_on_grace: walk.on_grace,
+    };
+    (stats, new_record_start)
+}
+
+fn build_capture_heatmap(counts: &DayCounts, today: NaiveDate, days: u32) -> CaptureHeatmap {
+    let start = today - Duration::days(i64::from(days) - 1);
+
+    let mut weekday_totals = [0u32; 7];
+    let mut total_notes = 0u32;
+    let heatmap_days = (0..days)
+        .map(|offset| {
+            let date = start + Duration::days(i64::from(offset));
+            let count = counts.get(&date).copied().unwrap_or(0);
+            weekday_totals[date.weekday().num_days_from_monday() as usize] += count;
+            total_notes += count;
+            HeatmapDay {
+                date: date.format("%Y-%m-%d").to_string(),
+                count,
+            }
+        })
+        .collect();
+
+    CaptureHeatmap {
+        days: heatmap_days,
+        weekday_totals,
+        total_notes,
+        current_streak_days: compute_capture_streak(counts, today),
+        longest_streak_days: compute_longest_streak(counts).0,
+    }
+}
+
+fn build_activity_breakdown(filenames: &[String]) -> ActivityBreakdown {
+    let mut hour_totals = [0u32; 24];
+    let mut weekday_totals = [0u32; 7];
+    let mut unknown_time = 0u32;
+
+    for filename in filenames {
+        match parse_timestamp_from_filename(filename) {
+            Some(timestamp) => {
+                hour_totals[timestamp.hour() as usize] += 1;
+                weekday_totals[timestamp.weekday().num_days_from_monday() as usize] += 1;
+            }
+            None => {
+                unknown_time += 1;
+                if let Some(date) = parse_date_from_filename(filename) {
+                    weekday_totals[date.weekday().num_days_from_monday() as usize] += 1;
+                }
+            }
         }
+    }
+
+    ActivityBreakdown {
+        hour_totals,
+        weekday_totals,
+        unknown_time,
+        total_notes: filenames.len() as u32,
+        most_active_hour: busiest_bucket(&hour_totals),
+        most_active_weekday: busiest_bucket(&weekday_totals),
+    }
+}
+
+/// Index of the largest non-zero count; ties go to the earliest bucket.
+fn busiest_bucket(totals: &[u32]) -> Option<u32> {
+    totals
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(&a.0)))
+        .map(|(bucket, _)| bucket as u32)
+}
+
+/// Capture time from the filename, falling back to the indexed mtime. A
+/// name carrying only a date (daily notes) keeps that day: the mtime only
+/// supplies the time when it falls on it, since such notes are edited for
+/// days after they are created.
+fn entry_created_at(entry: &NoteEntry) -> Option<NaiveDateTime> {
+    let indexed = NaiveDateTime::parse_from_str(&entry.created, "%Y%m%d-%H%M%S").ok();
+    let Some(created) = filename_template::created_from_filename(&entry.filename) else {
+        return indexed;
     };
+    created.timestamp().or_else(|| {
+        indexed
+            .filter(|at| at.date() == created.date)
+            .or_else(|| created.date.and_hms_opt(0, 0, 0))
+    })
+}
 
-    let mut streak = 0u32;
-    while unique_dates.contains(&cursor) {
-        streak += 1;
-        cursor -= Duration::days(1);
+/// Rows are written as they are built so large libraries never sit in
+/// memory as one string. Only notes longer than the indexed preview are
+/// read from disk for their word count; locked notes report zero.
+fn write_stats_export<W: Write>(
+    writer: &mut W,
+    format: StatsExportFormat,
+    entries: &[NoteEntry],
+    today: NaiveDate,
+) -> std::io::Result<()> {
+    let mut total_words = 0usize;
+    let mut total_chars = 0usize;
+
+    match format {
+        StatsExportFormat::Csv => writeln!(writer, "{}", CSV_HEADER)?,
+        StatsExportFormat::Json => write!(writer, "{{\"notes\":[")?,
+    }
+
+    for (i, entry) in entries.iter().enumerate() {
+        let created_at = entry_created_at(entry);
+
+        let row = NoteStatsRow {
+            created: created_at
+                .map(|t| t.format("%Y-%m-%dT%H:%M:%S").to_string())
+                .unwrap_or_default(),
+            folder: entry.folder.clone(),
+            word_count: entry_word_count(entry),
+            char_count: entry.content_len,
+        };
+        total_words += row.word_count;
+        total_chars += row.char_count;
+
+        match format {
+            StatsExportFormat::Csv => writeln!(
+                writer,
+                "{},{},{},{}",
+                row.created,
+                csv_field(&row.folder),
+                row.word_count,
+                row.char_count
+            )?,
+            StatsExportFormat::Json => {
+                if i > 0 {
+                    write!(writer, ",")?;
+                }
+                serde_json::to_writer(&mut *writer, &row)?;
+            }
+        }
+    }
+
+    let day_counts = day_counts_from_entries(entries);
+    let summary = StatsExportSummary {
+        total_notes: entries.len(),
+        total_words,
+        total_chars,
+        total_capture_days: day_counts.len() as u32,
+        current_streak_days: compute_capture_streak(&day_counts, today),
+        longest_streak_days: compute_longest_streak(&day_counts).0,
+    };
+
+    match format {
+        StatsExportFormat::Csv => {
+            writeln!(writer)?;
+            writeln!(writer, "metric,value")?;
+            writeln!(writer, "total_notes,{}", summary.total_notes)?;
+            writeln!(writer, "total_words,{}", summary.total_words)?;
+            writeln!(writer, "total_chars,{}", summary.total_chars)?;
+            writeln!(writer, "total_capture_days,{}", summary.total_capture_days)?;
+            writeln!(
+                writer,
+                "current_streak_days,{}",
+                summary.current_streak_days
+            )?;
+            writeln!(
+                writer,
+                "longest_streak_days,{}",
+                summary.longest_streak_days
+            )?;
+        }
+        StatsExportFormat::Json => {
+            write!(writer, "],\"summary\":")?;
+            serde_json::to_writer(&mut *writer, &summary)?;
+            writeln!(writer, "}}")?;
+        }
     }
 
-    streak
+    Ok(())
+}
+
+fn entry_word_count(entry: &NoteEntry) -> usize {
+    if entry.locked {
+        return 0;
+    }
+    // Short notes are indexed in full as their preview.
+    if entry.preview.len() >= entry.content_len {
+        return entry.preview.split_whitespace().count();
+    }
+    fs::read_to_string(&entry.path)
+        .map(|content| content.split_whitespace().count())
+        .unwrap_or(0)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Day counts as the index reports them for one note captured on each
+    /// of `dates`.
+    fn counts_for(dates: &[NaiveDate]) -> DayCounts {
+        let entries: Vec<NoteEntry> = dates
+            .iter()
+            .enumerate()
+            .map(|(i, date)| {
+                let filename = format!("{}-0900{:02}-note-ab12.md", date.format("%Y%m%d"), i);
+                index_entry(&filename, "Inbox", "note")
+            })
+            .collect();
+        day_counts_from_entries(&entries)
+    }
+
     #[test]
     fn parses_date_from_filename_prefix() {
         let date = parse_date_from_filename("20260206-101530-my-note.md");
@@ -148,7 +926,7 @@ mod tests {
             today - Duration::days(4),
         ];
 
-        let streak = compute_capture_streak_from_dates(&dates, today);
+        let streak = compute_capture_streak(&counts_for(&dates), today);
         assert_eq!(streak, 0);
     }
 
@@ -162,7 +940,7 @@ mod tests {
             today - Duration::days(5),
         ];
 
-        let streak = compute_capture_streak_from_dates(&dates, today);
+        let streak = compute_capture_streak(&counts_for(&dates), today);
         assert_eq!(streak, 3);
     }
 
@@ -176,7 +954,7 @@ mod tests {
             today - Duration::days(7),
         ];
 
-        let streak = compute_capture_streak_from_dates(&dates, today);
+        let streak = compute_capture_streak(&counts_for(&dates), today);
         assert_eq!(streak, 3);
     }
 
@@ -190,17 +968,352 @@ mod tests {
             today - Duration::days(1),
         ];
 
-        let streak = compute_capture_streak_from_dates(&dates, today);
+        let streak = compute_capture_streak(&counts_for(&dates), today);
         assert_eq!(streak, 2);
     }
 
     #[test]
     fn formats_streak_label_for_singular_day() {
-        assert_eq!(format_capture_streak_label(1), "Streak: 1 day");
+        assert_eq!(format_capture_streak_label(1, false), "Streak: 1 day");
     }
 
     #[test]
     fn formats_streak_label_for_plural_days() {
-        assert_eq!(format_capture_streak_label(5), "Streak: 5 days");
+        assert_eq!(format_capture_streak_label(5, false), "Streak: 5 days");
+        assert_eq!(
+            format_capture_streak_label(5, true),
+            "Streak: 5 days (on grace)"
+        );
+    }
+
+    #[test]
+    fn heatmap_counts_every_note_per_day_within_window() {
+        // 2026-02-06 is a Friday.
+        let today = NaiveDate::from_ymd_opt(2026, 2, 6).expect("valid date");
+        let dates = vec![
+            today,
+            today,
+            today - Duration::days(1),
+            today - Duration::days(10),
+        ];
+
+        let heatmap = build_capture_heatmap(&counts_for(&dates), today, 7);
+        assert_eq!(heatmap.days.len(), 7);
+        assert_eq!(heatmap.days[0].date, "2026-01-31");
+        assert_eq!(heatmap.days[6].count, 2);
+        assert_eq!(heatmap.days[5].count, 1);
+        assert_eq!(heatmap.total_notes, 3);
+        assert_eq!(heatmap.weekday_totals[4], 2);
+        assert_eq!(heatmap.weekday_totals[3], 1);
+        assert_eq!(heatmap.current_streak_days, 2);
+    }
+
+    #[test]
+    fn longest_streak_reports_run_and_end_date() {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).expect("valid date");
+        let mut dates: Vec<NaiveDate> = (0..4).map(|d| start + Duration::days(d)).collect();
+        dates.push(start + Duration::days(10));
+        dates.push(start + Duration::days(2));
+
+        let (longest, ended_on) = compute_longest_streak(&counts_for(&dates));
+        assert_eq!(longest, 4);
+        assert_eq!(ended_on, Some(start + Duration::days(3)));
+    }
+
+    fn previous_stats(longest: u32, notified_for: Option<&str>) -> CaptureStats {
+        CaptureStats {
+            capture_streak_days: 0,
+            longest_streak_days: longest,
+            longest_streak_ended_on: Some("2025-06-30".to_string()),
+            total_capture_days: longest,
+            last_computed_at: String::new(),
+            record_notified_for: notified_for.map(str::to_string),
+            grace_days_used: BTreeMap::new(),
+            streak_grace_days: vec![],
+            streak_on_grace: false,
+        }
+    }
+
+    #[test]
+    fn longest_streak_never_decreases() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 6).expect("valid date");
+        let dates = vec![today, today - Duration::days(1)];
+
+        let (stats, new_record) = merge_capture_stats(
+            Some(&previous_stats(47, None)),
+            &counts_for(&dates),
+            today,
+            0,
+        );
+        assert_eq!(stats.capture_streak_days, 2);
+        assert_eq!(stats.longest_streak_days, 47);
+        assert_eq!(stats.longest_streak_ended_on.as_deref(), Some("2025-06-30"));
+        assert_eq!(stats.total_capture_days, 2);
+        assert_eq!(new_record, None);
+    }
+
+    #[test]
+    fn new_record_is_announced_once_per_streak() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 6).expect("valid date");
+        let dates: Vec<NaiveDate> = (0..3).map(|d| today - Duration::days(d)).collect();
+        let start = today - Duration::days(2);
+
+        let (stats, new_record) = merge_capture_stats(
+            Some(&previous_stats(2, None)),
+            &counts_for(&dates),
+            today,
+            0,
+        );
+        assert_eq!(stats.longest_streak_days, 3);
+        assert_eq!(stats.longest_streak_ended_on.as_deref(), Some("2026-02-06"));
+        assert_eq!(new_record, Some(start));
+
+        let (_, repeat) = merge_capture_stats(
+            Some(&previous_stats(2, Some("2026-02-04"))),
+            &counts_for(&dates),
+            today,
+            0,
+        );
+        assert_eq!(repeat, None);
+
+        let (_, first_run) = merge_capture_stats(None, &counts_for(&dates), today, 0);
+        assert_eq!(first_run, None);
+    }
+
+    fn grace_stats(used: &[(&str, u32)], paid: &[&str]) -> CaptureStats {
+        CaptureStats {
+            grace_days_used: used
+                .iter()
+                .map(|(month, count)| (month.to_string(), *count))
+                .collect(),
+            streak_grace_days: paid.iter().map(|day| day.to_string()).collect(),
+            ..previous_stats(30, None)
+        }
+    }
+
+    /// Captures on 2026-02-01..=02-03 and 02-07..=02-09: a three-day gap.
+    fn vacation_dates() -> (Vec<NaiveDate>, NaiveDate) {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 2, d).expect("valid date");
+        (vec![day(1), day(2), day(3), day(7), day(8), day(9)], day(9))
+    }
+
+    #[test]
+    fn grace_bridges_a_gap_exactly_as_long_as_the_allowance() {
+        let (dates, today) = vacation_dates();
+        let (stats, _) = merge_capture_stats(None, &counts_for(&dates), today, 3);
+        assert_eq!(stats.capture_streak_days, 6);
+        assert_eq!(
+            stats.streak_grace_days,
+            vec!["2026-02-06", "2026-02-05", "2026-02-04"]
+        );
+        assert_eq!(stats.grace_days_used.get("2026-02"), Some(&3));
+        assert!(!stats.streak_on_grace);
+
+        // Recomputing the same streak doesn't spend the days again.
+        let (again, _) = merge_capture_stats(Some(&stats), &counts_for(&dates), today, 3);
+        assert_eq!(again.capture_streak_days, 6);
+        assert_eq!(again.grace_days_used.get("2026-02"), Some(&3));
+    }
+
+    #[test]
+    fn a_gap_one_day_longer_than_the_allowance_breaks_the_streak() {
+        let (dates, today) = vacation_dates();
+        let (stats, _) = merge_capture_stats(None, &counts_for(&dates), today, 2);
+        assert_eq!(stats.capture_streak_days, 3);
+        assert!(stats.streak_grace_days.is_empty());
+        assert!(stats.grace_days_used.is_empty());
+
+        // Grace already spent this month leaves too little for the gap.
+        let spent = grace_stats(&[("2026-02", 1)], &[]);
+        let (stats, _) = merge_capture_stats(Some(&spent), &counts_for(&dates), today, 3);
+        assert_eq!(stats.capture_streak_days, 3);
+        assert_eq!(stats.grace_days_used.get("2026-02"), Some(&1));
+    }
+
+    #[test]
+    fn grace_is_charged_to_the_month_of_each_missed_day() {
+        let day = |m, d| NaiveDate::from_ymd_opt(2026, m, d).expect("valid date");
+        // Missed 01-30, 01-31 and 02-01.
+        let dates = vec![day(1, 28), day(1, 29), day(2, 2)];
+        let counts = counts_for(&dates);
+        let (stats, _) = merge_capture_stats(None, &counts, day(2, 2), 2);
+        assert_eq!(stats.capture_streak_days, 3);
+        assert_eq!(stats.grace_days_used.get("2026-01"), Some(&2));
+        assert_eq!(stats.grace_days_used.get("2026-02"), Some(&1));
+
+        let (strict, _) = merge_capture_stats(None, &counts, day(2, 2), 1);
+        assert_eq!(strict.capture_streak_days, 1);
+    }
+
+    #[test]
+    fn days_since_the_last_capture_are_on_grace() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 2, d).expect("valid date");
+        let dates = vec![day(1), day(2), day(3)];
+        let (stats, _) = merge_capture_stats(None, &counts_for(&dates), day(6), 2);
+        assert_eq!(stats.capture_streak_days, 3);
+        assert!(stats.streak_on_grace);
+        assert_eq!(stats.grace_days_used.get("2026-02"), Some(&2));
+
+        // Paid days stay paid, and the next missed day has nothing left.
+        let (next_day, _) = merge_capture_stats(Some(&stats), &counts_for(&dates), day(7), 2);
+        assert_eq!(next_day.capture_streak_days, 0);
+        assert!(!next_day.streak_on_grace);
+        assert_eq!(next_day.grace_days_used.get("2026-02"), Some(&2));
+
+        // Today isn't missed yet, so no grace is needed before it ends.
+        let (today_open, _) = merge_capture_stats(None, &counts_for(&dates), day(4), 0);
+        assert_eq!(today_open.capture_streak_days, 3);
+        assert!(!today_open.streak_on_grace);
+    }
+
+    #[test]
+    fn parses_full_timestamp_only_when_time_segment_is_valid() {
+        let timestamp = parse_timestamp_from_filename("20260206-101530-my-note.md");
+        assert_eq!(
+            timestamp,
+            NaiveDate::from_ymd_opt(2026, 2, 6).and_then(|d| d.and_hms_opt(10, 15, 30))
+        );
+        assert_eq!(
+            parse_timestamp_from_filename("20260206-101530.md"),
+            timestamp
+        );
+        assert_eq!(parse_timestamp_from_filename("20260206-my-note.md"), None);
+        assert_eq!(
+            parse_timestamp_from_filename("20260206-256000-bad.md"),
+            None
+        );
+        assert_eq!(parse_timestamp_from_filename("meeting-notes.md"), None);
+    }
+
+    #[test]
+    fn activity_breakdown_buckets_unknown_times_separately() {
+        let filenames: Vec<String> = [
+            // Friday 2026-02-06
+            "20260206-091500-standup-ab12.md",
+            "20260206-094500-follow-up-cd34.md",
+            "20260206-220000-late-idea-ef56.md",
+            // Saturday 2026-02-07, no time segment
+            "20260207-groceries.md",
+            "grocery-list.md",
+        ]
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+
+        let breakdown = build_activity_breakdown(&filenames);
+        assert_eq!(breakdown.total_notes, 5);
+        assert_eq!(breakdown.unknown_time, 2);
+        assert_eq!(breakdown.hour_totals[0], 0);
+        assert_eq!(breakdown.hour_totals[9], 2);
+        assert_eq!(breakdown.hour_totals[22], 1);
+        assert_eq!(breakdown.weekday_totals[4], 3);
+        assert_eq!(breakdown.weekday_totals[5], 1);
+        assert_eq!(breakdown.most_active_hour, Some(9));
+        assert_eq!(breakdown.most_active_weekday, Some(4));
+    }
+
+    #[test]
+    fn empty_library_has_no_most_active_bucket() {
+        let breakdown = build_activity_breakdown(&[]);
+        assert_eq!(breakdown.total_notes, 0);
+        assert_eq!(breakdown.most_active_hour, None);
+        assert_eq!(breakdown.most_active_weekday, None);
+    }
+
+    fn index_entry(filename: &str, folder: &str, content: &str) -> NoteEntry {
+        NoteEntry {
+            path: format!("/tmp/Stik/{}/{}", folder, filename),
+            filename: filename.to_string(),
+            folder: folder.to_string(),
+            title: "Title".to_string(),
+            preview: content.to_string(),
+            created: "20990101-000000".to_string(),
+            content_len: content.len(),
+            locked: false,
+            word_count: 0,
+            reading_minutes: 0,
+            language: "en".to_string(),
+            truncated_in_index: false,
+            id: None,
+            pinned: false,
+            created_date: parse_date_from_filename(filename),
+            open_tasks: 0,
+            done_tasks: 0,
+        }
+    }
+
+    #[test]
+    fn csv_export_has_rows_and_summary() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 7).expect("valid date");
+        let entries = vec![
+            index_entry("20260206-091500-a-ab12.md", "Work, Misc", "one two three"),
+            index_entry("20260207-220000-b-cd34.md", "Inbox", "four"),
+        ];
+
+        let mut out = Vec::new();
+        write_stats_export(&mut out, StatsExportFormat::Csv, &entries, today).expect("export");
+        let csv = String::from_utf8(out).expect("utf8");
+
+        assert!(csv.starts_with("created,folder,word_count,char_count\n"));
+        assert!(csv.contains("2026-02-06T09:15:00,\"Work, Misc\",3,13\n"));
+        assert!(csv.contains("2026-02-07T22:00:00,Inbox,1,4\n"));
+        assert!(csv.contains("total_words,4\n"));
+        assert!(csv.contains("current_streak_days,2\n"));
+    }
+
+    #[test]
+    fn date_only_names_keep_their_day_in_exports() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 7).expect("valid date");
+        let entries = vec![
+            index_entry("20260206-daily.md", "Journal", "one two"),
+            index_entry("20260207-daily.md", "Journal", "three"),
+        ];
+
+        let mut out = Vec::new();
+        write_stats_export(&mut out, StatsExportFormat::Csv, &entries, today).expect("export");
+        let csv = String::from_utf8(out).expect("utf8");
+
+        assert!(csv.contains("2026-02-06T00:00:00,Journal,2,7\n"));
+        assert!(csv.contains("current_streak_days,2\n"));
+    }
+
+    #[test]
+    fn json_export_is_valid_json() {
+        let today = NaiveDate::from_ymd_opt(2026, 2, 7).expect("valid date");
+        let entries = vec![index_entry(
+            "20260206-091500-a-ab12.md",
+            "Work",
+            "hello world",
+        )];
+
+        let mut out = Vec::new();
+        write_stats_export(&mut out, StatsExportFormat::Json, &entries, today).expect("export");
+        let json: serde_json::Value = serde_json::from_slice(&out).expect("valid json");
+
+        assert_eq!(json["notes"][0]["folder"], "Work");
+        assert_eq!(json["notes"][0]["word_count"], 2);
+        assert_eq!(json["summary"]["total_notes"], 1);
+        assert_eq!(json["summary"]["longest_streak_days"], 1);
+    }
+
+    #[test]
+    fn stats_export_format_parses_case_insensitively() {
+        assert_eq!(
+            StatsExportFormat::parse(" CSV "),
+            Ok(StatsExportFormat::Csv)
+        );
+        assert_eq!(
+            StatsExportFormat::parse("json"),
+            Ok(StatsExportFormat::Json)
+        );
+        assert!(StatsExportFormat::parse("xlsx").is_err());
+    }
+
+    #[test]
+    fn midnight_timer_wakes_just_after_midnight() {
+        let now = NaiveDate::from_ymd_opt(2026, 2, 6)
+            .and_then(|d| d.and_hms_opt(23, 59, 0))
+            .expect("valid datetime");
+        assert_eq!(until_next_midnight(now), std::time::Duration::from_secs(65));
     }
 }
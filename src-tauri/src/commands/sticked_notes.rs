@@ -1,9 +1,17 @@
-use super::versioning;
+use super::settings::StickedSizeLimits;
+use super::{config_paths, index, tray_menu, versioning};
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use tauri::AppHandle;
 use uuid::Uuid;
 
+const STICKED_NOTES_FILE: &str = "sticked_notes.json";
+/// Longest an edit waits in memory before it is written, which bounds what
+/// a crash can lose.
+const FLUSH_DELAY: Duration = Duration::from_secs(1);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StickedNote {
     pub id: String,
@@ -13,6 +21,9 @@ pub struct StickedNote {
     pub size: Option<(f64, f64)>,
     pub created_at: String,
     pub updated_at: String,
+    /// Name of the template the note was created from, for display.
+    #[serde(default)]
+    pub template_origin: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -20,40 +31,148 @@ pub struct StickedNotesStore {
     pub notes: Vec<StickedNote>,
 }
 
-fn get_sticked_notes_path() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or("Could not find home directory")?;
-    let stik_config = home.join(".stik");
-    fs::create_dir_all(&stik_config).map_err(|e| e.to_string())?;
-    Ok(stik_config.join("sticked_notes.json"))
+/// The store as the app sees it. Creating and closing notes writes through;
+/// content, position and size edits (autosave sends one per keystroke
+/// pause) land here and are written behind at most once per `FLUSH_DELAY`.
+struct StoreCache {
+    /// File the store was read from. A different path means the config
+    /// moved and the store is reloaded.
+    path: Option<PathBuf>,
+    store: StickedNotesStore,
+    dirty: bool,
+    flush_scheduled: bool,
+}
+
+impl StoreCache {
+    const fn new() -> Self {
+        Self {
+            path: None,
+            store: StickedNotesStore { notes: Vec::new() },
+            dirty: false,
+            flush_scheduled: false,
+        }
+    }
+
+    /// Note unwritten edits. True when no flush is pending yet, so the
+    /// caller has to schedule one.
+    fn mark_dirty(&mut self) -> bool {
+        self.dirty = true;
+        !std::mem::replace(&mut self.flush_scheduled, true)
+    }
+
+    fn flushed(&mut self) {
+        self.dirty = false;
+        self.flush_scheduled = false;
+    }
 }
 
-fn load_sticked_notes() -> Result<StickedNotesStore, String> {
-    let path = get_sticked_notes_path()?;
-    match versioning::load_versioned::<StickedNotesStore>(&path)? {
+static CACHE: Mutex<StoreCache> = Mutex::new(StoreCache::new());
+
+fn load_sticked_notes(path: &Path) -> Result<StickedNotesStore, String> {
+    match versioning::load_versioned::<StickedNotesStore>(path)? {
         Some(store) => Ok(store),
         None => Ok(StickedNotesStore::default()),
     }
 }
 
 fn save_sticked_notes(store: &StickedNotesStore) -> Result<(), String> {
-    let path = get_sticked_notes_path()?;
+    let path = config_paths::writable_config_file(STICKED_NOTES_FILE)?;
     versioning::save_versioned(&path, store)
 }
 
+/// The cache, loaded from the current config location. Callers must drop
+/// it before rebuilding the tray, which lists the notes again.
+fn cache() -> Result<MutexGuard<'static, StoreCache>, String> {
+    let path = config_paths::config_file(STICKED_NOTES_FILE)?;
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if cache.path.as_ref() != Some(&path) {
+        if let (true, Some(previous)) = (cache.dirty, cache.path.as_ref()) {
+            if let Err(e) = versioning::save_versioned(previous, &cache.store) {
+                log::error!("Failed to save sticked notes before reloading: {}", e);
+            }
+        }
+        cache.store = load_sticked_notes(&path)?;
+        cache.path = Some(path);
+        cache.flushed();
+    }
+    Ok(cache)
+}
+
+/// Apply `change` to a copy of the store and write it, keeping the cache
+/// unchanged if the write fails.
+fn write_through<T>(
+    cache: &mut StoreCache,
+    change: impl FnOnce(&mut StickedNotesStore) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut store = cache.store.clone();
+    let result = change(&mut store)?;
+    save_sticked_notes(&store)?;
+    cache.store = store;
+    cache.flushed();
+    Ok(result)
+}
+
+fn schedule_flush(cache: &mut StoreCache) {
+    if !cache.mark_dirty() {
+        return;
+    }
+    let spawned = std::thread::Builder::new()
+        .name("stik-sticked-flush".to_string())
+        .spawn(|| {
+            std::thread::sleep(FLUSH_DELAY);
+            if let Err(e) = flush_sticked_notes() {
+                log::error!("Failed to save sticked notes: {}", e);
+            }
+        });
+    if spawned.is_err() {
+        // No flusher: write now rather than leave the edit unsaved.
+        if save_sticked_notes(&cache.store).is_ok() {
+            cache.flushed();
+        } else {
+            cache.flush_scheduled = false;
+        }
+    }
+}
+
+/// Write pending edits now. Called on quit; the flusher calls it too.
+#[tauri::command]
+pub fn flush_sticked_notes() -> Result<(), String> {
+    let mut cache = cache()?;
+    if !cache.dirty {
+        cache.flush_scheduled = false;
+        return Ok(());
+    }
+    let result = save_sticked_notes(&cache.store);
+    // A failed write stays dirty and is retried on the next edit.
+    cache.flush_scheduled = false;
+    if result.is_ok() {
+        cache.flushed();
+    }
+    result
+}
+
 #[tauri::command]
 pub fn list_sticked_notes() -> Result<Vec<StickedNote>, String> {
-    let store = load_sticked_notes()?;
-    Ok(store.notes)
+    Ok(cache()?.store.notes.clone())
 }
 
 #[tauri::command]
 pub fn create_sticked_note(
+    app: AppHandle,
     content: String,
     folder: String,
     position: Option<(f64, f64)>,
 ) -> Result<StickedNote, String> {
-    let mut store = load_sticked_notes()?;
+    create_sticked_note_inner(&app, content, folder, position, None)
+}
 
+pub fn create_sticked_note_inner(
+    app: &AppHandle,
+    content: String,
+    folder: String,
+    position: Option<(f64, f64)>,
+    template_origin: Option<String>,
+) -> Result<StickedNote, String> {
     let now = chrono::Utc::now().to_rfc3339();
     let note = StickedNote {
         id: Uuid::new_v4().to_string(),
@@ -63,31 +182,57 @@ pub fn create_sticked_note(
         size: Some((400.0, 280.0)),
         created_at: now.clone(),
         updated_at: now,
+        template_origin,
     };
 
-    store.notes.push(note.clone());
-    save_sticked_notes(&store)?;
+    write_through(&mut cache()?, |store| {
+        store.notes.push(note.clone());
+        Ok(())
+    })?;
+    tray_menu::rebuild_tray_menu(app);
 
     Ok(note)
 }
 
+/// Fit stored note sizes into `limits` after the limits change.
+pub fn clamp_sizes(limits: &StickedSizeLimits) -> Result<(), String> {
+    let mut cache = cache()?;
+    let out_of_bounds = cache
+        .store
+        .notes
+        .iter()
+        .any(|note| note.size.is_some_and(|size| limits.clamp(size) != size));
+    if !out_of_bounds {
+        return Ok(());
+    }
+    write_through(&mut cache, |store| {
+        for note in &mut store.notes {
+            note.size = note.size.map(|size| limits.clamp(size));
+        }
+        Ok(())
+    })
+}
+
 #[tauri::command]
 pub fn update_sticked_note(
+    app: AppHandle,
     id: String,
     content: Option<String>,
     folder: Option<String>,
     position: Option<(f64, f64)>,
     size: Option<(f64, f64)>,
 ) -> Result<StickedNote, String> {
-    let mut store = load_sticked_notes()?;
-
-    let note = store
+    let mut cache = cache()?;
+    let note = cache
+        .store
         .notes
         .iter_mut()
         .find(|n| n.id == id)
         .ok_or_else(|| format!("Sticked note not found: {}", id))?;
 
+    let mut renamed = false;
     if let Some(c) = content {
+        renamed = index::extract_title(&c) != index::extract_title(&note.content);
         note.content = c;
     }
     if let Some(f) = folder {
@@ -102,23 +247,31 @@ pub fn update_sticked_note(
     note.updated_at = chrono::Utc::now().to_rfc3339();
 
     let updated_note = note.clone();
-    save_sticked_notes(&store)?;
+    schedule_flush(&mut cache);
+    drop(cache);
+    // Autosave lands here on every keystroke pause; only a new first line
+    // changes what the tray lists.
+    if renamed {
+        tray_menu::rebuild_tray_menu(&app);
+    }
 
     Ok(updated_note)
 }
 
 #[tauri::command]
-pub fn close_sticked_note(id: String, save_to_folder: bool) -> Result<String, String> {
-    let mut store = load_sticked_notes()?;
-
-    let note_idx = store
+pub fn close_sticked_note(
+    app: AppHandle,
+    id: String,
+    save_to_folder: bool,
+) -> Result<String, String> {
+    let note = cache()?
+        .store
         .notes
         .iter()
-        .position(|n| n.id == id)
+        .find(|n| n.id == id)
+        .cloned()
         .ok_or_else(|| format!("Sticked note not found: {}", id))?;
 
-    let note = store.notes.remove(note_idx);
-
     // Save content to folder if requested and has content.
     // Returns the saved file path so the frontend can persist cursor position.
     let mut saved_path = String::new();
@@ -130,18 +283,39 @@ pub fn close_sticked_note(id: String, save_to_folder: bool) -> Result<String, St
         }
     }
 
-    save_sticked_notes(&store)?;
+    write_through(&mut cache()?, |store| {
+        store.notes.retain(|n| n.id != id);
+        Ok(())
+    })?;
+    tray_menu::rebuild_tray_menu(&app);
 
     Ok(saved_path)
 }
 
 #[tauri::command]
 pub fn get_sticked_note(id: String) -> Result<StickedNote, String> {
-    let store = load_sticked_notes()?;
-
-    store
+    cache()?
+        .store
         .notes
-        .into_iter()
+        .iter()
         .find(|n| n.id == id)
+        .cloned()
         .ok_or_else(|| format!("Sticked note not found: {}", id))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_schedule_one_flush_until_written() {
+        let mut cache = StoreCache::new();
+        assert!(cache.mark_dirty());
+        assert!(!cache.mark_dirty());
+        assert!(cache.dirty);
+
+        cache.flushed();
+        assert!(!cache.dirty);
+        assert!(cache.mark_dirty());
+    }
+}
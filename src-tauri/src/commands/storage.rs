@@ -5,9 +5,11 @@
 /// via DarwinKit JSON-RPC. When local or custom, direct std::fs (current behavior).
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use super::darwinkit;
+use super::file_watcher;
 use super::settings;
 
 // ── Storage Mode ──────────────────────────────────────────────────
@@ -19,43 +21,149 @@ pub enum StorageMode {
     Custom(String),
 }
 
+/// Notes root that stands in for the configured one while a test holds
+/// `test_support::use_notes_root`.
+#[cfg(test)]
+pub(crate) static TEST_NOTES_ROOT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+#[cfg(test)]
+fn test_notes_root() -> Option<PathBuf> {
+    TEST_NOTES_ROOT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
 /// Determine the active storage mode from settings.
 /// Priority: icloud.enabled > notes_directory (custom) > local default.
 pub fn current_mode() -> StorageMode {
+    #[cfg(test)]
+    if test_notes_root().is_some() {
+        return StorageMode::Local;
+    }
     match settings::load_settings_from_file() {
-        Ok(s) => {
-            if s.icloud.enabled {
-                StorageMode::ICloud
-            } else if !s.notes_directory.is_empty() {
-                let p = PathBuf::from(&s.notes_directory);
-                if p.is_absolute() {
-                    StorageMode::Custom(s.notes_directory)
-                } else {
-                    StorageMode::Local
-                }
-            } else {
-                StorageMode::Local
-            }
-        }
+        Ok(s) => mode_for(&s),
         Err(_) => StorageMode::Local,
     }
 }
 
+pub(crate) fn mode_for(s: &settings::StikSettings) -> StorageMode {
+    if s.icloud.enabled {
+        StorageMode::ICloud
+    } else if !s.notes_directory.is_empty() {
+        let p = PathBuf::from(&s.notes_directory);
+        if p.is_absolute() {
+            StorageMode::Custom(s.notes_directory.clone())
+        } else {
+            StorageMode::Local
+        }
+    } else {
+        StorageMode::Local
+    }
+}
+
+// ── Notes Directory Availability ──────────────────────────────────
+
+/// Error prefix when the configured `notes_directory` is missing (say, on a
+/// drive that isn't mounted) or can't be written to.
+pub const NOTES_DIR_UNAVAILABLE: &str = "NOTES_DIR_UNAVAILABLE";
+/// Emitted at startup with a `NotesDirectoryStatus` when it is unavailable.
+pub const NOTES_DIR_UNAVAILABLE_EVENT: &str = "notes-directory-unavailable";
+
+/// The configured directory the last check found unwritable. Writability
+/// is only probed by `check_notes_directory`; `stik_root` just reads this.
+static UNWRITABLE_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotesDirectoryStatus {
+    pub available: bool,
+    /// The configured `notes_directory`; empty for the default location
+    /// and iCloud.
+    pub path: String,
+    pub error: Option<String>,
+}
+
+fn unavailable_error(dir: &Path, reason: &str) -> String {
+    format!("{}: {} {}", NOTES_DIR_UNAVAILABLE, dir.display(), reason)
+}
+
+/// The configured directory must already exist: creating it would leave a
+/// stray folder on the mount point of a drive that isn't connected.
+fn ensure_custom_dir_available(dir: &Path) -> Result<(), String> {
+    if !dir.is_dir() {
+        return Err(unavailable_error(
+            dir,
+            "isn't connected or no longer exists",
+        ));
+    }
+    let unwritable = UNWRITABLE_DIR.lock().unwrap_or_else(|e| e.into_inner());
+    if unwritable.as_deref() == Some(dir) {
+        return Err(unavailable_error(dir, "isn't writable"));
+    }
+    Ok(())
+}
+
+fn is_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".stik-write-check-{:016x}", rand::random::<u64>()));
+    match fs::File::create(&probe) {
+        Ok(_) => {
+            let _ = fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Re-validate the configured notes directory: that it exists and that a
+/// file can be created in the Stik root inside it.
+pub fn check_notes_directory() -> NotesDirectoryStatus {
+    *UNWRITABLE_DIR.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    let settings = settings::load_settings_from_file().unwrap_or_default();
+    let StorageMode::Custom(dir) = mode_for(&settings) else {
+        return NotesDirectoryStatus {
+            available: true,
+            path: String::new(),
+            error: None,
+        };
+    };
+
+    let dir = PathBuf::from(dir);
+    let checked = stik_root().and_then(|root| {
+        if is_writable(&root) {
+            return Ok(());
+        }
+        *UNWRITABLE_DIR.lock().unwrap_or_else(|e| e.into_inner()) = Some(dir.clone());
+        Err(unavailable_error(&dir, "isn't writable"))
+    });
+    if let Err(e) = &checked {
+        log::warn!("Notes directory unavailable: {}", e);
+    }
+    NotesDirectoryStatus {
+        available: checked.is_ok(),
+        path: dir.to_string_lossy().to_string(),
+        error: checked.err(),
+    }
+}
+
 /// Get the root Stik directory for the current storage mode.
 /// When `use_directory_as_root` is enabled and a custom directory is set,
 /// the custom path is used directly without appending a `Stik/` subfolder.
+/// A custom directory that is missing or unwritable is a
+/// `NOTES_DIR_UNAVAILABLE` error.
 pub fn stik_root() -> Result<PathBuf, String> {
+    #[cfg(test)]
+    if let Some(root) = test_notes_root() {
+        return Ok(root);
+    }
     match current_mode() {
         StorageMode::ICloud => icloud_stik_root(),
         StorageMode::Custom(dir) => {
+            let dir = PathBuf::from(&dir);
+            ensure_custom_dir_available(&dir)?;
             let use_as_root = settings::load_settings_from_file()
                 .map(|s| s.use_directory_as_root)
                 .unwrap_or(false);
-            let path = if use_as_root {
-                PathBuf::from(&dir)
-            } else {
-                PathBuf::from(&dir).join("Stik")
-            };
+            let path = if use_as_root { dir } else { dir.join("Stik") };
             fs::create_dir_all(&path).map_err(|e| e.to_string())?;
             Ok(path)
         }
@@ -129,6 +237,41 @@ pub fn read_file(path: &str) -> Result<String, String> {
     }
 }
 
+/// Read at most `max_bytes` of a file, cut back to a char boundary.
+/// Returns the text and the file's full size in bytes.
+pub fn read_file_prefix(path: &str, max_bytes: usize) -> Result<(String, usize), String> {
+    if current_mode() == StorageMode::ICloud {
+        // Coordinated reads are whole-file only.
+        let mut content = read_file(path)?;
+        let size = content.len();
+        let mut end = max_bytes.min(size);
+        while !content.is_char_boundary(end) {
+            end -= 1;
+        }
+        content.truncate(end);
+        return Ok((content, size));
+    }
+
+    use std::io::Read;
+    let file = fs::File::open(path).map_err(|e| e.to_string())?;
+    let size = file.metadata().map_err(|e| e.to_string())?.len() as usize;
+    let mut bytes = Vec::with_capacity(max_bytes.min(size));
+    file.take(max_bytes as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|e| e.to_string())?;
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok((text, size)),
+        // Only a character split by the cut is tolerated.
+        Err(e) if e.utf8_error().error_len().is_none() => {
+            let valid = e.utf8_error().valid_up_to();
+            let mut bytes = e.into_bytes();
+            bytes.truncate(valid);
+            Ok((String::from_utf8(bytes).unwrap_or_default(), size))
+        }
+        Err(_) => Err("stream did not contain valid UTF-8".to_string()),
+    }
+}
+
 pub fn write_file(path: &str, content: &str) -> Result<(), String> {
     match current_mode() {
         StorageMode::ICloud => {
@@ -139,7 +282,11 @@ pub fn write_file(path: &str, content: &str) -> Result<(), String> {
             )?;
             Ok(())
         }
-        _ => fs::write(path, content).map_err(|e| e.to_string()),
+        _ => {
+            fs::write(path, content).map_err(|e| e.to_string())?;
+            file_watcher::mark_own_write(path);
+            Ok(())
+        }
     }
 }
 
@@ -155,7 +302,11 @@ pub fn write_bytes(path: &str, data: &[u8]) -> Result<(), String> {
             )?;
             Ok(())
         }
-        _ => fs::write(path, data).map_err(|e| e.to_string()),
+        _ => {
+            fs::write(path, data).map_err(|e| e.to_string())?;
+            file_watcher::mark_own_write(path);
+            Ok(())
+        }
     }
 }
 
@@ -169,7 +320,11 @@ pub fn delete_file(path: &str) -> Result<(), String> {
             )?;
             Ok(())
         }
-        _ => fs::remove_file(path).map_err(|e| e.to_string()),
+        _ => {
+            fs::remove_file(path).map_err(|e| e.to_string())?;
+            file_watcher::mark_own_write(path);
+            Ok(())
+        }
     }
 }
 
@@ -183,7 +338,12 @@ pub fn move_file(src: &str, dst: &str) -> Result<(), String> {
             )?;
             Ok(())
         }
-        _ => fs::rename(src, dst).map_err(|e| e.to_string()),
+        _ => {
+            fs::rename(src, dst).map_err(|e| e.to_string())?;
+            file_watcher::mark_own_write(src);
+            file_watcher::mark_own_write(dst);
+            Ok(())
+        }
     }
 }
 
@@ -199,6 +359,7 @@ pub fn copy_file(src: &str, dst: &str) -> Result<(), String> {
         }
         _ => {
             fs::copy(src, dst).map_err(|e| e.to_string())?;
+            file_watcher::mark_own_write(dst);
             Ok(())
         }
     }
@@ -334,3 +495,24 @@ pub fn stop_monitoring() -> Result<(), String> {
     darwinkit::call("icloud.stop_monitoring", None)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::temp_dir;
+
+    #[test]
+    fn missing_custom_directory_is_unavailable_and_not_created() {
+        let parent = temp_dir("unmounted-drive");
+        let dir = parent.join("drive");
+        let error = ensure_custom_dir_available(&dir).unwrap_err();
+        assert!(error.starts_with(NOTES_DIR_UNAVAILABLE));
+        assert!(!dir.exists());
+
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(ensure_custom_dir_available(&dir), Ok(()));
+        assert!(is_writable(&dir));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        let _ = fs::remove_dir_all(&parent);
+    }
+}
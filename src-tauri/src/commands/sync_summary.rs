@@ -0,0 +1,338 @@
+/// A markdown digest of what changed in the git-shared notes over the last
+/// few days, grouped by person and by note.
+///
+/// Stik's own sync commits ("stik: sync …") are collapsed into a count per
+/// author; anything a teammate committed by hand is listed on its own line.
+/// File paths are shown as note titles when the index knows them.
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::git_share::{self, GitIncomingCommit, STIK_COMMIT_PREFIX};
+use super::index::NoteIndex;
+use super::notes::{self, NoteSaved, NOTE_SAVED_EVENT};
+use super::settings;
+
+const MAX_SUMMARY_DAYS: u32 = 366;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncSummary {
+    pub markdown: String,
+    /// Commits in the window, autosaves included.
+    pub commits: usize,
+    /// The note it was written to; `None` when only previewed.
+    pub saved: Option<NoteSaved>,
+}
+
+#[derive(Default)]
+struct PersonActivity<'a> {
+    autosaves: usize,
+    commits: Vec<&'a GitIncomingCommit>,
+}
+
+#[derive(Default)]
+struct NoteActivity<'a> {
+    changes: usize,
+    authors: Vec<&'a str>,
+}
+
+fn is_autosave(commit: &GitIncomingCommit) -> bool {
+    commit.message.starts_with(STIK_COMMIT_PREFIX)
+}
+
+fn is_note_file(file: &str) -> bool {
+    file.to_ascii_lowercase().ends_with(".md")
+}
+
+/// The index title, else the file name without `.md`.
+fn title_for<'a>(file: &'a str, titles: &'a HashMap<String, String>) -> &'a str {
+    titles.get(file).map(String::as_str).unwrap_or_else(|| {
+        let name = file.rsplit('/').next().unwrap_or(file);
+        name.strip_suffix(".md").unwrap_or(name)
+    })
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{} {}", count, if count == 1 { one } else { many })
+}
+
+fn short_date(iso: &str) -> String {
+    DateTime::parse_from_rfc3339(iso)
+        .map(|date| date.format("%b %-d").to_string())
+        .unwrap_or_default()
+}
+
+/// The summary for `commits` (newest first) made between `from` and `to`.
+/// `local_author` is shown as "you".
+fn render_summary(
+    commits: &[GitIncomingCommit],
+    local_author: Option<&str>,
+    titles: &HashMap<String, String>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> String {
+    let mut lines = vec![format!(
+        "# Shared notes, {} – {}",
+        from.format("%b %-d"),
+        to.format("%b %-d, %Y")
+    )];
+    if commits.is_empty() {
+        lines.push(String::new());
+        lines.push("Nothing changed in the shared notes.".to_string());
+        return lines.join("\n") + "\n";
+    }
+
+    let is_local = |author: &str| Some(author) == local_author;
+    let display_name = |author: &str| -> String {
+        if is_local(author) {
+            "you".to_string()
+        } else {
+            author.to_string()
+        }
+    };
+
+    let mut people: HashMap<&str, PersonActivity> = HashMap::new();
+    let mut notes: HashMap<&str, NoteActivity> = HashMap::new();
+    for commit in commits {
+        let person = people.entry(commit.author.as_str()).or_default();
+        if is_autosave(commit) {
+            person.autosaves += 1;
+        } else {
+            person.commits.push(commit);
+        }
+        for file in commit.files.iter().filter(|file| is_note_file(file)) {
+            let note = notes.entry(title_for(file, titles)).or_default();
+            note.changes += 1;
+            if !note.authors.contains(&commit.author.as_str()) {
+                note.authors.push(&commit.author);
+            }
+        }
+    }
+
+    let mut people: Vec<(&str, PersonActivity)> = people.into_iter().collect();
+    people.sort_by(|(a, a_activity), (b, b_activity)| {
+        is_local(b)
+            .cmp(&is_local(a))
+            .then(
+                (b_activity.autosaves + b_activity.commits.len())
+                    .cmp(&(a_activity.autosaves + a_activity.commits.len())),
+            )
+            .then(a.cmp(b))
+    });
+    let mut notes: Vec<(&str, NoteActivity)> = notes.into_iter().collect();
+    notes.sort_by(|(a, a_activity), (b, b_activity)| {
+        b_activity.changes.cmp(&a_activity.changes).then(a.cmp(b))
+    });
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{} to {} by {}.",
+        plural(commits.len(), "change", "changes"),
+        plural(notes.len(), "note", "notes"),
+        plural(people.len(), "person", "people")
+    ));
+
+    lines.push(String::new());
+    lines.push("## By person".to_string());
+    for (author, activity) in &people {
+        lines.push(String::new());
+        let name = display_name(author);
+        lines.push(format!(
+            "### {}",
+            if is_local(author) { "You" } else { *author }
+        ));
+        for commit in &activity.commits {
+            let touched: Vec<&str> = commit
+                .files
+                .iter()
+                .filter(|file| is_note_file(file))
+                .map(|file| title_for(file, titles))
+                .collect();
+            let mut line = format!("- {} · {}", short_date(&commit.date), commit.message);
+            if !touched.is_empty() {
+                line.push_str(&format!(" ({})", touched.join(", ")));
+            }
+            lines.push(line);
+        }
+        if activity.autosaves > 0 {
+            lines.push(format!(
+                "- {} by {}",
+                plural(activity.autosaves, "autosave", "autosaves"),
+                name
+            ));
+        }
+    }
+
+    if !notes.is_empty() {
+        lines.push(String::new());
+        lines.push("## By note".to_string());
+        lines.push(String::new());
+        for (title, activity) in &notes {
+            let authors: Vec<String> = activity
+                .authors
+                .iter()
+                .map(|author| display_name(author))
+                .collect();
+            lines.push(format!(
+                "- **{}**: {} by {}",
+                title,
+                plural(activity.changes, "change", "changes"),
+                authors.join(", ")
+            ));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Titles of the notes `commits` touched, keyed by repository-relative path.
+fn note_titles(
+    index: &NoteIndex,
+    repo_path: &Path,
+    commits: &[GitIncomingCommit],
+) -> HashMap<String, String> {
+    commits
+        .iter()
+        .flat_map(|commit| commit.files.iter())
+        .filter(|file| is_note_file(file))
+        .filter_map(|file| {
+            let path = repo_path.join(file).to_string_lossy().to_string();
+            let entry = index.get(&path)?;
+            (!entry.title.trim().is_empty()).then(|| (file.clone(), entry.title))
+        })
+        .collect()
+}
+
+fn generate(app: &AppHandle, days: u32, save: bool) -> Result<SyncSummary, String> {
+    if days == 0 || days > MAX_SUMMARY_DAYS {
+        return Err(format!(
+            "Summary window must be 1 to {} days",
+            MAX_SUMMARY_DAYS
+        ));
+    }
+    let now = Local::now();
+    let since = now - Duration::days(i64::from(days));
+    let history = git_share::shared_history(since)?;
+    let titles = note_titles(
+        &app.state::<NoteIndex>(),
+        &history.repo_path,
+        &history.commits,
+    );
+    let markdown = render_summary(
+        &history.commits,
+        history.local_author.as_deref(),
+        &titles,
+        since.date_naive(),
+        now.date_naive(),
+    );
+
+    let saved = if save {
+        let settings = settings::get_settings()?;
+        let configured = settings.sync_summary_folder.trim();
+        let folder = if configured.is_empty() {
+            settings.default_folder.clone()
+        } else {
+            configured.to_string()
+        };
+        let saved = notes::save_note_inner(folder, markdown.clone())?;
+        notes::post_save_processing(app, &saved, &markdown);
+        let _ = app.emit(NOTE_SAVED_EVENT, &saved);
+        log::info!("Wrote {}-day sync summary to {}", days, saved.path);
+        Some(saved)
+    } else {
+        None
+    };
+
+    Ok(SyncSummary {
+        markdown,
+        commits: history.commits.len(),
+        saved,
+    })
+}
+
+/// Summarise the last `days` of the shared repository's history. Writes it
+/// as a note in `sync_summary_folder` unless `save` is false.
+#[tauri::command]
+pub async fn generate_sync_summary(
+    app: AppHandle,
+    days: u32,
+    save: Option<bool>,
+) -> Result<SyncSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || generate(&app, days, save.unwrap_or(true)))
+        .await
+        .map_err(|e| format!("Sync summary failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(author: &str, date: &str, message: &str, files: &[&str]) -> GitIncomingCommit {
+        GitIncomingCommit {
+            hash: format!("{}-{}", author, date),
+            author: author.to_string(),
+            date: date.to_string(),
+            message: message.to_string(),
+            files: files.iter().map(|file| file.to_string()).collect(),
+        }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 2, d).unwrap()
+    }
+
+    #[test]
+    fn autosaves_collapse_and_teammate_commits_are_listed() {
+        let commits = vec![
+            commit(
+                "Me",
+                "2026-02-06T18:00:00+01:00",
+                "stik: sync 2026-02-06 18:00:00 notes (autosave)",
+                &["Work/plan.md"],
+            ),
+            commit(
+                "Ada",
+                "2026-02-06T10:00:00+01:00",
+                "Rewrite goals",
+                &["Work/plan.md", "Work/.gitignore"],
+            ),
+            commit(
+                "Me",
+                "2026-02-05T09:00:00+01:00",
+                "stik: sync 2026-02-05 09:00:00 notes (periodic)",
+                &["Work/plan.md", "Work/old idea.md"],
+            ),
+        ];
+        let titles = HashMap::from([("Work/plan.md".to_string(), "Q1 plan".to_string())]);
+
+        assert_eq!(
+            render_summary(&commits, Some("Me"), &titles, day(1), day(7)),
+            "# Shared notes, Feb 1 – Feb 7, 2026\n\
+             \n\
+             3 changes to 2 notes by 2 people.\n\
+             \n\
+             ## By person\n\
+             \n\
+             ### You\n\
+             - 2 autosaves by you\n\
+             \n\
+             ### Ada\n\
+             - Feb 6 · Rewrite goals (Q1 plan)\n\
+             \n\
+             ## By note\n\
+             \n\
+             - **Q1 plan**: 3 changes by you, Ada\n\
+             - **old idea**: 1 change by you\n"
+        );
+    }
+
+    #[test]
+    fn an_empty_window_says_so() {
+        assert_eq!(
+            render_summary(&[], None, &HashMap::new(), day(1), day(7)),
+            "# Shared notes, Feb 1 – Feb 7, 2026\n\nNothing changed in the shared notes.\n"
+        );
+    }
+}
@@ -0,0 +1,201 @@
+/// Markdown task list parsing (`- [ ] todo`, `* [x] done`), matching the
+/// editor's checkbox syntax in `cm-task-toggle.ts`.
+use serde::Serialize;
+use std::collections::BTreeMap;
+use tauri::State;
+
+use super::index::{NoteEntry, NoteIndex};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskItem {
+    /// Zero-based line number in the note.
+    pub line: usize,
+    pub text: String,
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TaskCounts {
+    pub open: usize,
+    pub done: usize,
+}
+
+/// `(done, text)` when `raw` is a task line.
+fn task_line(raw: &str) -> Option<(bool, &str)> {
+    let rest = raw.trim_start();
+    let rest = rest
+        .strip_prefix("- ")
+        .or_else(|| rest.strip_prefix("* "))
+        .or_else(|| rest.strip_prefix("+ "))?;
+    let done = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    let text = rest[3..].trim();
+    (!text.is_empty()).then_some((done, text))
+}
+
+/// Every task line in `content`, checked or not.
+pub fn parse_tasks(content: &str) -> Vec<TaskItem> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(line, raw)| {
+            let (done, text) = task_line(raw)?;
+            Some(TaskItem {
+                line,
+                text: text.to_string(),
+                done,
+            })
+        })
+        .collect()
+}
+
+/// Open and checked tasks in `content`, leaving out code fences.
+pub fn count_tasks(content: &str) -> TaskCounts {
+    let mut counts = TaskCounts::default();
+    let mut in_fence = false;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        match task_line(line) {
+            Some((true, _)) if !in_fence => counts.done += 1,
+            Some((false, _)) if !in_fence => counts.open += 1,
+            _ => {}
+        }
+    }
+    counts
+}
+
+/// Unchecked tasks only.
+pub fn open_tasks(content: &str) -> Vec<TaskItem> {
+    parse_tasks(content)
+        .into_iter()
+        .filter(|task| !task.done)
+        .collect()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FolderTaskStats {
+    pub folder: String,
+    pub open_tasks: usize,
+    pub done_tasks: usize,
+    /// Notes with at least one task.
+    pub notes_with_tasks: usize,
+}
+
+/// Task totals per folder, in folder order. Folders without tasks report
+/// zeros.
+fn folder_task_stats(entries: &[NoteEntry]) -> Vec<FolderTaskStats> {
+    let mut folders: BTreeMap<&str, FolderTaskStats> = BTreeMap::new();
+    for entry in entries {
+        let stats = folders
+            .entry(&entry.folder)
+            .or_insert_with(|| FolderTaskStats {
+                folder: entry.folder.clone(),
+                ..Default::default()
+            });
+        stats.open_tasks += entry.open_tasks;
+        stats.done_tasks += entry.done_tasks;
+        if entry.open_tasks + entry.done_tasks > 0 {
+            stats.notes_with_tasks += 1;
+        }
+    }
+    folders.into_values().collect()
+}
+
+/// Open and checked task totals for each folder, or just `folder`.
+#[tauri::command]
+pub fn get_task_stats(
+    folder: Option<String>,
+    index: State<'_, NoteIndex>,
+) -> Result<Vec<FolderTaskStats>, String> {
+    let entries = index.list(folder.as_deref())?;
+    Ok(folder_task_stats(&entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_open_tasks_in_any_bullet_style() {
+        let content =
+            "# Plan\n- [ ] call Sam\n  * [x] book room\n+ [ ] send deck\n- [] not a task\n- [ ]\n";
+        let open: Vec<String> = open_tasks(content)
+            .into_iter()
+            .map(|task| task.text)
+            .collect();
+        assert_eq!(open, vec!["call Sam", "send deck"]);
+
+        let all = parse_tasks(content);
+        assert_eq!(all.len(), 3);
+        assert!(all[1].done);
+        assert_eq!(all[1].line, 2);
+    }
+
+    #[test]
+    fn counts_skip_tasks_inside_code_fences() {
+        let content = "- [ ] write
+- [x] plan
+```md
+- [ ] example
+- [x] example
+```
+* [X] ship
+";
+        assert_eq!(count_tasks(content), TaskCounts { open: 1, done: 2 });
+        assert_eq!(count_tasks("No tasks here"), TaskCounts::default());
+    }
+
+    fn entry(folder: &str, open_tasks: usize, done_tasks: usize) -> NoteEntry {
+        NoteEntry {
+            path: format!("/notes/{}/note.md", folder),
+            filename: "note.md".to_string(),
+            folder: folder.to_string(),
+            title: String::new(),
+            preview: String::new(),
+            created: String::new(),
+            content_len: 0,
+            locked: false,
+            word_count: 0,
+            reading_minutes: 0,
+            language: "und".to_string(),
+            truncated_in_index: false,
+            id: None,
+            pinned: false,
+            created_date: None,
+            open_tasks,
+            done_tasks,
+        }
+    }
+
+    #[test]
+    fn folder_totals_add_up_note_counts() {
+        let entries = vec![
+            entry("Work", 2, 1),
+            entry("Inbox", 0, 0),
+            entry("Work", 0, 3),
+            entry("Work", 0, 0),
+        ];
+        assert_eq!(
+            folder_task_stats(&entries),
+            vec![
+                FolderTaskStats {
+                    folder: "Inbox".to_string(),
+                    ..Default::default()
+                },
+                FolderTaskStats {
+                    folder: "Work".to_string(),
+                    open_tasks: 2,
+                    done_tasks: 4,
+                    notes_with_tasks: 2,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,297 @@
+/// Note templates for the capture window's template picker.
+///
+/// A few built-ins ship with Stik; the rest are the user's
+/// `custom_templates`, which double as `/` slash commands in the editor.
+/// Bodies may use the same placeholders as slash-command templates:
+/// `{{date}}`, `{{time}}`, `{{datetime}}`, `{{isodate}}`, `{{day}}`,
+/// plus `{{folder}}` and `{{cursor}}` for where the caret lands.
+use chrono::{Local, NaiveDateTime};
+use serde::Serialize;
+
+use super::settings::{self, CustomTemplate};
+
+const BUILTIN_PREFIX: &str = "builtin:";
+const CUSTOM_PREFIX: &str = "custom:";
+const CURSOR_PLACEHOLDER: &str = "{{cursor}}";
+const DAILY_COMMAND: &str = "daily";
+const PREVIEW_LINES: usize = 3;
+
+/// `(command, name, body)` of each built-in.
+const BUILTIN_TEMPLATES: [(&str, &str, &str); 3] = [
+    (
+        "meeting",
+        "Meeting",
+        "# Meeting — {{date}}\n\nAttendees: {{cursor}}\n\n## Agenda\n\n- \n\n## Notes\n\n- \n\n## Action Items\n\n- [ ] ",
+    ),
+    (
+        "daily",
+        "Daily",
+        "# {{day}}, {{date}}\n\n## Top priorities\n\n- [ ] {{cursor}}\n\n## Notes\n\n- \n",
+    ),
+    (
+        "todo",
+        "To-do",
+        "# To do — {{date}}\n\n- [ ] {{cursor}}\n- [ ] \n- [ ] \n",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateInfo {
+    /// `builtin:<command>` or `custom:<name>`.
+    pub id: String,
+    pub name: String,
+    /// First lines of the rendered body.
+    pub preview: String,
+    /// Slash command that inserts it in the editor, e.g. `/meeting`.
+    pub shortcut: String,
+    /// Shipped with Stik, so it can't be edited or deleted.
+    pub builtin: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AppliedTemplate {
+    pub body: String,
+    /// Caret position in UTF-16 code units, as the editor counts them.
+    pub cursor: usize,
+}
+
+struct Template {
+    id: String,
+    name: String,
+    command: String,
+    body: String,
+    builtin: bool,
+}
+
+fn all_templates(custom: &[CustomTemplate]) -> Vec<Template> {
+    let builtins = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(command, name, body)| Template {
+            id: format!("{}{}", BUILTIN_PREFIX, command),
+            name: name.to_string(),
+            command: command.to_string(),
+            body: body.to_string(),
+            builtin: true,
+        });
+    let custom = custom.iter().map(|template| Template {
+        id: format!("{}{}", CUSTOM_PREFIX, template.name),
+        name: template.name.clone(),
+        command: template.name.clone(),
+        body: template.body.clone(),
+        builtin: false,
+    });
+    builtins.chain(custom).collect()
+}
+
+fn render(body: &str, folder: &str, now: NaiveDateTime) -> AppliedTemplate {
+    let date = now.format("%B %-d, %Y").to_string();
+    let time = now.format("%H:%M").to_string();
+    let expanded = body
+        .replace("{{datetime}}", &format!("{} {}", date, time))
+        .replace("{{isodate}}", &now.format("%Y-%m-%d").to_string())
+        .replace("{{date}}", &date)
+        .replace("{{time}}", &time)
+        .replace("{{day}}", &now.format("%A").to_string())
+        .replace("{{folder}}", folder);
+    match expanded.find(CURSOR_PLACEHOLDER) {
+        Some(at) => AppliedTemplate {
+            cursor: expanded[..at].encode_utf16().count(),
+            body: expanded.replacen(CURSOR_PLACEHOLDER, "", 1),
+        },
+        None => AppliedTemplate {
+            cursor: expanded.encode_utf16().count(),
+            body: expanded,
+        },
+    }
+}
+
+fn preview(body: &str) -> String {
+    body.lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .take(PREVIEW_LINES)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn list(custom: &[CustomTemplate], now: NaiveDateTime) -> Vec<TemplateInfo> {
+    all_templates(custom)
+        .into_iter()
+        .map(|template| TemplateInfo {
+            preview: preview(&render(&template.body, "", now).body),
+            shortcut: format!("/{}", template.command),
+            id: template.id,
+            name: template.name,
+            builtin: template.builtin,
+        })
+        .collect()
+}
+
+fn find(custom: &[CustomTemplate], id: &str) -> Result<Template, String> {
+    all_templates(custom)
+        .into_iter()
+        .find(|template| template.id == id)
+        .ok_or_else(|| format!("Template not found: {}", id))
+}
+
+fn apply(
+    custom: &[CustomTemplate],
+    id: &str,
+    folder: &str,
+    now: NaiveDateTime,
+) -> Result<AppliedTemplate, String> {
+    find(custom, id).map(|template| render(&template.body, folder, now))
+}
+
+/// `(name, body)` of a template rendered now, for notes created from it
+/// rather than inserted into the editor.
+fn render_named(
+    custom: &[CustomTemplate],
+    id: &str,
+    folder: &str,
+    now: NaiveDateTime,
+) -> Result<(String, String), String> {
+    find(custom, id).map(|template| {
+        let body = render(&template.body, folder, now).body;
+        (template.name, body)
+    })
+}
+
+/// The template's name and its body with placeholders expanded as of now.
+pub fn render_template(id: &str, folder: &str) -> Result<(String, String), String> {
+    let settings = settings::get_settings()?;
+    render_named(
+        &settings.custom_templates,
+        id,
+        folder,
+        Local::now().naive_local(),
+    )
+}
+
+/// Body of a new daily note: the user's own `daily` template when they
+/// have one, otherwise the built-in.
+pub fn daily_note_body(custom: &[CustomTemplate], folder: &str, now: NaiveDateTime) -> String {
+    let prefix = if custom.iter().any(|template| template.name == DAILY_COMMAND) {
+        CUSTOM_PREFIX
+    } else {
+        BUILTIN_PREFIX
+    };
+    apply(custom, &format!("{}{}", prefix, DAILY_COMMAND), folder, now)
+        .map(|applied| applied.body)
+        .unwrap_or_default()
+}
+
+/// Built-in templates followed by the user's own.
+#[tauri::command]
+pub fn list_templates() -> Result<Vec<TemplateInfo>, String> {
+    let settings = settings::get_settings()?;
+    Ok(list(&settings.custom_templates, Local::now().naive_local()))
+}
+
+/// The template's body with placeholders expanded, ready to insert.
+#[tauri::command]
+pub fn apply_template(id: String, folder: String) -> Result<AppliedTemplate, String> {
+    let settings = settings::get_settings()?;
+    apply(
+        &settings.custom_templates,
+        &id,
+        &folder,
+        Local::now().naive_local(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 2, 6)
+            .and_then(|date| date.and_hms_opt(14, 2, 31))
+            .expect("valid time")
+    }
+
+    fn custom() -> Vec<CustomTemplate> {
+        vec![CustomTemplate {
+            name: "inbox".to_string(),
+            body: "Filed in {{folder}} on {{isodate}} at {{time}}\n{{cursor}}".to_string(),
+        }]
+    }
+
+    #[test]
+    fn builtins_come_first_and_are_flagged() {
+        let templates = list(&custom(), now());
+        let ids: Vec<_> = templates.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "builtin:meeting",
+                "builtin:daily",
+                "builtin:todo",
+                "custom:inbox"
+            ]
+        );
+        assert!(templates[..3].iter().all(|t| t.builtin));
+        assert!(!templates[3].builtin);
+        assert_eq!(templates[3].shortcut, "/inbox");
+        assert_eq!(
+            templates[0].preview,
+            "# Meeting — February 6, 2026\nAttendees:\n## Agenda"
+        );
+    }
+
+    #[test]
+    fn placeholders_expand_and_cursor_is_placed() {
+        let applied = apply(&custom(), "custom:inbox", "Work", now()).unwrap();
+        assert_eq!(applied.body, "Filed in Work on 2026-02-06 at 14:02\n");
+        assert_eq!(applied.cursor, applied.body.len());
+
+        let daily = apply(&[], "builtin:daily", "Inbox", now()).unwrap();
+        assert!(daily.body.starts_with("# Friday, February 6, 2026\n"));
+        assert_eq!(
+            &daily.body[..daily.cursor],
+            "# Friday, February 6, 2026\n\n## Top priorities\n\n- [ ] "
+        );
+    }
+
+    #[test]
+    fn a_custom_daily_template_replaces_the_builtin() {
+        let builtin = daily_note_body(&custom(), "Journal", now());
+        assert!(builtin.starts_with("# Friday, February 6, 2026\n"));
+
+        let own = vec![CustomTemplate {
+            name: "daily".to_string(),
+            body: "Journal for {{isodate}}\n".to_string(),
+        }];
+        assert_eq!(
+            daily_note_body(&own, "Journal", now()),
+            "Journal for 2026-02-06\n"
+        );
+    }
+
+    #[test]
+    fn named_render_carries_the_template_name() {
+        let (name, body) = render_named(&custom(), "custom:inbox", "Work", now()).unwrap();
+        assert_eq!(name, "inbox");
+        assert_eq!(body, "Filed in Work on 2026-02-06 at 14:02\n");
+
+        let (name, _) = render_named(&[], "builtin:todo", "Inbox", now()).unwrap();
+        assert_eq!(name, "To-do");
+    }
+
+    #[test]
+    fn cursor_counts_utf16_units() {
+        let applied = render("— 😀 {{cursor}}", "", now());
+        assert_eq!(applied.body, "— 😀 ");
+        assert_eq!(applied.cursor, 5);
+    }
+
+    #[test]
+    fn unknown_ids_are_an_error() {
+        assert_eq!(
+            apply(&custom(), "custom:missing", "Inbox", now()),
+            Err("Template not found: custom:missing".to_string())
+        );
+        assert!(apply(&custom(), "inbox", "Inbox", now()).is_err());
+    }
+}
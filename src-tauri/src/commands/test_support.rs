@@ -0,0 +1,33 @@
+/// Helpers shared by the unit tests in `commands`.
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use super::storage::TEST_NOTES_ROOT;
+
+static NOTES_ROOT_IN_USE: Mutex<()> = Mutex::new(());
+
+/// A new, empty directory under the system temp dir. Callers remove it.
+pub(crate) fn temp_dir(label: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("stik-{}-{}", label, uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+/// Points the notes root at `dir` until dropped. Tests that need it run
+/// one at a time.
+pub(crate) struct NotesRootGuard {
+    _in_use: MutexGuard<'static, ()>,
+}
+
+pub(crate) fn use_notes_root(dir: &Path) -> NotesRootGuard {
+    let in_use = NOTES_ROOT_IN_USE.lock().unwrap_or_else(|e| e.into_inner());
+    *TEST_NOTES_ROOT.lock().unwrap_or_else(|e| e.into_inner()) = Some(dir.to_path_buf());
+    NotesRootGuard { _in_use: in_use }
+}
+
+impl Drop for NotesRootGuard {
+    fn drop(&mut self) {
+        *TEST_NOTES_ROOT.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
@@ -0,0 +1,198 @@
+/// Import a dropped plain-text file (`.txt`, `.csv`, `.tsv`, `.log`) as a
+/// new note titled after the file.
+///
+/// Text is read as UTF-8, lossily when it isn't valid, with any BOM
+/// removed. Small CSV/TSV files become a markdown table; larger or ragged
+/// ones, and logs, go in a fenced code block so the editor doesn't render
+/// them as markdown. Files that look binary are refused.
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, Emitter};
+
+use super::notes::{self, NoteSaved, NOTE_SAVED_EVENT};
+
+const TEXT_EXTENSIONS: [&str; 5] = ["txt", "text", "csv", "tsv", "log"];
+const MAX_TEXT_IMPORT_BYTES: u64 = 2 * 1024 * 1024;
+/// Bytes sniffed for binary content.
+const BINARY_SNIFF_BYTES: usize = 8192;
+const TABLE_MAX_ROWS: usize = 50;
+const TABLE_MAX_COLUMNS: usize = 12;
+const UTF8_BOM: &str = "\u{feff}";
+
+fn decode(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    text.strip_prefix(UTF8_BOM)
+        .unwrap_or(text.as_ref())
+        .to_string()
+}
+
+/// A NUL byte, or mostly control characters, near the start. Text files
+/// have neither; images, archives and executables almost always do.
+fn looks_binary(bytes: &[u8]) -> bool {
+    let sample = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+    if sample.contains(&0) {
+        return true;
+    }
+    let control = sample
+        .iter()
+        .filter(|&&b| b < 0x20 && !matches!(b, b'\n' | b'\r' | b'\t' | 0x0c))
+        .count();
+    control * 10 > sample.len()
+}
+
+/// Split one delimited line, honouring double-quoted fields.
+fn split_record(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// A markdown table when the data is small and rectangular, with the first
+/// row as the header. Quoted newlines make records span lines, which this
+/// doesn't follow, so such files fall back to a code block.
+fn markdown_table(text: &str, delimiter: char) -> Option<String> {
+    let rows: Vec<Vec<String>> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| split_record(line, delimiter))
+        .collect();
+    let columns = rows.first()?.len();
+    if rows.len() < 2
+        || rows.len() > TABLE_MAX_ROWS + 1
+        || !(2..=TABLE_MAX_COLUMNS).contains(&columns)
+        || rows.iter().any(|row| row.len() != columns)
+    {
+        return None;
+    }
+
+    let render_row = |row: &[String]| {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| cell.trim().replace('|', "\\|"))
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+    let mut lines = vec![render_row(&rows[0])];
+    lines.push(format!("|{}", " --- |".repeat(columns)));
+    lines.extend(rows[1..].iter().map(|row| render_row(row)));
+    Some(lines.join("\n"))
+}
+
+/// `text` in a fence longer than any backtick run inside it.
+fn fenced(text: &str, language: &str) -> String {
+    let longest_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{}{}\n{}\n{}", fence, language, text.trim_end(), fence)
+}
+
+/// The note for a file named `filename` with extension `ext`.
+fn note_content(filename: &str, ext: &str, text: &str) -> String {
+    let body = match ext {
+        "csv" => markdown_table(text, ',').unwrap_or_else(|| fenced(text, "csv")),
+        "tsv" => markdown_table(text, '\t').unwrap_or_else(|| fenced(text, "tsv")),
+        "log" => fenced(text, "text"),
+        _ => text.trim_end().to_string(),
+    };
+    format!("# {}\n\n{}\n", filename, body)
+}
+
+/// Read a dropped text file into a new note in `folder`.
+#[tauri::command]
+pub fn import_text_file(
+    app: AppHandle,
+    folder: String,
+    file_path: String,
+) -> Result<NoteSaved, String> {
+    let path = Path::new(&file_path);
+    if !path.is_absolute() {
+        return Err("File path must be absolute".to_string());
+    }
+    if !path.is_file() {
+        return Err("Dropped file does not exist".to_string());
+    }
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+    if !TEXT_EXTENSIONS.contains(&ext.as_str()) {
+        return Err("Only .txt, .csv, .tsv and .log files can be imported".to_string());
+    }
+
+    let size = fs::metadata(path).map_err(|e| e.to_string())?.len();
+    if size > MAX_TEXT_IMPORT_BYTES {
+        return Err(format!(
+            "File is too large to import ({:.1} MB, limit {} MB)",
+            size as f64 / (1024.0 * 1024.0),
+            MAX_TEXT_IMPORT_BYTES / (1024 * 1024)
+        ));
+    }
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read dropped file: {}", e))?;
+    if looks_binary(&bytes) {
+        return Err("File looks binary, not text".to_string());
+    }
+
+    let filename = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let content = note_content(&filename, &ext, &decode(&bytes));
+    let saved = notes::save_note_inner(folder, content.clone())?;
+    notes::post_save_processing(&app, &saved, &content);
+    let _ = app.emit(NOTE_SAVED_EVENT, &saved);
+    Ok(saved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bom_is_stripped_and_invalid_utf8_is_replaced() {
+        assert_eq!(decode(b"\xef\xbb\xbfhello"), "hello");
+        assert_eq!(decode(b"caf\xe9"), "caf\u{fffd}");
+    }
+
+    #[test]
+    fn binary_files_are_detected() {
+        assert!(looks_binary(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"));
+        assert!(looks_binary(&[0x01; 64]));
+        assert!(!looks_binary(b"line one\nline\ttwo\r\n"));
+        assert!(!looks_binary("Grüße\n".as_bytes()));
+    }
+
+    #[test]
+    fn small_csv_becomes_a_table() {
+        let csv = "name,notes\nAda,\"first, programmer\"\nLinus,a|b\n";
+        assert_eq!(
+            note_content("people.csv", "csv", csv),
+            "# people.csv\n\n| name | notes |\n| --- | --- |\n| Ada | first, programmer |\n| Linus | a\\|b |\n"
+        );
+    }
+
+    #[test]
+    fn ragged_csv_and_logs_are_fenced() {
+        assert_eq!(
+            note_content("data.csv", "csv", "a,b\n1,2,3\n"),
+            "# data.csv\n\n```csv\na,b\n1,2,3\n```\n"
+        );
+        assert_eq!(
+            note_content("build.log", "log", "ok\n````\n"),
+            "# build.log\n\n`````text\nok\n````\n`````\n"
+        );
+    }
+}
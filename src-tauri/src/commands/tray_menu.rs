@@ -0,0 +1,537 @@
+/// Tray menu construction. The tray icon and its event handler live in the
+/// app binary; the menu is built here so note and folder commands can ask
+/// for a rebuild when what it lists goes stale.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use chrono::Local;
+use tauri::image::Image;
+use tauri::menu::{IconMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::{AppHandle, Manager, Wry};
+
+use super::folders;
+use super::git_share::{self, GitSyncStatus};
+use super::index::{self, NoteEntry, NoteIndex};
+use super::settings;
+use super::stats;
+use super::sticked_notes::{self, StickedNote};
+use super::templates;
+use crate::state::{AppState, TraySyncItems};
+
+pub const TRAY_ID: &str = "main-tray";
+pub const RECENT_NOTE_ID_PREFIX: &str = "recent-note:";
+/// Recent notes that have a stable note id are keyed by it, so a move
+/// after the menu was built doesn't break the item.
+pub const RECENT_NOTE_BY_ID_PREFIX: &str = "recent-note-id:";
+pub const NEW_NOTE_IN_ID_PREFIX: &str = "new-note-in:";
+pub const SYNC_SETTINGS_ID: &str = "sync_settings";
+pub const STATS_ID: &str = "open_stats";
+pub const STICKED_NOTE_ID_PREFIX: &str = "sticked-note:";
+pub const STICKED_SHOW_ALL_ID: &str = "sticked_show_all";
+pub const STICKED_HIDE_ALL_ID: &str = "sticked_hide_all";
+pub const STICKED_SAVE_CLOSE_ALL_ID: &str = "sticked_save_close_all";
+pub const STICKED_DISCARD_ALL_ID: &str = "sticked_discard_all";
+pub const STICKED_TEMPLATE_ID_PREFIX: &str = "sticked-template:";
+
+const RECENT_NOTES_LIMIT: usize = 5;
+const RECENT_TITLE_MAX_CHARS: usize = 40;
+const REBUILD_DEBOUNCE_MS: u64 = 500;
+const SYNC_ERROR_MAX_CHARS: usize = 60;
+const FOLDER_DOT_SIZE: u32 = 16;
+const FOLDER_DOT_RADIUS: f64 = 5.0;
+/// Shown until the startup task has computed the real streak.
+const STREAK_PLACEHOLDER_LABEL: &str = "Streak: …";
+
+static REBUILD_PENDING: AtomicBool = AtomicBool::new(false);
+
+pub fn build_tray_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let index = app.state::<NoteIndex>();
+    let streak_label = stats::capture_streak_label(Some(index.inner())).unwrap_or_else(|e| {
+        log::error!("Failed to compute capture streak: {}", e);
+        stats::format_capture_streak_label(0, false)
+    });
+    build_menu(app, &streak_label)
+}
+
+/// The menu for app launch: a placeholder streak instead of walking the
+/// notes folder. `stats::refresh_tray_streak` fills it in later.
+pub fn build_startup_tray_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    build_menu(app, STREAK_PLACEHOLDER_LABEL)
+}
+
+fn build_menu(app: &AppHandle, streak_label: &str) -> tauri::Result<Menu<Wry>> {
+    let index = app.state::<NoteIndex>();
+    let new_note = MenuItem::with_id(app, "new_note", "New Note", true, None::<&str>)?;
+    let new_note_in = build_new_note_in_submenu(app)?;
+    let recent = build_recent_submenu(app, &index)?;
+    let sticked = build_sticked_submenu(app)?;
+    let capture_streak =
+        MenuItem::with_id(app, "capture_streak", streak_label, false, None::<&str>)?;
+    let stats = MenuItem::with_id(app, STATS_ID, "Stats…", true, None::<&str>)?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let quit = MenuItem::with_id(app, "quit", "Quit Stik", true, None::<&str>)?;
+
+    let menu = Menu::with_items(
+        app,
+        &[
+            &new_note,
+            &new_note_in,
+            &recent,
+            &sticked,
+            &capture_streak,
+            &stats,
+        ],
+    )?;
+
+    let sync_items = match git_share::git_get_sync_status() {
+        Ok(status) if status.enabled => Some(build_sync_items(app, &status)?),
+        _ => None,
+    };
+    if let Some(items) = &sync_items {
+        menu.append(&items.status)?;
+    }
+    menu.append(&separator)?;
+    menu.append(&quit)?;
+
+    let state = app.state::<AppState>();
+    *state
+        .tray_streak_item
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = Some(capture_streak);
+    *state
+        .tray_sync_items
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = sync_items;
+
+    Ok(menu)
+}
+
+/// Mirror a git sync status change in the tray. Safe to call from the sync
+/// worker: menu updates are handed to the main thread. Toggling sharing on or
+/// off rebuilds the menu so the status shows up or disappears.
+pub fn update_sync_status(app: &AppHandle, status: &GitSyncStatus) {
+    let shown = app
+        .state::<AppState>()
+        .tray_sync_items
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .is_some();
+    if shown != status.enabled {
+        rebuild_tray_menu(app);
+        return;
+    }
+    if !status.enabled {
+        return;
+    }
+
+    let label = git_share::sync_status_label(status, Local::now());
+    let detail = sync_detail_label(status);
+    let handle = app.clone();
+    let _ = app.run_on_main_thread(move || {
+        let items = handle
+            .state::<AppState>()
+            .tray_sync_items
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        if let Some(items) = items {
+            let _ = items.status.set_text(label);
+            let _ = items.detail.set_text(detail);
+        }
+    });
+}
+
+/// Swap in a freshly built tray menu. Calls within the debounce window
+/// collapse into one rebuild, so bulk saves don't thrash the menu.
+pub fn rebuild_tray_menu(app: &AppHandle) {
+    if REBUILD_PENDING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app = app.clone();
+    let spawned = thread::Builder::new()
+        .name("stik-tray-rebuild".to_string())
+        .spawn(move || {
+            thread::sleep(Duration::from_millis(REBUILD_DEBOUNCE_MS));
+            REBUILD_PENDING.store(false, Ordering::SeqCst);
+
+            let Some(tray) = app.tray_by_id(TRAY_ID) else {
+                return;
+            };
+            match build_tray_menu(&app) {
+                Ok(menu) => {
+                    if let Err(e) = tray.set_menu(Some(menu)) {
+                        log::error!("Failed to set tray menu: {}", e);
+                    }
+                }
+                Err(e) => log::error!("Failed to rebuild tray menu: {}", e),
+            }
+        });
+    if spawned.is_err() {
+        REBUILD_PENDING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Folder behind a "New Note in" menu item id.
+pub fn new_note_folder(menu_id: &str) -> Option<&str> {
+    menu_id.strip_prefix(NEW_NOTE_IN_ID_PREFIX)
+}
+
+/// Sticked note id behind a "Sticked Notes" menu item id.
+pub fn sticked_note_id(menu_id: &str) -> Option<&str> {
+    menu_id.strip_prefix(STICKED_NOTE_ID_PREFIX)
+}
+
+/// Template id behind a "New Sticky from Template" menu item id.
+pub fn sticked_template_id(menu_id: &str) -> Option<&str> {
+    menu_id.strip_prefix(STICKED_TEMPLATE_ID_PREFIX)
+}
+
+/// The note behind a "Recent" menu item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecentNote<'a> {
+    Id(&'a str),
+    Path(&'a str),
+}
+
+/// Note behind a "Recent" menu item id.
+pub fn recent_note(menu_id: &str) -> Option<RecentNote<'_>> {
+    if let Some(id) = menu_id.strip_prefix(RECENT_NOTE_BY_ID_PREFIX) {
+        return Some(RecentNote::Id(id));
+    }
+    menu_id
+        .strip_prefix(RECENT_NOTE_ID_PREFIX)
+        .map(RecentNote::Path)
+}
+
+fn recent_menu_id(entry: &NoteEntry) -> String {
+    match &entry.id {
+        Some(id) => format!("{}{}", RECENT_NOTE_BY_ID_PREFIX, id),
+        None => format!("{}{}", RECENT_NOTE_ID_PREFIX, entry.path),
+    }
+}
+
+/// Folders in `list_folders()` order, each with its color dot.
+fn build_new_note_in_submenu(app: &AppHandle) -> tauri::Result<Submenu<Wry>> {
+    let submenu = Submenu::with_id(app, "new_note_in", "New Note in", true)?;
+    let folder_names = folders::list_folders().unwrap_or_else(|e| {
+        log::error!("Failed to list folders for tray: {}", e);
+        Vec::new()
+    });
+    let folder_colors = settings::load_settings_from_file()
+        .map(|s| s.folder_colors)
+        .unwrap_or_default();
+
+    for folder in folder_names {
+        let rgb = folder_dot_rgb(folder_colors.get(&folder).map(String::as_str));
+        submenu.append(&IconMenuItem::with_id(
+            app,
+            format!("{}{}", NEW_NOTE_IN_ID_PREFIX, folder),
+            &folder,
+            true,
+            Some(folder_dot_icon(rgb)),
+            None::<&str>,
+        )?)?;
+    }
+
+    Ok(submenu)
+}
+
+/// Dot colors from src/utils/folderColors.ts; unknown keys fall back to
+/// coral like `getFolderColor`.
+fn folder_dot_rgb(color_key: Option<&str>) -> (u8, u8, u8) {
+    match color_key {
+        Some("red") => (239, 68, 68),
+        Some("amber") => (245, 158, 11),
+        Some("green") => (34, 197, 94),
+        Some("teal") => (20, 184, 166),
+        Some("blue") => (59, 130, 246),
+        Some("purple") => (168, 85, 247),
+        Some("pink") => (236, 72, 153),
+        _ => (232, 112, 95),
+    }
+}
+
+/// A small anti-aliased filled circle on a transparent square.
+fn folder_dot_icon((r, g, b): (u8, u8, u8)) -> Image<'static> {
+    let size = FOLDER_DOT_SIZE;
+    let center = f64::from(size) / 2.0;
+    let mut rgba = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let dx = f64::from(x) + 0.5 - center;
+            let dy = f64::from(y) + 0.5 - center;
+            let coverage = (FOLDER_DOT_RADIUS + 0.5 - (dx * dx + dy * dy).sqrt()).clamp(0.0, 1.0);
+            rgba.extend_from_slice(&[r, g, b, (coverage * 255.0).round() as u8]);
+        }
+    }
+    Image::new_owned(rgba, size, size)
+}
+
+fn build_recent_submenu(app: &AppHandle, index: &NoteIndex) -> tauri::Result<Submenu<Wry>> {
+    let recent = Submenu::with_id(app, "recent", "Recent", true)?;
+    let entries = index.list(None).unwrap_or_else(|e| {
+        log::error!("Failed to list recent notes: {}", e);
+        Vec::new()
+    });
+
+    let picked = recent_entries(entries);
+    if picked.is_empty() {
+        recent.append(&MenuItem::with_id(
+            app,
+            "recent_empty",
+            "No notes yet",
+            false,
+            None::<&str>,
+        )?)?;
+    }
+    for entry in picked {
+        recent.append(&MenuItem::with_id(
+            app,
+            recent_menu_id(&entry),
+            truncate_title(&entry.title),
+            true,
+            None::<&str>,
+        )?)?;
+    }
+
+    Ok(recent)
+}
+
+fn build_sync_items(app: &AppHandle, status: &GitSyncStatus) -> tauri::Result<TraySyncItems> {
+    let detail = MenuItem::with_id(
+        app,
+        SYNC_SETTINGS_ID,
+        sync_detail_label(status),
+        true,
+        None::<&str>,
+    )?;
+    let status = Submenu::with_id_and_items(
+        app,
+        "sync_status",
+        git_share::sync_status_label(status, Local::now()),
+        true,
+        &[&detail],
+    )?;
+    Ok(TraySyncItems { status, detail })
+}
+
+/// The last error when there is one, otherwise a plain settings shortcut.
+/// Either way clicking it opens settings.
+fn sync_detail_label(status: &GitSyncStatus) -> String {
+    match status.last_error.as_deref().map(str::trim) {
+        Some(error) if !error.is_empty() => {
+            let first_line = error.lines().next().unwrap_or(error);
+            if first_line.chars().count() > SYNC_ERROR_MAX_CHARS {
+                let truncated: String = first_line.chars().take(SYNC_ERROR_MAX_CHARS - 1).collect();
+                format!("{}…", truncated.trim_end())
+            } else {
+                first_line.to_string()
+            }
+        }
+        _ => "Sync Settings…".to_string(),
+    }
+}
+
+fn build_sticked_submenu(app: &AppHandle) -> tauri::Result<Submenu<Wry>> {
+    let notes = sticked_notes::list_sticked_notes().unwrap_or_else(|e| {
+        log::error!("Failed to list sticked notes: {}", e);
+        Vec::new()
+    });
+
+    let sticked = Submenu::with_id(app, "sticked_notes", sticked_submenu_label(&notes), true)?;
+    for note in &notes {
+        sticked.append(&MenuItem::with_id(
+            app,
+            format!("{}{}", STICKED_NOTE_ID_PREFIX, note.id),
+            truncate_title(&index::extract_title(&note.content)),
+            true,
+            None::<&str>,
+        )?)?;
+    }
+    if !notes.is_empty() {
+        sticked.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+    sticked.append(&build_sticky_template_submenu(app)?)?;
+    sticked.append(&PredefinedMenuItem::separator(app)?)?;
+    sticked.append(&MenuItem::with_id(
+        app,
+        STICKED_SHOW_ALL_ID,
+        "Show All",
+        !notes.is_empty(),
+        None::<&str>,
+    )?)?;
+    sticked.append(&MenuItem::with_id(
+        app,
+        STICKED_HIDE_ALL_ID,
+        "Hide All",
+        !notes.is_empty(),
+        None::<&str>,
+    )?)?;
+    sticked.append(&PredefinedMenuItem::separator(app)?)?;
+    sticked.append(&MenuItem::with_id(
+        app,
+        STICKED_SAVE_CLOSE_ALL_ID,
+        "Save & Close All",
+        !notes.is_empty(),
+        None::<&str>,
+    )?)?;
+    sticked.append(&MenuItem::with_id(
+        app,
+        STICKED_DISCARD_ALL_ID,
+        "Close All Without Saving…",
+        !notes.is_empty(),
+        None::<&str>,
+    )?)?;
+
+    Ok(sticked)
+}
+
+/// Built-in and custom templates, each pinning a new sticky.
+fn build_sticky_template_submenu(app: &AppHandle) -> tauri::Result<Submenu<Wry>> {
+    let submenu = Submenu::with_id(
+        app,
+        "sticked_from_template",
+        "New Sticky from Template",
+        true,
+    )?;
+    let templates = templates::list_templates().unwrap_or_else(|e| {
+        log::error!("Failed to list templates for tray: {}", e);
+        Vec::new()
+    });
+    for template in templates {
+        submenu.append(&MenuItem::with_id(
+            app,
+            format!("{}{}", STICKED_TEMPLATE_ID_PREFIX, template.id),
+            truncate_title(&template.name),
+            true,
+            None::<&str>,
+        )?)?;
+    }
+    Ok(submenu)
+}
+
+fn sticked_submenu_label(notes: &[StickedNote]) -> String {
+    format!("Sticked Notes ({})", notes.len())
+}
+
+fn recent_entries(mut entries: Vec<NoteEntry>) -> Vec<NoteEntry> {
+    entries.sort_by(|a, b| b.created.cmp(&a.created));
+    entries.truncate(RECENT_NOTES_LIMIT);
+    entries
+}
+
+fn truncate_title(title: &str) -> String {
+    let title = title.trim_start_matches('#').trim();
+    if title.chars().count() <= RECENT_TITLE_MAX_CHARS {
+        return title.to_string();
+    }
+    let truncated: String = title.chars().take(RECENT_TITLE_MAX_CHARS - 1).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, created: &str) -> NoteEntry {
+        NoteEntry {
+            path: path.to_string(),
+            filename: path.to_string(),
+            folder: "Inbox".to_string(),
+            title: "Title".to_string(),
+            preview: String::new(),
+            created: created.to_string(),
+            content_len: 0,
+            locked: false,
+            word_count: 0,
+            reading_minutes: 0,
+            language: "en".to_string(),
+            truncated_in_index: false,
+            id: None,
+            pinned: false,
+            created_date: None,
+            open_tasks: 0,
+            done_tasks: 0,
+        }
+    }
+
+    #[test]
+    fn recent_entries_keeps_newest_five() {
+        let entries = (1..=7)
+            .map(|day| entry(&format!("note-{}", day), &format!("2026020{}-120000", day)))
+            .collect();
+
+        let paths: Vec<String> = recent_entries(entries)
+            .into_iter()
+            .map(|e| e.path)
+            .collect();
+        assert_eq!(paths, ["note-7", "note-6", "note-5", "note-4", "note-3"]);
+    }
+
+    #[test]
+    fn titles_are_truncated_on_char_boundaries() {
+        assert_eq!(truncate_title("# Short"), "Short");
+
+        let long = "é".repeat(60);
+        let truncated = truncate_title(&long);
+        assert_eq!(truncated.chars().count(), RECENT_TITLE_MAX_CHARS);
+        assert!(truncated.ends_with('…'));
+    }
+
+    #[test]
+    fn recent_menu_ids_round_trip_ids_and_paths() {
+        let id = format!("{}{}", RECENT_NOTE_ID_PREFIX, "/Users/me/Stik/Inbox/a.md");
+        assert_eq!(
+            recent_note(&id),
+            Some(RecentNote::Path("/Users/me/Stik/Inbox/a.md"))
+        );
+        let id = format!("{}{}", RECENT_NOTE_BY_ID_PREFIX, "1b4e28ba");
+        assert_eq!(recent_note(&id), Some(RecentNote::Id("1b4e28ba")));
+        assert_eq!(recent_note("new_note"), None);
+    }
+
+    #[test]
+    fn sync_detail_shows_first_error_line() {
+        let mut status = GitSyncStatus {
+            enabled: true,
+            linked_folder: None,
+            remote_url: None,
+            branch: "main".to_string(),
+            repository_layout: "stik_root".to_string(),
+            repo_initialized: true,
+            pending_changes: false,
+            syncing: false,
+            last_sync_at: None,
+            last_error: None,
+        };
+        assert_eq!(sync_detail_label(&status), "Sync Settings…");
+
+        status.last_error = Some("git push failed\nhint: fetch first".to_string());
+        assert_eq!(sync_detail_label(&status), "git push failed");
+    }
+
+    #[test]
+    fn sticked_menu_ids_and_label() {
+        let id = format!("{}{}", STICKED_NOTE_ID_PREFIX, "3f2a");
+        assert_eq!(sticked_note_id(&id), Some("3f2a"));
+        assert_eq!(sticked_note_id(RECENT_NOTE_ID_PREFIX), None);
+        let template = format!("{}builtin:todo", STICKED_TEMPLATE_ID_PREFIX);
+        assert_eq!(sticked_template_id(&template), Some("builtin:todo"));
+        assert_eq!(sticked_note_id(&template), None);
+        assert_eq!(sticked_submenu_label(&[]), "Sticked Notes (0)");
+    }
+
+    #[test]
+    fn folder_items_round_trip_and_dots_are_round() {
+        let id = format!("{}{}", NEW_NOTE_IN_ID_PREFIX, "Work");
+        assert_eq!(new_note_folder(&id), Some("Work"));
+        assert_eq!(folder_dot_rgb(Some("unknown")), folder_dot_rgb(None));
+
+        let icon = folder_dot_icon(folder_dot_rgb(Some("blue")));
+        let alpha = |x: u32, y: u32| icon.rgba()[((y * FOLDER_DOT_SIZE + x) * 4 + 3) as usize];
+        assert_eq!(alpha(8, 8), 255);
+        assert_eq!(alpha(0, 0), 0);
+    }
+}
@@ -0,0 +1,230 @@
+/// Clickable macOS notifications through UNUserNotificationCenter.
+///
+/// `macos_notify::show` goes through osascript, which can't report clicks.
+/// Notifications sent here carry a `<kind>:<payload>` request identifier;
+/// clicking one hands `kind` and `payload` to the handler passed to
+/// `register`. When notifications aren't authorized, or Stik runs outside
+/// an app bundle (e.g. `cargo run`), `show` falls back to osascript.
+use tauri::AppHandle;
+
+use super::macos_notify;
+
+/// Called on the main thread with `(kind, payload)` when a notification
+/// sent through `show` is clicked.
+pub type ClickHandler = fn(&AppHandle, &str, &str);
+
+pub fn notification_identifier(kind: &str, payload: &str) -> String {
+    format!("{}:{}", kind, payload)
+}
+
+/// Split an identifier built by `notification_identifier`.
+pub fn parse_identifier(identifier: &str) -> Option<(&str, &str)> {
+    identifier
+        .split_once(':')
+        .filter(|(kind, _)| !kind.is_empty())
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use super::*;
+    use block2::{Block, RcBlock};
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyObject, Bool, NSObject};
+    use objc2::{class, define_class, msg_send, ClassType};
+    use objc2_foundation::NSString;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::OnceLock;
+
+    #[link(name = "UserNotifications", kind = "framework")]
+    extern "C" {}
+
+    const AUTHORIZATION_OPTIONS: usize = (1 << 1) | (1 << 2); // sound | alert
+    const PRESENTATION_OPTIONS: usize = (1 << 1) | (1 << 3) | (1 << 4); // sound | list | banner
+    const DEFAULT_ACTION: &str = "com.apple.UNNotificationDefaultActionIdentifier";
+
+    static APP: OnceLock<(AppHandle, ClickHandler)> = OnceLock::new();
+    static DENIED: AtomicBool = AtomicBool::new(false);
+
+    define_class!(
+        #[unsafe(super(NSObject))]
+        #[name = "StikNotificationDelegate"]
+        struct NotificationDelegate;
+
+        impl NotificationDelegate {
+            #[unsafe(method(userNotificationCenter:didReceiveNotificationResponse:withCompletionHandler:))]
+            fn did_receive_response(
+                &self,
+                _center: &AnyObject,
+                response: &AnyObject,
+                completion: &Block<dyn Fn()>,
+            ) {
+                if let Some(identifier) = clicked_identifier(response) {
+                    if let (Some((app, handler)), Some((kind, payload))) =
+                        (APP.get(), parse_identifier(&identifier))
+                    {
+                        handler(app, kind, payload);
+                    }
+                }
+                completion.call(());
+            }
+
+            // Without this, notifications are silently dropped while a
+            // Stik window is focused.
+            #[unsafe(method(userNotificationCenter:willPresentNotification:withCompletionHandler:))]
+            fn will_present(
+                &self,
+                _center: &AnyObject,
+                _notification: &AnyObject,
+                completion: &Block<dyn Fn(usize)>,
+            ) {
+                completion.call((PRESENTATION_OPTIONS,));
+            }
+        }
+    );
+
+    fn clicked_identifier(response: &AnyObject) -> Option<String> {
+        unsafe {
+            let action: Retained<NSString> = msg_send![response, actionIdentifier];
+            if action.to_string() != DEFAULT_ACTION {
+                return None;
+            }
+            let notification: Retained<AnyObject> = msg_send![response, notification];
+            let request: Retained<AnyObject> = msg_send![&*notification, request];
+            let identifier: Retained<NSString> = msg_send![&*request, identifier];
+            Some(identifier.to_string())
+        }
+    }
+
+    /// UNUserNotificationCenter throws when the process has no bundle.
+    fn has_bundle_identifier() -> bool {
+        unsafe {
+            let bundle: Retained<AnyObject> = msg_send![class!(NSBundle), mainBundle];
+            let identifier: Option<Retained<NSString>> = msg_send![&*bundle, bundleIdentifier];
+            identifier.is_some()
+        }
+    }
+
+    fn center() -> Option<Retained<AnyObject>> {
+        if !has_bundle_identifier() {
+            return None;
+        }
+        unsafe { msg_send![class!(UNUserNotificationCenter), currentNotificationCenter] }
+    }
+
+    pub fn register(app: &AppHandle, handler: ClickHandler) {
+        let _ = APP.set((app.clone(), handler));
+        let Some(center) = center() else {
+            return;
+        };
+
+        unsafe {
+            let delegate: Retained<NotificationDelegate> =
+                msg_send![NotificationDelegate::class(), new];
+            let _: () = msg_send![&*center, setDelegate: &*delegate];
+            // The center only holds a weak reference.
+            std::mem::forget(delegate);
+
+            let on_authorization = RcBlock::new(|granted: Bool, _error: *mut AnyObject| {
+                DENIED.store(!granted.as_bool(), Ordering::SeqCst);
+            });
+            let _: () = msg_send![
+                &*center,
+                requestAuthorizationWithOptions: AUTHORIZATION_OPTIONS,
+                completionHandler: &*on_authorization
+            ];
+        }
+    }
+
+    pub fn show(
+        kind: &str,
+        payload: &str,
+        title: &str,
+        subtitle: &str,
+        body: &str,
+    ) -> Result<(), String> {
+        let center = match center() {
+            Some(center) if !DENIED.load(Ordering::SeqCst) => center,
+            _ => return macos_notify::show(title, subtitle, body),
+        };
+
+        let identifier = notification_identifier(kind, payload);
+        let fallback = (title.to_string(), subtitle.to_string(), body.to_string());
+        unsafe {
+            let content: Retained<AnyObject> = msg_send![class!(UNMutableNotificationContent), new];
+            let _: () = msg_send![&*content, setTitle: &*NSString::from_str(title)];
+            let _: () = msg_send![&*content, setSubtitle: &*NSString::from_str(subtitle)];
+            let _: () = msg_send![&*content, setBody: &*NSString::from_str(body)];
+
+            let request: Retained<AnyObject> = msg_send![
+                class!(UNNotificationRequest),
+                requestWithIdentifier: &*NSString::from_str(&identifier),
+                content: &*content,
+                trigger: None::<&AnyObject>
+            ];
+
+            // Permission revoked since launch: show it the old way instead.
+            let on_added = RcBlock::new(move |error: *mut AnyObject| {
+                if !error.is_null() {
+                    let (title, subtitle, body) = &fallback;
+                    let _ = macos_notify::show(title, subtitle, body);
+                }
+            });
+            let _: () = msg_send![
+                &*center,
+                addNotificationRequest: &*request,
+                withCompletionHandler: &*on_added
+            ];
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod imp {
+    use super::*;
+
+    pub fn register(_app: &AppHandle, _handler: ClickHandler) {}
+
+    pub fn show(
+        _kind: &str,
+        _payload: &str,
+        title: &str,
+        subtitle: &str,
+        body: &str,
+    ) -> Result<(), String> {
+        macos_notify::show(title, subtitle, body)
+    }
+}
+
+/// Become the notification delegate and ask for permission. Call once
+/// from setup, before any notification is shown.
+pub fn register(app: &AppHandle, handler: ClickHandler) {
+    imp::register(app, handler);
+}
+
+/// Show a notification that reports clicks as `(kind, payload)`.
+pub fn show(
+    kind: &str,
+    payload: &str,
+    title: &str,
+    subtitle: &str,
+    body: &str,
+) -> Result<(), String> {
+    imp::show(kind, payload, title, subtitle, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_keep_colons_in_the_payload() {
+        let identifier = notification_identifier("on-this-day", "/Volumes/A:B/note.md");
+        assert_eq!(
+            parse_identifier(&identifier),
+            Some(("on-this-day", "/Volumes/A:B/note.md"))
+        );
+        assert_eq!(parse_identifier(":orphan"), None);
+        assert_eq!(parse_identifier("no-kind"), None);
+    }
+}
@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Map, Value};
 use std::fs;
+use std::io::Write;
 use std::path::Path;
 
-const CURRENT_VERSION: u32 = 1;
+pub const CURRENT_VERSION: u32 = 2;
+
+/// Theme ids the pre-v2 `theme_mode` field could hold.
+const LEGACY_THEME_MODES: [&str; 3] = ["system", "light", "dark"];
 
 #[derive(Debug, Serialize, Deserialize)]
 struct VersionedStore {
@@ -26,6 +30,13 @@ pub fn load_versioned<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Optio
         if obj.contains_key("version") && obj.contains_key("data") {
             let store: VersionedStore =
                 serde_json::from_value(value).map_err(|e| e.to_string())?;
+            if store.version > CURRENT_VERSION {
+                return Err(format!(
+                    "{} was created by a newer Stik (format version {}); update Stik to open it",
+                    path.display(),
+                    store.version
+                ));
+            }
             let migrated = migrate(store.version, store.data)?;
             let result: T = serde_json::from_value(migrated).map_err(|e| e.to_string())?;
             return Ok(Some(result));
@@ -38,6 +49,22 @@ pub fn load_versioned<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Optio
     Ok(Some(result))
 }
 
+/// The format version `path` was written with: 0 for the legacy
+/// unversioned format, `None` when the file doesn't exist.
+pub fn stored_version(path: &Path) -> Result<Option<u32>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let value: Value = serde_json::from_str(&raw).map_err(|e| e.to_string())?;
+    let version = value
+        .as_object()
+        .filter(|obj| obj.contains_key("data"))
+        .and_then(|obj| obj.get("version")?.as_u64())
+        .unwrap_or(0);
+    Ok(Some(version as u32))
+}
+
 /// Save data in versioned format.
 pub fn save_versioned<T: Serialize>(path: &Path, data: &T) -> Result<(), String> {
     let data_value = serde_json::to_value(data).map_err(|e| e.to_string())?;
@@ -45,16 +72,48 @@ pub fn save_versioned<T: Serialize>(path: &Path, data: &T) -> Result<(), String>
         version: CURRENT_VERSION,
         data: data_value,
     };
-    let content = serde_json::to_string_pretty(&store).map_err(|e| e.to_string())?;
+    atomic_write_json(path, &store)
+}
 
-    // Atomic write via temp file
-    let tmp_path = path.with_extension("json.tmp");
-    fs::write(&tmp_path, &content).map_err(|e| e.to_string())?;
-    fs::rename(&tmp_path, path).map_err(|e| e.to_string())
+/// Write `data` as pretty-printed JSON, replacing `path` atomically.
+pub fn atomic_write_json<T: Serialize>(path: &Path, data: &T) -> Result<(), String> {
+    let content = serde_json::to_vec_pretty(data).map_err(|e| e.to_string())?;
+    atomic_write(path, &content)
+}
+
+/// Replace `path` with `bytes` so that a crash leaves either the old or the
+/// new file, never a partial or empty one: write a uniquely named temp file
+/// in the same directory, fsync it, rename it over `path`, then fsync the
+/// directory so the rename itself is durable.
+pub fn atomic_write(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("Not a file path: {}", path.display()))?
+        .to_string_lossy();
+    // Random suffix: concurrent writers and crashed leftovers never collide.
+    let tmp_path = dir.join(format!(".{}.{:016x}.tmp", file_name, rand::random::<u64>()));
+
+    let written = (|| {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)?;
+        fs::File::open(dir)?.sync_all()
+    })();
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(format!("Failed to write {}: {}", path.display(), e));
+    }
+    Ok(())
 }
 
 /// Apply migrations from `from_version` to CURRENT_VERSION.
 /// Version 0 → 1 is a no-op (data format unchanged, just wrapping in envelope).
+/// Version 1 → 2 only changes settings; other stores pass through.
 fn migrate(from_version: u32, data: Value) -> Result<Value, String> {
     let mut current = data;
     let mut version = from_version;
@@ -62,6 +121,7 @@ fn migrate(from_version: u32, data: Value) -> Result<Value, String> {
     while version < CURRENT_VERSION {
         current = match version {
             0 => migrate_v0_to_v1(current)?,
+            1 => migrate_v1_to_v2(current)?,
             _ => return Err(format!("Unknown migration version: {}", version)),
         };
         version += 1;
@@ -74,3 +134,188 @@ fn migrate(from_version: u32, data: Value) -> Result<Value, String> {
 fn migrate_v0_to_v1(data: Value) -> Result<Value, String> {
     Ok(data)
 }
+
+/// v1 → v2 (settings): `theme_mode` is folded into `active_theme` and
+/// dropped, and the `viewing_window_size` / `capture_window_size` tuples
+/// move into the `window_sizes` map. Stores without those keys are unchanged.
+fn migrate_v1_to_v2(mut data: Value) -> Result<Value, String> {
+    let Some(obj) = data.as_object_mut() else {
+        return Ok(data);
+    };
+
+    if let Some(theme_mode) = obj.remove("theme_mode") {
+        let active_empty = obj
+            .get("active_theme")
+            .and_then(Value::as_str)
+            .is_none_or(str::is_empty);
+        let legacy = theme_mode
+            .as_str()
+            .filter(|mode| LEGACY_THEME_MODES.contains(mode));
+        if let (true, Some(mode)) = (active_empty, legacy) {
+            obj.insert("active_theme".to_string(), Value::String(mode.to_string()));
+        }
+    }
+
+    for (legacy_key, window) in [
+        ("viewing_window_size", "viewing"),
+        ("capture_window_size", "capture"),
+    ] {
+        let Some(size) = obj.remove(legacy_key) else {
+            continue;
+        };
+        if size.is_null() {
+            continue;
+        }
+        // A `null` map is as good as a missing one.
+        let sizes = obj.entry("window_sizes").or_insert(Value::Null);
+        if sizes.is_null() {
+            *sizes = Value::Object(Map::new());
+        }
+        if let Some(sizes) = sizes.as_object_mut() {
+            sizes.entry(window).or_insert(size);
+        }
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::settings::StikSettings;
+    use crate::commands::test_support::temp_dir;
+    use std::path::PathBuf;
+
+    fn fixture(label: &str, json: &str) -> PathBuf {
+        let path = temp_dir(&format!("versioning-{}", label)).join("store.json");
+        fs::write(&path, json).expect("write fixture");
+        path
+    }
+
+    fn remove_fixture(path: &Path) {
+        if let Some(dir) = path.parent() {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+
+    fn load_settings(label: &str, json: &str) -> Result<Option<StikSettings>, String> {
+        let path = fixture(label, json);
+        let loaded = load_versioned::<StikSettings>(&path);
+        remove_fixture(&path);
+        loaded
+    }
+
+    const V1_SETTINGS: &str = r#"{
+        "shortcut_mappings": [],
+        "default_folder": "Inbox",
+        "theme_mode": "dark",
+        "active_theme": "",
+        "viewing_window_size": [520.0, 360.0],
+        "capture_window_size": [400.0, 300.0]
+    }"#;
+
+    #[test]
+    fn unversioned_settings_migrate_to_v2() {
+        let settings = load_settings("v0", V1_SETTINGS).unwrap().expect("loaded");
+        assert_eq!(settings.active_theme, "dark");
+        assert_eq!(settings.window_size("viewing"), Some((520.0, 360.0)));
+        assert_eq!(settings.window_size("capture"), Some((400.0, 300.0)));
+    }
+
+    #[test]
+    fn v1_settings_migrate_to_v2() {
+        let json = format!(r#"{{"version": 1, "data": {}}}"#, V1_SETTINGS);
+        let settings = load_settings("v1", &json).unwrap().expect("loaded");
+        assert_eq!(settings.active_theme, "dark");
+        assert_eq!(settings.window_size("capture"), Some((400.0, 300.0)));
+
+        // An explicit active theme wins over the legacy mode.
+        let json = r#"{"version": 1, "data": {
+            "shortcut_mappings": [],
+            "default_folder": "Inbox",
+            "theme_mode": "dark",
+            "active_theme": "nord"
+        }}"#;
+        let settings = load_settings("v1-theme", json).unwrap().expect("loaded");
+        assert_eq!(settings.active_theme, "nord");
+        assert_eq!(settings.window_size("viewing"), None);
+    }
+
+    #[test]
+    fn null_window_sizes_still_take_the_legacy_sizes() {
+        let json = r#"{"version": 1, "data": {
+            "shortcut_mappings": [],
+            "default_folder": "Inbox",
+            "window_sizes": null,
+            "viewing_window_size": [520.0, 360.0]
+        }}"#;
+        let settings = load_settings("v1-null-sizes", json)
+            .unwrap()
+            .expect("loaded");
+        assert_eq!(settings.window_size("viewing"), Some((520.0, 360.0)));
+    }
+
+    #[test]
+    fn v2_settings_round_trip_unchanged() {
+        let mut settings = StikSettings::default();
+        settings.active_theme = "nord".to_string();
+        settings.set_window_size("capture", 410.0, 290.0);
+
+        let path = fixture("v2", "");
+        save_versioned(&path, &settings).expect("save");
+        let raw: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(raw["version"], 2);
+        assert!(raw["data"].get("theme_mode").is_none());
+
+        let loaded = load_versioned::<StikSettings>(&path)
+            .unwrap()
+            .expect("loaded");
+        remove_fixture(&path);
+        assert_eq!(loaded.active_theme, "nord");
+        assert_eq!(loaded.window_size("capture"), Some((410.0, 290.0)));
+    }
+
+    #[test]
+    fn newer_versions_are_refused() {
+        let json = r#"{"version": 3, "data": {"active_theme": "nord"}}"#;
+        let error = load_settings("v3", json).unwrap_err();
+        assert!(error.contains("created by a newer Stik"), "{}", error);
+    }
+
+    #[test]
+    fn atomic_writes_replace_the_file_and_leave_no_temp_files() {
+        let path = fixture("atomic", "old contents");
+        atomic_write_json(&path, &serde_json::json!({ "saved": true })).expect("write");
+        let raw: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(raw["saved"], true);
+
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        let leftovers = fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.starts_with(&format!(".{}.", file_name)) && name.ends_with(".tmp")
+            })
+            .count();
+        remove_fixture(&path);
+        assert_eq!(leftovers, 0);
+    }
+
+    #[test]
+    fn other_stores_pass_through_v2_migration() {
+        let data = serde_json::json!({ "capture_streak_days": 4, "window_sizes": null });
+        assert_eq!(migrate(1, data.clone()).unwrap(), data);
+    }
+
+    #[test]
+    fn stored_version_reads_the_wrapper_only() {
+        let versioned = fixture("stored-v2", r#"{"version": 2, "data": {"version": 9}}"#);
+        let legacy = fixture("stored-v0", r#"{"version": 9, "default_folder": "Inbox"}"#);
+        assert_eq!(stored_version(&versioned).unwrap(), Some(2));
+        assert_eq!(stored_version(&legacy).unwrap(), Some(0));
+        remove_fixture(&versioned);
+        remove_fixture(&legacy);
+        assert_eq!(stored_version(&versioned).unwrap(), None);
+    }
+}
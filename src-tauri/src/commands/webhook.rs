@@ -0,0 +1,186 @@
+// Outgoing webhook for personal automations (n8n, Zapier, …).
+//
+// After a note is saved, updated or deleted, POSTs its metadata (and the
+// content when `webhook_include_content` is on) to `webhook_url`. With a
+// `webhook_secret`, the body is signed as `X-Stik-Signature: sha256=<hex>`
+// (HMAC-SHA256). Delivery is fire-and-forget: a few retries with backoff,
+// then the event is dropped. Nothing happens while the URL is empty.
+
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use super::index::extract_title;
+use super::settings::StikSettings;
+
+const RETRY_DELAYS_SECONDS: [u64; 2] = [2, 10];
+const REQUEST_TIMEOUT_SECONDS: u64 = 10;
+pub const SIGNATURE_HEADER: &str = "X-Stik-Signature";
+
+pub const NOTE_SAVED: &str = "note.saved";
+pub const NOTE_UPDATED: &str = "note.updated";
+pub const NOTE_DELETED: &str = "note.deleted";
+
+static CLIENT: OnceLock<Result<reqwest::Client, String>> = OnceLock::new();
+
+/// One client for every delivery, so connections are pooled.
+fn client() -> Result<&'static reqwest::Client, String> {
+    CLIENT
+        .get_or_init(|| {
+            reqwest::Client::builder()
+                .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECONDS))
+                .build()
+                .map_err(|e| e.to_string())
+        })
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
+/// Queue a webhook for `event`. `content` is the note's content (the last
+/// known content for deletions), used for the title and word count.
+pub fn notify(event: &str, path: &str, folder: &str, content: &str) {
+    let Ok(settings) = super::settings::load_settings_from_file() else {
+        return;
+    };
+    let url = settings.webhook_url.trim().to_string();
+    if url.is_empty() {
+        return;
+    }
+
+    let body = build_payload(event, path, folder, content, &settings).to_string();
+    let signature = (!settings.webhook_secret.is_empty())
+        .then(|| sign(settings.webhook_secret.as_bytes(), body.as_bytes()));
+    let event = event.to_string();
+
+    tauri::async_runtime::spawn(async move {
+        deliver(&event, &url, body, signature).await;
+    });
+}
+
+fn build_payload(
+    event: &str,
+    path: &str,
+    folder: &str,
+    content: &str,
+    settings: &StikSettings,
+) -> Value {
+    let mut payload = json!({
+        "event": event,
+        "path": path,
+        "folder": folder,
+        "title": extract_title(content),
+        "word_count": content.split_whitespace().count(),
+        "timestamp": chrono::Local::now().to_rfc3339(),
+    });
+    if settings.webhook_include_content && event != NOTE_DELETED {
+        payload["content"] = json!(content);
+    }
+    payload
+}
+
+async fn deliver(event: &str, url: &str, body: String, signature: Option<String>) {
+    let client = match client() {
+        Ok(client) => client,
+        Err(e) => {
+            log::warn!("Webhook {} dropped: {}", event, e);
+            return;
+        }
+    };
+
+    let mut attempt = 0;
+    loop {
+        let mut request = client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .body(body.clone());
+        if let Some(signature) = &signature {
+            request = request.header(SIGNATURE_HEADER, format!("sha256={}", signature));
+        }
+
+        let error = match request.send().await {
+            Ok(resp) if resp.status().is_success() => return,
+            Ok(resp) => format!("HTTP {}", resp.status()),
+            Err(e) => e.to_string(),
+        };
+
+        let Some(delay) = RETRY_DELAYS_SECONDS.get(attempt).copied() else {
+            log::warn!(
+                "Webhook {} dropped after {} attempts: {}",
+                event,
+                attempt + 1,
+                error
+            );
+            return;
+        };
+        attempt += 1;
+        tokio::time::sleep(Duration::from_secs(delay)).await;
+    }
+}
+
+/// Hex HMAC-SHA256 of `message` under `key`.
+fn sign(key: &[u8], message: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(message);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_matches_rfc_4231() {
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Keys longer than a block are hashed first.
+        assert_eq!(
+            sign(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn content_is_only_sent_when_opted_in() {
+        let mut settings = StikSettings::default();
+        let payload = build_payload(
+            NOTE_SAVED,
+            "/n/Inbox/a.md",
+            "Inbox",
+            "Title\nbody",
+            &settings,
+        );
+        assert_eq!(payload["title"], "Title");
+        assert_eq!(payload["word_count"], 2);
+        assert!(payload.get("content").is_none());
+
+        settings.webhook_include_content = true;
+        let payload = build_payload(
+            NOTE_SAVED,
+            "/n/Inbox/a.md",
+            "Inbox",
+            "Title\nbody",
+            &settings,
+        );
+        assert_eq!(payload["content"], "Title\nbody");
+
+        let payload = build_payload(
+            NOTE_DELETED,
+            "/n/Inbox/a.md",
+            "Inbox",
+            "Title\nbody",
+            &settings,
+        );
+        assert!(payload.get("content").is_none());
+    }
+}
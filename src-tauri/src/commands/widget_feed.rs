@@ -0,0 +1,260 @@
+/// A small JSON feed of the latest notes for widgets and other tools,
+/// behind the opt-in `widget_feed_enabled` setting.
+///
+/// `~/.stik/widget/feed.json` holds the most recently edited notes and the
+/// capture streak. It is rewritten a few seconds after notes change, so a
+/// bulk operation costs one write. Locked notes and notes in
+/// `widget_excluded_folders` never appear in it. Turning the setting off
+/// deletes the file.
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager, State};
+
+use super::index::{NoteEntry, NoteIndex};
+use super::settings::{self, StikSettings};
+use super::{config_paths, folders, spotlight, stats, versioning};
+
+const FEED_DIR: &str = "widget";
+const FEED_FILE: &str = "feed.json";
+const FEED_NOTE_LIMIT: usize = 10;
+const FEED_PREVIEW_CHARS: usize = 200;
+const FEED_DEBOUNCE: Duration = Duration::from_secs(3);
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static WRITE_PENDING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WidgetNote {
+    pub title: String,
+    pub folder: String,
+    pub created: String,
+    /// RFC 3339 modification time, when the file could be read.
+    pub modified: Option<String>,
+    pub preview: String,
+    /// `stik://open` link that opens the note.
+    pub link: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WidgetFeed {
+    /// RFC 3339.
+    pub generated_at: String,
+    pub capture_streak_days: u32,
+    /// Most recently edited first.
+    pub notes: Vec<WidgetNote>,
+}
+
+fn feed_path() -> Result<PathBuf, String> {
+    Ok(config_paths::home_config_dir()?
+        .join(FEED_DIR)
+        .join(FEED_FILE))
+}
+
+fn truncate_chars(text: &str, limit: usize) -> String {
+    match text.char_indices().nth(limit) {
+        Some((at, _)) => format!("{}…", text[..at].trim_end()),
+        None => text.to_string(),
+    }
+}
+
+/// The newest `FEED_NOTE_LIMIT` of `entries`, each paired with its
+/// modification time, leaving out locked notes and excluded folders.
+fn feed_notes(
+    entries: Vec<(NoteEntry, Option<SystemTime>)>,
+    excluded_folders: &[String],
+    stik_root: &Path,
+) -> Vec<WidgetNote> {
+    let mut entries: Vec<_> = entries
+        .into_iter()
+        .filter(|(entry, _)| !entry.locked && !excluded_folders.contains(&entry.folder))
+        .collect();
+    entries.sort_by(|(a, a_modified), (b, b_modified)| {
+        b_modified
+            .cmp(a_modified)
+            .then_with(|| b.created.cmp(&a.created))
+    });
+    entries
+        .into_iter()
+        .take(FEED_NOTE_LIMIT)
+        .map(|(entry, modified)| WidgetNote {
+            title: if entry.title.trim().is_empty() {
+                entry.filename.trim_end_matches(".md").to_string()
+            } else {
+                entry.title.clone()
+            },
+            link: spotlight::open_link(
+                Path::new(&entry.path)
+                    .strip_prefix(stik_root)
+                    .unwrap_or(Path::new(&entry.path)),
+            ),
+            modified: modified.map(|time| DateTime::<Local>::from(time).to_rfc3339()),
+            preview: truncate_chars(&entry.preview, FEED_PREVIEW_CHARS),
+            folder: entry.folder,
+            created: entry.created,
+        })
+        .collect()
+}
+
+fn build_feed(index: &NoteIndex, settings: &StikSettings) -> Result<WidgetFeed, String> {
+    let stik_root = folders::get_stik_folder()?;
+    let entries = index
+        .list(None)?
+        .into_iter()
+        .map(|entry| {
+            let modified = fs::metadata(&entry.path).and_then(|m| m.modified()).ok();
+            (entry, modified)
+        })
+        .collect();
+    Ok(WidgetFeed {
+        generated_at: Local::now().to_rfc3339(),
+        capture_streak_days: stats::calculate_and_persist_capture_streak(Some(index))?,
+        notes: feed_notes(entries, &settings.widget_excluded_folders, &stik_root),
+    })
+}
+
+/// Rewrite the feed file now, if the setting is on.
+pub fn write_widget_feed(index: &NoteIndex) -> Result<(), String> {
+    let settings = settings::get_settings()?;
+    if !settings.widget_feed_enabled {
+        return Ok(());
+    }
+    let feed = build_feed(index, &settings)?;
+    let path = feed_path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    versioning::atomic_write_json(&path, &feed)
+}
+
+fn remove_feed_file() {
+    if let Ok(path) = feed_path() {
+        if path.exists() {
+            if let Err(e) = fs::remove_file(&path) {
+                log::error!("Failed to remove widget feed: {}", e);
+            }
+        }
+    }
+}
+
+/// Remember the app handle and pick up the current setting. Call once
+/// during app setup.
+pub fn register_app(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+    let enabled = settings::get_settings()
+        .map(|settings| settings.widget_feed_enabled)
+        .unwrap_or(false);
+    ENABLED.store(enabled, Ordering::SeqCst);
+    if enabled {
+        notes_changed();
+    }
+}
+
+/// Write the feed when the setting is on (excluded folders may have
+/// changed), delete it when the setting was just turned off.
+pub fn apply_settings(settings: &StikSettings) {
+    let enabled = settings.widget_feed_enabled;
+    let was_enabled = ENABLED.swap(enabled, Ordering::SeqCst);
+    if enabled {
+        notes_changed();
+    } else if was_enabled {
+        remove_feed_file();
+    }
+}
+
+/// Schedule a feed rewrite after notes were saved, moved or deleted.
+/// Calls within `FEED_DEBOUNCE` of each other share one write.
+pub fn notes_changed() {
+    if !ENABLED.load(Ordering::SeqCst) || WRITE_PENDING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let Some(app) = APP.get().cloned() else {
+        WRITE_PENDING.store(false, Ordering::SeqCst);
+        return;
+    };
+    let spawned = thread::Builder::new()
+        .name("stik-widget-feed".to_string())
+        .spawn(move || {
+            thread::sleep(FEED_DEBOUNCE);
+            // Cleared before reading the index, so a change made while the
+            // feed is built schedules another write.
+            WRITE_PENDING.store(false, Ordering::SeqCst);
+            if let Err(e) = write_widget_feed(&app.state::<NoteIndex>()) {
+                log::error!("Failed to write widget feed: {}", e);
+            }
+        });
+    if spawned.is_err() {
+        WRITE_PENDING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// The feed as it would be written now, whether or not the setting is on.
+#[tauri::command]
+pub fn get_widget_feed(index: State<'_, NoteIndex>) -> Result<WidgetFeed, String> {
+    let settings = settings::get_settings()?;
+    build_feed(&index, &settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(folder: &str, name: &str, locked: bool) -> NoteEntry {
+        NoteEntry {
+            path: format!("/notes/{}/{}", folder, name),
+            filename: name.to_string(),
+            folder: folder.to_string(),
+            title: String::new(),
+            preview: "x".repeat(300),
+            created: name.trim_end_matches(".md").to_string(),
+            content_len: 300,
+            locked,
+            word_count: 1,
+            reading_minutes: 1,
+            language: "und".to_string(),
+            truncated_in_index: false,
+            id: None,
+            pinned: false,
+            created_date: None,
+            open_tasks: 0,
+            done_tasks: 0,
+        }
+    }
+
+    fn at(secs: u64) -> Option<SystemTime> {
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    #[test]
+    fn newest_edits_first_without_locked_or_excluded_notes() {
+        let entries = vec![
+            (entry("Inbox", "20260101-a.md", false), at(100)),
+            (entry("Journal", "20260102-b.md", false), at(400)),
+            (entry("Inbox", "20260103-c.md", true), at(300)),
+            (entry("Work", "20260104-d.md", false), at(200)),
+        ];
+        let notes = feed_notes(entries, &["Journal".to_string()], Path::new("/notes"));
+
+        let titles: Vec<&str> = notes.iter().map(|note| note.title.as_str()).collect();
+        assert_eq!(titles, vec!["20260104-d", "20260101-a"]);
+        assert_eq!(notes[0].link, "stik://open?path=Work%2F20260104-d.md");
+        assert_eq!(notes[0].preview.chars().count(), FEED_PREVIEW_CHARS + 1);
+        assert!(notes[0].preview.ends_with('…'));
+    }
+
+    #[test]
+    fn only_the_newest_ten_are_kept() {
+        let entries = (0..15)
+            .map(|i| (entry("Inbox", &format!("{:02}.md", i), false), at(i)))
+            .collect();
+        let notes = feed_notes(entries, &[], Path::new("/notes"));
+        assert_eq!(notes.len(), FEED_NOTE_LIMIT);
+        assert_eq!(notes[0].title, "14");
+    }
+}
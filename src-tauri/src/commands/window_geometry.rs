@@ -0,0 +1,348 @@
+/// Per-note viewing window geometry, so each note reopens where and at the
+/// size it was last left instead of sharing one global frame.
+///
+/// Keys are the note's stable id when it has one, so geometry follows a
+/// note through renames and moves; notes without an id fall back to a
+/// stable hash of their path. Looking a key up never writes to the note.
+/// Stores from before ids were keyed by path hash only and are migrated
+/// once for the notes that already carry an id. Values are `(x, y, width, height)` with the position in physical
+/// pixels (what `outer_position()` reports) and the size in logical pixels
+/// (what `inner_size()` on the builder takes).
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use super::config_paths;
+use super::index::NoteIndex;
+use super::settings::StickedSizeLimits;
+use super::{note_ids, notes, versioning};
+
+pub type WindowGeometry = (f64, f64, f64, f64);
+
+const SAVE_DEBOUNCE_MS: u64 = 750;
+const PRUNE_INTERVAL_SECONDS: u64 = 6 * 60 * 60;
+const PRUNE_INITIAL_DELAY_SECONDS: u64 = 120;
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct WindowGeometryStore {
+    window_geometry: HashMap<String, WindowGeometry>,
+    /// Path-hash keys of live notes have been moved to note ids.
+    #[serde(default)]
+    keyed_by_note_id: bool,
+}
+
+/// Geometry updates not yet flushed to disk, plus whether a flush is queued.
+#[derive(Default)]
+struct PendingGeometry {
+    updates: HashMap<String, WindowGeometry>,
+    flush_scheduled: bool,
+}
+
+static PENDING: OnceLock<Mutex<PendingGeometry>> = OnceLock::new();
+static PRUNER_RUNNING: OnceLock<()> = OnceLock::new();
+
+fn pending() -> &'static Mutex<PendingGeometry> {
+    PENDING.get_or_init(|| Mutex::new(PendingGeometry::default()))
+}
+
+/// FNV-1a over the path bytes, as 16 hex chars. Unlike `DefaultHasher` the
+/// output is fixed across Rust releases, so it is safe to persist.
+pub fn path_key(path: &str) -> String {
+    let hash = path.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    format!("{:016x}", hash)
+}
+
+/// The key the note at `path` is stored under: its id if it already has
+/// one, else its path hash.
+pub fn note_key(index: &NoteIndex, path: &str) -> String {
+    index
+        .get(path)
+        .and_then(|entry| entry.id)
+        .or_else(|| {
+            notes::get_note_content_inner(path)
+                .ok()
+                .and_then(|content| note_ids::note_id(&content))
+        })
+        .unwrap_or_else(|| path_key(path))
+}
+
+/// Move the geometry saved under a note's path hash to the id it was just
+/// given.
+pub fn adopt_note_id(path: &str, id: &str) {
+    flush_pending();
+    let keys = HashMap::from([(path_key(path), id.to_string())]);
+    let result = load_store().and_then(|mut store| {
+        if !store.window_geometry.contains_key(&path_key(path)) {
+            return Ok(());
+        }
+        rekey(&mut store.window_geometry, &keys);
+        save_store(&store)
+    });
+    if let Err(e) = result {
+        log::error!("Failed to move window geometry to note id: {}", e);
+    }
+}
+
+/// Last saved geometry under `key` (see `note_key`), including unflushed
+/// updates.
+pub fn geometry_for(key: &str) -> Option<WindowGeometry> {
+    if let Some(geometry) = pending()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .updates
+        .get(key)
+    {
+        return Some(*geometry);
+    }
+
+    load_store()
+        .ok()
+        .and_then(|store| store.window_geometry.get(key).copied())
+}
+
+/// Record a note window's geometry under `key`. Resize and move events
+/// arrive in bursts, so writes are coalesced into one save per debounce
+/// window.
+pub fn remember(key: &str, geometry: WindowGeometry) {
+    let mut state = pending().lock().unwrap_or_else(|e| e.into_inner());
+    state.updates.insert(key.to_string(), geometry);
+    if state.flush_scheduled {
+        return;
+    }
+    state.flush_scheduled = true;
+    drop(state);
+
+    let spawned = thread::Builder::new()
+        .name("stik-window-geometry".to_string())
+        .spawn(|| {
+            thread::sleep(Duration::from_millis(SAVE_DEBOUNCE_MS));
+            flush_pending();
+        });
+    if spawned.is_err() {
+        flush_pending();
+    }
+}
+
+fn flush_pending() {
+    let updates = {
+        let mut state = pending().lock().unwrap_or_else(|e| e.into_inner());
+        state.flush_scheduled = false;
+        std::mem::take(&mut state.updates)
+    };
+    if updates.is_empty() {
+        return;
+    }
+
+    let result = load_store().and_then(|mut store| {
+        store.window_geometry.extend(updates);
+        save_store(&store)
+    });
+    if let Err(e) = result {
+        log::error!("Failed to save window geometry: {}", e);
+    }
+}
+
+/// Fit every stored size into `limits` after the limits change.
+pub fn clamp_sizes(limits: &StickedSizeLimits) {
+    flush_pending();
+    let result = load_store().and_then(|mut store| {
+        if !clamp_geometry(&mut store.window_geometry, limits) {
+            return Ok(());
+        }
+        save_store(&store)
+    });
+    if let Err(e) = result {
+        log::error!("Failed to fit window geometry to new size limits: {}", e);
+    }
+}
+
+/// True when any size changed.
+fn clamp_geometry(
+    geometry: &mut HashMap<String, WindowGeometry>,
+    limits: &StickedSizeLimits,
+) -> bool {
+    let mut changed = false;
+    for (_, _, width, height) in geometry.values_mut() {
+        let clamped = limits.clamp((*width, *height));
+        if clamped != (*width, *height) {
+            (*width, *height) = clamped;
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Start the background job that drops geometry for notes that no longer
+/// exist, after moving path-keyed geometry over to note ids the first
+/// time. Call once during app setup.
+pub fn start_pruner(app: AppHandle) {
+    if PRUNER_RUNNING.set(()).is_err() {
+        return;
+    }
+
+    thread::Builder::new()
+        .name("stik-window-geometry-prune".to_string())
+        .spawn(move || {
+            thread::sleep(Duration::from_secs(PRUNE_INITIAL_DELAY_SECONDS));
+            if let Err(e) = migrate_to_note_ids(&app) {
+                log::error!("Failed to migrate window geometry to note ids: {}", e);
+            }
+            loop {
+                if let Err(e) = prune_missing(&app) {
+                    log::error!("Failed to prune window geometry: {}", e);
+                }
+                thread::sleep(Duration::from_secs(PRUNE_INTERVAL_SECONDS));
+            }
+        })
+        .ok();
+}
+
+/// Re-key geometry stored under a live note's path hash by the id the note
+/// already has. Notes without one keep their path key. Runs until it has
+/// completed once.
+fn migrate_to_note_ids(app: &AppHandle) -> Result<(), String> {
+    let mut store = load_store()?;
+    if store.keyed_by_note_id {
+        return Ok(());
+    }
+    // Without a built index no note could be matched; try again next start.
+    let Some(entries) = app.state::<NoteIndex>().snapshot() else {
+        return Ok(());
+    };
+
+    let mut keys: HashMap<String, String> = HashMap::new();
+    for entry in entries {
+        let Some(id) = entry.id else {
+            continue;
+        };
+        let old_key = path_key(&entry.path);
+        if store.window_geometry.contains_key(&old_key) {
+            keys.insert(old_key, id);
+        }
+    }
+    rekey(&mut store.window_geometry, &keys);
+    store.keyed_by_note_id = true;
+    save_store(&store)
+}
+
+fn rekey(geometry: &mut HashMap<String, WindowGeometry>, keys: &HashMap<String, String>) {
+    for (old_key, new_key) in keys {
+        if let Some(value) = geometry.remove(old_key) {
+            geometry.entry(new_key.clone()).or_insert(value);
+        }
+    }
+}
+
+fn prune_missing(app: &AppHandle) -> Result<(), String> {
+    // Without a built index every entry would look orphaned.
+    let Some(entries) = app.state::<NoteIndex>().snapshot() else {
+        return Ok(());
+    };
+    let live: HashSet<String> = entries
+        .iter()
+        .map(|entry| entry.id.clone().unwrap_or_else(|| path_key(&entry.path)))
+        .collect();
+
+    let mut store = load_store()?;
+    let before = store.window_geometry.len();
+    retain_live(&mut store.window_geometry, &live);
+    if store.window_geometry.len() != before {
+        save_store(&store)?;
+    }
+    Ok(())
+}
+
+fn retain_live(geometry: &mut HashMap<String, WindowGeometry>, live: &HashSet<String>) {
+    geometry.retain(|key, _| live.contains(key));
+}
+
+/// Move a window so it lies inside `area` (x, y, width, height; physical
+/// pixels). Windows larger than the area are pinned to its top-left.
+pub fn clamp_to_area(x: f64, y: f64, w: f64, h: f64, area: (f64, f64, f64, f64)) -> (f64, f64) {
+    let (ax, ay, aw, ah) = area;
+    let max_x = (ax + aw - w).max(ax);
+    let max_y = (ay + ah - h).max(ay);
+    (x.clamp(ax, max_x), y.clamp(ay, max_y))
+}
+
+fn load_store() -> Result<WindowGeometryStore, String> {
+    let path = config_paths::config_file("window_geometry.json")?;
+    match versioning::load_versioned::<WindowGeometryStore>(&path)? {
+        Some(store) => Ok(store),
+        None => Ok(WindowGeometryStore::default()),
+    }
+}
+
+fn save_store(store: &WindowGeometryStore) -> Result<(), String> {
+    let path = config_paths::writable_config_file("window_geometry.json")?;
+    versioning::save_versioned(&path, store)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_key_is_stable_and_distinguishes_similar_paths() {
+        assert_eq!(path_key(""), "cbf29ce484222325");
+        assert_eq!(path_key("/Stik/Inbox/a.md"), path_key("/Stik/Inbox/a.md"));
+        assert_ne!(
+            path_key("/Stik/Inbox/a.b.md"),
+            path_key("/Stik/Inbox/a b.md")
+        );
+    }
+
+    #[test]
+    fn clamps_offscreen_windows_into_area() {
+        let area = (0.0, 0.0, 1920.0, 1080.0);
+        assert_eq!(
+            clamp_to_area(3000.0, 200.0, 400.0, 300.0, area),
+            (1520.0, 200.0)
+        );
+        assert_eq!(clamp_to_area(-500.0, -50.0, 400.0, 300.0, area), (0.0, 0.0));
+        assert_eq!(
+            clamp_to_area(100.0, 100.0, 2500.0, 300.0, area),
+            (0.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn rekey_moves_path_keys_to_note_ids() {
+        let mut geometry = HashMap::new();
+        geometry.insert(path_key("/Stik/Inbox/a.md"), (1.0, 2.0, 400.0, 300.0));
+        geometry.insert(path_key("/Stik/Inbox/gone.md"), (0.0, 0.0, 400.0, 300.0));
+        geometry.insert("note-b".to_string(), (5.0, 5.0, 500.0, 350.0));
+        let keys: HashMap<String, String> = [
+            (path_key("/Stik/Inbox/a.md"), "note-a".to_string()),
+            (path_key("/Stik/Inbox/b.md"), "note-b".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        rekey(&mut geometry, &keys);
+        assert_eq!(geometry.get("note-a"), Some(&(1.0, 2.0, 400.0, 300.0)));
+        assert_eq!(geometry.get("note-b"), Some(&(5.0, 5.0, 500.0, 350.0)));
+        assert!(!geometry.contains_key(&path_key("/Stik/Inbox/a.md")));
+        // Unmatched path keys are left for the pruner.
+        assert!(geometry.contains_key(&path_key("/Stik/Inbox/gone.md")));
+    }
+
+    #[test]
+    fn prune_keeps_only_live_notes() {
+        let mut geometry = HashMap::new();
+        geometry.insert(path_key("/Stik/Inbox/kept.md"), (0.0, 0.0, 400.0, 300.0));
+        geometry.insert(path_key("/Stik/Inbox/gone.md"), (0.0, 0.0, 400.0, 300.0));
+        let live: HashSet<String> = [path_key("/Stik/Inbox/kept.md")].into_iter().collect();
+
+        retain_live(&mut geometry, &live);
+        assert_eq!(geometry.len(), 1);
+        assert!(geometry.contains_key(&path_key("/Stik/Inbox/kept.md")));
+    }
+}
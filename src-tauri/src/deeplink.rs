@@ -0,0 +1,251 @@
+/// `stik://` links from other apps (calendars, task managers):
+///
+/// - `stik://open?path=Work/note.md` opens a note for viewing
+/// - `stik://new?folder=Work&content=…` opens the postit prefilled
+/// - `stik://search?q=…` opens the command palette on a query
+///
+/// macOS delivers these through `RunEvent::Opened` once the scheme is
+/// registered in Info.plist. Bad links are reported with a notification.
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Manager, Url};
+
+use crate::commands::{folders, macos_notify, settings, spotlight};
+use crate::state::AppState;
+use crate::windows;
+
+pub const SCHEME: &str = "stik";
+
+#[derive(Debug, PartialEq)]
+enum DeepLink {
+    Open {
+        path: String,
+    },
+    New {
+        folder: Option<String>,
+        content: String,
+    },
+    Search {
+        query: String,
+    },
+}
+
+pub fn handle_url(app: &AppHandle, url: &Url) {
+    if let Err(e) = parse(url).and_then(|link| dispatch(app, link)) {
+        log::warn!("Rejected {} link {}: {}", SCHEME, url, e);
+        let _ = macos_notify::show("Stik", "Couldn't open link", &e);
+    }
+}
+
+/// Canonical `stik://open` link for a note, copied to the clipboard. The
+/// path is stored relative to the Stik folder so links survive moving it.
+#[tauri::command]
+pub fn copy_note_link(path: String) -> Result<String, String> {
+    let root = folders::get_stik_folder()?;
+    let note = resolve_note_path(&path, &root)?;
+    let link = build_open_link(&note, &root);
+
+    let mut clipboard =
+        arboard::Clipboard::new().map_err(|e| format!("Clipboard unavailable: {e}"))?;
+    clipboard
+        .set_text(link.clone())
+        .map_err(|e| format!("Failed to write link to clipboard: {e}"))?;
+    Ok(link)
+}
+
+fn parse(url: &Url) -> Result<DeepLink, String> {
+    if url.scheme() != SCHEME {
+        return Err(format!("Not a {}:// link", SCHEME));
+    }
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    // `stik://open?…` carries the action as the host, `stik:open?…` as the path.
+    let action = url
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| url.path().trim_matches('/'));
+
+    match action {
+        "open" => {
+            let path = param("path")
+                .filter(|path| !path.trim().is_empty())
+                .ok_or("The link is missing a note path")?;
+            Ok(DeepLink::Open { path })
+        }
+        "new" => Ok(DeepLink::New {
+            folder: param("folder")
+                .map(|folder| folder.trim().to_string())
+                .filter(|folder| !folder.is_empty()),
+            content: param("content").unwrap_or_default(),
+        }),
+        "search" => Ok(DeepLink::Search {
+            query: param("q").unwrap_or_default(),
+        }),
+        other => Err(format!("Unknown link action '{}'", other)),
+    }
+}
+
+fn dispatch(app: &AppHandle, link: DeepLink) -> Result<(), String> {
+    match link {
+        DeepLink::Open { path } => {
+            let note = resolve_note_path(&path, &folders::get_stik_folder()?)?;
+            crate::handle_opened_files(app, vec![note]);
+        }
+        DeepLink::New { folder, content } => {
+            let folder = match folder {
+                Some(folder) => {
+                    folders::validate_name(&folder)?;
+                    folder
+                }
+                None => settings::get_settings().unwrap_or_default().default_folder,
+            };
+            windows::show_postit_with_folder(app, &folder);
+            if !content.is_empty() {
+                if let Some(postit) = app.get_webview_window("postit") {
+                    let _ = postit.emit(
+                        "transfer-content",
+                        serde_json::json!({ "content": content, "folder": folder }),
+                    );
+                }
+            }
+        }
+        DeepLink::Search { query } => {
+            *app.state::<AppState>()
+                .pending_palette_query
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()) = Some(query);
+            windows::show_command_palette(app);
+            // A freshly created palette picks the query up on mount instead.
+            if let Some(palette) = app.get_webview_window("command-palette") {
+                let _ = palette.emit("palette-query", ());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Resolve a linked path (absolute, or relative to the Stik folder) to an
+/// existing markdown note inside the Stik folder.
+fn resolve_note_path(raw: &str, stik_root: &Path) -> Result<PathBuf, String> {
+    let path = Path::new(raw.trim());
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        stik_root.join(path)
+    };
+
+    // Canonicalizing resolves `..` and symlinks before the containment check.
+    let note = path
+        .canonicalize()
+        .map_err(|_| format!("Note not found: {}", raw))?;
+    let root = stik_root
+        .canonicalize()
+        .unwrap_or_else(|_| stik_root.to_path_buf());
+    if !note.starts_with(&root) {
+        return Err("Links can only open notes inside the Stik folder".to_string());
+    }
+
+    let is_markdown = note
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"))
+        .unwrap_or(false);
+    if !is_markdown || !note.is_file() {
+        return Err("Links can only open markdown notes".to_string());
+    }
+    Ok(note)
+}
+
+fn build_open_link(note: &Path, stik_root: &Path) -> String {
+    let root = stik_root
+        .canonicalize()
+        .unwrap_or_else(|_| stik_root.to_path_buf());
+    spotlight::open_link(note.strip_prefix(&root).unwrap_or(note))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::temp_dir;
+    use std::fs;
+
+    fn temp_root() -> PathBuf {
+        let dir = temp_dir("deeplink");
+        fs::create_dir_all(dir.join("Work")).expect("create temp stik root");
+        dir
+    }
+
+    fn url(value: &str) -> Url {
+        Url::parse(value).expect("valid url")
+    }
+
+    #[test]
+    fn parses_each_action_with_percent_decoding() {
+        assert_eq!(
+            parse(&url("stik://open?path=Work%2Fmy%20note.md")),
+            Ok(DeepLink::Open {
+                path: "Work/my note.md".to_string()
+            })
+        );
+        assert_eq!(
+            parse(&url("stik://new?folder=Work&content=Call%20Sam%0Atomorrow")),
+            Ok(DeepLink::New {
+                folder: Some("Work".to_string()),
+                content: "Call Sam\ntomorrow".to_string(),
+            })
+        );
+        assert_eq!(
+            parse(&url("stik://search?q=quarterly+plan")),
+            Ok(DeepLink::Search {
+                query: "quarterly plan".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_links() {
+        assert!(parse(&url("stik://open")).is_err());
+        assert!(parse(&url("stik://delete?path=Work/a.md")).is_err());
+        assert!(parse(&url("https://open?path=Work/a.md")).is_err());
+    }
+
+    #[test]
+    fn only_resolves_markdown_notes_inside_the_stik_folder() {
+        let root = temp_root();
+        let note = root.join("Work").join("plan.md");
+        fs::write(&note, "# Plan").expect("write note");
+        let outside_dir = temp_dir("outside");
+        let outside = outside_dir.join("outside.md");
+        fs::write(&outside, "# Outside").expect("write outside note");
+
+        assert!(resolve_note_path("Work/plan.md", &root).is_ok());
+        assert!(resolve_note_path(&note.to_string_lossy(), &root).is_ok());
+        assert!(resolve_note_path("Work/../../escape.md", &root).is_err());
+        assert!(resolve_note_path(&outside.to_string_lossy(), &root).is_err());
+        assert!(resolve_note_path("Work/missing.md", &root).is_err());
+        assert!(resolve_note_path("Work", &root).is_err());
+
+        let _ = fs::remove_dir_all(outside_dir);
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn open_links_round_trip_through_parse() {
+        let root = temp_root();
+        let note = root.join("Work").join("a b+c.md");
+        fs::write(&note, "# Note").expect("write note");
+
+        let link = build_open_link(&note.canonicalize().expect("canonical"), &root);
+        assert!(link.starts_with("stik://open?path=Work%2F"));
+        let Ok(DeepLink::Open { path }) = parse(&url(&link)) else {
+            panic!("link should parse: {}", link);
+        };
+        assert_eq!(path, "Work/a b+c.md");
+        assert!(resolve_note_path(&path, &root).is_ok());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
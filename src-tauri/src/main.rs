@@ -1,7 +1,10 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod cli;
 mod commands;
+mod deeplink;
+mod palette;
 mod shortcuts;
 mod state;
 mod tray;
@@ -10,15 +13,17 @@ mod windows;
 use commands::embeddings::EmbeddingIndex;
 use commands::index::NoteIndex;
 use commands::{
-    ai_assistant, analytics, apple_notes, cursor_positions, darwinkit, dictation, embeddings,
-    file_watcher, folders, git_share, icloud, index, macos_notify, note_lock, notes,
-    on_this_day, settings, share, stats, sticked_notes, storage,
+    ai_assistant, analytics, app_log, apple_notes, backup, capture_context, capture_draft, cloud_sync, config_paths, crash_reports, cursor_positions, darwinkit, day_one, diagnostics, dictation, digest,
+    edit_locks, embeddings, expiry, export, file_watcher, filename_template, folders, git_credentials, git_share, icloud, index, integration_server,
+    library_export, macos_notify, note_ids, note_lock, note_metadata,
+    notes, on_this_day, onboarding, pins, reminders, settings, selection_capture, share, speech_dictation, spotlight, stats, sticked_notes, storage, sync_summary, tasks, templates, text_import, tray_menu, user_notifications,
+    widget_feed, window_geometry,
 };
 use shortcuts::shortcut_to_string;
 use state::AppState;
 use tauri::{AppHandle, Emitter, Manager, RunEvent};
 use tauri_plugin_global_shortcut::{Code, Modifiers, ShortcutState};
-use windows::{show_command_palette, show_postit_with_folder, show_settings};
+use windows::{show_command_palette, show_postit_with_folder, show_settings, show_stats_window};
 
 fn folder_for_opened_note(path: &std::path::Path, stik_root: &std::path::Path) -> String {
     if let Ok(relative) = path.strip_prefix(stik_root) {
@@ -51,11 +56,11 @@ fn handle_opened_files(app: &AppHandle, paths: Vec<std::path::PathBuf>) {
             }).await {
                 Ok(Ok(content)) => content,
                 Ok(Err(err)) => {
-                    eprintln!("Failed to read opened markdown file {}: {}", path_str, err);
+                    log::error!("Failed to read opened markdown file {}: {}", path_str, err);
                     return;
                 }
                 Err(err) => {
-                    eprintln!("Failed to read opened markdown file {}: task join error: {}", path_str, err);
+                    log::error!("Failed to read opened markdown file {}: task join error: {}", path_str, err);
                     return;
                 }
             };
@@ -67,12 +72,29 @@ fn handle_opened_files(app: &AppHandle, paths: Vec<std::path::PathBuf>) {
                 .unwrap_or_default();
 
             if let Err(err) = windows::open_note_for_viewing(app_handle, content, folder, path_str).await {
-                eprintln!("Failed to open markdown file from Finder: {}", err);
+                log::error!("Failed to open markdown file from Finder: {}", err);
             }
         });
     }
 }
 
+/// Clicks on notifications sent through `user_notifications::show`.
+fn handle_notification_click(app: &AppHandle, kind: &str, payload: &str) {
+    if kind != on_this_day::NOTIFICATION_KIND {
+        return;
+    }
+    let path = std::path::PathBuf::from(payload);
+    if !path.is_file() {
+        let _ = macos_notify::show(
+            "On This Day",
+            "Note not found",
+            "It was moved or deleted after the notification was shown.",
+        );
+        return;
+    }
+    handle_opened_files(app, vec![path]);
+}
+
 /// Tracks whether we've already opened System Settings and notified
 /// the user about missing Accessibility this session. Reset back to
 /// false as soon as a clip_capture succeeds — so if the user grants
@@ -95,34 +117,11 @@ static CLIP_PERMISSION_WARNED: std::sync::atomic::AtomicBool =
 /// the text comes straight from the source app's accessibility tree.
 /// This is the same approach PopClip, Alfred, and Raycast use.
 fn clip_capture(app: &AppHandle) {
-    // Write to a dedicated file so the trace survives across process
-    // boundaries and is trivially grep-able. eprintln also goes to the
-    // parent's stderr in debug, but that's swallowed when Stik is
-    // launched via `open`.
-    //
-    // Each call logs a tag with the Stik version + a build marker so
-    // you can check `/tmp/stik-clip.log` and know *which* Stik binary
-    // just ran — useful when iterating rapidly.
-    let log = |msg: &str| {
-        eprintln!("[clip_capture] {}", msg);
-        if cfg!(debug_assertions) {
-            use std::io::Write;
-            if let Ok(mut f) = std::fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open("/tmp/stik-clip.log")
-            {
-                let _ = writeln!(
-                    f,
-                    "[{}] {}",
-                    chrono::Local::now().format("%H:%M:%S%.3f"),
-                    msg
-                );
-            }
-        }
-    };
+    // Each step goes to the Stik log at debug level; `set_log_level("debug")`
+    // makes the trace visible without a rebuild.
+    let trace = |msg: &str| log::debug!("clip_capture: {}", msg);
 
-    log(&format!(
+    trace(&format!(
         "--- clip_capture v{} (AX-read) ---",
         env!("CARGO_PKG_VERSION")
     ));
@@ -131,12 +130,12 @@ fn clip_capture(app: &AppHandle) {
     //    no error even when TCC denies it — it just returns an empty
     //    result — so this pre-check is the only way to give the user
     //    a clear "permission needed" message upfront.
-    if !is_accessibility_granted() {
-        log("AXIsProcessTrusted = false — Accessibility NOT granted");
+    if !selection_capture::is_accessibility_granted() {
+        trace("AXIsProcessTrusted = false — Accessibility NOT granted");
         warn_about_accessibility();
         return;
     }
-    log("AXIsProcessTrusted = true");
+    trace("AXIsProcessTrusted = true");
 
     // 2. Read the selected text directly from the focused UI element
     //    via the Accessibility API. No keystroke simulation, no
@@ -144,11 +143,11 @@ fn clip_capture(app: &AppHandle) {
     //    from the source app's accessibility tree.
     let text = match read_selected_text_via_ax() {
         Some(t) if !t.trim().is_empty() => {
-            log(&format!("AX read OK, selected text length = {}", t.len()));
+            trace(&format!("AX read OK, selected text length = {}", t.len()));
             t
         }
         Some(_) => {
-            log("AX read OK but selected text is empty");
+            trace("AX read OK but selected text is empty");
             let _ = macos_notify::show(
                 "Stik",
                 "Nothing selected",
@@ -157,7 +156,7 @@ fn clip_capture(app: &AppHandle) {
             return;
         }
         None => {
-            log("AX read failed — app doesn't expose selected text");
+            trace("AX read failed — app doesn't expose selected text");
             let _ = macos_notify::show(
                 "Stik",
                 "Can't read selection",
@@ -175,7 +174,7 @@ fn clip_capture(app: &AppHandle) {
     // 4. Save the note
     match notes::save_note_inner(folder.clone(), text.clone()) {
         Ok(result) => {
-            log(&format!("saved note: {}", result.path));
+            trace(&format!("saved note: {}", result.path));
             notes::post_save_processing(app, &result, &text);
 
             // Clear the warned flag now that capture actually works —
@@ -198,7 +197,7 @@ fn clip_capture(app: &AppHandle) {
             );
         }
         Err(e) => {
-            log(&format!("save failed: {}", e));
+            trace(&format!("save failed: {}", e));
             let _ = macos_notify::show("Stik", "Save failed", &e);
         }
     }
@@ -229,25 +228,6 @@ fn warn_about_accessibility() {
     }
 }
 
-/// Checks whether the Stik process currently has Accessibility
-/// permission, *without* prompting the user. This is the authoritative
-/// TCC query — if it returns false, CGEventPost will silently drop
-/// any keystrokes we send, so there's no point trying.
-#[cfg(target_os = "macos")]
-fn is_accessibility_granted() -> bool {
-    // AXIsProcessTrustedWithOptions is declared in ApplicationServices/
-    // AXUIElement.h and takes a CFDictionaryRef (or nullptr). Passing
-    // nullptr performs a silent check without popping the system
-    // prompt — which is what we want here since we open the Settings
-    // pane ourselves via `open -b`.
-    use std::ffi::c_void;
-    #[link(name = "ApplicationServices", kind = "framework")]
-    unsafe extern "C" {
-        fn AXIsProcessTrustedWithOptions(options: *const c_void) -> bool;
-    }
-    unsafe { AXIsProcessTrustedWithOptions(std::ptr::null()) }
-}
-
 /// Reads the currently-selected text from whatever UI element is
 /// focused system-wide, via the macOS Accessibility API.
 ///
@@ -337,7 +317,62 @@ fn open_accessibility_settings() {
         .spawn();
 }
 
+/// Startup work that needs the built note index: the tray's real streak
+/// and recent notes, then the embedding build.
+fn on_index_built(app: &AppHandle) {
+    tray_menu::rebuild_tray_menu(app);
+    let ai_enabled = settings::get_settings()
+        .map(|s| s.ai_features_enabled)
+        .unwrap_or(true);
+    if ai_enabled {
+        let index = app.state::<NoteIndex>();
+        let emb = app.state::<EmbeddingIndex>();
+        embeddings::build_embeddings(&index, &emb);
+    }
+}
+
 fn main() {
+    app_log::init();
+    crash_reports::install_panic_hook();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // Invoked by git as a credential helper: answer and exit before any
+    // single-instance forwarding or window setup.
+    if args.first().map(String::as_str) == Some(git_credentials::HELPER_ARG) {
+        std::process::exit(git_credentials::run_helper(args.get(1).map(String::as_str)));
+    }
+    let mut launch_command = match cli::parse_args(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("stik: {}\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if let Some(command) = launch_command.as_mut() {
+        if let Err(e) = cli::read_piped_stdin(command) {
+            eprintln!("stik: {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    // Single instance: hand the command (or a plain relaunch) to the
+    // running app and exit before any window exists.
+    let forwarded = launch_command.clone().unwrap_or(cli::CliCommand::Show);
+    if let cli::Forwarded::Handled(result) = cli::forward(&forwarded) {
+        match result {
+            Ok(message) => {
+                if !message.is_empty() {
+                    println!("{}", message);
+                }
+                std::process::exit(0);
+            }
+            Err(e) => {
+                eprintln!("stik: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
     tauri::Builder::default()
         .manage(AppState::new())
         .manage(NoteIndex::new())
@@ -374,6 +409,10 @@ fn main() {
                                     show_settings(app);
                                     return;
                                 }
+                                "stats" => {
+                                    show_stats_window(app);
+                                    return;
+                                }
                                 "last_note" => {
                                     let app = app.clone();
                                     tauri::async_runtime::spawn(async move {
@@ -381,6 +420,15 @@ fn main() {
                                     });
                                     return;
                                 }
+                                "daily_note" => {
+                                    let app = app.clone();
+                                    tauri::async_runtime::spawn(async move {
+                                        if let Err(e) = windows::open_daily_note(app, None).await {
+                                            log::error!("Failed to open daily note: {}", e);
+                                        }
+                                    });
+                                    return;
+                                }
                                 "clip_capture" => {
                                     let app = app.clone();
                                     std::thread::Builder::new()
@@ -391,6 +439,28 @@ fn main() {
                                         .ok();
                                     return;
                                 }
+                                "capture_selection" => {
+                                    let app = app.clone();
+                                    std::thread::Builder::new()
+                                        .name("stik-capture-selection".to_string())
+                                        .spawn(move || {
+                                            if !selection_capture::is_accessibility_granted() {
+                                                warn_about_accessibility();
+                                                return;
+                                            }
+                                            if let Err(e) =
+                                                selection_capture::capture_selection(&app, "")
+                                            {
+                                                let _ = macos_notify::show(
+                                                    "Stik",
+                                                    "Capture failed",
+                                                    &e,
+                                                );
+                                            }
+                                        })
+                                        .ok();
+                                    return;
+                                }
                                 "voice_note" => {
                                     // Open a fresh postit for the default
                                     // folder, then tell the webview to
@@ -439,51 +509,124 @@ fn main() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .invoke_handler(tauri::generate_handler![
             notes::save_note,
+            capture_context::save_note_with_context,
             notes::update_note,
             notes::list_notes,
+            notes::get_recent_notes_in_folder,
+            pins::pin_note,
+            pins::unpin_note,
+            pins::list_pinned_notes,
             notes::search_notes,
+            notes::search_notes_faceted,
+            notes::format_note,
             notes::delete_note,
+            notes::delete_notes,
             notes::move_note,
+            notes::move_note_to_new_folder,
+            notes::undo_auto_file,
             notes::get_note_content,
+            note_metadata::get_note_metadata,
+            note_ids::get_note_id,
+            note_ids::resolve_note_id,
+            outline::get_note_outline,
+            cloud_sync::find_cloud_conflict_duplicates,
+            cloud_sync::merge_cloud_conflict_duplicate,
             notes::save_note_image,
             notes::save_note_image_from_path,
+            text_import::import_text_file,
             folders::list_folders,
             folders::create_folder,
             folders::delete_folder,
             folders::rename_folder,
             folders::get_folder_stats,
+            folders::get_folder_badges,
+            onboarding::get_onboarding_state,
+            onboarding::complete_onboarding,
             folders::get_notes_directory,
+            folders::recheck_notes_directory,
+            config_paths::get_config_location,
             index::rebuild_index,
             settings::get_settings,
             settings::save_settings,
             git_share::git_prepare_repository,
             git_share::git_sync_now,
+            git_share::git_preview_sync,
+            sync_summary::generate_sync_summary,
             git_share::git_get_sync_status,
             git_share::git_open_remote_url,
+            git_credentials::set_git_credentials,
+            git_credentials::clear_git_credentials,
             on_this_day::check_on_this_day_now,
+            on_this_day::get_on_this_day_notes,
+            digest::generate_daily_digest,
+            backup::run_backup_now,
+            backup::list_backups,
+            backup::get_backup_status,
+            backup::restore_backup,
+            expiry::set_note_expiry,
             share::build_clipboard_payload,
+            export::export_note_html,
+            export::export_note_bundle,
+            library_export::export_library,
+            day_one::import_day_one,
+            embeddings::embedding_index_health,
+            reminders::export_tasks_to_reminders,
             share::copy_rich_text_to_clipboard,
+            share::share_note_via_sheet,
             share::copy_note_image_to_clipboard,
             share::copy_visible_note_image_to_clipboard,
+            share::save_note_image_snapshot,
             stats::get_capture_streak,
+            stats::get_capture_heatmap,
+            stats::get_capture_stats,
+            stats::get_activity_breakdown,
+            stats::export_stats,
             sticked_notes::list_sticked_notes,
             sticked_notes::create_sticked_note,
             sticked_notes::update_sticked_note,
             sticked_notes::close_sticked_note,
             sticked_notes::get_sticked_note,
+            sticked_notes::flush_sticked_notes,
+            tasks::get_task_stats,
+            templates::list_templates,
+            templates::apply_template,
             windows::hide_window,
             windows::hide_postit,
+            windows::capture_blurred,
+            capture_draft::take_capture_draft,
             windows::create_sticked_window,
             windows::close_sticked_window,
+            windows::focus_sticked_window,
+            windows::list_open_note_windows,
+            windows::focus_note_window,
+            windows::set_sticked_windows_visible,
+            windows::save_and_close_all_sticked,
+            windows::close_all_sticked_without_saving,
             windows::pin_capture_note,
+            windows::pin_template,
+            windows::open_daily_note,
             windows::open_note_for_viewing,
             windows::get_viewing_note_content,
+            windows::debug_state_report,
+            windows::set_viewing_note_dirty,
+            windows::show_quick_peek,
+            windows::get_quick_peek_content,
             windows::open_command_palette,
+            windows::pin_command_palette,
+            windows::take_pending_palette_query,
+            palette::list_palette_actions,
+            palette::execute_palette_action,
             windows::open_search,
             windows::open_manager,
             windows::open_settings,
+            windows::open_stats,
             windows::transfer_to_capture,
+            windows::toggle_zen_window,
+            windows::enter_zen_window,
+            windows::get_zen_content,
+            windows::exit_zen_window,
             windows::reopen_last_note,
+            deeplink::copy_note_link,
             shortcuts::reload_shortcuts,
             shortcuts::pause_shortcuts,
             shortcuts::resume_shortcuts,
@@ -499,6 +642,19 @@ fn main() {
             darwinkit::semantic_search,
             darwinkit::suggest_folder,
             analytics::get_analytics_device_id,
+            analytics::get_analytics_status,
+            analytics::get_analytics_log,
+            analytics::clear_analytics_log,
+            crash_reports::list_crash_reports,
+            crash_reports::get_crash_report,
+            crash_reports::delete_crash_reports,
+            app_log::get_recent_logs,
+            app_log::set_log_level,
+            diagnostics::get_diagnostics,
+            diagnostics::copy_diagnostics_to_clipboard,
+            spotlight::reindex_spotlight,
+            widget_feed::get_widget_feed,
+            selection_capture::capture_frontmost_selection,
             ai_assistant::ai_available,
             ai_assistant::ai_rephrase,
             ai_assistant::ai_summarize,
@@ -506,6 +662,8 @@ fn main() {
             ai_assistant::ai_generate,
             apple_notes::list_apple_notes,
             apple_notes::import_apple_note,
+            apple_notes::import_apple_notes,
+            apple_notes::apple_import_cancel,
             apple_notes::check_apple_notes_access,
             apple_notes::open_full_disk_access_settings,
             windows::show_apple_notes_picker_cmd,
@@ -526,6 +684,8 @@ fn main() {
             note_lock::save_locked_note,
             note_lock::is_note_locked,
             note_lock::export_recovery_key,
+            edit_locks::lock_note_edits,
+            edit_locks::unlock_note_edits,
             dictation::dictation_list_models,
             dictation::dictation_get_status,
             dictation::dictation_download_model,
@@ -534,19 +694,64 @@ fn main() {
             dictation::dictation_set_active_model,
             dictation::dictation_start,
             dictation::dictation_stop,
+            speech_dictation::start_dictation,
+            speech_dictation::stop_dictation,
         ])
-        .setup(|app| {
+        .setup(move |app| {
             let settings = settings::get_settings().unwrap_or_default();
+            embeddings::register_app(app.handle());
+            spotlight::register_app(app.handle());
+            widget_feed::register_app(app.handle());
+            filename_template::apply_settings(&settings);
+            user_notifications::register(app.handle(), handle_notification_click);
+
+            // Portable config on a drive that isn't mounted: run read-only.
+            if let Ok(config_paths::ConfigLocation::Unavailable(dir)) = config_paths::location() {
+                let dir = dir.to_string_lossy().to_string();
+                log::warn!("Portable config unavailable at {}; using read-only defaults", dir);
+                let _ = macos_notify::show(
+                    "Stik",
+                    "Notes drive not connected",
+                    "Settings and state are read-only until it is back.",
+                );
+                let _ = app.emit(config_paths::UNAVAILABLE_EVENT, dir);
+            }
+
+            // Notes directory on a drive that isn't mounted (or read-only):
+            // commands fail with NOTES_DIR_UNAVAILABLE until it is rechecked.
+            let notes_dir = storage::check_notes_directory();
+            if !notes_dir.available {
+                let _ = app.emit(storage::NOTES_DIR_UNAVAILABLE_EVENT, &notes_dir);
+            }
 
-            // Build in-memory note index — deferred when iCloud is enabled
-            // (needs DarwinKit bridge to resolve the iCloud container path)
+            // Build in-memory note index off the main thread; a search that
+            // arrives first waits for it instead of scanning again. Deferred
+            // when iCloud is enabled (needs DarwinKit bridge to resolve the
+            // iCloud container path)
             if !settings.icloud.enabled {
-                let index = app.state::<NoteIndex>();
-                if let Err(e) = index.build() {
-                    eprintln!("Failed to build note index: {}", e);
-                }
-                // Watch local notes directory for external changes
-                file_watcher::start(app.handle().clone());
+                let handle = app.handle().clone();
+                std::thread::Builder::new()
+                    .name("stik-index-build".to_string())
+                    .spawn(move || {
+                        let index = handle.state::<NoteIndex>();
+                        if let Err(e) = index.build() {
+                            log::error!("Failed to build note index: {}", e);
+                        }
+                        // Watch local notes directory for external changes
+                        file_watcher::start(handle.clone());
+                        if let Err(e) = on_this_day::maybe_show_on_this_day_notification(&index) {
+                            log::error!("Failed to check On This Day notification: {}", e);
+                        }
+                        on_index_built(&handle);
+                    })
+                    .ok();
+            }
+            if settings.ai_features_enabled {
+                let handle = app.handle().clone();
+                std::thread::Builder::new()
+                    .name("stik-embeddings-load".to_string())
+                    .spawn(move || handle.state::<EmbeddingIndex>().ensure_loaded())
+                    .ok();
             }
             shortcuts::register_shortcuts_from_settings(app.handle(), &settings);
             analytics::start_analytics(app.handle());
@@ -556,14 +761,8 @@ fn main() {
                 settings::apply_dock_icon_visibility(true);
             }
 
-            if !settings.icloud.enabled {
-                if let Err(e) = on_this_day::maybe_show_on_this_day_notification() {
-                    eprintln!("Failed to check On This Day notification: {}", e);
-                }
-            }
-
             // Restore capture window size from settings
-            if let Some((w, h)) = settings.capture_window_size {
+            if let Some((w, h)) = settings.window_size(settings::CAPTURE_WINDOW) {
                 if let Some(win) = app.get_webview_window("postit") {
                     let _ = win.set_size(tauri::Size::Logical(tauri::LogicalSize::new(w, h)));
                 }
@@ -572,7 +771,8 @@ fn main() {
             // (DevTools auto-open removed. ⌘⌥I still opens them on
             //  demand from the global-shortcut handler at line 200-ish.)
 
-            windows::restore_sticked_notes(app.handle());
+            // Sticked notes are restored once the event loop is running
+            // (RunEvent::Ready below).
             tray::setup_tray(app)?;
 
             // Apply tray icon visibility from settings
@@ -582,6 +782,11 @@ fn main() {
                 }
             }
             git_share::start_background_worker(app.handle().clone());
+            digest::start_scheduler(app.handle().clone());
+            backup::start_scheduler(app.handle().clone());
+            on_this_day::start_scheduler(app.handle().clone());
+            expiry::start_scheduler(app.handle().clone());
+            window_geometry::start_pruner(app.handle().clone());
 
             // Start DarwinKit sidecar bridge unconditionally — it now hosts
             // dictation (WhisperKit) which is needed regardless of the AI or
@@ -634,27 +839,13 @@ fn main() {
                             // Build note index now that DarwinKit can resolve the iCloud container
                             let index = monitor_handle.state::<NoteIndex>();
                             if let Err(e) = index.build() {
-                                eprintln!("Failed to build note index (iCloud): {}", e);
+                                log::error!("Failed to build note index (iCloud): {}", e);
                             }
 
                             if let Err(e) = storage::start_monitoring() {
-                                eprintln!("Failed to start iCloud monitoring: {}", e);
+                                log::error!("Failed to start iCloud monitoring: {}", e);
                             }
-                        })
-                        .ok();
-                }
-
-                let ai_enabled = settings::get_settings()
-                    .map(|s| s.ai_features_enabled)
-                    .unwrap_or(true);
-                if ai_enabled {
-                    let handle = app.handle().clone();
-                    std::thread::Builder::new()
-                        .name("stik-embeddings".to_string())
-                        .spawn(move || {
-                            let index = handle.state::<NoteIndex>();
-                            let emb = handle.state::<EmbeddingIndex>();
-                            embeddings::build_embeddings(&index, &emb);
+                            on_index_built(&monitor_handle);
                         })
                         .ok();
                 }
@@ -675,18 +866,42 @@ fn main() {
                     }
                 });
             } else {
-                eprintln!("Warning: postit window not found during setup");
+                log::warn!("Postit window not found during setup");
+            }
+
+            integration_server::start(app.handle().clone());
+            cli::start_listener(app.handle().clone());
+            if let Some(command) = launch_command.take() {
+                cli::run_on_launch(app.handle().clone(), command);
             }
 
             Ok(())
         })
         .build(tauri::generate_context!())
         .unwrap_or_else(|e| {
-            eprintln!("Fatal: Tauri application failed to build: {}", e);
+            log::error!("Tauri application failed to build: {}", e);
             std::process::exit(1);
         })
         .run(|app, event| {
+            if let RunEvent::Ready = event {
+                windows::restore_sticked_notes(app);
+                // Nothing can summon the capture window before onboarding
+                // picks a shortcut, so open it for the welcome flow.
+                if let Ok(settings) = settings::get_settings() {
+                    if !settings.onboarding.completed {
+                        show_postit_with_folder(app, &settings.default_folder);
+                    }
+                }
+            }
+            if let RunEvent::Exit = event {
+                if let Err(e) = sticked_notes::flush_sticked_notes() {
+                    log::error!("Failed to save sticked notes on exit: {}", e);
+                }
+            }
             if let RunEvent::Opened { urls } = event {
+                for url in urls.iter().filter(|url| url.scheme() == deeplink::SCHEME) {
+                    deeplink::handle_url(app, url);
+                }
                 let paths = urls
                     .into_iter()
                     .filter(|url| url.scheme() == "file")
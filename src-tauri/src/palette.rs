@@ -0,0 +1,332 @@
+/// Command palette actions, defined here rather than in the webview so new
+/// backend features show up in the palette without frontend changes.
+use chrono::{Duration, Local};
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::templates::{self, TemplateInfo};
+use crate::commands::{ai_assistant, darwinkit, digest, git_share, settings, sticked_notes};
+use crate::state::AppState;
+use crate::windows;
+
+/// Prefix of the generated "New Sticky from Template" action ids; the rest
+/// is the template id.
+const STICKY_TEMPLATE_ACTION_PREFIX: &str = "sticky_template:";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PaletteAction {
+    pub id: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub category: String,
+    /// Current binding from `system_shortcuts`, if the action has one.
+    pub shortcut: Option<String>,
+    pub enabled: bool,
+}
+
+/// Runtime state the `enabled` flags depend on, read at call time.
+struct PaletteContext {
+    git_enabled: bool,
+    ai_ready: bool,
+    has_sticked_notes: bool,
+    has_last_note: bool,
+    system_shortcuts: HashMap<String, String>,
+    templates: Vec<TemplateInfo>,
+}
+
+struct ActionSpec {
+    id: &'static str,
+    title: &'static str,
+    subtitle: Option<&'static str>,
+    category: &'static str,
+    /// Key into `system_shortcuts`.
+    shortcut_action: Option<&'static str>,
+    enabled: fn(&PaletteContext) -> bool,
+}
+
+const ACTIONS: &[ActionSpec] = &[
+    ActionSpec {
+        id: "new_note",
+        title: "New Note",
+        subtitle: Some("Capture into the default folder"),
+        category: "Notes",
+        shortcut_action: None,
+        enabled: |_| true,
+    },
+    ActionSpec {
+        id: "reopen_last_note",
+        title: "Reopen Last Note",
+        subtitle: None,
+        category: "Notes",
+        shortcut_action: Some("last_note"),
+        enabled: |ctx| ctx.has_last_note,
+    },
+    ActionSpec {
+        id: "show_sticked_notes",
+        title: "Show Sticked Notes",
+        subtitle: None,
+        category: "Sticked Notes",
+        shortcut_action: None,
+        enabled: |ctx| ctx.has_sticked_notes,
+    },
+    ActionSpec {
+        id: "hide_sticked_notes",
+        title: "Hide Sticked Notes",
+        subtitle: None,
+        category: "Sticked Notes",
+        shortcut_action: None,
+        enabled: |ctx| ctx.has_sticked_notes,
+    },
+    ActionSpec {
+        id: "save_and_close_sticked_notes",
+        title: "Save & Close All Sticked Notes",
+        subtitle: Some("Save each note into its folder"),
+        category: "Sticked Notes",
+        shortcut_action: None,
+        enabled: |ctx| ctx.has_sticked_notes,
+    },
+    ActionSpec {
+        id: "discard_sticked_notes",
+        title: "Close All Sticked Notes Without Saving",
+        subtitle: None,
+        category: "Sticked Notes",
+        shortcut_action: None,
+        enabled: |ctx| ctx.has_sticked_notes,
+    },
+    ActionSpec {
+        id: "sync_now",
+        title: "Sync Now",
+        subtitle: Some("Commit, pull and push the linked git folder"),
+        category: "Sync",
+        shortcut_action: None,
+        enabled: |ctx| ctx.git_enabled,
+    },
+    ActionSpec {
+        id: "import_apple_notes",
+        title: "Import from Apple Notes",
+        subtitle: None,
+        category: "Import",
+        shortcut_action: None,
+        enabled: |_| cfg!(target_os = "macos"),
+    },
+    ActionSpec {
+        id: "generate_daily_digest",
+        title: "Generate Yesterday's Digest",
+        subtitle: Some("Summarize yesterday's captures on-device"),
+        category: "AI",
+        shortcut_action: None,
+        enabled: |ctx| ctx.ai_ready,
+    },
+    ActionSpec {
+        id: "open_stats",
+        title: "Stats",
+        subtitle: None,
+        category: "App",
+        shortcut_action: Some("stats"),
+        enabled: |_| true,
+    },
+    ActionSpec {
+        id: "open_settings",
+        title: "Settings",
+        subtitle: None,
+        category: "App",
+        shortcut_action: Some("settings"),
+        enabled: |_| true,
+    },
+];
+
+#[tauri::command]
+pub fn list_palette_actions(app: AppHandle) -> Result<Vec<PaletteAction>, String> {
+    Ok(build_actions(&palette_context(&app)))
+}
+
+#[tauri::command]
+pub async fn execute_palette_action(app: AppHandle, id: String) -> Result<bool, String> {
+    if let Some(template_id) = id.strip_prefix(STICKY_TEMPLATE_ACTION_PREFIX) {
+        windows::pin_template(app, template_id.to_string(), String::new()).await?;
+        return Ok(true);
+    }
+    let spec = find_action(&id)?;
+    if !(spec.enabled)(&palette_context(&app)) {
+        return Err(format!("'{}' is not available right now", spec.title));
+    }
+
+    match spec.id {
+        "new_note" => {
+            let settings = settings::get_settings().unwrap_or_default();
+            windows::show_postit_with_folder(&app, &settings.default_folder);
+        }
+        "reopen_last_note" => {
+            windows::reopen_last_note(app).await?;
+        }
+        "show_sticked_notes" => {
+            windows::set_sticked_windows_visible(app, true)?;
+        }
+        "hide_sticked_notes" => {
+            windows::set_sticked_windows_visible(app, false)?;
+        }
+        "save_and_close_sticked_notes" => {
+            windows::save_and_close_all_sticked(app)?;
+        }
+        "discard_sticked_notes" => windows::confirm_close_all_sticked_without_saving(&app),
+        "sync_now" => git_share::notify_force_sync(),
+        "import_apple_notes" => windows::show_apple_notes_picker(&app),
+        "generate_daily_digest" => {
+            let yesterday = Local::now().date_naive() - Duration::days(1);
+            tauri::async_runtime::spawn_blocking(move || {
+                digest::generate_daily_digest_inner(&app, yesterday)
+            })
+            .await
+            .map_err(|e| format!("Daily digest failed: {}", e))??;
+        }
+        "open_stats" => windows::show_stats_window(&app),
+        "open_settings" => windows::show_settings(&app),
+        other => return Err(format!("Palette action '{}' has no handler", other)),
+    }
+    Ok(true)
+}
+
+fn find_action(id: &str) -> Result<&'static ActionSpec, String> {
+    ACTIONS
+        .iter()
+        .find(|spec| spec.id == id)
+        .ok_or_else(|| format!("Unknown palette action: {}", id))
+}
+
+fn palette_context(app: &AppHandle) -> PaletteContext {
+    let settings = settings::load_settings_from_file().unwrap_or_default();
+    let has_last_note = app
+        .state::<AppState>()
+        .last_saved_note
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .is_some();
+
+    PaletteContext {
+        git_enabled: git_share::git_get_sync_status()
+            .map(|status| status.enabled)
+            .unwrap_or(false),
+        ai_ready: ai_assistant::check_ai_enabled().is_ok() && darwinkit::darwinkit_status().ready,
+        has_sticked_notes: sticked_notes::list_sticked_notes()
+            .map(|notes| !notes.is_empty())
+            .unwrap_or(false),
+        has_last_note,
+        system_shortcuts: settings.system_shortcuts,
+        templates: templates::list_templates().unwrap_or_default(),
+    }
+}
+
+fn build_actions(ctx: &PaletteContext) -> Vec<PaletteAction> {
+    ACTIONS
+        .iter()
+        .map(|spec| PaletteAction {
+            id: spec.id.to_string(),
+            title: spec.title.to_string(),
+            subtitle: spec.subtitle.map(str::to_string),
+            category: spec.category.to_string(),
+            shortcut: spec
+                .shortcut_action
+                .and_then(|action| ctx.system_shortcuts.get(action))
+                .filter(|shortcut| !shortcut.is_empty())
+                .cloned(),
+            enabled: (spec.enabled)(ctx),
+        })
+        .chain(ctx.templates.iter().map(|template| PaletteAction {
+            id: format!("{}{}", STICKY_TEMPLATE_ACTION_PREFIX, template.id),
+            title: format!("New Sticky from Template: {}", template.name),
+            subtitle: template.preview.lines().next().map(str::to_string),
+            category: "Sticked Notes".to_string(),
+            shortcut: None,
+            enabled: true,
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> PaletteContext {
+        PaletteContext {
+            git_enabled: false,
+            ai_ready: false,
+            has_sticked_notes: false,
+            has_last_note: false,
+            system_shortcuts: settings::default_system_shortcuts(),
+            templates: Vec::new(),
+        }
+    }
+
+    fn action<'a>(actions: &'a [PaletteAction], id: &str) -> &'a PaletteAction {
+        actions
+            .iter()
+            .find(|action| action.id == id)
+            .expect("action listed")
+    }
+
+    #[test]
+    fn enabled_flags_follow_runtime_state() {
+        let actions = build_actions(&context());
+        assert!(!action(&actions, "sync_now").enabled);
+        assert!(!action(&actions, "generate_daily_digest").enabled);
+        assert!(!action(&actions, "reopen_last_note").enabled);
+        assert!(action(&actions, "open_settings").enabled);
+
+        let actions = build_actions(&PaletteContext {
+            git_enabled: true,
+            has_last_note: true,
+            ..context()
+        });
+        assert!(action(&actions, "sync_now").enabled);
+        assert!(action(&actions, "reopen_last_note").enabled);
+    }
+
+    #[test]
+    fn shortcuts_come_from_system_shortcuts() {
+        let mut ctx = context();
+        ctx.system_shortcuts
+            .insert("settings".to_string(), "Cmd+Alt+S".to_string());
+        let actions = build_actions(&ctx);
+        assert_eq!(
+            action(&actions, "open_settings").shortcut.as_deref(),
+            Some("Cmd+Alt+S")
+        );
+        assert_eq!(action(&actions, "new_note").shortcut, None);
+    }
+
+    #[test]
+    fn templates_become_sticky_actions() {
+        let actions = build_actions(&PaletteContext {
+            templates: vec![TemplateInfo {
+                id: "custom:standup".to_string(),
+                name: "standup".to_string(),
+                preview: "# Standup\nYesterday:".to_string(),
+                shortcut: "/standup".to_string(),
+                builtin: false,
+            }],
+            ..context()
+        });
+        let sticky = action(&actions, "sticky_template:custom:standup");
+        assert_eq!(sticky.title, "New Sticky from Template: standup");
+        assert_eq!(sticky.subtitle.as_deref(), Some("# Standup"));
+        assert!(sticky.enabled);
+    }
+
+    #[test]
+    fn unknown_action_ids_error() {
+        assert!(find_action("open_settings").is_ok());
+        let err = find_action("launch_rockets")
+            .err()
+            .expect("unknown id errors");
+        assert!(err.contains("launch_rockets"));
+    }
+
+    #[test]
+    fn action_ids_are_unique() {
+        let mut ids: Vec<&str> = ACTIONS.iter().map(|spec| spec.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), ACTIONS.len());
+    }
+}
@@ -206,7 +206,14 @@ pub fn register_shortcuts_from_settings(app: &AppHandle, settings: &StikSettings
     let mut map = state.shortcut_to_folder.lock().unwrap_or_else(|e| e.into_inner());
     map.clear();
 
-    for mapping in &settings.shortcut_mappings {
+    // The seeded folder shortcuts wait for onboarding, which picks the
+    // capture shortcut.
+    let folder_mappings: &[settings::ShortcutMapping] = if settings.onboarding.completed {
+        &settings.shortcut_mappings
+    } else {
+        &[]
+    };
+    for mapping in folder_mappings {
         if !mapping.enabled {
             continue;
         }
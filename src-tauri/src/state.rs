@@ -1,5 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Mutex;
+use std::time::Instant;
+use tauri::menu::{MenuItem, Submenu};
+use tauri::Wry;
 
 pub struct LastSavedNote {
     pub path: String,
@@ -11,15 +14,102 @@ pub struct ViewingNoteContent {
     pub content: String,
     pub folder: String,
     pub path: String,
+    /// The window has edits that differ from `content`; external changes
+    /// are offered as a conflict instead of replacing them.
+    pub has_unsaved_edits: bool,
+    /// Last time a window loaded this entry; the oldest closed ones are
+    /// evicted first once the cache is over its limit.
+    pub last_used: Instant,
+}
+
+/// What the quick-peek window is showing. Unlike `viewing_notes` there is
+/// at most one, and it holds rendered HTML since the peek is read-only.
+pub struct QuickPeekNote {
+    pub path: String,
+    pub title: String,
+    pub html: String,
+}
+
+/// Capture content while it is being edited in the zen window, and the
+/// sticked notes that were lowered to make room for it.
+pub struct ZenSession {
+    pub content: String,
+    pub folder: String,
+    pub lowered_sticked: Vec<String>,
+}
+
+/// Tray git status submenu and the item inside it that carries the last
+/// error (or a settings shortcut).
+#[derive(Clone)]
+pub struct TraySyncItems {
+    pub status: Submenu<Wry>,
+    pub detail: MenuItem<Wry>,
+}
+
+/// A recent `save_note`, so an identical save moments later (a double
+/// ⌘Enter, a frontend retry) returns this note instead of writing another.
+#[derive(Debug, Clone)]
+pub struct RecentSave {
+    pub content_hash: u64,
+    pub folder: String,
+    pub at: Instant,
+    /// Where the note ended up, after any auto-filing.
+    pub path: String,
+    pub saved_folder: String,
+    pub filename: String,
+}
+
+/// The app the capture window was summoned over and, for browsers, the
+/// page it was showing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureSource {
+    pub app: String,
+    pub title: Option<String>,
+    pub url: Option<String>,
+}
+
+/// Where we last placed an auxiliary window, and whether the user has
+/// dragged it since.
+pub struct WindowPlacement {
+    pub position: (i32, i32),
+    pub user_moved: bool,
 }
 
 pub struct AppState {
     pub shortcut_to_folder: Mutex<HashMap<String, String>>,
     pub shortcut_to_action: Mutex<HashMap<String, String>>,
     pub viewing_notes: Mutex<HashMap<String, ViewingNoteContent>>,
+    /// Note path -> viewing window id, the reverse of `viewing_notes`.
+    pub viewing_note_ids: Mutex<HashMap<String, String>>,
+    pub quick_peek_note: Mutex<Option<QuickPeekNote>>,
+    /// Some while the zen window is open.
+    pub zen_session: Mutex<Option<ZenSession>>,
     pub previous_focused_window: Mutex<Option<String>>,
     pub postit_was_visible: Mutex<bool>,
+    /// Pid of the app that was frontmost when the postit was shown, so
+    /// hiding the postit can hand focus back to it.
+    pub previous_frontmost_app: Mutex<Option<i32>>,
+    /// Where the open capture was started, for its `capture_context`
+    /// footer. Only recorded while that setting is on.
+    pub capture_source: Mutex<Option<CaptureSource>>,
+    /// Pinned palettes stay open behind the notes opened from them.
+    /// Deliberately not persisted; every launch starts unpinned.
+    pub command_palette_pinned: Mutex<bool>,
+    /// Query from a `stik://search` link, taken by the palette once shown.
+    pub pending_palette_query: Mutex<Option<String>>,
     pub last_saved_note: Mutex<Option<LastSavedNote>>,
+    /// Auto-filed note path -> folder it was saved to, for `undo_auto_file`.
+    pub auto_filed_notes: Mutex<HashMap<String, String>>,
+    /// Newest last; see `RecentSave`.
+    pub recent_saves: Mutex<VecDeque<RecentSave>>,
+    /// Tray "Streak: N days" item, set once the tray is built.
+    pub tray_streak_item: Mutex<Option<MenuItem<Wry>>>,
+    /// None while git sharing is off and the tray shows no sync status.
+    pub tray_sync_items: Mutex<Option<TraySyncItems>>,
+    /// Window label -> placement, for windows opened on the cursor's monitor.
+    pub window_placements: Mutex<HashMap<String, WindowPlacement>>,
+    /// Labels of open `sticked-*` windows, most recently focused first.
+    pub note_window_focus_order: Mutex<Vec<String>>,
 }
 
 impl AppState {
@@ -28,9 +118,22 @@ impl AppState {
             shortcut_to_folder: Mutex::new(HashMap::new()),
             shortcut_to_action: Mutex::new(HashMap::new()),
             viewing_notes: Mutex::new(HashMap::new()),
+            viewing_note_ids: Mutex::new(HashMap::new()),
+            quick_peek_note: Mutex::new(None),
+            zen_session: Mutex::new(None),
             previous_focused_window: Mutex::new(None),
             postit_was_visible: Mutex::new(false),
+            previous_frontmost_app: Mutex::new(None),
+            capture_source: Mutex::new(None),
+            command_palette_pinned: Mutex::new(false),
+            pending_palette_query: Mutex::new(None),
             last_saved_note: Mutex::new(None),
+            auto_filed_notes: Mutex::new(HashMap::new()),
+            recent_saves: Mutex::new(VecDeque::new()),
+            tray_streak_item: Mutex::new(None),
+            tray_sync_items: Mutex::new(None),
+            window_placements: Mutex::new(HashMap::new()),
+            note_window_focus_order: Mutex::new(Vec::new()),
         }
     }
 }
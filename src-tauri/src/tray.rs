@@ -1,41 +1,115 @@
-use crate::commands::{settings, stats};
-use crate::windows::show_postit_with_folder;
+use crate::commands::index::NoteIndex;
+use crate::commands::{macos_notify, notes, settings, stats, sticked_notes, tray_menu};
+use crate::windows::{
+    confirm_close_all_sticked_without_saving, focus_sticked_window, open_note_for_viewing,
+    pin_template, save_and_close_all_sticked, set_sticked_windows_visible, show_postit_with_folder,
+    show_settings, show_stats_window,
+};
+use std::path::Path;
 use tauri::image::Image;
-use tauri::menu::{Menu, MenuItem};
 use tauri::tray::TrayIconBuilder;
-use tauri::App;
+use tauri::{App, AppHandle, Manager};
 
 pub fn setup_tray(app: &App) -> Result<(), Box<dyn std::error::Error>> {
-    let streak_days = stats::calculate_and_persist_capture_streak().unwrap_or_else(|e| {
-        eprintln!("Failed to compute capture streak: {}", e);
-        0
-    });
-    let streak_label = stats::format_capture_streak_label(streak_days);
-
-    let quit = MenuItem::with_id(app, "quit", "Quit Stik", true, None::<&str>)?;
-    let new_note = MenuItem::with_id(app, "new_note", "New Note", true, None::<&str>)?;
-    let capture_streak =
-        MenuItem::with_id(app, "capture_streak", &streak_label, false, None::<&str>)?;
-
-    let menu = Menu::with_items(app, &[&new_note, &capture_streak, &quit])?;
+    let menu = tray_menu::build_startup_tray_menu(app.handle())?;
+    stats::start_midnight_streak_timer(app.handle().clone());
 
     let tray_icon = Image::from_bytes(include_bytes!("../icons/tray-icon.png"))?;
 
-    let _tray = TrayIconBuilder::with_id("main-tray")
+    let _tray = TrayIconBuilder::with_id(tray_menu::TRAY_ID)
         .icon(tray_icon)
         .icon_as_template(true)
         .menu(&menu)
         .on_menu_event(|app, event| match event.id.as_ref() {
             "quit" => {
+                let _ = sticked_notes::flush_sticked_notes();
                 app.exit(0);
             }
             "new_note" => {
                 let settings = settings::get_settings().unwrap_or_default();
                 show_postit_with_folder(app, &settings.default_folder);
             }
-            _ => {}
+            tray_menu::SYNC_SETTINGS_ID => {
+                show_settings(app);
+            }
+            tray_menu::STATS_ID => {
+                show_stats_window(app);
+            }
+            tray_menu::STICKED_SHOW_ALL_ID => {
+                let _ = set_sticked_windows_visible(app.clone(), true);
+            }
+            tray_menu::STICKED_HIDE_ALL_ID => {
+                let _ = set_sticked_windows_visible(app.clone(), false);
+            }
+            tray_menu::STICKED_SAVE_CLOSE_ALL_ID => {
+                if let Err(e) = save_and_close_all_sticked(app.clone()) {
+                    log::error!("Failed to save and close sticked notes: {}", e);
+                }
+            }
+            tray_menu::STICKED_DISCARD_ALL_ID => {
+                confirm_close_all_sticked_without_saving(app);
+            }
+            id => {
+                if let Some(folder) = tray_menu::new_note_folder(id) {
+                    show_postit_with_folder(app, folder);
+                } else if let Some(note) = tray_menu::recent_note(id) {
+                    open_recent_note(app, note);
+                } else if let Some(note_id) = tray_menu::sticked_note_id(id) {
+                    if let Err(e) = focus_sticked_window(app.clone(), note_id.to_string()) {
+                        log::error!("Failed to focus sticked note: {}", e);
+                        tray_menu::rebuild_tray_menu(app);
+                    }
+                } else if let Some(template_id) = tray_menu::sticked_template_id(id) {
+                    let app = app.clone();
+                    let template_id = template_id.to_string();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = pin_template(app, template_id, String::new()).await {
+                            log::error!("Failed to pin template: {}", e);
+                        }
+                    });
+                }
+            }
         })
         .build(app)?;
 
     Ok(())
 }
+
+fn open_recent_note(app: &AppHandle, note: tray_menu::RecentNote) {
+    // Notes with an id are found wherever they moved to; the menu may still
+    // predate a delete, so say so rather than erroring.
+    let path = match note {
+        tray_menu::RecentNote::Id(id) => app.state::<NoteIndex>().path_for_id(id).ok().flatten(),
+        tray_menu::RecentNote::Path(path) => Some(path.to_string()),
+    };
+    let content = path
+        .as_deref()
+        .and_then(|path| notes::get_note_content_inner(path).ok());
+    let (Some(path), Some(content)) = (path, content) else {
+        let _ = macos_notify::show("Stik", "Recent note", "That note no longer exists.");
+        if let tray_menu::RecentNote::Path(path) = note {
+            app.state::<NoteIndex>().remove(path);
+        }
+        tray_menu::rebuild_tray_menu(app);
+        return;
+    };
+
+    let folder = app
+        .state::<NoteIndex>()
+        .get(&path)
+        .map(|entry| entry.folder)
+        .or_else(|| {
+            Path::new(&path)
+                .parent()
+                .and_then(|parent| parent.file_name())
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .unwrap_or_default();
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = open_note_for_viewing(app, content, folder, path).await {
+            log::error!("Failed to open recent note: {}", e);
+        }
+    });
+}
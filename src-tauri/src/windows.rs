@@ -1,15 +1,35 @@
-use crate::commands::{notes, settings, sticked_notes};
-use crate::state::{AppState, LastSavedNote};
+use crate::commands::{
+    capture_context, capture_draft, daily_note, edit_locks, embeddings, export, focus_mode, frontmost_app, index, note_lock, note_metadata,
+    notes, settings, share, sticked_notes, templates, window_geometry,
+};
+use crate::state::{AppState, LastSavedNote, QuickPeekNote, WindowPlacement, ZenSession};
+use settings::{StickedSizeLimits, StikSettings};
 use sticked_notes::StickedNote;
-use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, WebviewUrl, WebviewWindowBuilder};
+use tauri::{
+    AppHandle, Emitter, Manager, PhysicalPosition, Runtime, WebviewUrl, WebviewWindowBuilder,
+};
 
 const SETTINGS_WINDOW_WIDTH: f64 = 860.0;
 const SETTINGS_WINDOW_HEIGHT: f64 = 720.0;
 const SETTINGS_WINDOW_MIN_WIDTH: f64 = 760.0;
 const SETTINGS_WINDOW_MIN_HEIGHT: f64 = 560.0;
+const STATS_WINDOW_WIDTH: f64 = 720.0;
+const STATS_WINDOW_HEIGHT: f64 = 560.0;
+const STATS_WINDOW_MIN_WIDTH: f64 = 560.0;
+const STATS_WINDOW_MIN_HEIGHT: f64 = 420.0;
+const QUICK_PEEK_WIDTH: f64 = 380.0;
+const QUICK_PEEK_HEIGHT: f64 = 280.0;
+/// Gap between the anchor (e.g. a search result's edge) and the peek.
+const QUICK_PEEK_OFFSET: f64 = 12.0;
+const ZEN_WINDOW: &str = "zen";
 
 /// Minimum overlap (in physical pixels) between window and monitor for the position to be usable.
 const MIN_OVERLAP: f64 = 80.0;
+/// Gap between restored sticked windows, so a dozen of them don't all
+/// spin up webviews in the same frame.
+const STICKED_RESTORE_STAGGER_MS: u64 = 40;
+/// How often a deferred sticky restore checks whether Focus has ended.
+const STICKED_RESTORE_DEFER_SECONDS: u64 = 30;
 
 /// Check if a window at (x, y) with the given size overlaps sufficiently with any connected
 /// monitor. All coordinates are in **physical pixels** (same space as `outerPosition()`).
@@ -62,10 +82,15 @@ fn remember_last_note(state: &AppState, path: &str, folder: &str) {
 
 pub fn show_postit_with_folder(app: &AppHandle, folder: &str) {
     if let Some(window) = app.get_webview_window("postit") {
+        if !window.is_visible().unwrap_or(false) {
+            remember_frontmost_app(app);
+            capture_context::remember_capture_source(app);
+        }
         if let Ok(s) = settings::load_settings_from_file() {
             // Restore persisted capture window size
-            let (w, h) = s.capture_window_size.unwrap_or((400.0, 280.0));
-            if s.capture_window_size.is_some() {
+            let saved_size = s.window_size(settings::CAPTURE_WINDOW);
+            let (w, h) = saved_size.unwrap_or((400.0, 280.0));
+            if saved_size.is_some() {
                 let _ = window.set_size(tauri::Size::Logical(tauri::LogicalSize::new(w, h)));
             }
             // Restore position only if it's visible on a connected monitor.
@@ -85,99 +110,239 @@ pub fn show_postit_with_folder(app: &AppHandle, folder: &str) {
     }
 }
 
-pub fn show_command_palette(app: &AppHandle) {
-    {
-        let state = app.state::<AppState>();
-        let mut postit_visible = state.postit_was_visible.lock().unwrap_or_else(|e| e.into_inner());
-        *postit_visible = app
-            .get_webview_window("postit")
-            .map(|w| w.is_visible().unwrap_or(false))
-            .unwrap_or(false);
-    }
+/// Work area (x, y, width, height; physical pixels) of the monitor under
+/// the cursor. Falls back to the primary monitor when the cursor position
+/// is unknown or lies on a monitor that is no longer connected.
+fn cursor_monitor_work_area(app: &AppHandle) -> Option<(f64, f64, f64, f64)> {
+    let areas: Vec<(f64, f64, f64, f64)> = app
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(monitor_work_area)
+        .collect();
+
+    let under_cursor = app
+        .cursor_position()
+        .ok()
+        .and_then(|cursor| area_containing((cursor.x, cursor.y), &areas));
+
+    under_cursor
+        .map(|i| areas[i])
+        .or_else(|| {
+            app.primary_monitor()
+                .ok()
+                .flatten()
+                .as_ref()
+                .map(monitor_work_area)
+        })
+        .or_else(|| areas.first().copied())
+}
 
-    for (label, window) in app.webview_windows() {
-        if label.starts_with("sticked-") {
-            let _ = window.set_always_on_top(false);
-        }
-    }
+fn monitor_work_area(monitor: &tauri::Monitor) -> (f64, f64, f64, f64) {
+    let area = monitor.work_area();
+    (
+        area.position.x as f64,
+        area.position.y as f64,
+        area.size.width as f64,
+        area.size.height as f64,
+    )
+}
 
-    if let Some(window) = app.get_webview_window("command-palette") {
-        let _ = window.show();
-        let _ = window.set_focus();
+fn area_containing(point: (f64, f64), areas: &[(f64, f64, f64, f64)]) -> Option<usize> {
+    let (px, py) = point;
+    areas
+        .iter()
+        .position(|&(x, y, w, h)| px >= x && px < x + w && py >= y && py < y + h)
+}
+
+fn center_in_area(w: f64, h: f64, area: (f64, f64, f64, f64)) -> (f64, f64) {
+    let (x, y, aw, ah) = area;
+    (x + ((aw - w) / 2.0).max(0.0), y + ((ah - h) / 2.0).max(0.0))
+}
+
+/// Center `win` on the cursor's monitor and start noticing manual moves,
+/// so later re-shows leave a user-placed window alone.
+fn place_on_cursor_monitor(app: &AppHandle, win: &tauri::WebviewWindow) {
+    let (Some(area), Ok(size)) = (cursor_monitor_work_area(app), win.outer_size()) else {
+        let _ = win.center();
         return;
-    }
+    };
+    let (x, y) = center_in_area(size.width as f64, size.height as f64, area);
+    let position = (x.round() as i32, y.round() as i32);
 
-    let window = WebviewWindowBuilder::new(
-        app,
-        "command-palette",
-        WebviewUrl::App("index.html?window=command-palette".into()),
-    )
-    .title("Command Palette")
-    .inner_size(700.0, 480.0)
-    .resizable(false)
-    .decorations(false)
-    .transparent(true)
-    .always_on_top(true)
-    .skip_taskbar(true)
-    .center()
-    .build();
+    let label = win.label().to_string();
+    let is_new = {
+        let state = app.state::<AppState>();
+        let mut placements = state.window_placements.lock().unwrap_or_else(|e| e.into_inner());
+        placements
+            .insert(
+                label.clone(),
+                WindowPlacement {
+                    position,
+                    user_moved: false,
+                },
+            )
+            .is_none()
+    };
+    let _ = win.set_position(tauri::Position::Physical(PhysicalPosition::new(
+        position.0, position.1,
+    )));
 
-    if let Ok(win) = window {
+    if is_new {
         let app_handle = app.clone();
-        win.on_window_event(move |event| {
-            match event {
-                tauri::WindowEvent::Focused(focused) => {
-                    if !focused {
-                        for (label, window) in app_handle.webview_windows() {
-                            if label.starts_with("sticked-") {
-                                let _ = window.set_always_on_top(true);
-                            }
-                        }
-                    }
-                }
-                tauri::WindowEvent::Destroyed => {
-                    for (label, window) in app_handle.webview_windows() {
-                        if label.starts_with("sticked-") {
-                            let _ = window.set_always_on_top(true);
-                        }
-                    }
-
-                    let state = app_handle.state::<AppState>();
-                    let postit_visible = *state.postit_was_visible.lock().unwrap_or_else(|e| e.into_inner());
-
-                    if postit_visible {
-                        let has_viewing_windows = app_handle
-                            .webview_windows()
-                            .iter()
-                            .any(|(label, _)| label.starts_with("sticked-view-"));
-                        if !has_viewing_windows {
-                            if let Some(postit) = app_handle.get_webview_window("postit") {
-                                let _ = postit.show();
-                                let _ = postit.set_focus();
-                            }
-                        }
+        win.on_window_event(move |event| match event {
+            tauri::WindowEvent::Moved(pos) => {
+                let state = app_handle.state::<AppState>();
+                let mut placements =
+                    state.window_placements.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(placement) = placements.get_mut(&label) {
+                    // A couple of pixels of slack for scale-factor rounding.
+                    let (x, y) = placement.position;
+                    if (pos.x - x).abs() > 2 || (pos.y - y).abs() > 2 {
+                        placement.user_moved = true;
                     }
                 }
-                _ => {}
             }
+            tauri::WindowEvent::Destroyed => {
+                let state = app_handle.state::<AppState>();
+                let mut placements =
+                    state.window_placements.lock().unwrap_or_else(|e| e.into_inner());
+                placements.remove(&label);
+            }
+            _ => {}
         });
     }
 }
 
-pub fn show_settings(app: &AppHandle) {
-    {
+/// Follow the cursor to another monitor on re-show, unless the user moved
+/// the window themselves this session.
+fn reshow_on_cursor_monitor(app: &AppHandle, win: &tauri::WebviewWindow) {
+    let user_moved = {
         let state = app.state::<AppState>();
-        let mut prev_window = state.previous_focused_window.lock().unwrap_or_else(|e| e.into_inner());
-        *prev_window = None;
+        let placements = state.window_placements.lock().unwrap_or_else(|e| e.into_inner());
+        placements
+            .get(win.label())
+            .is_some_and(|placement| placement.user_moved)
+    };
+    if !user_moved {
+        place_on_cursor_monitor(app, win);
+    }
+}
+
+/// Auxiliary windows that drop sticked notes below themselves while open.
+const OVERLAY_WINDOWS: [&str; 3] = ["command-palette", "settings", "stats"];
+
+/// How an overlay window hands the screen back when it goes away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct OverlayPolicy {
+    /// Put sticked notes back on top as soon as the overlay loses focus,
+    /// not only when it closes.
+    restore_on_blur: bool,
+    /// On close, refocus the sticked note that had focus when it opened.
+    refocus_previous_sticked: bool,
+    /// On close, leave the postit hidden while notes opened from the
+    /// overlay are being viewed.
+    yield_to_viewing_windows: bool,
+}
+
+const SETTINGS_POLICY: OverlayPolicy = OverlayPolicy {
+    restore_on_blur: false,
+    refocus_previous_sticked: true,
+    yield_to_viewing_windows: false,
+};
+
+const PALETTE_POLICY: OverlayPolicy = OverlayPolicy {
+    restore_on_blur: true,
+    refocus_previous_sticked: false,
+    yield_to_viewing_windows: true,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OverlayEvent {
+    Focused(bool),
+    Destroyed,
+}
 
-        for (label, window) in app.webview_windows() {
-            if label.starts_with("sticked-") {
-                if window.is_focused().unwrap_or(false) {
-                    *prev_window = Some(label.clone());
-                    break;
+/// What the rest of the app looks like when an overlay event arrives.
+#[derive(Debug, Default)]
+struct OverlayContext<'a> {
+    previous_sticked: Option<&'a str>,
+    postit_was_visible: bool,
+    has_viewing_windows: bool,
+    /// Another overlay is still up and will restore things when it closes.
+    other_overlay_open: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum FocusTarget<'a> {
+    Sticked(&'a str),
+    Postit,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct OverlayActions<'a> {
+    raise_sticked: bool,
+    focus: Option<FocusTarget<'a>>,
+}
+
+fn overlay_actions<'a>(
+    policy: OverlayPolicy,
+    event: OverlayEvent,
+    ctx: &OverlayContext<'a>,
+) -> OverlayActions<'a> {
+    // Raising sticked notes while another overlay is up would put them
+    // over it; that overlay restores them when it goes away.
+    if ctx.other_overlay_open {
+        return OverlayActions::default();
+    }
+    match event {
+        OverlayEvent::Focused(true) => OverlayActions::default(),
+        OverlayEvent::Focused(false) => OverlayActions {
+            raise_sticked: policy.restore_on_blur,
+            focus: None,
+        },
+        OverlayEvent::Destroyed => {
+            let previous = ctx
+                .previous_sticked
+                .filter(|_| policy.refocus_previous_sticked);
+            let focus = match previous {
+                Some(label) => Some(FocusTarget::Sticked(label)),
+                None if ctx.postit_was_visible
+                    && !(policy.yield_to_viewing_windows && ctx.has_viewing_windows) =>
+                {
+                    Some(FocusTarget::Postit)
                 }
+                None => None,
+            };
+            OverlayActions {
+                raise_sticked: true,
+                focus,
             }
         }
+    }
+}
+
+fn set_sticked_always_on_top(app: &AppHandle, on_top: bool) {
+    for (label, window) in app.webview_windows() {
+        if label.starts_with("sticked-") {
+            let _ = window.set_always_on_top(on_top);
+        }
+    }
+}
+
+/// Before an overlay opens: remember which sticked note had focus and
+/// whether the postit was up, and drop sticked notes below it.
+fn lower_sticked_windows_for(app: &AppHandle) {
+    {
+        let state = app.state::<AppState>();
+        let mut prev_window = state.previous_focused_window.lock().unwrap_or_else(|e| e.into_inner());
+        *prev_window = app
+            .webview_windows()
+            .into_iter()
+            .find(|(label, window)| {
+                label.starts_with("sticked-") && window.is_focused().unwrap_or(false)
+            })
+            .map(|(label, _)| label);
 
         let mut postit_visible = state.postit_was_visible.lock().unwrap_or_else(|e| e.into_inner());
         *postit_visible = app
@@ -186,73 +351,311 @@ pub fn show_settings(app: &AppHandle) {
             .unwrap_or(false);
     }
 
-    for (label, window) in app.webview_windows() {
-        if label.starts_with("sticked-") {
-            let _ = window.set_always_on_top(false);
-        }
+    set_sticked_always_on_top(app, false);
+}
+
+fn handle_overlay_event(app: &AppHandle, label: &str, policy: OverlayPolicy, event: OverlayEvent) {
+    let windows = app.webview_windows();
+    let state = app.state::<AppState>();
+    let previous = state
+        .previous_focused_window
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    let ctx = OverlayContext {
+        previous_sticked: previous.as_deref(),
+        postit_was_visible: *state.postit_was_visible.lock().unwrap_or_else(|e| e.into_inner()),
+        has_viewing_windows: windows.keys().any(|l| l.starts_with("sticked-view-")),
+        other_overlay_open: OVERLAY_WINDOWS.iter().any(|other| {
+            *other != label
+                && windows
+                    .get(*other)
+                    .is_some_and(|w| w.is_visible().unwrap_or(false))
+        }),
+    };
+
+    let actions = overlay_actions(policy, event, &ctx);
+    if actions.raise_sticked {
+        set_sticked_always_on_top(app, true);
+    }
+    let target = match actions.focus {
+        Some(FocusTarget::Sticked(label)) => windows.get(label),
+        Some(FocusTarget::Postit) => windows.get("postit"),
+        None => None,
+    };
+    if let Some(window) = target {
+        let _ = window.show();
+        let _ = window.set_focus();
     }
+}
 
-    if let Some(window) = app.get_webview_window("settings") {
+/// Show the overlay window `label`, building it with `configure` the first
+/// time, with sticked notes lowered beneath it until `policy` says to put
+/// them back. Returns the window and whether it was just created.
+fn with_overlay_window<'a, F>(
+    app: &'a AppHandle,
+    label: &str,
+    configure: F,
+    policy: OverlayPolicy,
+) -> Option<(tauri::WebviewWindow, bool)>
+where
+    F: FnOnce(
+        WebviewWindowBuilder<'a, tauri::Wry, AppHandle>,
+    ) -> WebviewWindowBuilder<'a, tauri::Wry, AppHandle>,
+{
+    lower_sticked_windows_for(app);
+
+    if let Some(window) = app.get_webview_window(label) {
+        reshow_on_cursor_monitor(app, &window);
         let _ = window.show();
         let _ = window.set_focus();
-        return;
+        return Some((window, false));
     }
 
-    let window = WebviewWindowBuilder::new(
+    let builder = WebviewWindowBuilder::new(
         app,
-        "settings",
-        WebviewUrl::App("index.html?window=settings".into()),
+        label,
+        WebviewUrl::App(format!("index.html?window={}", label).into()),
     )
-    .title("Settings")
-    .inner_size(SETTINGS_WINDOW_WIDTH, SETTINGS_WINDOW_HEIGHT)
-    .min_inner_size(SETTINGS_WINDOW_MIN_WIDTH, SETTINGS_WINDOW_MIN_HEIGHT)
-    .resizable(true)
     .decorations(false)
     .transparent(true)
     .always_on_top(true)
     .skip_taskbar(true)
-    .center()
-    .build();
+    .visible(false);
+    let win = match configure(builder).build() {
+        Ok(win) => win,
+        Err(e) => {
+            log::error!("Failed to open {} window: {}", label, e);
+            set_sticked_always_on_top(app, true);
+            return None;
+        }
+    };
 
-    if let Ok(win) = window {
-        let app_handle = app.clone();
-        win.on_window_event(move |event| {
-            if let tauri::WindowEvent::Destroyed = event {
-                for (label, window) in app_handle.webview_windows() {
-                    if label.starts_with("sticked-") {
-                        let _ = window.set_always_on_top(true);
-                    }
-                }
+    place_on_cursor_monitor(app, &win);
+    let _ = win.show();
+    let _ = win.set_focus();
+    let app_handle = app.clone();
+    let own_label = label.to_string();
+    win.on_window_event(move |event| {
+        let event = match event {
+            tauri::WindowEvent::Focused(focused) => OverlayEvent::Focused(*focused),
+            tauri::WindowEvent::Destroyed => OverlayEvent::Destroyed,
+            _ => return,
+        };
+        handle_overlay_event(&app_handle, &own_label, policy, event);
+    });
+    Some((win, true))
+}
 
-                let state = app_handle.state::<AppState>();
-                let prev_window = state.previous_focused_window.lock().unwrap_or_else(|e| e.into_inner());
-                let postit_visible = *state.postit_was_visible.lock().unwrap_or_else(|e| e.into_inner());
+pub fn show_command_palette(app: &AppHandle) {
+    let Some((win, created)) = with_overlay_window(
+        app,
+        "command-palette",
+        |builder| {
+            builder
+                .title("Command Palette")
+                .inner_size(700.0, 480.0)
+                .resizable(false)
+        },
+        PALETTE_POLICY,
+    ) else {
+        return;
+    };
+    if !created {
+        return;
+    }
 
-                if let Some(label) = prev_window.as_ref() {
-                    if let Some(window) = app_handle.get_webview_window(label) {
-                        let _ = window.show();
-                        let _ = window.set_focus();
-                    }
-                } else if postit_visible {
-                    if let Some(postit) = app_handle.get_webview_window("postit") {
-                        let _ = postit.show();
-                        let _ = postit.set_focus();
-                    }
-                }
+    let app_handle = app.clone();
+    win.on_window_event(move |event| match event {
+        tauri::WindowEvent::Focused(focused) => {
+            let pinned = *app_handle
+                .state::<AppState>()
+                .command_palette_pinned
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if let Some(palette) = app_handle.get_webview_window("command-palette") {
+                // Pinned: drop behind the viewing windows opened from
+                // the palette, and come back on top when refocused.
+                let _ = palette.set_always_on_top(!pinned || *focused);
             }
-        });
+        }
+        tauri::WindowEvent::Destroyed => {
+            *app_handle
+                .state::<AppState>()
+                .command_palette_pinned
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()) = false;
+        }
+        _ => {}
+    });
+}
+
+pub fn show_settings(app: &AppHandle) {
+    with_overlay_window(
+        app,
+        "settings",
+        |builder| {
+            builder
+                .title("Settings")
+                .inner_size(SETTINGS_WINDOW_WIDTH, SETTINGS_WINDOW_HEIGHT)
+                .min_inner_size(SETTINGS_WINDOW_MIN_WIDTH, SETTINGS_WINDOW_MIN_HEIGHT)
+                .resizable(true)
+        },
+        SETTINGS_POLICY,
+    );
+}
+
+pub fn show_stats_window(app: &AppHandle) {
+    let stats = with_overlay_window(
+        app,
+        "stats",
+        |builder| {
+            builder
+                .title("Stats")
+                .inner_size(STATS_WINDOW_WIDTH, STATS_WINDOW_HEIGHT)
+                .min_inner_size(STATS_WINDOW_MIN_WIDTH, STATS_WINDOW_MIN_HEIGHT)
+                .resizable(true)
+        },
+        SETTINGS_POLICY,
+    );
+    // A fresh webview may not be listening yet; it also fetches on mount.
+    if let Some((win, _)) = stats {
+        let _ = win.emit("stats-opened", ());
+    }
+}
+
+/// Keep the command palette open while notes are opened from it. Closing
+/// the palette unpins it again.
+#[tauri::command]
+pub fn pin_command_palette(app: AppHandle, pinned: bool) -> Result<bool, String> {
+    let state = app.state::<AppState>();
+    *state
+        .command_palette_pinned
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = pinned;
+    if !pinned {
+        if let Some(palette) = app.get_webview_window("command-palette") {
+            let _ = palette.set_always_on_top(true);
+        }
     }
+    Ok(pinned)
+}
+
+/// Hand a `stik://search` query to the palette, once.
+#[tauri::command]
+pub fn take_pending_palette_query(app: AppHandle) -> Option<String> {
+    app.state::<AppState>()
+        .pending_palette_query
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
 }
 
 #[tauri::command]
 pub fn hide_window(window: tauri::Window) {
+    if window.label() == "postit" {
+        hide_postit(window.app_handle().clone());
+        return;
+    }
     let _ = window.hide();
 }
 
 #[tauri::command]
 pub fn hide_postit(app: AppHandle) {
     if let Some(window) = app.get_webview_window("postit") {
+        let postit_focused = window.is_focused().unwrap_or(false);
+        let other_window_focused = app
+            .webview_windows()
+            .iter()
+            .any(|(label, w)| label != "postit" && w.is_focused().unwrap_or(false));
         let _ = window.hide();
+        restore_frontmost_app(&app, postit_focused, other_window_focused);
+    }
+}
+
+/// The postit lost focus with `content` in it (empty and slash-query
+/// buffers are hidden by the frontend directly). Applies
+/// `capture_blur_behavior`; returns whether the postit was hidden.
+#[tauri::command]
+pub fn capture_blurred(app: AppHandle, content: String, folder: String) -> Result<bool, String> {
+    if notes::is_effectively_empty_markdown(&content) {
+        hide_postit(app);
+        return Ok(true);
+    }
+    let behavior = settings::get_settings()?.capture_blur_behavior;
+    match behavior.as_str() {
+        settings::CAPTURE_BLUR_SAVE => {
+            capture_context::save_note_with_context(
+                app.clone(),
+                folder,
+                content,
+                app.state::<index::NoteIndex>(),
+                app.state::<embeddings::EmbeddingIndex>(),
+            )?;
+        }
+        settings::CAPTURE_BLUR_KEEP_DRAFT => capture_draft::stash(&content, &folder)?,
+        // `discard`: leave the window and its text alone.
+        _ => return Ok(false),
+    }
+    let _ = app.emit_to("postit", "capture-cleared", ());
+    hide_postit(app);
+    Ok(true)
+}
+
+fn remember_frontmost_app(app: &AppHandle) {
+    let pid = frontmost_app::frontmost_pid().filter(|pid| !frontmost_app::is_stik(*pid));
+    let state = app.state::<AppState>();
+    *state
+        .previous_frontmost_app
+        .lock()
+        .unwrap_or_else(|e| e.into_inner()) = pid;
+}
+
+/// Re-activate the app the postit was summoned over, so macOS doesn't hand
+/// focus to an arbitrary Stik window once the postit is gone.
+fn restore_frontmost_app(app: &AppHandle, postit_focused: bool, other_window_focused: bool) {
+    let pid = app
+        .state::<AppState>()
+        .previous_frontmost_app
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take();
+    let Some(pid) = pid else {
+        return;
+    };
+
+    let stik_frontmost = frontmost_app::frontmost_pid().is_some_and(frontmost_app::is_stik);
+    if should_restore_frontmost_app(postit_focused, other_window_focused, stik_frontmost) {
+        // A false return means the app quit while the postit was open.
+        let _ = frontmost_app::activate(pid);
+    }
+}
+
+/// Only restore when nothing else claimed focus: a hide from the focused
+/// postit (save, Escape) always qualifies, while a blur-hide only does if
+/// the user didn't move to another Stik window or another app.
+fn should_restore_frontmost_app(
+    postit_focused: bool,
+    other_window_focused: bool,
+    stik_frontmost: bool,
+) -> bool {
+    postit_focused || (!other_window_focused && stik_frontmost)
+}
+
+fn sticked_size_limits() -> StickedSizeLimits {
+    settings::load_settings_from_file()
+        .unwrap_or_default()
+        .sticked_size_limits()
+}
+
+/// The builder with the configured maximum size, if there is one.
+fn with_max_size<'a, R: Runtime, M: Manager<R>>(
+    builder: WebviewWindowBuilder<'a, R, M>,
+    limits: &StickedSizeLimits,
+) -> WebviewWindowBuilder<'a, R, M> {
+    match limits.max {
+        Some((width, height)) => builder.max_inner_size(width, height),
+        None => builder,
     }
 }
 
@@ -265,26 +668,27 @@ pub fn create_sticked_window(app: AppHandle, note: StickedNote) -> Result<bool,
     }
 
     let saved_position = note.position;
-    let (width, height) = note.size.unwrap_or((400.0, 280.0));
+    let limits = sticked_size_limits();
+    let (width, height) = limits.clamp(note.size.unwrap_or((400.0, 280.0)));
     let url = format!("index.html?window=sticked&id={}", note.id);
 
     // Build hidden — position after creation using PhysicalPosition to avoid
     // the logical/physical mismatch in WebviewWindowBuilder::position().
-    let window = WebviewWindowBuilder::new(&app, &window_label, WebviewUrl::App(url.into()))
+    let builder = WebviewWindowBuilder::new(&app, &window_label, WebviewUrl::App(url.into()))
         .title("Sticked Note")
         .inner_size(width, height)
-        .min_inner_size(320.0, 200.0)
-        .max_inner_size(800.0, 600.0)
+        .min_inner_size(limits.min.0, limits.min.1)
         .resizable(true)
         .decorations(false)
         .transparent(true)
         .always_on_top(true)
         .skip_taskbar(true)
-        .visible(false)
-        .build();
+        .visible(false);
+    let window = with_max_size(builder, &limits).build();
 
     match window {
         Ok(win) => {
+            track_note_window_focus(&app, &win);
             if let Some((x, y)) = saved_position {
                 let _ = win.set_position(tauri::Position::Physical(
                     PhysicalPosition::new(x as i32, y as i32),
@@ -306,27 +710,32 @@ pub fn create_sticked_window_centered(app: AppHandle, note: StickedNote) -> Resu
         return Ok(true);
     }
 
-    let (width, height) = note.size.unwrap_or((400.0, 280.0));
+    let limits = sticked_size_limits();
+    let (width, height) = limits.clamp(note.size.unwrap_or((400.0, 280.0)));
     let url = format!("index.html?window=sticked&id={}", note.id);
 
-    let window = WebviewWindowBuilder::new(&app, &window_label, WebviewUrl::App(url.into()))
+    let builder = WebviewWindowBuilder::new(&app, &window_label, WebviewUrl::App(url.into()))
         .title("Sticked Note")
         .inner_size(width, height)
-        .min_inner_size(320.0, 200.0)
-        .max_inner_size(800.0, 600.0)
-        .center()
+        .min_inner_size(limits.min.0, limits.min.1)
         .resizable(true)
         .decorations(false)
         .transparent(true)
         .always_on_top(true)
         .skip_taskbar(true)
-        .build();
+        .visible(false);
+    let window = with_max_size(builder, &limits).build();
 
-    if let Err(e) = window {
-        return Err(format!("Failed to create sticked window: {}", e));
+    match window {
+        Ok(win) => {
+            track_note_window_focus(&app, &win);
+            place_on_cursor_monitor(&app, &win);
+            let _ = win.show();
+            let _ = win.set_focus();
+            Ok(true)
+        }
+        Err(e) => Err(format!("Failed to create sticked window: {}", e)),
     }
-
-    Ok(true)
 }
 
 #[tauri::command]
@@ -339,32 +748,170 @@ pub fn close_sticked_window(app: AppHandle, id: String) -> Result<bool, String>
 
     // Clean up viewing note cache to prevent memory leak
     if id.starts_with("view-") {
-        let state = app.state::<AppState>();
-        let mut viewing_notes = state.viewing_notes.lock().unwrap_or_else(|e| e.into_inner());
-        viewing_notes.remove(&id);
+        forget_viewing_note(&app.state::<AppState>(), &id);
     }
 
     Ok(true)
 }
 
+/// Drop the cached content of viewing window `id` and its path mapping.
+fn forget_viewing_note(state: &AppState, id: &str) {
+    let removed = state
+        .viewing_notes
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(id);
+    if let Some(note) = removed {
+        let mut viewing_note_ids = state
+            .viewing_note_ids
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if viewing_note_ids.get(&note.path).map(String::as_str) == Some(id) {
+            viewing_note_ids.remove(&note.path);
+        }
+    }
+}
+
+/// Ids to evict so at most `limit` entries remain, least recently used
+/// first. Entries whose window is still open are never picked.
+fn viewing_notes_to_evict(
+    entries: &[(String, std::time::Instant, bool)],
+    limit: usize,
+) -> Vec<String> {
+    let mut closed: Vec<_> = entries.iter().filter(|(_, _, open)| !open).collect();
+    closed.sort_by_key(|(_, last_used, _)| *last_used);
+    closed
+        .into_iter()
+        .take(entries.len().saturating_sub(limit))
+        .map(|(id, _, _)| id.clone())
+        .collect()
+}
+
+/// Keep `viewing_notes` within `limit`. Windows normally clean up after
+/// themselves when destroyed; this catches the ones that never got the
+/// chance, like a crashed webview.
+fn evict_viewing_notes(app: &AppHandle, limit: usize) {
+    let state = app.state::<AppState>();
+    let entries: Vec<_> = state
+        .viewing_notes
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .map(|note| {
+            let open = app
+                .get_webview_window(&format!("sticked-{}", note.id))
+                .is_some();
+            (note.id.clone(), note.last_used, open)
+        })
+        .collect();
+    for id in viewing_notes_to_evict(&entries, limit) {
+        forget_viewing_note(&state, &id);
+    }
+}
+
+/// Sizes of the in-memory maps in `AppState`, for spotting leaks. Debug
+/// builds only.
+#[tauri::command]
+pub fn debug_state_report(app: AppHandle) -> Result<serde_json::Value, String> {
+    if !cfg!(debug_assertions) {
+        return Err("debug_state_report is only available in debug builds".to_string());
+    }
+    let state = app.state::<AppState>();
+    let len = |map: &std::sync::Mutex<std::collections::HashMap<String, String>>| {
+        map.lock().unwrap_or_else(|e| e.into_inner()).len()
+    };
+    let (viewing_notes, viewing_note_bytes, open_viewing_windows) = {
+        let viewing_notes = state
+            .viewing_notes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        (
+            viewing_notes.len(),
+            viewing_notes
+                .values()
+                .map(|note| note.content.len())
+                .sum::<usize>(),
+            viewing_notes
+                .keys()
+                .filter(|id| app.get_webview_window(&format!("sticked-{}", id)).is_some())
+                .count(),
+        )
+    };
+    Ok(serde_json::json!({
+        "viewing_notes": viewing_notes,
+        "viewing_note_bytes": viewing_note_bytes,
+        "open_viewing_windows": open_viewing_windows,
+        "viewing_note_ids": len(&state.viewing_note_ids),
+        "auto_filed_notes": len(&state.auto_filed_notes),
+        "shortcut_to_folder": len(&state.shortcut_to_folder),
+        "shortcut_to_action": len(&state.shortcut_to_action),
+        "window_placements": state
+            .window_placements
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len(),
+        "note_window_focus_order": state
+            .note_window_focus_order
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len(),
+    }))
+}
+
 #[tauri::command]
 pub async fn pin_capture_note(
     app: AppHandle,
     content: String,
     folder: String,
+) -> Result<StickedNote, String> {
+    let note = pin_new_sticked_note(&app, content, folder, None)?;
+
+    if let Some(window) = app.get_webview_window("postit") {
+        let _ = window.hide();
+    }
+
+    Ok(note)
+}
+
+/// A new sticky from a template. Placeholders are expanded once, here, so
+/// the note keeps its date when the window reopens. An empty folder means
+/// the default folder.
+#[tauri::command]
+pub async fn pin_template(
+    app: AppHandle,
+    template_id: String,
+    folder: String,
+) -> Result<StickedNote, String> {
+    let folder = if folder.trim().is_empty() {
+        settings::get_settings()?.default_folder
+    } else {
+        folder
+    };
+    let (name, body) = templates::render_template(&template_id, &folder)?;
+    pin_new_sticked_note(&app, body, folder, Some(name))
+}
+
+fn pin_new_sticked_note(
+    app: &AppHandle,
+    content: String,
+    folder: String,
+    template_origin: Option<String>,
 ) -> Result<StickedNote, String> {
     // Read saved viewing position so the pinned note opens where the last
     // sticked/viewing window was, not always centered.
     let saved = settings::load_settings_from_file().ok();
     let saved_pos = saved.as_ref().and_then(|s| s.viewing_window_position);
-    let saved_size = saved.as_ref().and_then(|s| s.viewing_window_size);
+    let saved_size = saved
+        .as_ref()
+        .and_then(|s| s.window_size(settings::VIEWING_WINDOW));
 
-    let mut note = sticked_notes::create_sticked_note(content, folder, None)?;
+    let mut note =
+        sticked_notes::create_sticked_note_inner(app, content, folder, None, template_origin)?;
 
     // Use saved viewing position if it's on a connected monitor, otherwise center.
     let use_saved = saved_pos.is_some_and(|(x, y)| {
         let (w, h) = saved_size.unwrap_or((400.0, 280.0));
-        is_window_visible_on_any_monitor(&app, x, y, w, h)
+        is_window_visible_on_any_monitor(app, x, y, w, h)
     });
 
     if let (true, Some((x, y))) = (use_saved, saved_pos) {
@@ -382,6 +929,7 @@ pub async fn pin_capture_note(
     if let Some(win) = app.get_webview_window(&window_label) {
         if let (Ok(pos), Ok(size)) = (win.outer_position(), win.outer_size()) {
             let _ = sticked_notes::update_sticked_note(
+                app.clone(),
                 note.id.clone(),
                 None,
                 None,
@@ -397,13 +945,19 @@ pub async fn pin_capture_note(
         }
     }
 
-    if let Some(window) = app.get_webview_window("postit") {
-        let _ = window.hide();
-    }
-
     Ok(note)
 }
 
+/// Window id for a note opened for viewing. Hashing the canonical path
+/// keeps paths that differ only in `.`, spaces or separators apart.
+fn viewing_note_id(path: &str) -> String {
+    let canonical = std::path::Path::new(path)
+        .canonicalize()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| path.to_string());
+    format!("view-{}", window_geometry::path_key(&canonical))
+}
+
 #[tauri::command]
 pub async fn open_note_for_viewing(
     app: AppHandle,
@@ -416,16 +970,23 @@ pub async fn open_note_for_viewing(
         remember_last_note(&state, &path, &folder);
     }
 
-    let id = format!("view-{}", path.replace(['/', '\\', '.', ' '], "-"));
+    let id = viewing_note_id(&path);
     let window_label = format!("sticked-{}", id);
 
     if app.get_webview_window(&window_label).is_some() {
         return Ok(true);
     }
 
+    let saved_settings = settings::load_settings_from_file().ok();
+
     {
         let state = app.state::<AppState>();
         let mut viewing_notes = state.viewing_notes.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .viewing_note_ids
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(path.clone(), id.clone());
         viewing_notes.insert(
             id.clone(),
             crate::state::ViewingNoteContent {
@@ -433,17 +994,36 @@ pub async fn open_note_for_viewing(
                 content,
                 folder,
                 path: path.clone(),
+                has_unsaved_edits: false,
+                last_used: std::time::Instant::now(),
             },
         );
     }
+    let cache_limit = saved_settings
+        .as_ref()
+        .map(|s| s.viewing_note_cache_limit)
+        .unwrap_or(settings::DEFAULT_VIEWING_NOTE_CACHE_LIMIT);
+    // The entry just added is the newest, so a limit of 1 still keeps it.
+    evict_viewing_notes(&app, cache_limit.max(1));
 
     let url = format!("index.html?window=sticked&id={}&viewing=true", id);
-
-    let saved_settings = settings::load_settings_from_file().ok();
-    let (width, height) = saved_settings
-        .as_ref()
-        .and_then(|s| s.viewing_window_size)
+    // Keyed by the note's id when it has one, so the geometry survives
+    // renames and moves. Viewing a note never gives it an id.
+    let geometry_key = window_geometry::note_key(&app.state::<index::NoteIndex>(), &path);
+    let note_geometry = window_geometry::geometry_for(&geometry_key);
+    let (width, height) = note_geometry
+        .map(|(_, _, w, h)| (w, h))
+        .or_else(|| {
+            saved_settings
+                .as_ref()
+                .and_then(|s| s.window_size(settings::VIEWING_WINDOW))
+        })
         .unwrap_or((450.0, 320.0));
+    let limits = saved_settings
+        .as_ref()
+        .map(|s| s.sticked_size_limits())
+        .unwrap_or_else(|| StikSettings::default().sticked_size_limits());
+    let (width, height) = limits.clamp((width, height));
     let saved_position = saved_settings.as_ref().and_then(|s| s.viewing_window_position);
 
     // Build hidden — we position after creation using PhysicalPosition to avoid
@@ -451,8 +1031,7 @@ pub async fn open_note_for_viewing(
     let builder = WebviewWindowBuilder::new(&app, &window_label, WebviewUrl::App(url.into()))
         .title("View Note")
         .inner_size(width, height)
-        .min_inner_size(320.0, 200.0)
-        .max_inner_size(800.0, 600.0)
+        .min_inner_size(limits.min.0, limits.min.1)
         .resizable(true)
         .decorations(false)
         .transparent(true)
@@ -460,15 +1039,22 @@ pub async fn open_note_for_viewing(
         .skip_taskbar(true)
         .visible(false);
 
-    let window = builder.build();
+    let window = with_max_size(builder, &limits).build();
 
     match window {
         Ok(win) => {
-            // Restore saved position in physical pixels, or center as fallback.
+            track_note_window_focus(&app, &win);
+            // This note's own geometry wins (pulled back on-screen if its
+            // monitor is gone); then the global viewing position; then center.
             let positioned = saved_position.is_some_and(|(x, y)| {
                 is_window_visible_on_any_monitor(&app, x, y, width, height)
             });
-            if let (true, Some((x, y))) = (positioned, saved_position) {
+            if let Some((x, y, _, _)) = note_geometry {
+                let (x, y) = clamp_to_best_monitor(&win, x, y);
+                let _ = win.set_position(tauri::Position::Physical(
+                    PhysicalPosition::new(x as i32, y as i32),
+                ));
+            } else if let (true, Some((x, y))) = (positioned, saved_position) {
                 let _ = win.set_position(tauri::Position::Physical(
                     PhysicalPosition::new(x as i32, y as i32),
                 ));
@@ -476,31 +1062,250 @@ pub async fn open_note_for_viewing(
                 let _ = win.center();
             }
 
+            let tracked = win.clone();
+            let app_handle = app.clone();
+            win.on_window_event(move |event| match event {
+                tauri::WindowEvent::Resized(_) | tauri::WindowEvent::Moved(_) => {
+                    remember_viewing_geometry(&tracked, &geometry_key);
+                }
+                // However the window went away, its content goes with it.
+                tauri::WindowEvent::Destroyed => {
+                    forget_viewing_note(&app_handle.state::<AppState>(), &id);
+                }
+                _ => {}
+            });
+
             let _ = win.show();
             let _ = win.set_focus();
             Ok(true)
         }
-        Err(e) => Err(format!("Failed to create viewing window: {}", e)),
+        Err(e) => {
+            forget_viewing_note(&app.state::<AppState>(), &id);
+            Err(format!("Failed to create viewing window: {}", e))
+        }
+    }
+}
+
+fn remember_viewing_geometry(win: &tauri::WebviewWindow, geometry_key: &str) {
+    let (Ok(pos), Ok(size)) = (win.outer_position(), win.inner_size()) else {
+        return;
+    };
+    let scale = win.scale_factor().unwrap_or(1.0);
+    window_geometry::remember(
+        geometry_key,
+        (
+            pos.x as f64,
+            pos.y as f64,
+            size.width as f64 / scale,
+            size.height as f64 / scale,
+        ),
+    );
+}
+
+/// Clamp a physical position into the work area of the monitor the window
+/// overlaps most, or the primary monitor when it overlaps none.
+fn clamp_to_best_monitor(win: &tauri::WebviewWindow, x: f64, y: f64) -> (f64, f64) {
+    let Ok(size) = win.outer_size() else {
+        return (x, y);
+    };
+    let (w, h) = (size.width as f64, size.height as f64);
+    let monitors = win.available_monitors().unwrap_or_default();
+
+    let work_area = |monitor: &tauri::Monitor| {
+        let area = monitor.work_area();
+        (
+            area.position.x as f64,
+            area.position.y as f64,
+            area.size.width as f64,
+            area.size.height as f64,
+        )
+    };
+    let overlap = |(ax, ay, aw, ah): (f64, f64, f64, f64)| {
+        let overlap_w = ((x + w).min(ax + aw) - x.max(ax)).max(0.0);
+        let overlap_h = ((y + h).min(ay + ah) - y.max(ay)).max(0.0);
+        overlap_w * overlap_h
+    };
+
+    let best = monitors
+        .iter()
+        .map(work_area)
+        .filter(|area| overlap(*area) > 0.0)
+        .max_by(|a, b| overlap(*a).total_cmp(&overlap(*b)))
+        .or_else(|| win.primary_monitor().ok().flatten().as_ref().map(work_area))
+        .or_else(|| monitors.first().map(work_area));
+
+    match best {
+        Some(area) => window_geometry::clamp_to_area(x, y, w, h, area),
+        None => (x, y),
     }
 }
 
 #[tauri::command]
 pub fn get_viewing_note_content(app: AppHandle, id: String) -> Result<serde_json::Value, String> {
     let state = app.state::<AppState>();
-    let viewing_notes = state.viewing_notes.lock().unwrap_or_else(|e| e.into_inner());
-
-    if let Some(note) = viewing_notes.get(&id) {
+    let mut viewing_notes = state.viewing_notes.lock().unwrap_or_else(|e| e.into_inner());
+
+    if let Some(note) = viewing_notes.get_mut(&id) {
+        note.last_used = std::time::Instant::now();
+        let word_count = if note_lock::is_locked_content(&note.content) {
+            0
+        } else {
+            index::count_words(&note.content)
+        };
+        let metadata =
+            note_metadata::note_metadata(&app.state::<index::NoteIndex>(), &note.path, true).ok();
         Ok(serde_json::json!({
             "id": note.id,
             "content": note.content,
             "folder": note.folder,
-            "path": note.path
+            "path": note.path,
+            "word_count": word_count,
+            "reading_minutes": index::reading_minutes(word_count),
+            "read_only": edit_locks::is_edit_locked(&note.path),
+            "created": metadata.as_ref().map(|m| m.created.as_str()),
+            "modified": metadata.as_ref().map(|m| m.modified.as_str()),
+            "size_bytes": metadata.as_ref().map(|m| m.size_bytes),
+            "tags": metadata.as_ref().map(|m| m.tags.as_slice()).unwrap_or_default()
         }))
     } else {
         Err("Viewing note content not found".to_string())
     }
 }
 
+/// Show a read-only preview of a note next to `anchor_x`/`anchor_y`
+/// (physical screen pixels). There is a single `peek` window: peeking at
+/// another note swaps its content instead of opening a second one.
+#[tauri::command]
+pub fn show_quick_peek(
+    app: AppHandle,
+    path: String,
+    anchor_x: f64,
+    anchor_y: f64,
+) -> Result<bool, String> {
+    let content = notes::get_note_content_inner(&path)?;
+    if note_lock::is_locked_content(&content) {
+        return Err("Unlock the note to preview it".to_string());
+    }
+    let note_dir = std::path::Path::new(&path).parent();
+    let html = share::markdown_to_html(
+        &content,
+        &share::HtmlOptions {
+            note_dir,
+            smart_punctuation: settings::load_settings_from_file()
+                .map(|s| s.smart_punctuation)
+                .unwrap_or(false),
+            asset_urls: None,
+        },
+    );
+    {
+        let state = app.state::<AppState>();
+        *state
+            .quick_peek_note
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(QuickPeekNote {
+            title: export::document_title(&content),
+            path,
+            html,
+        });
+    }
+
+    let win = match app.get_webview_window("peek") {
+        Some(win) => {
+            let _ = win.emit("quick-peek-updated", ());
+            win
+        }
+        None => {
+            let win = WebviewWindowBuilder::new(
+                &app,
+                "peek",
+                WebviewUrl::App("index.html?window=peek".into()),
+            )
+            .title("Quick Peek")
+            .inner_size(QUICK_PEEK_WIDTH, QUICK_PEEK_HEIGHT)
+            .resizable(false)
+            .decorations(false)
+            .transparent(true)
+            .always_on_top(true)
+            .skip_taskbar(true)
+            .visible(false)
+            .build()
+            .map_err(|e| format!("Failed to create quick peek window: {}", e))?;
+
+            let peek = win.clone();
+            win.on_window_event(move |event| {
+                if let tauri::WindowEvent::Focused(false) = event {
+                    let _ = peek.hide();
+                }
+            });
+            win
+        }
+    };
+
+    let monitors = app.available_monitors().unwrap_or_default();
+    let areas: Vec<(f64, f64, f64, f64)> = monitors.iter().map(monitor_work_area).collect();
+    if let Some(i) = area_containing((anchor_x, anchor_y), &areas) {
+        let scale = monitors[i].scale_factor();
+        let size = (QUICK_PEEK_WIDTH * scale, QUICK_PEEK_HEIGHT * scale);
+        let (x, y) = quick_peek_position((anchor_x, anchor_y), size, areas[i]);
+        let _ = win.set_position(tauri::Position::Physical(PhysicalPosition::new(
+            x.round() as i32,
+            y.round() as i32,
+        )));
+    } else {
+        let _ = win.center();
+    }
+
+    let _ = win.show();
+    let _ = win.set_focus();
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn get_quick_peek_content(app: AppHandle) -> Result<serde_json::Value, String> {
+    let state = app.state::<AppState>();
+    let peek = state.quick_peek_note.lock().unwrap_or_else(|e| e.into_inner());
+
+    match peek.as_ref() {
+        Some(note) => Ok(serde_json::json!({
+            "path": note.path,
+            "title": note.title,
+            "html": note.html
+        })),
+        None => Err("Quick peek content not found".to_string()),
+    }
+}
+
+/// Top-left (physical pixels) for a peek of `size` beside `anchor`: to the
+/// right when it fits, otherwise to the left, then kept inside `area`.
+fn quick_peek_position(
+    anchor: (f64, f64),
+    size: (f64, f64),
+    area: (f64, f64, f64, f64),
+) -> (f64, f64) {
+    let (ax, ay) = anchor;
+    let (w, h) = size;
+    let right = ax + QUICK_PEEK_OFFSET;
+    let x = if right + w > area.0 + area.2 {
+        ax - QUICK_PEEK_OFFSET - w
+    } else {
+        right
+    };
+    window_geometry::clamp_to_area(x, ay, w, h, area)
+}
+
+/// Called by a viewing window whenever its editor starts or stops differing
+/// from the loaded file, so external changes know whether to overwrite it.
+#[tauri::command]
+pub fn set_viewing_note_dirty(app: AppHandle, id: String, dirty: bool) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut viewing_notes = state.viewing_notes.lock().unwrap_or_else(|e| e.into_inner());
+    let note = viewing_notes
+        .get_mut(&id)
+        .ok_or("Viewing note content not found")?;
+    note.has_unsaved_edits = dirty;
+    Ok(())
+}
+
 #[tauri::command]
 pub fn transfer_to_capture(app: AppHandle, content: String, folder: String) -> Result<bool, String> {
     if let Some(window) = app.get_webview_window("postit") {
@@ -516,6 +1321,167 @@ pub fn transfer_to_capture(app: AppHandle, content: String, folder: String) -> R
     }
 }
 
+/// Enter or leave zen mode. Entering asks the postit for its content
+/// (`enter_zen_window`); leaving asks the zen window for its edits
+/// (`exit_zen_window`). Returns whether zen mode is being entered.
+#[tauri::command]
+pub fn toggle_zen_window(app: AppHandle) -> Result<bool, String> {
+    let active = app
+        .state::<AppState>()
+        .zen_session
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .is_some();
+    if active {
+        let _ = app.emit_to(ZEN_WINDOW, "zen-exit-requested", ());
+        return Ok(false);
+    }
+
+    let postit_visible = app
+        .get_webview_window("postit")
+        .is_some_and(|w| w.is_visible().unwrap_or(false));
+    if !postit_visible {
+        return Err("Zen mode starts from the capture window".to_string());
+    }
+    let _ = app.emit_to("postit", "zen-enter-requested", ());
+    Ok(true)
+}
+
+/// Open the zen window over the postit's monitor with the capture content,
+/// then hide the postit and lower sticked notes until zen mode ends.
+#[tauri::command]
+pub fn enter_zen_window(app: AppHandle, content: String, folder: String) -> Result<bool, String> {
+    let state = app.state::<AppState>();
+    let mut session = state.zen_session.lock().unwrap_or_else(|e| e.into_inner());
+    if session.is_some() {
+        return Ok(false);
+    }
+
+    let postit = app.get_webview_window("postit");
+    let area = postit
+        .as_ref()
+        .and_then(|w| w.current_monitor().ok().flatten())
+        .as_ref()
+        .map(monitor_work_area)
+        .or_else(|| cursor_monitor_work_area(&app));
+
+    let win = match app.get_webview_window(ZEN_WINDOW) {
+        Some(win) => win,
+        None => {
+            let win = WebviewWindowBuilder::new(
+                &app,
+                ZEN_WINDOW,
+                WebviewUrl::App("index.html?window=zen".into()),
+            )
+            .title("Zen")
+            .resizable(false)
+            .decorations(false)
+            .always_on_top(true)
+            .skip_taskbar(true)
+            .visible(false)
+            .build()
+            .map_err(|e| format!("Failed to create zen window: {}", e))?;
+
+            // Closed some other way than Escape: hand back what it was opened with.
+            let app_handle = app.clone();
+            win.on_window_event(move |event| {
+                if let tauri::WindowEvent::Destroyed = event {
+                    let session = app_handle
+                        .state::<AppState>()
+                        .zen_session
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .take();
+                    if let Some(session) = session {
+                        end_zen_session(&app_handle, session.lowered_sticked);
+                        let _ = transfer_to_capture(
+                            app_handle.clone(),
+                            session.content,
+                            session.folder,
+                        );
+                    }
+                }
+            });
+            win
+        }
+    };
+
+    if let Some((x, y, w, h)) = area {
+        let _ = win.set_position(tauri::Position::Physical(PhysicalPosition::new(
+            x as i32, y as i32,
+        )));
+        let _ = win.set_size(tauri::Size::Physical(tauri::PhysicalSize::new(
+            w as u32, h as u32,
+        )));
+    }
+
+    let mut lowered_sticked = Vec::new();
+    for (label, window) in app.webview_windows() {
+        if label.starts_with("sticked-") && window.set_always_on_top(false).is_ok() {
+            lowered_sticked.push(label);
+        }
+    }
+
+    // A fresh webview may not be listening yet; it also fetches on mount.
+    let payload = serde_json::json!({ "content": content, "folder": folder });
+    *session = Some(ZenSession {
+        content,
+        folder,
+        lowered_sticked,
+    });
+    drop(session);
+    let _ = win.emit("zen-content", payload);
+
+    if let Some(postit) = postit {
+        let _ = postit.hide();
+    }
+    let _ = win.show();
+    let _ = win.set_focus();
+    Ok(true)
+}
+
+#[tauri::command]
+pub fn get_zen_content(app: AppHandle) -> Result<serde_json::Value, String> {
+    let state = app.state::<AppState>();
+    let session = state.zen_session.lock().unwrap_or_else(|e| e.into_inner());
+    match session.as_ref() {
+        Some(session) => Ok(serde_json::json!({
+            "content": session.content,
+            "folder": session.folder
+        })),
+        None => Err("Zen mode is not active".to_string()),
+    }
+}
+
+/// Leave zen mode: hide the zen window, put sticked notes back on top and
+/// re-show the postit with the edited content.
+#[tauri::command]
+pub fn exit_zen_window(app: AppHandle, content: String, folder: String) -> Result<bool, String> {
+    let session = app
+        .state::<AppState>()
+        .zen_session
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take();
+    let Some(session) = session else {
+        return Ok(false);
+    };
+
+    if let Some(win) = app.get_webview_window(ZEN_WINDOW) {
+        let _ = win.hide();
+    }
+    end_zen_session(&app, session.lowered_sticked);
+    transfer_to_capture(app, content, folder)
+}
+
+fn end_zen_session(app: &AppHandle, lowered_sticked: Vec<String>) {
+    for label in lowered_sticked {
+        if let Some(window) = app.get_webview_window(&label) {
+            let _ = window.set_always_on_top(true);
+        }
+    }
+}
+
 #[tauri::command]
 pub fn open_command_palette(app: AppHandle) -> Result<bool, String> {
     show_command_palette(&app);
@@ -540,6 +1506,12 @@ pub fn open_settings(app: AppHandle) -> Result<bool, String> {
     Ok(true)
 }
 
+#[tauri::command]
+pub fn open_stats(app: AppHandle) -> Result<bool, String> {
+    show_stats_window(&app);
+    Ok(true)
+}
+
 #[tauri::command]
 pub async fn reopen_last_note(app: AppHandle) -> Result<bool, String> {
     let (path, folder) = {
@@ -555,6 +1527,14 @@ pub async fn reopen_last_note(app: AppHandle) -> Result<bool, String> {
     open_note_for_viewing(app, content, folder, path).await
 }
 
+/// Open today's daily note, creating it from the daily template first if
+/// needed. `folder` overrides the `daily_note_folder` setting.
+#[tauri::command]
+pub async fn open_daily_note(app: AppHandle, folder: Option<String>) -> Result<bool, String> {
+    let note = daily_note::ensure_daily_note(&app, folder)?;
+    open_note_for_viewing(app, note.content, note.folder, note.path).await
+}
+
 pub fn show_apple_notes_picker(app: &AppHandle) {
     if let Some(window) = app.get_webview_window("apple-notes-picker") {
         let _ = window.show();
@@ -597,17 +1577,276 @@ pub fn show_apple_notes_picker_cmd(app: AppHandle) -> Result<bool, String> {
     Ok(true)
 }
 
-pub fn restore_sticked_notes(app: &AppHandle) {
-    if let Ok(notes) = sticked_notes::list_sticked_notes() {
-        for note in notes {
-            let _ = create_sticked_window(app.clone(), note);
+/// Bring a sticked note's window forward, recreating it if it was closed
+/// while the note is still stored.
+#[tauri::command]
+pub fn focus_sticked_window(app: AppHandle, id: String) -> Result<bool, String> {
+    let window_label = format!("sticked-{}", id);
+
+    if app.get_webview_window(&window_label).is_none() {
+        let note = sticked_notes::get_sticked_note(id)?;
+        create_sticked_window(app.clone(), note)?;
+    }
+
+    let window = app
+        .get_webview_window(&window_label)
+        .ok_or("Sticked note window not found")?;
+    let _ = window.show();
+    let _ = window.set_focus();
+    Ok(true)
+}
+
+/// Move `label` to the front of the focus order.
+fn record_focus(order: &mut Vec<String>, label: &str) {
+    order.retain(|l| l != label);
+    order.insert(0, label.to_string());
+}
+
+/// Keep `note_window_focus_order` up to date for a new `sticked-*` window.
+/// It joins at the back until it is focused, and leaves when destroyed.
+fn track_note_window_focus(app: &AppHandle, win: &tauri::WebviewWindow) {
+    let label = win.label().to_string();
+    {
+        let state = app.state::<AppState>();
+        let mut order = state
+            .note_window_focus_order
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if !order.contains(&label) {
+            order.push(label.clone());
+        }
+    }
+
+    let app_handle = app.clone();
+    win.on_window_event(move |event| {
+        let state = app_handle.state::<AppState>();
+        let mut order = state
+            .note_window_focus_order
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        match event {
+            tauri::WindowEvent::Focused(true) => record_focus(&mut order, &label),
+            tauri::WindowEvent::Destroyed => order.retain(|l| l != &label),
+            _ => {}
+        }
+    });
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct OpenNoteWindow {
+    pub label: String,
+    /// `sticked` or `viewing`.
+    pub kind: &'static str,
+    pub title: String,
+    pub folder: String,
+    pub focused: bool,
+}
+
+/// Every open sticked and viewing window, most recently focused first, for
+/// the note switcher.
+#[tauri::command]
+pub fn list_open_note_windows(app: AppHandle) -> Result<Vec<OpenNoteWindow>, String> {
+    let state = app.state::<AppState>();
+    let stored = sticked_notes::list_sticked_notes().unwrap_or_default();
+    let order = state
+        .note_window_focus_order
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+
+    let mut windows: Vec<OpenNoteWindow> = {
+        let viewing_notes = state.viewing_notes.lock().unwrap_or_else(|e| e.into_inner());
+        app.webview_windows()
+            .into_iter()
+            .filter_map(|(label, window)| {
+                let id = label.strip_prefix("sticked-")?;
+                let (kind, content, folder) = match viewing_notes.get(id) {
+                    Some(note) => ("viewing", note.content.as_str(), note.folder.clone()),
+                    None => {
+                        let note = stored.iter().find(|note| note.id == id)?;
+                        ("sticked", note.content.as_str(), note.folder.clone())
+                    }
+                };
+                let title = if note_lock::is_locked_content(content) {
+                    "Locked note".to_string()
+                } else {
+                    export::document_title(content)
+                };
+                Some(OpenNoteWindow {
+                    focused: window.is_focused().unwrap_or(false),
+                    label,
+                    kind,
+                    title,
+                    folder,
+                })
+            })
+            .collect()
+    };
+    // Windows that never had focus (restored at launch) go last.
+    windows.sort_by_key(|window| {
+        order
+            .iter()
+            .position(|label| label == &window.label)
+            .unwrap_or(usize::MAX)
+    });
+    Ok(windows)
+}
+
+/// Show, raise and focus the note window `label` from the switcher.
+#[tauri::command]
+pub fn focus_note_window(app: AppHandle, label: String) -> Result<bool, String> {
+    if !label.starts_with("sticked-") {
+        return Err("Not a note window".to_string());
+    }
+    let window = app
+        .get_webview_window(&label)
+        .ok_or("Note window not found")?;
+    let _ = window.unminimize();
+    let _ = window.show();
+    let _ = window.set_focus();
+    Ok(true)
+}
+
+/// Hide or show every sticked and viewing window at once. Showing also
+/// restores windows for stored notes that have none.
+#[tauri::command]
+pub fn set_sticked_windows_visible(app: AppHandle, visible: bool) -> Result<bool, String> {
+    if visible {
+        restore_sticked_notes(&app);
+    }
+
+    for (label, window) in app.webview_windows() {
+        if !label.starts_with("sticked-") {
+            continue;
+        }
+        if visible {
+            let _ = window.show();
+        } else {
+            let _ = window.hide();
         }
     }
+
+    Ok(true)
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct StickedCloseSummary {
+    /// Files written for sticked notes that had content.
+    pub saved: Vec<String>,
+    /// Ids of notes closed without a file because they were empty.
+    pub empty: Vec<String>,
+    /// Notes left open because saving failed, as `(id, error)`.
+    pub failed: Vec<(String, String)>,
+}
+
+/// Save every non-empty sticked note into its folder and close all of
+/// them, as closing each one with "Save & Close" would.
+#[tauri::command]
+pub fn save_and_close_all_sticked(app: AppHandle) -> Result<StickedCloseSummary, String> {
+    let mut summary = StickedCloseSummary::default();
+    for note in sticked_notes::list_sticked_notes()? {
+        match sticked_notes::close_sticked_note(app.clone(), note.id.clone(), true) {
+            Ok(path) if path.is_empty() => summary.empty.push(note.id.clone()),
+            Ok(path) => summary.saved.push(path),
+            Err(e) => {
+                summary.failed.push((note.id, e));
+                continue;
+            }
+        }
+        close_sticked_window(app.clone(), note.id)?;
+    }
+    Ok(summary)
+}
+
+/// Discard every sticked note and close its window. `confirm` must be
+/// true, so a stray call can't throw notes away. Returns how many closed.
+#[tauri::command]
+pub fn close_all_sticked_without_saving(app: AppHandle, confirm: bool) -> Result<usize, String> {
+    if !confirm {
+        return Err("Closing all sticked notes without saving needs confirmation".to_string());
+    }
+    let notes = sticked_notes::list_sticked_notes()?;
+    for note in &notes {
+        sticked_notes::close_sticked_note(app.clone(), note.id.clone(), false)?;
+        close_sticked_window(app.clone(), note.id.clone())?;
+    }
+    Ok(notes.len())
+}
+
+/// Ask before discarding every sticked note, for the tray and the palette
+/// where there is no webview to confirm in.
+pub fn confirm_close_all_sticked_without_saving(app: &AppHandle) {
+    use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+    let count = sticked_notes::list_sticked_notes()
+        .map(|notes| notes.len())
+        .unwrap_or(0);
+    if count == 0 {
+        return;
+    }
+    let app_handle = app.clone();
+    app.dialog()
+        .message(format!(
+            "Close {} sticked note{} without saving? Their content will be lost.",
+            count,
+            if count == 1 { "" } else { "s" }
+        ))
+        .title("Close All Sticked Notes")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Close Without Saving".to_string(),
+            "Cancel".to_string(),
+        ))
+        .show(move |confirmed| {
+            if !confirmed {
+                return;
+            }
+            if let Err(e) = close_all_sticked_without_saving(app_handle, true) {
+                log::error!("Failed to close sticked notes: {}", e);
+            }
+        });
+}
+
+pub fn restore_sticked_notes(app: &AppHandle) {
+    let notes = match sticked_notes::list_sticked_notes() {
+        Ok(notes) if !notes.is_empty() => notes,
+        _ => return,
+    };
+    let app = app.clone();
+    std::thread::Builder::new()
+        .name("stik-sticked-restore".to_string())
+        .spawn(move || {
+            // Don't pop stickies over a presentation; wait until Focus ends.
+            if focus_mode::should_suppress_interruptions() {
+                log::info!("Deferring sticked note restore until Focus or quiet hours end");
+                while focus_mode::should_suppress_interruptions() {
+                    std::thread::sleep(std::time::Duration::from_secs(
+                        STICKED_RESTORE_DEFER_SECONDS,
+                    ));
+                }
+            }
+            for (i, note) in notes.into_iter().enumerate() {
+                if i > 0 {
+                    std::thread::sleep(std::time::Duration::from_millis(
+                        STICKED_RESTORE_STAGGER_MS,
+                    ));
+                }
+                if let Err(e) = create_sticked_window(app.clone(), note) {
+                    log::error!("Failed to restore sticked note: {}", e);
+                }
+            }
+        })
+        .ok();
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{remember_last_note, SETTINGS_WINDOW_MIN_WIDTH, SETTINGS_WINDOW_WIDTH};
+    use super::{
+        area_containing, center_in_area, overlay_actions, quick_peek_position, record_focus,
+        remember_last_note, should_restore_frontmost_app, viewing_note_id, viewing_notes_to_evict,
+        FocusTarget, OverlayActions, OverlayContext, OverlayEvent, PALETTE_POLICY, SETTINGS_POLICY,
+        SETTINGS_WINDOW_MIN_WIDTH, SETTINGS_WINDOW_WIDTH,
+    };
     use crate::state::AppState;
 
     #[test]
@@ -626,4 +1865,171 @@ mod tests {
         assert!(SETTINGS_WINDOW_MIN_WIDTH >= 760.0);
         assert!(SETTINGS_WINDOW_WIDTH > SETTINGS_WINDOW_MIN_WIDTH);
     }
+
+    #[test]
+    fn finds_monitor_under_cursor_and_centers_in_it() {
+        let areas = [
+            (0.0, 0.0, 1920.0, 1080.0),
+            (1920.0, 0.0, 2560.0, 1440.0),
+            (-1440.0, -400.0, 1440.0, 2560.0),
+        ];
+        assert_eq!(area_containing((100.0, 100.0), &areas), Some(0));
+        assert_eq!(area_containing((2000.0, 50.0), &areas), Some(1));
+        assert_eq!(area_containing((-10.0, -300.0), &areas), Some(2));
+        assert_eq!(area_containing((9000.0, 9000.0), &areas), None);
+
+        assert_eq!(center_in_area(560.0, 440.0, areas[1]), (2920.0, 500.0));
+        assert_eq!(center_in_area(3000.0, 440.0, areas[0]), (0.0, 320.0));
+    }
+
+    #[test]
+    fn viewing_ids_differ_for_paths_that_used_to_collide() {
+        // All of these mapped to the same label under `-` substitution.
+        let paths = [
+            "/Stik/Inbox/a.b.md",
+            "/Stik/Inbox/a b.md",
+            "/Stik/Inbox/a-b.md",
+            "/Stik/In box/a.md",
+            "/Stik/In.box/a.md",
+        ];
+        let ids: std::collections::HashSet<String> =
+            paths.iter().map(|path| viewing_note_id(path)).collect();
+        assert_eq!(ids.len(), paths.len());
+        assert!(ids.iter().all(|id| id.starts_with("view-")));
+        assert_eq!(
+            viewing_note_id("/Stik/Inbox/a.b.md"),
+            viewing_note_id("/Stik/Inbox/a.b.md")
+        );
+    }
+
+    #[test]
+    fn restores_previous_app_only_when_focus_is_unclaimed() {
+        // Hidden from the focused postit (save or Escape).
+        assert!(should_restore_frontmost_app(true, false, true));
+        // Blurred with Stik still frontmost but no window focused.
+        assert!(should_restore_frontmost_app(false, false, true));
+        // Blurred because the user clicked a sticked note or settings.
+        assert!(!should_restore_frontmost_app(false, true, true));
+        // Blurred because the user switched to another app.
+        assert!(!should_restore_frontmost_app(false, false, false));
+    }
+
+    #[test]
+    fn focusing_a_note_window_moves_it_to_the_front() {
+        let mut order = vec!["sticked-a".to_string(), "sticked-view-b".to_string()];
+        record_focus(&mut order, "sticked-view-b");
+        assert_eq!(order, vec!["sticked-view-b", "sticked-a"]);
+        record_focus(&mut order, "sticked-c");
+        assert_eq!(order, vec!["sticked-c", "sticked-view-b", "sticked-a"]);
+    }
+
+    #[test]
+    fn quick_peek_stays_on_screen_near_edges() {
+        let area = (0.0, 0.0, 1920.0, 1080.0);
+        let size = (380.0, 280.0);
+        // Room on the right: sits just past the anchor.
+        assert_eq!(quick_peek_position((500.0, 300.0), size, area), (512.0, 300.0));
+        // Near the right edge: flips to the left of the anchor.
+        assert_eq!(quick_peek_position((1800.0, 300.0), size, area), (1408.0, 300.0));
+        // Near the bottom: pulled up into the work area.
+        assert_eq!(quick_peek_position((500.0, 1000.0), size, area), (512.0, 800.0));
+        // Secondary monitor to the left of the primary.
+        let left = (-1440.0, 0.0, 1440.0, 900.0);
+        assert_eq!(quick_peek_position((-100.0, 50.0), size, left), (-492.0, 50.0));
+    }
+
+    #[test]
+    fn evicts_least_recently_used_closed_viewing_notes() {
+        let start = std::time::Instant::now();
+        let at = |secs| start + std::time::Duration::from_secs(secs);
+        let entries = vec![
+            ("view-open".to_string(), at(0), true),
+            ("view-old".to_string(), at(1), false),
+            ("view-recent".to_string(), at(3), false),
+            ("view-older".to_string(), at(0), false),
+        ];
+        assert_eq!(
+            viewing_notes_to_evict(&entries, 2),
+            vec!["view-older", "view-old"]
+        );
+        assert!(viewing_notes_to_evict(&entries, 4).is_empty());
+        // Open windows keep their content even past the limit.
+        assert_eq!(viewing_notes_to_evict(&entries, 0).len(), 3);
+    }
+
+    #[test]
+    fn closing_an_overlay_restores_sticked_notes_and_focus() {
+        let ctx = OverlayContext {
+            previous_sticked: Some("sticked-1"),
+            postit_was_visible: true,
+            has_viewing_windows: true,
+            other_overlay_open: false,
+        };
+        assert_eq!(
+            overlay_actions(SETTINGS_POLICY, OverlayEvent::Destroyed, &ctx),
+            OverlayActions {
+                raise_sticked: true,
+                focus: Some(FocusTarget::Sticked("sticked-1")),
+            }
+        );
+        // The palette leaves notes opened from it in front.
+        assert_eq!(
+            overlay_actions(PALETTE_POLICY, OverlayEvent::Destroyed, &ctx),
+            OverlayActions {
+                raise_sticked: true,
+                focus: None,
+            }
+        );
+        let no_viewing = OverlayContext {
+            has_viewing_windows: false,
+            ..ctx
+        };
+        assert_eq!(
+            overlay_actions(PALETTE_POLICY, OverlayEvent::Destroyed, &no_viewing).focus,
+            Some(FocusTarget::Postit)
+        );
+        let hidden_postit = OverlayContext {
+            previous_sticked: None,
+            postit_was_visible: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            overlay_actions(SETTINGS_POLICY, OverlayEvent::Destroyed, &hidden_postit),
+            OverlayActions {
+                raise_sticked: true,
+                focus: None,
+            }
+        );
+    }
+
+    #[test]
+    fn only_the_palette_restores_on_blur() {
+        let ctx = OverlayContext::default();
+        assert!(overlay_actions(PALETTE_POLICY, OverlayEvent::Focused(false), &ctx).raise_sticked);
+        assert!(
+            !overlay_actions(SETTINGS_POLICY, OverlayEvent::Focused(false), &ctx).raise_sticked
+        );
+        assert_eq!(
+            overlay_actions(PALETTE_POLICY, OverlayEvent::Focused(true), &ctx),
+            OverlayActions::default()
+        );
+    }
+
+    #[test]
+    fn another_open_overlay_keeps_sticked_notes_lowered() {
+        // e.g. the palette blurs or closes because it opened Settings.
+        let ctx = OverlayContext {
+            postit_was_visible: true,
+            other_overlay_open: true,
+            ..Default::default()
+        };
+        for policy in [PALETTE_POLICY, SETTINGS_POLICY] {
+            for event in [OverlayEvent::Focused(false), OverlayEvent::Destroyed] {
+                assert_eq!(
+                    overlay_actions(policy, event, &ctx),
+                    OverlayActions::default()
+                );
+            }
+        }
+    }
 }
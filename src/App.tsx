@@ -7,7 +7,13 @@ import PostIt from "./components/PostIt";
 import SettingsModal from "./components/SettingsModal";
 import CommandPalette from "./components/CommandPalette";
 import AnalyticsNotice from "./components/AnalyticsNotice";
+import NotesDirectoryBanner from "./components/NotesDirectoryBanner";
+import PortableConfigBanner from "./components/PortableConfigBanner";
+import Onboarding from "./components/Onboarding";
 import AppleNotesPicker from "./components/AppleNotesPicker";
+import StatsWindow from "./components/StatsWindow";
+import QuickPeek from "./components/QuickPeek";
+import ZenWindow from "./components/ZenWindow";
 import { useTheme } from "./hooks/useTheme";
 import type { StickedNote, StikSettings } from "@/types";
 import { isMarkdownEffectivelyEmpty } from "@/utils/normalizeMarkdownForCopy";
@@ -19,7 +25,10 @@ type WindowType =
   | "sticked"
   | "settings"
   | "command-palette"
-  | "apple-notes-picker";
+  | "apple-notes-picker"
+  | "stats"
+  | "peek"
+  | "zen";
 const PENDING_UPDATE_KEY = "stik_pending_update_version";
 
 function getWindowInfo(): { type: WindowType; id?: string; viewing?: boolean } {
@@ -50,6 +59,18 @@ function getWindowInfo(): { type: WindowType; id?: string; viewing?: boolean } {
     return { type: "apple-notes-picker" };
   }
 
+  if (windowType === "stats") {
+    return { type: "stats" };
+  }
+
+  if (windowType === "peek") {
+    return { type: "peek" };
+  }
+
+  if (windowType === "zen") {
+    return { type: "zen" };
+  }
+
   return { type: "postit" };
 }
 
@@ -59,10 +80,14 @@ export default function App() {
   const [stickedNote, setStickedNote] = useState<StickedNote | null>(null);
   const [loadError, setLoadError] = useState<string | null>(null);
   const contentRef = useRef("");
+  const folderRef = useRef("");
   const blurIgnoreUntilRef = useRef(0);
   const pendingBlurHideRef = useRef<number | null>(null);
   const skipNextBlurHideRef = useRef(false);
   const [showAnalyticsNotice, setShowAnalyticsNotice] = useState(false);
+  const [onboardingShortcuts, setOnboardingShortcuts] = useState<
+    string[] | null
+  >(null);
   const windowInfo = getWindowInfo();
 
   const resolveFolder = useCallback(
@@ -118,6 +143,9 @@ export default function App() {
             content: string;
             folder: string;
             path: string;
+            read_only?: boolean;
+            created?: string | null;
+            modified?: string | null;
           }>("get_viewing_note_content", { id: windowInfo.id });
           setStickedNote({
             id: data.id,
@@ -125,9 +153,10 @@ export default function App() {
             folder: data.folder,
             position: null,
             size: null,
-            created_at: "",
-            updated_at: "",
+            created_at: data.created ?? "",
+            updated_at: data.modified ?? "",
             originalPath: data.path,
+            read_only: data.read_only,
           });
           setCurrentFolder(data.folder);
         } catch (error) {
@@ -152,7 +181,12 @@ export default function App() {
       });
   }, [windowInfo.type, windowInfo.id, windowInfo.viewing]);
 
-  // Hide postit on blur only when editor is empty
+  useEffect(() => {
+    folderRef.current = currentFolder;
+  }, [currentFolder]);
+
+  // Hide postit on blur when editor is empty; otherwise the backend applies
+  // the capture_blur_behavior setting.
   useEffect(() => {
     if (windowInfo.type !== "postit") return;
 
@@ -211,6 +245,13 @@ export default function App() {
         });
         if (shouldHide) {
           await invoke("hide_window");
+        } else {
+          await invoke("capture_blurred", {
+            content: contentRef.current,
+            folder: folderRef.current,
+          }).catch((error) =>
+            console.error("Failed to handle capture blur:", error),
+          );
         }
       }, 140);
     });
@@ -327,7 +368,9 @@ export default function App() {
 
     invoke<StikSettings>("get_settings")
       .then((s) => {
-        if (!s.analytics_notice_dismissed) {
+        if (s.onboarding && !s.onboarding.completed) {
+          setOnboardingShortcuts(Object.values(s.system_shortcuts));
+        } else if (!s.analytics_notice_dismissed) {
           setShowAnalyticsNotice(true);
         }
       })
@@ -345,6 +388,11 @@ export default function App() {
     setShowAnalyticsNotice(false);
   }, []);
 
+  const handleOnboardingComplete = useCallback((defaultFolder: string) => {
+    setOnboardingShortcuts(null);
+    setCurrentFolder(defaultFolder);
+  }, []);
+
   const handleSave = useCallback(
     async (
       content: string,
@@ -360,7 +408,7 @@ export default function App() {
         setCurrentFolder(resolvedFolder);
       }
 
-      const result = await invoke<{ path: string }>("save_note", {
+      const result = await invoke<{ path: string }>("save_note_with_context", {
         folder: resolvedFolder,
         content,
       });
@@ -395,6 +443,21 @@ export default function App() {
     return <CommandPalette />;
   }
 
+  // Render stats dashboard if this is that window type
+  if (windowInfo.type === "stats") {
+    return <StatsWindow />;
+  }
+
+  // Render quick-peek preview if this is that window type
+  if (windowInfo.type === "peek") {
+    return <QuickPeek />;
+  }
+
+  // Render the distraction-free editor if this is the zen window
+  if (windowInfo.type === "zen") {
+    return <ZenWindow />;
+  }
+
   // Render Apple Notes picker if this is that window type
   if (windowInfo.type === "apple-notes-picker") {
     return <AppleNotesPicker />;
@@ -445,6 +508,9 @@ export default function App() {
         initialContent={stickedNote.content}
         isViewing={windowInfo.viewing}
         originalPath={stickedNote.originalPath}
+        readOnly={stickedNote.read_only}
+        modifiedAt={stickedNote.originalPath ? stickedNote.updated_at : undefined}
+        templateOrigin={stickedNote.template_origin ?? undefined}
       />
     );
   }
@@ -468,9 +534,17 @@ export default function App() {
         onOpenSettings={handleOpenSettings}
         onContentChange={handleContentChange}
       />
+      <NotesDirectoryBanner />
+      <PortableConfigBanner />
       {showAnalyticsNotice && (
         <AnalyticsNotice onDismiss={handleDismissAnalyticsNotice} />
       )}
+      {onboardingShortcuts && (
+        <Onboarding
+          systemShortcuts={onboardingShortcuts}
+          onComplete={handleOnboardingComplete}
+        />
+      )}
     </>
   );
 }
@@ -1,7 +1,14 @@
 import { useState, useEffect, useRef, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
-import type { AppleNoteEntry, StikSettings } from "@/types";
+import type {
+  AppleImportProgress,
+  AppleImportSummary,
+  AppleListProgress,
+  AppleNoteEntry,
+  StikSettings,
+} from "@/types";
 import { formatRelativeDate } from "@/utils/formatRelativeDate";
 
 export default function AppleNotesPicker() {
@@ -14,9 +21,26 @@ export default function AppleNotesPicker() {
   const [error, setError] = useState<string | null>(null);
   const [needsPermission, setNeedsPermission] = useState(false);
   const [targetFolder, setTargetFolder] = useState("");
+  const [listProgress, setListProgress] = useState<AppleListProgress | null>(null);
+  const [importProgress, setImportProgress] = useState<AppleImportProgress | null>(null);
+  const [importSummary, setImportSummary] = useState<AppleImportSummary | null>(null);
   const inputRef = useRef<HTMLInputElement>(null);
   const resultsRef = useRef<HTMLDivElement>(null);
 
+  // Listing and bulk imports stream progress while they run in Rust
+  useEffect(() => {
+    const unlistenList = listen<AppleListProgress>("apple-list-progress", (e) =>
+      setListProgress(e.payload)
+    );
+    const unlistenImport = listen<AppleImportProgress>("apple-import-progress", (e) =>
+      setImportProgress(e.payload)
+    );
+    return () => {
+      unlistenList.then((fn) => fn());
+      unlistenImport.then((fn) => fn());
+    };
+  }, []);
+
   // Load notes + resolve target folder on mount
   useEffect(() => {
     const loadNotes = async () => {
@@ -101,11 +125,42 @@ export default function AppleNotesPicker() {
     [isImporting, targetFolder]
   );
 
+  // Import every note currently shown; stays open to report the result
+  const handleImportAll = useCallback(async () => {
+    if (isImporting || filteredNotes.length === 0) return;
+
+    setIsImporting(true);
+    setImportSummary(null);
+    setImportProgress({ done: 0, total: filteredNotes.length });
+    try {
+      const summary = await invoke<AppleImportSummary>("import_apple_notes", {
+        noteIds: filteredNotes.map((n) => n.note_id),
+        folder: targetFolder,
+      });
+      setImportSummary(summary);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setImportProgress(null);
+      setIsImporting(false);
+    }
+  }, [isImporting, filteredNotes, targetFolder]);
+
+  const cancelImport = useCallback(() => {
+    invoke("apple_import_cancel").catch((err) =>
+      console.error("Failed to cancel import:", err)
+    );
+  }, []);
+
   // Keyboard navigation
   useEffect(() => {
     const handleKeyDown = async (e: KeyboardEvent) => {
       if (e.key === "Escape") {
         e.preventDefault();
+        if (importProgress) {
+          cancelImport();
+          return;
+        }
         await getCurrentWindow().close();
       } else if (e.key === "ArrowDown") {
         e.preventDefault();
@@ -121,7 +176,7 @@ export default function AppleNotesPicker() {
 
     window.addEventListener("keydown", handleKeyDown);
     return () => window.removeEventListener("keydown", handleKeyDown);
-  }, [filteredNotes, selectedIndex, handleImport]);
+  }, [filteredNotes, selectedIndex, handleImport, importProgress, cancelImport]);
 
   // Scroll selected item into view
   useEffect(() => {
@@ -236,7 +291,9 @@ export default function AppleNotesPicker() {
             className="flex-1 bg-transparent text-[15px] text-ink placeholder:text-stone outline-none"
           />
           {isLoading && (
-            <span className="text-stone text-sm animate-pulse">...</span>
+            <span className="text-stone text-sm animate-pulse">
+              {listProgress ? `${listProgress.loaded}/${listProgress.total}` : "..."}
+            </span>
           )}
         </div>
       </div>
@@ -245,7 +302,9 @@ export default function AppleNotesPicker() {
       <div ref={resultsRef} className="flex-1 overflow-y-auto">
         {isLoading ? (
           <div className="p-4 text-center text-stone text-sm">
-            Loading Apple Notes...
+            {listProgress
+              ? `Loading Apple Notes... ${listProgress.loaded} of ${listProgress.total}`
+              : "Loading Apple Notes..."}
           </div>
         ) : filteredNotes.length === 0 ? (
           <div className="p-4 text-center text-stone text-sm">
@@ -254,41 +313,41 @@ export default function AppleNotesPicker() {
               : "No notes found in Apple Notes"}
           </div>
         ) : (
-          filteredNotes.map((note, index) => (
-            <button
-              key={note.note_id}
-              onClick={() => handleImport(note)}
-              onMouseEnter={() => setSelectedIndex(index)}
-              disabled={isImporting}
-              className={`w-full px-4 py-3 text-left border-b border-line/50 transition-colors ${
-                index === selectedIndex
-                  ? "bg-coral/10"
-                  : "hover:bg-line/30"
-              } ${isImporting ? "opacity-50" : ""}`}
-            >
-              <div className="flex items-center gap-2 mb-1">
-                <span className="px-2 py-0.5 rounded-full text-[10px] font-semibold bg-coral-light text-coral">
-                  {note.folder_name}
-                </span>
-                <span className="text-[10px] text-stone font-mono">
-                  {formatRelativeDate(note.modified_date)}
-                </span>
-                {note.account_name !== "Local" && (
-                  <span className="text-[9px] text-stone/60">
-                    {note.account_name}
+            filteredNotes.map((note, index) => (
+              <button
+                key={note.note_id}
+                onClick={() => handleImport(note)}
+                onMouseEnter={() => setSelectedIndex(index)}
+                disabled={isImporting}
+                className={`w-full px-4 py-3 text-left border-b border-line/50 transition-colors ${
+                  index === selectedIndex
+                    ? "bg-coral/10"
+                    : "hover:bg-line/30"
+                } ${isImporting ? "opacity-50" : ""}`}
+              >
+                <div className="flex items-center gap-2 mb-1">
+                  <span className="px-2 py-0.5 rounded-full text-[10px] font-semibold bg-coral-light text-coral">
+                    {note.folder_name}
                   </span>
-                )}
-              </div>
-              <p className="text-[14px] font-medium text-ink leading-relaxed truncate">
-                {note.title || "Untitled"}
-              </p>
-              {note.snippet && (
-                <p className="text-[12px] text-stone leading-relaxed mt-0.5 truncate">
-                  {note.snippet}
+                  <span className="text-[10px] text-stone font-mono">
+                    {formatRelativeDate(note.modified_date)}
+                  </span>
+                  {note.account_name !== "Local" && (
+                    <span className="text-[9px] text-stone/60">
+                      {note.account_name}
+                    </span>
+                  )}
+                </div>
+                <p className="text-[14px] font-medium text-ink leading-relaxed truncate">
+                  {note.title || "Untitled"}
                 </p>
-              )}
-            </button>
-          ))
+                {note.snippet && (
+                  <p className="text-[12px] text-stone leading-relaxed mt-0.5 truncate">
+                    {note.snippet}
+                  </p>
+                )}
+              </button>
+            ))
         )}
       </div>
 
@@ -297,6 +356,27 @@ export default function AppleNotesPicker() {
         onMouseDown={startDrag}
         className="flex items-center justify-between px-4 py-2 border-t border-line text-[10px] text-stone drag-handle"
       >
+        {importProgress ? (
+          <div className="flex items-center gap-3">
+            <span className="text-ink">
+              Importing {importProgress.done} of {importProgress.total}...
+            </span>
+            <button
+              onClick={cancelImport}
+              className="px-2 py-0.5 bg-line hover:bg-line/70 text-ink rounded transition-colors"
+            >
+              Cancel
+            </button>
+          </div>
+        ) : importSummary ? (
+          <span className="text-ink">
+            {importSummary.cancelled
+              ? `Cancelled after ${importSummary.imported} of ${importSummary.total} notes`
+              : `Imported ${importSummary.imported} of ${importSummary.total} notes`}
+            {importSummary.failed.length > 0 &&
+              ` (${importSummary.failed.length} failed)`}
+          </span>
+        ) : (
         <div className="flex items-center gap-3">
           <span>
             <kbd className="px-1.5 py-0.5 bg-line rounded text-[9px]">
@@ -310,10 +390,20 @@ export default function AppleNotesPicker() {
             </kbd>{" "}
             import
           </span>
+          {filteredNotes.length > 1 && (
+            <button
+              onClick={handleImportAll}
+              disabled={isImporting}
+              className="text-coral hover:text-coral/80 transition-colors"
+            >
+              Import all {filteredNotes.length}
+            </button>
+          )}
         </div>
+        )}
         <span>
           <kbd className="px-1.5 py-0.5 bg-line rounded text-[9px]">esc</kbd>{" "}
-          close
+          {importProgress ? "cancel" : "close"}
         </span>
       </div>
     </div>
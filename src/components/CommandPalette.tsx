@@ -4,9 +4,14 @@ import { emit, listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import type {
   NoteInfo,
+  MovedToNewFolder,
   SearchResult,
   SemanticResult,
-  FolderStats,
+  FolderBadge,
+  FolderChange,
+  FacetedSearch,
+  PaletteAction,
+  SearchFacets,
   StikSettings,
 } from "@/types";
 import {
@@ -17,6 +22,7 @@ import ConfirmDialog from "./ConfirmDialog";
 import LockPrompt from "./LockPrompt";
 import FolderSidebar from "./command-palette/FolderSidebar";
 import NoteList from "./command-palette/NoteList";
+import ActionList from "./command-palette/ActionList";
 import MovePicker from "./command-palette/MovePicker";
 
 /** Derive a human-readable title from a Stik filename like `20260310-114522-my-note-a1b2.md` */
@@ -60,14 +66,28 @@ function Toast({ message, onDone }: { message: string; onDone: () => void }) {
 export default function CommandPalette() {
   // Search state
   const [query, setQuery] = useState("");
+  const [isPinned, setIsPinned] = useState(false);
+  const [actions, setActions] = useState<PaletteAction[]>([]);
+  // A leading ">" switches from note search to backend actions
+  const isActionMode = query.trimStart().startsWith(">");
+  const actionQuery = isActionMode
+    ? query.trimStart().slice(1).trim().toLowerCase()
+    : "";
+  const filteredActions = actions.filter(
+    (action) =>
+      !actionQuery ||
+      action.title.toLowerCase().includes(actionQuery) ||
+      action.category.toLowerCase().includes(actionQuery),
+  );
   const [results, setResults] = useState<SearchResult[]>([]);
   const [semanticResults, setSemanticResults] = useState<SemanticResult[]>([]);
+  const [searchFacets, setSearchFacets] = useState<SearchFacets | null>(null);
   const [selectedNoteIndex, setSelectedNoteIndex] = useState(0);
   const [recentNotes, setRecentNotes] = useState<SearchResult[]>([]);
   const [isSearching, setIsSearching] = useState(false);
 
   // Folder state
-  const [folderStats, setFolderStats] = useState<FolderStats[]>([]);
+  const [folderStats, setFolderStats] = useState<FolderBadge[]>([]);
   const [selectedFolder, setSelectedFolder] = useState<string | null>(null);
   const [folderColors, setFolderColors] = useState<Record<string, string>>({});
   const [folders, setFolders] = useState<string[]>([]);
@@ -121,7 +141,7 @@ export default function CommandPalette() {
   const loadFolderStats = useCallback(async () => {
     try {
       const [stats, allNotes] = await Promise.all([
-        invoke<FolderStats[]>("get_folder_stats"),
+        invoke<FolderBadge[]>("get_folder_badges"),
         invoke<NoteInfo[]>("list_notes", { folder: null }),
       ]);
 
@@ -160,9 +180,14 @@ export default function CommandPalette() {
         setFolderColors(event.payload.folder_colors ?? {});
       },
     );
+    const unlistenFolders = listen<FolderChange[]>("folders-changed", () => {
+      loadFolderStats();
+      invoke<string[]>("list_folders").then(setFolders);
+    });
 
     return () => {
       unlistenSettings.then((fn) => fn());
+      unlistenFolders.then((fn) => fn());
     };
   }, [loadFolderStats]);
 
@@ -181,17 +206,33 @@ export default function CommandPalette() {
             snippet: normalizeNoteSnippet(n.content),
             created: n.created,
             locked: n.locked,
+            read_only: n.read_only,
+            open_tasks: n.open_tasks,
+            done_tasks: n.done_tasks,
           })),
         );
       },
     );
   }, [selectedFolder]);
 
+  // Enabled flags depend on runtime state, so refetch on each entry
+  useEffect(() => {
+    if (!isActionMode) return;
+    invoke<PaletteAction[]>("list_palette_actions")
+      .then(setActions)
+      .catch((error) => console.error("Failed to list actions:", error));
+  }, [isActionMode]);
+
   // Search: text + semantic in parallel (debounced)
   useEffect(() => {
+    if (query.trimStart().startsWith(">")) {
+      setSelectedNoteIndex(0);
+      return;
+    }
     if (!query.trim()) {
       setResults(recentNotes);
       setSemanticResults([]);
+      setSearchFacets(null);
       setSelectedNoteIndex(0);
       return;
     }
@@ -201,9 +242,10 @@ export default function CommandPalette() {
       const trimmed = query.trim();
 
       const [textResult, semanticResult] = await Promise.allSettled([
-        invoke<SearchResult[]>("search_notes", {
+        invoke<FacetedSearch>("search_notes_faceted", {
           query: trimmed,
           folder: selectedFolder,
+          includeFacets: true,
         }),
         invoke<SemanticResult[]>("semantic_search", {
           query: trimmed,
@@ -212,8 +254,11 @@ export default function CommandPalette() {
       ]);
 
       const textResults =
-        textResult.status === "fulfilled" ? textResult.value : [];
+        textResult.status === "fulfilled" ? textResult.value.results : [];
       setResults(textResults);
+      setSearchFacets(
+        textResult.status === "fulfilled" ? textResult.value.facets : null,
+      );
 
       if (semanticResult.status === "fulfilled") {
         const textPaths = new Set(textResults.map((r) => r.path));
@@ -259,6 +304,18 @@ export default function CommandPalette() {
     }
   }, []);
 
+  // Pinned palettes stay open so several results can be opened in turn
+  const togglePinned = useCallback(async () => {
+    try {
+      const pinned = await invoke<boolean>("pin_command_palette", {
+        pinned: !isPinned,
+      });
+      setIsPinned(pinned);
+    } catch (error) {
+      console.error("Failed to pin command palette:", error);
+    }
+  }, [isPinned]);
+
   const openNote = useCallback(
     async (result: SearchResult) => {
       try {
@@ -270,15 +327,48 @@ export default function CommandPalette() {
           folder: result.folder,
           path: result.path,
         });
-        closePalette();
+        if (!isPinned) closePalette();
       } catch (error) {
         console.error("Failed to open note:", error);
         setToast(`Couldn't open note: ${String(error)}`);
       }
     },
-    [closePalette],
+    [closePalette, isPinned],
+  );
+
+  const runAction = useCallback(
+    async (action: PaletteAction) => {
+      if (!action.enabled) return;
+      try {
+        await invoke("execute_palette_action", { id: action.id });
+        if (!isPinned) closePalette();
+      } catch (error) {
+        setToast(String(error));
+      }
+    },
+    [closePalette, isPinned],
   );
 
+  // Anchor the peek to the right edge of the result row, in screen pixels
+  const peekNote = useCallback(async (path: string, itemIndex: number) => {
+    const items = resultsRef.current?.querySelectorAll<HTMLElement>("button");
+    const rect = items?.[itemIndex]?.getBoundingClientRect();
+    try {
+      const win = getCurrentWindow();
+      const [position, scale] = await Promise.all([
+        win.outerPosition(),
+        win.scaleFactor(),
+      ]);
+      await invoke("show_quick_peek", {
+        path,
+        anchorX: position.x + (rect ? rect.right : 0) * scale,
+        anchorY: position.y + (rect ? rect.top : 0) * scale,
+      });
+    } catch (error) {
+      setToast(String(error));
+    }
+  }, []);
+
   const handleSelectResult = useCallback(
     async (result: SearchResult) => {
       if (result.locked) {
@@ -315,6 +405,9 @@ export default function CommandPalette() {
       snippet: normalizeNoteSnippet(n.content),
       created: n.created,
       locked: n.locked,
+      read_only: n.read_only,
+      open_tasks: n.open_tasks,
+      done_tasks: n.done_tasks,
     }));
     setRecentNotes(recent);
 
@@ -331,32 +424,57 @@ export default function CommandPalette() {
     }
   }, [loadFolderStats, query, selectedFolder]);
 
-  // Refresh note list when files change externally (local watcher or iCloud sync)
+  // Refresh note list when files change externally (local watcher or iCloud
+  // sync) or a note is saved, updated, deleted, moved or expired elsewhere
   useEffect(() => {
-    const unlistenFiles = listen("files-changed", () => {
-      refreshAfterChange();
-    });
-    const unlistenICloud = listen("icloud-files-changed", () => {
-      refreshAfterChange();
-    });
+    const unlisteners = [
+      "files-changed",
+      "icloud-files-changed",
+      "note-saved",
+      "note-updated",
+      "note-deleted",
+      "notes-deleted",
+      "note-moved",
+      "notes-expired",
+    ].map((event) => listen(event, () => refreshAfterChange()));
     return () => {
-      unlistenFiles.then((fn) => fn());
-      unlistenICloud.then((fn) => fn());
+      unlisteners.forEach((unlisten) => unlisten.then((fn) => fn()));
     };
   }, [refreshAfterChange]);
 
+  // stik://search links hand their query over through the backend
+  useEffect(() => {
+    const applyPendingQuery = async () => {
+      const pending = await invoke<string | null>("take_pending_palette_query");
+      if (pending !== null) {
+        setQuery(pending);
+        inputRef.current?.focus();
+      }
+    };
+    applyPendingQuery();
+    const unlisten = listen("palette-query", () => {
+      applyPendingQuery();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   // Delete note
   const handleDeleteNote = useCallback(
     async (note: SearchResult) => {
       try {
         await invoke("delete_note", { path: note.path });
-        // Notify viewing windows about deletion
-        await emit("note-deleted", note.path);
         setConfirmDelete(null);
         await refreshAfterChange();
       } catch (error) {
         console.error("Failed to delete note:", error);
-        setToast(String(error));
+        setConfirmDelete(null);
+        setToast(
+          String(error) === "NOTE_LOCKED"
+            ? "Note is read-only; press ⌘E to allow edits"
+            : String(error),
+        );
       }
     },
     [refreshAfterChange],
@@ -403,6 +521,25 @@ export default function CommandPalette() {
     [refreshAfterChange],
   );
 
+  // Move note into a folder created on the way
+  const handleMoveNoteToNewFolder = useCallback(
+    async (note: SearchResult, folderName: string) => {
+      try {
+        await invoke<MovedToNewFolder>("move_note_to_new_folder", {
+          path: note.path,
+          newFolderName: folderName,
+        });
+        setShowMoveModal(null);
+      } catch (error) {
+        console.error("Failed to move note:", error);
+        setToast(String(error));
+      }
+      // Refresh either way: the folder may exist even if the move failed.
+      await refreshAfterChange();
+    },
+    [refreshAfterChange],
+  );
+
   // Save settings helper — keeps settingsRef in sync and notifies other windows
   const saveAndEmitSettings = useCallback(
     async (patch: Partial<StikSettings>) => {
@@ -570,6 +707,13 @@ export default function CommandPalette() {
         return;
       }
 
+      // Cmd+P: pin / unpin
+      if (e.key === "p" && (e.metaKey || e.ctrlKey)) {
+        e.preventDefault();
+        togglePinned();
+        return;
+      }
+
       // Any printable character: focus search input + right pane
       if (
         e.key.length === 1 &&
@@ -583,6 +727,22 @@ export default function CommandPalette() {
         return; // Let the key propagate to the input
       }
 
+      if (focusPane === "right" && isActionMode) {
+        if (e.key === "ArrowDown") {
+          e.preventDefault();
+          setSelectedNoteIndex((i) =>
+            Math.min(i + 1, filteredActions.length - 1),
+          );
+        } else if (e.key === "ArrowUp") {
+          e.preventDefault();
+          setSelectedNoteIndex((i) => Math.max(i - 1, 0));
+        } else if (e.key === "Enter" && filteredActions[selectedNoteIndex]) {
+          e.preventDefault();
+          runAction(filteredActions[selectedNoteIndex]);
+        }
+        return;
+      }
+
       if (focusPane === "right") {
         const totalItems = results.length + semanticResults.length;
 
@@ -609,6 +769,18 @@ export default function CommandPalette() {
             const note = results[selectedNoteIndex];
             setConfirmDelete({ type: "note", note });
           }
+        } else if (
+          e.key === "y" &&
+          (e.metaKey || e.ctrlKey) &&
+          totalItems > 0
+        ) {
+          // Cmd+Y: quick peek at the selected result
+          e.preventDefault();
+          const item =
+            selectedNoteIndex < results.length
+              ? results[selectedNoteIndex]
+              : semanticResults[selectedNoteIndex - results.length];
+          if (item) peekNote(item.path, selectedNoteIndex);
         } else if (
           e.key === "m" &&
           (e.metaKey || e.ctrlKey) &&
@@ -658,6 +830,33 @@ export default function CommandPalette() {
             };
             toggleLock();
           }
+        } else if (
+          e.key === "e" &&
+          (e.metaKey || e.ctrlKey) &&
+          totalItems > 0
+        ) {
+          e.preventDefault();
+          const note =
+            selectedNoteIndex < results.length
+              ? results[selectedNoteIndex]
+              : null;
+          if (note) {
+            const toggleEditLock = async () => {
+              try {
+                if (note.read_only) {
+                  await invoke("unlock_note_edits", { path: note.path });
+                  setToast("Note can be edited again");
+                } else {
+                  await invoke("lock_note_edits", { path: note.path });
+                  setToast("Note is now read-only");
+                }
+                await refreshAfterChange();
+              } catch (err) {
+                setToast(String(err));
+              }
+            };
+            toggleEditLock();
+          }
         }
       } else {
         // Left pane (folder sidebar)
@@ -730,6 +929,11 @@ export default function CommandPalette() {
     handleSelectResult,
     refreshAfterChange,
     closePalette,
+    togglePinned,
+    peekNote,
+    isActionMode,
+    filteredActions,
+    runAction,
   ]);
 
   const toggleSidebarPosition = useCallback(async () => {
@@ -795,6 +999,17 @@ export default function CommandPalette() {
           {isSearching && (
             <span className="text-stone text-sm animate-pulse">...</span>
           )}
+          <button
+            onClick={togglePinned}
+            title={isPinned ? "Unpin palette (⌘P)" : "Keep palette open (⌘P)"}
+            className={`text-[11px] px-2 py-0.5 rounded-md border transition-colors ${
+              isPinned
+                ? "border-coral text-coral"
+                : "border-line text-stone hover:text-ink"
+            }`}
+          >
+            {isPinned ? "Pinned" : "Pin"}
+          </button>
         </div>
       </div>
 
@@ -840,30 +1055,42 @@ export default function CommandPalette() {
             setRenamingFolderName(null);
           }}
           position={sidebarPosition}
+          searchFacets={searchFacets}
         />
 
-        <NoteList
-          results={results}
-          semanticResults={semanticResults}
-          selectedIndex={selectedNoteIndex}
-          query={query}
-          isSearching={isSearching}
-          folderColors={folderColors}
-          focused={focusPane === "right"}
-          resultsRef={resultsRef}
-          onSelectResult={handleSelectResult}
-          onSetSelectedIndex={setSelectedNoteIndex}
-          isCreatingNote={isCreatingNote}
-          newNoteTitle={newNoteTitle}
-          onSetNewNoteTitle={setNewNoteTitle}
-          onCreateNote={handleCreateNote}
-          onCancelCreateNote={() => {
-            setIsCreatingNote(false);
-            setNewNoteTitle("");
-          }}
-          selectedFolder={selectedFolder}
-          folders={folders}
-        />
+{isActionMode ? (
+          <ActionList
+            actions={filteredActions}
+            selectedIndex={selectedNoteIndex}
+            focused={focusPane === "right"}
+            resultsRef={resultsRef}
+            onRunAction={runAction}
+            onSetSelectedIndex={setSelectedNoteIndex}
+          />
+        ) : (
+          <NoteList
+            results={results}
+            semanticResults={semanticResults}
+            selectedIndex={selectedNoteIndex}
+            query={query}
+            isSearching={isSearching}
+            folderColors={folderColors}
+            focused={focusPane === "right"}
+            resultsRef={resultsRef}
+            onSelectResult={handleSelectResult}
+            onSetSelectedIndex={setSelectedNoteIndex}
+            isCreatingNote={isCreatingNote}
+            newNoteTitle={newNoteTitle}
+            onSetNewNoteTitle={setNewNoteTitle}
+            onCreateNote={handleCreateNote}
+            onCancelCreateNote={() => {
+              setIsCreatingNote(false);
+              setNewNoteTitle("");
+            }}
+            selectedFolder={selectedFolder}
+            folders={folders}
+          />
+        )}
       </div>
 
       {/* Footer */}
@@ -900,6 +1127,10 @@ export default function CommandPalette() {
             <kbd className="px-1.5 py-0.5 bg-line rounded text-[9px]">⌘L</kbd>{" "}
             lock
           </span>
+          <span>
+            <kbd className="px-1.5 py-0.5 bg-line rounded text-[9px]">⌘E</kbd>{" "}
+            read-only
+          </span>
         </div>
         <div className="flex items-center gap-3">
           <button
@@ -954,6 +1185,9 @@ export default function CommandPalette() {
           folders={folders}
           folderColors={folderColors}
           onMove={(targetFolder) => handleMoveNote(showMoveModal, targetFolder)}
+          onMoveToNewFolder={(folderName) =>
+            handleMoveNoteToNewFolder(showMoveModal, folderName)
+          }
           onCancel={() => setShowMoveModal(null)}
         />
       )}
@@ -60,7 +60,7 @@ import { filenameToSlug } from "@/utils/wikiLink";
 import { normalizeUrl } from "@/utils/normalizeUrl";
 import { isImageUrl } from "@/utils/isImageUrl";
 import { isImageFile } from "@/utils/isImageFile";
-import { extractDroppedImagePath } from "@/utils/droppedImagePath";
+import { extractDroppedImagePath, extractDroppedTextPath } from "@/utils/droppedImagePath";
 import {
   findExternalLinkAtOffset,
   shouldShowCmdLinkCursor,
@@ -80,11 +80,15 @@ interface EditorProps {
   vimEnabled?: boolean;
   showFormatToolbar?: boolean;
   textDirection?: "auto" | "ltr" | "rtl";
+  /** Show the note without allowing edits (edit-locked notes). */
+  readOnly?: boolean;
   onVimModeChange?: (mode: VimMode) => void;
   onVimSaveAndClose?: () => void;
   onVimCloseWithoutSaving?: () => void;
   onImagePaste?: (file: File) => Promise<string | null>;
   onImageDropPath?: (path: string) => Promise<string | null>;
+  /** A `.txt`/`.csv`/`.tsv`/`.log` file was dropped; import it as a note. */
+  onTextFileDrop?: (path: string) => void;
   onWikiLinkClick?: (slug: string, path: string) => void;
   onCursorChange?: (head: number, anchor: number) => void;
 }
@@ -112,11 +116,13 @@ const Editor = forwardRef<EditorRef, EditorProps>(
       vimEnabled,
       showFormatToolbar,
       textDirection = "auto",
+      readOnly = false,
       onVimModeChange,
       onVimSaveAndClose,
       onVimCloseWithoutSaving,
       onImagePaste,
       onImageDropPath,
+      onTextFileDrop,
       onWikiLinkClick,
       onCursorChange,
     },
@@ -143,6 +149,8 @@ const Editor = forwardRef<EditorRef, EditorProps>(
     onImagePasteRef.current = onImagePaste;
     const onImageDropPathRef = useRef(onImageDropPath);
     onImageDropPathRef.current = onImageDropPath;
+    const onTextFileDropRef = useRef(onTextFileDrop);
+    onTextFileDropRef.current = onTextFileDrop;
     const onWikiLinkClickRef = useRef(onWikiLinkClick);
     onWikiLinkClickRef.current = onWikiLinkClick;
     const onCursorChangeRef = useRef(onCursorChange);
@@ -318,6 +326,14 @@ const Editor = forwardRef<EditorRef, EditorProps>(
           const droppedPlainText = event.dataTransfer?.getData("text/plain")?.trim() ?? "";
           const droppedValue = droppedUri || droppedPlainText;
           const droppedPath = extractDroppedImagePath(droppedValue);
+          const droppedTextPath = extractDroppedTextPath(droppedValue);
+
+          if (droppedTextPath && onTextFileDropRef.current) {
+            event.preventDefault();
+            lastDomDropAtRef.current = Date.now();
+            onTextFileDropRef.current(droppedTextPath);
+            return true;
+          }
 
           if (droppedPath && onImageDropPathRef.current) {
             event.preventDefault();
@@ -537,6 +553,10 @@ const Editor = forwardRef<EditorRef, EditorProps>(
         EditorView.contentAttributes.of({ class: "stik-editor" }),
       ];
 
+      if (readOnly) {
+        extensions.push(EditorState.readOnly.of(true), EditorView.editable.of(false));
+      }
+
       // Add vim mode if enabled
       if (vimEnabled) {
         extensions.push(vimCompartment.of(createVimExtension()));
@@ -594,9 +614,18 @@ const Editor = forwardRef<EditorRef, EditorProps>(
       void getCurrentWindow()
         .onDragDropEvent((event) => {
           if (event.payload.type !== "drop") return;
-          if (!onImageDropPathRef.current) return;
           if (Date.now() - lastDomDropAtRef.current < 200) return;
 
+          const droppedTextPath = event.payload.paths
+            .map((path) => extractDroppedTextPath(path))
+            .find((path): path is string => Boolean(path));
+          if (droppedTextPath && onTextFileDropRef.current) {
+            onTextFileDropRef.current(droppedTextPath);
+            return;
+          }
+
+          if (!onImageDropPathRef.current) return;
+
           const droppedPath = event.payload.paths
             .map((path) => extractDroppedImagePath(path))
             .find((path): path is string => Boolean(path));
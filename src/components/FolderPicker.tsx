@@ -1,6 +1,8 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { getFolderColor } from "@/utils/folderColors";
+import type { FolderBadge, FolderChange } from "@/types";
 
 interface FolderPickerProps {
   query: string;
@@ -16,11 +18,24 @@ export default function FolderPicker({
   folderColors = {},
 }: FolderPickerProps) {
   const [folders, setFolders] = useState<string[]>([]);
+  const [badges, setBadges] = useState<Record<string, FolderBadge>>({});
   const [selectedIndex, setSelectedIndex] = useState(0);
 
-  // Load folders on mount
+  // Load folders on mount and whenever they change on disk
   useEffect(() => {
-    invoke<string[]>("list_folders").then(setFolders);
+    const load = () => {
+      invoke<string[]>("list_folders").then(setFolders);
+      invoke<FolderBadge[]>("get_folder_badges")
+        .then((list) =>
+          setBadges(Object.fromEntries(list.map((b) => [b.name, b]))),
+        )
+        .catch(() => {});
+    };
+    load();
+    const unlisten = listen<FolderChange[]>("folders-changed", load);
+    return () => {
+      unlisten.then((fn) => fn());
+    };
   }, []);
 
   // Filter folders based on query
@@ -84,6 +99,22 @@ export default function FolderPicker({
                 ●
               </span>
               <span className="flex-1 text-[13px] font-medium">{folder}</span>
+              {(badges[folder]?.unsynced_count ?? 0) > 0 && (
+                <span
+                  title="Not synced yet"
+                  className={`text-[10px] ${i === selectedIndex ? "text-white/80" : "text-coral"}`}
+                >
+                  ↑{badges[folder].unsynced_count}
+                </span>
+              )}
+              {(badges[folder]?.sticked_count ?? 0) > 0 && (
+                <span
+                  title="Sticked notes"
+                  className={`text-[10px] ${i === selectedIndex ? "text-white/80" : "text-stone"}`}
+                >
+                  ◆{badges[folder].sticked_count}
+                </span>
+              )}
               {i === selectedIndex && (
                 <kbd className="text-[9px] px-1.5 py-0.5 bg-white/20 rounded text-white/90 font-mono">
                   enter
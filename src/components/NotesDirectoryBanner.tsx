@@ -0,0 +1,88 @@
+import { useCallback, useEffect, useState } from "react";
+import { emit, listen } from "@tauri-apps/api/event";
+import { invoke } from "@tauri-apps/api/core";
+import type { NotesDirectoryStatus, StikSettings } from "@/types";
+
+const UNAVAILABLE_PREFIX = "NOTES_DIR_UNAVAILABLE";
+
+/** Shown while the configured notes directory is missing or read-only. */
+export default function NotesDirectoryBanner() {
+  const [error, setError] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    // The startup event can fire before this window listens, so ask too.
+    invoke<string>("get_notes_directory").catch((err) => {
+      const message = String(err);
+      if (message.startsWith(UNAVAILABLE_PREFIX)) setError(message);
+    });
+
+    const unlisten = listen<NotesDirectoryStatus>(
+      "notes-directory-unavailable",
+      (event) => setError(event.payload.error),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const recheck = useCallback(async () => {
+    const status = await invoke<NotesDirectoryStatus>("recheck_notes_directory");
+    setError(status.available ? null : status.error);
+  }, []);
+
+  const handleRetry = useCallback(async () => {
+    setBusy(true);
+    try {
+      await recheck();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }, [recheck]);
+
+  const handleUseDefault = useCallback(async () => {
+    setBusy(true);
+    try {
+      const settings = await invoke<StikSettings>("get_settings");
+      const updated = { ...settings, notes_directory: "" };
+      await invoke("save_settings", { settings: updated });
+      await emit("settings-changed", updated);
+      await recheck();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  }, [recheck]);
+
+  if (!error) return null;
+
+  return (
+    <div className="fixed top-2 left-2 right-2 z-40 p-3 bg-coral-light border border-coral/20 rounded-xl shadow-stik">
+      <p className="text-[12px] text-ink font-medium">Notes folder unavailable</p>
+      <p className="mt-0.5 text-[11px] text-stone leading-relaxed break-all">
+        {error.replace(`${UNAVAILABLE_PREFIX}: `, "")}
+      </p>
+      <div className="mt-2 flex gap-2">
+        <button
+          type="button"
+          disabled={busy}
+          onClick={() => void handleRetry()}
+          className="px-2.5 py-1 text-[11px] font-medium bg-coral text-white rounded-md disabled:opacity-50"
+        >
+          Retry
+        </button>
+        <button
+          type="button"
+          disabled={busy}
+          onClick={() => void handleUseDefault()}
+          className="px-2.5 py-1 text-[11px] font-medium text-stone hover:text-ink hover:bg-line rounded-md disabled:opacity-50"
+        >
+          Switch to default
+        </button>
+      </div>
+    </div>
+  );
+}
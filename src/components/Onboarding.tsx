@@ -0,0 +1,138 @@
+import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import ShortcutRecorder from "./ShortcutRecorder";
+import type { OnboardingChoices } from "@/types";
+
+const STARTER_FOLDERS = ["Work", "Ideas", "Personal"];
+const DEFAULT_CAPTURE_SHORTCUT = "CommandOrControl+Shift+S";
+
+interface OnboardingProps {
+  systemShortcuts: string[];
+  onComplete: (defaultFolder: string) => void;
+}
+
+/**
+ * First-launch welcome. Folder shortcuts stay unregistered until this
+ * completes, so the capture shortcut picked here can't collide with them.
+ */
+export default function Onboarding({
+  systemShortcuts,
+  onComplete,
+}: OnboardingProps) {
+  const [defaultFolder, setDefaultFolder] = useState("Inbox");
+  const [starterFolders, setStarterFolders] = useState<string[]>(STARTER_FOLDERS);
+  const [analyticsEnabled, setAnalyticsEnabled] = useState(true);
+  const [captureShortcut, setCaptureShortcut] = useState(DEFAULT_CAPTURE_SHORTCUT);
+  const [error, setError] = useState<string | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  const toggleStarter = (folder: string) => {
+    setStarterFolders((current) =>
+      current.includes(folder)
+        ? current.filter((f) => f !== folder)
+        : [...current, folder],
+    );
+  };
+
+  const handleStart = async () => {
+    setSaving(true);
+    setError(null);
+    const choices: OnboardingChoices = {
+      default_folder: defaultFolder.trim(),
+      starter_folders: starterFolders,
+      analytics_enabled: analyticsEnabled,
+      capture_shortcut: captureShortcut,
+    };
+    try {
+      await invoke("complete_onboarding", { choices });
+      await invoke("reload_shortcuts");
+      onComplete(choices.default_folder);
+    } catch (err) {
+      setError(String(err));
+      setSaving(false);
+    }
+  };
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/40 backdrop-blur-[2px]">
+      <div className="bg-bg rounded-[14px] w-[380px] shadow-stik flex flex-col overflow-hidden">
+        <div className="px-5 pt-5 pb-0">
+          <h2 className="text-[14px] font-semibold text-ink mb-2.5">
+            Welcome to Stik
+          </h2>
+          <p className="text-[12px] text-stone leading-relaxed">
+            Quick thoughts land in your default folder. You can change all of
+            this later in Settings.
+          </p>
+        </div>
+
+        <div className="mx-5 my-3.5 border-t border-line" />
+
+        <div className="px-5 flex flex-col gap-3">
+          <label className="flex flex-col gap-1.5">
+            <span className="text-[12px] font-medium text-ink">Default folder</span>
+            <input
+              type="text"
+              value={defaultFolder}
+              onChange={(e) => setDefaultFolder(e.target.value)}
+              className="px-2.5 py-1.5 text-[12px] bg-line/40 rounded-md outline-none text-ink"
+            />
+          </label>
+
+          <div className="flex flex-col gap-1.5">
+            <span className="text-[12px] font-medium text-ink">Also create</span>
+            <div className="flex items-center gap-2">
+              {STARTER_FOLDERS.map((folder) => (
+                <button
+                  key={folder}
+                  type="button"
+                  onClick={() => toggleStarter(folder)}
+                  className={`px-2.5 py-1 text-[11px] font-medium rounded-md transition-colors ${
+                    starterFolders.includes(folder)
+                      ? "bg-coral/10 text-coral"
+                      : "text-stone hover:bg-line"
+                  }`}
+                >
+                  {folder}
+                </button>
+              ))}
+            </div>
+          </div>
+
+          <div className="flex flex-col gap-1.5">
+            <span className="text-[12px] font-medium text-ink">Capture shortcut</span>
+            <ShortcutRecorder
+              value={captureShortcut}
+              onChange={setCaptureShortcut}
+              reservedShortcuts={systemShortcuts}
+            />
+          </div>
+
+          <label className="flex items-center gap-2 text-[12px] text-stone">
+            <input
+              type="checkbox"
+              checked={analyticsEnabled}
+              onChange={(e) => setAnalyticsEnabled(e.target.checked)}
+            />
+            Share anonymous usage analytics (never your notes)
+          </label>
+
+          {error && <p className="text-[11px] text-coral">{error}</p>}
+        </div>
+
+        <div className="mx-5 my-3.5 border-t border-line" />
+
+        <div className="px-5 pb-5">
+          <button
+            type="button"
+            onClick={() => void handleStart()}
+            disabled={saving || !defaultFolder.trim()}
+            className="w-full py-2.5 text-[13px] font-semibold text-white bg-coral rounded-full hover:bg-coral-dark transition-colors shadow-coral-sm disabled:opacity-50"
+          >
+            Start writing
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,59 @@
+import { useCallback, useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { invoke } from "@tauri-apps/api/core";
+import type { ConfigLocation } from "@/types";
+
+/** Shown while portable mode is on but its folder's drive isn't connected. */
+export default function PortableConfigBanner() {
+  const [dir, setDir] = useState<string | null>(null);
+  const [busy, setBusy] = useState(false);
+
+  const recheck = useCallback(async () => {
+    const location = await invoke<ConfigLocation>("get_config_location");
+    setDir(location.kind === "unavailable" ? location.dir : null);
+  }, []);
+
+  useEffect(() => {
+    // The startup event can fire before this window listens, so ask too.
+    recheck().catch(() => {});
+
+    const unlisten = listen<string>("portable-config-unavailable", (event) =>
+      setDir(event.payload),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [recheck]);
+
+  const handleRetry = useCallback(async () => {
+    setBusy(true);
+    try {
+      await recheck();
+    } catch (err) {
+      console.error("Failed to check portable config:", err);
+    } finally {
+      setBusy(false);
+    }
+  }, [recheck]);
+
+  if (!dir) return null;
+
+  return (
+    <div className="fixed bottom-2 left-2 right-2 z-40 p-3 bg-coral-light border border-coral/20 rounded-xl shadow-stik">
+      <p className="text-[12px] text-ink font-medium">Notes drive not connected</p>
+      <p className="mt-0.5 text-[11px] text-stone leading-relaxed break-all">
+        Settings and state are read-only until {dir} is back.
+      </p>
+      <div className="mt-2 flex gap-2">
+        <button
+          type="button"
+          disabled={busy}
+          onClick={() => void handleRetry()}
+          className="px-2.5 py-1 text-[11px] font-medium bg-coral text-white rounded-md disabled:opacity-50"
+        >
+          Retry
+        </button>
+      </div>
+    </div>
+  );
+}
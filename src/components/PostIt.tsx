@@ -8,11 +8,19 @@ import {
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { listen } from "@tauri-apps/api/event";
 import { invoke, convertFileSrc } from "@tauri-apps/api/core";
+import { ask, save } from "@tauri-apps/plugin-dialog";
 import Editor, { type EditorRef } from "./Editor";
 import FolderPicker from "./FolderPicker";
 import AiMenu from "./AiMenu";
 import SpeechButton from "./SpeechButton";
-import type { StickedNote, StikSettings } from "@/types";
+import type {
+  CaptureDraft,
+  FolderChange,
+  NoteDeleted,
+  NoteSaved,
+  StickedNote,
+  StikSettings,
+} from "@/types";
 import type { VimMode } from "@/extensions/cm-vim";
 import {
   getSlashCommandNames,
@@ -25,13 +33,16 @@ import {
 import { shouldSaveOnGlobalEscape } from "@/utils/captureEscape";
 import { isCaptureSlashQuery } from "@/utils/slashQuery";
 import { markdownToPlainText } from "@/utils/markdownToHtml";
+import { renderElementToPngBase64 } from "@/utils/canvasSnapshot";
 import { shouldOpenVimCommandBar } from "@/utils/vimCommandKey";
+import { matchesShortcut } from "@/utils/systemShortcuts";
 import {
   resolveImagePaths,
   unresolveImagePaths,
 } from "@/utils/imageMarkdownPaths";
 import { resolveCaptureFolder } from "@/utils/folderSelection";
 import { getFolderColor } from "@/utils/folderColors";
+import { formatRelativeDate } from "@/utils/formatRelativeDate";
 import { formatShortcutDisplay } from "./ShortcutRecorder";
 import { loadGoogleFont, loadCustomFont } from "@/utils/fonts";
 import SyncIndicator from "./SyncIndicator";
@@ -51,6 +62,9 @@ interface PostItProps {
   initialContent?: string;
   isViewing?: boolean;
   originalPath?: string; // For viewing notes - the original file path to update
+  readOnly?: boolean; // Edit-locked viewing note: no edits, nothing to save
+  modifiedAt?: string; // Viewing note's mtime, YYYYMMDD-HHMMSS
+  templateOrigin?: string; // Sticky created from a template: its name
 }
 
 function fallbackHtmlFromPlainText(text: string): string {
@@ -104,6 +118,9 @@ export default function PostIt({
   initialContent = "",
   isViewing = false,
   originalPath,
+  readOnly = false,
+  modifiedAt,
+  templateOrigin,
 }: PostItProps) {
   const [content, setContent] = useState(initialContent || "");
   const [showPicker, setShowPicker] = useState(false);
@@ -136,7 +153,6 @@ export default function PostIt({
     "auto",
   );
   const [icloudEnabled, setIcloudEnabled] = useState(false);
-  const [zenMode, setZenMode] = useState(false);
   const [dictationActiveModel, setDictationActiveModel] = useState<
     string | null
   >(null);
@@ -312,8 +328,16 @@ export default function PostIt({
       setDictationActiveModel(event.payload.dictation?.active_model ?? null);
       setDictationLanguage(event.payload.dictation?.active_language ?? null);
     });
+    const unlistenFolders = listen<FolderChange[]>("folders-changed", () => {
+      invoke<string[]>("list_folders")
+        .then((f) => {
+          foldersRef.current = f;
+        })
+        .catch(() => {});
+    });
     return () => {
       unlisten.then((fn) => fn());
+      unlistenFolders.then((fn) => fn());
     };
   }, []);
 
@@ -321,20 +345,102 @@ export default function PostIt({
   useEffect(() => {
     if (!isViewing || !originalPath) return;
 
-    const unlisten = listen<string>("note-deleted", (event) => {
-      if (event.payload === originalPath) {
+    const closeIfDeleted = (deleted: NoteDeleted[]) => {
+      if (deleted.some((note) => note.path === originalPath)) {
         const idToClose = currentStickedId || stickedId;
         if (idToClose) {
           invoke("close_sticked_window", { id: idToClose });
         }
       }
-    });
+    };
+    const unlisten = listen<NoteDeleted>("note-deleted", (event) =>
+      closeIfDeleted([event.payload]),
+    );
+    const unlistenBatch = listen<NoteDeleted[]>("notes-deleted", (event) =>
+      closeIfDeleted(event.payload),
+    );
 
     return () => {
       unlisten.then((fn) => fn());
+      unlistenBatch.then((fn) => fn());
     };
   }, [isViewing, originalPath, currentStickedId, stickedId]);
 
+  // Tell the backend when a viewing window has unsaved edits, so external
+  // changes to the file arrive as a conflict instead of replacing them.
+  const viewingBaselineRef = useRef<string | null>(null);
+  const viewingDirtyRef = useRef(false);
+  useEffect(() => {
+    const viewingId = currentStickedId || stickedId;
+    if (!isViewing || !viewingId) return;
+    if (viewingBaselineRef.current === null) {
+      if (content) viewingBaselineRef.current = content;
+      return;
+    }
+    const dirty = content !== viewingBaselineRef.current;
+    if (dirty === viewingDirtyRef.current) return;
+    viewingDirtyRef.current = dirty;
+    invoke("set_viewing_note_dirty", { id: viewingId, dirty }).catch(() => {});
+  }, [content, isViewing, currentStickedId, stickedId]);
+
+  // Apply external edits to the file backing this viewing window
+  useEffect(() => {
+    const viewingId = currentStickedId || stickedId;
+    if (!isViewing || !viewingId) return;
+
+    const applyDiskContent = (diskContent: string) => {
+      viewingBaselineRef.current = diskContent;
+      viewingDirtyRef.current = false;
+      setContent(diskContent);
+      editorRef.current?.setContent(
+        notesDir
+          ? resolveImagePaths(
+              diskContent,
+              `${notesDir}/${folder}`,
+              convertFileSrc,
+            )
+          : diskContent,
+      );
+    };
+
+    type ViewingNoteChange = { id: string; path: string; content: string };
+    const unlistenUpdated = listen<ViewingNoteChange>(
+      "viewing-note-updated",
+      (event) => {
+        if (event.payload.id === viewingId) {
+          applyDiskContent(event.payload.content);
+        }
+      },
+    );
+    const unlistenConflict = listen<ViewingNoteChange>(
+      "viewing-note-conflict",
+      async (event) => {
+        if (event.payload.id !== viewingId) return;
+        const takeTheirs = await ask(
+          "This note was changed outside Stik while you were editing it.",
+          {
+            title: "Note changed on disk",
+            kind: "warning",
+            okLabel: "Take Theirs",
+            cancelLabel: "Keep Mine",
+          },
+        );
+        if (takeTheirs) {
+          applyDiskContent(event.payload.content);
+          invoke("set_viewing_note_dirty", {
+            id: viewingId,
+            dirty: false,
+          }).catch(() => {});
+        }
+      },
+    );
+
+    return () => {
+      unlistenUpdated.then((fn) => fn());
+      unlistenConflict.then((fn) => fn());
+    };
+  }, [isViewing, currentStickedId, stickedId, notesDir, folder]);
+
   // Focus editor on mount, when folder changes, or when editor becomes available after settings load
   useEffect(() => {
     if (vimEnabled === null) return; // editor not mounted yet
@@ -492,6 +598,48 @@ export default function PostIt({
     };
   }, [isSticked, notesDir, onFolderChange]);
 
+  // A blur under the save/keep_draft policies hands the buffer to the
+  // backend and clears it here; a stashed draft comes back with the next
+  // capture session (or the next launch).
+  useEffect(() => {
+    if (isSticked) return;
+
+    const restoreDraft = () => {
+      if (!isMarkdownEffectivelyEmpty(contentRef.current)) return;
+      invoke<CaptureDraft | null>("take_capture_draft")
+        .then((draft) => {
+          if (!draft) return;
+          setContent(draft.content);
+          const resolvedContent = notesDir
+            ? resolveImagePaths(
+                draft.content,
+                `${notesDir}/${draft.folder}`,
+                convertFileSrc,
+              )
+            : draft.content;
+          setTimeout(() => {
+            editorRef.current?.setContent(resolvedContent);
+            editorRef.current?.moveToEnd?.();
+          }, 100);
+        })
+        .catch(() => {});
+    };
+    restoreDraft();
+
+    const unlistenTriggered = listen("shortcut-triggered", restoreDraft);
+    const unlistenCleared = listen("capture-cleared", () => {
+      setContent("");
+      onContentChange?.("");
+      editorRef.current?.clear();
+      contentRef.current = "";
+    });
+
+    return () => {
+      unlistenTriggered.then((fn) => fn());
+      unlistenCleared.then((fn) => fn());
+    };
+  }, [isSticked, notesDir, onContentChange]);
+
   // Slash-query state is cleared on new sessions via shortcut-triggered
   // and on save via handleSaveAndClose. No separate postit-blur listener
   // needed — it caused a race where a delayed blur during reopen would
@@ -654,35 +802,39 @@ export default function PostIt({
     handleSaveAndClose,
   ]);
 
-  // Zen mode shortcut (reads from settings, defaults to Cmd+.)
+  // Zen mode: the backend asks for the capture content, then opens the
+  // zen window with it and hides this one.
   useEffect(() => {
-    const shortcutStr = systemShortcuts.zen_mode || "Cmd+Period";
-    const handleZenToggle = (e: KeyboardEvent) => {
-      const parts = shortcutStr.split("+");
-      const key = parts[parts.length - 1];
-      const needsMeta = parts.some(
-        (p) => p === "Cmd" || p === "Command" || p === "Meta",
-      );
-      const needsShift = parts.some((p) => p === "Shift");
-      const needsAlt = parts.some((p) => p === "Alt" || p === "Option");
-      const needsCtrl = parts.some((p) => p === "Ctrl" || p === "Control");
+    if (isSticked) return;
 
-      if (needsMeta !== e.metaKey) return;
-      if (needsShift !== e.shiftKey) return;
-      if (needsAlt !== e.altKey) return;
-      if (needsCtrl !== e.ctrlKey) return;
+    const unlisten = listen("zen-enter-requested", () => {
+      invoke("enter_zen_window", {
+        content: contentRef.current,
+        folder,
+      }).catch((err) => console.error("Failed to enter zen mode:", err));
+    });
 
-      // Match the key portion
-      const eventKey =
-        e.key === "." ? "Period" : e.key === "," ? "Comma" : e.key;
-      if (eventKey.toLowerCase() !== key.toLowerCase()) return;
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [isSticked, folder]);
 
+  // Zen mode shortcut (reads from settings, defaults to Cmd+.). In-app
+  // only, so the system-wide Cmd+. keeps working in other apps.
+  useEffect(() => {
+    if (isSticked) return;
+
+    const shortcutStr = systemShortcuts.zen_mode || "Cmd+Period";
+    const handleZenToggle = (e: KeyboardEvent) => {
+      if (!matchesShortcut(e, shortcutStr)) return;
       e.preventDefault();
-      setZenMode((prev) => !prev);
+      invoke("toggle_zen_window").catch((err) =>
+        console.error("Failed to enter zen mode:", err),
+      );
     };
     window.addEventListener("keydown", handleZenToggle);
     return () => window.removeEventListener("keydown", handleZenToggle);
-  }, [systemShortcuts.zen_mode]);
+  }, [isSticked, systemShortcuts.zen_mode]);
 
   // Dictation shortcut (reads from settings, defaults to Cmd+Shift+D)
   useEffect(() => {
@@ -870,7 +1022,13 @@ export default function PostIt({
                 requestAnimationFrame(() => resolve()),
               );
             });
-            await invoke("copy_visible_note_image_to_clipboard");
+            const outcome = await invoke<string>(
+              "copy_visible_note_image_to_clipboard",
+            );
+            if (outcome === "needs_canvas_snapshot") {
+              const pngBase64 = await renderElementToPngBase64(document.body);
+              await invoke("copy_note_image_to_clipboard", { pngBase64 });
+            }
             showToast("Copied as image");
           } finally {
             document.documentElement.classList.remove("capturing-image");
@@ -898,6 +1056,61 @@ export default function PostIt({
     [content, folder, isCopying, copyPlainText, showToast],
   );
 
+  const handleSaveImage = useCallback(async () => {
+    setIsCopyMenuOpen(false);
+    if (isCopying) return;
+    if (isMarkdownEffectivelyEmpty(content)) {
+      showToast("Nothing to save");
+      return;
+    }
+
+    const title =
+      content
+        .split("\n")[0]
+        .replace(/^#+\s*/, "")
+        .replace(/[^\p{L}\p{N} _-]/gu, "")
+        .trim()
+        .slice(0, 60) || "Note";
+    const outputPath = await save({
+      title: "Save as image",
+      defaultPath: `${title}.png`,
+      filters: [{ name: "PNG", extensions: ["png"] }],
+    });
+    if (!outputPath) return;
+
+    flushSync(() => {
+      setIsCopying(true);
+      setCopyMode("image");
+    });
+    document.documentElement.classList.add("capturing-image");
+    try {
+      await new Promise<void>((resolve) => {
+        requestAnimationFrame(() => requestAnimationFrame(() => resolve()));
+      });
+      const windowLabel = getCurrentWindow().label;
+      try {
+        await invoke("save_note_image_snapshot", { windowLabel, outputPath });
+      } catch (error) {
+        // No native capture off macOS: send a canvas snapshot instead.
+        if (!String(error).startsWith("No native capture")) throw error;
+        const pngBase64 = await renderElementToPngBase64(document.body);
+        await invoke("save_note_image_snapshot", {
+          windowLabel,
+          outputPath,
+          pngBase64,
+        });
+      }
+      showToast("Saved as image");
+    } catch (error) {
+      console.error("Failed to save note image:", error);
+      showToast("Save failed");
+    } finally {
+      document.documentElement.classList.remove("capturing-image");
+      setIsCopying(false);
+      setCopyMode(null);
+    }
+  }, [content, isCopying, showToast]);
+
   const hasMeaningfulContent = !isMarkdownEffectivelyEmpty(content);
   const hasValidFolder = folder.trim().length > 0;
   // Pin from capture mode
@@ -1003,11 +1216,14 @@ export default function PostIt({
             saveToFolder: true,
           });
         } else if (isViewing && originalPath) {
-          // Viewing note - update the existing file
-          await invoke("update_note", {
-            path: originalPath,
-            content: currentContent,
-          });
+          // Viewing note - update the existing file (edit-locked notes
+          // can't have changed)
+          if (!readOnly) {
+            await invoke("update_note", {
+              path: originalPath,
+              content: currentContent,
+            });
+          }
           savedNotePath = originalPath;
         } else {
           // If unpinned (not viewing), save as new file
@@ -1500,6 +1716,30 @@ export default function PostIt({
     [folder],
   );
 
+  // Dropped .txt/.csv/.log files become their own note, opened for viewing
+  const handleTextFileDrop = useCallback(
+    async (path: string) => {
+      try {
+        const saved = await invoke<NoteSaved>("import_text_file", {
+          folder,
+          filePath: path,
+        });
+        const noteContent = await invoke<string>("get_note_content", {
+          path: saved.path,
+        });
+        await invoke("open_note_for_viewing", {
+          content: noteContent,
+          folder: saved.folder,
+          path: saved.path,
+        });
+      } catch (err) {
+        console.error("Failed to import dropped file:", err);
+        showToast(String(err));
+      }
+    },
+    [folder, showToast],
+  );
+
   // Show save animation
   if (isSaving) {
     return (
@@ -1541,7 +1781,7 @@ export default function PostIt({
       <div
         className={`w-full h-full rounded-[14px] overflow-hidden flex flex-col ${
           isSticked && isPinned ? "sticked-note" : ""
-        } ${zenMode ? "zen-mode" : ""}`}
+        }`}
         style={{ backgroundColor: `rgb(var(--color-bg) / ${windowOpacity})` }}
       >
         {/* Header - draggable */}
@@ -1551,297 +1791,333 @@ export default function PostIt({
             isSticked && isPinned ? "sticked-header" : ""
           }`}
         >
-          {!zenMode && (
-            <>
-              <div className="flex items-center gap-2">
-                {/* Pin button */}
-                {!isSticked ? (
-                  // Capture mode: pin to create sticked note
-                  <button
-                    data-capture-hide
-                    onClick={handlePin}
-                    disabled={!hasMeaningfulContent || isPinning}
-                    className={`w-6 h-6 flex items-center justify-center rounded-md transition-colors ${
-                      hasMeaningfulContent
-                        ? "hover:bg-coral-light text-coral hover:text-coral"
-                        : "text-stone/50 cursor-not-allowed"
-                    }`}
-                    title="Pin to screen"
-                  >
-                    <svg
-                      width="14"
-                      height="14"
-                      viewBox="0 0 24 24"
-                      fill="none"
-                      stroke="currentColor"
-                      strokeWidth="2"
-                      strokeLinecap="round"
-                      strokeLinejoin="round"
-                    >
-                      <line x1="12" y1="17" x2="12" y2="22" />
-                      <path d="M5 17h14v-1.76a2 2 0 0 0-1.11-1.79l-1.78-.9A2 2 0 0 1 15 10.76V6h1a2 2 0 0 0 0-4H8a2 2 0 0 0 0 4h1v4.76a2 2 0 0 1-1.11 1.79l-1.78.9A2 2 0 0 0 5 15.24Z" />
-                    </svg>
-                  </button>
-                ) : (
-                  // Sticked mode: toggle pin state
-                  <button
-                    data-capture-hide
-                    onClick={handleTogglePin}
-                    className={`w-6 h-6 flex items-center justify-center rounded-md transition-colors ${
-                      isPinned
-                        ? "text-coral hover:bg-coral-light"
-                        : "text-stone hover:bg-line hover:text-coral"
-                    }`}
-                    title={
-                      isPinned
-                        ? "Unpin (won't restore on restart)"
-                        : "Pin (will restore on restart)"
-                    }
-                  >
-                    <svg
-                      width="14"
-                      height="14"
-                      viewBox="0 0 24 24"
-                      fill={isPinned ? "currentColor" : "none"}
-                      stroke="currentColor"
-                      strokeWidth="2"
-                      strokeLinecap="round"
-                      strokeLinejoin="round"
-                    >
-                      <line x1="12" y1="17" x2="12" y2="22" />
-                      <path d="M5 17h14v-1.76a2 2 0 0 0-1.11-1.79l-1.78-.9A2 2 0 0 1 15 10.76V6h1a2 2 0 0 0 0-4H8a2 2 0 0 0 0 4h1v4.76a2 2 0 0 1-1.11 1.79l-1.78.9A2 2 0 0 0 5 15.24Z" />
-                    </svg>
-                  </button>
-                )}
+          <div className="flex items-center gap-2">
+            {/* Pin button */}
+            {!isSticked ? (
+              // Capture mode: pin to create sticked note
+              <button
+                data-capture-hide
+                onClick={handlePin}
+                disabled={!hasMeaningfulContent || isPinning}
+                className={`w-6 h-6 flex items-center justify-center rounded-md transition-colors ${
+                  hasMeaningfulContent
+                    ? "hover:bg-coral-light text-coral hover:text-coral"
+                    : "text-stone/50 cursor-not-allowed"
+                }`}
+                title="Pin to screen"
+              >
+                <svg
+                  width="14"
+                  height="14"
+                  viewBox="0 0 24 24"
+                  fill="none"
+                  stroke="currentColor"
+                  strokeWidth="2"
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                >
+                  <line x1="12" y1="17" x2="12" y2="22" />
+                  <path d="M5 17h14v-1.76a2 2 0 0 0-1.11-1.79l-1.78-.9A2 2 0 0 1 15 10.76V6h1a2 2 0 0 0 0-4H8a2 2 0 0 0 0 4h1v4.76a2 2 0 0 1-1.11 1.79l-1.78.9A2 2 0 0 0 5 15.24Z" />
+                </svg>
+              </button>
+            ) : (
+              // Sticked mode: toggle pin state
+              <button
+                data-capture-hide
+                onClick={handleTogglePin}
+                className={`w-6 h-6 flex items-center justify-center rounded-md transition-colors ${
+                  isPinned
+                    ? "text-coral hover:bg-coral-light"
+                    : "text-stone hover:bg-line hover:text-coral"
+                }`}
+                title={
+                  isPinned
+                    ? "Unpin (won't restore on restart)"
+                    : "Pin (will restore on restart)"
+                }
+              >
+                <svg
+                  width="14"
+                  height="14"
+                  viewBox="0 0 24 24"
+                  fill={isPinned ? "currentColor" : "none"}
+                  stroke="currentColor"
+                  strokeWidth="2"
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                >
+                  <line x1="12" y1="17" x2="12" y2="22" />
+                  <path d="M5 17h14v-1.76a2 2 0 0 0-1.11-1.79l-1.78-.9A2 2 0 0 1 15 10.76V6h1a2 2 0 0 0 0-4H8a2 2 0 0 0 0 4h1v4.76a2 2 0 0 1-1.11 1.79l-1.78.9A2 2 0 0 0 5 15.24Z" />
+                </svg>
+              </button>
+            )}
+
+            <button
+              onClick={() => setShowPicker(!showPicker)}
+              className={`flex items-center gap-1.5 px-2.5 py-1 rounded-pill text-[11px] font-semibold transition-colors hover:opacity-80 ${
+                hasValidFolder
+                  ? `${getFolderColor(folder, folderColors).badgeBg} ${getFolderColor(folder, folderColors).badgeText}`
+                  : "bg-line text-stone"
+              }`}
+            >
+              <span
+                className="text-[8px]"
+                style={{ color: getFolderColor(folder, folderColors).dot }}
+              >
+                ●
+              </span>
+              <span>{folder || "Stik"}</span>
+              <span className="text-[8px] opacity-50">▼</span>
+            </button>
+
+            {readOnly && (
+              <span
+                className="flex items-center gap-1 px-2 py-0.5 rounded-pill bg-line text-stone text-[10px] font-semibold"
+                title="Read-only. Select it in the command palette and press ⌘E to allow edits."
+              >
+                <svg
+                  width="10"
+                  height="10"
+                  viewBox="0 0 24 24"
+                  fill="none"
+                  stroke="currentColor"
+                  strokeWidth="2.5"
+                  strokeLinecap="round"
+                  strokeLinejoin="round"
+                >
+                  <rect x="5" y="11" width="14" height="10" rx="2" />
+                  <path d="M8 11V7a4 4 0 0 1 8 0v4" />
+                </svg>
+                Read-only
+              </span>
+            )}
 
+            {isViewing && modifiedAt && (
+              <span className="text-[10px] text-stone">
+                Edited {formatRelativeDate(modifiedAt)}
+              </span>
+            )}
+
+            {isSticked && !isViewing && templateOrigin && (
+              <span className="text-[10px] text-stone truncate max-w-[120px]">
+                From {templateOrigin}
+              </span>
+            )}
+
+            {suggestedFolder && (
+              <button
+                data-capture-hide
+                onClick={() => {
+                  onFolderChange(suggestedFolder);
+                  setSuggestedFolder(null);
+                }}
+                className="flex items-center gap-1 px-2 py-0.5 rounded-pill text-[10px] font-medium bg-coral/10 text-coral hover:bg-coral/20 transition-colors"
+              >
+                <span>→</span>
+                <span>{suggestedFolder}?</span>
+              </button>
+            )}
+          </div>
+
+          <div
+            data-capture-hide
+            className="flex items-center gap-3 text-[10px] text-stone"
+          >
+            <div className="relative" ref={copyMenuRef}>
+              {!(isCopying && copyMode === "image") && (
                 <button
-                  onClick={() => setShowPicker(!showPicker)}
-                  className={`flex items-center gap-1.5 px-2.5 py-1 rounded-pill text-[11px] font-semibold transition-colors hover:opacity-80 ${
-                    hasValidFolder
-                      ? `${getFolderColor(folder, folderColors).badgeBg} ${getFolderColor(folder, folderColors).badgeText}`
-                      : "bg-line text-stone"
+                  onClick={() => setIsCopyMenuOpen((open) => !open)}
+                  className={`p-1 rounded-md transition-colors ${
+                    isCopyMenuOpen
+                      ? "text-coral bg-coral-light"
+                      : "text-stone hover:bg-line hover:text-ink"
                   }`}
+                  title="Actions"
                 >
-                  <span
-                    className="text-[8px]"
-                    style={{ color: getFolderColor(folder, folderColors).dot }}
+                  <svg
+                    width="14"
+                    height="14"
+                    viewBox="0 0 14 14"
+                    fill="none"
+                    xmlns="http://www.w3.org/2000/svg"
                   >
-                    ●
-                  </span>
-                  <span>{folder || "Stik"}</span>
-                  <span className="text-[8px] opacity-50">▼</span>
+                    <circle cx="7" cy="3" r="1.2" fill="currentColor" />
+                    <circle cx="7" cy="7" r="1.2" fill="currentColor" />
+                    <circle cx="7" cy="11" r="1.2" fill="currentColor" />
+                  </svg>
                 </button>
+              )}
 
-                {suggestedFolder && (
+              {isCopyMenuOpen && (
+                <div className="absolute top-full right-0 mt-1 w-40 rounded-lg border border-line bg-bg shadow-stik overflow-hidden z-[240]">
                   <button
-                    data-capture-hide
-                    onClick={() => {
-                      onFolderChange(suggestedFolder);
-                      setSuggestedFolder(null);
-                    }}
-                    className="flex items-center gap-1 px-2 py-0.5 rounded-pill text-[10px] font-medium bg-coral/10 text-coral hover:bg-coral/20 transition-colors"
+                    onClick={() => void handleCopy("rich")}
+                    className="w-full px-3 py-2 text-left text-[11px] text-ink hover:bg-line/50 transition-colors"
                   >
-                    <span>→</span>
-                    <span>{suggestedFolder}?</span>
+                    Copy as rich text
                   </button>
-                )}
-              </div>
-
-              <div
-                data-capture-hide
-                className="flex items-center gap-3 text-[10px] text-stone"
-              >
-                <div className="relative" ref={copyMenuRef}>
-                  {!(isCopying && copyMode === "image") && (
-                    <button
-                      onClick={() => setIsCopyMenuOpen((open) => !open)}
-                      className={`p-1 rounded-md transition-colors ${
-                        isCopyMenuOpen
-                          ? "text-coral bg-coral-light"
-                          : "text-stone hover:bg-line hover:text-ink"
-                      }`}
-                      title="Actions"
-                    >
-                      <svg
-                        width="14"
-                        height="14"
-                        viewBox="0 0 14 14"
-                        fill="none"
-                        xmlns="http://www.w3.org/2000/svg"
-                      >
-                        <circle cx="7" cy="3" r="1.2" fill="currentColor" />
-                        <circle cx="7" cy="7" r="1.2" fill="currentColor" />
-                        <circle cx="7" cy="11" r="1.2" fill="currentColor" />
-                      </svg>
-                    </button>
-                  )}
-
-                  {isCopyMenuOpen && (
-                    <div className="absolute top-full right-0 mt-1 w-40 rounded-lg border border-line bg-bg shadow-stik overflow-hidden z-[240]">
-                      <button
-                        onClick={() => void handleCopy("rich")}
-                        className="w-full px-3 py-2 text-left text-[11px] text-ink hover:bg-line/50 transition-colors"
-                      >
-                        Copy as rich text
-                      </button>
-                      <button
-                        onClick={() => void handleCopy("markdown")}
-                        className="w-full px-3 py-2 text-left text-[11px] text-ink hover:bg-line/50 transition-colors"
-                      >
-                        Copy as markdown
-                      </button>
-                      <button
-                        onClick={() => void handleCopy("image")}
-                        className="w-full px-3 py-2 text-left text-[11px] text-ink hover:bg-line/50 transition-colors"
-                      >
-                        Copy as image
-                      </button>
-                      <div className="border-t border-line" />
-                      <button
-                        onClick={async () => {
-                          setIsCopyMenuOpen(false);
-                          try {
-                            await invoke("show_apple_notes_picker_cmd");
-                          } catch (err) {
-                            console.error(
-                              "Failed to open Apple Notes picker:",
-                              err,
-                            );
-                          }
-                        }}
-                        className="w-full px-3 py-2 text-left text-[11px] text-ink hover:bg-line/50 transition-colors"
-                      >
-                        Import from Apple Notes
-                      </button>
-                    </div>
-                  )}
-                </div>
-
-                <SpeechButton
-                  ref={speechRef}
-                  activeModel={dictationActiveModel}
-                  language={dictationLanguage}
-                  onActiveModelSelected={async (modelId, lang) => {
-                    // Persist the modal choice into settings.json so
-                    // next launch / ⌘⇧V use the same model without
-                    // reprompting. Also update local state so the
-                    // current session reflects the choice immediately.
-                    try {
-                      const current =
-                        await invoke<StikSettings>("get_settings");
-                      const next: StikSettings = {
-                        ...current,
-                        dictation: {
-                          active_model: modelId,
-                          active_language: lang,
-                          enabled: current.dictation?.enabled ?? true,
-                        },
-                      };
-                      await invoke("save_settings", { settings: next });
-                      setDictationActiveModel(modelId);
-                      setDictationLanguage(lang);
-                      await getCurrentWindow().emit("settings-changed", next);
-                    } catch (e) {
-                      console.error("Failed to persist dictation choice:", e);
-                    }
-                  }}
-                  getInsertOrigin={() => {
-                    const view = editorRef.current?.getView();
-                    speechPartialLenRef.current = 0;
-                    return view ? view.state.selection.main.head : 0;
-                  }}
-                  onPartialText={(text, from) => {
-                    const view = editorRef.current?.getView();
-                    if (!view) return;
-                    // Replace only the previous partial insertion — not
-                    // everything to end-of-doc — so text after the cursor
-                    // is preserved when dictating mid-document.
-                    const to = Math.min(
-                      from + speechPartialLenRef.current,
-                      view.state.doc.length,
-                    );
-                    view.dispatch({
-                      changes: { from, to, insert: text },
-                      selection: { anchor: from + text.length },
-                    });
-                    speechPartialLenRef.current = text.length;
-                  }}
-                  onTranscription={(text, from) => {
-                    const view = editorRef.current?.getView();
-                    if (view) {
-                      const to = Math.min(
-                        from + speechPartialLenRef.current,
-                        view.state.doc.length,
-                      );
-                      view.dispatch({
-                        changes: { from, to, insert: text },
-                        selection: { anchor: from + text.length },
-                      });
-                      setContent(view.state.doc.toString());
-                    } else {
-                      setContent((prev) => prev + (prev ? " " : "") + text);
-                    }
-                    speechPartialLenRef.current = 0;
-                  }}
-                />
-
-                <AiMenu
-                  content={content}
-                  folder={folder}
-                  onApplyText={(text) => {
-                    editorRef.current?.setContent(text);
-                    setContent(text);
-                  }}
-                  onShowToast={(msg) => setToast(msg)}
-                  disabled={!hasMeaningfulContent}
-                />
-
-                {isSticked && isPinned ? (
-                  <div className="flex items-center gap-1.5">
-                    <button
-                      onClick={handleCloseWithoutSaving}
-                      className="px-2 py-1 rounded-md hover:bg-line text-stone hover:text-ink transition-colors text-[10px]"
-                      title="Close without saving"
-                    >
-                      Close
-                    </button>
-                    <button
-                      onClick={handleSaveAndCloseSticked}
-                      disabled={!hasMeaningfulContent}
-                      className={`px-2.5 py-1 rounded-md text-[10px] font-medium transition-colors ${
-                        hasMeaningfulContent
-                          ? "bg-coral text-white hover:bg-coral/90"
-                          : "bg-line text-stone cursor-not-allowed"
-                      }`}
-                      title={
-                        hasMeaningfulContent
-                          ? "Save to folder and close"
-                          : "Nothing to save"
-                      }
-                    >
-                      Save
-                    </button>
-                  </div>
-                ) : isSticked ? (
                   <button
-                    onClick={handleSaveAndCloseSticked}
-                    className="px-2.5 py-1.5 bg-coral-light text-coral rounded-lg text-[10px] font-semibold hover:bg-coral hover:text-white transition-colors cursor-pointer"
-                    title="Save and close (Esc)"
+                    onClick={() => void handleCopy("markdown")}
+                    className="w-full px-3 py-2 text-left text-[11px] text-ink hover:bg-line/50 transition-colors"
                   >
-                    esc
+                    Copy as markdown
                   </button>
-                ) : (
                   <button
-                    onClick={handleSaveAndClose}
-                    className="px-2.5 py-1.5 rounded-lg text-[10px] font-semibold transition-colors bg-coral-light text-coral hover:bg-coral hover:text-white cursor-pointer"
-                    title="Save and close (Esc)"
+                    onClick={() => void handleCopy("image")}
+                    className="w-full px-3 py-2 text-left text-[11px] text-ink hover:bg-line/50 transition-colors"
                   >
-                    esc
+                    Copy as image
                   </button>
-                )}
+                  <button
+                    onClick={() => void handleSaveImage()}
+                    className="w-full px-3 py-2 text-left text-[11px] text-ink hover:bg-line/50 transition-colors"
+                  >
+                    Save as image…
+                  </button>
+                  <div className="border-t border-line" />
+                  <button
+                    onClick={async () => {
+                      setIsCopyMenuOpen(false);
+                      try {
+                        await invoke("show_apple_notes_picker_cmd");
+                      } catch (err) {
+                        console.error(
+                          "Failed to open Apple Notes picker:",
+                          err,
+                        );
+                      }
+                    }}
+                    className="w-full px-3 py-2 text-left text-[11px] text-ink hover:bg-line/50 transition-colors"
+                  >
+                    Import from Apple Notes
+                  </button>
+                </div>
+              )}
+            </div>
+
+            <SpeechButton
+              ref={speechRef}
+              activeModel={dictationActiveModel}
+              language={dictationLanguage}
+              onActiveModelSelected={async (modelId, lang) => {
+                // Persist the modal choice into settings.json so
+                // next launch / ⌘⇧V use the same model without
+                // reprompting. Also update local state so the
+                // current session reflects the choice immediately.
+                try {
+                  const current =
+                    await invoke<StikSettings>("get_settings");
+                  const next: StikSettings = {
+                    ...current,
+                    dictation: {
+                      active_model: modelId,
+                      active_language: lang,
+                      enabled: current.dictation?.enabled ?? true,
+                    },
+                  };
+                  await invoke("save_settings", { settings: next });
+                  setDictationActiveModel(modelId);
+                  setDictationLanguage(lang);
+                  await getCurrentWindow().emit("settings-changed", next);
+                } catch (e) {
+                  console.error("Failed to persist dictation choice:", e);
+                }
+              }}
+              getInsertOrigin={() => {
+                const view = editorRef.current?.getView();
+                speechPartialLenRef.current = 0;
+                return view ? view.state.selection.main.head : 0;
+              }}
+              onPartialText={(text, from) => {
+                const view = editorRef.current?.getView();
+                if (!view) return;
+                // Replace only the previous partial insertion — not
+                // everything to end-of-doc — so text after the cursor
+                // is preserved when dictating mid-document.
+                const to = Math.min(
+                  from + speechPartialLenRef.current,
+                  view.state.doc.length,
+                );
+                view.dispatch({
+                  changes: { from, to, insert: text },
+                  selection: { anchor: from + text.length },
+                });
+                speechPartialLenRef.current = text.length;
+              }}
+              onTranscription={(text, from) => {
+                const view = editorRef.current?.getView();
+                if (view) {
+                  const to = Math.min(
+                    from + speechPartialLenRef.current,
+                    view.state.doc.length,
+                  );
+                  view.dispatch({
+                    changes: { from, to, insert: text },
+                    selection: { anchor: from + text.length },
+                  });
+                  setContent(view.state.doc.toString());
+                } else {
+                  setContent((prev) => prev + (prev ? " " : "") + text);
+                }
+                speechPartialLenRef.current = 0;
+              }}
+            />
+
+            <AiMenu
+              content={content}
+              folder={folder}
+              onApplyText={(text) => {
+                editorRef.current?.setContent(text);
+                setContent(text);
+              }}
+              onShowToast={(msg) => setToast(msg)}
+              disabled={!hasMeaningfulContent}
+            />
+
+            {isSticked && isPinned ? (
+              <div className="flex items-center gap-1.5">
+                <button
+                  onClick={handleCloseWithoutSaving}
+                  className="px-2 py-1 rounded-md hover:bg-line text-stone hover:text-ink transition-colors text-[10px]"
+                  title="Close without saving"
+                >
+                  Close
+                </button>
+                <button
+                  onClick={handleSaveAndCloseSticked}
+                  disabled={!hasMeaningfulContent}
+                  className={`px-2.5 py-1 rounded-md text-[10px] font-medium transition-colors ${
+                    hasMeaningfulContent
+                      ? "bg-coral text-white hover:bg-coral/90"
+                      : "bg-line text-stone cursor-not-allowed"
+                  }`}
+                  title={
+                    hasMeaningfulContent
+                      ? "Save to folder and close"
+                      : "Nothing to save"
+                  }
+                >
+                  Save
+                </button>
               </div>
-            </>
-          )}
+            ) : isSticked ? (
+              <button
+                onClick={handleSaveAndCloseSticked}
+                className="px-2.5 py-1.5 bg-coral-light text-coral rounded-lg text-[10px] font-semibold hover:bg-coral hover:text-white transition-colors cursor-pointer"
+                title="Save and close (Esc)"
+              >
+                esc
+              </button>
+            ) : (
+              <button
+                onClick={handleSaveAndClose}
+                className="px-2.5 py-1.5 rounded-lg text-[10px] font-semibold transition-colors bg-coral-light text-coral hover:bg-coral hover:text-white cursor-pointer"
+                title="Save and close (Esc)"
+              >
+                esc
+              </button>
+            )}
+          </div>
         </div>
 
         {/* Editor */}
@@ -1857,26 +2133,28 @@ export default function PostIt({
             <div className="h-full" /> // wait for notes dir to resolve .assets image paths
           ) : (
             <Editor
-              key={`${vimEnabled ? "vim" : "novim"}-${textDirection}`}
+              key={`${vimEnabled ? "vim" : "novim"}-${textDirection}-${readOnly}`}
               ref={editorRef}
               onChange={handleContentChange}
               placeholder={isSticked ? "Sticked note..." : "Type a thought..."}
               initialContent={resolvedInitialContent || initialContent}
               vimEnabled={vimEnabled}
-              showFormatToolbar={zenMode ? false : formatToolbar}
+              showFormatToolbar={formatToolbar}
               textDirection={textDirection}
+              readOnly={readOnly}
               onVimModeChange={setVimMode}
               onVimSaveAndClose={runVimSaveAndClose}
               onVimCloseWithoutSaving={runVimDiscardAndClose}
               onImagePaste={handleImagePaste}
               onImageDropPath={handleImageDropPath}
+              onTextFileDrop={handleTextFileDrop}
               onWikiLinkClick={handleWikiLinkClick}
               onCursorChange={handleCursorChange}
             />
           )}
 
           {/* Folder Picker */}
-          {showPicker && !zenMode && (
+          {showPicker && (
             <FolderPicker
               query={content.startsWith("/") ? content.slice(1) : ""}
               onSelect={handleFolderSelect}
@@ -1890,154 +2168,110 @@ export default function PostIt({
         </div>
 
         {/* Footer - draggable (or command bar when vim command mode) */}
-        {/* Vim command bar always renders when active (even in zen mode) */}
-        {(!zenMode || (vimEnabled && vimMode === "command")) &&
-          (vimEnabled && vimMode === "command" ? (
-            <div
-              data-capture-hide
-              className="flex flex-col border-t border-line"
-            >
-              {/* entire vim command bar hidden during capture */}
-              {vimCommandError && (
-                <div className="px-4 py-1 text-[11px] text-coral bg-coral-light/30">
-                  {vimCommandError}
-                </div>
-              )}
-              <div className="flex items-center px-4 py-1.5 bg-ink/5">
-                <span className="text-[13px] font-mono text-coral font-bold mr-0.5">
-                  :
-                </span>
-                <input
-                  ref={commandInputRef}
-                  type="text"
-                  value={vimCommand}
-                  onChange={(e) => {
-                    setVimCommand(e.target.value);
-                    setVimCommandError("");
-                  }}
-                  onKeyDown={(e) => {
-                    if (e.key === "Enter") {
-                      e.preventDefault();
-                      executeVimCommand(vimCommand);
-                    } else if (e.key === "Escape") {
-                      e.preventDefault();
-                      dismissCommandBar();
-                    } else if (e.key === "Backspace" && !vimCommand) {
-                      e.preventDefault();
-                      dismissCommandBar();
-                    }
-                  }}
-                  className="flex-1 bg-transparent text-[13px] font-mono text-ink outline-none placeholder:text-stone/50"
-                  placeholder="wq  q!"
-                  spellCheck={false}
-                  autoComplete="off"
-                />
+        {vimEnabled && vimMode === "command" ? (
+          <div
+            data-capture-hide
+            className="flex flex-col border-t border-line"
+          >
+            {/* entire vim command bar hidden during capture */}
+            {vimCommandError && (
+              <div className="px-4 py-1 text-[11px] text-coral bg-coral-light/30">
+                {vimCommandError}
               </div>
+            )}
+            <div className="flex items-center px-4 py-1.5 bg-ink/5">
+              <span className="text-[13px] font-mono text-coral font-bold mr-0.5">
+                :
+              </span>
+              <input
+                ref={commandInputRef}
+                type="text"
+                value={vimCommand}
+                onChange={(e) => {
+                  setVimCommand(e.target.value);
+                  setVimCommandError("");
+                }}
+                onKeyDown={(e) => {
+                  if (e.key === "Enter") {
+                    e.preventDefault();
+                    executeVimCommand(vimCommand);
+                  } else if (e.key === "Escape") {
+                    e.preventDefault();
+                    dismissCommandBar();
+                  } else if (e.key === "Backspace" && !vimCommand) {
+                    e.preventDefault();
+                    dismissCommandBar();
+                  }
+                }}
+                className="flex-1 bg-transparent text-[13px] font-mono text-ink outline-none placeholder:text-stone/50"
+                placeholder="wq  q!"
+                spellCheck={false}
+                autoComplete="off"
+              />
             </div>
-          ) : (
-            <div
-              onMouseDown={startDrag}
-              className="flex items-center justify-between px-4 py-2 border-t border-line text-[10px] drag-handle"
-            >
-              <span className="flex items-center gap-2 font-mono text-stone">
-                <span>
-                  <span className="text-coral">~</span>/Stik/
-                  {folder && (
-                    <>
-                      <span className="text-coral">{folder}</span>/
-                    </>
+          </div>
+        ) : (
+          <div
+            onMouseDown={startDrag}
+            className="flex items-center justify-between px-4 py-2 border-t border-line text-[10px] drag-handle"
+          >
+            <span className="flex items-center gap-2 font-mono text-stone">
+              <span>
+                <span className="text-coral">~</span>/Stik/
+                {folder && (
+                  <>
+                    <span className="text-coral">{folder}</span>/
+                  </>
+                )}
+              </span>
+              <SyncIndicator enabled={icloudEnabled} />
+            </span>
+            <div className="flex items-center gap-2">
+              {vimEnabled ? (
+                <span className="vim-mode-indicator text-stone">
+                  {vimMode === "normal" ? (
+                    <span className="text-coral">-- NORMAL --</span>
+                  ) : vimMode === "visual" ? (
+                    <span className="text-amber-500">-- VISUAL --</span>
+                  ) : vimMode === "visual-line" ? (
+                    <span className="text-amber-500">-- VISUAL LINE --</span>
+                  ) : (
+                    <span className="text-green-600">-- INSERT --</span>
                   )}
                 </span>
-                <SyncIndicator enabled={icloudEnabled} />
-              </span>
-              <div className="flex items-center gap-2">
-                {vimEnabled ? (
-                  <span className="vim-mode-indicator text-stone">
-                    {vimMode === "normal" ? (
-                      <span className="text-coral">-- NORMAL --</span>
-                    ) : vimMode === "visual" ? (
-                      <span className="text-amber-500">-- VISUAL --</span>
-                    ) : vimMode === "visual-line" ? (
-                      <span className="text-amber-500">-- VISUAL LINE --</span>
-                    ) : (
-                      <span className="text-green-600">-- INSERT --</span>
-                    )}
-                  </span>
-                ) : isSticked && !isPinned && !isViewing ? (
-                  <span className="text-stone">
-                    <span className="text-amber-500">○</span> unpinned
-                  </span>
-                ) : (
-                  <span className="text-stone">
-                    <span className="text-coral">✦</span> markdown supported
-                  </span>
-                )}
-                {(onOpenSettings || isSticked) && (
-                  <span data-capture-hide className="contents">
-                    {!vimEnabled && (
-                      <button
-                        onClick={() => {
-                          const next = !formatToolbar;
-                          setFormatToolbar(next);
-                          try {
-                            localStorage.setItem(
-                              "stik:format-toolbar",
-                              next ? "1" : "0",
-                            );
-                          } catch {}
-                        }}
-                        className={`w-6 h-6 flex items-center justify-center rounded-md transition-colors ${
-                          formatToolbar
-                            ? "text-coral hover:bg-coral-light"
-                            : "text-stone hover:bg-line hover:text-ink"
-                        }`}
-                        title={
-                          formatToolbar
-                            ? "Hide format buttons"
-                            : "Show format buttons"
-                        }
-                      >
-                        <svg
-                          width="14"
-                          height="14"
-                          viewBox="0 0 24 24"
-                          fill="none"
-                          stroke="currentColor"
-                          strokeWidth="2"
-                          strokeLinecap="round"
-                          strokeLinejoin="round"
-                        >
-                          <path d="M4 7V4h16v3" />
-                          <path d="M9 20h6" />
-                          <path d="M12 4v16" />
-                        </svg>
-                      </button>
-                    )}
-                    <button
-                      onClick={() => invoke("open_command_palette")}
-                      className="w-6 h-6 flex items-center justify-center rounded-md hover:bg-line text-stone hover:text-ink transition-colors"
-                      title={`Command Palette (${formatShortcutDisplay(systemShortcuts.search || "Cmd+Shift+P")})`}
-                    >
-                      <svg
-                        width="14"
-                        height="14"
-                        viewBox="0 0 24 24"
-                        fill="none"
-                        stroke="currentColor"
-                        strokeWidth="2"
-                        strokeLinecap="round"
-                        strokeLinejoin="round"
-                      >
-                        <circle cx="11" cy="11" r="8" />
-                        <line x1="21" y1="21" x2="16.65" y2="16.65" />
-                      </svg>
-                    </button>
+              ) : isSticked && !isPinned && !isViewing ? (
+                <span className="text-stone">
+                  <span className="text-amber-500">○</span> unpinned
+                </span>
+              ) : (
+                <span className="text-stone">
+                  <span className="text-coral">✦</span> markdown supported
+                </span>
+              )}
+              {(onOpenSettings || isSticked) && (
+                <span data-capture-hide className="contents">
+                  {!vimEnabled && (
                     <button
-                      onClick={() =>
-                        isSticked ? invoke("open_settings") : onOpenSettings?.()
+                      onClick={() => {
+                        const next = !formatToolbar;
+                        setFormatToolbar(next);
+                        try {
+                          localStorage.setItem(
+                            "stik:format-toolbar",
+                            next ? "1" : "0",
+                          );
+                        } catch {}
+                      }}
+                      className={`w-6 h-6 flex items-center justify-center rounded-md transition-colors ${
+                        formatToolbar
+                          ? "text-coral hover:bg-coral-light"
+                          : "text-stone hover:bg-line hover:text-ink"
+                      }`}
+                      title={
+                        formatToolbar
+                          ? "Hide format buttons"
+                          : "Show format buttons"
                       }
-                      className="w-6 h-6 flex items-center justify-center rounded-md hover:bg-line text-stone hover:text-ink transition-colors"
-                      title={`Settings (${formatShortcutDisplay(systemShortcuts.settings || "Cmd+Shift+Comma")})`}
                     >
                       <svg
                         width="14"
@@ -2049,15 +2283,57 @@ export default function PostIt({
                         strokeLinecap="round"
                         strokeLinejoin="round"
                       >
-                        <circle cx="12" cy="12" r="3" />
-                        <path d="M19.4 15a1.65 1.65 0 0 0 .33 1.82l.06.06a2 2 0 0 1 0 2.83 2 2 0 0 1-2.83 0l-.06-.06a1.65 1.65 0 0 0-1.82-.33 1.65 1.65 0 0 0-1 1.51V21a2 2 0 0 1-2 2 2 2 0 0 1-2-2v-.09A1.65 1.65 0 0 0 9 19.4a1.65 1.65 0 0 0-1.82.33l-.06.06a2 2 0 0 1-2.83 0 2 2 0 0 1 0-2.83l.06-.06a1.65 1.65 0 0 0 .33-1.82 1.65 1.65 0 0 0-1.51-1H3a2 2 0 0 1-2-2 2 2 0 0 1 2-2h.09A1.65 1.65 0 0 0 4.6 9a1.65 1.65 0 0 0-.33-1.82l-.06-.06a2 2 0 0 1 0-2.83 2 2 0 0 1 2.83 0l.06.06a1.65 1.65 0 0 0 1.82.33H9a1.65 1.65 0 0 0 1-1.51V3a2 2 0 0 1 2-2 2 2 0 0 1 2 2v.09a1.65 1.65 0 0 0 1 1.51 1.65 1.65 0 0 0 1.82-.33l.06-.06a2 2 0 0 1 2.83 0 2 2 0 0 1 0 2.83l-.06.06a1.65 1.65 0 0 0-.33 1.82V9a1.65 1.65 0 0 0 1.51 1H21a2 2 0 0 1 2 2 2 2 0 0 1-2 2h-.09a1.65 1.65 0 0 0-1.51 1z" />
+                        <path d="M4 7V4h16v3" />
+                        <path d="M9 20h6" />
+                        <path d="M12 4v16" />
                       </svg>
                     </button>
-                  </span>
-                )}
-              </div>
+                  )}
+                  <button
+                    onClick={() => invoke("open_command_palette")}
+                    className="w-6 h-6 flex items-center justify-center rounded-md hover:bg-line text-stone hover:text-ink transition-colors"
+                    title={`Command Palette (${formatShortcutDisplay(systemShortcuts.search || "Cmd+Shift+P")})`}
+                  >
+                    <svg
+                      width="14"
+                      height="14"
+                      viewBox="0 0 24 24"
+                      fill="none"
+                      stroke="currentColor"
+                      strokeWidth="2"
+                      strokeLinecap="round"
+                      strokeLinejoin="round"
+                    >
+                      <circle cx="11" cy="11" r="8" />
+                      <line x1="21" y1="21" x2="16.65" y2="16.65" />
+                    </svg>
+                  </button>
+                  <button
+                    onClick={() =>
+                      isSticked ? invoke("open_settings") : onOpenSettings?.()
+                    }
+                    className="w-6 h-6 flex items-center justify-center rounded-md hover:bg-line text-stone hover:text-ink transition-colors"
+                    title={`Settings (${formatShortcutDisplay(systemShortcuts.settings || "Cmd+Shift+Comma")})`}
+                  >
+                    <svg
+                      width="14"
+                      height="14"
+                      viewBox="0 0 24 24"
+                      fill="none"
+                      stroke="currentColor"
+                      strokeWidth="2"
+                      strokeLinecap="round"
+                      strokeLinejoin="round"
+                    >
+                      <circle cx="12" cy="12" r="3" />
+                      <path d="M19.4 15a1.65 1.65 0 0 0 .33 1.82l.06.06a2 2 0 0 1 0 2.83 2 2 0 0 1-2.83 0l-.06-.06a1.65 1.65 0 0 0-1.82-.33 1.65 1.65 0 0 0-1 1.51V21a2 2 0 0 1-2 2 2 2 0 0 1-2-2v-.09A1.65 1.65 0 0 0 9 19.4a1.65 1.65 0 0 0-1.82.33l-.06.06a2 2 0 0 1-2.83 0 2 2 0 0 1 0-2.83l.06-.06a1.65 1.65 0 0 0 .33-1.82 1.65 1.65 0 0 0-1.51-1H3a2 2 0 0 1-2-2 2 2 0 0 1 2-2h.09A1.65 1.65 0 0 0 4.6 9a1.65 1.65 0 0 0-.33-1.82l-.06-.06a2 2 0 0 1 0-2.83 2 2 0 0 1 2.83 0l.06.06a1.65 1.65 0 0 0 1.82.33H9a1.65 1.65 0 0 0 1-1.51V3a2 2 0 0 1 2-2 2 2 0 0 1 2 2v.09a1.65 1.65 0 0 0 1 1.51 1.65 1.65 0 0 0 1.82-.33l.06-.06a2 2 0 0 1 2.83 0 2 2 0 0 1 0 2.83l-.06.06a1.65 1.65 0 0 0-.33 1.82V9a1.65 1.65 0 0 0 1.51 1H21a2 2 0 0 1 2 2 2 2 0 0 1-2 2h-.09a1.65 1.65 0 0 0-1.51 1z" />
+                    </svg>
+                  </button>
+                </span>
+              )}
             </div>
-          ))}
+          </div>
+        )}
       </div>
       {toast && <Toast message={toast} onDone={() => setToast(null)} />}
     </>
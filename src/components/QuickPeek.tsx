@@ -0,0 +1,54 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+
+interface QuickPeekContent {
+  path: string;
+  title: string;
+  html: string;
+}
+
+/** Read-only preview of a search hit. Hides on blur (backend) or Escape. */
+export default function QuickPeek() {
+  const [peek, setPeek] = useState<QuickPeekContent | null>(null);
+
+  const load = useCallback(async () => {
+    try {
+      setPeek(await invoke<QuickPeekContent>("get_quick_peek_content"));
+    } catch (err) {
+      console.error("Failed to load quick peek:", err);
+    }
+  }, []);
+
+  useEffect(() => {
+    load();
+    const unlisten = listen("quick-peek-updated", () => load());
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [load]);
+
+  useEffect(() => {
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") {
+        e.preventDefault();
+        getCurrentWindow().hide();
+      }
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, []);
+
+  return (
+    <div className="w-full h-full bg-bg rounded-[14px] border border-line flex flex-col overflow-hidden">
+      <div className="px-4 py-2 border-b border-line text-[12px] font-semibold text-ink truncate">
+        {peek?.title ?? ""}
+      </div>
+      <div
+        className="flex-1 overflow-y-auto px-4 py-3 text-[13px] text-ink leading-relaxed [&_a]:text-coral [&_img]:max-w-full [&_img]:rounded-md"
+        dangerouslySetInnerHTML={{ __html: peek?.html ?? "" }}
+      />
+    </div>
+  );
+}
@@ -3,6 +3,11 @@ import { invoke } from "@tauri-apps/api/core";
 import { open, save } from "@tauri-apps/plugin-dialog";
 import ShortcutRecorder from "./ShortcutRecorder";
 import type {
+  BackupArchive,
+  BackupProgress,
+  BackupSettings,
+  BackupStatus,
+  CaptureBlurBehavior,
   CustomFontEntry,
   CustomTemplate,
   CustomThemeDefinition,
@@ -196,6 +201,259 @@ function SettingsToast({
   );
 }
 
+const DEFAULT_BACKUP_SETTINGS: BackupSettings = {
+  enabled: false,
+  destination: "",
+  frequency_days: 1,
+  retention_count: 10,
+  excluded_folders: [],
+};
+
+function formatBackupTime(iso: string | null): string {
+  return iso ? new Date(iso).toLocaleString() : "never";
+}
+
+function BackupSection({
+  settings,
+  folders,
+  onSettingsChange,
+}: {
+  settings: StikSettings;
+  folders: string[];
+  onSettingsChange: (settings: StikSettings) => void;
+}) {
+  const backup = settings.backup ?? DEFAULT_BACKUP_SETTINGS;
+  const [status, setStatus] = useState<BackupStatus | null>(null);
+  const [archives, setArchives] = useState<BackupArchive[]>([]);
+  const [progress, setProgress] = useState<BackupProgress | null>(null);
+  const [isRunning, setIsRunning] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+
+  const updateBackup = (updates: Partial<BackupSettings>) =>
+    onSettingsChange({ ...settings, backup: { ...backup, ...updates } });
+
+  const refresh = useCallback(async () => {
+    try {
+      setStatus(await invoke<BackupStatus>("get_backup_status"));
+      setArchives(await invoke<BackupArchive[]>("list_backups"));
+    } catch (error) {
+      setMessage(String(error));
+    }
+  }, []);
+
+  useEffect(() => {
+    refresh();
+  }, [refresh, backup.destination]);
+
+  useEffect(() => {
+    const unlistenProgress = listen<BackupProgress>("backup-progress", (event) =>
+      setProgress(event.payload),
+    );
+    const unlistenFinished = listen<BackupStatus>("backup-finished", () => {
+      setProgress(null);
+      refresh();
+    });
+    return () => {
+      unlistenProgress.then((fn) => fn());
+      unlistenFinished.then((fn) => fn());
+    };
+  }, [refresh]);
+
+  const chooseDestination = async () => {
+    const selected = await open({
+      directory: true,
+      multiple: false,
+      title: "Choose where to keep backups",
+      defaultPath: backup.destination || undefined,
+    });
+    if (typeof selected === "string") {
+      updateBackup({ destination: selected });
+    }
+  };
+
+  const runNow = async () => {
+    setIsRunning(true);
+    setMessage(null);
+    try {
+      await invoke<BackupStatus>("run_backup_now");
+    } catch (error) {
+      setMessage(String(error));
+    } finally {
+      setIsRunning(false);
+      setProgress(null);
+      refresh();
+    }
+  };
+
+  const restore = async (archive: BackupArchive) => {
+    const selected = await open({
+      directory: true,
+      multiple: false,
+      title: "Choose a new, empty folder to restore into",
+    });
+    if (typeof selected !== "string") return;
+    try {
+      const restored = await invoke<string>("restore_backup", {
+        path: archive.path,
+        destination: selected,
+      });
+      setMessage(`Restored to ${restored}`);
+    } catch (error) {
+      setMessage(String(error));
+    }
+  };
+
+  const toggleExcluded = (folder: string) => {
+    const excluded = backup.excluded_folders.includes(folder)
+      ? backup.excluded_folders.filter((f) => f !== folder)
+      : [...backup.excluded_folders, folder];
+    updateBackup({ excluded_folders: excluded });
+  };
+
+  return (
+    <div className="p-4 bg-line/30 rounded-xl border border-line/50 space-y-3">
+      <label className="flex items-center justify-between gap-3">
+        <div>
+          <p className="text-[13px] text-ink font-medium">Automatic backups</p>
+          <p className="mt-1 text-[12px] text-stone leading-relaxed">
+            Zip the whole notes folder on a schedule. Git history is left
+            out.
+          </p>
+        </div>
+        <button
+          type="button"
+          onClick={() => updateBackup({ enabled: !backup.enabled })}
+          className={`relative w-11 h-6 rounded-full transition-colors shrink-0 ${
+            backup.enabled ? "bg-coral" : "bg-line"
+          }`}
+          title="Toggle automatic backups"
+        >
+          <span
+            className={`absolute left-0.5 top-0.5 w-5 h-5 rounded-full bg-white transition-transform pointer-events-none ${
+              backup.enabled ? "translate-x-5" : "translate-x-0"
+            }`}
+          />
+        </button>
+      </label>
+
+      <div className="flex items-center gap-2">
+        <p className="flex-1 text-[12px] text-stone truncate">
+          {backup.destination || "No backup folder chosen"}
+        </p>
+        <button
+          type="button"
+          onClick={chooseDestination}
+          className="px-3 py-1.5 text-[12px] text-coral border border-coral/30 rounded-lg hover:bg-coral-light transition-colors"
+        >
+          Choose folder…
+        </button>
+      </div>
+
+      <div className="flex items-center gap-4 text-[12px] text-stone">
+        <label className="flex items-center gap-2">
+          Every
+          <input
+            type="number"
+            min={1}
+            value={backup.frequency_days}
+            onChange={(e) =>
+              updateBackup({
+                frequency_days: Math.max(1, Number(e.target.value) || 1),
+              })
+            }
+            className="w-14 px-2 py-1 bg-bg border border-line rounded text-ink"
+          />
+          days
+        </label>
+        <label className="flex items-center gap-2">
+          Keep
+          <input
+            type="number"
+            min={0}
+            value={backup.retention_count}
+            onChange={(e) =>
+              updateBackup({
+                retention_count: Math.max(0, Number(e.target.value) || 0),
+              })
+            }
+            className="w-14 px-2 py-1 bg-bg border border-line rounded text-ink"
+          />
+          archives (0 keeps all)
+        </label>
+      </div>
+
+      {folders.length > 0 && (
+        <div>
+          <p className="text-[12px] text-stone mb-1.5">Leave out</p>
+          <div className="flex flex-wrap gap-2">
+            {folders.map((folder) => (
+              <label
+                key={folder}
+                className="flex items-center gap-1.5 text-[12px] text-ink"
+              >
+                <input
+                  type="checkbox"
+                  checked={backup.excluded_folders.includes(folder)}
+                  onChange={() => toggleExcluded(folder)}
+                />
+                {folder}
+              </label>
+            ))}
+          </div>
+        </div>
+      )}
+
+      <div className="flex items-center justify-between gap-3">
+        <p className="text-[12px] text-stone leading-relaxed">
+          {progress
+            ? `Copying ${progress.done} of ${progress.total} files…`
+            : status?.last_error
+              ? `Last backup failed: ${status.last_error}`
+              : `Last backup: ${formatBackupTime(status?.last_success_at ?? null)}`}
+        </p>
+        <button
+          type="button"
+          onClick={runNow}
+          disabled={isRunning || !backup.destination}
+          className="px-3 py-2 text-[12px] text-coral border border-coral/30 rounded-lg hover:bg-coral-light transition-colors disabled:opacity-50 shrink-0"
+        >
+          {isRunning ? "Backing up…" : "Back up now"}
+        </button>
+      </div>
+
+      {archives.length > 0 && (
+        <div className="space-y-1">
+          {archives.map((archive) => (
+            <div
+              key={archive.path}
+              className="flex items-center justify-between gap-3 text-[12px]"
+            >
+              <span className="text-ink">
+                {formatBackupTime(archive.created_at)}
+                <span className="text-stone">
+                  {" "}
+                  · {(archive.size_bytes / (1024 * 1024)).toFixed(1)} MB
+                </span>
+              </span>
+              <button
+                type="button"
+                onClick={() => restore(archive)}
+                className="text-coral hover:underline"
+              >
+                Restore…
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      {message && (
+        <p className="text-[12px] text-stone leading-relaxed">{message}</p>
+      )}
+    </div>
+  );
+}
+
 function PrivacySection({
   settings,
   onSettingsChange,
@@ -816,6 +1074,11 @@ function AppearanceSection({
 
   const selectedFont = settings.font_family ?? null;
   const windowOpacity = settings.window_opacity ?? 1.0;
+  const stickedMinSize = settings.sticked_min_size ?? [320, 200];
+  const stickedMaxSize =
+    settings.sticked_max_size === undefined ? [800, 600] : settings.sticked_max_size;
+  const sizeInputClass =
+    "w-20 px-2 py-1 bg-bg border border-line rounded-lg text-[12px] font-mono text-ink tabular-nums outline-none focus:border-coral";
   const customFonts: CustomFontEntry[] = settings.custom_fonts ?? [];
 
   // Lazily load all built-in Google Fonts and any saved custom fonts when the tab opens.
@@ -868,11 +1131,11 @@ function AppearanceSection({
     if (entry) setToast(`Font "${entry.name}" removed`);
   };
 
-  const activeTheme = settings.active_theme || settings.theme_mode || "system";
+  const activeTheme = settings.active_theme || "system";
   const customThemes = settings.custom_themes ?? [];
 
   const selectTheme = (id: string) => {
-    onSettingsChange({ ...settings, active_theme: id, theme_mode: id });
+    onSettingsChange({ ...settings, active_theme: id });
   };
 
   const startNewTheme = () => {
@@ -907,7 +1170,6 @@ function AppearanceSection({
       ...settings,
       custom_themes: updated,
       active_theme: editingTheme.id,
-      theme_mode: editingTheme.id,
     });
     setEditingTheme(null);
     setToast(
@@ -924,7 +1186,6 @@ function AppearanceSection({
 
     if (activeTheme === id) {
       newSettings.active_theme = "system";
-      newSettings.theme_mode = "";
     }
 
     onSettingsChange({ ...settings, ...newSettings });
@@ -953,7 +1214,6 @@ function AppearanceSection({
         ...settings,
         custom_themes: updated,
         active_theme: imported.id,
-        theme_mode: imported.id,
       });
       setToast(`Theme "${imported.name}" imported`);
     } catch (error) {
@@ -1309,6 +1569,93 @@ function AppearanceSection({
           </p>
         </div>
 
+        {/* ── Sticky Note Size ── */}
+        <div className="p-4 bg-line/30 rounded-xl border border-line/50">
+          <p className="text-[13px] text-ink font-medium mb-3">
+            Sticky Note Size
+          </p>
+          <div className="flex items-center gap-2 mb-2 text-[12px] text-stone">
+            <span className="w-16">Smallest</span>
+            <input
+              type="number"
+              min={240}
+              value={stickedMinSize[0]}
+              onChange={(e) =>
+                onSettingsChange({
+                  ...settings,
+                  sticked_min_size: [Number(e.target.value), stickedMinSize[1]],
+                })
+              }
+              className={sizeInputClass}
+            />
+            <span>×</span>
+            <input
+              type="number"
+              min={160}
+              value={stickedMinSize[1]}
+              onChange={(e) =>
+                onSettingsChange({
+                  ...settings,
+                  sticked_min_size: [stickedMinSize[0], Number(e.target.value)],
+                })
+              }
+              className={sizeInputClass}
+            />
+          </div>
+          <div className="flex items-center gap-2 text-[12px] text-stone">
+            <span className="w-16">Largest</span>
+            {stickedMaxSize ? (
+              <>
+                <input
+                  type="number"
+                  min={stickedMinSize[0]}
+                  value={stickedMaxSize[0]}
+                  onChange={(e) =>
+                    onSettingsChange({
+                      ...settings,
+                      sticked_max_size: [Number(e.target.value), stickedMaxSize[1]],
+                    })
+                  }
+                  className={sizeInputClass}
+                />
+                <span>×</span>
+                <input
+                  type="number"
+                  min={stickedMinSize[1]}
+                  value={stickedMaxSize[1]}
+                  onChange={(e) =>
+                    onSettingsChange({
+                      ...settings,
+                      sticked_max_size: [stickedMaxSize[0], Number(e.target.value)],
+                    })
+                  }
+                  className={sizeInputClass}
+                />
+              </>
+            ) : (
+              <span className="text-ink">No maximum</span>
+            )}
+            <label className="ml-auto flex items-center gap-1.5">
+              <input
+                type="checkbox"
+                checked={stickedMaxSize === null}
+                onChange={(e) =>
+                  onSettingsChange({
+                    ...settings,
+                    sticked_max_size: e.target.checked ? null : [800, 600],
+                  })
+                }
+                className="accent-coral"
+              />
+              No maximum
+            </label>
+          </div>
+          <p className="mt-2 text-[11px] text-stone leading-relaxed">
+            Limits for resizing sticked and viewing notes, in points. Notes
+            already larger or smaller are fitted when you change these.
+          </p>
+        </div>
+
         <div className="p-3 bg-coral-light/40 border border-coral/20 rounded-xl">
           <p className="text-[12px] text-stone leading-relaxed">
             Themes control all colors across Stik — the editor, command palette,
@@ -2059,6 +2406,46 @@ export default function SettingsContent({
             </p>
           </div>
 
+          <div>
+            <p className="text-[12px] text-stone mb-1.5">Daily note folder</p>
+            <div className="max-w-[360px]">
+              <Dropdown
+                value={settings.daily_note_folder ?? ""}
+                options={[
+                  { value: "", label: "Same as default folder" },
+                  ...folders.map((f) => ({ value: f, label: f })),
+                ]}
+                onChange={(value) =>
+                  onSettingsChange({ ...settings, daily_note_folder: value })
+                }
+              />
+            </div>
+            <p className="mt-1.5 text-[12px] text-stone leading-relaxed">
+              Where today's note is created by the daily note shortcut.
+            </p>
+          </div>
+
+          {settings.git_sharing.enabled && !settings.icloud?.enabled && (
+            <div>
+              <p className="text-[12px] text-stone mb-1.5">Sync summary folder</p>
+              <div className="max-w-[360px]">
+                <Dropdown
+                  value={settings.sync_summary_folder ?? ""}
+                  options={[
+                    { value: "", label: "Same as default folder" },
+                    ...folders.map((f) => ({ value: f, label: f })),
+                  ]}
+                  onChange={(value) =>
+                    onSettingsChange({ ...settings, sync_summary_folder: value })
+                  }
+                />
+              </div>
+              <p className="mt-1.5 text-[12px] text-stone leading-relaxed">
+                Where summaries of shared-folder activity are saved.
+              </p>
+            </div>
+          )}
+
           {!settings.icloud?.enabled &&
           settings.git_sharing.enabled &&
           gitSyncStatus?.repo_initialized ? (
@@ -2090,6 +2477,12 @@ export default function SettingsContent({
               </p>
             </div>
           ) : null}
+
+          <BackupSection
+            settings={settings}
+            folders={folders}
+            onSettingsChange={onSettingsChange}
+          />
         </div>
       )}
 
@@ -2206,6 +2599,117 @@ export default function SettingsContent({
             </div>
           </div>
 
+          <div className="p-4 bg-line/30 rounded-xl border border-line/50">
+            <p className="text-[13px] text-ink font-medium mb-1">
+              When the capture window loses focus
+            </p>
+            <p className="text-[12px] text-stone leading-relaxed mb-3">
+              Empty notes always close. Choose what happens to a note you
+              were still typing.
+            </p>
+            <div className="max-w-[240px]">
+              <Dropdown
+                value={settings.capture_blur_behavior || "discard"}
+                options={[
+                  { value: "discard", label: "Leave it open" },
+                  { value: "save", label: "Save and close" },
+                  { value: "keep_draft", label: "Keep as draft and close" },
+                ]}
+                onChange={(value) =>
+                  onSettingsChange({
+                    ...settings,
+                    capture_blur_behavior: value as CaptureBlurBehavior,
+                  })
+                }
+              />
+            </div>
+          </div>
+
+          <label className="flex items-center justify-between gap-3 p-4 bg-line/30 rounded-xl border border-line/50">
+            <div>
+              <p className="text-[13px] text-ink font-medium">
+                Remember where you captured
+              </p>
+              <p className="mt-1 text-[12px] text-stone leading-relaxed">
+                End new notes with the app you were in and, for browsers, the
+                page title and link.
+              </p>
+            </div>
+            <button
+              type="button"
+              onClick={() =>
+                onSettingsChange({
+                  ...settings,
+                  capture_context: !settings.capture_context,
+                })
+              }
+              className={`relative w-11 h-6 rounded-full transition-colors shrink-0 ${
+                settings.capture_context ? "bg-coral" : "bg-line"
+              }`}
+              title="Toggle capture context footer"
+            >
+              <span
+                className={`absolute left-0.5 top-0.5 w-5 h-5 rounded-full bg-white transition-transform pointer-events-none ${
+                  settings.capture_context ? "translate-x-5" : "translate-x-0"
+                }`}
+              />
+            </button>
+          </label>
+
+          <label className="flex items-center justify-between gap-3 p-4 bg-line/30 rounded-xl border border-line/50">
+            <div>
+              <p className="text-[13px] text-ink font-medium">
+                Respect Focus modes
+              </p>
+              <p className="mt-1 text-[12px] text-stone leading-relaxed">
+                While Do Not Disturb or another Focus is on, hold back
+                notifications and sticky notes until it ends.
+              </p>
+            </div>
+            <button
+              type="button"
+              onClick={() =>
+                onSettingsChange({
+                  ...settings,
+                  respect_focus_modes: !settings.respect_focus_modes,
+                })
+              }
+              className={`relative w-11 h-6 rounded-full transition-colors shrink-0 ${
+                settings.respect_focus_modes ? "bg-coral" : "bg-line"
+              }`}
+              title="Toggle respecting Focus modes"
+            >
+              <span
+                className={`absolute left-0.5 top-0.5 w-5 h-5 rounded-full bg-white transition-transform pointer-events-none ${
+                  settings.respect_focus_modes ? "translate-x-5" : "translate-x-0"
+                }`}
+              />
+            </button>
+          </label>
+
+          <div className="p-4 bg-line/30 rounded-xl border border-line/50">
+            <p className="text-[13px] text-ink font-medium mb-1">
+              Note filenames
+            </p>
+            <p className="text-[12px] text-stone leading-relaxed mb-3">
+              Use {"{date}"}, {"{time}"}, {"{slug}"} and {"{uuid4}"}. Names
+              need {"{date}"} plus {"{time}"} or {"{uuid4}"}; existing notes
+              keep their names.
+            </p>
+            <input
+              type="text"
+              value={settings.filename_template ?? "{date}-{time}-{slug}-{uuid4}"}
+              onChange={(e) =>
+                onSettingsChange({
+                  ...settings,
+                  filename_template: e.target.value,
+                })
+              }
+              placeholder="{date}-{time}-{slug}-{uuid4}"
+              className="w-full px-3 py-2.5 bg-bg border border-line rounded-lg text-[13px] text-ink font-mono placeholder:text-stone/70 focus:outline-none focus:border-coral/50"
+            />
+          </div>
+
           <label className="flex items-center justify-between gap-3 p-4 bg-line/30 rounded-xl border border-line/50">
             <div>
               <p className="text-[13px] text-ink font-medium">Hide Dock icon</p>
@@ -0,0 +1,178 @@
+import { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import type {
+  ActivityBreakdown,
+  CaptureHeatmap,
+  CaptureStats,
+} from "@/types";
+
+const WEEKDAYS = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+function formatHour(hour: number): string {
+  const suffix = hour < 12 ? "am" : "pm";
+  const h = hour % 12 === 0 ? 12 : hour % 12;
+  return `${h}${suffix}`;
+}
+
+function heatColor(count: number, max: number): string {
+  if (count === 0 || max === 0) return "rgb(var(--color-line))";
+  const opacity = 0.25 + 0.75 * (count / max);
+  return `rgb(var(--color-coral) / ${opacity.toFixed(2)})`;
+}
+
+export default function StatsWindow() {
+  const [stats, setStats] = useState<CaptureStats | null>(null);
+  const [heatmap, setHeatmap] = useState<CaptureHeatmap | null>(null);
+  const [activity, setActivity] = useState<ActivityBreakdown | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const refresh = useCallback(async () => {
+    try {
+      const [nextStats, nextHeatmap, nextActivity] = await Promise.all([
+        invoke<CaptureStats>("get_capture_stats"),
+        invoke<CaptureHeatmap>("get_capture_heatmap", { days: 0 }),
+        invoke<ActivityBreakdown>("get_activity_breakdown"),
+      ]);
+      setStats(nextStats);
+      setHeatmap(nextHeatmap);
+      setActivity(nextActivity);
+      setError(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  }, []);
+
+  // Fetch on mount, and again every time the window is re-shown
+  useEffect(() => {
+    refresh();
+    const unlisten = listen("stats-opened", () => refresh());
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [refresh]);
+
+  useEffect(() => {
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") {
+        e.preventDefault();
+        getCurrentWindow().close();
+      }
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, []);
+
+  const startDrag = useCallback(async (e: React.MouseEvent) => {
+    if ((e.target as HTMLElement).closest("button")) return;
+    try {
+      await getCurrentWindow().startDragging();
+    } catch (err) {
+      console.error("Failed to start drag:", err);
+    }
+  }, []);
+
+  const maxDay = Math.max(0, ...(heatmap?.days.map((d) => d.count) ?? []));
+  const maxHour = Math.max(0, ...(activity?.hour_totals ?? []));
+
+  return (
+    <div className="w-full h-full bg-bg rounded-[14px] flex flex-col overflow-hidden">
+      <div
+        onMouseDown={startDrag}
+        className="px-5 py-3 border-b border-line flex items-center justify-between drag-handle"
+      >
+        <h1 className="text-sm font-semibold text-ink">Stats</h1>
+        <button
+          onClick={() => getCurrentWindow().close()}
+          className="text-[11px] text-stone hover:text-ink transition-colors"
+        >
+          Close
+        </button>
+      </div>
+
+      {error ? (
+        <div className="flex-1 flex items-center justify-center text-coral text-sm">
+          {error}
+        </div>
+      ) : (
+        <div className="flex-1 overflow-y-auto px-5 py-4 flex flex-col gap-5">
+          <div className="grid grid-cols-4 gap-3">
+            {[
+              ["Current streak", stats?.capture_streak_days],
+              ["Longest streak", stats?.longest_streak_days],
+              ["Days captured", stats?.total_capture_days],
+              ["Notes (year)", heatmap?.total_notes],
+            ].map(([label, value]) => (
+              <div
+                key={label as string}
+                className="bg-surface rounded-lg border border-line px-3 py-2"
+              >
+                <div className="text-[10px] text-stone uppercase tracking-wide">
+                  {label}
+                </div>
+                <div className="text-lg font-semibold text-ink">
+                  {value ?? "–"}
+                </div>
+              </div>
+            ))}
+          </div>
+
+          <section>
+            <h2 className="text-[11px] font-semibold text-stone mb-2">
+              Last year
+            </h2>
+            <div className="grid grid-flow-col grid-rows-7 gap-[2px] w-fit">
+              {heatmap?.days.map((day) => (
+                <div
+                  key={day.date}
+                  title={`${day.date}: ${day.count}`}
+                  className="w-[9px] h-[9px] rounded-[2px]"
+                  style={{ background: heatColor(day.count, maxDay) }}
+                />
+              ))}
+            </div>
+          </section>
+
+          <section>
+            <h2 className="text-[11px] font-semibold text-stone mb-2">
+              By hour
+              {activity?.most_active_hour != null &&
+                ` · busiest ${formatHour(activity.most_active_hour)}`}
+            </h2>
+            <div className="flex items-end gap-[3px] h-20">
+              {activity?.hour_totals.map((count, hour) => (
+                <div
+                  key={hour}
+                  title={`${formatHour(hour)}: ${count}`}
+                  className="flex-1 bg-coral/70 rounded-t-[2px]"
+                  style={{
+                    height: maxHour ? `${(count / maxHour) * 100}%` : "0%",
+                  }}
+                />
+              ))}
+            </div>
+          </section>
+
+          <section>
+            <h2 className="text-[11px] font-semibold text-stone mb-2">
+              By weekday
+              {activity?.most_active_weekday != null &&
+                ` · busiest ${WEEKDAYS[activity.most_active_weekday]}`}
+            </h2>
+            <div className="grid grid-cols-7 gap-2">
+              {activity?.weekday_totals.map((count, day) => (
+                <div key={WEEKDAYS[day]} className="text-center">
+                  <div className="text-[13px] font-semibold text-ink">
+                    {count}
+                  </div>
+                  <div className="text-[10px] text-stone">{WEEKDAYS[day]}</div>
+                </div>
+              ))}
+            </div>
+          </section>
+        </div>
+      )}
+    </div>
+  );
+}
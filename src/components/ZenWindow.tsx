@@ -0,0 +1,129 @@
+import { useState, useEffect, useCallback, useRef } from "react";
+import { invoke, convertFileSrc } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import Editor, { type EditorRef } from "./Editor";
+import type { StikSettings } from "@/types";
+import {
+  resolveImagePaths,
+  unresolveImagePaths,
+} from "@/utils/imageMarkdownPaths";
+import { matchesShortcut } from "@/utils/systemShortcuts";
+
+interface ZenContent {
+  content: string;
+  folder: string;
+}
+
+/**
+ * Full-screen writing surface for the capture note. The backend hands the
+ * postit's content over on entry; Escape (or the zen shortcut) sends the
+ * edits back to the postit.
+ */
+export default function ZenWindow() {
+  const editorRef = useRef<EditorRef>(null);
+  const contentRef = useRef("");
+  const [folder, setFolder] = useState("");
+  const [fontSize, setFontSize] = useState(14);
+  const [zenShortcut, setZenShortcut] = useState("Cmd+Period");
+  const [notesDir, setNotesDir] = useState<string | null>(null);
+  const [initialContent, setInitialContent] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<string>("get_notes_directory")
+      .then(setNotesDir)
+      .catch(() => {});
+    invoke<StikSettings>("get_settings")
+      .then((s) => {
+        setFontSize(s.font_size ?? 14);
+        setZenShortcut(s.system_shortcuts?.zen_mode || "Cmd+Period");
+      })
+      .catch(() => {});
+  }, []);
+
+  const resolve = useCallback(
+    (payload: ZenContent) => {
+      contentRef.current = payload.content;
+      setFolder(payload.folder);
+      return notesDir
+        ? resolveImagePaths(
+            payload.content,
+            `${notesDir}/${payload.folder}`,
+            convertFileSrc,
+          )
+        : payload.content;
+    },
+    [notesDir],
+  );
+
+  useEffect(() => {
+    if (notesDir === null) return;
+    invoke<ZenContent>("get_zen_content")
+      .then((payload) => setInitialContent(resolve(payload)))
+      .catch(() => setInitialContent(""));
+    // Later sessions reuse this window and arrive as events.
+    const unlisten = listen<ZenContent>("zen-content", (event) => {
+      editorRef.current?.setContent(resolve(event.payload));
+      editorRef.current?.focus();
+      editorRef.current?.moveToEnd?.();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [notesDir, resolve]);
+
+  useEffect(() => {
+    if (initialContent === null) return;
+    setTimeout(() => {
+      editorRef.current?.focus();
+      editorRef.current?.moveToEnd?.();
+    }, 50);
+  }, [initialContent]);
+
+  const exitZen = useCallback(() => {
+    invoke("exit_zen_window", {
+      content: contentRef.current,
+      folder,
+    }).catch((err) => console.error("Failed to exit zen mode:", err));
+  }, [folder]);
+
+  useEffect(() => {
+    const unlisten = listen("zen-exit-requested", () => exitZen());
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape" || matchesShortcut(e, zenShortcut)) {
+        e.preventDefault();
+        exitZen();
+      }
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => {
+      unlisten.then((fn) => fn());
+      window.removeEventListener("keydown", handleKeyDown);
+    };
+  }, [exitZen, zenShortcut]);
+
+  const handleChange = useCallback((newContent: string) => {
+    contentRef.current = unresolveImagePaths(newContent);
+  }, []);
+
+  return (
+    <div className="zen-mode w-full h-full bg-bg flex flex-col">
+      <div />
+      <div
+        className="flex-1 min-h-0 w-full max-w-[720px] mx-auto px-8 pb-12"
+        style={
+          { "--editor-font-size": `${fontSize + 2}px` } as React.CSSProperties
+        }
+      >
+        {initialContent !== null && (
+          <Editor
+            ref={editorRef}
+            onChange={handleChange}
+            placeholder="Type a thought..."
+            initialContent={initialContent}
+            showFormatToolbar={false}
+          />
+        )}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,66 @@
+import type { PaletteAction } from "@/types";
+
+interface ActionListProps {
+  actions: PaletteAction[];
+  selectedIndex: number;
+  focused: boolean;
+  resultsRef: React.RefObject<HTMLDivElement | null>;
+  onRunAction: (action: PaletteAction) => void;
+  onSetSelectedIndex: (index: number) => void;
+}
+
+/** Backend-defined actions, shown when the query starts with ">". */
+export default function ActionList({
+  actions,
+  selectedIndex,
+  focused,
+  resultsRef,
+  onRunAction,
+  onSetSelectedIndex,
+}: ActionListProps) {
+  if (actions.length === 0) {
+    return (
+      <div className="flex-1 flex items-center justify-center p-4">
+        <span className="text-stone text-sm">No matching actions</span>
+      </div>
+    );
+  }
+
+  return (
+    <div ref={resultsRef} className="flex-1 overflow-y-auto">
+      {actions.map((action, index) => {
+        const isSelected = index === selectedIndex && focused;
+        return (
+          <button
+            key={action.id}
+            disabled={!action.enabled}
+            onClick={() => onRunAction(action)}
+            onMouseEnter={() => onSetSelectedIndex(index)}
+            className={`w-full px-4 py-2.5 text-left border-b border-line/50 transition-colors disabled:opacity-40 ${
+              isSelected ? "bg-coral/10" : "hover:bg-line/30"
+            }`}
+          >
+            <div className="flex items-center gap-2">
+              <p className="flex-1 text-[14px] font-medium text-ink truncate">
+                {action.title}
+              </p>
+              {action.shortcut && (
+                <span className="shrink-0 text-[10px] text-stone font-mono">
+                  {action.shortcut}
+                </span>
+              )}
+              <span className="shrink-0 px-2 py-0.5 rounded-full text-[10px] font-semibold bg-line/60 text-stone">
+                {action.category}
+              </span>
+            </div>
+            {action.subtitle && (
+              <p className="text-[12px] text-stone leading-relaxed">
+                {action.subtitle}
+              </p>
+            )}
+          </button>
+        );
+      })}
+    </div>
+  );
+}
@@ -1,8 +1,8 @@
-import type { FolderStats } from "@/types";
+import type { FolderBadge, SearchFacets } from "@/types";
 import { FOLDER_COLORS, FOLDER_COLOR_KEYS, getFolderColor } from "@/utils/folderColors";
 
 interface FolderSidebarProps {
-  folderStats: FolderStats[];
+  folderStats: FolderBadge[];
   totalNoteCount: number;
   selectedFolder: string | null;
   folderColors: Record<string, string>;
@@ -22,6 +22,8 @@ interface FolderSidebarProps {
   onRenameFolder: () => void;
   onCancelRename: () => void;
   position?: "left" | "right";
+  /** Match counts for the current query; replaces the note counts. */
+  searchFacets?: SearchFacets | null;
 }
 
 export default function FolderSidebar({
@@ -45,8 +47,12 @@ export default function FolderSidebar({
   onRenameFolder,
   onCancelRename,
   position = "left",
+  searchFacets = null,
 }: FolderSidebarProps) {
   const borderClass = position === "left" ? "border-r" : "border-l";
+  const matchesByFolder = new Map(
+    (searchFacets?.folders ?? []).map((f) => [f.name, f.count]),
+  );
 
   return (
     <div className={`w-[200px] shrink-0 ${borderClass} border-line flex flex-col overflow-hidden`}>
@@ -72,7 +78,7 @@ export default function FolderSidebar({
                 : "bg-line text-stone"
             }`}
           >
-            {totalNoteCount}
+            {searchFacets ? searchFacets.total : totalNoteCount}
           </span>
         </button>
 
@@ -128,6 +134,26 @@ export default function FolderSidebar({
                     {folder.name}
                   </span>
                 )}
+                {!isCurrentlyRenaming && folder.unsynced_count > 0 && (
+                  <span
+                    title={`${folder.unsynced_count} not synced yet`}
+                    className={`text-[10px] ${
+                      isSelected && focused ? "text-white/80" : "text-coral"
+                    }`}
+                  >
+                    ↑{folder.unsynced_count}
+                  </span>
+                )}
+                {!isCurrentlyRenaming && folder.sticked_count > 0 && (
+                  <span
+                    title={`${folder.sticked_count} sticked`}
+                    className={`text-[10px] ${
+                      isSelected && focused ? "text-white/80" : "text-stone"
+                    }`}
+                  >
+                    ◆{folder.sticked_count}
+                  </span>
+                )}
                 {!isCurrentlyRenaming && (
                   <span
                     className={`text-[10px] px-1.5 py-0.5 rounded ${
@@ -138,7 +164,9 @@ export default function FolderSidebar({
                         : "bg-line text-stone"
                     }`}
                   >
-                    {folder.note_count}
+                    {searchFacets
+                      ? (matchesByFolder.get(folder.name) ?? 0)
+                      : folder.note_count}
                   </span>
                 )}
               </button>
@@ -1,4 +1,4 @@
-import { useState, useEffect } from "react";
+import { useState, useEffect, useRef } from "react";
 import type { SearchResult } from "@/types";
 import { getFolderColor } from "@/utils/folderColors";
 
@@ -7,6 +7,7 @@ interface MovePickerProps {
   folders: string[];
   folderColors: Record<string, string>;
   onMove: (targetFolder: string) => void;
+  onMoveToNewFolder: (folderName: string) => void;
   onCancel: () => void;
 }
 
@@ -15,8 +16,11 @@ export default function MovePicker({
   folders,
   folderColors,
   onMove,
+  onMoveToNewFolder,
   onCancel,
 }: MovePickerProps) {
+  const [newFolderName, setNewFolderName] = useState("");
+  const newFolderInputRef = useRef<HTMLInputElement>(null);
   const [selectedIndex, setSelectedIndex] = useState(() => {
     const idx = folders.findIndex((f) => f !== note.folder);
     return idx >= 0 ? idx : 0;
@@ -24,6 +28,13 @@ export default function MovePicker({
 
   useEffect(() => {
     const handleKey = (e: KeyboardEvent) => {
+      // The new-folder field handles its own typing and Enter.
+      if (
+        e.key !== "Escape" &&
+        document.activeElement === newFolderInputRef.current
+      ) {
+        return;
+      }
       if (e.key === "Escape") {
         e.preventDefault();
         e.stopPropagation();
@@ -109,6 +120,21 @@ export default function MovePicker({
             );
           })}
         </div>
+        <div className="px-4 py-2 border-t border-line">
+          <input
+            ref={newFolderInputRef}
+            value={newFolderName}
+            onChange={(e) => setNewFolderName(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === "Enter" && newFolderName.trim()) {
+                e.preventDefault();
+                onMoveToNewFolder(newFolderName.trim());
+              }
+            }}
+            placeholder="New folder…"
+            className="w-full px-2 py-1.5 text-[12px] bg-line/30 rounded text-ink placeholder:text-stone outline-none focus:ring-1 focus:ring-coral"
+          />
+        </div>
         <div className="flex items-center justify-between px-4 py-2 border-t border-line text-[10px] text-stone">
           <span>
             <kbd className="px-1.5 py-0.5 bg-line rounded text-[9px]">
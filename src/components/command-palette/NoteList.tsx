@@ -183,6 +183,8 @@ export default function NoteList({
             </div>
             <span className="text-[10px] text-stone font-mono">
               {formatRelativeDate(result.created)}
+              {result.open_tasks + result.done_tasks > 0 &&
+                ` · ${result.done_tasks}/${result.open_tasks + result.done_tasks} tasks done`}
             </span>
             {shouldShowSnippet && !result.locked && (
               <p className="text-[12px] text-stone leading-relaxed mt-0.5">
@@ -17,14 +17,14 @@ export function useTheme() {
 
     invoke<StikSettings>("get_settings")
       .then((s) => {
-        activeThemeId = s.active_theme || s.theme_mode || "";
+        activeThemeId = s.active_theme || "";
         customThemes = s.custom_themes ?? [];
         apply();
       })
       .catch(() => apply());
 
     const unlistenSettings = listen<StikSettings>("settings-changed", (e) => {
-      activeThemeId = e.payload.active_theme || e.payload.theme_mode || "";
+      activeThemeId = e.payload.active_theme || "";
       customThemes = e.payload.custom_themes ?? [];
       apply();
     });
@@ -1,5 +1,6 @@
 import type { ThemeColors, CustomThemeDefinition } from "@/types";
 import { rgbToHex } from "@/utils/color";
+import builtinThemes from "./builtin.json";
 
 export interface BuiltinTheme {
   id: string;
@@ -8,164 +9,14 @@ export interface BuiltinTheme {
   colors: ThemeColors;
 }
 
-const light: BuiltinTheme = {
-  id: "light",
-  name: "Light",
-  isDark: false,
-  colors: {
-    bg: "255 252 249",
-    surface: "255 255 255",
-    ink: "26 26 26",
-    stone: "122 122 122",
-    line: "240 238 235",
-    accent: "232 112 95",
-    accent_light: "255 241 238",
-    accent_dark: "214 96 79",
-  },
-};
-
-const dark: BuiltinTheme = {
-  id: "dark",
-  name: "Dark",
-  isDark: true,
-  colors: {
-    bg: "28 25 23",
-    surface: "41 37 36",
-    ink: "245 240 235",
-    stone: "168 162 158",
-    line: "68 64 60",
-    accent: "232 112 95",
-    accent_light: "61 37 32",
-    accent_dark: "214 96 79",
-  },
-};
-
-const sepia: BuiltinTheme = {
-  id: "sepia",
-  name: "Sepia",
-  isDark: false,
-  colors: {
-    bg: "245 235 220",
-    surface: "250 242 230",
-    ink: "62 48 36",
-    stone: "140 120 100",
-    line: "225 210 190",
-    accent: "180 100 60",
-    accent_light: "245 225 210",
-    accent_dark: "160 80 45",
-  },
-};
-
-const nord: BuiltinTheme = {
-  id: "nord",
-  name: "Nord",
-  isDark: true,
-  colors: {
-    bg: "46 52 64",
-    surface: "59 66 82",
-    ink: "236 239 244",
-    stone: "165 175 191",
-    line: "67 76 94",
-    accent: "136 192 208",
-    accent_light: "46 62 74",
-    accent_dark: "94 162 182",
-  },
-};
-
-const rosePine: BuiltinTheme = {
-  id: "rose-pine",
-  name: "Rose Pine",
-  isDark: true,
-  colors: {
-    bg: "25 23 36",
-    surface: "30 28 44",
-    ink: "224 222 244",
-    stone: "144 140 170",
-    line: "38 35 58",
-    accent: "235 111 146",
-    accent_light: "50 30 40",
-    accent_dark: "210 90 125",
-  },
-};
-
-const solarizedLight: BuiltinTheme = {
-  id: "solarized-light",
-  name: "Solarized Light",
-  isDark: false,
-  colors: {
-    bg: "253 246 227",
-    surface: "238 232 213",
-    ink: "0 43 54",
-    stone: "88 110 117",
-    line: "220 213 194",
-    accent: "38 139 210",
-    accent_light: "230 240 250",
-    accent_dark: "30 115 180",
-  },
-};
-
-const solarizedDark: BuiltinTheme = {
-  id: "solarized-dark",
-  name: "Solarized Dark",
-  isDark: true,
-  colors: {
-    bg: "0 43 54",
-    surface: "7 54 66",
-    ink: "253 246 227",
-    stone: "147 161 161",
-    line: "14 65 78",
-    accent: "38 139 210",
-    accent_light: "10 55 70",
-    accent_dark: "30 115 180",
-  },
-};
-
-const dracula: BuiltinTheme = {
-  id: "dracula",
-  name: "Dracula",
-  isDark: true,
-  colors: {
-    bg: "40 42 54",
-    surface: "50 52 68",
-    ink: "248 248 242",
-    stone: "148 150 164",
-    line: "62 64 82",
-    accent: "189 147 249",
-    accent_light: "55 45 75",
-    accent_dark: "160 120 220",
-  },
-};
-
-const tokyoNight: BuiltinTheme = {
-  id: "tokyo-night",
-  name: "Tokyo Night",
-  isDark: true,
-  colors: {
-    bg: "26 27 38",
-    surface: "36 40 59",
-    ink: "192 202 245",
-    stone: "130 140 170",
-    line: "41 46 66",
-    accent: "125 207 255",
-    accent_light: "30 50 65",
-    accent_dark: "100 180 230",
-  },
-};
-
-export const BUILTIN_THEMES: BuiltinTheme[] = [
-  light,
-  dark,
-  sepia,
-  nord,
-  rosePine,
-  solarizedLight,
-  solarizedDark,
-  dracula,
-  tokyoNight,
-];
+/** Palettes live in `builtin.json` so the Rust side can read them too. */
+export const BUILTIN_THEMES: BuiltinTheme[] = builtinThemes;
 
 export const BUILTIN_THEME_MAP = new Map(BUILTIN_THEMES.map((t) => [t.id, t]));
 
+const light = BUILTIN_THEME_MAP.get("light")!;
+const dark = BUILTIN_THEME_MAP.get("dark")!;
+
 function computeEditorTokens(colors: ThemeColors, isDark: boolean) {
   const accentHex = rgbToHex(colors.accent);
   const stoneHex = rgbToHex(colors.stone);
@@ -6,7 +6,18 @@ export interface StickedNote {
   size: [number, number] | null;
   created_at: string;
   updated_at: string;
+  /** Name of the template the sticky was created from. */
+  template_origin?: string | null;
   originalPath?: string;
+  /** Viewing windows only: the note is edit-locked. */
+  read_only?: boolean;
+}
+
+export interface StickedCloseSummary {
+  saved: string[];
+  empty: string[];
+  /** `[id, error]` for notes left open because saving failed. */
+  failed: [string, string][];
 }
 
 export interface ShortcutMapping {
@@ -29,6 +40,19 @@ export interface CustomTemplate {
   body: string;
 }
 
+export interface TemplateInfo {
+  id: string;
+  name: string;
+  preview: string;
+  shortcut: string;
+  builtin: boolean;
+}
+
+export interface AppliedTemplate {
+  body: string;
+  cursor: number;
+}
+
 export interface CustomFontEntry {
   name: string; // font-family CSS name (derived from filename)
   path: string; // absolute path to the font file on disk
@@ -83,7 +107,6 @@ export interface StikSettings {
   git_sharing: GitSharingSettings;
   ai_features_enabled: boolean;
   vim_mode_enabled: boolean;
-  theme_mode: string;
   notes_directory: string;
   hide_dock_icon: boolean;
   folder_colors: Record<string, string>;
@@ -96,7 +119,7 @@ export interface StikSettings {
   auto_update_enabled: boolean;
   text_direction: string;
   hide_tray_icon: boolean;
-  capture_window_size: [number, number] | null;
+  window_sizes?: Record<string, [number, number]>;
   active_theme: string;
   custom_themes: CustomThemeDefinition[];
   font_family?: string | null; // null = system default
@@ -106,6 +129,68 @@ export interface StikSettings {
   note_lock: NoteLockSettings;
   use_directory_as_root?: boolean;
   dictation?: DictationSettings;
+  onboarding?: OnboardingSettings;
+  capture_blur_behavior?: CaptureBlurBehavior;
+  capture_context?: boolean;
+  filename_template?: string;
+  daily_note_folder?: string;
+  sync_summary_folder?: string;
+  widget_feed_enabled?: boolean;
+  widget_excluded_folders?: string[];
+  streak_grace_days_per_month?: number;
+  backup?: BackupSettings;
+  respect_focus_modes?: boolean;
+  /** Set with `set_log_level`; empty = the build's default. */
+  log_level?: string;
+  /** Sticked/viewing window resize limits, logical pixels. */
+  sticked_min_size?: [number, number];
+  /** null = no maximum. */
+  sticked_max_size?: [number, number] | null;
+}
+
+/** What a blur does with a non-empty capture window. */
+export type CaptureBlurBehavior = "discard" | "save" | "keep_draft";
+
+export interface NoteMetadata {
+  path: string;
+  created: string; // YYYYMMDD-HHMMSS
+  modified: string; // YYYYMMDD-HHMMSS
+  size_bytes: number;
+  word_count: number;
+  tags: string[];
+  sticked: boolean;
+}
+
+export type FolderChange =
+  | { kind: "created"; name: string }
+  | { kind: "removed"; name: string }
+  | { kind: "renamed"; from: string; to: string };
+
+export interface OpenNoteWindow {
+  label: string;
+  kind: "sticked" | "viewing";
+  title: string;
+  folder: string;
+  focused: boolean;
+}
+
+export interface CaptureDraft {
+  content: string;
+  folder: string;
+  saved_at: string;
+}
+
+export interface OnboardingSettings {
+  completed: boolean;
+  completed_at: string | null;
+  version: string | null;
+}
+
+export interface OnboardingChoices {
+  default_folder: string;
+  starter_folders: string[];
+  analytics_enabled: boolean;
+  capture_shortcut: string;
 }
 
 export interface DictationSettings {
@@ -135,6 +220,13 @@ export interface DictationDownloadProgress {
   bytes_total: number;
 }
 
+export interface RecentNote {
+  path: string;
+  title: string;
+  preview: string;
+  created: string;
+}
+
 export interface NoteInfo {
   path: string;
   filename: string;
@@ -142,6 +234,66 @@ export interface NoteInfo {
   content: string;
   created: string;
   locked?: boolean;
+  word_count?: number;
+  reading_minutes?: number;
+  language?: string;
+  truncated_in_index?: boolean;
+  read_only?: boolean;
+  pinned?: boolean;
+  /** Unchecked tasks outside code fences; 0 when there are none. */
+  open_tasks: number;
+  done_tasks: number;
+}
+
+/** Payload of the `note-saved` and `note-updated` events. */
+export interface NoteSaved {
+  path: string;
+  folder: string;
+  filename: string;
+}
+
+/** Payload of the `note-deleted` event; `notes-deleted` sends a list. */
+export interface NoteDeleted {
+  path: string;
+  folder: string;
+}
+
+/** One path's outcome from `delete_notes`. */
+export interface NoteDeletion {
+  path: string;
+  trashed_path: string | null;
+  error: string | null;
+}
+
+/** Payload of the `note-moved` event. */
+export interface NoteMoved {
+  old_path: string;
+  old_folder: string;
+  path: string;
+  folder: string;
+  filename: string;
+}
+
+/** One folder's totals from `get_task_stats`. */
+export interface FolderTaskStats {
+  folder: string;
+  open_tasks: number;
+  done_tasks: number;
+  notes_with_tasks: number;
+}
+
+/** Result of `move_note_to_new_folder`. */
+export interface MovedToNewFolder {
+  note: NoteInfo;
+  /** False when the folder already existed. */
+  folder_created: boolean;
+}
+
+/** One entry of the `notes-expired` event. */
+export interface ExpiredNote {
+  path: string;
+  folder: string;
+  trashed_path: string;
 }
 
 export interface SearchResult {
@@ -152,6 +304,40 @@ export interface SearchResult {
   snippet: string;
   created: string;
   locked?: boolean;
+  word_count?: number;
+  reading_minutes?: number;
+  language?: string;
+  truncated_in_index?: boolean;
+  read_only?: boolean;
+  pinned?: boolean;
+  /** Unchecked tasks outside code fences; 0 when there are none. */
+  open_tasks: number;
+  done_tasks: number;
+}
+
+/** A `name 2.md` copy a sync client left next to `name.md`. */
+export interface CloudConflictDuplicate {
+  original: string;
+  duplicate: string;
+  folder: string;
+  identical: boolean;
+}
+
+export interface FacetCount {
+  name: string;
+  count: number;
+}
+
+/** Match counts for a query, ignoring the folder filter. */
+export interface SearchFacets {
+  total: number;
+  folders: FacetCount[];
+  tags: FacetCount[];
+}
+
+export interface FacetedSearch {
+  results: SearchResult[];
+  facets: SearchFacets | null;
 }
 
 export interface SemanticResult {
@@ -164,14 +350,60 @@ export interface SemanticResult {
   similarity: number;
 }
 
+export interface PaletteAction {
+  id: string;
+  title: string;
+  subtitle: string | null;
+  category: string;
+  shortcut: string | null;
+  enabled: boolean;
+}
+
 export interface FolderStats {
   name: string;
   note_count: number;
 }
 
+export interface FolderBadge extends FolderStats {
+  sticked_count: number;
+  /** Notes not yet committed to the git-shared repository. */
+  unsynced_count: number;
+}
+
 export interface CaptureStreakStatus {
   days: number;
   label: string;
+  /** Carried over the days since the last capture by grace days. */
+  on_grace: boolean;
+}
+
+export interface CaptureStats {
+  capture_streak_days: number;
+  longest_streak_days: number;
+  longest_streak_ended_on: string | null;
+  total_capture_days: number;
+  last_computed_at: string;
+  /** Grace days spent per month, keyed `YYYY-MM`. */
+  grace_days_used: Record<string, number>;
+  streak_grace_days: string[];
+  streak_on_grace: boolean;
+}
+
+export interface CaptureHeatmap {
+  days: { date: string; count: number }[];
+  weekday_totals: number[];
+  total_notes: number;
+  current_streak_days: number;
+  longest_streak_days: number;
+}
+
+export interface ActivityBreakdown {
+  hour_totals: number[];
+  weekday_totals: number[];
+  unknown_time: number;
+  total_notes: number;
+  most_active_hour: number | null;
+  most_active_weekday: number | null;
 }
 
 export interface OnThisDayStatus {
@@ -185,6 +417,7 @@ export interface OnThisDayStatus {
 export interface ClipboardPayload {
   plain_text: string;
   html: string;
+  stripped_text: string;
 }
 
 export interface AppleNoteEntry {
@@ -196,6 +429,28 @@ export interface AppleNoteEntry {
   account_name: string;
 }
 
+export interface AppleListProgress {
+  loaded: number;
+  total: number;
+}
+
+export interface AppleImportProgress {
+  done: number;
+  total: number;
+}
+
+export interface AppleImportFailure {
+  note_id: number;
+  reason: string;
+}
+
+export interface AppleImportSummary {
+  imported: number;
+  total: number;
+  cancelled: boolean;
+  failed: AppleImportFailure[];
+}
+
 export interface GitSyncStatus {
   enabled: boolean;
   linked_folder: string | null;
@@ -203,8 +458,131 @@ export interface GitSyncStatus {
   branch: string;
   repository_layout: "folder_root" | "stik_root";
   repo_initialized: boolean;
+  has_credentials: boolean;
   pending_changes: boolean;
   syncing: boolean;
   last_sync_at: string | null;
   last_error: string | null;
 }
+
+export interface GitLocalChange {
+  path: string;
+  change: "added" | "modified" | "deleted" | "renamed" | "conflicted";
+  previous_path: string | null;
+}
+
+export interface GitIncomingCommit {
+  hash: string;
+  author: string;
+  date: string;
+  message: string;
+  files: string[];
+}
+
+/** Result of `git_preview_sync`: what "Sync now" would do. */
+export interface GitSyncPreview {
+  local_changes: GitLocalChange[];
+  incoming_commits: GitIncomingCommit[];
+  predicted_conflicts: string[];
+  fetch_failed: boolean;
+  fetch_error: string | null;
+}
+
+/** Result of `generate_sync_summary`. */
+export interface SyncSummary {
+  markdown: string;
+  commits: number;
+  /** null when only previewed (`save: false`). */
+  saved: NoteSaved | null;
+}
+
+export interface WidgetNote {
+  title: string;
+  folder: string;
+  created: string;
+  modified: string | null;
+  preview: string;
+  link: string;
+}
+
+/** Result of `get_widget_feed`; the same shape as `~/.stik/widget/feed.json`. */
+export interface WidgetFeed {
+  generated_at: string;
+  capture_streak_days: number;
+  notes: WidgetNote[];
+}
+
+/** Payload of `notes-directory-unavailable` and result of `recheck_notes_directory`. */
+export interface NotesDirectoryStatus {
+  available: boolean;
+  path: string;
+  error: string | null;
+}
+
+/** Result of `get_config_location`; `dir` is the unreachable portable folder when `kind` is "unavailable". */
+export interface ConfigLocation {
+  kind: "home" | "portable" | "unavailable";
+  dir: string;
+}
+
+/** Result of `save_note_image_snapshot`. */
+export interface SavedImageSnapshot {
+  path: string;
+  width: number;
+  height: number;
+}
+
+export interface BackupSettings {
+  enabled: boolean;
+  /** Absolute folder outside the notes directory. */
+  destination: string;
+  frequency_days: number;
+  /** 0 keeps every archive. */
+  retention_count: number;
+  excluded_folders: string[];
+}
+
+/** Last backup outcome, from `get_backup_status` and `backup-finished`. */
+export interface BackupStatus {
+  last_attempt_at: string | null;
+  last_success_at: string | null;
+  last_archive: string | null;
+  last_files: number;
+  last_archive_bytes: number;
+  last_error: string | null;
+}
+
+export interface BackupArchive {
+  path: string;
+  created_at: string;
+  size_bytes: number;
+}
+
+/** Payload of the `backup-progress` event. */
+export interface BackupProgress {
+  done: number;
+  total: number;
+  path: string;
+}
+
+export interface DarwinKitStatus {
+  ready: boolean;
+  version: string | null;
+  capabilities: string[];
+}
+
+export interface Diagnostics {
+  app_version: string;
+  os_version: string;
+  notes_dir: string | null;
+  notes_dir_writable: boolean;
+  note_count: number;
+  folder_count: number;
+  index_age_seconds: number | null;
+  embedded_notes: number;
+  darwinkit: DarwinKitStatus;
+  git_sync: GitSyncStatus | null;
+  settings_path: string | null;
+  settings_version: number | null;
+  recent_log: string[];
+}
@@ -0,0 +1,67 @@
+const SVG_NS = "http://www.w3.org/2000/svg";
+const XHTML_NS = "http://www.w3.org/1999/xhtml";
+
+function collectDocumentCss(): string {
+  return Array.from(document.styleSheets)
+    .map((sheet) => {
+      try {
+        return Array.from(sheet.cssRules)
+          .map((rule) => rule.cssText)
+          .join("\n");
+      } catch {
+        // Cross-origin sheets can't be read; skip them.
+        return "";
+      }
+    })
+    .join("\n");
+}
+
+/**
+ * Rasterize an element through an SVG foreignObject and return the PNG as
+ * base64 (no `data:` prefix). Used where the backend has no native webview
+ * capture (non-macOS) — the result goes to `copy_note_image_to_clipboard`.
+ */
+export async function renderElementToPngBase64(
+  element: HTMLElement,
+): Promise<string> {
+  const { width, height } = element.getBoundingClientRect();
+  const scale = window.devicePixelRatio || 1;
+
+  // Theme tokens live on <html> as inline CSS variables; carry them over.
+  const root = document.createElementNS(XHTML_NS, "div") as HTMLElement;
+  root.className = document.documentElement.className;
+  root.setAttribute(
+    "style",
+    document.documentElement.getAttribute("style") ?? "",
+  );
+  const style = document.createElementNS(XHTML_NS, "style");
+  style.textContent = collectDocumentCss();
+  root.appendChild(style);
+  root.appendChild(element.cloneNode(true));
+
+  const svg = document.createElementNS(SVG_NS, "svg");
+  svg.setAttribute("width", String(width));
+  svg.setAttribute("height", String(height));
+  const foreignObject = document.createElementNS(SVG_NS, "foreignObject");
+  foreignObject.setAttribute("width", "100%");
+  foreignObject.setAttribute("height", "100%");
+  foreignObject.appendChild(root);
+  svg.appendChild(foreignObject);
+
+  const markup = new XMLSerializer().serializeToString(svg);
+  const image = new Image();
+  image.src = `data:image/svg+xml;charset=utf-8,${encodeURIComponent(markup)}`;
+  await image.decode();
+
+  const canvas = document.createElement("canvas");
+  canvas.width = Math.ceil(width * scale);
+  canvas.height = Math.ceil(height * scale);
+  const context = canvas.getContext("2d");
+  if (!context) {
+    throw new Error("Canvas 2D context unavailable");
+  }
+  context.scale(scale, scale);
+  context.drawImage(image, 0, 0, width, height);
+
+  return canvas.toDataURL("image/png").split(",")[1] ?? "";
+}
@@ -1,5 +1,5 @@
 import { describe, expect, it } from "vitest";
-import { extractDroppedImagePath } from "./droppedImagePath";
+import { extractDroppedImagePath, extractDroppedTextPath } from "./droppedImagePath";
 
 describe("extractDroppedImagePath", () => {
   it("extracts image path from file URL", () => {
@@ -25,3 +25,13 @@ describe("extractDroppedImagePath", () => {
     expect(extractDroppedImagePath("https://example.com/image.png")).toBeNull();
   });
 });
+
+describe("extractDroppedTextPath", () => {
+  it("accepts plain-text files and rejects images", () => {
+    expect(extractDroppedTextPath("file:///Users/massi/Desktop/data%201.CSV")).toBe(
+      "/Users/massi/Desktop/data 1.CSV"
+    );
+    expect(extractDroppedTextPath("/var/log/build.log")).toBe("/var/log/build.log");
+    expect(extractDroppedTextPath("/Users/massi/Desktop/photo.png")).toBeNull();
+  });
+});
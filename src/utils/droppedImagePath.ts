@@ -1,14 +1,12 @@
 const IMAGE_PATH_EXTENSIONS = /\.(png|jpe?g|gif|webp|svg|bmp|avif)$/i;
-
-function hasImageExtension(path: string): boolean {
-  return IMAGE_PATH_EXTENSIONS.test(path);
-}
+/** Plain-text files `import_text_file` turns into notes. */
+const TEXT_PATH_EXTENSIONS = /\.(txt|text|csv|tsv|log)$/i;
 
 /**
- * Extract a local absolute image file path from a drop payload string.
- * Supports `file://` URLs and direct absolute POSIX paths.
+ * Extract a local absolute file path matching `extensions` from a drop
+ * payload string. Supports `file://` URLs and direct absolute POSIX paths.
  */
-export function extractDroppedImagePath(value: string): string | null {
+function extractDroppedPath(value: string, extensions: RegExp): string | null {
   const trimmed = value.trim();
   if (!trimmed) return null;
 
@@ -23,7 +21,7 @@ export function extractDroppedImagePath(value: string): string | null {
         pathname = pathname.slice(1);
       }
 
-      return hasImageExtension(pathname) ? pathname : null;
+      return extensions.test(pathname) ? pathname : null;
     } catch {
       return null;
     }
@@ -33,5 +31,15 @@ export function extractDroppedImagePath(value: string): string | null {
     return null;
   }
 
-  return hasImageExtension(trimmed) ? trimmed : null;
+  return extensions.test(trimmed) ? trimmed : null;
+}
+
+/** Local absolute image file path from a drop payload string. */
+export function extractDroppedImagePath(value: string): string | null {
+  return extractDroppedPath(value, IMAGE_PATH_EXTENSIONS);
+}
+
+/** Local absolute plain-text file path from a drop payload string. */
+export function extractDroppedTextPath(value: string): string | null {
+  return extractDroppedPath(value, TEXT_PATH_EXTENSIONS);
 }
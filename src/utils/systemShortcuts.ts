@@ -2,11 +2,14 @@ export const SYSTEM_SHORTCUT_ACTIONS = [
   "search",
   "manager",
   "settings",
+  "stats",
   "last_note",
   "zen_mode",
   "dictation",
   "voice_note",
   "clip_capture",
+  "capture_selection",
+  "daily_note",
 ] as const;
 export type SystemAction = (typeof SYSTEM_SHORTCUT_ACTIONS)[number];
 
@@ -14,22 +17,28 @@ export const SYSTEM_SHORTCUT_DEFAULTS: Record<SystemAction, string> = {
   search: "Cmd+Shift+P",
   manager: "Cmd+Shift+M",
   settings: "Cmd+Shift+Comma",
+  stats: "Cmd+Shift+Y",
   last_note: "Cmd+Shift+L",
   zen_mode: "Cmd+Period",
   dictation: "Cmd+Shift+D",
   voice_note: "Cmd+Shift+V",
   clip_capture: "Cmd+Shift+C",
+  capture_selection: "Cmd+Ctrl+C",
+  daily_note: "Cmd+Shift+J",
 };
 
 export const SYSTEM_SHORTCUT_LABELS: Record<SystemAction, string> = {
   search: "Command Palette",
   manager: "Command Palette (alt)",
   settings: "Settings",
+  stats: "Stats",
   last_note: "Last note",
   zen_mode: "Zen mode",
   dictation: "Toggle dictation (in note)",
   voice_note: "New voice note",
   clip_capture: "Capture clipboard",
+  capture_selection: "Capture selection (copy)",
+  daily_note: "Today's daily note",
 };
 
 /** Get all system shortcut values for use as reserved list */
@@ -38,3 +47,23 @@ export function getSystemShortcutValues(
 ): string[] {
   return Object.values(systemShortcuts);
 }
+
+/** Whether a keydown matches an in-app shortcut such as "Cmd+Period". */
+export function matchesShortcut(e: KeyboardEvent, shortcut: string): boolean {
+  const parts = shortcut.split("+");
+  const key = parts[parts.length - 1];
+  const needsMeta = parts.some(
+    (p) => p === "Cmd" || p === "Command" || p === "Meta",
+  );
+  const needsShift = parts.some((p) => p === "Shift");
+  const needsAlt = parts.some((p) => p === "Alt" || p === "Option");
+  const needsCtrl = parts.some((p) => p === "Ctrl" || p === "Control");
+
+  if (needsMeta !== e.metaKey) return false;
+  if (needsShift !== e.shiftKey) return false;
+  if (needsAlt !== e.altKey) return false;
+  if (needsCtrl !== e.ctrlKey) return false;
+
+  const eventKey = e.key === "." ? "Period" : e.key === "," ? "Comma" : e.key;
+  return eventKey.toLowerCase() === key.toLowerCase();
+}